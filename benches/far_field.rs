@@ -0,0 +1,68 @@
+//! Benchmarks a full attraction pass (every entity's potential from all
+//! others) by brute force and with an `AttractionTree` at θ = 0.5.
+//!
+//! Entities are spread at constant density, so the tree pass should grow
+//! roughly as n log n from 1k to 20k entities while brute force grows as
+//! n² (it is skipped at 20k, where one pass takes seconds).
+//!
+//! ```bash
+//! cargo bench --bench far_field
+//! ```
+
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
+use rand::{rngs::StdRng, Rng, SeedableRng};
+use synthetic_consciousness::attraction::{attraction_potential, AttractionConfig};
+use synthetic_consciousness::barnes_hut::AttractionTree;
+use synthetic_consciousness::{Real, SimulationConfig};
+
+const ENTITY_COUNTS: [usize; 3] = [1_000, 5_000, 20_000];
+const BRUTE_FORCE_MAX: usize = 5_000;
+const THETA: Real = 0.5;
+
+/// `count` positions in a square holding one entity per unit area.
+fn positions(count: usize) -> Vec<Vec<Real>> {
+    let mut rng = StdRng::seed_from_u64(5);
+    let side = (count as Real).sqrt();
+    (0..count).map(|_| vec![rng.gen_range(0.0..side), rng.gen_range(0.0..side)]).collect()
+}
+
+fn brute_force_pass(positions: &[Vec<Real>], weights: &mut [Real], config: &AttractionConfig) -> Real {
+    let mut total = 0.0;
+    for i in 0..positions.len() {
+        // A zero weight leaves the entity itself out of its own sum
+        weights[i] = 0.0;
+        total += attraction_potential(&positions[i], positions, weights, config);
+        weights[i] = 1.0;
+    }
+    total
+}
+
+fn tree_pass(positions: &[Vec<Real>], weights: &[Real], config: &AttractionConfig) -> Real {
+    let tree = AttractionTree::build(positions, weights);
+    (0..positions.len()).map(|i| tree.potential(&positions[i], Some(i), config)).sum()
+}
+
+fn bench_far_field(c: &mut Criterion) {
+    let config = AttractionConfig {
+        theta: THETA,
+        ..SimulationConfig::default_2d().attraction
+    };
+    let mut group = c.benchmark_group("attraction_pass");
+    group.sample_size(10);
+    for count in ENTITY_COUNTS {
+        let positions = positions(count);
+        let mut weights = vec![1.0; count];
+        if count <= BRUTE_FORCE_MAX {
+            group.bench_with_input(BenchmarkId::new("brute_force", count), &count, |b, _| {
+                b.iter(|| brute_force_pass(black_box(&positions), &mut weights, &config))
+            });
+        }
+        group.bench_with_input(BenchmarkId::new("barnes_hut", count), &count, |b, _| {
+            b.iter(|| tree_pass(black_box(&positions), &weights, &config))
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, bench_far_field);
+criterion_main!(benches);
@@ -0,0 +1,60 @@
+//! Benchmarks `MemoryGraph::cluster_event` on graphs of 100, 1000 and
+//! 10k nodes.
+//!
+//! Event vectors have 2 components (a 2D stimulus) or 32. Each iteration
+//! clusters one new event against every existing member, so this is
+//! dominated by reading stored events.
+//!
+//! ```bash
+//! cargo bench --bench memory
+//! ```
+
+use criterion::{black_box, criterion_group, criterion_main, BatchSize, BenchmarkId, Criterion};
+use rand::{rngs::StdRng, Rng, SeedableRng};
+use synthetic_consciousness::entities::CLUSTER_TAU;
+use synthetic_consciousness::memory::MemoryGraph;
+use synthetic_consciousness::Real;
+
+const NODE_COUNTS: [usize; 3] = [100, 1_000, 10_000];
+const EVENT_DIMS: [usize; 2] = [2, 32];
+
+fn random_event(rng: &mut StdRng, dim: usize) -> Vec<Real> {
+    (0..dim).map(|_| rng.gen_range(-1.0..1.0)).collect()
+}
+
+/// Graph of `nodes` clustered random events.
+fn clustered_graph(rng: &mut StdRng, nodes: usize, dim: usize) -> MemoryGraph {
+    let mut graph = MemoryGraph::new();
+    for t in 0..nodes {
+        graph.add_clustered_event(&random_event(rng, dim), t as u64, CLUSTER_TAU);
+    }
+    graph
+}
+
+fn bench_cluster_event(c: &mut Criterion) {
+    let mut group = c.benchmark_group("cluster_event");
+    group.sample_size(20);
+    for dim in EVENT_DIMS {
+        for nodes in NODE_COUNTS {
+            let mut rng = StdRng::seed_from_u64(7);
+            let graph = clustered_graph(&mut rng, nodes, dim);
+            let probe = random_event(&mut rng, dim);
+            let id = format!("{}d/{}", dim, nodes);
+            group.bench_function(BenchmarkId::from_parameter(id), |b| {
+                b.iter_batched_ref(
+                    || {
+                        let mut graph = graph.clone();
+                        let idx = graph.add_event(&probe, nodes as u64);
+                        (graph, idx)
+                    },
+                    |(graph, idx)| graph.cluster_event(black_box(&probe), *idx, CLUSTER_TAU),
+                    BatchSize::LargeInput,
+                )
+            });
+        }
+    }
+    group.finish();
+}
+
+criterion_group!(benches, bench_cluster_event);
+criterion_main!(benches);
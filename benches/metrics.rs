@@ -0,0 +1,48 @@
+//! Benchmarks `Metrics::compute` at 100 and 1000 entities, and
+//! `Metrics::compute_with` serially and in parallel at 5000 entities.
+//!
+//! ```bash
+//! cargo bench --bench metrics
+//! ```
+
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use synthetic_consciousness::{Metrics, Simulation, SimulationConfig};
+
+const COMPUTE_ENTITY_COUNTS: [u32; 2] = [100, 1000];
+const ENTITIES: u32 = 5000;
+const WARMUP_STEPS: usize = 3;
+const SEED: u64 = 42;
+
+fn warmed_up(entities: u32) -> Simulation {
+    let mut config = SimulationConfig::default_2d();
+    config.simulation.num_entities = entities;
+    config.simulation.seed = SEED;
+    Simulation::bench_prepare(config, WARMUP_STEPS).unwrap()
+}
+
+fn bench_compute(c: &mut Criterion) {
+    let mut group = c.benchmark_group("metrics_compute");
+    for entities in COMPUTE_ENTITY_COUNTS {
+        let sim = warmed_up(entities);
+        group.bench_with_input(BenchmarkId::from_parameter(entities), &entities, |b, _| {
+            b.iter(|| Metrics::compute(&sim.entities, sim.timestamp))
+        });
+    }
+    group.finish();
+}
+
+fn bench_metrics(c: &mut Criterion) {
+    let sim = warmed_up(ENTITIES);
+    let entities = sim.entities.all_entities();
+
+    let mut group = c.benchmark_group("metrics");
+    for (label, parallel) in [("serial", false), ("parallel", true)] {
+        group.bench_with_input(BenchmarkId::new(label, ENTITIES), &parallel, |b, &parallel| {
+            b.iter(|| Metrics::compute_with(&entities, sim.timestamp, parallel))
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, bench_compute, bench_metrics);
+criterion_main!(benches);
@@ -0,0 +1,42 @@
+//! Benchmarks `Simulation::step` at 10, 100 and 1000 entities.
+//!
+//! Each size runs with memory activations recorded every step (the former
+//! behaviour) and with `attention_capture_interval = 0`, which skips
+//! building them.
+//!
+//! ```bash
+//! cargo bench --bench step
+//! ```
+
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use synthetic_consciousness::{Simulation, SimulationConfig};
+
+const ENTITY_COUNTS: [u32; 3] = [10, 100, 1000];
+const WARMUP_STEPS: usize = 20;
+const SEED: u64 = 42;
+
+/// Simulation with `entities` entities, stepped past its start-up growth.
+fn warmed_up(entities: u32, attention_capture_interval: u32) -> Simulation {
+    let mut config = SimulationConfig::default_2d();
+    config.simulation.num_entities = entities;
+    config.simulation.seed = SEED;
+    config.simulation.attention_capture_interval = attention_capture_interval;
+    Simulation::bench_prepare(config, WARMUP_STEPS).unwrap()
+}
+
+fn bench_step(c: &mut Criterion) {
+    let mut group = c.benchmark_group("step");
+    group.sample_size(10);
+    for entities in ENTITY_COUNTS {
+        for (label, interval) in [("attentions_every_step", 1), ("attentions_skipped", 0)] {
+            let mut sim = warmed_up(entities, interval);
+            group.bench_with_input(BenchmarkId::new(label, entities), &entities, |b, _| {
+                b.iter(|| sim.step())
+            });
+        }
+    }
+    group.finish();
+}
+
+criterion_group!(benches, bench_step);
+criterion_main!(benches);
@@ -0,0 +1,47 @@
+//! Build script: generates the C header for the `ffi` feature and the
+//! gRPC stubs from `proto/simulation.proto` for the `grpc` feature.
+//!
+//! ## Author
+//! Ayomide I. Daniels (Morningstar)
+
+fn main() {
+    println!("cargo:rerun-if-changed=build.rs");
+
+    #[cfg(feature = "ffi")]
+    generate_c_header();
+
+    #[cfg(feature = "grpc")]
+    compile_protos();
+}
+
+/// Generate the tonic/prost code for the gRPC service.
+///
+/// Uses `$PROTOC` if set, otherwise the vendored protoc binary.
+#[cfg(feature = "grpc")]
+fn compile_protos() {
+    println!("cargo:rerun-if-changed=proto/simulation.proto");
+
+    if std::env::var_os("PROTOC").is_none() {
+        let protoc = protoc_bin_vendored::protoc_bin_path().expect("vendored protoc is available for this platform");
+        std::env::set_var("PROTOC", protoc);
+    }
+    tonic_build::compile_protos("proto/simulation.proto").expect("proto/simulation.proto compiles");
+}
+
+/// Write `include/synthetic_consciousness.h` from the FFI module.
+#[cfg(feature = "ffi")]
+fn generate_c_header() {
+    println!("cargo:rerun-if-changed=src/bindings/ffi.rs");
+    println!("cargo:rerun-if-changed=cbindgen.toml");
+
+    let crate_dir = std::env::var("CARGO_MANIFEST_DIR").expect("CARGO_MANIFEST_DIR is set by cargo");
+    std::fs::create_dir_all(format!("{}/include", crate_dir)).expect("include directory is writable");
+    let config = cbindgen::Config::from_file(format!("{}/cbindgen.toml", crate_dir))
+        .expect("cbindgen.toml is valid");
+    cbindgen::Builder::new()
+        .with_src(format!("{}/src/bindings/ffi.rs", crate_dir))
+        .with_config(config)
+        .generate()
+        .expect("C header generation failed")
+        .write_to_file(format!("{}/include/synthetic_consciousness.h", crate_dir));
+}
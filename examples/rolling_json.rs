@@ -0,0 +1,114 @@
+//! Step observers: a rolling JSON file of recent metrics, and an early stop
+//! when the run diverges.
+//!
+//! `RollingJson` rewrites a small JSON file after every step with the
+//! metrics of the last `window` steps, so a dashboard (or `watch cat`) can
+//! follow a run without parsing the full history. `StopOnDivergence` ends
+//! the run once any metric stops being finite.
+//!
+//! ```bash
+//! cargo run --example rolling_json -- recent_metrics.json
+//! ```
+
+use serde::Serialize;
+use std::collections::VecDeque;
+use synthetic_consciousness::plugin::{SimulationObserver, StepControl};
+use synthetic_consciousness::{Metrics, Simulation, SimulationConfig};
+
+/// Keeps the metrics of the last `window` steps in a JSON file.
+struct RollingJson {
+    path: String,
+    window: usize,
+    recent: VecDeque<Metrics>,
+}
+
+/// Contents of the rolling file.
+#[derive(Serialize)]
+struct Snapshot<'a> {
+    step: u64,
+    total_steps: u32,
+    entities: usize,
+    recent: &'a VecDeque<Metrics>,
+}
+
+impl RollingJson {
+    fn new(path: &str, window: usize) -> Self {
+        RollingJson {
+            path: path.to_string(),
+            window: window.max(1),
+            recent: VecDeque::with_capacity(window),
+        }
+    }
+
+    /// Write the snapshot beside the file and rename it over, so readers
+    /// never see a half-written file.
+    fn write(&self, sim: &Simulation) -> std::io::Result<()> {
+        let snapshot = Snapshot {
+            step: sim.timestamp,
+            total_steps: sim.config.simulation.num_steps,
+            entities: sim.entities.iter().len(),
+            recent: &self.recent,
+        };
+        let partial = format!("{}.partial", self.path);
+        std::fs::write(&partial, serde_json::to_vec_pretty(&snapshot)?)?;
+        std::fs::rename(&partial, &self.path)
+    }
+}
+
+impl SimulationObserver for RollingJson {
+    fn on_step(&mut self, sim: &Simulation) -> StepControl {
+        if let Some(metrics) = sim.metrics_history.last() {
+            if self.recent.len() == self.window {
+                self.recent.pop_front();
+            }
+            self.recent.push_back(metrics.clone());
+        }
+        if let Err(e) = self.write(sim) {
+            eprintln!("Cannot write {}: {}", self.path, e);
+        }
+        StepControl::Continue
+    }
+}
+
+/// Stops the run at the first step with a NaN or infinite metric.
+struct StopOnDivergence;
+
+impl SimulationObserver for StopOnDivergence {
+    fn on_step(&mut self, sim: &Simulation) -> StepControl {
+        let Some(m) = sim.metrics_history.last() else {
+            return StepControl::Continue;
+        };
+        let values = [
+            m.attention_entropy,
+            m.memory_diversity,
+            m.velocity_stability,
+            m.identity_coherence,
+            m.cluster_stability,
+            m.affective_strength,
+            m.average_essence,
+        ];
+        if values.iter().all(|value| value.is_finite()) {
+            StepControl::Continue
+        } else {
+            eprintln!("Diverged at step {}; stopping", sim.timestamp);
+            StepControl::Stop
+        }
+    }
+}
+
+fn main() {
+    let path = std::env::args().nth(1).unwrap_or_else(|| "recent_metrics.json".to_string());
+    let mut config = SimulationConfig::default_2d();
+    config.simulation.num_steps = 500;
+
+    let mut sim = Simulation::new(config).expect("default configuration is valid");
+    sim.add_observer(Box::new(RollingJson::new(&path, 20)));
+    sim.add_observer(Box::new(StopOnDivergence));
+    sim.run();
+    sim.finalize_results();
+
+    println!(
+        "Ran {} of {} steps; the last 20 are in {}",
+        sim.timestamp, sim.config.simulation.num_steps, path
+    );
+}
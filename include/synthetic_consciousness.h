@@ -0,0 +1,152 @@
+#ifndef SYNTHETIC_CONSCIOUSNESS_H
+#define SYNTHETIC_CONSCIOUSNESS_H
+
+/* Generated by cbindgen from src/bindings/ffi.rs; do not edit. */
+
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+// Success.
+#define SC_OK 0
+
+// A required pointer argument was null.
+#define SC_ERR_NULL_POINTER -1
+
+// The configuration is invalid or could not be parsed.
+#define SC_ERR_INVALID_CONFIG -2
+
+// A file could not be read or written.
+#define SC_ERR_IO -3
+
+// A string argument was not valid UTF-8.
+#define SC_ERR_INVALID_STRING -4
+
+// The output buffer is too small.
+#define SC_ERR_BUFFER_TOO_SMALL -5
+
+// No data is available yet (e.g. metrics before the first step).
+#define SC_ERR_NO_DATA -6
+
+// A Rust panic was caught at the boundary.
+#define SC_ERR_PANIC -7
+
+// Opaque simulation configuration.
+typedef struct ScConfig ScConfig;
+
+// Opaque simulation.
+typedef struct ScSimulation ScSimulation;
+
+// Consciousness metrics snapshot.
+typedef struct ScMetrics {
+  uint64_t timestamp;
+  float attention_entropy;
+  float memory_diversity;
+  float velocity_stability;
+  float identity_coherence;
+  float cluster_stability;
+  float affective_strength;
+  float essence_trajectory;
+  float average_essence;
+  float phase_synchrony;
+  float mean_energy;
+  float role_stability;
+} ScMetrics;
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+// Message of the most recent error on this thread.
+//
+// The string is owned by the library and valid until the next failing
+// call on the same thread. It is empty if no error has occurred.
+const char *sc_last_error(void);
+
+// Default 2D configuration. Release with `sc_free_config`.
+struct ScConfig *sc_config_default_2d(void);
+
+// Default 3D configuration. Release with `sc_free_config`.
+struct ScConfig *sc_config_default_3d(void);
+
+// Load a configuration from a TOML file.
+//
+// # Safety
+// `path` must be a NUL-terminated string and `out` a valid pointer.
+int32_t sc_config_from_toml(const char *path, struct ScConfig **out);
+
+// Release a configuration. Null is ignored.
+//
+// # Safety
+// `config` must come from this library and not be used afterwards.
+void sc_free_config(struct ScConfig *config);
+
+// Create a simulation from a configuration (which is copied).
+//
+// # Safety
+// `config` and `out` must be valid pointers.
+int32_t sc_simulation_new(const struct ScConfig *config, struct ScSimulation **out);
+
+// Release a simulation. Null is ignored.
+//
+// # Safety
+// `sim` must come from this library and not be used afterwards.
+void sc_free_simulation(struct ScSimulation *sim);
+
+// Advance the simulation by `steps` steps.
+//
+// # Safety
+// `sim` must be a valid simulation pointer.
+int32_t sc_simulation_step(struct ScSimulation *sim, uint32_t steps);
+
+// Run the configured number of steps and finalize the results.
+//
+// # Safety
+// `sim` must be a valid simulation pointer.
+int32_t sc_simulation_run(struct ScSimulation *sim);
+
+// Number of live entities.
+//
+// # Safety
+// `sim` and `out` must be valid pointers.
+int32_t sc_get_entity_count(const struct ScSimulation *sim, uintptr_t *out);
+
+// Spatial dimensionality of the simulation.
+//
+// # Safety
+// `sim` and `out` must be valid pointers.
+int32_t sc_get_dimension(const struct ScSimulation *sim, uintptr_t *out);
+
+// Copy entity positions, flattened in entity id order, into `out_ptr`.
+//
+// Needs room for `entity_count * dimension` floats; returns
+// `SC_ERR_BUFFER_TOO_SMALL` without writing anything otherwise.
+//
+// # Safety
+// `out_ptr` must point to at least `len` writable floats.
+int32_t sc_get_positions(const struct ScSimulation *sim, float *out_ptr, uintptr_t len);
+
+// Metrics of the most recent step.
+//
+// # Safety
+// `sim` and `out` must be valid pointers.
+int32_t sc_get_metrics(const struct ScSimulation *sim, struct ScMetrics *out);
+
+// Consciousness score of the most recent finalization (0 before).
+//
+// # Safety
+// `sim` and `out` must be valid pointers.
+int32_t sc_get_consciousness_score(const struct ScSimulation *sim, float *out);
+
+// Write the metrics history as CSV.
+//
+// # Safety
+// `sim` must be valid and `path` NUL-terminated.
+int32_t sc_export_metrics_csv(const struct ScSimulation *sim, const char *path);
+
+#ifdef __cplusplus
+}  // extern "C"
+#endif  // __cplusplus
+
+#endif  /* SYNTHETIC_CONSCIOUSNESS_H */
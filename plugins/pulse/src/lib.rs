@@ -0,0 +1,89 @@
+//! Pulse plugin: an example stimulus source.
+//!
+//! Every `period` steps each entity senses a pulse of `amplitude` along
+//! spatial axis `axis`; in between, entities receive the built-in noise.
+//! The plugin is registered as `"pulse"` at link time and also exports the
+//! registration function, so it can be loaded from its cdylib with the
+//! `unsafe-plugins` feature.
+//!
+//! ```toml
+//! [plugins.stimulus]
+//! name = "pulse"
+//! params = { period = 10, amplitude = 0.5, axis = 0 }
+//! ```
+//!
+//! ## Author
+//! Ayomide I. Daniels (Morningstar)
+
+use synthetic_consciousness::entities::Entity;
+use synthetic_consciousness::plugin::{PluginParams, PluginRegistry, StimulusSource};
+use synthetic_consciousness::{register_stimulus_source, Real};
+
+/// Name the plugin is registered under.
+pub const PLUGIN_NAME: &str = "pulse";
+
+/// Periodic pulse along one axis.
+#[derive(Clone, Debug)]
+pub struct PulseStimulus {
+    /// Steps between pulses (a pulse fires at step 0).
+    pub period: u64,
+    /// Pulse magnitude.
+    pub amplitude: Real,
+    /// Spatial axis of the pulse.
+    pub axis: usize,
+}
+
+impl PulseStimulus {
+    /// Build from configuration parameters.
+    ///
+    /// Defaults: `period = 10`, `amplitude = 0.5`, `axis = 0`.
+    ///
+    /// # Returns
+    /// The source, or an error for a non-positive period, a negative axis
+    /// or a non-numeric value
+    pub fn from_params(params: &PluginParams) -> Result<Box<dyn StimulusSource>, String> {
+        let integer = |key: &str, default: i64| match params.get(key) {
+            None => Ok(default),
+            Some(value) => value.as_integer().ok_or_else(|| format!("'{}' must be an integer", key)),
+        };
+        let amplitude = match params.get("amplitude") {
+            None => 0.5,
+            Some(value) => value
+                .as_float()
+                .or_else(|| value.as_integer().map(|i| i as f64))
+                .ok_or("'amplitude' must be a number")? as Real,
+        };
+        let period = integer("period", 10)?;
+        let axis = integer("axis", 0)?;
+        if period < 1 {
+            return Err("'period' must be at least 1".to_string());
+        }
+        if axis < 0 {
+            return Err("'axis' must not be negative".to_string());
+        }
+        Ok(Box::new(PulseStimulus {
+            period: period as u64,
+            amplitude,
+            axis: axis as usize,
+        }))
+    }
+}
+
+impl StimulusSource for PulseStimulus {
+    fn stimulus(&mut self, entity: &Entity, step: u64) -> Option<Vec<Real>> {
+        if !step.is_multiple_of(self.period) {
+            return None;
+        }
+        let mut pulse = vec![0.0; entity.pose.position.len()];
+        *pulse.get_mut(self.axis)? = self.amplitude;
+        Some(pulse)
+    }
+}
+
+register_stimulus_source!(PLUGIN_NAME, PulseStimulus::from_params);
+
+/// Registration entry point for runtime loading.
+#[no_mangle]
+pub fn synthetic_consciousness_register(registry: &mut PluginRegistry) {
+    registry.register_stimulus_source(PLUGIN_NAME, PulseStimulus::from_params);
+}
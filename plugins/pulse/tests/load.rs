@@ -0,0 +1,67 @@
+//! Loads the pulse plugin by name and checks its pulses reach entities.
+//!
+//! `cargo test -p pulse-stimulus` (add `--features unsafe-plugins` to also
+//! load it from the cdylib)
+
+use synthetic_consciousness::plugin::{PluginParams, PluginRegistry, PluginSpec};
+use synthetic_consciousness::{Simulation, SimulationConfig};
+
+// Link the plugin so its registration is collected
+use pulse_stimulus as _;
+
+fn pulse_config() -> SimulationConfig {
+    let params: PluginParams = toml::from_str("period = 2\namplitude = 0.75").unwrap();
+    let mut config = SimulationConfig::default_2d();
+    config.simulation.num_entities = 4;
+    config.plugins.stimulus = Some(PluginSpec {
+        name: "pulse".to_string(),
+        params,
+    });
+    config
+}
+
+/// Run four steps and check every entity sensed the pulse at steps 0 and 2 only.
+fn assert_pulses_sensed(mut sim: Simulation) {
+    for _ in 0..4 {
+        sim.step();
+    }
+    for entity in sim.entities.all_entities() {
+        for step in 0..4 {
+            let pulsed = entity
+                .memory_graph
+                .nodes
+                .iter()
+                .any(|node| node.timestamp == step && entity.memory_graph.node_event(node) == [0.75, 0.0]);
+            assert_eq!(pulsed, step % 2 == 0, "entity {} at step {}", entity.id.0, step);
+        }
+    }
+}
+
+#[test]
+fn pulse_stimuli_reach_entities() {
+    assert!(PluginRegistry::with_registered().stimulus_source_names().contains(&"pulse"));
+    assert_pulses_sensed(Simulation::new(pulse_config()).unwrap());
+}
+
+#[test]
+fn unknown_plugin_or_bad_params_are_rejected() {
+    let mut config = pulse_config();
+    config.plugins.stimulus.as_mut().unwrap().params = toml::from_str("period = 0").unwrap();
+    let error = Simulation::new(config.clone()).err().unwrap();
+    assert!(error.contains("period"), "{}", error);
+
+    config.plugins.stimulus.as_mut().unwrap().name = "missing".to_string();
+    let error = Simulation::new(config).err().unwrap();
+    assert!(error.contains("Unknown stimulus source"), "{}", error);
+}
+
+#[cfg(feature = "unsafe-plugins")]
+#[test]
+fn pulse_loads_from_cdylib() {
+    let exe = std::env::current_exe().unwrap();
+    let path = exe.parent().unwrap().join(libloading::library_filename("pulse_stimulus"));
+    let mut registry = PluginRegistry::new();
+    unsafe { registry.load_library(path.to_str().unwrap()) }.unwrap();
+    assert_eq!(registry.stimulus_source_names(), ["pulse"]);
+    assert_pulses_sensed(Simulation::with_plugins(pulse_config(), &registry).unwrap());
+}
@@ -0,0 +1,84 @@
+"""End-to-end tests for the Python bindings.
+
+Build the extension first, e.g. `maturin develop --features python`,
+then run `pytest python/tests`.
+"""
+
+import csv
+
+import pytest
+
+import synthetic_consciousness as sc
+
+
+def small_config(steps=200):
+    config = sc.SimulationConfig.default_2d()
+    config.num_entities = 10
+    config.num_steps = steps
+    return config
+
+
+def test_run_200_steps_end_to_end(tmp_path):
+    sim = sc.Simulation(small_config(200))
+    summary = sim.run()
+
+    assert sim.timestamp == 200
+    assert summary.num_steps == 200
+    assert 0.0 <= sim.consciousness_score() <= 1.0
+    assert summary.consciousness_score == pytest.approx(sim.consciousness_score())
+    assert len(summary.passed_metrics) + len(summary.failed_metrics) > 0
+
+    history = sim.metrics_history()
+    assert len(history) == 200
+    assert "attention_entropy" in history[0]
+
+    columns = sim.metrics_columns()
+    assert len(columns["average_essence"]) == 200
+
+    latest = sim.latest_metrics()
+    assert latest.timestamp == history[-1]["timestamp"]
+
+    path = tmp_path / "metrics.csv"
+    sim.export_metrics_csv(str(path))
+    with open(path) as f:
+        assert f.readline().startswith("# config_fingerprint: ")
+        rows = list(csv.DictReader(f))
+    assert len(rows) == 200
+
+
+def test_manual_stepping_then_finalize():
+    sim = sc.Simulation(small_config(50))
+    sim.step(20)
+    assert sim.timestamp == 20
+    summary = sim.finalize()
+    assert summary.num_steps == 20
+
+
+def test_config_get_and_set():
+    config = sc.SimulationConfig.default_2d()
+    config.set("dynamics.damping", 0.95)
+    assert config.get("dynamics.damping") == pytest.approx(0.95)
+    with pytest.raises(KeyError):
+        config.set("dynamics.no_such_field", 1)
+    with pytest.raises(ValueError):
+        config.set("simulation.num_steps", "many")
+
+
+def test_invalid_config_raises_value_error():
+    config = sc.SimulationConfig.default_2d()
+    config.dt = -1.0
+    with pytest.raises(ValueError):
+        sc.Simulation(config)
+
+
+def test_metrics_arrow_matches_history():
+    pa = pytest.importorskip("pyarrow")
+    sim = sc.Simulation(small_config(60))
+    sim.run()
+    if not hasattr(sim, "metrics_arrow"):
+        pytest.skip("built without the arrow feature")
+    batch = sim.metrics_arrow()
+    assert isinstance(batch, pa.RecordBatch)
+    assert batch.schema.field("step").type == pa.uint64()
+    assert batch.schema.field("average_essence").type == pa.float32()
+    assert batch.num_rows == len(sim.metrics_history())
@@ -0,0 +1,1199 @@
+//! Analysis module: post-hoc and runtime analyses of collective dynamics.
+//!
+//! This module hosts measurements that look at the population as a whole
+//! rather than at individual consciousness metrics:
+//! - **Phase Synchrony**: Coherence of motion direction between entity pairs
+//! - **Introspection**: One-call runtime summary of simulation state
+//! - **Sensitivity**: Lyapunov exponents from perturbed initial conditions
+//! - **Counterfactuals**: Ablation of one entity's parameters
+//! - **Resonance**: Consciousness score under periodic kernel-width forcing
+//! - **Workspace Integration**: Reach of a broadcast over the attention graph
+//! - **State-Space Distance**: Total variation between recorded steps
+//! - **Temporal Self-Similarity**: Recurrence of metric vectors over time
+//! - **Recurrence Quantification**: RQA measures of a single metric series
+//! - **Emergent Leaders**: PageRank over cumulative attraction
+//! - **Algebraic Connectivity**: Spectral integration of the attention graph
+//! - **DataFrames** (`polars` feature): metrics and trajectories as polars
+//!   frames, with rolling means and per-entity summaries (see `dataframe`)
+//!
+//! ## Phase Synchrony
+//!
+//! Each entity's motion phase is `φ = atan2(v_y, v_x)`; in 3D this is the
+//! heading projected onto the xy plane, so vertical motion is ignored. The
+//! phase coherence of a pair over a window of W steps is
+//! `|mean(exp(i·(φ_i − φ_j)))|`, which is 1 for perfectly phase-locked
+//! motion and near 0 for independent motion; `global_synchrony()` averages
+//! it over all pairs at O(n²·W). The value reported every step
+//! (`Metrics::phase_synchrony`) is the O(n·W) Kuramoto order parameter
+//! `|mean_i exp(i·φ_i)|` averaged over the window (`kuramoto_order()`),
+//! which also requires the entities to share a heading.
+//! Phase synchrony between oscillators is a widely studied neural correlate
+//! of consciousness.
+//!
+//! ## Workspace Integration
+//!
+//! Inspired by Global Workspace Theory: a broadcast from the entity with the
+//! highest affective strength spreads along attention links stronger than a
+//! threshold. The reachable fraction of the population measures how globally
+//! accessible its content is (1.0 = fully integrated workspace).
+//!
+//! ## Sensitivity to Initial Conditions
+//!
+//! Copies of the current state are run with every position displaced by
+//! `ε` in a random direction. The separation `d(t)` of each entity from its
+//! unperturbed trajectory is fitted to `d(t) = ε·exp(λ·t)` by least squares
+//! on `ln(d/ε)`; `λ > 0` signals chaotic sensitivity. The copies continue the
+//! simulation's random stream, so they receive the same sensory noise for
+//! as long as they draw the same random numbers; divergence after that
+//! also reflects the noise.
+//!
+//! ## Counterfactuals
+//!
+//! Two copies of the current state are run side by side for `num_steps`
+//! steps: a baseline and one in which a single entity carries modified
+//! parameters (an [`EntityConfig`] override, e.g. zero curiosity). The
+//! difference of each metric's mean over the two runs measures that
+//! entity's contribution to the collective dynamics. Both copies continue
+//! the simulation's random stream, but once the modified entity changes
+//! which random numbers are drawn (e.g. by signaling) the noise differs,
+//! so small deltas can arise from noise alone; compare against the spread
+//! of repeated runs before drawing conclusions.
+//!
+//! ## Resonance
+//!
+//! The computational analogue of EEG frequency tagging: copies of the
+//! current state are driven with a sinusoidal kernel width
+//! `σ(t) = σ₀·(1 + RESONANCE_AMPLITUDE·sin(2π·f·t))` (`t` in simulation
+//! time, `f` in cycles per unit time) for `RESONANCE_SAMPLES` frequencies
+//! spread evenly over a range. The consciousness score of each run traces a
+//! response spectrum; its peak is the frequency the collective dynamics
+//! resonate with. Scores move in discrete steps (one per passed criterion),
+//! so flat stretches are common.
+//!
+//! ## State-Space Distance
+//!
+//! Each recorded step is reduced to a histogram over a joint
+//! position-velocity grid (`TVD_BINS` bins per axis; velocity components
+//! span ±the largest component seen in either step). The total variation
+//! distance `½·Σ|p − q|` between two histograms is 0 for identical
+//! occupancy and 1 for disjoint occupancy. Low values mark near-stationary
+//! periods; peaks mark dynamical event boundaries.
+//!
+//! ## Temporal Self-Similarity
+//!
+//! The eight primary metrics at each recorded step form a vector `m_i`.
+//! The matrix `S[i][j] = cos(m_i, m_j)` is symmetric with a unit diagonal.
+//! Square blocks along the diagonal mark stable phases; bright cells far
+//! from the diagonal mark states the run returns to (periodicity).
+//!
+//! ## Recurrence Quantification
+//!
+//! For a metric series `x`, the recurrence matrix is `R[i][j] = 1` if
+//! `|x_i − x_j| < ε`, with the trivial main diagonal excluded. From it:
+//! - **Recurrence rate**: fraction of recurrent points
+//! - **Determinism**: fraction of recurrent points on diagonal lines of
+//!   length ≥ `RQA_MIN_LINE` (the series repeats whole stretches)
+//! - **Laminarity**: fraction on vertical lines of length ≥ `RQA_MIN_LINE`
+//!   (the series lingers in a state)
+//! - **Trapping time**: mean length of those vertical lines
+//! - **Longest diagonal line**: length of the longest repeated stretch
+//!
+//! High determinism indicates structured rather than random dynamics.
+//!
+//! ## Emergent Leaders
+//!
+//! The social interaction graph links every pair of entities with the
+//! attraction they have exchanged so far, summed over the recorded steps.
+//! A random walker follows each entity's links in proportion to their
+//! weight (row-normalized), jumping to a uniformly random entity with
+//! probability `1 − PAGERANK_DAMPING`; entities without links jump
+//! uniformly. The stationary distribution, found by `PAGERANK_ITERATIONS`
+//! rounds of power iteration, is the PageRank score: entities that many
+//! well-connected entities are drawn to score highest. Scores sum to 1.
+//!
+//! ## Algebraic Connectivity
+//!
+//! With the attention matrix `W` as edge weights, the graph Laplacian is
+//! `L = D − W` (`D` = diagonal of weighted degrees). Its smallest eigenvalue
+//! is 0 with the all-ones eigenvector; the second smallest, `λ₂`, is the
+//! algebraic connectivity. `λ₂ = 0` iff the graph is disconnected, and it
+//! grows with the weight of the weakest cut, so a high `λ₂` marks a network
+//! that is well integrated and hard to split. `λ₂` is found by power
+//! iteration on `c·I − L` (`c` = twice the largest degree, an upper bound
+//! on the spectrum) with the all-ones component projected out each round.
+//!
+//! ## Author
+//! Ayomide I. Daniels (Morningstar)
+
+use crate::real::Real;
+use crate::attraction::compute_kernel;
+use crate::config::SimulationConfigPatch;
+use crate::entities::{EntityConfig, EntityId};
+use std::collections::VecDeque;
+use crate::geometry::{periodic_distance, Coords};
+use crate::memory::MemoryGraph;
+use crate::metrics::Metrics;
+use crate::results::SimulationStep;
+use crate::simulation::{Simulation, SimulationRng};
+use rand::{Rng, SeedableRng};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+#[cfg(feature = "polars")]
+pub use crate::dataframe::{metrics_dataframe, per_entity_summary, rolling_metric_means, trajectories_dataframe};
+
+/// Probability that the PageRank walker follows a link.
+pub const PAGERANK_DAMPING: Real = 0.85;
+
+/// Power-iteration rounds used for PageRank.
+pub const PAGERANK_ITERATIONS: usize = 30;
+
+/// Number of top-ranked leaders highlighted in reports and the GUI.
+pub const TOP_LEADERS: usize = 3;
+
+/// Power-iteration rounds used for the algebraic connectivity.
+pub const SPECTRAL_ITERATIONS: usize = 200;
+
+/// Number of recent steps used for phase synchrony.
+pub const SYNCHRONY_WINDOW: usize = 50;
+
+/// Compute the motion phase of a velocity vector.
+///
+/// Only the x and y components are used: a 3D velocity is projected onto
+/// the xy plane, so its z component (climb or dive) does not change the
+/// phase.
+///
+/// # Returns
+/// `atan2(v_y, v_x)`, or 0 for vectors with fewer than two components
+pub fn velocity_phase(velocity: &[Real]) -> Real {
+    if velocity.len() < 2 {
+        return 0.0;
+    }
+    velocity[1].atan2(velocity[0])
+}
+
+/// Phase coherence between two phase series.
+///
+/// Uses the most recent samples common to both series.
+///
+/// # Arguments
+/// * `phases_a` - Phase history of the first entity (oldest first)
+/// * `phases_b` - Phase history of the second entity (oldest first)
+///
+/// # Returns
+/// `|mean(exp(i·(φ_a − φ_b)))|` in [0, 1], or 0 if there is no overlap
+pub fn phase_coherence(phases_a: &[Real], phases_b: &[Real]) -> Real {
+    let n = phases_a.len().min(phases_b.len());
+    if n == 0 {
+        return 0.0;
+    }
+    let a = &phases_a[phases_a.len() - n..];
+    let b = &phases_b[phases_b.len() - n..];
+
+    let (mut re, mut im) = (0.0 as Real, 0.0 as Real);
+    for (pa, pb) in a.iter().zip(b.iter()) {
+        let delta = pa - pb;
+        re += delta.cos();
+        im += delta.sin();
+    }
+    (re * re + im * im).sqrt() / n as Real
+}
+
+/// Separation (in multiples of ε) at which trajectories count as diverged.
+pub const DIVERGENCE_FACTOR: Real = 10.0;
+
+/// Result of `Simulation::run_sensitivity_to_initial_conditions()`.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct SensitivityReport {
+    /// Estimated maximal Lyapunov exponent per entity (ordered by id),
+    /// averaged over perturbations, in units of 1/time.
+    pub lyapunov_exponents: Vec<Real>,
+    /// Mean time for separations to reach `DIVERGENCE_FACTOR·ε`
+    /// (the horizon is used for pairs that never diverge).
+    pub mean_divergence_time: Real,
+    /// True if the largest exponent is positive.
+    pub chaos_indicator: bool,
+}
+
+/// Result of `Simulation::run_counterfactual()`.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct CounterfactualResult {
+    /// Metrics of every step of the unmodified run.
+    pub baseline_metrics: Vec<Metrics>,
+    /// Metrics of every step of the run with the modified entity.
+    pub counterfactual_metrics: Vec<Metrics>,
+    /// Per metric (see `Metrics::FIELD_NAMES`), mean over the counterfactual
+    /// run minus mean over the baseline run.
+    pub metric_deltas: HashMap<String, Real>,
+    /// Whether the baseline run met the consciousness criteria.
+    pub baseline_consciousness_achieved: bool,
+    /// Whether the counterfactual run met the consciousness criteria.
+    pub counterfactual_consciousness_achieved: bool,
+}
+
+/// Mean of each metric field over a run.
+fn mean_field_values(metrics: &[Metrics]) -> [Real; Metrics::FIELD_NAMES.len()] {
+    let mut sums = [0.0 as Real; Metrics::FIELD_NAMES.len()];
+    for m in metrics {
+        for (sum, value) in sums.iter_mut().zip(m.field_values()) {
+            *sum += value;
+        }
+    }
+    if !metrics.is_empty() {
+        sums.iter_mut().for_each(|sum| *sum /= metrics.len() as Real);
+    }
+    sums
+}
+
+/// Relative amplitude of the sinusoidal kernel-width forcing.
+pub const RESONANCE_AMPLITUDE: Real = 0.5;
+
+/// Number of forcing frequencies sampled by the resonance experiment.
+pub const RESONANCE_SAMPLES: usize = 10;
+
+/// Result of `Simulation::run_resonance_experiment()`.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ResonanceSpectrum {
+    /// Forcing frequencies, ascending (cycles per unit simulation time).
+    pub frequencies: Vec<Real>,
+    /// Consciousness score of the run forced at each frequency.
+    pub consciousness_scores: Vec<Real>,
+    /// Frequency with the highest score (lowest frequency on ties).
+    pub peak_frequency: Real,
+}
+
+/// Least-squares exponential growth rate through the origin.
+///
+/// Fits `ln(d(t)/ε) = λ·t` for the samples `(t, d)` with `d > 0`.
+///
+/// # Returns
+/// Estimated λ, or 0 if no usable samples
+pub fn fit_exponential_rate(samples: &[(Real, Real)], epsilon: Real) -> Real {
+    let (mut num, mut den) = (0.0 as Real, 0.0 as Real);
+    for &(t, d) in samples {
+        if d > 0.0 && t > 0.0 {
+            num += t * (d / epsilon).ln();
+            den += t * t;
+        }
+    }
+    if den > 0.0 {
+        num / den
+    } else {
+        0.0
+    }
+}
+
+/// Grid resolution per position and velocity axis for state-space distance.
+pub const TVD_BINS: usize = 4;
+
+/// Step spacing of the total variation series in the report.
+pub const TVD_STRIDE: u64 = 10;
+
+/// Map a value in [lo, hi] to one of `TVD_BINS` bins.
+fn grid_bin(value: Real, lo: Real, hi: Real) -> usize {
+    if hi <= lo {
+        return 0;
+    }
+    let t = ((value - lo) / (hi - lo)).clamp(0.0, 1.0);
+    ((t * TVD_BINS as Real) as usize).min(TVD_BINS - 1)
+}
+
+/// Normalized occupancy of the joint position-velocity grid for a step.
+fn state_histogram(step: &SimulationStep, bounds: &[Real], max_speed: Real) -> HashMap<Vec<usize>, Real> {
+    let mut histogram = HashMap::new();
+    let n = step.entity_positions.len();
+    if n == 0 {
+        return histogram;
+    }
+    for (id, position) in &step.entity_positions {
+        let mut cell: Vec<usize> = position
+            .iter()
+            .zip(bounds.iter())
+            .map(|(p, b)| grid_bin(*p, 0.0, *b))
+            .collect();
+        if let Some((_, velocity)) = step.entity_velocities.iter().find(|(v, _)| v == id) {
+            cell.extend(velocity.iter().map(|v| grid_bin(*v, -max_speed, max_speed)));
+        }
+        *histogram.entry(cell).or_insert(0.0) += 1.0 / n as Real;
+    }
+    histogram
+}
+
+/// Total variation distance between the state distributions of two steps.
+/// 
+/// # Arguments
+/// * `a` - First recorded step
+/// * `b` - Second recorded step
+/// * `bounds` - World bounds used for the position grid
+/// 
+/// # Returns
+/// Distance in [0, 1] (0 if both steps are empty)
+pub fn total_variation_distance(a: &SimulationStep, b: &SimulationStep, bounds: &[Real]) -> Real {
+    let max_speed = a
+        .entity_velocities
+        .iter()
+        .chain(b.entity_velocities.iter())
+        .flat_map(|(_, v)| v.iter())
+        .fold(0.0 as Real, |m, v| m.max(v.abs()));
+
+    let p = state_histogram(a, bounds, max_speed);
+    let q = state_histogram(b, bounds, max_speed);
+    let mut distance = 0.0;
+    for (cell, mass) in &p {
+        distance += (mass - q.get(cell).copied().unwrap_or(0.0)).abs();
+    }
+    for (cell, mass) in &q {
+        if !p.contains_key(cell) {
+            distance += mass;
+        }
+    }
+    0.5 * distance
+}
+
+/// Minimum diagonal/vertical line length counted by RQA.
+pub const RQA_MIN_LINE: usize = 2;
+
+/// Recurrence threshold used in the report, as a fraction of the series'
+/// standard deviation.
+pub const RQA_THRESHOLD_FRACTION: Real = 0.1;
+
+/// Recurrence quantification measures of a time series.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct RQA {
+    /// Fraction of recurrent point pairs
+    pub recurrence_rate: Real,
+    /// Fraction of recurrent points on diagonal lines
+    pub determinism: Real,
+    /// Fraction of recurrent points on vertical lines
+    pub laminarity: Real,
+    /// Mean vertical line length
+    pub trapping_time: Real,
+    /// Longest diagonal line (excluding the main diagonal)
+    pub max_diagonal_line: u32,
+}
+
+/// Recurrence quantification analysis of a scalar series.
+/// 
+/// # Arguments
+/// * `series` - Values in time order
+/// * `threshold` - Recurrence radius ε
+/// 
+/// # Returns
+/// RQA measures (all zero for fewer than two samples or no recurrences)
+pub fn recurrence_quantification(series: &[Real], threshold: Real) -> RQA {
+    let n = series.len();
+    if n < 2 {
+        return RQA::default();
+    }
+    let recurrent = |i: usize, j: usize| i != j && (series[i] - series[j]).abs() < threshold;
+
+    // Diagonal lines: R is symmetric, so scan the upper triangle and count twice.
+    let mut recurrent_points = 0usize;
+    let mut diagonal_points = 0usize;
+    let mut max_diagonal_line = 0usize;
+    for offset in 1..n {
+        let mut run = 0usize;
+        for i in 0..=(n - offset) {
+            if i < n - offset && recurrent(i, i + offset) {
+                run += 1;
+                continue;
+            }
+            recurrent_points += 2 * run;
+            if run >= RQA_MIN_LINE {
+                diagonal_points += 2 * run;
+            }
+            max_diagonal_line = max_diagonal_line.max(run);
+            run = 0;
+        }
+    }
+    if recurrent_points == 0 {
+        return RQA::default();
+    }
+
+    let mut vertical_points = 0usize;
+    let mut vertical_lines = 0usize;
+    for j in 0..n {
+        let mut run = 0usize;
+        for i in 0..=n {
+            if i < n && recurrent(i, j) {
+                run += 1;
+                continue;
+            }
+            if run >= RQA_MIN_LINE {
+                vertical_points += run;
+                vertical_lines += 1;
+            }
+            run = 0;
+        }
+    }
+
+    RQA {
+        recurrence_rate: recurrent_points as Real / (n * (n - 1)) as Real,
+        determinism: diagonal_points as Real / recurrent_points as Real,
+        laminarity: vertical_points as Real / recurrent_points as Real,
+        trapping_time: if vertical_lines > 0 {
+            vertical_points as Real / vertical_lines as Real
+        } else {
+            0.0
+        },
+        max_diagonal_line: max_diagonal_line as u32,
+    }
+}
+
+/// Largest side of the self-similarity matrix drawn in the HTML report.
+pub const SELF_SIMILARITY_REPORT_SIZE: usize = 100;
+
+/// Pairwise cosine similarity of the core metric vectors of a sequence of
+/// snapshots.
+/// 
+/// # Arguments
+/// * `metrics` - Metrics snapshots in time order
+/// 
+/// # Returns
+/// Symmetric `n × n` matrix with `S[i][j] = cos(m_i, m_j)`
+pub fn temporal_self_similarity(metrics: &[&Metrics]) -> Vec<Vec<Real>> {
+    let vectors: Vec<[Real; 8]> = metrics.iter().map(|m| m.core_vector()).collect();
+    let n = vectors.len();
+    let mut matrix = vec![vec![0.0; n]; n];
+    for i in 0..n {
+        for j in i..n {
+            let similarity = MemoryGraph::cosine_similarity(&vectors[i], &vectors[j]);
+            matrix[i][j] = similarity;
+            matrix[j][i] = similarity;
+        }
+    }
+    matrix
+}
+
+/// PageRank of a weighted graph by power iteration.
+/// 
+/// # Arguments
+/// * `weights` - Square matrix; `weights[i][j]` is the weight of the link i → j
+/// * `damping` - Probability of following a link rather than jumping
+/// * `iterations` - Power-iteration rounds
+/// 
+/// # Returns
+/// Score per node, summing to 1 (empty for an empty graph)
+pub fn pagerank(weights: &[Vec<Real>], damping: Real, iterations: usize) -> Vec<Real> {
+    let n = weights.len();
+    if n == 0 {
+        return Vec::new();
+    }
+    let out_weight: Vec<Real> = weights.iter().map(|row| row.iter().sum()).collect();
+    let mut rank = vec![1.0 / n as Real; n];
+    for _ in 0..iterations {
+        // Rank of nodes without links is spread uniformly
+        let dangling: Real = (0..n).filter(|&i| out_weight[i] <= 0.0).map(|i| rank[i]).sum();
+        let base = (1.0 - damping + damping * dangling) / n as Real;
+        let mut next = vec![base; n];
+        for i in 0..n {
+            if out_weight[i] <= 0.0 {
+                continue;
+            }
+            let share = damping * rank[i] / out_weight[i];
+            for (j, w) in weights[i].iter().enumerate() {
+                next[j] += share * w;
+            }
+        }
+        rank = next;
+    }
+    rank
+}
+
+/// Algebraic connectivity (second smallest Laplacian eigenvalue) of a graph.
+///
+/// See the module docs for the method. The start vector is fixed, so the
+/// result is deterministic.
+///
+/// # Arguments
+/// * `weights` - Symmetric non-negative matrix of edge weights (diagonal ignored)
+/// * `iterations` - Power-iteration rounds
+///
+/// # Returns
+/// `λ₂ ≥ 0`, or 0 for graphs with fewer than two nodes
+pub fn algebraic_connectivity(weights: &[Vec<Real>], iterations: usize) -> Real {
+    let n = weights.len();
+    if n < 2 {
+        return 0.0;
+    }
+    let degree: Vec<Real> = (0..n)
+        .map(|i| (0..n).filter(|&j| j != i).map(|j| weights[i][j]).sum())
+        .collect();
+    let shift = 2.0 * degree.iter().copied().fold(0.0, Real::max);
+    if shift <= 0.0 {
+        return 0.0;
+    }
+    let laplacian = |v: &[Real]| -> Vec<Real> {
+        (0..n)
+            .map(|i| degree[i] * v[i] - (0..n).filter(|&j| j != i).map(|j| weights[i][j] * v[j]).sum::<Real>())
+            .collect()
+    };
+    let deflate_and_normalize = |v: &mut Vec<Real>| -> bool {
+        let mean = v.iter().sum::<Real>() / n as Real;
+        v.iter_mut().for_each(|x| *x -= mean);
+        let norm = v.iter().map(|x| x * x).sum::<Real>().sqrt();
+        if norm <= Real::EPSILON {
+            return false;
+        }
+        v.iter_mut().for_each(|x| *x /= norm);
+        true
+    };
+
+    // Irregular start vector, unlikely to be orthogonal to the Fiedler vector
+    let mut v: Vec<Real> = (0..n).map(|i| ((i + 1) as Real * 1.618).sin()).collect();
+    if !deflate_and_normalize(&mut v) {
+        return 0.0;
+    }
+    for _ in 0..iterations {
+        let lv = laplacian(&v);
+        let mut next: Vec<Real> = v.iter().zip(&lv).map(|(x, l)| shift * x - l).collect();
+        if !deflate_and_normalize(&mut next) {
+            break;
+        }
+        v = next;
+    }
+
+    // Rayleigh quotient of the unit vector v
+    let lv = laplacian(&v);
+    v.iter().zip(&lv).map(|(x, l)| x * l).sum::<Real>().max(0.0)
+}
+
+/// Runtime state summary returned by `Simulation::introspect()`.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct IntrospectionReport {
+    /// Current simulation step.
+    pub timestamp: u64,
+    /// Number of live entities.
+    pub entity_count: usize,
+    /// Memory nodes summed over all entities.
+    pub total_memory_nodes: usize,
+    /// Belief clusters summed over all entities.
+    pub total_clusters: usize,
+    /// Memory edges summed over all entities.
+    pub total_edges: usize,
+    /// Mean activation over all memory nodes.
+    pub mean_activation: Real,
+    /// Mean essence index over all entities.
+    pub mean_essence: Real,
+    /// Per entity (ordered by id), distance to the neighbor with the
+    /// strongest attraction kernel value.
+    pub dominant_kernel_distances: Vec<Real>,
+    /// Metrics computed on the current state.
+    pub current_metrics: Metrics,
+    /// Rough RAM estimate of entity state vectors in bytes.
+    pub estimated_memory_bytes: usize,
+}
+
+impl Simulation {
+    /// Entity ids in ascending order, used as the row/column order of
+    /// pairwise analysis matrices.
+    pub fn sorted_entity_ids(&self) -> Vec<EntityId> {
+        self.entities.iter().map(|e| e.id).collect()
+    }
+
+    /// Compute the pairwise phase synchrony matrix.
+    ///
+    /// `matrix[i][j]` is the phase coherence of entities i and j over the
+    /// last `SYNCHRONY_WINDOW` steps, with rows and columns ordered as in
+    /// `sorted_entity_ids()`. The diagonal is 1 for entities with history.
+    /// High off-diagonal entries indicate synchronized motion pairs.
+    ///
+    /// # Returns
+    /// Square matrix of coherence values in [0, 1]
+    pub fn compute_synchrony_matrix(&self) -> Vec<Vec<Real>> {
+        let (n, values) = self.synchrony_values();
+        values.chunks(n.max(1)).map(<[Real]>::to_vec).collect()
+    }
+
+    /// Global synchrony: mean off-diagonal entry of the synchrony matrix.
+    ///
+    /// Costs O(n²·W) for n entities and a window of W steps, so it is
+    /// computed on demand only; the per-step `Metrics::phase_synchrony` is
+    /// the cheaper `kuramoto_order()`.
+    ///
+    /// # Returns
+    /// Mean pairwise phase coherence, or 0 with fewer than two entities
+    pub fn global_synchrony(&self) -> Real {
+        let (n, values) = self.synchrony_values();
+        if n < 2 {
+            return 0.0;
+        }
+        let mut total = 0.0;
+        for (k, value) in values.iter().enumerate() {
+            if k / n != k % n {
+                total += value;
+            }
+        }
+        total / (n * (n - 1)) as Real
+    }
+
+    /// Kuramoto order parameter averaged over the synchrony window.
+    ///
+    /// For each of the last `SYNCHRONY_WINDOW` steps (histories aligned at
+    /// their most recent sample) the unit phasors of the entities with a
+    /// sample for that step are averaged; the magnitude of the mean is 1
+    /// when they all move in the same direction and near 0 for independent
+    /// or opposed motion. Steps with fewer than two samples are skipped.
+    /// Unlike `global_synchrony()`, a constant phase offset between entities
+    /// lowers the value, and the cost is O(n·W) rather than O(n²·W).
+    ///
+    /// # Returns
+    /// Mean order parameter in [0, 1], or 0 with fewer than two entities
+    pub fn kuramoto_order(&self) -> Real {
+        // (Σ cos φ, Σ sin φ, samples) per step back from the newest
+        let mut slots = [(0.0 as Real, 0.0 as Real, 0usize); SYNCHRONY_WINDOW];
+        for entity in self.entities.iter() {
+            let Some(history) = self.phase_history.get(&entity.id) else {
+                continue;
+            };
+            for (slot, phase) in slots.iter_mut().zip(history.iter().rev()) {
+                slot.0 += phase.cos();
+                slot.1 += phase.sin();
+                slot.2 += 1;
+            }
+        }
+
+        let (mut total, mut counted) = (0.0, 0);
+        for &(re, im, samples) in slots.iter().filter(|slot| slot.2 >= 2) {
+            total += (re * re + im * im).sqrt() / samples as Real;
+            counted += 1;
+        }
+        if counted == 0 {
+            0.0
+        } else {
+            total / counted as Real
+        }
+    }
+
+    /// Synchrony matrix flattened in row-major order, with its size.
+    ///
+    /// Phase histories are copied into one contiguous buffer, so the cost
+    /// is a few allocations regardless of the entity count.
+    fn synchrony_values(&self) -> (usize, Vec<Real>) {
+        let ids = self.sorted_entity_ids();
+        let n = ids.len();
+        let mut phases = Vec::with_capacity(n * SYNCHRONY_WINDOW);
+        let mut spans = Vec::with_capacity(n);
+        for id in &ids {
+            let start = phases.len();
+            if let Some(history) = self.phase_history.get(id) {
+                phases.extend(history.iter().copied());
+            }
+            spans.push(start..phases.len());
+        }
+
+        let mut values = vec![0.0; n * n];
+        for i in 0..n {
+            for j in i..n {
+                let coherence = phase_coherence(&phases[spans[i].clone()], &phases[spans[j].clone()]);
+                values[i * n + j] = coherence;
+                values[j * n + i] = coherence;
+            }
+        }
+        (n, values)
+    }
+
+    /// Summarize the current runtime state in a single call.
+    ///
+    /// Intended as a health check for operators: it aggregates memory
+    /// graph sizes, activation, essence and current metrics without
+    /// touching the step history or any exported files.
+    ///
+    /// # Returns
+    /// Introspection report for the current step
+    pub fn introspect(&self) -> IntrospectionReport {
+        let ids = self.sorted_entity_ids();
+        let entities: Vec<_> = ids
+            .iter()
+            .filter_map(|id| self.entities.get_entity(*id))
+            .collect();
+
+        let mut total_memory_nodes = 0;
+        let mut total_clusters = 0;
+        let mut total_edges = 0;
+        let mut activation_sum = 0.0;
+        let mut state_floats = 0;
+        for entity in &entities {
+            let graph = &entity.memory_graph;
+            total_memory_nodes += graph.nodes.len();
+            total_clusters += graph.clusters.len();
+            total_edges += graph.edges.len();
+            activation_sum += graph.nodes.iter().map(|n| n.activation).sum::<Real>();
+            state_floats += entity.state.memory.len()
+                + entity.state.context.len()
+                + entity.state.traits.len();
+        }
+
+        let mean_activation = if total_memory_nodes > 0 {
+            activation_sum / total_memory_nodes as Real
+        } else {
+            0.0
+        };
+        let mean_essence = if entities.is_empty() {
+            0.0
+        } else {
+            entities.iter().map(|e| e.essence.value).sum::<Real>() / entities.len() as Real
+        };
+
+        // Distance to the neighbor with the largest weighted kernel value
+        let attraction = &self.config.attraction;
+        let dominant_kernel_distances = entities
+            .iter()
+            .map(|entity| {
+                let mut best: Option<(Real, Real)> = None;
+                for other in &entities {
+                    if other.id == entity.id {
+                        continue;
+                    }
+                    let distance = entity.pose.distance_to(&other.pose);
+                    let strength = other.emitted_attraction_weight()
+                        * compute_kernel(&attraction.kernel, distance, attraction.sigma);
+                    if best.is_none_or(|(s, _)| strength > s) {
+                        best = Some((strength, distance));
+                    }
+                }
+                best.map(|(_, d)| d).unwrap_or(0.0)
+            })
+            .collect();
+
+        let mut current_metrics = Metrics::compute(&self.entities, self.timestamp);
+        current_metrics.phase_synchrony = self.kuramoto_order();
+
+        IntrospectionReport {
+            timestamp: self.timestamp,
+            entity_count: entities.len(),
+            total_memory_nodes,
+            total_clusters,
+            total_edges,
+            mean_activation,
+            mean_essence,
+            dominant_kernel_distances,
+            current_metrics,
+            estimated_memory_bytes: state_floats * std::mem::size_of::<Real>(),
+        }
+    }
+
+    /// Compare a run against one where a single entity is modified.
+    ///
+    /// Forks the current state twice, applies `counterfactual_config` to
+    /// entity `entity_id` in the second fork, runs both for `num_steps`
+    /// steps and compares their metrics (see the module docs). The
+    /// simulation itself is not advanced.
+    ///
+    /// # Arguments
+    /// * `entity_id` - Entity to modify (the `id` field of the config is ignored)
+    /// * `counterfactual_config` - Labels and parameter overrides for that entity
+    ///
+    /// # Returns
+    /// Metrics of both runs and their differences, or an error if the entity
+    /// does not exist or the overrides are out of range
+    pub fn run_counterfactual(
+        &self,
+        entity_id: EntityId,
+        counterfactual_config: EntityConfig,
+    ) -> Result<CounterfactualResult, String> {
+        if !counterfactual_config.is_valid() {
+            return Err(format!("Counterfactual overrides for entity {} are out of range", entity_id.0));
+        }
+        let mut baseline = self.fork();
+        let mut counterfactual = self.fork();
+        let entity = counterfactual
+            .entities
+            .get_entity_mut(entity_id)
+            .ok_or_else(|| format!("No entity with id {}", entity_id.0))?;
+        counterfactual_config.apply_to(entity);
+
+        for sim in [&mut baseline, &mut counterfactual] {
+            for _ in 0..self.config.simulation.num_steps {
+                sim.step();
+            }
+            sim.results.analyze_consciousness();
+        }
+
+        let baseline_means = mean_field_values(&baseline.metrics_history);
+        let counterfactual_means = mean_field_values(&counterfactual.metrics_history);
+        let metric_deltas = Metrics::FIELD_NAMES
+            .iter()
+            .enumerate()
+            .map(|(k, name)| (name.to_string(), counterfactual_means[k] - baseline_means[k]))
+            .collect();
+
+        Ok(CounterfactualResult {
+            baseline_consciousness_achieved: baseline.results.consciousness_analysis.consciousness_achieved,
+            counterfactual_consciousness_achieved: counterfactual.results.consciousness_analysis.consciousness_achieved,
+            baseline_metrics: baseline.metrics_history,
+            counterfactual_metrics: counterfactual.metrics_history,
+            metric_deltas,
+        })
+    }
+
+    /// Measure the consciousness score under periodic forcing of `σ`.
+    ///
+    /// Runs one copy of the current state per sampled frequency for
+    /// `steps` steps (see the module docs). The simulation itself is not
+    /// advanced.
+    ///
+    /// # Arguments
+    /// * `frequency_range` - (lowest, highest) frequency; the bounds are
+    ///   put in order and negative values treated as 0
+    /// * `steps` - Length of each run
+    ///
+    /// # Returns
+    /// Score per frequency and the peak frequency
+    pub fn run_resonance_experiment(&self, frequency_range: (Real, Real), steps: u32) -> ResonanceSpectrum {
+        let low = frequency_range.0.min(frequency_range.1).max(0.0);
+        let high = frequency_range.0.max(frequency_range.1).max(0.0);
+        let frequencies: Vec<Real> = if high > low {
+            (0..RESONANCE_SAMPLES)
+                .map(|k| low + (high - low) * k as Real / (RESONANCE_SAMPLES - 1) as Real)
+                .collect()
+        } else {
+            vec![low]
+        };
+
+        let base_sigma = self.config.attraction.sigma;
+        let dt = self.config.dynamics.dt;
+        let consciousness_scores: Vec<Real> = frequencies
+            .iter()
+            .map(|&frequency| {
+                let mut sim = self.fork();
+                for k in 0..steps {
+                    let phase = 2.0 * crate::real::consts::PI * frequency * k as Real * dt;
+                    let patch = SimulationConfigPatch {
+                        sigma: Some(base_sigma * (1.0 + RESONANCE_AMPLITUDE * phase.sin())),
+                        ..Default::default()
+                    };
+                    // σ stays within [0.5σ₀, 1.5σ₀], so the patch is always valid
+                    let _ = sim.apply_config_patch(patch);
+                    sim.step();
+                }
+                sim.results.analyze_consciousness();
+                sim.results.consciousness_analysis.consciousness_score
+            })
+            .collect();
+
+        let peak = consciousness_scores
+            .iter()
+            .enumerate()
+            .fold(0, |best, (k, score)| if *score > consciousness_scores[best] { k } else { best });
+        ResonanceSpectrum {
+            peak_frequency: frequencies[peak],
+            frequencies,
+            consciousness_scores,
+        }
+    }
+
+    /// Estimate sensitivity to initial conditions from perturbed copies.
+    ///
+    /// The current state is the base initial state. A reference copy and
+    /// `n_perturbations` perturbed copies (each position displaced by
+    /// `epsilon` in a random direction) are run for `num_steps` steps.
+    /// Per-entity separations are fitted to `d(t) = ε·exp(λ·t)` until they
+    /// saturate at a quarter of the smallest world bound. The simulation
+    /// itself is not advanced; the report is also stored in the results
+    /// for the HTML report.
+    ///
+    /// # Arguments
+    /// * `n_perturbations` - Number of perturbed runs
+    /// * `epsilon` - Initial displacement magnitude
+    ///
+    /// # Returns
+    /// Sensitivity report with per-entity Lyapunov exponents
+    pub fn run_sensitivity_to_initial_conditions(
+        &mut self,
+        n_perturbations: u32,
+        epsilon: Real,
+    ) -> SensitivityReport {
+        let ids = self.sorted_entity_ids();
+        let horizon = self.config.simulation.num_steps;
+        let dt = self.config.dynamics.dt;
+        let geometry = self.config.geometry.clone();
+        let saturation = geometry.bounds.iter().cloned().fold(Real::MAX, Real::min) / 4.0;
+
+        let positions = |sim: &Simulation| -> Vec<Option<Coords>> {
+            ids.iter()
+                .map(|id| sim.entities.get_entity(*id).map(|e| e.pose.position))
+                .collect()
+        };
+
+        let mut reference = self.fork();
+        let mut reference_path = Vec::with_capacity(horizon as usize);
+        for _ in 0..horizon {
+            reference.step();
+            reference_path.push(positions(&reference));
+        }
+
+        // Perturbation directions: reproducible, and apart from the copies' own stream
+        let mut rng = SimulationRng::seed_from_u64(self.seed() ^ self.timestamp.rotate_left(32));
+        let mut rate_sums = vec![0.0 as Real; ids.len()];
+        let mut divergence_time_sum = 0.0 as Real;
+        let mut divergence_samples = 0usize;
+
+        for _ in 0..n_perturbations {
+            let mut perturbed = self.fork();
+            for id in &ids {
+                if let Some(entity) = perturbed.entities.get_entity_mut(*id) {
+                    let direction: Vec<Real> =
+                        entity.pose.position.iter().map(|_| rng.gen_range(-1.0..1.0)).collect();
+                    let norm = direction.iter().map(|d| d * d).sum::<Real>().sqrt().max(1e-6);
+                    for (p, d) in entity.pose.position.iter_mut().zip(direction.iter()) {
+                        *p += epsilon * d / norm;
+                    }
+                }
+            }
+
+            let mut samples: Vec<Vec<(Real, Real)>> = vec![Vec::new(); ids.len()];
+            let mut diverged_at: Vec<Option<Real>> = vec![None; ids.len()];
+            for (step, reference_positions) in reference_path.iter().enumerate() {
+                perturbed.step();
+                let t = (step + 1) as Real * dt;
+                for (i, position) in positions(&perturbed).iter().enumerate() {
+                    let (Some(a), Some(b)) = (position, &reference_positions[i]) else {
+                        continue;
+                    };
+                    let d = periodic_distance(a, b, &geometry);
+                    if d < saturation && diverged_at[i].is_none() {
+                        samples[i].push((t, d));
+                    }
+                    // Coinciding trajectories never diverge, even for ε = 0
+                    if d > 0.0 && d >= DIVERGENCE_FACTOR * epsilon && diverged_at[i].is_none() {
+                        diverged_at[i] = Some(t);
+                    }
+                }
+            }
+
+            for i in 0..ids.len() {
+                rate_sums[i] += fit_exponential_rate(&samples[i], epsilon);
+                divergence_time_sum += diverged_at[i].unwrap_or(horizon as Real * dt);
+                divergence_samples += 1;
+            }
+        }
+
+        let runs = n_perturbations.max(1) as Real;
+        let lyapunov_exponents: Vec<Real> = rate_sums.iter().map(|s| s / runs).collect();
+        let max_exponent = lyapunov_exponents.iter().cloned().fold(Real::MIN, Real::max);
+
+        let report = SensitivityReport {
+            mean_divergence_time: if divergence_samples > 0 {
+                divergence_time_sum / divergence_samples as Real
+            } else {
+                0.0
+            },
+            chaos_indicator: !lyapunov_exponents.is_empty() && max_exponent > 0.0,
+            lyapunov_exponents,
+        };
+        self.results.sensitivity = Some(report.clone());
+        report
+    }
+
+    /// Pairwise mutual attention matrix.
+    ///
+    /// Rows and columns are ordered as in `sorted_entity_ids()`; the
+    /// diagonal is zero.
+    ///
+    /// # Returns
+    /// Symmetric matrix of `Entity::attention_strength` values
+    pub fn attention_matrix(&self) -> Vec<Vec<Real>> {
+        let entities: Vec<_> = self
+            .sorted_entity_ids()
+            .iter()
+            .filter_map(|id| self.entities.get_entity(*id))
+            .collect();
+        let n = entities.len();
+        let mut matrix = vec![vec![0.0; n]; n];
+        for i in 0..n {
+            for j in (i + 1)..n {
+                let strength = entities[i].attention_strength(entities[j]);
+                matrix[i][j] = strength;
+                matrix[j][i] = strength;
+            }
+        }
+        matrix
+    }
+
+    /// Fraction of entities reached by a broadcast from the most active one.
+    ///
+    /// Runs a breadth-first search from the entity with the highest
+    /// affective strength (lowest id on ties), following attention links
+    /// with strength above `threshold`.
+    ///
+    /// # Arguments
+    /// * `threshold` - Minimum attention strength for a link to carry the broadcast
+    ///
+    /// # Returns
+    /// Reachable fraction in [0, 1] (the source counts as reached), or 0 with no entities
+    pub fn compute_workspace_integration(&self, threshold: Real) -> Real {
+        let ids = self.sorted_entity_ids();
+        let n = ids.len();
+        if n == 0 {
+            return 0.0;
+        }
+
+        let source = ids
+            .iter()
+            .enumerate()
+            .filter_map(|(i, id)| self.entities.get_entity(*id).map(|e| (i, e.affective_strength())))
+            .fold((0, Real::MIN), |best, (i, s)| if s > best.1 { (i, s) } else { best })
+            .0;
+
+        let attention = self.attention_matrix();
+        let mut reached = vec![false; n];
+        reached[source] = true;
+        let mut queue = VecDeque::from([source]);
+        while let Some(i) = queue.pop_front() {
+            for j in 0..n {
+                if !reached[j] && attention[i][j] > threshold {
+                    reached[j] = true;
+                    queue.push_back(j);
+                }
+            }
+        }
+
+        reached.iter().filter(|&&r| r).count() as Real / n as Real
+    }
+
+    /// Rank entities by influence on the social interaction graph.
+    ///
+    /// Edge weights are the cumulative attraction between each pair over
+    /// the recorded steps; see the module docs for the PageRank model.
+    ///
+    /// # Returns
+    /// (entity id, PageRank score) sorted by descending score (ascending
+    /// id on ties); scores sum to 1
+    pub fn detect_emergent_leaders(&self) -> Vec<(EntityId, Real)> {
+        let ids = self.sorted_entity_ids();
+        let index: HashMap<EntityId, usize> = ids.iter().enumerate().map(|(i, id)| (*id, i)).collect();
+        let mut weights = vec![vec![0.0; ids.len()]; ids.len()];
+        for ((a, b), weight) in &self.interaction_weights {
+            if let (Some(&i), Some(&j)) = (index.get(a), index.get(b)) {
+                weights[i][j] += weight;
+                weights[j][i] += weight;
+            }
+        }
+
+        let scores = pagerank(&weights, PAGERANK_DAMPING, PAGERANK_ITERATIONS);
+        let mut ranked: Vec<(EntityId, Real)> = ids.into_iter().zip(scores).collect();
+        ranked.sort_by(|a, b| b.1.total_cmp(&a.1).then(a.0.cmp(&b.0)));
+        ranked
+    }
+
+    /// Algebraic connectivity of the attention-weighted social graph.
+    ///
+    /// Uses `attention_matrix()` as edge weights; see the module docs.
+    ///
+    /// # Arguments
+    /// * `k` - Power-iteration rounds (`SPECTRAL_ITERATIONS` is a good default)
+    ///
+    /// # Returns
+    /// `λ₂ ≥ 0`; 0 when the graph is disconnected or has fewer than two entities
+    pub fn compute_integration_information_spectral(&self, k: usize) -> Real {
+        algebraic_connectivity(&self.attention_matrix(), k)
+    }
+
+    /// Total variation distance between the state distributions at two steps.
+    /// 
+    /// Positions and velocities are taken from the recorded step snapshots
+    /// and discretized to a `TVD_BINS`-per-axis grid.
+    /// 
+    /// # Arguments
+    /// * `step_a` - First step number
+    /// * `step_b` - Second step number
+    /// 
+    /// # Returns
+    /// Distance in [0, 1], or 0 if either step was not recorded
+    pub fn compute_total_variation_distance(&self, step_a: u64, step_b: u64) -> Real {
+        let find = |n: u64| self.results.steps.iter().find(|s| s.step_number == n);
+        match (find(step_a), find(step_b)) {
+            (Some(a), Some(b)) => total_variation_distance(a, b, &self.config.geometry.bounds),
+            _ => 0.0,
+        }
+    }
+
+    /// Total variation distance between each recorded step and the one
+    /// `stride` steps earlier.
+    /// 
+    /// # Returns
+    /// (step number, distance) pairs in step order
+    pub fn total_variation_series(&self, stride: u64) -> Vec<(u64, Real)> {
+        let stride = stride.max(1);
+        let steps = &self.results.steps;
+        let mut series = Vec::new();
+        for step in steps {
+            if step.step_number < stride {
+                continue;
+            }
+            let target = step.step_number - stride;
+            if let Some(previous) = steps.iter().find(|s| s.step_number == target) {
+                series.push((
+                    step.step_number,
+                    total_variation_distance(previous, step, &self.config.geometry.bounds),
+                ));
+            }
+        }
+        series
+    }
+
+    /// Recurrence quantification of one metric's per-step time series.
+    /// 
+    /// # Arguments
+    /// * `metric` - Metric name as in [`Metrics::to_map`] (e.g. `"average_essence"`)
+    /// * `threshold` - Recurrence radius ε in the metric's units
+    /// 
+    /// # Returns
+    /// RQA measures (all zero for an unknown metric or a too-short run)
+    pub fn compute_recurrence_quantification(&self, metric: &str, threshold: Real) -> RQA {
+        let series: Vec<Real> = self
+            .metrics_history
+            .iter()
+            .filter_map(|m| m.to_map().get(metric).copied())
+            .collect();
+        recurrence_quantification(&series, threshold)
+    }
+
+    /// RQA of every core metric with a threshold of
+    /// `RQA_THRESHOLD_FRACTION` × the series' standard deviation (at least
+    /// `Real::EPSILON`, so constant series count as fully recurrent).
+    /// 
+    /// # Returns
+    /// (metric name, threshold, measures) for each core metric
+    pub fn core_recurrence_quantification(&self) -> Vec<(String, Real, RQA)> {
+        Metrics::CORE_NAMES
+            .iter()
+            .enumerate()
+            .map(|(k, name)| {
+                let series: Vec<Real> = self.metrics_history.iter().map(|m| m.core_vector()[k]).collect();
+                let n = series.len().max(1) as Real;
+                let mean = series.iter().sum::<Real>() / n;
+                let std = (series.iter().map(|x| (x - mean).powi(2)).sum::<Real>() / n).sqrt();
+                let threshold = (RQA_THRESHOLD_FRACTION * std).max(Real::EPSILON);
+                (name.to_string(), threshold, recurrence_quantification(&series, threshold))
+            })
+            .collect()
+    }
+
+    /// Temporal self-similarity matrix of the metrics at every recorded step.
+    /// 
+    /// # Returns
+    /// `S[i][j]` = cosine similarity of the eight core metrics at recorded
+    /// steps `i` and `j` (empty if nothing was recorded)
+    pub fn compute_temporal_self_similarity_matrix(&self) -> Vec<Vec<Real>> {
+        let metrics: Vec<&Metrics> = self.results.steps.iter().map(|s| &s.metrics).collect();
+        temporal_self_similarity(&metrics)
+    }
+
+    /// Self-similarity matrix over at most `max_size` evenly spaced
+    /// recorded steps, for reporting.
+    /// 
+    /// # Returns
+    /// (step numbers, matrix) for the sampled steps
+    pub fn sampled_self_similarity(&self, max_size: usize) -> (Vec<u64>, Vec<Vec<Real>>) {
+        let steps = &self.results.steps;
+        let stride = steps.len().div_ceil(max_size.max(1)).max(1);
+        let sampled: Vec<&SimulationStep> = steps.iter().step_by(stride).collect();
+        let metrics: Vec<&Metrics> = sampled.iter().map(|s| &s.metrics).collect();
+        (
+            sampled.iter().map(|s| s.step_number).collect(),
+            temporal_self_similarity(&metrics),
+        )
+    }
+}
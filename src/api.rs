@@ -0,0 +1,360 @@
+//! API module: HTTP control interface for running simulations as a service.
+//!
+//! Enabled by the `http` feature. An [`ApiServer`] keeps a registry of runs
+//! keyed by UUID; each run executes on its own background thread and can be
+//! polled or cancelled while it progresses.
+//!
+//! ## Endpoints
+//!
+//! | Method | Path | Response |
+//! |--------|------|----------|
+//! | `POST` | `/runs` | Start a run from a `SimulationConfig` JSON body → `201 {"id", "status"}` |
+//! | `GET` | `/runs` | Ids and statuses of all runs |
+//! | `GET` | `/runs/{id}` | `{"id", "status", "step", "total_steps", "metrics", "done"}` |
+//! | `GET` | `/runs/{id}/results` | Summary of a completed run; `?full=true` for the full results |
+//! | `DELETE` | `/runs/{id}` | Request cancellation → `202` with the current status |
+//!
+//! Errors are `{"error": "..."}` with status 400 (bad config), 404 (unknown
+//! run), 409 (results not available yet) or 429 (too many active runs).
+//! `status` is one of `running`, `completed`, `cancelled` or `failed`.
+//!
+//! The active-run limit is checked before any work is done for a request;
+//! the simulation is then built on the run's own thread, so a large
+//! population never blocks the server. Configuration errors found while
+//! building (e.g. an unknown plugin) mark the run `failed` with the reason.
+//!
+//! Cancellation is cooperative: the run stops before its next step.
+//!
+//! Finished runs are kept for polling until more than
+//! [`DEFAULT_MAX_FINISHED_RUNS`] (see [`ApiServer::with_max_finished_runs`])
+//! have accumulated; the oldest are then evicted as new runs start.
+//!
+//! ## Author
+//! Ayomide I. Daniels (Morningstar)
+
+use crate::real::Real;
+use crate::config::SimulationConfig;
+use crate::metrics::Metrics;
+use crate::results::SimulationResults;
+use crate::simulation::Simulation;
+use serde::Serialize;
+use serde_json::json;
+use std::collections::HashMap;
+use std::io;
+use std::net::SocketAddr;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::Instant;
+use tiny_http::{Header, Method, Request, Response, Server};
+use uuid::Uuid;
+
+/// Default limit on simultaneously running simulations.
+pub const DEFAULT_MAX_ACTIVE_RUNS: usize = 4;
+
+/// Default number of finished runs kept in the registry.
+pub const DEFAULT_MAX_FINISHED_RUNS: usize = 64;
+
+/// Lifecycle state of a run.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum RunStatus {
+    Running,
+    Completed,
+    Cancelled,
+    Failed,
+}
+
+/// Shared progress of one run, updated by its worker thread.
+struct RunProgress {
+    status: RunStatus,
+    step: u64,
+    total_steps: u32,
+    metrics: Option<Metrics>,
+    results: Option<SimulationResults>,
+    error: Option<String>,
+}
+
+/// Registry entry for a run.
+struct RunHandle {
+    progress: Arc<Mutex<RunProgress>>,
+    stop: Arc<AtomicBool>,
+    /// When the run was started, to evict the oldest finished runs first.
+    started: Instant,
+}
+
+impl RunHandle {
+    /// Whether the run is still executing.
+    fn is_running(&self) -> bool {
+        self.progress.lock().is_ok_and(|p| p.status == RunStatus::Running)
+    }
+}
+
+/// Consciousness verdict returned by `GET /runs/{id}/results`.
+#[derive(Serialize)]
+struct RunSummary<'a> {
+    id: String,
+    steps: u64,
+    consciousness_score: Real,
+    consciousness_achieved: bool,
+    passed_metrics: &'a [String],
+    failed_metrics: &'a [String],
+    narrative: String,
+}
+
+/// HTTP server managing a registry of simulation runs.
+pub struct ApiServer {
+    server: Server,
+    runs: Mutex<HashMap<Uuid, RunHandle>>,
+    max_active_runs: usize,
+    max_finished_runs: usize,
+}
+
+impl ApiServer {
+    /// Bind to `addr`.
+    ///
+    /// # Arguments
+    /// * `addr` - Listen address, e.g. `"0.0.0.0:8080"` (port 0 picks a free port)
+    /// * `max_active_runs` - Runs allowed to execute at once
+    ///
+    /// # Returns
+    /// The bound server (call [`ApiServer::serve`] to handle requests)
+    pub fn bind(addr: &str, max_active_runs: usize) -> io::Result<Self> {
+        let server = Server::http(addr).map_err(io::Error::other)?;
+        Ok(ApiServer {
+            server,
+            runs: Mutex::new(HashMap::new()),
+            max_active_runs: max_active_runs.max(1),
+            max_finished_runs: DEFAULT_MAX_FINISHED_RUNS,
+        })
+    }
+
+    /// Keep at most `max` finished runs (default [`DEFAULT_MAX_FINISHED_RUNS`]).
+    pub fn with_max_finished_runs(mut self, max: usize) -> Self {
+        self.max_finished_runs = max;
+        self
+    }
+
+    /// Address the server is listening on.
+    pub fn local_addr(&self) -> Option<SocketAddr> {
+        self.server.server_addr().to_ip()
+    }
+
+    /// Handle requests until [`ApiServer::shutdown`] is called.
+    pub fn serve(&self) {
+        for request in self.server.incoming_requests() {
+            self.handle(request);
+        }
+    }
+
+    /// Stop [`ApiServer::serve`] and cancel every active run.
+    pub fn shutdown(&self) {
+        if let Ok(runs) = self.runs.lock() {
+            for run in runs.values() {
+                run.stop.store(true, Ordering::Relaxed);
+            }
+        }
+        self.server.unblock();
+    }
+
+    /// Route one request.
+    fn handle(&self, mut request: Request) {
+        let url = request.url().to_string();
+        let (path, query) = url.split_once('?').unwrap_or((url.as_str(), ""));
+        let segments: Vec<&str> = path.split('/').filter(|s| !s.is_empty()).collect();
+
+        let (status, body) = match (request.method(), segments.as_slice()) {
+            (Method::Post, ["runs"]) => {
+                let mut body = String::new();
+                match request.as_reader().read_to_string(&mut body) {
+                    Ok(_) => self.start_run(&body),
+                    Err(e) => error(400, &e.to_string()),
+                }
+            }
+            (Method::Get, ["runs"]) => self.list_runs(),
+            (Method::Get, ["runs", id]) => self.with_run(id, run_status),
+            (Method::Get, ["runs", id, "results"]) => {
+                let full = query.split('&').any(|p| p == "full=true" || p == "full=1");
+                self.with_run(id, |id, run| run_results(id, run, full))
+            }
+            (Method::Delete, ["runs", id]) => self.with_run(id, |id, run| {
+                run.stop.store(true, Ordering::Relaxed);
+                let (_, status) = run_status(id, run);
+                (202, status)
+            }),
+            _ => error(404, "no such endpoint"),
+        };
+
+        let response = Response::from_string(body.to_string())
+            .with_status_code(status)
+            .with_header(Header::from_bytes("Content-Type", "application/json").expect("static header is valid"));
+        let _ = request.respond(response);
+    }
+
+    /// `POST /runs`: check the active-run limit, validate the config and
+    /// start a worker thread that builds and runs the simulation.
+    fn start_run(&self, body: &str) -> (u16, serde_json::Value) {
+        let Ok(mut runs) = self.runs.lock() else {
+            return error(500, "run registry unavailable");
+        };
+        let active = runs.values().filter(|run| run.is_running()).count();
+        if active >= self.max_active_runs {
+            return error(429, &format!("{} runs already active", active));
+        }
+
+        let config: SimulationConfig = match serde_json::from_str(body) {
+            Ok(config) => config,
+            Err(e) => return error(400, &format!("invalid config JSON: {}", e)),
+        };
+        if let Err(e) = config.validate() {
+            return error(400, &e);
+        }
+
+        evict_finished(&mut runs, self.max_finished_runs.saturating_sub(1));
+        let id = Uuid::new_v4();
+        let stop = Arc::new(AtomicBool::new(false));
+        let progress = Arc::new(Mutex::new(RunProgress {
+            status: RunStatus::Running,
+            step: 0,
+            total_steps: config.simulation.num_steps,
+            metrics: None,
+            results: None,
+            error: None,
+        }));
+        let (worker_progress, worker_stop) = (Arc::clone(&progress), Arc::clone(&stop));
+        thread::spawn(move || execute_run(config, worker_stop, worker_progress));
+        runs.insert(id, RunHandle { progress, stop, started: Instant::now() });
+
+        (201, json!({ "id": id.to_string(), "status": RunStatus::Running }))
+    }
+
+    /// `GET /runs`: every run's id and status.
+    fn list_runs(&self) -> (u16, serde_json::Value) {
+        let Ok(runs) = self.runs.lock() else {
+            return error(500, "run registry unavailable");
+        };
+        let list: Vec<serde_json::Value> = runs
+            .iter()
+            .map(|(id, run)| run_status(id, run).1)
+            .collect();
+        (200, json!(list))
+    }
+
+    /// Look up a run by id and apply `f`, or answer 404.
+    fn with_run(
+        &self,
+        id: &str,
+        f: impl FnOnce(&Uuid, &RunHandle) -> (u16, serde_json::Value),
+    ) -> (u16, serde_json::Value) {
+        let Ok(uuid) = Uuid::parse_str(id) else {
+            return error(404, "unknown run id");
+        };
+        let Ok(runs) = self.runs.lock() else {
+            return error(500, "run registry unavailable");
+        };
+        match runs.get(&uuid) {
+            Some(run) => f(&uuid, run),
+            None => error(404, "unknown run id"),
+        }
+    }
+}
+
+/// Drop the oldest finished runs until at most `keep` remain.
+fn evict_finished(runs: &mut HashMap<Uuid, RunHandle>, keep: usize) {
+    let mut finished: Vec<(Instant, Uuid)> =
+        runs.iter().filter(|(_, run)| !run.is_running()).map(|(id, run)| (run.started, *id)).collect();
+    if finished.len() <= keep {
+        return;
+    }
+    finished.sort();
+    for (_, id) in &finished[..finished.len() - keep] {
+        runs.remove(id);
+    }
+}
+
+/// Worker thread body: build the simulation, then step until done or
+/// cancelled, publishing progress.
+fn execute_run(config: SimulationConfig, stop: Arc<AtomicBool>, progress: Arc<Mutex<RunProgress>>) {
+    let outcome = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+        let mut sim = Simulation::new(config)?;
+        sim.set_stop_flag(stop);
+        let total = sim.config.simulation.num_steps as u64;
+        while sim.timestamp < total && !sim.stop_requested() {
+            sim.step();
+            if let Ok(mut p) = progress.lock() {
+                p.step = sim.timestamp;
+                p.metrics = sim.metrics_history.last().cloned();
+            }
+        }
+        if sim.stop_requested() {
+            return Ok(RunStatus::Cancelled);
+        }
+        sim.finalize_results();
+        if let Ok(mut p) = progress.lock() {
+            p.results = Some(sim.results.clone());
+        }
+        Ok(RunStatus::Completed)
+    }));
+
+    if let Ok(mut p) = progress.lock() {
+        match outcome {
+            Ok(Ok(status)) => p.status = status,
+            Ok(Err(e)) => {
+                p.status = RunStatus::Failed;
+                p.error = Some(e);
+            }
+            Err(_) => {
+                p.status = RunStatus::Failed;
+                p.error = Some("simulation panicked".to_string());
+            }
+        }
+    }
+}
+
+/// `GET /runs/{id}` body.
+fn run_status(id: &Uuid, run: &RunHandle) -> (u16, serde_json::Value) {
+    let Ok(p) = run.progress.lock() else {
+        return error(500, "run state unavailable");
+    };
+    (
+        200,
+        json!({
+            "id": id.to_string(),
+            "status": p.status,
+            "step": p.step,
+            "total_steps": p.total_steps,
+            "metrics": p.metrics,
+            "done": p.status != RunStatus::Running,
+            "error": p.error,
+        }),
+    )
+}
+
+/// `GET /runs/{id}/results` body.
+fn run_results(id: &Uuid, run: &RunHandle, full: bool) -> (u16, serde_json::Value) {
+    let Ok(p) = run.progress.lock() else {
+        return error(500, "run state unavailable");
+    };
+    let Some(results) = &p.results else {
+        return error(409, &format!("run is {:?}; results are only available once completed", p.status));
+    };
+    if full {
+        return (200, serde_json::to_value(results).unwrap_or_default());
+    }
+    let analysis = &results.consciousness_analysis;
+    let summary = RunSummary {
+        id: id.to_string(),
+        steps: p.step,
+        consciousness_score: analysis.consciousness_score,
+        consciousness_achieved: analysis.consciousness_achieved,
+        passed_metrics: &analysis.passed_metrics,
+        failed_metrics: &analysis.failed_metrics,
+        narrative: results.narrative_summary(),
+    };
+    (200, serde_json::to_value(&summary).unwrap_or_default())
+}
+
+/// Error response body.
+fn error(status: u16, message: &str) -> (u16, serde_json::Value) {
+    (status, json!({ "error": message }))
+}
@@ -0,0 +1,290 @@
+//! Barnes–Hut module: far-field approximation of the attraction field.
+//!
+//! Summing every kernel term makes one attraction pass over `n` entities
+//! O(n²). An [`AttractionTree`] is a quadtree (2D) or octree (3D) over the
+//! entity positions in which every cell keeps its total weight and
+//! weighted center of mass. A query walks the tree from the root: a cell
+//! of side `s` whose center of mass lies at distance `d` is replaced by a
+//! single kernel term `W·K(d)` when `s / d < θ` (the opening criterion),
+//! and opened otherwise; leaves are summed exactly. Building the tree is
+//! O(n log n) and a query is O(log n) for moderate θ, so a full pass is
+//! O(n log n).
+//!
+//! θ is `AttractionConfig::theta`. θ = 0 never approximates: it sums the
+//! same terms as [`attraction_potential`](crate::attraction::attraction_potential)
+//! and calls [`attention_gradient`], so results are identical to brute
+//! force. Larger θ trades accuracy for speed; around 0.5 is a common
+//! choice. The monopole approximation assumes
+//! non-negative weights; cells holding a negative weight are always opened.
+//!
+//! ## Author
+//! Ayomide I. Daniels (Morningstar)
+
+use crate::attraction::{attention_gradient, compute_kernel, kernel_slope, AttractionConfig};
+use crate::real::Real;
+
+/// Most positions a leaf cell holds before it is split.
+const LEAF_SIZE: usize = 8;
+
+/// Depth limit, reached only by (nearly) coincident positions.
+const MAX_DEPTH: usize = 32;
+
+/// One cell of the tree.
+#[derive(Clone, Debug)]
+struct Cell {
+    /// Side length of the (cubic) cell.
+    size: Real,
+    /// Total weight of the positions inside.
+    mass: Real,
+    /// Whether the cell may be replaced by its center of mass.
+    approximable: bool,
+    /// Range of tree slots (see `AttractionTree::order`) inside the cell.
+    start: usize,
+    end: usize,
+    /// Range of child cells in `AttractionTree::cells` (empty for leaves).
+    children: (usize, usize),
+}
+
+/// Quadtree/octree over weighted entity positions.
+///
+/// Built once per set of positions, then queried for any number of
+/// positions (typically every entity, excluding itself).
+#[derive(Clone, Debug)]
+pub struct AttractionTree {
+    dim: usize,
+    /// Positions and weights as given (missing weights are 1).
+    others: Vec<Vec<Real>>,
+    weights: Vec<Real>,
+    cells: Vec<Cell>,
+    /// Center of mass of each cell, `dim` values per cell.
+    centers: Vec<Real>,
+    /// Original index of the position in each tree slot.
+    order: Vec<usize>,
+    /// Tree slot of each original index.
+    rank: Vec<usize>,
+    /// Positions in tree-slot order, `dim` values per slot.
+    points: Vec<Real>,
+}
+
+impl AttractionTree {
+    /// Build the tree.
+    ///
+    /// # Arguments
+    /// * `others` - Entity positions (all of the same dimensionality)
+    /// * `weights` - Per-entity influence weights (missing entries count as 1)
+    ///
+    /// # Returns
+    /// Tree ready for queries
+    pub fn build(others: &[Vec<Real>], weights: &[Real]) -> Self {
+        let dim = others.first().map_or(0, Vec::len);
+        let weights: Vec<Real> = (0..others.len()).map(|i| weights.get(i).copied().unwrap_or(1.0)).collect();
+        let mut tree = AttractionTree {
+            dim,
+            others: others.to_vec(),
+            weights,
+            cells: Vec::new(),
+            centers: Vec::new(),
+            order: (0..others.len()).collect(),
+            rank: Vec::new(),
+            points: Vec::new(),
+        };
+        if !others.is_empty() {
+            let mut min = others[0].clone();
+            let mut max = others[0].clone();
+            for position in others {
+                for d in 0..dim {
+                    min[d] = min[d].min(position[d]);
+                    max[d] = max[d].max(position[d]);
+                }
+            }
+            let size = (0..dim).map(|d| max[d] - min[d]).fold(0.0, Real::max);
+            let root = tree.new_cell(0, others.len(), if size > 0.0 { size } else { 1.0 });
+            tree.cells.push(root);
+            tree.split(0, &min, 0);
+        }
+
+        tree.rank = vec![0; tree.order.len()];
+        for (slot, &idx) in tree.order.iter().enumerate() {
+            tree.rank[idx] = slot;
+        }
+        tree.points = tree.order.iter().flat_map(|&idx| tree.others[idx].iter().copied()).collect();
+        tree
+    }
+
+    /// Number of positions in the tree.
+    pub fn len(&self) -> usize {
+        self.others.len()
+    }
+
+    /// Whether the tree holds no positions.
+    pub fn is_empty(&self) -> bool {
+        self.others.is_empty()
+    }
+
+    /// Cell over `order[start..end]`, with its aggregate weight and center of mass.
+    fn new_cell(&mut self, start: usize, end: usize, size: Real) -> Cell {
+        let mut mass = 0.0;
+        let mut approximable = true;
+        let mut weighted = vec![0.0; self.dim];
+        let mut plain = vec![0.0; self.dim];
+        for &idx in &self.order[start..end] {
+            let w = self.weights[idx];
+            approximable &= w >= 0.0;
+            mass += w;
+            for d in 0..self.dim {
+                weighted[d] += w * self.others[idx][d];
+                plain[d] += self.others[idx][d];
+            }
+        }
+        approximable &= mass > 0.0;
+        let count = (end - start) as Real;
+        self.centers.extend((0..self.dim).map(|d| if approximable { weighted[d] / mass } else { plain[d] / count }));
+        Cell {
+            size,
+            mass,
+            approximable,
+            start,
+            end,
+            children: (0, 0),
+        }
+    }
+
+    /// Recursively split cell `cell_idx` with lower corner `min` into
+    /// its non-empty orthants.
+    fn split(&mut self, cell_idx: usize, min: &[Real], depth: usize) {
+        let Cell { start, end, size, .. } = self.cells[cell_idx];
+        if end - start <= LEAF_SIZE || depth >= MAX_DEPTH {
+            return;
+        }
+        let half = size / 2.0;
+        let orthant = |position: &[Real]| {
+            (0..position.len()).fold(0usize, |code, d| code | (usize::from(position[d] >= min[d] + half) << d))
+        };
+        let others = &self.others;
+        self.order[start..end].sort_by_key(|&idx| orthant(&others[idx]));
+
+        // One child per run of equal orthant codes
+        let mut runs = Vec::new();
+        let mut run_start = start;
+        while run_start < end {
+            let code = orthant(&self.others[self.order[run_start]]);
+            let mut run_end = run_start + 1;
+            while run_end < end && orthant(&self.others[self.order[run_end]]) == code {
+                run_end += 1;
+            }
+            runs.push((code, run_start, run_end));
+            run_start = run_end;
+        }
+
+        let first_child = self.cells.len();
+        for &(_, run_start, run_end) in &runs {
+            let cell = self.new_cell(run_start, run_end, half);
+            self.cells.push(cell);
+        }
+        self.cells[cell_idx].children = (first_child, self.cells.len());
+        for (offset, &(code, _, _)) in runs.iter().enumerate() {
+            let child_min: Vec<Real> = (0..min.len())
+                .map(|d| if code >> d & 1 == 1 { min[d] + half } else { min[d] })
+                .collect();
+            self.split(first_child + offset, &child_min, depth + 1);
+        }
+    }
+
+    /// Visit the terms of the approximated field at `position`.
+    ///
+    /// Calls `term(weight, center)` once per approximated cell and once per
+    /// exactly summed position, skipping the position `exclude`.
+    fn for_each_term(&self, position: &[Real], exclude: Option<usize>, theta: Real, mut term: impl FnMut(Real, &[Real])) {
+        if self.cells.is_empty() {
+            return;
+        }
+        let excluded_slot = exclude.and_then(|idx| self.rank.get(idx).copied());
+        let mut stack = vec![0];
+        while let Some(cell_idx) = stack.pop() {
+            let cell = &self.cells[cell_idx];
+            let center = &self.centers[cell_idx * self.dim..(cell_idx + 1) * self.dim];
+            let holds_excluded = excluded_slot.is_some_and(|slot| (cell.start..cell.end).contains(&slot));
+            if cell.approximable && !holds_excluded && cell.size < theta * distance(position, center) {
+                term(cell.mass, center);
+            } else if cell.children.0 == cell.children.1 {
+                for slot in cell.start..cell.end {
+                    if Some(slot) != excluded_slot {
+                        term(self.weights[self.order[slot]], &self.points[slot * self.dim..(slot + 1) * self.dim]);
+                    }
+                }
+            } else {
+                stack.extend(cell.children.0..cell.children.1);
+            }
+        }
+    }
+
+    /// Approximate attraction potential at `position`.
+    ///
+    /// # Arguments
+    /// * `position` - Position to evaluate the potential at
+    /// * `exclude` - Index of a position to leave out (the querying entity)
+    /// * `kernel_config` - Kernel configuration; `theta` sets the accuracy
+    ///
+    /// # Returns
+    /// Scalar potential, equal to
+    /// [`attraction_potential`](crate::attraction::attraction_potential) over the other
+    /// positions when `theta` is 0
+    pub fn potential(&self, position: &[Real], exclude: Option<usize>, kernel_config: &AttractionConfig) -> Real {
+        if kernel_config.theta <= 0.0 {
+            let mut potential = 0.0;
+            for (idx, other) in self.others.iter().enumerate() {
+                if Some(idx) != exclude {
+                    let kernel_val = compute_kernel(&kernel_config.kernel, distance(position, other), kernel_config.sigma);
+                    potential += self.weights[idx] * kernel_val;
+                }
+            }
+            return potential;
+        }
+
+        let mut potential = 0.0;
+        self.for_each_term(position, exclude, kernel_config.theta, |weight, center| {
+            potential += weight * compute_kernel(&kernel_config.kernel, distance(position, center), kernel_config.sigma);
+        });
+        potential
+    }
+
+    /// Approximate attention gradient at `position`.
+    ///
+    /// Far cells contribute the analytic gradient of their monopole term.
+    ///
+    /// # Arguments
+    /// * `position` - Position to compute the gradient at
+    /// * `exclude` - Index of a position to leave out (the querying entity)
+    /// * `kernel_config` - Kernel configuration; `theta` sets the accuracy
+    ///
+    /// # Returns
+    /// Negative potential gradient; [`attention_gradient`] over the other
+    /// positions when `theta` is 0
+    pub fn gradient(&self, position: &[Real], exclude: Option<usize>, kernel_config: &AttractionConfig) -> Vec<Real> {
+        if kernel_config.theta <= 0.0 {
+            let (others, weights): (Vec<Vec<Real>>, Vec<Real>) = self
+                .others
+                .iter()
+                .zip(&self.weights)
+                .enumerate()
+                .filter(|(idx, _)| Some(*idx) != exclude)
+                .map(|(_, (other, &weight))| (other.clone(), weight))
+                .unzip();
+            return attention_gradient(position, &others, &weights, kernel_config);
+        }
+
+        let mut gradient = vec![0.0; position.len()];
+        self.for_each_term(position, exclude, kernel_config.theta, |weight, center| {
+            let slope = kernel_slope(&kernel_config.kernel, distance(position, center), kernel_config.sigma);
+            for (g, (p, c)) in gradient.iter_mut().zip(position.iter().zip(center)) {
+                *g -= weight * slope * (p - c);
+            }
+        });
+        gradient
+    }
+}
+
+/// Euclidean distance, summed in dimension order like `attraction_potential`.
+fn distance(a: &[Real], b: &[Real]) -> Real {
+    a.iter().zip(b).map(|(x, y)| (x - y).powi(2)).sum::<Real>().sqrt()
+}
@@ -0,0 +1,356 @@
+//! C FFI: a stable C ABI for embedding the simulation in other runtimes.
+//!
+//! Configurations and simulations are opaque heap objects owned by the
+//! caller, who must release them with the matching `sc_free_*` function.
+//! Every fallible function returns an `SC_*` status code; panics are caught
+//! at the boundary and reported as `SC_ERR_PANIC`, and the message of the
+//! most recent error is available from `sc_last_error()`.
+//!
+//! The C header `include/synthetic_consciousness.h` is generated by
+//! cbindgen from this module when the `ffi` feature is enabled.
+//!
+//! ```c
+//! ScConfig *config = sc_config_default_2d();
+//! ScSimulation *sim = NULL;
+//! if (sc_simulation_new(config, &sim) == SC_OK) {
+//!     sc_simulation_run(sim);
+//!     size_t count = 0, dim = 0;
+//!     sc_get_entity_count(sim, &count);
+//!     sc_get_dimension(sim, &dim);
+//!     float *positions = malloc(count * dim * sizeof(float));
+//!     sc_get_positions(sim, positions, count * dim);
+//!     free(positions);
+//!     sc_free_simulation(sim);
+//! }
+//! sc_free_config(config);
+//! ```
+//!
+//! ## Author
+//! Ayomide I. Daniels (Morningstar)
+
+use crate::config::SimulationConfig;
+use crate::metrics::Metrics;
+use crate::real::to_f32;
+use crate::simulation::Simulation;
+use std::cell::RefCell;
+use std::ffi::{c_char, CStr, CString};
+use std::panic::{catch_unwind, AssertUnwindSafe};
+
+/// Success.
+pub const SC_OK: i32 = 0;
+/// A required pointer argument was null.
+pub const SC_ERR_NULL_POINTER: i32 = -1;
+/// The configuration is invalid or could not be parsed.
+pub const SC_ERR_INVALID_CONFIG: i32 = -2;
+/// A file could not be read or written.
+pub const SC_ERR_IO: i32 = -3;
+/// A string argument was not valid UTF-8.
+pub const SC_ERR_INVALID_STRING: i32 = -4;
+/// The output buffer is too small.
+pub const SC_ERR_BUFFER_TOO_SMALL: i32 = -5;
+/// No data is available yet (e.g. metrics before the first step).
+pub const SC_ERR_NO_DATA: i32 = -6;
+/// A Rust panic was caught at the boundary.
+pub const SC_ERR_PANIC: i32 = -7;
+
+/// Opaque simulation configuration.
+pub struct ScConfig(SimulationConfig);
+
+/// Opaque simulation.
+pub struct ScSimulation(Simulation);
+
+/// Consciousness metrics snapshot.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default)]
+pub struct ScMetrics {
+    pub timestamp: u64,
+    pub attention_entropy: f32,
+    pub memory_diversity: f32,
+    pub velocity_stability: f32,
+    pub identity_coherence: f32,
+    pub cluster_stability: f32,
+    pub affective_strength: f32,
+    pub essence_trajectory: f32,
+    pub average_essence: f32,
+    pub phase_synchrony: f32,
+    pub mean_energy: f32,
+    pub role_stability: f32,
+}
+
+impl From<&Metrics> for ScMetrics {
+    fn from(m: &Metrics) -> Self {
+        ScMetrics {
+            timestamp: m.timestamp,
+            attention_entropy: to_f32(m.attention_entropy),
+            memory_diversity: to_f32(m.memory_diversity),
+            velocity_stability: to_f32(m.velocity_stability),
+            identity_coherence: to_f32(m.identity_coherence),
+            cluster_stability: to_f32(m.cluster_stability),
+            affective_strength: to_f32(m.affective_strength),
+            essence_trajectory: to_f32(m.essence_trajectory),
+            average_essence: to_f32(m.average_essence),
+            phase_synchrony: to_f32(m.phase_synchrony),
+            mean_energy: to_f32(m.mean_energy),
+            role_stability: to_f32(m.role_stability),
+        }
+    }
+}
+
+thread_local! {
+    static LAST_ERROR: RefCell<CString> = RefCell::new(CString::default());
+}
+
+/// Record an error message for `sc_last_error()` and return its code.
+fn fail(code: i32, message: impl Into<String>) -> i32 {
+    let message = CString::new(message.into().replace('\0', " ")).unwrap_or_default();
+    LAST_ERROR.with(|e| *e.borrow_mut() = message);
+    code
+}
+
+/// Run `f`, converting a panic into `SC_ERR_PANIC`.
+fn guard(f: impl FnOnce() -> i32) -> i32 {
+    match catch_unwind(AssertUnwindSafe(f)) {
+        Ok(code) => code,
+        Err(payload) => {
+            let message = payload
+                .downcast_ref::<&str>()
+                .map(|s| s.to_string())
+                .or_else(|| payload.downcast_ref::<String>().cloned())
+                .unwrap_or_else(|| "unknown panic".to_string());
+            fail(SC_ERR_PANIC, format!("panic: {}", message))
+        }
+    }
+}
+
+/// Message of the most recent error on this thread.
+///
+/// The string is owned by the library and valid until the next failing
+/// call on the same thread. It is empty if no error has occurred.
+#[no_mangle]
+pub extern "C" fn sc_last_error() -> *const c_char {
+    LAST_ERROR.with(|e| e.borrow().as_ptr())
+}
+
+/// Default 2D configuration. Release with `sc_free_config`.
+#[no_mangle]
+pub extern "C" fn sc_config_default_2d() -> *mut ScConfig {
+    Box::into_raw(Box::new(ScConfig(SimulationConfig::default_2d())))
+}
+
+/// Default 3D configuration. Release with `sc_free_config`.
+#[no_mangle]
+pub extern "C" fn sc_config_default_3d() -> *mut ScConfig {
+    Box::into_raw(Box::new(ScConfig(SimulationConfig::default_3d())))
+}
+
+/// Load a configuration from a TOML file.
+///
+/// # Safety
+/// `path` must be a NUL-terminated string and `out` a valid pointer.
+#[no_mangle]
+pub unsafe extern "C" fn sc_config_from_toml(path: *const c_char, out: *mut *mut ScConfig) -> i32 {
+    guard(|| {
+        if path.is_null() || out.is_null() {
+            return fail(SC_ERR_NULL_POINTER, "null argument");
+        }
+        let Ok(path) = CStr::from_ptr(path).to_str() else {
+            return fail(SC_ERR_INVALID_STRING, "path is not valid UTF-8");
+        };
+        match SimulationConfig::from_toml(path) {
+            Ok(config) => {
+                *out = Box::into_raw(Box::new(ScConfig(config)));
+                SC_OK
+            }
+            Err(e) => fail(SC_ERR_INVALID_CONFIG, e.to_string()),
+        }
+    })
+}
+
+/// Release a configuration. Null is ignored.
+///
+/// # Safety
+/// `config` must come from this library and not be used afterwards.
+#[no_mangle]
+pub unsafe extern "C" fn sc_free_config(config: *mut ScConfig) {
+    if !config.is_null() {
+        drop(Box::from_raw(config));
+    }
+}
+
+/// Create a simulation from a configuration (which is copied).
+///
+/// # Safety
+/// `config` and `out` must be valid pointers.
+#[no_mangle]
+pub unsafe extern "C" fn sc_simulation_new(config: *const ScConfig, out: *mut *mut ScSimulation) -> i32 {
+    guard(|| {
+        if config.is_null() || out.is_null() {
+            return fail(SC_ERR_NULL_POINTER, "null argument");
+        }
+        match Simulation::new((*config).0.clone()) {
+            Ok(sim) => {
+                *out = Box::into_raw(Box::new(ScSimulation(sim)));
+                SC_OK
+            }
+            Err(e) => fail(SC_ERR_INVALID_CONFIG, e),
+        }
+    })
+}
+
+/// Release a simulation. Null is ignored.
+///
+/// # Safety
+/// `sim` must come from this library and not be used afterwards.
+#[no_mangle]
+pub unsafe extern "C" fn sc_free_simulation(sim: *mut ScSimulation) {
+    if !sim.is_null() {
+        drop(Box::from_raw(sim));
+    }
+}
+
+/// Advance the simulation by `steps` steps.
+///
+/// # Safety
+/// `sim` must be a valid simulation pointer.
+#[no_mangle]
+pub unsafe extern "C" fn sc_simulation_step(sim: *mut ScSimulation, steps: u32) -> i32 {
+    guard(|| {
+        let Some(sim) = sim.as_mut() else {
+            return fail(SC_ERR_NULL_POINTER, "null simulation");
+        };
+        for _ in 0..steps {
+            sim.0.step();
+        }
+        SC_OK
+    })
+}
+
+/// Run the configured number of steps and finalize the results.
+///
+/// # Safety
+/// `sim` must be a valid simulation pointer.
+#[no_mangle]
+pub unsafe extern "C" fn sc_simulation_run(sim: *mut ScSimulation) -> i32 {
+    guard(|| {
+        let Some(sim) = sim.as_mut() else {
+            return fail(SC_ERR_NULL_POINTER, "null simulation");
+        };
+        sim.0.run();
+        sim.0.finalize_results();
+        SC_OK
+    })
+}
+
+/// Number of live entities.
+///
+/// # Safety
+/// `sim` and `out` must be valid pointers.
+#[no_mangle]
+pub unsafe extern "C" fn sc_get_entity_count(sim: *const ScSimulation, out: *mut usize) -> i32 {
+    guard(|| {
+        let (Some(sim), false) = (sim.as_ref(), out.is_null()) else {
+            return fail(SC_ERR_NULL_POINTER, "null argument");
+        };
+        *out = sim.0.entities.count();
+        SC_OK
+    })
+}
+
+/// Spatial dimensionality of the simulation.
+///
+/// # Safety
+/// `sim` and `out` must be valid pointers.
+#[no_mangle]
+pub unsafe extern "C" fn sc_get_dimension(sim: *const ScSimulation, out: *mut usize) -> i32 {
+    guard(|| {
+        let (Some(sim), false) = (sim.as_ref(), out.is_null()) else {
+            return fail(SC_ERR_NULL_POINTER, "null argument");
+        };
+        *out = sim.0.config.geometry.dimension;
+        SC_OK
+    })
+}
+
+/// Copy entity positions, flattened in entity id order, into `out_ptr`.
+///
+/// Needs room for `entity_count * dimension` floats; returns
+/// `SC_ERR_BUFFER_TOO_SMALL` without writing anything otherwise.
+///
+/// # Safety
+/// `out_ptr` must point to at least `len` writable floats.
+#[no_mangle]
+pub unsafe extern "C" fn sc_get_positions(sim: *const ScSimulation, out_ptr: *mut f32, len: usize) -> i32 {
+    guard(|| {
+        let (Some(sim), false) = (sim.as_ref(), out_ptr.is_null()) else {
+            return fail(SC_ERR_NULL_POINTER, "null argument");
+        };
+        let entities = sim.0.entities.all_entities();
+        let needed: usize = entities.iter().map(|e| e.pose.position.len()).sum();
+        if len < needed {
+            return fail(
+                SC_ERR_BUFFER_TOO_SMALL,
+                format!("positions need {} floats, buffer holds {}", needed, len),
+            );
+        }
+        let out = std::slice::from_raw_parts_mut(out_ptr, needed);
+        for (slot, value) in out.iter_mut().zip(entities.iter().flat_map(|e| e.pose.position.iter())) {
+            *slot = to_f32(*value);
+        }
+        SC_OK
+    })
+}
+
+/// Metrics of the most recent step.
+///
+/// # Safety
+/// `sim` and `out` must be valid pointers.
+#[no_mangle]
+pub unsafe extern "C" fn sc_get_metrics(sim: *const ScSimulation, out: *mut ScMetrics) -> i32 {
+    guard(|| {
+        let (Some(sim), false) = (sim.as_ref(), out.is_null()) else {
+            return fail(SC_ERR_NULL_POINTER, "null argument");
+        };
+        match sim.0.metrics_history.last() {
+            Some(metrics) => {
+                *out = ScMetrics::from(metrics);
+                SC_OK
+            }
+            None => fail(SC_ERR_NO_DATA, "no step has been run"),
+        }
+    })
+}
+
+/// Consciousness score of the most recent finalization (0 before).
+///
+/// # Safety
+/// `sim` and `out` must be valid pointers.
+#[no_mangle]
+pub unsafe extern "C" fn sc_get_consciousness_score(sim: *const ScSimulation, out: *mut f32) -> i32 {
+    guard(|| {
+        let (Some(sim), false) = (sim.as_ref(), out.is_null()) else {
+            return fail(SC_ERR_NULL_POINTER, "null argument");
+        };
+        *out = to_f32(sim.0.consciousness_score());
+        SC_OK
+    })
+}
+
+/// Write the metrics history as CSV.
+///
+/// # Safety
+/// `sim` must be valid and `path` NUL-terminated.
+#[no_mangle]
+pub unsafe extern "C" fn sc_export_metrics_csv(sim: *const ScSimulation, path: *const c_char) -> i32 {
+    guard(|| {
+        let (Some(sim), false) = (sim.as_ref(), path.is_null()) else {
+            return fail(SC_ERR_NULL_POINTER, "null argument");
+        };
+        let Ok(path) = CStr::from_ptr(path).to_str() else {
+            return fail(SC_ERR_INVALID_STRING, "path is not valid UTF-8");
+        };
+        match sim.0.export_metrics_csv(path) {
+            Ok(()) => SC_OK,
+            Err(e) => fail(SC_ERR_IO, e.to_string()),
+        }
+    })
+}
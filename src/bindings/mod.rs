@@ -0,0 +1,22 @@
+//! Bindings module: foreign-language interfaces to the simulation.
+//!
+//! Each binding lives behind its own cargo feature so the core crate keeps
+//! no extra dependencies by default:
+//! - **python** (`python` feature): PyO3 extension module for scripting
+//!   experiments from Python and notebooks
+//! - **ffi** (`ffi` feature): Stable C ABI with a cbindgen-generated header
+//!   for embedding in C/C++ tools
+//! - **wasm** (`wasm` feature): wasm-bindgen API for running the core in the
+//!   browser; build with `--no-default-features` to drop the native GUI
+//!
+//! ## Author
+//! Ayomide I. Daniels (Morningstar)
+
+#[cfg(feature = "python")]
+pub mod python;
+
+#[cfg(feature = "ffi")]
+pub mod ffi;
+
+#[cfg(feature = "wasm")]
+pub mod wasm;
@@ -0,0 +1,488 @@
+//! Python bindings: a PyO3 extension module for scripting experiments.
+//!
+//! Build with maturin (`maturin develop --features python`) and import as
+//! `synthetic_consciousness`:
+//!
+//! ```python
+//! import synthetic_consciousness as sc
+//!
+//! config = sc.SimulationConfig.default_2d()
+//! config.num_steps = 200
+//! config.set("dynamics.damping", 0.95)
+//! sim = sc.Simulation(config)
+//! summary = sim.run()
+//! history = sim.metrics_history()   # list of dicts
+//! columns = sim.metrics_columns()   # dict of lists, e.g. for numpy/pandas
+//! ```
+//!
+//! ## Exposed Classes
+//!
+//! - **SimulationConfig**: Presets, TOML/JSON I/O, and dotted-key `get`/`set`
+//! - **Simulation**: Stepping, running, metrics, CSV export and (with the
+//!   `arrow` feature) `metrics_arrow()` as a pyarrow RecordBatch
+//! - **Metrics**: One metrics snapshot
+//! - **SimulationSummary**: Consciousness verdict of a finalized run
+//!
+//! Invalid configurations raise `ValueError`; file errors raise `IOError`.
+//! Running and stepping release the GIL.
+//!
+//! ## Author
+//! Ayomide I. Daniels (Morningstar)
+
+// The #[pymethods] expansion converts PyErr into itself
+#![allow(clippy::useless_conversion)]
+
+use crate::real::Real;
+use crate::config::SimulationConfig;
+use crate::metrics::Metrics;
+use crate::simulation::Simulation;
+use pyo3::exceptions::{PyIOError, PyKeyError, PyTypeError, PyValueError};
+use pyo3::prelude::*;
+use pyo3::types::{PyBool, PyDict, PyFloat, PyList, PyString};
+use std::collections::HashMap;
+
+/// Simulation configuration.
+#[pyclass(name = "SimulationConfig", module = "synthetic_consciousness")]
+#[derive(Clone)]
+pub struct PyConfig {
+    inner: SimulationConfig,
+}
+
+#[pymethods]
+impl PyConfig {
+    /// Default 2D configuration.
+    #[staticmethod]
+    fn default_2d() -> Self {
+        PyConfig {
+            inner: SimulationConfig::default_2d(),
+        }
+    }
+
+    /// Default 3D configuration.
+    #[staticmethod]
+    fn default_3d() -> Self {
+        PyConfig {
+            inner: SimulationConfig::default_3d(),
+        }
+    }
+
+    /// Named preset from the preset library.
+    #[staticmethod]
+    fn preset(name: &str) -> PyResult<Self> {
+        SimulationConfig::preset_library()
+            .remove(name)
+            .map(|inner| PyConfig { inner })
+            .ok_or_else(|| PyKeyError::new_err(format!("unknown preset '{}'", name)))
+    }
+
+    /// Load a configuration from a TOML file.
+    #[staticmethod]
+    fn from_toml(path: &str) -> PyResult<Self> {
+        let inner = SimulationConfig::from_toml(path)
+            .map_err(|e| PyValueError::new_err(e.to_string()))?;
+        Ok(PyConfig { inner })
+    }
+
+    /// Parse a configuration from a JSON string.
+    #[staticmethod]
+    fn from_json(json: &str) -> PyResult<Self> {
+        let inner = serde_json::from_str(json).map_err(|e| PyValueError::new_err(e.to_string()))?;
+        Ok(PyConfig { inner })
+    }
+
+    /// Write the configuration to a TOML file.
+    fn to_toml(&self, path: &str) -> PyResult<()> {
+        self.inner
+            .to_toml(path)
+            .map_err(|e| PyIOError::new_err(e.to_string()))
+    }
+
+    /// Serialize the configuration to a JSON string.
+    fn to_json(&self) -> PyResult<String> {
+        serde_json::to_string_pretty(&self.inner).map_err(|e| PyValueError::new_err(e.to_string()))
+    }
+
+    /// Check the configuration, raising `ValueError` if it is invalid.
+    fn validate(&self) -> PyResult<()> {
+        self.inner.validate().map_err(PyValueError::new_err)
+    }
+
+    /// Read a field by dotted key, e.g. `"dynamics.dt"`.
+    fn get(&self, py: Python<'_>, key: &str) -> PyResult<PyObject> {
+        let root = serde_json::to_value(&self.inner).map_err(|e| PyValueError::new_err(e.to_string()))?;
+        let mut node = &root;
+        for part in key.split('.') {
+            node = node
+                .get(part)
+                .ok_or_else(|| PyKeyError::new_err(format!("unknown config key '{}'", key)))?;
+        }
+        json_to_py(py, node)
+    }
+
+    /// Set a field by dotted key, e.g. `config.set("dynamics.dt", 0.02)`.
+    ///
+    /// Raises `KeyError` for unknown keys and `ValueError` if the value
+    /// has the wrong type.
+    fn set(&mut self, key: &str, value: &Bound<'_, PyAny>) -> PyResult<()> {
+        let mut root = serde_json::to_value(&self.inner).map_err(|e| PyValueError::new_err(e.to_string()))?;
+        let mut node = &mut root;
+        for part in key.split('.') {
+            node = node
+                .get_mut(part)
+                .ok_or_else(|| PyKeyError::new_err(format!("unknown config key '{}'", key)))?;
+        }
+        *node = py_to_json(value)?;
+        self.inner = serde_json::from_value(root)
+            .map_err(|e| PyValueError::new_err(format!("invalid value for '{}': {}", key, e)))?;
+        Ok(())
+    }
+
+    #[getter]
+    fn num_entities(&self) -> u32 {
+        self.inner.simulation.num_entities
+    }
+
+    #[setter]
+    fn set_num_entities(&mut self, value: u32) {
+        self.inner.simulation.num_entities = value;
+    }
+
+    #[getter]
+    fn num_steps(&self) -> u32 {
+        self.inner.simulation.num_steps
+    }
+
+    #[setter]
+    fn set_num_steps(&mut self, value: u32) {
+        self.inner.simulation.num_steps = value;
+    }
+
+    #[getter]
+    fn dimension(&self) -> usize {
+        self.inner.geometry.dimension
+    }
+
+    #[getter]
+    fn dt(&self) -> Real {
+        self.inner.dynamics.dt
+    }
+
+    #[setter]
+    fn set_dt(&mut self, value: Real) {
+        self.inner.dynamics.dt = value;
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "SimulationConfig(dimension={}, num_entities={}, num_steps={})",
+            self.inner.geometry.dimension, self.inner.simulation.num_entities, self.inner.simulation.num_steps
+        )
+    }
+}
+
+/// One snapshot of the consciousness metrics.
+#[pyclass(name = "Metrics", module = "synthetic_consciousness", get_all)]
+#[derive(Clone)]
+pub struct PyMetrics {
+    timestamp: u64,
+    attention_entropy: Real,
+    memory_diversity: Real,
+    velocity_stability: Real,
+    identity_coherence: Real,
+    cluster_stability: Real,
+    affective_strength: Real,
+    essence_trajectory: Real,
+    average_essence: Real,
+    phase_synchrony: Real,
+    mean_energy: Real,
+    role_stability: Real,
+}
+
+impl From<&Metrics> for PyMetrics {
+    fn from(m: &Metrics) -> Self {
+        PyMetrics {
+            timestamp: m.timestamp,
+            attention_entropy: m.attention_entropy,
+            memory_diversity: m.memory_diversity,
+            velocity_stability: m.velocity_stability,
+            identity_coherence: m.identity_coherence,
+            cluster_stability: m.cluster_stability,
+            affective_strength: m.affective_strength,
+            essence_trajectory: m.essence_trajectory,
+            average_essence: m.average_essence,
+            phase_synchrony: m.phase_synchrony,
+            mean_energy: m.mean_energy,
+            role_stability: m.role_stability,
+        }
+    }
+}
+
+#[pymethods]
+impl PyMetrics {
+    /// Metrics as a `{name: value}` dict.
+    fn to_dict(&self) -> HashMap<String, Real> {
+        metrics_map(self)
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "Metrics(timestamp={}, attention_entropy={:.4}, affective_strength={:.4}, average_essence={:.3})",
+            self.timestamp, self.attention_entropy, self.affective_strength, self.average_essence
+        )
+    }
+}
+
+/// Consciousness verdict of a finalized run.
+#[pyclass(name = "SimulationSummary", module = "synthetic_consciousness", get_all)]
+#[derive(Clone)]
+pub struct PySummary {
+    num_entities: usize,
+    num_steps: u64,
+    consciousness_score: Real,
+    consciousness_achieved: bool,
+    passed_metrics: Vec<String>,
+    failed_metrics: Vec<String>,
+    narrative: String,
+}
+
+#[pymethods]
+impl PySummary {
+    fn __repr__(&self) -> String {
+        format!(
+            "SimulationSummary(score={:.3}, achieved={}, steps={}, entities={})",
+            self.consciousness_score,
+            if self.consciousness_achieved { "True" } else { "False" },
+            self.num_steps,
+            self.num_entities
+        )
+    }
+}
+
+/// A running simulation.
+#[pyclass(name = "Simulation", module = "synthetic_consciousness")]
+pub struct PySimulation {
+    inner: Simulation,
+}
+
+#[pymethods]
+impl PySimulation {
+    /// Create a simulation, raising `ValueError` for an invalid config.
+    #[new]
+    fn new(config: &PyConfig) -> PyResult<Self> {
+        let inner = Simulation::new(config.inner.clone()).map_err(PyValueError::new_err)?;
+        Ok(PySimulation { inner })
+    }
+
+    /// Advance `n` steps (default 1) without finalizing.
+    #[pyo3(signature = (n = 1))]
+    fn step(&mut self, py: Python<'_>, n: u32) {
+        let sim = &mut self.inner;
+        py.allow_threads(|| {
+            for _ in 0..n {
+                sim.step();
+            }
+        });
+    }
+
+    /// Run the configured number of steps and finalize the results.
+    fn run(&mut self, py: Python<'_>) -> PySummary {
+        let sim = &mut self.inner;
+        py.allow_threads(|| {
+            sim.run();
+            sim.finalize_results();
+        });
+        self.summary()
+    }
+
+    /// Finalize the results of a manually stepped run.
+    fn finalize(&mut self) -> PySummary {
+        self.inner.finalize_results();
+        self.summary()
+    }
+
+    /// Summary of the most recent finalization.
+    fn summary(&self) -> PySummary {
+        let analysis = &self.inner.results.consciousness_analysis;
+        PySummary {
+            num_entities: self.inner.entities.count(),
+            num_steps: self.inner.timestamp,
+            consciousness_score: analysis.consciousness_score,
+            consciousness_achieved: analysis.consciousness_achieved,
+            passed_metrics: analysis.passed_metrics.clone(),
+            failed_metrics: analysis.failed_metrics.clone(),
+            narrative: self.inner.results.narrative_summary(),
+        }
+    }
+
+    /// Current step number.
+    #[getter]
+    fn timestamp(&self) -> u64 {
+        self.inner.timestamp
+    }
+
+    /// Number of live entities.
+    #[getter]
+    fn num_entities(&self) -> usize {
+        self.inner.entities.count()
+    }
+
+    /// Metrics of the most recent step, if any.
+    fn latest_metrics(&self) -> Option<PyMetrics> {
+        self.inner.metrics_history.last().map(PyMetrics::from)
+    }
+
+    /// Metrics of every step as a list of dicts.
+    fn metrics_history(&self) -> Vec<HashMap<String, Real>> {
+        self.inner
+            .metrics_history
+            .iter()
+            .map(|m| metrics_map(&PyMetrics::from(m)))
+            .collect()
+    }
+
+    /// Metrics of every step as a dict of equally long lists.
+    ///
+    /// Suitable for `numpy.asarray(columns[name])` or `pandas.DataFrame(columns)`.
+    fn metrics_columns(&self) -> HashMap<String, Vec<Real>> {
+        let mut columns: HashMap<String, Vec<Real>> = HashMap::new();
+        for metrics in &self.inner.metrics_history {
+            for (name, value) in metrics_map(&PyMetrics::from(metrics)) {
+                columns.entry(name).or_default().push(value);
+            }
+        }
+        columns
+    }
+
+    /// Metrics of every recorded step as a `pyarrow.RecordBatch`.
+    ///
+    /// Handed over through the Arrow C data interface without copying;
+    /// needs pyarrow at runtime and a build with the `arrow` feature.
+    #[cfg(feature = "arrow")]
+    fn metrics_arrow(&self, py: Python<'_>) -> PyResult<PyObject> {
+        use arrow::pyarrow::ToPyArrow;
+
+        let batch = self
+            .inner
+            .results
+            .metrics_batch()
+            .map_err(|e| PyValueError::new_err(e.to_string()))?;
+        batch.to_pyarrow(py)
+    }
+
+    /// Consciousness score of the most recent finalization (0 before).
+    fn consciousness_score(&self) -> Real {
+        self.inner.consciousness_score()
+    }
+
+    /// Whether the most recent finalization found consciousness achieved.
+    fn consciousness_achieved(&self) -> bool {
+        self.inner.consciousness_achieved()
+    }
+
+    /// Write the metrics history as CSV.
+    fn export_metrics_csv(&self, path: &str) -> PyResult<()> {
+        self.inner
+            .export_metrics_csv(path)
+            .map_err(|e| PyIOError::new_err(e.to_string()))
+    }
+
+    /// Write per-entity state as CSV.
+    fn export_entities_csv(&self, path: &str) -> PyResult<()> {
+        self.inner
+            .export_entities_csv(path)
+            .map_err(|e| PyIOError::new_err(e.to_string()))
+    }
+
+    /// Write text and HTML reports using `prefix` for the file names.
+    fn generate_report(&self, prefix: &str) -> PyResult<()> {
+        self.inner
+            .generate_report(prefix)
+            .map_err(|e| PyIOError::new_err(e.to_string()))
+    }
+}
+
+/// Metric values by name (timestamp included as a float).
+fn metrics_map(m: &PyMetrics) -> HashMap<String, Real> {
+    HashMap::from([
+        ("timestamp".to_string(), m.timestamp as Real),
+        ("attention_entropy".to_string(), m.attention_entropy),
+        ("memory_diversity".to_string(), m.memory_diversity),
+        ("velocity_stability".to_string(), m.velocity_stability),
+        ("identity_coherence".to_string(), m.identity_coherence),
+        ("cluster_stability".to_string(), m.cluster_stability),
+        ("affective_strength".to_string(), m.affective_strength),
+        ("essence_trajectory".to_string(), m.essence_trajectory),
+        ("average_essence".to_string(), m.average_essence),
+        ("phase_synchrony".to_string(), m.phase_synchrony),
+        ("mean_energy".to_string(), m.mean_energy),
+        ("role_stability".to_string(), m.role_stability),
+    ])
+}
+
+/// Convert a JSON value to the equivalent Python object.
+fn json_to_py(py: Python<'_>, value: &serde_json::Value) -> PyResult<PyObject> {
+    Ok(match value {
+        serde_json::Value::Null => py.None(),
+        serde_json::Value::Bool(b) => b.into_py(py),
+        serde_json::Value::Number(n) => match n.as_i64() {
+            Some(i) => i.into_py(py),
+            None => n.as_f64().unwrap_or(f64::NAN).into_py(py),
+        },
+        serde_json::Value::String(s) => s.into_py(py),
+        serde_json::Value::Array(items) => {
+            let list = PyList::empty_bound(py);
+            for item in items {
+                list.append(json_to_py(py, item)?)?;
+            }
+            list.into_py(py)
+        }
+        serde_json::Value::Object(map) => {
+            let dict = PyDict::new_bound(py);
+            for (k, v) in map {
+                dict.set_item(k, json_to_py(py, v)?)?;
+            }
+            dict.into_py(py)
+        }
+    })
+}
+
+/// Convert a Python object (None, bool, int, float, str, list, dict) to JSON.
+fn py_to_json(value: &Bound<'_, PyAny>) -> PyResult<serde_json::Value> {
+    if value.is_none() {
+        Ok(serde_json::Value::Null)
+    } else if value.is_instance_of::<PyBool>() {
+        Ok(serde_json::Value::Bool(value.extract()?))
+    } else if let Ok(i) = value.extract::<i64>() {
+        Ok(serde_json::Value::from(i))
+    } else if value.is_instance_of::<PyFloat>() {
+        let f: f64 = value.extract()?;
+        serde_json::Number::from_f64(f)
+            .map(serde_json::Value::Number)
+            .ok_or_else(|| PyValueError::new_err("non-finite floats are not supported"))
+    } else if value.is_instance_of::<PyString>() {
+        Ok(serde_json::Value::String(value.extract()?))
+    } else if let Ok(list) = value.downcast::<PyList>() {
+        list.iter().map(|item| py_to_json(&item)).collect::<PyResult<Vec<_>>>().map(serde_json::Value::Array)
+    } else if let Ok(dict) = value.downcast::<PyDict>() {
+        let mut map = serde_json::Map::new();
+        for (k, v) in dict.iter() {
+            map.insert(k.extract::<String>()?, py_to_json(&v)?);
+        }
+        Ok(serde_json::Value::Object(map))
+    } else {
+        Err(PyTypeError::new_err(format!(
+            "unsupported config value type '{}'",
+            value.get_type().name()?
+        )))
+    }
+}
+
+/// Python module definition.
+#[pymodule]
+fn synthetic_consciousness(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add_class::<PyConfig>()?;
+    m.add_class::<PySimulation>()?;
+    m.add_class::<PyMetrics>()?;
+    m.add_class::<PySummary>()?;
+    Ok(())
+}
@@ -0,0 +1,122 @@
+//! WebAssembly bindings: a wasm-bindgen API for running the simulation in
+//! the browser or Node.
+//!
+//! Build the core without the GUI and with this layer:
+//!
+//! ```bash
+//! wasm-pack build --target web -- --no-default-features --features wasm
+//! ```
+//!
+//! ```js
+//! import init, { WasmSimulation } from "./pkg/synthetic_consciousness.js";
+//!
+//! await init();
+//! const sim = new WasmSimulation(JSON.stringify(config));
+//! sim.step_n(10);
+//! const xy = sim.positions();              // Float32Array, dimension floats per entity
+//! const metrics = JSON.parse(sim.metrics_json());
+//! ```
+//!
+//! ## Platform Notes
+//!
+//! - Randomness comes from `crypto.getRandomValues` (getrandom's `js` backend)
+//! - Timestamps in results are UTC
+//! - File-based exports (reports, CSV) return I/O errors, as wasm32 has no
+//!   filesystem; read results through the JSON accessors instead
+//!
+//! ## Author
+//! Ayomide I. Daniels (Morningstar)
+
+use crate::config::SimulationConfig;
+use crate::real::{to_f32, Real};
+use crate::simulation::Simulation;
+use js_sys::Float32Array;
+use serde::Serialize;
+use wasm_bindgen::prelude::*;
+
+/// Consciousness verdict of a finalized run, as returned by `summary_json`.
+#[derive(Serialize)]
+struct WasmSummary<'a> {
+    num_entities: usize,
+    num_steps: u64,
+    consciousness_score: Real,
+    consciousness_achieved: bool,
+    passed_metrics: &'a [String],
+    failed_metrics: &'a [String],
+    narrative: String,
+}
+
+/// Simulation handle exposed to JavaScript.
+#[wasm_bindgen]
+pub struct WasmSimulation {
+    inner: Simulation,
+}
+
+#[wasm_bindgen]
+impl WasmSimulation {
+    /// Create a simulation from a JSON-serialized `SimulationConfig`.
+    ///
+    /// # Arguments
+    /// * `config_json` - Configuration in the same shape as the TOML files
+    ///
+    /// # Returns
+    /// The simulation, or an error if the JSON or configuration is invalid
+    #[wasm_bindgen(constructor)]
+    pub fn new(config_json: &str) -> Result<WasmSimulation, JsError> {
+        let config: SimulationConfig = serde_json::from_str(config_json)?;
+        config.validate().map_err(|e| JsError::new(&e))?;
+        let inner = Simulation::new(config).map_err(|e| JsError::new(&e))?;
+        Ok(WasmSimulation { inner })
+    }
+
+    /// Advance the simulation by `n` steps.
+    pub fn step_n(&mut self, n: u32) {
+        for _ in 0..n {
+            self.inner.step();
+        }
+    }
+
+    /// Number of live entities.
+    pub fn entity_count(&self) -> usize {
+        self.inner.entities.count()
+    }
+
+    /// Spatial dimensionality.
+    pub fn dimension(&self) -> usize {
+        self.inner.config.geometry.dimension
+    }
+
+    /// Entity positions, flattened in entity id order.
+    ///
+    /// # Returns
+    /// `entity_count() * dimension()` floats
+    pub fn positions(&self) -> Float32Array {
+        let entities = self.inner.entities.all_entities();
+        let flat: Vec<f32> = entities
+            .iter()
+            .flat_map(|e| e.pose.position.iter().map(|&v| to_f32(v)))
+            .collect();
+        Float32Array::from(flat.as_slice())
+    }
+
+    /// Metrics of the most recent step as JSON (`null` before the first step).
+    pub fn metrics_json(&self) -> Result<String, JsError> {
+        Ok(serde_json::to_string(&self.inner.metrics_history.last())?)
+    }
+
+    /// Finalize the results so far and return the consciousness verdict as JSON.
+    pub fn summary_json(&mut self) -> Result<String, JsError> {
+        self.inner.finalize_results();
+        let analysis = &self.inner.results.consciousness_analysis;
+        let summary = WasmSummary {
+            num_entities: self.inner.entities.count(),
+            num_steps: self.inner.timestamp,
+            consciousness_score: analysis.consciousness_score,
+            consciousness_achieved: analysis.consciousness_achieved,
+            passed_metrics: &analysis.passed_metrics,
+            failed_metrics: &analysis.failed_metrics,
+            narrative: self.inner.results.narrative_summary(),
+        };
+        Ok(serde_json::to_string(&summary)?)
+    }
+}
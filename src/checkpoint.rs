@@ -0,0 +1,287 @@
+//! Checkpoint module: save a running simulation to disk and resume it.
+//!
+//! A checkpoint holds the full state of a [`Simulation`]: the configuration
+//! in effect, the step counter, every entity (pose, velocity, state vector,
+//! memory graph, essence, energy), the position of the random stream, the
+//! metrics history and the accumulated results, plus the bookkeeping the
+//! step phases carry between steps (phase history, in-flight signals, the
+//! previous leader set, cumulative interaction weights, runtime config
+//! patches and stimuli scheduled for later steps). Resuming a checkpoint and running the remaining steps gives
+//! the same final metrics as an uninterrupted run with the same seed.
+//!
+//! Not saved: plugins (re-created from the configuration on load), custom
+//! sense models, progress reporters, stop flags and GUI overrides.
+//!
+//! ## Format
+//!
+//! JSON, gzip-compressed when the path ends in `.gz` (loading detects gzip
+//! from the content). Files are written to a temporary sibling and renamed
+//! into place, so a crash mid-write leaves the previous checkpoint intact.
+//!
+//! ## Periodic Checkpoints
+//!
+//! [`Checkpointer`] is a [`SimulationObserver`] that saves every N steps:
+//!
+//! ```no_run
+//! use synthetic_consciousness::checkpoint::Checkpointer;
+//! use synthetic_consciousness::{Simulation, SimulationConfig};
+//!
+//! let mut sim = Simulation::new(SimulationConfig::default_2d()).unwrap();
+//! sim.add_observer(Box::new(Checkpointer::new("run.ckpt.json.gz", 1000)));
+//! sim.run();
+//! ```
+//!
+//! ## Author
+//! Ayomide I. Daniels (Morningstar)
+
+use crate::config::{SimulationConfig, SimulationConfigPatch};
+use crate::entities::{EntityId, EntityPool};
+use crate::memory::ActivationSums;
+use crate::metrics::Metrics;
+use crate::plugin::{PluginRegistry, SimulationObserver, StepControl};
+use crate::real::Real;
+use crate::results::SimulationResults;
+use crate::signaling::Signal;
+use crate::simulation::{RngState, Simulation};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeSet, VecDeque};
+use std::io::{Read, Write};
+
+/// Version written into checkpoints; files of other versions are rejected.
+pub const CHECKPOINT_VERSION: u32 = 1;
+
+/// Serialized state of a simulation.
+///
+/// Maps are stored as lists sorted by key, so equal states give equal files.
+#[derive(Serialize, Deserialize)]
+struct Checkpoint {
+    version: u32,
+    config: SimulationConfig,
+    timestamp: u64,
+    rng: RngState,
+    /// Wall-clock seconds spent stepping before this checkpoint
+    wall_clock_seconds: f64,
+    entities: EntityPool,
+    /// Running activation sums of each entity's memory graph; these drift
+    /// from an exact recount, so restoring them keeps resumed runs on track
+    memory_sums: Vec<(EntityId, ActivationSums)>,
+    metrics_history: Vec<Metrics>,
+    results: SimulationResults,
+    phase_history: Vec<(EntityId, VecDeque<Real>)>,
+    pending_signals: Vec<Signal>,
+    last_emission: Vec<(EntityId, u64)>,
+    signal_counts: (usize, usize),
+    previous_leaders: BTreeSet<EntityId>,
+    role_stability: Real,
+    config_patches: Vec<(u64, SimulationConfigPatch)>,
+    interaction_weights: Vec<((EntityId, EntityId), Real)>,
+    #[serde(default)]
+    scheduled_stimuli: Vec<(u64, EntityId, Vec<Real>)>,
+}
+
+/// Version field read before the rest of a checkpoint.
+#[derive(Deserialize)]
+struct CheckpointHeader {
+    version: Option<u32>,
+}
+
+impl Simulation {
+    /// Save the full simulation state to `path`.
+    ///
+    /// # Arguments
+    /// * `path` - Checkpoint file; gzip-compressed if it ends in `.gz`
+    ///
+    /// # Returns
+    /// Error if the state cannot be serialized or the file written
+    pub fn save_checkpoint(&self, path: &str) -> Result<(), Box<dyn std::error::Error>> {
+        let mut phase_history: Vec<_> = self.phase_history.iter().map(|(id, h)| (*id, h.clone())).collect();
+        phase_history.sort_by_key(|(id, _)| *id);
+        let mut last_emission: Vec<_> = self.last_emission.iter().map(|(id, step)| (*id, *step)).collect();
+        last_emission.sort_by_key(|(id, _)| *id);
+        let mut interaction_weights: Vec<_> = self.interaction_weights.iter().map(|(pair, w)| (*pair, *w)).collect();
+        interaction_weights.sort_by_key(|(pair, _)| *pair);
+        let mut memory_sums: Vec<_> = self
+            .entities
+            .iter()
+            .map(|entity| (entity.id, entity.memory_graph.activation_sums().clone()))
+            .collect();
+        memory_sums.sort_by_key(|(id, _)| *id);
+
+        let checkpoint = Checkpoint {
+            version: CHECKPOINT_VERSION,
+            config: self.config.clone(),
+            timestamp: self.timestamp,
+            rng: self.rng_state(),
+            wall_clock_seconds: self.wall_clock_seconds(),
+            entities: self.entities.clone(),
+            memory_sums,
+            metrics_history: self.metrics_history.clone(),
+            results: self.results.clone(),
+            phase_history,
+            pending_signals: self.pending_signals.clone(),
+            last_emission,
+            signal_counts: self.signal_counts,
+            previous_leaders: self.previous_leaders.clone(),
+            role_stability: self.role_stability,
+            config_patches: self.config_patches.clone(),
+            interaction_weights,
+            scheduled_stimuli: self
+                .scheduled_stimuli
+                .iter()
+                .flat_map(|(step, queued)| queued.iter().map(|(id, stimulus)| (*step, *id, stimulus.clone())))
+                .collect(),
+        };
+        let json = serde_json::to_vec(&checkpoint)?;
+
+        // Write beside the target, then rename over it
+        let partial = format!("{}.partial", path);
+        if path.ends_with(".gz") {
+            let mut encoder = flate2::write::GzEncoder::new(std::fs::File::create(&partial)?, flate2::Compression::default());
+            encoder.write_all(&json)?;
+            encoder.finish()?.sync_all()?;
+        } else {
+            let mut file = std::fs::File::create(&partial)?;
+            file.write_all(&json)?;
+            file.sync_all()?;
+        }
+        std::fs::rename(&partial, path)?;
+        Ok(())
+    }
+
+    /// Resume a simulation saved with [`Simulation::save_checkpoint`].
+    ///
+    /// Plugins named in the saved configuration are resolved against the
+    /// plugins registered at link time.
+    ///
+    /// # Arguments
+    /// * `path` - Checkpoint file (plain or gzip-compressed JSON)
+    ///
+    /// # Returns
+    /// The simulation at the saved step, or an error naming the file and
+    /// what is wrong with it
+    pub fn load_checkpoint(path: &str) -> Result<Self, Box<dyn std::error::Error>> {
+        Self::load_checkpoint_with_plugins(path, &PluginRegistry::with_registered())
+    }
+
+    /// Resume a checkpoint, resolving configured plugins against `registry`.
+    ///
+    /// # Arguments
+    /// * `path` - Checkpoint file (plain or gzip-compressed JSON)
+    /// * `registry` - Plugin constructors (e.g. with libraries loaded)
+    pub fn load_checkpoint_with_plugins(
+        path: &str,
+        registry: &PluginRegistry,
+    ) -> Result<Self, Box<dyn std::error::Error>> {
+        let bytes = std::fs::read(path).map_err(|e| format!("cannot read checkpoint '{}': {}", path, e))?;
+        let json = if bytes.starts_with(&[0x1f, 0x8b]) {
+            let mut json = Vec::new();
+            flate2::read::GzDecoder::new(bytes.as_slice())
+                .read_to_end(&mut json)
+                .map_err(|e| format!("'{}' is not a valid gzip file: {}", path, e))?;
+            json
+        } else {
+            bytes
+        };
+        let header: CheckpointHeader = serde_json::from_slice(&json)
+            .map_err(|e| format!("'{}' is not a checkpoint: {}", path, e))?;
+        match header.version {
+            Some(CHECKPOINT_VERSION) => {}
+            Some(version) => {
+                return Err(format!(
+                    "'{}' is a version {} checkpoint; expected version {}",
+                    path, version, CHECKPOINT_VERSION
+                )
+                .into())
+            }
+            None => return Err(format!("'{}' is not a checkpoint: no version", path).into()),
+        }
+        let checkpoint: Checkpoint = serde_json::from_slice(&json)
+            .map_err(|e| format!("'{}' is not a checkpoint: {}", path, e))?;
+
+        let mut sim = Simulation::with_plugins(checkpoint.config, registry)
+            .map_err(|e| format!("checkpoint '{}' has an invalid configuration: {}", path, e))?;
+        sim.timestamp = checkpoint.timestamp;
+        sim.restore_rng(checkpoint.rng);
+        sim.set_prior_wall_clock(checkpoint.wall_clock_seconds);
+        sim.entities = checkpoint.entities;
+        for (id, sums) in checkpoint.memory_sums {
+            if let Some(entity) = sim.entities.get_entity_mut(id) {
+                entity.memory_graph.restore_activation_sums(sums);
+            }
+        }
+        sim.metrics_history = checkpoint.metrics_history;
+        sim.results = checkpoint.results;
+        sim.phase_history = checkpoint.phase_history.into_iter().collect();
+        sim.pending_signals = checkpoint.pending_signals;
+        sim.last_emission = checkpoint.last_emission.into_iter().collect();
+        sim.signal_counts = checkpoint.signal_counts;
+        sim.previous_leaders = checkpoint.previous_leaders;
+        sim.role_stability = checkpoint.role_stability;
+        sim.config_patches = checkpoint.config_patches;
+        sim.interaction_weights = checkpoint.interaction_weights.into_iter().collect();
+        for (step, id, stimulus) in checkpoint.scheduled_stimuli {
+            sim.scheduled_stimuli.entry(step).or_default().push((id, stimulus));
+        }
+        Ok(sim)
+    }
+}
+
+/// Observer saving a checkpoint every `every` steps.
+///
+/// Each save replaces the previous file. A failed save is logged as a
+/// warning and the run continues; [`Checkpointer::last_error`] keeps the
+/// latest failure.
+#[derive(Clone, Debug)]
+pub struct Checkpointer {
+    path: String,
+    every: u64,
+    saved: u32,
+    last_error: Option<String>,
+}
+
+impl Checkpointer {
+    /// Save to `path` whenever the step count is a multiple of `every`
+    /// (at least 1).
+    pub fn new(path: impl Into<String>, every: u64) -> Self {
+        Checkpointer {
+            path: path.into(),
+            every: every.max(1),
+            saved: 0,
+            last_error: None,
+        }
+    }
+
+    /// Checkpoint file written to.
+    pub fn path(&self) -> &str {
+        &self.path
+    }
+
+    /// Number of checkpoints written so far.
+    pub fn saved(&self) -> u32 {
+        self.saved
+    }
+
+    /// Latest save failure, if any.
+    pub fn last_error(&self) -> Option<&str> {
+        self.last_error.as_deref()
+    }
+}
+
+impl SimulationObserver for Checkpointer {
+    fn on_step(&mut self, sim: &Simulation) -> StepControl {
+        if sim.timestamp == 0 || !sim.timestamp.is_multiple_of(self.every) {
+            return StepControl::Continue;
+        }
+        match sim.save_checkpoint(&self.path) {
+            Ok(()) => {
+                self.saved += 1;
+                log::debug!("Checkpoint at step {} written to {}", sim.timestamp, self.path);
+            }
+            Err(e) => {
+                log::warn!("Cannot write checkpoint at step {} to {}: {}", sim.timestamp, self.path, e);
+                self.last_error = Some(e.to_string());
+            }
+        }
+        StepControl::Continue
+    }
+}
@@ -0,0 +1,586 @@
+//! CLI module: command-line parsing for the simulation binary.
+//!
+//! [`Command::parse`] turns the binary's arguments into a subcommand with
+//! typed options: `run` (the default, [`CliArgs`]), `replay`
+//! ([`ReplayArgs`]), `sweep` ([`SweepArgs`]) or `validate`
+//! ([`ValidateArgs`]).
+//! Options take their value either inline (`--steps=5000`) or as the next
+//! argument (`--steps 5000`). A bare argument without `=` is the
+//! configuration file; `key=value` arguments are passed to
+//! [`SimulationConfig::apply_cli_args`](crate::config::SimulationConfig::apply_cli_args).
+//!
+//! A config path of `-` reads the configuration from standard input, as
+//! does giving no path while standard input is not a terminal (an empty
+//! stream falls back to the default configuration). `--format toml|json`
+//! names the format; without it the format is guessed from the text.
+//!
+//! `-q`/`--quiet` and `-v`/`--verbose` select the [`Verbosity`]; the binary
+//! writes all of its console output through the `log` facade and
+//! [`init_logging`] installs a [`ConsoleLogger`] filtered to that level.
+//!
+//! The process exit code is [`RunOutcome::exit_code`]: 0 when the run
+//! completed and consciousness was achieved, 3 when it completed without
+//! meeting the criteria (0 with `--no-verdict-exit-code`), 1 for command-line
+//! or configuration errors and 2 for failures while running or exporting.
+//! `validate` exits with 1 when a file has semantic problems and 4 when a
+//! file cannot be read or parsed.
+//!
+//! `--steps`, `--entities` and `--seed` ([`RunOverrides`]) replace the
+//! corresponding `simulation.*` fields of the loaded configuration. They
+//! are applied after the `key=value` overrides, so they win over them, and
+//! before validation.
+//!
+//! `--checkpoint-every N` saves the run every N steps to `--checkpoint`
+//! (default [`DEFAULT_CHECKPOINT_PATH`]); `--resume PATH` continues a saved
+//! run, taking its configuration from the checkpoint.
+//!
+//! ## Author
+//! Ayomide I. Daniels (Morningstar)
+
+use crate::config::{ConfigFormat, STDIN_PATH};
+use crate::export::ExportFormat;
+use crate::sweep::SweepParam;
+use log::{Level, LevelFilter, Log, Metadata, Record};
+use std::str::FromStr;
+
+/// Subcommand of the binary.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Command {
+    /// Run a simulation (the default without a subcommand, or `run`)
+    Run(CliArgs),
+    /// Play back saved results in the GUI (`replay`)
+    Replay(ReplayArgs),
+    /// Run a parameter grid over a base configuration (`sweep`)
+    Sweep(SweepArgs),
+    /// Check configuration files without running them (`validate`)
+    Validate(ValidateArgs),
+}
+
+impl Command {
+    /// Parse the binary's arguments, starting with an optional subcommand.
+    ///
+    /// # Arguments
+    /// * `args` - Arguments without the program name
+    ///
+    /// # Returns
+    /// The subcommand and its options, or a message naming the offending argument
+    pub fn parse<S: AsRef<str>>(args: &[S]) -> Result<Self, String> {
+        match args.first().map(AsRef::as_ref) {
+            Some("run") => CliArgs::parse(&args[1..]).map(Command::Run),
+            Some("replay") => ReplayArgs::parse(&args[1..]).map(Command::Replay),
+            Some("sweep") => SweepArgs::parse(&args[1..]).map(Command::Sweep),
+            Some("validate") => ValidateArgs::parse(&args[1..]).map(Command::Validate),
+            _ => CliArgs::parse(args).map(Command::Run),
+        }
+    }
+}
+
+/// Options of `replay <results> [--speed X] [--start-step N]`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ReplayArgs {
+    /// Saved results (`.json`, or gzip-compressed `.json.gz`)
+    pub results_path: String,
+    /// Playback speed multiplier (default 1.0)
+    pub speed: f64,
+    /// First step shown (default 0)
+    pub start_step: u64,
+    /// Console output level
+    pub verbosity: Verbosity,
+}
+
+impl ReplayArgs {
+    /// Parse the arguments after `replay`.
+    ///
+    /// # Returns
+    /// The options, or a message naming the offending argument
+    pub fn parse<S: AsRef<str>>(args: &[S]) -> Result<Self, String> {
+        let mut results_path = None;
+        let mut speed: f64 = 1.0;
+        let mut start_step = 0;
+        let mut verbosity = Verbosity::Normal;
+        let mut args = args.iter().map(AsRef::as_ref);
+        while let Some(arg) = args.next() {
+            match arg {
+                "-q" | "--quiet" => verbosity = Verbosity::Quiet,
+                "-v" | "--verbose" => verbosity = Verbosity::Verbose,
+                _ if arg.starts_with('-') => {
+                    let (name, value) = option_value(arg, &mut args, &["--speed", "--start-step"])?;
+                    if name == "--speed" {
+                        speed = parse_value(name, value, "a positive number")?;
+                        if !(speed.is_finite() && speed > 0.0) {
+                            return Err(format!("--speed must be positive, got '{}'", value));
+                        }
+                    } else {
+                        start_step = parse_value(name, value, "a step number")?;
+                    }
+                }
+                _ if results_path.is_none() => results_path = Some(arg.to_string()),
+                _ => return Err(format!("unexpected argument '{}' (results file already given)", arg)),
+            }
+        }
+        Ok(ReplayArgs {
+            results_path: results_path.ok_or("replay needs a results file")?,
+            speed,
+            start_step,
+            verbosity,
+        })
+    }
+}
+
+/// Options of `sweep <config> --param path=v1,v2,... [--jobs N] [--out DIR]`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SweepArgs {
+    /// Base configuration every cell starts from
+    pub config_path: String,
+    /// Swept parameters (`--param`, repeatable; the first varies slowest)
+    pub params: Vec<SweepParam>,
+    /// Cells run at a time (`--jobs`; default one per CPU)
+    pub jobs: Option<u32>,
+    /// Output directory (`--out`, default `sweep_results`)
+    pub out_dir: String,
+    /// Console output level
+    pub verbosity: Verbosity,
+}
+
+impl SweepArgs {
+    /// Default output directory.
+    pub const DEFAULT_OUT_DIR: &'static str = "sweep_results";
+
+    /// Parse the arguments after `sweep`.
+    ///
+    /// # Returns
+    /// The options, or a message naming the offending argument
+    pub fn parse<S: AsRef<str>>(args: &[S]) -> Result<Self, String> {
+        let mut config_path = None;
+        let mut params = Vec::new();
+        let mut jobs = None;
+        let mut out_dir = Self::DEFAULT_OUT_DIR.to_string();
+        let mut verbosity = Verbosity::Normal;
+        let mut args = args.iter().map(AsRef::as_ref);
+        while let Some(arg) = args.next() {
+            match arg {
+                "-q" | "--quiet" => verbosity = Verbosity::Quiet,
+                "-v" | "--verbose" => verbosity = Verbosity::Verbose,
+                _ if arg.starts_with('-') => {
+                    let (name, value) = option_value(arg, &mut args, &["--param", "--jobs", "--out"])?;
+                    match name {
+                        "--param" => params.push(value.parse::<SweepParam>().map_err(|e| format!("--param: {}", e))?),
+                        "--jobs" => jobs = Some(parse_nonzero(name, value)?),
+                        _ => out_dir = value.to_string(),
+                    }
+                }
+                _ if config_path.is_none() => config_path = Some(arg.to_string()),
+                _ => return Err(format!("unexpected argument '{}' (config file already given)", arg)),
+            }
+        }
+        if params.is_empty() {
+            return Err("sweep needs at least one --param path=value1,value2,...".to_string());
+        }
+        Ok(SweepArgs {
+            config_path: config_path.ok_or("sweep needs a base config file")?,
+            params,
+            jobs,
+            out_dir,
+            verbosity,
+        })
+    }
+}
+
+/// Options of `validate [config...] [--format toml|json]`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ValidateArgs {
+    /// Configuration files to check, in order (`-` is standard input; none
+    /// means standard input when it is piped)
+    pub config_paths: Vec<String>,
+    /// Format of the configurations (`--format`; guessed if absent)
+    pub config_format: Option<ConfigFormat>,
+    /// Console output level
+    pub verbosity: Verbosity,
+}
+
+impl ValidateArgs {
+    /// Parse the arguments after `validate`.
+    ///
+    /// # Returns
+    /// The options, or a message naming the offending argument
+    pub fn parse<S: AsRef<str>>(args: &[S]) -> Result<Self, String> {
+        let mut config_paths = Vec::new();
+        let mut config_format = None;
+        let mut verbosity = Verbosity::Normal;
+        let mut args = args.iter().map(AsRef::as_ref);
+        while let Some(arg) = args.next() {
+            match arg {
+                "-q" | "--quiet" => verbosity = Verbosity::Quiet,
+                "-v" | "--verbose" => verbosity = Verbosity::Verbose,
+                STDIN_PATH => config_paths.push(arg.to_string()),
+                _ if arg.starts_with('-') => {
+                    let (_, value) = option_value(arg, &mut args, &["--format"])?;
+                    config_format = Some(parse_format(value)?);
+                }
+                _ => config_paths.push(arg.to_string()),
+            }
+        }
+        Ok(ValidateArgs {
+            config_paths,
+            config_format,
+            verbosity,
+        })
+    }
+}
+
+/// Options that take a value.
+const VALUE_OPTIONS: [&str; 12] = [
+    "--format",
+    "--steps",
+    "--entities",
+    "--seed",
+    "--export",
+    "--metrics",
+    "--serve",
+    "--grpc",
+    "--plugin-lib",
+    "--checkpoint",
+    "--checkpoint-every",
+    "--resume",
+];
+
+/// Parsed command line of the simulation binary.
+#[derive(Clone, Debug, PartialEq)]
+pub struct CliArgs {
+    /// Configuration file, or `-` for standard input (if absent: piped
+    /// standard input, else the default 2D configuration)
+    pub config_path: Option<String>,
+    /// Format of the configuration (`--format`; guessed from the text if absent)
+    pub config_format: Option<ConfigFormat>,
+    /// `key=value` configuration overrides, in order
+    pub overrides: Vec<String>,
+    /// `--steps`, `--entities` and `--seed`
+    pub run_overrides: RunOverrides,
+    /// Console output level (`--quiet`, `--verbose`)
+    pub verbosity: Verbosity,
+    /// Open the real-time visualization (`--visualize`)
+    pub visualize: bool,
+    /// Files to write when the run finishes (`--export`)
+    pub export: Vec<ExportFormat>,
+    /// Prometheus endpoint address (`--metrics`)
+    pub metrics_addr: Option<String>,
+    /// HTTP control API address (`--serve`)
+    pub serve_addr: Option<String>,
+    /// gRPC API address (`--grpc`)
+    pub grpc_addr: Option<String>,
+    /// Plugin libraries to load (`--plugin-lib`, repeatable)
+    pub plugin_libs: Vec<String>,
+    /// Exit with [`EXIT_NOT_CONSCIOUS`] when the criteria were not met
+    /// (disabled by `--no-verdict-exit-code`)
+    pub verdict_exit_code: bool,
+    /// Checkpoint file (`--checkpoint`, default [`DEFAULT_CHECKPOINT_PATH`])
+    pub checkpoint_path: Option<String>,
+    /// Save a checkpoint every N steps (`--checkpoint-every`)
+    pub checkpoint_every: Option<u32>,
+    /// Continue a run from a checkpoint instead of loading a configuration
+    /// (`--resume`)
+    pub resume: Option<String>,
+}
+
+/// Checkpoint file written by `--checkpoint-every` without `--checkpoint`.
+pub const DEFAULT_CHECKPOINT_PATH: &str = "checkpoint.json.gz";
+
+impl Default for CliArgs {
+    fn default() -> Self {
+        CliArgs {
+            config_path: None,
+            config_format: None,
+            overrides: Vec::new(),
+            run_overrides: RunOverrides::default(),
+            verbosity: Verbosity::Normal,
+            visualize: false,
+            export: ExportFormat::DEFAULT.to_vec(),
+            metrics_addr: None,
+            serve_addr: None,
+            grpc_addr: None,
+            plugin_libs: Vec::new(),
+            verdict_exit_code: true,
+            checkpoint_path: None,
+            checkpoint_every: None,
+            resume: None,
+        }
+    }
+}
+
+/// Exit code of a run that completed and achieved consciousness.
+pub const EXIT_SUCCESS: i32 = 0;
+
+/// Exit code of an invalid command line or configuration.
+pub const EXIT_CONFIG_ERROR: i32 = 1;
+
+/// Exit code of a failure while running or writing results.
+pub const EXIT_RUNTIME_ERROR: i32 = 2;
+
+/// Exit code of a run that completed without meeting the consciousness criteria.
+pub const EXIT_NOT_CONSCIOUS: i32 = 3;
+
+/// Exit code of `validate` when a configuration file cannot be read or parsed.
+pub const EXIT_PARSE_ERROR: i32 = 4;
+
+/// How an invocation of the binary ended.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RunOutcome {
+    /// The simulation ran to the end
+    Completed { consciousness_achieved: bool },
+    /// Ended cleanly without a verdict: a server (`--serve`, `--grpc`)
+    /// shut down or a replay finished
+    NoVerdict,
+    /// The command line or configuration was rejected
+    ConfigError,
+    /// A configuration file could not be read or parsed (`validate`)
+    ParseError,
+    /// Running, serving or exporting failed
+    RuntimeError,
+}
+
+impl RunOutcome {
+    /// Process exit code of the outcome.
+    ///
+    /// # Arguments
+    /// * `verdict_exit_code` - Whether a completed run without consciousness
+    ///   exits with [`EXIT_NOT_CONSCIOUS`] rather than [`EXIT_SUCCESS`]
+    ///
+    /// # Returns
+    /// One of the `EXIT_*` constants
+    pub fn exit_code(self, verdict_exit_code: bool) -> i32 {
+        match self {
+            RunOutcome::Completed { consciousness_achieved: false } if verdict_exit_code => EXIT_NOT_CONSCIOUS,
+            RunOutcome::Completed { .. } | RunOutcome::NoVerdict => EXIT_SUCCESS,
+            RunOutcome::ConfigError => EXIT_CONFIG_ERROR,
+            RunOutcome::ParseError => EXIT_PARSE_ERROR,
+            RunOutcome::RuntimeError => EXIT_RUNTIME_ERROR,
+        }
+    }
+}
+
+/// How much the binary writes to the console.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Verbosity {
+    /// Errors only (on stderr); nothing on stdout
+    Quiet,
+    /// Banner, final metrics and summary
+    #[default]
+    Normal,
+    /// Also step progress and diagnostics
+    Verbose,
+}
+
+impl Verbosity {
+    /// Most detailed log level shown.
+    pub fn level_filter(self) -> LevelFilter {
+        match self {
+            Verbosity::Quiet => LevelFilter::Error,
+            Verbosity::Normal => LevelFilter::Info,
+            Verbosity::Verbose => LevelFilter::Debug,
+        }
+    }
+}
+
+/// Simulation size and seed given as dedicated options.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct RunOverrides {
+    /// Replaces `simulation.num_steps`
+    pub steps: Option<u32>,
+    /// Replaces `simulation.num_entities`
+    pub entities: Option<u32>,
+    /// Replaces `simulation.seed`
+    pub seed: Option<u64>,
+}
+
+impl CliArgs {
+    /// Parse the binary's arguments.
+    ///
+    /// # Arguments
+    /// * `args` - Arguments without the program name
+    ///
+    /// # Returns
+    /// The parsed options, or a message naming the offending argument
+    pub fn parse<S: AsRef<str>>(args: &[S]) -> Result<Self, String> {
+        let mut cli = CliArgs::default();
+        let mut args = args.iter().map(AsRef::as_ref);
+        while let Some(arg) = args.next() {
+            match arg {
+                "--visualize" => {
+                    cli.visualize = true;
+                    continue;
+                }
+                "-q" | "--quiet" => {
+                    cli.verbosity = Verbosity::Quiet;
+                    continue;
+                }
+                "-v" | "--verbose" => {
+                    cli.verbosity = Verbosity::Verbose;
+                    continue;
+                }
+                "--no-verdict-exit-code" => {
+                    cli.verdict_exit_code = false;
+                    continue;
+                }
+                _ => {}
+            }
+            if arg == STDIN_PATH || !arg.starts_with('-') {
+                if arg.contains('=') {
+                    cli.overrides.push(arg.to_string());
+                } else if cli.config_path.is_none() {
+                    cli.config_path = Some(arg.to_string());
+                } else {
+                    return Err(format!("unexpected argument '{}' (config file already given)", arg));
+                }
+                continue;
+            }
+
+            let (name, value) = option_value(arg, &mut args, &VALUE_OPTIONS)?;
+
+            match name {
+                "--format" => cli.config_format = Some(parse_format(value)?),
+                "--steps" => cli.run_overrides.steps = Some(parse_nonzero(name, value)?),
+                "--entities" => cli.run_overrides.entities = Some(parse_nonzero(name, value)?),
+                "--seed" => cli.run_overrides.seed = Some(parse_value(name, value, "an unsigned integer")?),
+                "--export" => {
+                    cli.export = ExportFormat::parse_list(value).map_err(|e| format!("--export: {}", e))?
+                }
+                "--metrics" => cli.metrics_addr = Some(value.to_string()),
+                "--serve" => cli.serve_addr = Some(value.to_string()),
+                "--grpc" => cli.grpc_addr = Some(value.to_string()),
+                "--checkpoint" => cli.checkpoint_path = Some(value.to_string()),
+                "--checkpoint-every" => cli.checkpoint_every = Some(parse_nonzero(name, value)?),
+                "--resume" => cli.resume = Some(value.to_string()),
+                _ => cli.plugin_libs.push(value.to_string()),
+            }
+        }
+        if cli.checkpoint_path.is_some() && cli.checkpoint_every.is_none() {
+            return Err("--checkpoint needs --checkpoint-every".to_string());
+        }
+        if cli.resume.is_some()
+            && (cli.config_path.is_some() || !cli.overrides.is_empty() || !cli.run_overrides.is_empty())
+        {
+            return Err("--resume takes the configuration from the checkpoint; \
+                        drop the config file and overrides"
+                .to_string());
+        }
+        Ok(cli)
+    }
+
+    /// Checkpoint file and interval, if periodic checkpoints were requested.
+    pub fn checkpoint(&self) -> Option<(&str, u32)> {
+        let every = self.checkpoint_every?;
+        Some((self.checkpoint_path.as_deref().unwrap_or(DEFAULT_CHECKPOINT_PATH), every))
+    }
+
+    /// Every configuration override, in the order to apply them.
+    ///
+    /// # Returns
+    /// The `key=value` arguments followed by [`RunOverrides::to_cli_args`],
+    /// for `SimulationConfig::apply_cli_args`
+    pub fn config_overrides(&self) -> Vec<String> {
+        let mut overrides = self.overrides.clone();
+        overrides.extend(self.run_overrides.to_cli_args());
+        overrides
+    }
+}
+
+impl RunOverrides {
+    /// Whether no override was given.
+    pub fn is_empty(&self) -> bool {
+        self.steps.is_none() && self.entities.is_none() && self.seed.is_none()
+    }
+
+    /// The overrides as `key=value` configuration arguments.
+    ///
+    /// # Returns
+    /// Section-qualified arguments such as `simulation.num_steps=5000`
+    pub fn to_cli_args(&self) -> Vec<String> {
+        let mut args = Vec::new();
+        if let Some(steps) = self.steps {
+            args.push(format!("simulation.num_steps={}", steps));
+        }
+        if let Some(entities) = self.entities {
+            args.push(format!("simulation.num_entities={}", entities));
+        }
+        if let Some(seed) = self.seed {
+            args.push(format!("simulation.seed={}", seed));
+        }
+        args
+    }
+}
+
+/// Logger of the binary: info and debug lines on stdout, warnings and
+/// errors on stderr.
+///
+/// Debug and trace records of other crates (e.g. the GUI toolkit) are
+/// dropped so `--verbose` shows this crate's diagnostics only.
+pub struct ConsoleLogger;
+
+impl Log for ConsoleLogger {
+    fn enabled(&self, metadata: &Metadata) -> bool {
+        metadata.level() <= log::max_level()
+            && (metadata.level() <= Level::Warn || metadata.target().starts_with("synthetic_consciousness"))
+    }
+
+    fn log(&self, record: &Record) {
+        if !self.enabled(record.metadata()) {
+            return;
+        }
+        match record.level() {
+            Level::Error => eprintln!("{}", record.args()),
+            Level::Warn => eprintln!("warning: {}", record.args()),
+            Level::Info => println!("{}", record.args()),
+            Level::Debug | Level::Trace => println!("[{}] {}", record.level().as_str().to_lowercase(), record.args()),
+        }
+    }
+
+    fn flush(&self) {}
+}
+
+/// Install [`ConsoleLogger`] as the global logger at `verbosity`.
+///
+/// Only the first call installs the logger; later calls just change the level.
+pub fn init_logging(verbosity: Verbosity) {
+    static LOGGER: ConsoleLogger = ConsoleLogger;
+    let _ = log::set_logger(&LOGGER);
+    log::set_max_level(verbosity.level_filter());
+}
+
+/// Split an option into its name and value (inline after `=`, or the next argument).
+fn option_value<'a>(
+    arg: &'a str,
+    rest: &mut impl Iterator<Item = &'a str>,
+    known: &[&str],
+) -> Result<(&'a str, &'a str), String> {
+    let (name, inline) = match arg.split_once('=') {
+        Some((name, value)) => (name, Some(value)),
+        None => (arg, None),
+    };
+    if !known.contains(&name) {
+        return Err(format!("unknown option '{}'", name));
+    }
+    match inline {
+        Some(value) => Ok((name, value)),
+        None => rest.next().map(|value| (name, value)).ok_or_else(|| format!("{} needs a value", name)),
+    }
+}
+
+/// Parse an option value.
+fn parse_value<T: FromStr>(name: &str, value: &str, expected: &str) -> Result<T, String> {
+    value
+        .parse()
+        .map_err(|_| format!("{} expects {}, got '{}'", name, expected, value))
+}
+
+/// Parse the value of `--format`.
+fn parse_format(value: &str) -> Result<ConfigFormat, String> {
+    value.parse().map_err(|e| format!("--format: {}", e))
+}
+
+/// Parse a count that must be at least 1.
+fn parse_nonzero(name: &str, value: &str) -> Result<u32, String> {
+    match parse_value(name, value, "a positive integer")? {
+        0 => Err(format!("{} must be at least 1", name)),
+        count => Ok(count),
+    }
+}
@@ -0,0 +1,209 @@
+//! Columnar module: Arrow tables of simulation results.
+//!
+//! Enabled by the `arrow` feature. [`SimulationResults::to_arrow`] builds
+//! three in-memory Arrow record batches that dataframe libraries (polars,
+//! pyarrow, DuckDB) can take over without copying or touching disk:
+//!
+//! | Table | One row per | Columns |
+//! |-------|-------------|---------|
+//! | metrics | recorded step | `step`, then every field of `Metrics` |
+//! | trajectories | recorded step × entity | `step`, `entity_id`, `x`, `y`, `z`, `vx`, `vy`, `vz`, `essence`, `energy` |
+//! | attractions | recorded step × attracting pair | `step`, `source`, `target`, `force` |
+//!
+//! Floating-point columns are Float32, or Float64 with the `f64` feature.
+//! `z`/`vz` are null in 2D runs. The schemas are defined once here
+//! ([`metrics_schema`], [`trajectories_schema`], [`attractions_schema`]);
+//! the Parquet exporter (`parquet` feature) and IPC serialization write
+//! the same batches, so every format shares them.
+//!
+//! ## Author
+//! Ayomide I. Daniels (Morningstar)
+
+use crate::real::Real;
+use crate::metrics::Metrics;
+use crate::results::SimulationResults;
+use arrow::array::{ArrayRef, UInt32Array, UInt64Array};
+#[cfg(not(feature = "f64"))]
+use arrow::array::{Float32Array as RealArray, Float32Builder as RealBuilder};
+#[cfg(feature = "f64")]
+use arrow::array::{Float64Array as RealArray, Float64Builder as RealBuilder};
+use arrow::datatypes::{DataType, Field, Schema, SchemaRef};
+use arrow::error::ArrowError;
+use arrow::ipc::writer::StreamWriter;
+use arrow::record_batch::RecordBatch;
+use std::sync::Arc;
+
+/// Arrow type of [`Real`] columns.
+#[cfg(not(feature = "f64"))]
+const REAL_TYPE: DataType = DataType::Float32;
+
+/// Arrow type of [`Real`] columns.
+#[cfg(feature = "f64")]
+const REAL_TYPE: DataType = DataType::Float64;
+
+/// Schema of the metrics table.
+pub fn metrics_schema() -> SchemaRef {
+    let mut fields = vec![Field::new("step", DataType::UInt64, false)];
+    fields.extend(Metrics::FIELD_NAMES.iter().map(|name| Field::new(*name, REAL_TYPE, false)));
+    Arc::new(Schema::new(fields))
+}
+
+/// Schema of the trajectories table.
+pub fn trajectories_schema() -> SchemaRef {
+    Arc::new(Schema::new(vec![
+        Field::new("step", DataType::UInt64, false),
+        Field::new("entity_id", DataType::UInt32, false),
+        Field::new("x", REAL_TYPE, false),
+        Field::new("y", REAL_TYPE, false),
+        Field::new("z", REAL_TYPE, true),
+        Field::new("vx", REAL_TYPE, false),
+        Field::new("vy", REAL_TYPE, false),
+        Field::new("vz", REAL_TYPE, true),
+        Field::new("essence", REAL_TYPE, false),
+        Field::new("energy", REAL_TYPE, false),
+    ]))
+}
+
+/// Schema of the attractions table.
+pub fn attractions_schema() -> SchemaRef {
+    Arc::new(Schema::new(vec![
+        Field::new("step", DataType::UInt64, false),
+        Field::new("source", DataType::UInt32, false),
+        Field::new("target", DataType::UInt32, false),
+        Field::new("force", REAL_TYPE, false),
+    ]))
+}
+
+/// The three result tables.
+#[derive(Clone, Debug)]
+pub struct ArrowTables {
+    pub metrics: RecordBatch,
+    pub trajectories: RecordBatch,
+    pub attractions: RecordBatch,
+}
+
+impl ArrowTables {
+    /// Tables with their names, in a fixed order.
+    pub fn named(&self) -> [(&'static str, &RecordBatch); 3] {
+        [
+            ("metrics", &self.metrics),
+            ("trajectories", &self.trajectories),
+            ("attractions", &self.attractions),
+        ]
+    }
+}
+
+/// Serialize a batch in the Arrow IPC streaming format.
+///
+/// # Returns
+/// Bytes readable by `pyarrow.ipc.open_stream` or `polars.read_ipc_stream`
+pub fn to_ipc_stream(batch: &RecordBatch) -> Result<Vec<u8>, ArrowError> {
+    let mut buffer = Vec::new();
+    {
+        let mut writer = StreamWriter::try_new(&mut buffer, &batch.schema())?;
+        writer.write(batch)?;
+        writer.finish()?;
+    }
+    Ok(buffer)
+}
+
+impl SimulationResults {
+    /// Build the metrics, trajectories and attractions tables.
+    ///
+    /// Rows come from the recorded steps, in step order (entities by id).
+    ///
+    /// # Returns
+    /// Record batches using the schemas of this module
+    pub fn to_arrow(&self) -> Result<ArrowTables, ArrowError> {
+        Ok(ArrowTables {
+            metrics: self.metrics_batch()?,
+            trajectories: self.trajectories_batch()?,
+            attractions: self.attractions_batch()?,
+        })
+    }
+
+    /// Metrics table (one row per recorded step).
+    pub fn metrics_batch(&self) -> Result<RecordBatch, ArrowError> {
+        let mut columns: Vec<ArrayRef> = vec![Arc::new(UInt64Array::from_iter_values(
+            self.steps.iter().map(|s| s.step_number),
+        ))];
+        for k in 0..Metrics::FIELD_NAMES.len() {
+            columns.push(Arc::new(RealArray::from_iter_values(
+                self.steps.iter().map(|s| s.metrics.field_values()[k]),
+            )));
+        }
+        RecordBatch::try_new(metrics_schema(), columns)
+    }
+
+    /// Trajectories table (one row per recorded step and entity).
+    pub fn trajectories_batch(&self) -> Result<RecordBatch, ArrowError> {
+        let rows = self.trajectory_rows();
+        let mut coords: [RealBuilder; 6] = Default::default();
+        for row in &rows {
+            for axis in 0..3 {
+                coords[axis].append_option(axis_value(row.position, axis));
+                coords[axis + 3].append_option(axis_value(row.velocity, axis));
+            }
+        }
+
+        let mut columns: Vec<ArrayRef> = vec![
+            Arc::new(UInt64Array::from_iter_values(rows.iter().map(|r| r.step))),
+            Arc::new(UInt32Array::from_iter_values(rows.iter().map(|r| r.entity_id))),
+        ];
+        columns.extend(coords.iter_mut().map(|b| Arc::new(b.finish()) as ArrayRef));
+        columns.push(Arc::new(RealArray::from_iter_values(rows.iter().map(|r| r.essence))));
+        columns.push(Arc::new(RealArray::from_iter_values(rows.iter().map(|r| r.energy))));
+        RecordBatch::try_new(trajectories_schema(), columns)
+    }
+
+    /// Attractions table (one row per recorded step and attracting pair).
+    pub fn attractions_batch(&self) -> Result<RecordBatch, ArrowError> {
+        let rows = self
+            .steps
+            .iter()
+            .flat_map(|s| s.attractions.iter().map(move |a| (s.step_number, a)));
+        let (mut steps, mut sources, mut targets, mut forces) = (Vec::new(), Vec::new(), Vec::new(), Vec::new());
+        for (step, (source, target, force)) in rows {
+            steps.push(step);
+            sources.push(*source);
+            targets.push(*target);
+            forces.push(*force);
+        }
+        RecordBatch::try_new(
+            attractions_schema(),
+            vec![
+                Arc::new(UInt64Array::from(steps)),
+                Arc::new(UInt32Array::from(sources)),
+                Arc::new(UInt32Array::from(targets)),
+                Arc::new(RealArray::from(forces)),
+            ],
+        )
+    }
+
+    /// Write the three tables as `<prefix>_metrics.parquet`,
+    /// `<prefix>_trajectories.parquet` and `<prefix>_attractions.parquet`.
+    ///
+    /// # Returns
+    /// Paths of the written files
+    #[cfg(feature = "parquet")]
+    pub fn export_parquet(&self, prefix: &str) -> Result<Vec<String>, Box<dyn std::error::Error>> {
+        use parquet::arrow::ArrowWriter;
+
+        let tables = self.to_arrow()?;
+        let mut paths = Vec::new();
+        for (name, batch) in tables.named() {
+            let path = format!("{}_{}.parquet", prefix, name);
+            let file = std::fs::File::create(&path)?;
+            let mut writer = ArrowWriter::try_new(file, batch.schema(), None)?;
+            writer.write(batch)?;
+            writer.close()?;
+            paths.push(path);
+        }
+        Ok(paths)
+    }
+}
+
+/// Component `axis` of a vector, or null if the vector is shorter.
+fn axis_value(values: &[Real], axis: usize) -> Option<Real> {
+    values.get(axis).copied()
+}
@@ -11,20 +11,94 @@
 //! - **Geometry**: Dimensionality, spatial bounds, boundary conditions
 //! - **Attraction**: Kernel type (Gaussian/InverseDistance), sigma, lambda
 //! - **State**: Memory/context dimensions, decay rates
+//! - **Drives**: Innate drive distributions and situational responsiveness
 //! - **Dynamics**: Motion integration, velocity enforcement
 //! - **Essence**: Well-being baseline, decay, experience scaling
 //! - **Simulation**: Entity count, step count, timestep, random seed
+//! - **Energy**: Metabolic drain and replenishment rates
+//! - **Reproduction**: Offspring thresholds, mutation rates, population cap
+//! - **Signaling**: Emission probability, essence gate, hearing range
+//! - **Roles**: Leader percentile, attraction boost, follower bias, decay
+//! - **Environment**: Fixed attractors, repellers and obstacles
+//! - **Entities**: Per-entity labels and parameter overrides
+//! - **Species**: Optional named entity types with parameter overrides
+//! - **Plugins**: Stimulus source and observers selected by name
+//! - **Recording**: Which steps are recorded into the results, and which fields
+//! - **Schedules**: Parameters that change over the run (see [`crate::schedule`])
+//!
+//! ## Fingerprint
+//!
+//! [`SimulationConfig::fingerprint`] is the SHA-256 of the configuration's
+//! canonical TOML (keys sorted at every level), so two configurations have
+//! the same fingerprint exactly when every parameter is equal. Exported
+//! artifacts carry it to identify the configuration that produced them.
+//!
+//! ## Loading
+//!
+//! [`SimulationConfig::load`] reads TOML or JSON (chosen by a
+//! [`ConfigFormat`] or guessed from the text) from a file, or from standard
+//! input when the path is [`STDIN_PATH`] (`-`), so a generator script can
+//! pipe configurations straight into the binary.
+//!
+//! ## Inheritance
+//!
+//! A TOML configuration may start with `extends = "base.toml"` and contain
+//! only the fields that differ from its parent. The parent path is
+//! relative to the file naming it (to the current directory for standard
+//! input) and may itself extend another file, up to
+//! [`MAX_EXTENDS_DEPTH`] parents; cycles are reported. Tables are merged
+//! field by field with the child winning, while values such as arrays are
+//! replaced as a whole. [`SimulationConfig::from_toml`] and
+//! [`SimulationConfig::load`] resolve `extends` transparently, so the
+//! merged configuration is what runs, is fingerprinted and is embedded in
+//! the results.
+//!
+//! ## Checking
+//!
+//! [`SimulationConfig::validate`] is the gate every simulation passes and
+//! stops at the first problem. [`SimulationConfig::problems`] runs the same
+//! rules field by field plus extended checks (kernel parameters, rates and
+//! thresholds that must lie in `[0, 1]`, an empty run) and reports every
+//! problem with the TOML path of its field, for the binary's `validate`
+//! subcommand.
+//!
+//! ## Command-Line Overrides
+//!
+//! `key=value` arguments (e.g. `sigma=2.0 dynamics.dt=0.005`) override
+//! individual fields; see [`SimulationConfig::apply_cli_args`] for the
+//! accepted keys.
+//!
+//! ## Runtime Patches
+//!
+//! A [`SimulationConfigPatch`] changes a few parameters of a running
+//! simulation (`Simulation::apply_config_patch`), e.g. from a control law
+//! reacting to the metrics of the previous step.
 //!
 //! ## Author
 //! Ayomide I. Daniels (Morningstar)
 
 use serde::{Deserialize, Serialize};
+use crate::real::Real;
 use crate::geometry::GeometryConfig;
 use crate::attraction::AttractionConfig;
 use crate::state::StateConfig;
-use crate::dynamics::DynamicsConfig;
+use crate::dynamics::{DriveConfig, DynamicsConfig};
 use crate::essence::EssenceConfig;
+use crate::energy::EnergyConfig;
+use crate::environment::EnvironmentConfig;
+use crate::entities::EntityConfig;
+use crate::reproduction::ReproductionConfig;
+use crate::roles::RoleConfig;
+use crate::signaling::SignalingConfig;
+use crate::plugin::PluginsConfig;
+use crate::results::RecordingConfig;
+use crate::schedule::{parameter_mut, ParameterSchedule, SCHEDULABLE_PARAMETERS, STIMULUS_AMPLITUDE};
+use crate::species::SpeciesConfig;
+use crate::error::SimulationError;
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
 use std::fs;
+use std::path::{Path, PathBuf};
 
 /// Complete simulation configuration.
 /// 
@@ -37,8 +111,43 @@ pub struct SimulationConfig {
     pub attraction: AttractionConfig,
     pub state: StateConfig,
     pub dynamics: DynamicsConfig,
+    /// Innate drive distributions and situational responsiveness.
+    #[serde(default)]
+    pub drives: DriveConfig,
     pub essence: EssenceConfig,
     pub simulation: SimulationParams,
+    /// Metabolism (disabled by default, leaving energy constant).
+    #[serde(default)]
+    pub energy: EnergyConfig,
+    /// Offspring spawning (disabled by default).
+    #[serde(default)]
+    pub reproduction: ReproductionConfig,
+    /// Direct signaling between entities (disabled by default).
+    #[serde(default)]
+    pub signaling: SignalingConfig,
+    /// Leader/follower role dynamics (disabled by default).
+    #[serde(default)]
+    pub roles: RoleConfig,
+    /// Fixed points shaping the attraction field (none by default).
+    #[serde(default)]
+    pub environment: EnvironmentConfig,
+    /// Named entity species (empty = homogeneous population).
+    #[serde(default)]
+    pub species: BTreeMap<String, SpeciesConfig>,
+    /// Per-entity settings (e.g. labels), keyed by entity id.
+    #[serde(default)]
+    pub entities: Vec<EntityConfig>,
+    /// Plugins selected by name (none by default).
+    #[serde(default)]
+    pub plugins: PluginsConfig,
+    /// Cadence and fields of the recorded steps (every step, everything
+    /// by default).
+    #[serde(default)]
+    pub recording: RecordingConfig,
+    /// Parameters changing over the run, keyed by parameter path (none by
+    /// default).
+    #[serde(default)]
+    pub schedules: BTreeMap<String, ParameterSchedule>,
 }
 
 /// Metadata about the simulation.
@@ -58,8 +167,37 @@ pub struct MetadataConfig {
 pub struct SimulationParams {
     pub num_entities: u32,
     pub num_steps: u32,
-    pub dt: f32,
+    pub dt: Real,
+    /// Seed of the random stream; equal seeds give identical runs
     pub seed: u64,
+    /// Additionally compute metrics separately for each species.
+    #[serde(default)]
+    pub per_species_metrics: bool,
+    /// Spatial layout used to place entities at initialization.
+    #[serde(default)]
+    pub initial_layout: InitialLayout,
+    /// Record attention distributions (`SimulationStep::attentions`) every
+    /// this many steps; 0 never records them. They are the largest part of
+    /// a recorded step (one value per entity pair).
+    #[serde(default = "default_attention_capture_interval")]
+    pub attention_capture_interval: u32,
+    /// Time every phase of a step (see `Simulation::phase_timings`).
+    #[serde(default)]
+    pub profile_phases: bool,
+}
+
+fn default_attention_capture_interval() -> u32 {
+    1
+}
+
+/// Initial placement of entities in space.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum InitialLayout {
+    /// Uniformly random positions within bounds.
+    #[default]
+    Random,
+    /// Regular lattice filling the bounds (cell-centered).
+    Grid,
 }
 
 impl SimulationConfig {
@@ -72,8 +210,82 @@ impl SimulationConfig {
     /// Parsed configuration or error if file cannot be read/parsed
     pub fn from_toml(path: &str) -> Result<Self, Box<dyn std::error::Error>> {
         let contents = fs::read_to_string(path)?;
-        let config = toml::from_str(&contents)?;
-        Ok(config)
+        Ok(Self::parse_toml(&contents, Some(Path::new(path)))?)
+    }
+
+    /// Parse a configuration from TOML or JSON text.
+    ///
+    /// A TOML `extends` path is resolved relative to the current directory.
+    ///
+    /// # Arguments
+    /// * `contents` - Configuration text
+    /// * `format` - Format of the text, or None to guess with [`ConfigFormat::sniff`]
+    ///
+    /// # Returns
+    /// Parsed configuration or the parser's message
+    pub fn parse(contents: &str, format: Option<ConfigFormat>) -> Result<Self, String> {
+        Self::parse_from(contents, format, None)
+    }
+
+    /// Parse TOML or JSON text read from `origin` (None for standard input).
+    fn parse_from(contents: &str, format: Option<ConfigFormat>, origin: Option<&Path>) -> Result<Self, String> {
+        match format.unwrap_or_else(|| ConfigFormat::sniff(contents)) {
+            ConfigFormat::Toml => Self::parse_toml(contents, origin),
+            ConfigFormat::Json => serde_json::from_str(contents).map_err(|e| format!("JSON parse error: {}", e)),
+        }
+    }
+
+    /// Parse TOML text, merging in the files named by `extends`.
+    fn parse_toml(contents: &str, origin: Option<&Path>) -> Result<Self, String> {
+        let table: toml::Table = contents.parse().map_err(|e: toml::de::Error| e.to_string())?;
+        if !table.contains_key(EXTENDS_KEY) {
+            // Straight from the text, so errors keep their line and column
+            return toml::from_str(contents).map_err(|e| e.to_string());
+        }
+        let mut chain: Vec<PathBuf> = origin.and_then(|path| path.canonicalize().ok()).into_iter().collect();
+        let merged = resolve_extends(table, origin, &mut chain)?;
+        // Re-parsed as text so errors quote the offending line of the merge
+        let merged = toml::to_string(&merged).map_err(|e| e.to_string())?;
+        toml::from_str(&merged).map_err(|e| format!("in the merged configuration: {}", e))
+    }
+
+    /// Load a configuration from a file, or from standard input for `-`.
+    ///
+    /// Standard input is read to its end; empty input is an error.
+    ///
+    /// # Arguments
+    /// * `path` - Path to the configuration file, or [`STDIN_PATH`]
+    /// * `format` - Format of the contents, or None to guess from them
+    ///
+    /// # Returns
+    /// Parsed configuration, or why it could not be read or parsed
+    pub fn load(path: &str, format: Option<ConfigFormat>) -> Result<Self, String> {
+        let contents = if path == STDIN_PATH {
+            let contents = read_stdin()?;
+            if contents.trim().is_empty() {
+                return Err("no configuration on standard input".to_string());
+            }
+            contents
+        } else {
+            fs::read_to_string(path).map_err(|e| format!("cannot read '{}': {}", path, e))?
+        };
+        let origin = (path != STDIN_PATH).then(|| Path::new(path));
+        Self::parse_from(&contents, format, origin)
+    }
+
+    /// Load a configuration and list its problems.
+    ///
+    /// # Arguments
+    /// * `path` - Path to the configuration file, or [`STDIN_PATH`]
+    /// * `format` - Format of the contents, or None to guess from them
+    ///
+    /// # Returns
+    /// The configuration and its [`problems`](Self::problems), or the read
+    /// or parse error if the input is not a configuration at all
+    pub fn check_file(path: &str, format: Option<ConfigFormat>) -> Result<(Self, Vec<ConfigProblem>), String> {
+        let config = Self::load(path, format)?;
+        let problems = config.problems();
+        Ok((config, problems))
     }
 
     /// Save configuration to TOML file.
@@ -89,6 +301,31 @@ impl SimulationConfig {
         Ok(())
     }
 
+    /// Serialize to TOML with keys sorted at every level.
+    /// 
+    /// # Returns
+    /// Canonical TOML text, identical for equal configurations
+    pub fn canonical_toml(&self) -> Result<String, Box<dyn std::error::Error>> {
+        let value = sort_toml_keys(toml::Value::try_from(self)?);
+        Ok(toml::to_string(&value)?)
+    }
+
+    /// SHA-256 of the canonical TOML as a lowercase hex string.
+    /// 
+    /// # Returns
+    /// 64-character fingerprint of this configuration
+    pub fn fingerprint(&self) -> String {
+        use sha2::{Digest, Sha256};
+
+        let canonical = self
+            .canonical_toml()
+            .expect("configuration is always representable as TOML");
+        Sha256::digest(canonical.as_bytes())
+            .iter()
+            .map(|byte| format!("{:02x}", byte))
+            .collect()
+    }
+
     /// Default configuration for 2D plane.
     /// 
     /// Creates a reasonable starting configuration for 2D simulations
@@ -112,6 +349,8 @@ impl SimulationConfig {
                 kernel: crate::attraction::KernelType::Gaussian,
                 sigma: 1.0,
                 lambda: 0.5,
+                theta: 0.0,
+                cutoff: None,
             },
             state: StateConfig {
                 memory_dim: 100,
@@ -119,12 +358,18 @@ impl SimulationConfig {
                 decay_alpha: 0.95,
                 beta_attention: 0.5,
                 gamma_memory: 0.3,
+                dedup_threshold: None,
             },
             dynamics: DynamicsConfig {
                 dt: 0.01,
                 min_speed: 0.05,
                 damping: 0.99,
+                max_action_force: 0.05,
+                legacy_pipeline: false,
+                auto_heading: true,
+                max_turn_rate: 10.0,
             },
+            drives: DriveConfig::default(),
             essence: EssenceConfig {
                 baseline: 5.0,
                 decay: 0.1,
@@ -135,7 +380,21 @@ impl SimulationConfig {
                 num_steps: 1000,
                 dt: 0.01,
                 seed: 42,
+                per_species_metrics: false,
+                initial_layout: InitialLayout::Random,
+                attention_capture_interval: 1,
+                profile_phases: false,
             },
+            energy: EnergyConfig::default(),
+            reproduction: ReproductionConfig::default(),
+            signaling: SignalingConfig::default(),
+            roles: RoleConfig::default(),
+            environment: EnvironmentConfig::default(),
+            species: BTreeMap::new(),
+            entities: Vec::new(),
+            plugins: PluginsConfig::default(),
+            recording: RecordingConfig::default(),
+            schedules: BTreeMap::new(),
         }
     }
 
@@ -155,6 +414,103 @@ impl SimulationConfig {
         config
     }
 
+    /// Library of named research presets.
+    /// 
+    /// Each preset is a complete, valid configuration for a well-known
+    /// experiment type; its metadata name and description explain the
+    /// purpose. Available presets:
+    /// - `minimal_2d`: 5 entities, 500 steps smoke test
+    /// - `dense_3d`: 50 entities, 2000 steps in a crowded 3D volume
+    /// - `social_emergence`: wide, strong coupling plus frequent signaling,
+    ///   so beliefs spread between neighbors
+    /// - `chaos_regime`: high softmax temperature and large kernel sigma
+    /// - `crystalline`: low temperature with lattice initialization
+    /// - `hebbian_learning`: repeated experiences reinforce their memory
+    ///   node, with strong consolidation and slow forgetting
+    /// 
+    /// # Returns
+    /// Map from preset name to configuration
+    pub fn preset_library() -> HashMap<String, SimulationConfig> {
+        let mut presets = HashMap::new();
+
+        let mut minimal = Self::default_2d();
+        minimal.simulation.num_entities = 5;
+        minimal.simulation.num_steps = 500;
+        minimal.metadata.name = "minimal_2d".to_string();
+        minimal.metadata.description =
+            "Smallest meaningful 2D run (5 entities, 500 steps) for smoke tests and quick iteration"
+                .to_string();
+        presets.insert("minimal_2d".to_string(), minimal);
+
+        let mut dense = Self::default_3d();
+        dense.simulation.num_entities = 50;
+        dense.simulation.num_steps = 2000;
+        dense.metadata.name = "dense_3d".to_string();
+        dense.metadata.description =
+            "Crowded 3D volume (50 entities, 2000 steps) where many kernels overlap".to_string();
+        presets.insert("dense_3d".to_string(), dense);
+
+        let mut social = Self::default_2d();
+        social.simulation.num_entities = 30;
+        social.simulation.num_steps = 2000;
+        social.attraction.sigma = 3.0;
+        social.essence.experience_scale = 2.0;
+        social.state.beta_attention = 0.8;
+        social.signaling.enabled = true;
+        social.signaling.emit_probability = 0.2;
+        // Any entity may signal, not only those with extreme essence
+        social.signaling.essence_extremity = 0.0;
+        social.signaling.hearing_range = 4.0;
+        social.metadata.name = "social_emergence".to_string();
+        social.metadata.description =
+            "High contagion: wide attraction kernel, strong attention coupling, amplified \
+             experience, and every entity frequently broadcasting its dominant belief to its \
+             neighbors"
+                .to_string();
+        presets.insert("social_emergence".to_string(), social);
+
+        let mut chaos = Self::default_2d();
+        chaos.simulation.num_entities = 20;
+        chaos.simulation.num_steps = 2000;
+        chaos.attraction.lambda = 5.0;
+        chaos.attraction.sigma = 5.0;
+        chaos.dynamics.damping = 1.0;
+        chaos.metadata.name = "chaos_regime".to_string();
+        chaos.metadata.description =
+            "High softmax temperature and large kernel sigma with undamped motion to probe \
+             sensitive, chaotic dynamics"
+                .to_string();
+        presets.insert("chaos_regime".to_string(), chaos);
+
+        let mut crystalline = Self::default_2d();
+        crystalline.simulation.num_entities = 16;
+        crystalline.simulation.num_steps = 1000;
+        crystalline.simulation.initial_layout = InitialLayout::Grid;
+        crystalline.attraction.lambda = 0.05;
+        crystalline.attraction.sigma = 0.5;
+        crystalline.dynamics.damping = 0.9;
+        crystalline.metadata.name = "crystalline".to_string();
+        crystalline.metadata.description =
+            "Low temperature, narrow kernel and strong damping from a lattice initialization \
+             to study ordered, near-static configurations"
+                .to_string();
+        presets.insert("crystalline".to_string(), crystalline);
+
+        let mut hebbian = Self::default_2d();
+        hebbian.simulation.num_steps = 3000;
+        hebbian.state.gamma_memory = 0.8;
+        hebbian.state.decay_alpha = 0.995;
+        hebbian.state.dedup_threshold = Some(0.95);
+        hebbian.metadata.name = "hebbian_learning".to_string();
+        hebbian.metadata.description =
+            "Repeated experiences reinforce the memory node they match instead of adding one \
+             (Hebbian style), with strong memory consolidation (gamma) and slow forgetting"
+                .to_string();
+        presets.insert("hebbian_learning".to_string(), hebbian);
+
+        presets
+    }
+
     /// Validate configuration parameters.
     /// 
     /// Checks that all configuration values are within acceptable ranges
@@ -167,14 +523,834 @@ impl SimulationConfig {
             return Err("Geometry configuration invalid".to_string());
         }
 
+        if self.attraction.theta.is_nan() || self.attraction.theta < 0.0 {
+            return Err("Barnes-Hut theta must be non-negative".to_string());
+        }
+
+        if self.attraction.cutoff.is_some_and(|c| !(c > 0.0 && c.is_finite())) {
+            return Err("Attraction cutoff must be positive".to_string());
+        }
+
         if self.state.memory_dim == 0 || self.state.context_dim == 0 {
             return Err("State dimensions must be positive".to_string());
         }
 
-        if self.dynamics.dt <= 0.0 || self.dynamics.min_speed < 0.0 {
+        if self.state.dedup_threshold.is_some_and(|t| !(-1.0..=1.0).contains(&t)) {
+            return Err("Memory dedup threshold must be within [-1, 1]".to_string());
+        }
+
+        if self.dynamics.dt <= 0.0
+            || self.dynamics.min_speed < 0.0
+            || self.dynamics.max_action_force < 0.0
+            || self.dynamics.max_turn_rate <= 0.0
+        {
             return Err("Dynamics parameters must be valid".to_string());
         }
 
+        if let Err((i, message)) = self.environment.check(self.geometry.dimension) {
+            return Err(format!("Environment point {}: {}", i, message));
+        }
+
+        if !self.drives.is_valid() {
+            return Err("Drive distributions must be valid".to_string());
+        }
+
+        if self.energy.capacity < 0.0
+            || self.energy.initial < 0.0
+            || self.energy.initial > self.energy.capacity
+        {
+            return Err("Energy initial value must be within [0, capacity]".to_string());
+        }
+
+        if self.energy.speed_drain < 0.0 || self.energy.action_drain < 0.0 || self.energy.replenish_rate < 0.0 {
+            return Err("Energy drain and replenish rates must be non-negative".to_string());
+        }
+
+        if !self.reproduction.is_valid() {
+            return Err("Reproduction parameters out of range".to_string());
+        }
+
+        if !self.signaling.is_valid() {
+            return Err("Signaling parameters out of range".to_string());
+        }
+
+        if !self.roles.is_valid() {
+            return Err("Role parameters out of range".to_string());
+        }
+
+        for (name, species) in &self.species {
+            if !species.is_valid() {
+                return Err(format!("Species '{}' has invalid overrides", name));
+            }
+        }
+
+        for entity in &self.entities {
+            if entity.id == 0 || entity.id > self.total_entities() {
+                return Err(format!("Entity config refers to unknown entity id {}", entity.id));
+            }
+            if !entity.is_valid() {
+                return Err(format!("Entity {} has invalid overrides", entity.id));
+            }
+        }
+
+        if !self.plugins.is_valid() {
+            return Err("Every selected plugin needs a name".to_string());
+        }
+
+        if self.recording.record_every == 0 {
+            return Err("Recording cadence must be at least 1".to_string());
+        }
+
+        for (path, schedule) in &self.schedules {
+            if !SCHEDULABLE_PARAMETERS.contains(&path.as_str()) {
+                return Err(format!("Schedule for unknown parameter '{}'", path));
+            }
+            schedule.check().map_err(|e| format!("Schedule for '{}': {}", path, e))?;
+            for value in [schedule.start, schedule.end] {
+                if path == STIMULUS_AMPLITUDE {
+                    if value < 0.0 {
+                        return Err(format!("Schedule for '{}' reaches a negative amplitude", path));
+                    }
+                    continue;
+                }
+                self.with_scheduled_value(path, value)
+                    .validate()
+                    .map_err(|e| format!("Schedule for '{}' reaches {}: {}", path, value, e))?;
+            }
+        }
+
         Ok(())
     }
+
+    /// Copy without schedules, with the parameter at `path` set to `value`.
+    fn with_scheduled_value(&self, path: &str, value: Real) -> SimulationConfig {
+        let mut config = self.clone();
+        config.schedules.clear();
+        if let Some(parameter) = parameter_mut(&mut config, path) {
+            *parameter = value;
+        }
+        config
+    }
+
+    /// Every problem of the configuration, with the TOML path of its field.
+    ///
+    /// Covers each rule of [`validate`](Self::validate) (so the list is
+    /// empty only if `validate` passes) and additionally requires a positive
+    /// kernel `sigma`, a non-negative `lambda`, positive bounds and time
+    /// steps, decay rates within [0, 1] and at least one entity and step.
+    ///
+    /// # Returns
+    /// The problems in section order; empty for a usable configuration
+    pub fn problems(&self) -> Vec<ConfigProblem> {
+        let mut problems = Vec::new();
+        let mut require = |ok: bool, path: &str, message: &str| {
+            if !ok {
+                problems.push(ConfigProblem::new(path, message));
+            }
+        };
+        let unit = |v: Real| (0.0..=1.0).contains(&v);
+        let non_negative = |v: Real| v >= 0.0 && v.is_finite();
+        let positive = |v: Real| v > 0.0 && v.is_finite();
+
+        let geometry = &self.geometry;
+        require((2..=3).contains(&geometry.dimension), "geometry.dimension", "must be 2 or 3");
+        require(
+            geometry.bounds.len() == geometry.dimension,
+            "geometry.bounds",
+            &format!("needs one bound per dimension ({} given for {}D)", geometry.bounds.len(), geometry.dimension),
+        );
+        for (axis, bound) in geometry.bounds.iter().enumerate() {
+            require(positive(*bound), &format!("geometry.bounds[{}]", axis), "must be positive");
+        }
+
+        require(positive(self.attraction.sigma), "attraction.sigma", "kernel width must be positive");
+        require(non_negative(self.attraction.lambda), "attraction.lambda", "softmax temperature must be non-negative");
+        require(self.attraction.theta >= 0.0, "attraction.theta", "Barnes-Hut theta must be non-negative");
+        require(self.attraction.cutoff.is_none_or(positive), "attraction.cutoff", "pair cutoff must be positive");
+
+        require(self.state.memory_dim > 0, "state.memory_dim", "must be positive");
+        require(self.state.context_dim > 0, "state.context_dim", "must be positive");
+        require(unit(self.state.decay_alpha), "state.decay_alpha", "must be within [0, 1]");
+        require(non_negative(self.state.beta_attention), "state.beta_attention", "must be non-negative");
+        require(non_negative(self.state.gamma_memory), "state.gamma_memory", "must be non-negative");
+        require(
+            self.state.dedup_threshold.is_none_or(|t| (-1.0..=1.0).contains(&t)),
+            "state.dedup_threshold",
+            "must be within [-1, 1]",
+        );
+
+        require(positive(self.dynamics.dt), "dynamics.dt", "must be positive");
+        require(self.dynamics.min_speed >= 0.0, "dynamics.min_speed", "must be non-negative");
+        require(unit(self.dynamics.damping), "dynamics.damping", "must be within [0, 1]");
+        require(self.dynamics.max_action_force >= 0.0, "dynamics.max_action_force", "must be non-negative");
+        require(self.dynamics.max_turn_rate > 0.0, "dynamics.max_turn_rate", "must be positive");
+
+        require(self.drives.preservation.is_valid(), "drives.preservation", "distribution parameters out of range");
+        require(self.drives.curiosity.is_valid(), "drives.curiosity", "distribution parameters out of range");
+        require(self.drives.responsiveness >= 0.0, "drives.responsiveness", "must be non-negative");
+
+        require((0.0..=10.0).contains(&self.essence.baseline), "essence.baseline", "must be within [0, 10]");
+        require(unit(self.essence.decay), "essence.decay", "must be within [0, 1]");
+        require(non_negative(self.essence.experience_scale), "essence.experience_scale", "must be non-negative");
+
+        if let Err((i, message)) = self.environment.check(self.geometry.dimension) {
+            require(false, &format!("environment.points[{}]", i), &message);
+        }
+
+        require(self.simulation.num_entities > 0, "simulation.num_entities", "must be at least 1");
+        require(self.simulation.num_steps > 0, "simulation.num_steps", "must be at least 1");
+        require(positive(self.simulation.dt), "simulation.dt", "must be positive");
+
+        require(self.energy.capacity >= 0.0, "energy.capacity", "must be non-negative");
+        require(
+            self.energy.initial >= 0.0 && self.energy.initial <= self.energy.capacity,
+            "energy.initial",
+            "must be within [0, capacity]",
+        );
+        require(non_negative(self.energy.speed_drain), "energy.speed_drain", "must be non-negative");
+        require(non_negative(self.energy.action_drain), "energy.action_drain", "must be non-negative");
+        require(non_negative(self.energy.replenish_rate), "energy.replenish_rate", "must be non-negative");
+
+        let reproduction = &self.reproduction;
+        require(unit(reproduction.energy_transfer), "reproduction.energy_transfer", "must be within [0, 1]");
+        require(unit(reproduction.prototype_inheritance), "reproduction.prototype_inheritance", "must be within [0, 1]");
+        require(reproduction.spawn_radius >= 0.0, "reproduction.spawn_radius", "must be non-negative");
+        require(reproduction.trait_mutation >= 0.0, "reproduction.trait_mutation", "must be non-negative");
+        require(reproduction.drive_mutation >= 0.0, "reproduction.drive_mutation", "must be non-negative");
+
+        require(unit(self.signaling.emit_probability), "signaling.emit_probability", "must be within [0, 1]");
+        require(self.signaling.essence_extremity >= 0.0, "signaling.essence_extremity", "must be non-negative");
+        require(self.signaling.hearing_range > 0.0, "signaling.hearing_range", "must be positive");
+
+        require(unit(self.roles.leader_percentile), "roles.leader_percentile", "must be within [0, 1]");
+        require(unit(self.roles.decay), "roles.decay", "must be within [0, 1]");
+        require(self.roles.attraction_boost >= 0.0, "roles.attraction_boost", "must be non-negative");
+        require(self.roles.follower_bias >= 0.0, "roles.follower_bias", "must be non-negative");
+
+        for (name, species) in &self.species {
+            let path = format!("species.{}", name);
+            require(species.mass.is_none_or(|m| m > 0.0), &format!("{}.mass", path), "must be positive");
+            require(
+                species.stimulus_amplitude.is_none_or(|a| a >= 0.0),
+                &format!("{}.stimulus_amplitude", path),
+                "must be non-negative",
+            );
+            require(
+                species.attraction_weight.is_none_or(|w| w >= 0.0),
+                &format!("{}.attraction_weight", path),
+                "must be non-negative",
+            );
+            let essence = &species.essence;
+            require(
+                essence.baseline.is_none_or(|b| (0.0..=10.0).contains(&b)),
+                &format!("{}.essence.baseline", path),
+                "must be within [0, 10]",
+            );
+            require(essence.decay.is_none_or(unit), &format!("{}.essence.decay", path), "must be within [0, 1]");
+            require(
+                essence.experience_scale.is_none_or(|s| s >= 0.0),
+                &format!("{}.essence.experience_scale", path),
+                "must be non-negative",
+            );
+            let state = &species.state;
+            require(state.decay_alpha.is_none_or(unit), &format!("{}.state.decay_alpha", path), "must be within [0, 1]");
+            require(
+                state.beta_attention.is_none_or(|b| b >= 0.0),
+                &format!("{}.state.beta_attention", path),
+                "must be non-negative",
+            );
+            require(
+                state.gamma_memory.is_none_or(|g| g >= 0.0),
+                &format!("{}.state.gamma_memory", path),
+                "must be non-negative",
+            );
+        }
+
+        let total_entities = self.total_entities();
+        for (index, entity) in self.entities.iter().enumerate() {
+            let path = format!("entities[{}]", index);
+            require(
+                entity.id != 0 && entity.id <= total_entities,
+                &format!("{}.id", path),
+                &format!("refers to unknown entity id {} (ids are 1 to {})", entity.id, total_entities),
+            );
+            require(entity.mass.is_none_or(|m| m > 0.0), &format!("{}.mass", path), "must be positive");
+            require(
+                entity.stimulus_amplitude.is_none_or(|a| a >= 0.0),
+                &format!("{}.stimulus_amplitude", path),
+                "must be non-negative",
+            );
+            require(
+                entity.attraction_weight.is_none_or(|w| w >= 0.0),
+                &format!("{}.attraction_weight", path),
+                "must be non-negative",
+            );
+        }
+
+        if let Some(stimulus) = &self.plugins.stimulus {
+            require(!stimulus.name.is_empty(), "plugins.stimulus.name", "must not be empty");
+        }
+        for (index, observer) in self.plugins.observers.iter().enumerate() {
+            require(!observer.name.is_empty(), &format!("plugins.observers[{}].name", index), "must not be empty");
+        }
+
+        require(self.recording.record_every > 0, "recording.record_every", "must be at least 1");
+
+        for (path, schedule) in &self.schedules {
+            let field = format!("schedules.\"{}\"", path);
+            if !SCHEDULABLE_PARAMETERS.contains(&path.as_str()) {
+                require(false, &field, "is not a parameter that can be scheduled");
+                continue;
+            }
+            if let Err(e) = schedule.check() {
+                require(false, &field, &e);
+                continue;
+            }
+            for (end, value) in [("start", schedule.start), ("end", schedule.end)] {
+                if path == STIMULUS_AMPLITUDE {
+                    require(value >= 0.0, &format!("{}.{}", field, end), "must be non-negative");
+                    continue;
+                }
+                for problem in self.with_scheduled_value(path, value).problems() {
+                    if problem.path == *path {
+                        require(false, &format!("{}.{}", field, end), &problem.message);
+                    }
+                }
+            }
+        }
+
+        problems
+    }
+
+    /// One-screen summary of the effective configuration.
+    ///
+    /// # Returns
+    /// Aligned `label: value` lines, one per section
+    pub fn summary(&self) -> String {
+        let enabled = |on: bool| if on { "enabled" } else { "disabled" };
+        let plugins: Vec<&str> = self
+            .plugins
+            .stimulus
+            .iter()
+            .chain(&self.plugins.observers)
+            .map(|spec| spec.name.as_str())
+            .collect();
+        let lines = [
+            ("Name", format!("{} (v{})", self.metadata.name, self.metadata.version)),
+            (
+                "Geometry",
+                format!(
+                    "{}D, bounds {:?}, {}",
+                    self.geometry.dimension,
+                    self.geometry.bounds,
+                    if self.geometry.periodic { "periodic" } else { "bounded" }
+                ),
+            ),
+            (
+                "Run",
+                format!(
+                    "{} entities, {} steps, dt {}, seed {}, {:?} layout",
+                    self.total_entities(),
+                    self.simulation.num_steps,
+                    self.simulation.dt,
+                    self.simulation.seed,
+                    self.simulation.initial_layout
+                ),
+            ),
+            (
+                "Attraction",
+                format!(
+                    "{:?} kernel, sigma {}, lambda {}, theta {}, pair cutoff {}",
+                    self.attraction.kernel,
+                    self.attraction.sigma,
+                    self.attraction.lambda,
+                    self.attraction.theta,
+                    self.attraction.effective_cutoff()
+                ),
+            ),
+            (
+                "State",
+                format!(
+                    "memory {}, context {}, decay {}, dedup {}",
+                    self.state.memory_dim,
+                    self.state.context_dim,
+                    self.state.decay_alpha,
+                    self.state.dedup_threshold.map_or("off".to_string(), |t| t.to_string())
+                ),
+            ),
+            (
+                "Dynamics",
+                format!(
+                    "dt {}, min speed {}, damping {}",
+                    self.dynamics.dt, self.dynamics.min_speed, self.dynamics.damping
+                ),
+            ),
+            (
+                "Essence",
+                format!(
+                    "baseline {}, decay {}, experience scale {}",
+                    self.essence.baseline, self.essence.decay, self.essence.experience_scale
+                ),
+            ),
+            (
+                "Subsystems",
+                format!(
+                    "energy {}, reproduction {}, signaling {}, roles {}",
+                    enabled(self.energy.enabled),
+                    enabled(self.reproduction.enabled),
+                    enabled(self.signaling.enabled),
+                    enabled(self.roles.enabled)
+                ),
+            ),
+            (
+                "Population",
+                format!("{} species, {} entity overrides", self.species.len(), self.entities.len()),
+            ),
+            (
+                "Environment",
+                if self.environment.is_empty() {
+                    "featureless".to_string()
+                } else {
+                    let points = &self.environment.points;
+                    let count = |f: &dyn Fn(&crate::environment::EnvironmentPoint) -> bool| points.iter().filter(|p| f(p)).count();
+                    format!(
+                        "{} attractors, {} repellers, {} obstacles",
+                        count(&|p| !p.obstacle && p.strength > 0.0),
+                        count(&|p| !p.obstacle && p.strength < 0.0),
+                        count(&|p| p.obstacle)
+                    )
+                },
+            ),
+            ("Recording", self.recording_summary()),
+            (
+                "Schedules",
+                if self.schedules.is_empty() {
+                    "none".to_string()
+                } else {
+                    self.schedules
+                        .iter()
+                        .map(|(path, schedule)| format!("{} {}", path, schedule.describe()))
+                        .collect::<Vec<_>>()
+                        .join("; ")
+                },
+            ),
+            ("Plugins", if plugins.is_empty() { "none".to_string() } else { plugins.join(", ") }),
+            ("Fingerprint", self.fingerprint()),
+        ];
+        let lines: Vec<String> = lines.iter().map(|(label, value)| format!("{:<13}{}", format!("{}:", label), value)).collect();
+        lines.join("\n")
+    }
+
+    /// Recording cadence and the optional fields left out, for `summary()`.
+    fn recording_summary(&self) -> String {
+        let recording = &self.recording;
+        let skipped: Vec<&str> = [
+            ("positions", recording.positions),
+            ("attractions", recording.attractions),
+            ("clusters", recording.clusters),
+            ("attentions", recording.attentions),
+        ]
+        .iter()
+        .filter(|(_, on)| !on)
+        .map(|(name, _)| *name)
+        .collect();
+        let cadence = match recording.record_every {
+            1 => "every step".to_string(),
+            n => format!("every {} steps", n),
+        };
+        if skipped.is_empty() {
+            cadence
+        } else {
+            format!("{}, without {}", cadence, skipped.join(", "))
+        }
+    }
+
+    /// Build a configuration from `key=value` command-line arguments.
+    /// 
+    /// Starts from [`SimulationConfig::default_2d`] and applies every
+    /// argument with [`SimulationConfig::apply_cli_args`].
+    /// 
+    /// # Arguments
+    /// * `args` - Arguments such as `["sigma=2.0", "num_entities=20"]`
+    /// 
+    /// # Returns
+    /// The validated configuration, or the first key/value that was rejected
+    pub fn from_cli_args(args: &[String]) -> Result<Self, SimulationError> {
+        let mut config = Self::default_2d();
+        config.apply_cli_args(args)?;
+        Ok(config)
+    }
+
+    /// Override fields from `key=value` command-line arguments.
+    /// 
+    /// Keys use section-qualified names (`geometry.dimension`,
+    /// `dynamics.dt`, ...). The common parameters also accept a bare name:
+    /// `num_entities`, `num_steps`, `seed`, `layout`, `dimension`,
+    /// `periodic`, `kernel`, `sigma`, `lambda`, `damping`, `name`.
+    /// Changing the dimension resizes `geometry.bounds` by repeating the
+    /// first bound. The result is validated after all overrides are applied.
+    /// 
+    /// # Arguments
+    /// * `args` - Arguments of the form `key=value`
+    /// 
+    /// # Returns
+    /// `ValidationError` for malformed arguments, unknown keys or an invalid
+    /// result; `ParseError` for values of the wrong type
+    pub fn apply_cli_args(&mut self, args: &[String]) -> Result<(), SimulationError> {
+        for arg in args {
+            let (key, value) = arg.split_once('=').ok_or_else(|| {
+                SimulationError::ValidationError(format!("expected key=value, got '{}'", arg))
+            })?;
+            self.apply_cli_arg(key.trim(), value.trim())?;
+        }
+        self.validate().map_err(SimulationError::ValidationError)
+    }
+
+    /// Apply a single `key=value` override.
+    fn apply_cli_arg(&mut self, key: &str, value: &str) -> Result<(), SimulationError> {
+        match key {
+            "metadata.name" | "name" => self.metadata.name = value.to_string(),
+            "metadata.description" => self.metadata.description = value.to_string(),
+
+            "geometry.dimension" | "dimension" => {
+                let dimension: usize = parse_cli_value(key, value, "a positive integer")?;
+                let bound = self.geometry.bounds.first().copied().unwrap_or(10.0);
+                self.geometry.dimension = dimension;
+                self.geometry.bounds.resize(dimension, bound);
+            }
+            "geometry.periodic" | "periodic" => self.geometry.periodic = parse_cli_value(key, value, "true or false")?,
+
+            "attraction.kernel" | "kernel" => {
+                self.attraction.kernel = match value {
+                    "Gaussian" | "gaussian" => crate::attraction::KernelType::Gaussian,
+                    "InverseDistance" | "inverse_distance" => crate::attraction::KernelType::InverseDistance,
+                    _ => {
+                        return Err(SimulationError::ParseError {
+                            key: key.to_string(),
+                            value: value.to_string(),
+                            expected: "Gaussian or InverseDistance",
+                        })
+                    }
+                }
+            }
+            "attraction.sigma" | "sigma" => self.attraction.sigma = parse_cli_value(key, value, "a number")?,
+            "attraction.lambda" | "lambda" => self.attraction.lambda = parse_cli_value(key, value, "a number")?,
+            "attraction.theta" => self.attraction.theta = parse_cli_value(key, value, "a number")?,
+            "attraction.cutoff" => self.attraction.cutoff = Some(parse_cli_value(key, value, "a number")?),
+
+            "state.memory_dim" => self.state.memory_dim = parse_cli_value(key, value, "a positive integer")?,
+            "state.context_dim" => self.state.context_dim = parse_cli_value(key, value, "a positive integer")?,
+            "state.decay_alpha" => self.state.decay_alpha = parse_cli_value(key, value, "a number")?,
+            "state.beta_attention" => self.state.beta_attention = parse_cli_value(key, value, "a number")?,
+            "state.gamma_memory" => self.state.gamma_memory = parse_cli_value(key, value, "a number")?,
+            "state.dedup_threshold" => self.state.dedup_threshold = Some(parse_cli_value(key, value, "a number")?),
+
+            "dynamics.dt" => self.dynamics.dt = parse_cli_value(key, value, "a number")?,
+            "dynamics.min_speed" => self.dynamics.min_speed = parse_cli_value(key, value, "a number")?,
+            "dynamics.damping" | "damping" => self.dynamics.damping = parse_cli_value(key, value, "a number")?,
+            "dynamics.max_action_force" => self.dynamics.max_action_force = parse_cli_value(key, value, "a number")?,
+            "dynamics.legacy_pipeline" => self.dynamics.legacy_pipeline = parse_cli_value(key, value, "true or false")?,
+            "dynamics.auto_heading" => self.dynamics.auto_heading = parse_cli_value(key, value, "true or false")?,
+            "dynamics.max_turn_rate" => self.dynamics.max_turn_rate = parse_cli_value(key, value, "a number")?,
+
+            "essence.baseline" => self.essence.baseline = parse_cli_value(key, value, "a number")?,
+            "essence.decay" => self.essence.decay = parse_cli_value(key, value, "a number")?,
+            "essence.experience_scale" => self.essence.experience_scale = parse_cli_value(key, value, "a number")?,
+
+            "simulation.num_entities" | "num_entities" => {
+                self.simulation.num_entities = parse_cli_value(key, value, "a positive integer")?
+            }
+            "simulation.num_steps" | "num_steps" => {
+                self.simulation.num_steps = parse_cli_value(key, value, "a positive integer")?
+            }
+            "simulation.dt" => self.simulation.dt = parse_cli_value(key, value, "a number")?,
+            "simulation.seed" | "seed" => self.simulation.seed = parse_cli_value(key, value, "an unsigned integer")?,
+            "simulation.per_species_metrics" => {
+                self.simulation.per_species_metrics = parse_cli_value(key, value, "true or false")?
+            }
+            "simulation.profile_phases" => {
+                self.simulation.profile_phases = parse_cli_value(key, value, "true or false")?
+            }
+            "simulation.attention_capture_interval" => {
+                self.simulation.attention_capture_interval = parse_cli_value(key, value, "an unsigned integer")?
+            }
+            "simulation.initial_layout" | "layout" => {
+                self.simulation.initial_layout = match value {
+                    "Random" | "random" => InitialLayout::Random,
+                    "Grid" | "grid" => InitialLayout::Grid,
+                    _ => {
+                        return Err(SimulationError::ParseError {
+                            key: key.to_string(),
+                            value: value.to_string(),
+                            expected: "Random or Grid",
+                        })
+                    }
+                }
+            }
+
+            "recording.record_every" => {
+                self.recording.record_every = parse_cli_value(key, value, "a positive integer")?
+            }
+            "recording.positions" => self.recording.positions = parse_cli_value(key, value, "true or false")?,
+            "recording.attractions" => self.recording.attractions = parse_cli_value(key, value, "true or false")?,
+            "recording.clusters" => self.recording.clusters = parse_cli_value(key, value, "true or false")?,
+            "recording.attentions" => self.recording.attentions = parse_cli_value(key, value, "true or false")?,
+
+            _ => return Err(SimulationError::ValidationError(format!("unknown configuration key '{}'", key))),
+        }
+        Ok(())
+    }
+
+    /// Total number of entities created at initialization.
+    /// 
+    /// Species counts are populated first; any remainder of
+    /// `simulation.num_entities` is filled with default-species entities.
+    pub fn total_entities(&self) -> u32 {
+        let species_total: u32 = self.species.values().map(|s| s.count).sum();
+        species_total.max(self.simulation.num_entities)
+    }
+}
+
+/// Config path that reads the configuration from standard input.
+pub const STDIN_PATH: &str = "-";
+
+/// Text format of a configuration.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ConfigFormat {
+    /// TOML, as in the example configuration files
+    Toml,
+    /// JSON with the same structure (e.g. from `serde_json` or a script)
+    Json,
+}
+
+impl ConfigFormat {
+    /// Guess the format of configuration text.
+    ///
+    /// A JSON configuration is an object, so text whose first non-blank
+    /// character is `{` is JSON; anything else is TOML, which cannot start
+    /// with a brace.
+    pub fn sniff(contents: &str) -> Self {
+        if contents.trim_start().starts_with('{') {
+            ConfigFormat::Json
+        } else {
+            ConfigFormat::Toml
+        }
+    }
+}
+
+impl std::str::FromStr for ConfigFormat {
+    type Err = String;
+
+    fn from_str(name: &str) -> Result<Self, String> {
+        match name {
+            "toml" => Ok(ConfigFormat::Toml),
+            "json" => Ok(ConfigFormat::Json),
+            _ => Err(format!("unknown config format '{}' (toml or json)", name)),
+        }
+    }
+}
+
+/// Top-level key naming the configuration a TOML file inherits from.
+pub const EXTENDS_KEY: &str = "extends";
+
+/// Most parents an `extends` chain may have.
+pub const MAX_EXTENDS_DEPTH: usize = 8;
+
+/// Replace `table`'s `extends` key by the (recursively resolved) parent it names.
+///
+/// # Arguments
+/// * `table` - Parsed child file
+/// * `origin` - Path of the child file (None: standard input, resolved
+///   relative to the current directory)
+/// * `chain` - Canonical paths of the files being resolved, child first
+///
+/// # Returns
+/// The parent's fields overlaid with the child's, or why a parent could
+/// not be loaded (unreadable, unparsable, a cycle or too deep a chain)
+fn resolve_extends(mut table: toml::Table, origin: Option<&Path>, chain: &mut Vec<PathBuf>) -> Result<toml::Table, String> {
+    let Some(extends) = table.remove(EXTENDS_KEY) else {
+        return Ok(table);
+    };
+    let child = origin.map_or("standard input".to_string(), |path| format!("'{}'", path.display()));
+    let extends = extends
+        .as_str()
+        .ok_or_else(|| format!("`{}` in {} must be a path", EXTENDS_KEY, child))?;
+    let parent_path = origin.and_then(Path::parent).unwrap_or(Path::new("")).join(extends);
+    let canonical = parent_path
+        .canonicalize()
+        .map_err(|e| format!("cannot read '{}' (extended by {}): {}", parent_path.display(), child, e))?;
+
+    if let Some(start) = chain.iter().position(|path| *path == canonical) {
+        let cycle: Vec<String> = chain[start..]
+            .iter()
+            .chain(std::iter::once(&canonical))
+            .map(|path| path.display().to_string())
+            .collect();
+        return Err(format!("`{}` cycle: {}", EXTENDS_KEY, cycle.join(" -> ")));
+    }
+    if chain.len() > MAX_EXTENDS_DEPTH {
+        return Err(format!(
+            "`{}` chain is deeper than {} files at '{}'",
+            EXTENDS_KEY,
+            MAX_EXTENDS_DEPTH,
+            parent_path.display()
+        ));
+    }
+
+    let contents = fs::read_to_string(&canonical).map_err(|e| format!("cannot read '{}': {}", parent_path.display(), e))?;
+    let parent: toml::Table = contents
+        .parse()
+        .map_err(|e: toml::de::Error| format!("in '{}': {}", parent_path.display(), e))?;
+    chain.push(canonical);
+    let mut merged = resolve_extends(parent, Some(&parent_path), chain)?;
+    chain.pop();
+    merge_tables(&mut merged, table);
+    Ok(merged)
+}
+
+/// Overlay `overlay` onto `base`: tables merge key by key, anything else
+/// (numbers, strings, arrays such as `bounds` or `[[entities]]`) replaces.
+fn merge_tables(base: &mut toml::Table, overlay: toml::Table) {
+    for (key, value) in overlay {
+        match base.entry(key) {
+            toml::map::Entry::Occupied(mut entry) => match (entry.get_mut(), value) {
+                (toml::Value::Table(base_table), toml::Value::Table(overlay_table)) => merge_tables(base_table, overlay_table),
+                (slot, value) => *slot = value,
+            },
+            toml::map::Entry::Vacant(entry) => {
+                entry.insert(value);
+            }
+        }
+    }
+}
+
+/// Read standard input to its end.
+fn read_stdin() -> Result<String, String> {
+    use std::io::Read;
+
+    let mut contents = String::new();
+    std::io::stdin()
+        .read_to_string(&mut contents)
+        .map_err(|e| format!("cannot read standard input: {}", e))?;
+    Ok(contents)
+}
+
+/// A configuration problem found by [`SimulationConfig::problems`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct ConfigProblem {
+    /// TOML path of the offending field (e.g. `attraction.sigma`, `entities[2].id`)
+    pub path: String,
+    /// What is wrong with it
+    pub message: String,
+}
+
+impl ConfigProblem {
+    fn new(path: &str, message: &str) -> Self {
+        ConfigProblem {
+            path: path.to_string(),
+            message: message.to_string(),
+        }
+    }
+}
+
+impl fmt::Display for ConfigProblem {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.path, self.message)
+    }
+}
+
+/// Parse a command-line override value.
+fn parse_cli_value<T: std::str::FromStr>(key: &str, value: &str, expected: &'static str) -> Result<T, SimulationError> {
+    value.parse().map_err(|_| SimulationError::ParseError {
+        key: key.to_string(),
+        value: value.to_string(),
+        expected,
+    })
+}
+
+/// Parameters that may change while a simulation runs.
+/// 
+/// `None` fields are left unchanged.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SimulationConfigPatch {
+    /// Attraction kernel bandwidth
+    pub sigma: Option<Real>,
+    /// Velocity damping factor
+    pub damping: Option<Real>,
+    /// Perpetual velocity floor
+    pub min_speed: Option<Real>,
+    /// Essence response to experiences
+    pub experience_scale: Option<Real>,
+}
+
+impl SimulationConfigPatch {
+    /// Whether the patch changes nothing.
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// Check that every set value is finite and in range.
+    /// 
+    /// `sigma` must be positive, `damping` within [0, 1], `min_speed`
+    /// and `experience_scale` non-negative.
+    pub fn is_valid(&self) -> bool {
+        let finite = |v: Option<Real>| v.is_none_or(Real::is_finite);
+        [self.sigma, self.damping, self.min_speed, self.experience_scale].into_iter().all(finite)
+            && self.sigma.is_none_or(|v| v > 0.0)
+            && self.damping.is_none_or(|v| (0.0..=1.0).contains(&v))
+            && self.min_speed.is_none_or(|v| v >= 0.0)
+            && self.experience_scale.is_none_or(|v| v >= 0.0)
+    }
+
+    /// Set the non-`None` fields on `config`.
+    pub fn apply_to(&self, config: &mut SimulationConfig) {
+        if let Some(sigma) = self.sigma {
+            config.attraction.sigma = sigma;
+        }
+        if let Some(damping) = self.damping {
+            config.dynamics.damping = damping;
+        }
+        if let Some(min_speed) = self.min_speed {
+            config.dynamics.min_speed = min_speed;
+        }
+        if let Some(scale) = self.experience_scale {
+            config.essence.experience_scale = scale;
+        }
+    }
+
+    /// Changed parameters as `key=value` pairs, e.g. `"sigma=2 damping=0.95"`.
+    pub fn describe(&self) -> String {
+        [
+            ("sigma", self.sigma),
+            ("damping", self.damping),
+            ("min_speed", self.min_speed),
+            ("experience_scale", self.experience_scale),
+        ]
+        .iter()
+        .filter_map(|(key, value)| value.map(|v| format!("{}={}", key, v)))
+        .collect::<Vec<_>>()
+        .join(" ")
+    }
+}
+
+/// Number of fingerprint characters used as a filename suffix.
+pub const FINGERPRINT_SUFFIX_LEN: usize = 12;
+
+/// Rebuild every table of a TOML value with its keys in sorted order.
+fn sort_toml_keys(value: toml::Value) -> toml::Value {
+    match value {
+        toml::Value::Table(table) => {
+            let mut entries: Vec<(String, toml::Value)> = table.into_iter().collect();
+            entries.sort_by(|a, b| a.0.cmp(&b.0));
+            toml::Value::Table(
+                entries
+                    .into_iter()
+                    .map(|(key, value)| (key, sort_toml_keys(value)))
+                    .collect(),
+            )
+        }
+        toml::Value::Array(items) => toml::Value::Array(items.into_iter().map(sort_toml_keys).collect()),
+        other => other,
+    }
 }
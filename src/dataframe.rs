@@ -0,0 +1,130 @@
+//! DataFrame module: polars frames of simulation results for Rust-side analysis.
+//!
+//! Enabled by the `polars` feature, which is heavy (it compiles a large part
+//! of polars) and is never needed by the simulation itself; leave it off
+//! unless you analyse results in Rust. The functions are re-exported from
+//! `analysis`.
+//!
+//! | Frame | One row per | Columns |
+//! |-------|-------------|---------|
+//! | [`metrics_dataframe`] | metrics snapshot | `step` (u64), then every metric field (`Real`) |
+//! | [`trajectories_dataframe`] | recorded step × entity | `step` (u64), `entity_id` (u32), `x`, `y`, `z`, `vx`, `vy`, `vz`, `essence`, `energy` (`Real`) |
+//!
+//! `step` comes first and is sorted, so it serves as the index column.
+//! `z`/`vz` are null in 2D runs. Column names match the Arrow tables of the
+//! `arrow` feature.
+//!
+//! On top of the frames:
+//! - [`rolling_metric_means`]: trailing moving average of every metric
+//! - [`per_entity_summary`]: per-entity aggregates of a trajectories frame
+//!
+//! ## Author
+//! Ayomide I. Daniels (Morningstar)
+
+use crate::real::Real;
+use crate::metrics::Metrics;
+use crate::results::SimulationResults;
+use polars::prelude::*;
+
+/// Polars type of [`Real`] columns.
+const REAL_TYPE: DataType = if cfg!(feature = "f64") { DataType::Float64 } else { DataType::Float32 };
+
+/// Build a frame with one row per metrics snapshot.
+///
+/// # Arguments
+/// * `metrics` - Snapshots, e.g. `Simulation::metrics_history`
+///
+/// # Returns
+/// `step` (from `Metrics::timestamp`) followed by one `Real` column per metric
+pub fn metrics_dataframe(metrics: &[Metrics]) -> PolarsResult<DataFrame> {
+    let mut columns = vec![Column::new(
+        "step".into(),
+        metrics.iter().map(|m| m.timestamp).collect::<Vec<u64>>(),
+    )];
+    for (k, name) in Metrics::FIELD_NAMES.iter().enumerate() {
+        let values: Vec<Real> = metrics.iter().map(|m| m.field_values()[k]).collect();
+        columns.push(Column::new((*name).into(), values));
+    }
+    DataFrame::new(columns)
+}
+
+/// Build a frame with one row per recorded step and entity.
+///
+/// # Arguments
+/// * `results` - Results with recorded steps
+///
+/// # Returns
+/// Rows in step order, entities by id within a step
+pub fn trajectories_dataframe(results: &SimulationResults) -> PolarsResult<DataFrame> {
+    let rows = results.trajectory_rows();
+    let axis = |values: &[Real], i: usize| values.get(i).copied();
+
+    let mut columns = vec![
+        Column::new("step".into(), rows.iter().map(|r| r.step).collect::<Vec<u64>>()),
+        Column::new("entity_id".into(), rows.iter().map(|r| r.entity_id).collect::<Vec<u32>>()),
+    ];
+    for (i, name) in ["x", "y", "z"].iter().enumerate() {
+        let values: Vec<Option<Real>> = rows.iter().map(|r| axis(r.position, i)).collect();
+        columns.push(Column::new((*name).into(), values));
+    }
+    for (i, name) in ["vx", "vy", "vz"].iter().enumerate() {
+        let values: Vec<Option<Real>> = rows.iter().map(|r| axis(r.velocity, i)).collect();
+        columns.push(Column::new((*name).into(), values));
+    }
+    columns.push(Column::new("essence".into(), rows.iter().map(|r| r.essence).collect::<Vec<Real>>()));
+    columns.push(Column::new("energy".into(), rows.iter().map(|r| r.energy).collect::<Vec<Real>>()));
+    DataFrame::new(columns)
+}
+
+/// Trailing moving average of every metric column.
+///
+/// The first `window - 1` rows average over the rows available so far.
+///
+/// # Arguments
+/// * `metrics` - Frame from [`metrics_dataframe`]
+/// * `window` - Window length in rows (at least 1)
+///
+/// # Returns
+/// `step` and the smoothed metric columns (same names, `Real`)
+pub fn rolling_metric_means(metrics: &DataFrame, window: usize) -> PolarsResult<DataFrame> {
+    let options = RollingOptionsFixedWindow {
+        window_size: window.max(1),
+        min_periods: 1,
+        ..Default::default()
+    };
+    let mut exprs = vec![col("step")];
+    exprs.extend(
+        Metrics::FIELD_NAMES
+            .iter()
+            .map(|name| col(*name).rolling_mean(options.clone()).cast(REAL_TYPE)),
+    );
+    metrics.clone().lazy().select(exprs).collect()
+}
+
+/// Per-entity aggregates of a trajectories frame.
+///
+/// # Arguments
+/// * `trajectories` - Frame from [`trajectories_dataframe`]
+///
+/// # Returns
+/// One row per entity (ascending id) with `samples` (recorded steps),
+/// `mean_essence`, `min_essence`, `max_essence`, `mean_energy` and
+/// `mean_speed` (missing `vz` counts as 0)
+pub fn per_entity_summary(trajectories: &DataFrame) -> PolarsResult<DataFrame> {
+    let squared = |name: &str| col(name).fill_null(lit(0.0 as Real)).pow(2);
+    let speed = (squared("vx") + squared("vy") + squared("vz")).sqrt();
+    trajectories
+        .clone()
+        .lazy()
+        .group_by([col("entity_id")])
+        .agg([
+            len().cast(DataType::UInt32).alias("samples"),
+            col("essence").mean().alias("mean_essence"),
+            col("essence").min().alias("min_essence"),
+            col("essence").max().alias("max_essence"),
+            col("energy").mean().alias("mean_energy"),
+            speed.mean().alias("mean_speed"),
+        ])
+        .sort(["entity_id"], Default::default())
+        .collect()
+}
@@ -4,6 +4,7 @@
 //! - Velocity integration from acceleration forces
 //! - Damping to prevent unbounded speeds
 //! - Perpetual velocity enforcement (minimum speed injection)
+//! - Baseline drives (self-preservation and curiosity)
 //!
 //! ## Perpetual Velocity
 //!
@@ -17,22 +18,61 @@
 //! velocity, we guarantee that entities remain engaged with their environment,
 //! creating the conditions for emergent awareness.
 //!
+//! ## Drives
+//!
+//! Each entity has innate drives sampled at creation from configurable
+//! distributions. Every decision step the effective drives are the innate
+//! values plus a response to the current situation: self-preservation
+//! rises with crowding (inverse nearest-neighbor distance) and curiosity
+//! with attention magnitude.
+//!
 //! ## Author
 //! Ayomide I. Daniels (Morningstar)
 
+use crate::real::Real;
+use rand::Rng;
 use serde::{Deserialize, Serialize};
 
+/// Distance below which crowding no longer increases self-preservation.
+pub const MIN_DRIVE_DISTANCE: Real = 0.1;
+
 /// Configuration for dynamics integration.
 /// 
 /// Controls timestep, velocity constraints, and damping.
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct DynamicsConfig {
     /// Time step.
-    pub dt: f32,
+    pub dt: Real,
     /// Minimum speed to maintain.
-    pub min_speed: f32,
+    pub min_speed: Real,
     /// Velocity damping per step.
-    pub damping: f32,
+    pub damping: Real,
+    /// Maximum magnitude of the force an entity's action can produce.
+    #[serde(default = "default_max_action_force")]
+    pub max_action_force: Real,
+    /// Use the legacy pipeline in which decisions are discarded, a
+    /// constant placeholder acceleration drives entities and the state
+    /// vector follows a placeholder gradient (deprecated).
+    #[serde(default)]
+    pub legacy_pipeline: bool,
+    /// Turn each entity to face its direction of motion.
+    #[serde(default = "default_auto_heading")]
+    pub auto_heading: bool,
+    /// Maximum heading change in radians per unit time.
+    #[serde(default = "default_max_turn_rate")]
+    pub max_turn_rate: Real,
+}
+
+fn default_max_action_force() -> Real {
+    0.05
+}
+
+fn default_auto_heading() -> bool {
+    true
+}
+
+fn default_max_turn_rate() -> Real {
+    10.0
 }
 
 impl DynamicsConfig {
@@ -41,10 +81,87 @@ impl DynamicsConfig {
             dt: 0.01,
             min_speed: 0.05,
             damping: 0.99,
+            max_action_force: default_max_action_force(),
+            legacy_pipeline: false,
+            auto_heading: default_auto_heading(),
+            max_turn_rate: default_max_turn_rate(),
         }
     }
 }
 
+/// Distribution from which an innate drive is sampled.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum DriveDistribution {
+    /// Same value for every entity.
+    Constant { value: Real },
+    /// Uniform on [min, max].
+    Uniform { min: Real, max: Real },
+    /// Normal with the given mean and standard deviation.
+    Normal { mean: Real, std: Real },
+}
+
+impl DriveDistribution {
+    /// Draw a drive value (clamped to be non-negative).
+    pub fn sample(&self, rng: &mut impl Rng) -> Real {
+        let value = match *self {
+            DriveDistribution::Constant { value } => value,
+            DriveDistribution::Uniform { min, max } => {
+                if max > min {
+                    rng.gen_range(min..max)
+                } else {
+                    min
+                }
+            }
+            DriveDistribution::Normal { mean, std } => {
+                // Box-Muller transform
+                let u1: Real = rng.gen_range(Real::EPSILON..1.0);
+                let u2: Real = rng.gen();
+                mean + std * (-2.0 * u1.ln()).sqrt() * (2.0 * crate::real::consts::PI * u2).cos()
+            }
+        };
+        value.max(0.0)
+    }
+
+    /// Check parameter ranges.
+    pub fn is_valid(&self) -> bool {
+        match *self {
+            DriveDistribution::Constant { value } => value >= 0.0,
+            DriveDistribution::Uniform { min, max } => min >= 0.0 && max >= min,
+            DriveDistribution::Normal { std, .. } => std >= 0.0,
+        }
+    }
+}
+
+/// Configuration of entity drives.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct DriveConfig {
+    /// Distribution of innate self-preservation.
+    pub preservation: DriveDistribution,
+    /// Distribution of innate curiosity.
+    pub curiosity: DriveDistribution,
+    /// Weight of the situational response added to the innate drives
+    /// (0 keeps drives at their innate values).
+    pub responsiveness: Real,
+}
+
+impl Default for DriveConfig {
+    fn default() -> Self {
+        DriveConfig {
+            preservation: DriveDistribution::Uniform { min: 0.25, max: 0.75 },
+            curiosity: DriveDistribution::Uniform { min: 0.25, max: 0.75 },
+            responsiveness: 0.1,
+        }
+    }
+}
+
+impl DriveConfig {
+    /// Check parameter ranges.
+    pub fn is_valid(&self) -> bool {
+        self.preservation.is_valid() && self.curiosity.is_valid() && self.responsiveness >= 0.0
+    }
+}
+
 /// Integrate motion with perpetual velocity enforcement.
 /// 
 /// Updates position and velocity using semi-implicit Euler integration:
@@ -62,9 +179,9 @@ impl DynamicsConfig {
 /// * `acceleration` - Acceleration vector for this timestep
 /// * `config` - Dynamics configuration
 pub fn integrate_motion(
-    position: &mut [f32],
-    velocity: &mut [f32],
-    acceleration: &[f32],
+    position: &mut [Real],
+    velocity: &mut [Real],
+    acceleration: &[Real],
     config: &DynamicsConfig,
 ) {
     let dt = config.dt;
@@ -78,7 +195,7 @@ pub fn integrate_motion(
     }
 
     // Enforce perpetual velocity (minimum speed injection)
-    let speed_sq: f32 = velocity.iter().map(|v| v * v).sum();
+    let speed_sq: Real = velocity.iter().map(|v| v * v).sum();
     let speed = speed_sq.sqrt();
 
     if speed < min_speed && speed > 1e-6 {
@@ -98,22 +215,54 @@ pub fn integrate_motion(
     }
 }
 
-/// Compute acceleration from attraction gradient.
-pub fn compute_acceleration_from_gradient(gradient: &[f32]) -> Vec<f32> {
-    // Simplified: acceleration proportional to gradient
-    gradient.iter().map(|g| g * 0.1).collect()
+/// Acceleration per unit of attraction gradient.
+pub const GRADIENT_ACCELERATION: Real = 0.1;
+
+/// Compute acceleration from an attention prompt.
+/// 
+/// The prompt is the negative gradient of the attraction potential
+/// (F = -∇Φ, see [`crate::attraction::attention_gradient`]). Entities are
+/// drawn up the potential, toward the others, so the acceleration is
+/// `-GRADIENT_ACCELERATION · F`.
+/// 
+/// # Arguments
+/// * `gradient` - Attention prompt of the entity
+pub fn compute_acceleration_from_gradient(gradient: &[Real]) -> Vec<Real> {
+    let mut acceleration = vec![0.0; gradient.len()];
+    add_acceleration_from_gradient(gradient, &mut acceleration);
+    acceleration
+}
+
+/// Add the acceleration from an attention prompt to `acceleration`
+/// without allocating (see [`compute_acceleration_from_gradient`]).
+/// 
+/// # Arguments
+/// * `gradient` - Attention prompt (negative attraction gradient)
+/// * `acceleration` - Accumulated acceleration (same dimensionality)
+pub fn add_acceleration_from_gradient(gradient: &[Real], acceleration: &mut [Real]) {
+    for (a, g) in acceleration.iter_mut().zip(gradient) {
+        *a -= g * GRADIENT_ACCELERATION;
+    }
 }
 
 /// Compute baseline drives (self-preservation and curiosity).
+/// 
+/// Self-preservation grows with crowding as the inverse of the distance
+/// to the nearest other entity (saturating at `MIN_DRIVE_DISTANCE`), and
+/// curiosity equals the attention magnitude.
+/// 
+/// # Arguments
+/// * `min_distance_to_others` - Distance to the nearest other entity
+///   (infinite when alone)
+/// * `attention_magnitude` - Norm of the entity's attention state
+/// 
+/// # Returns
+/// (self-preservation, curiosity)
 pub fn compute_baseline_drives(
-    min_distance_to_others: f32,
-    attention_magnitude: f32,
-) -> (f32, f32) {
-    let preservation = if min_distance_to_others > 1e-6 {
-        1.0 / min_distance_to_others
-    } else {
-        1.0
-    };
+    min_distance_to_others: Real,
+    attention_magnitude: Real,
+) -> (Real, Real) {
+    let preservation = 1.0 / min_distance_to_others.max(MIN_DRIVE_DISTANCE);
     let curiosity = attention_magnitude;
 
     (preservation, curiosity)
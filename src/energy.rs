@@ -0,0 +1,97 @@
+//! Energy module: metabolic budget for entity motion.
+//!
+//! This module gives motion a cost, so that purposeful motion has to be paid
+//! for. Metabolism is off unless `energy.enabled` is set; then each entity
+//! carries an energy reserve that is:
+//! - Drained every step proportionally to speed² and action magnitude
+//! - Replenished by positive-valence experiences (belief clusters with
+//!   positive affective signal)
+//!
+//! ## Exhaustion and Dormancy
+//!
+//! When energy reaches zero the entity's acceleration is clamped to a small
+//! maximum and the minimum speed is no longer enforced, so it can drift to a
+//! halt until positive experiences replenish it. Optionally, an entity that stays exhausted for
+//! long enough becomes dormant and stops emitting attraction.
+//!
+//! ## Bookkeeping
+//!
+//! Entities keep a ledger of energy gained and spent so that, at every step,
+//! `energy == initial + gained - spent` holds exactly (up to float rounding).
+//!
+//! ## Author
+//! Ayomide I. Daniels (Morningstar)
+
+use crate::real::Real;
+use serde::{Deserialize, Serialize};
+
+/// Configuration for entity metabolism.
+///
+/// Metabolism is disabled by default, and rates of zero disable the
+/// corresponding flow; either way energy stays constant.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct EnergyConfig {
+    /// Enable drain, replenishment and exhaustion.
+    #[serde(default)]
+    pub enabled: bool,
+    /// Initial energy of every entity.
+    pub initial: Real,
+    /// Maximum energy an entity can store.
+    pub capacity: Real,
+    /// Drain per step per unit of speed².
+    pub speed_drain: Real,
+    /// Drain per step per unit of action (acceleration) magnitude.
+    pub action_drain: Real,
+    /// Gain per step per unit of positive affective signal.
+    pub replenish_rate: Real,
+    /// Maximum acceleration magnitude while exhausted.
+    pub exhausted_max_acceleration: Real,
+    /// Consecutive exhausted steps before becoming dormant (0 = never).
+    pub dormancy_steps: u32,
+}
+
+impl Default for EnergyConfig {
+    fn default() -> Self {
+        EnergyConfig {
+            enabled: false,
+            initial: 100.0,
+            capacity: 100.0,
+            speed_drain: 0.0,
+            action_drain: 0.0,
+            replenish_rate: 0.0,
+            exhausted_max_acceleration: 0.001,
+            dormancy_steps: 0,
+        }
+    }
+}
+
+/// Clamp an acceleration vector to a maximum magnitude (in place).
+///
+/// # Arguments
+/// * `acceleration` - Acceleration vector to limit
+/// * `max_magnitude` - Largest allowed Euclidean norm
+pub fn clamp_magnitude(acceleration: &mut [Real], max_magnitude: Real) {
+    let magnitude = acceleration.iter().map(|a| a * a).sum::<Real>().sqrt();
+    let scale = magnitude_scale(magnitude, max_magnitude);
+    if scale < 1.0 {
+        for a in acceleration.iter_mut() {
+            *a *= scale;
+        }
+    }
+}
+
+/// Factor limiting a vector's magnitude, as applied by [`clamp_magnitude`].
+///
+/// # Arguments
+/// * `magnitude` - Euclidean norm of the vector
+/// * `max_magnitude` - Largest allowed norm
+///
+/// # Returns
+/// `max_magnitude / magnitude` if the vector is too long, otherwise 1
+pub fn magnitude_scale(magnitude: Real, max_magnitude: Real) -> Real {
+    if magnitude > max_magnitude && magnitude > 0.0 {
+        max_magnitude / magnitude
+    } else {
+        1.0
+    }
+}
@@ -0,0 +1,269 @@
+//! Ensemble module: one configuration run under many seeds.
+//!
+//! A single run's consciousness verdict depends on its seed. [`run_ensemble`]
+//! runs the same configuration once per seed, each run independent of the
+//! others, and aggregates the final value of every metric (mean, standard
+//! deviation, min and max) together with the consciousness score and the
+//! fraction of runs in which consciousness was achieved.
+//! [`run_ensemble_with_jobs`] does the same on a thread pool.
+//!
+//! [`EnsembleResults`] serializes to JSON ([`EnsembleResults::save_json`])
+//! and writes its own text report ([`EnsembleResults::generate_text_report`])
+//! with one row per metric and one per run.
+//!
+//! ## Author
+//! Ayomide I. Daniels (Morningstar)
+
+use crate::config::SimulationConfig;
+use crate::metrics::Metrics;
+use crate::real::{from_f64, to_f64, Real};
+use crate::simulation::Simulation;
+use rayon::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// Outcome of one run of an ensemble.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct EnsembleRun {
+    /// Seed of the run
+    pub seed: u64,
+    /// Steps executed
+    pub steps: u64,
+    /// Entities alive at the end
+    pub entities: usize,
+    /// Overall consciousness score
+    pub consciousness_score: Real,
+    /// Whether the consciousness criteria were met
+    pub consciousness_achieved: bool,
+    /// Final value of each of [`Metrics::FIELD_NAMES`]
+    pub final_metrics: [Real; 11],
+}
+
+/// Spread of one quantity over the runs of an ensemble.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct SummaryStats {
+    /// Name of the quantity (a metric field or `consciousness_score`)
+    pub name: String,
+    pub mean: Real,
+    /// Sample standard deviation; zero for fewer than two runs
+    pub std: Real,
+    pub min: Real,
+    pub max: Real,
+}
+
+impl SummaryStats {
+    /// Statistics of `values`, all zero when there are none.
+    ///
+    /// # Arguments
+    /// * `name` - Name of the quantity
+    /// * `values` - One value per run
+    pub fn of(name: &str, values: &[Real]) -> Self {
+        let mut stats = SummaryStats {
+            name: name.to_string(),
+            ..Default::default()
+        };
+        if values.is_empty() {
+            return stats;
+        }
+        let n = values.len() as f64;
+        let mean = values.iter().map(|&v| to_f64(v)).sum::<f64>() / n;
+        let variance = if values.len() > 1 {
+            values.iter().map(|&v| (to_f64(v) - mean).powi(2)).sum::<f64>() / (n - 1.0)
+        } else {
+            0.0
+        };
+        stats.mean = from_f64(mean);
+        stats.std = from_f64(variance.sqrt());
+        stats.min = values.iter().copied().fold(Real::INFINITY, Real::min);
+        stats.max = values.iter().copied().fold(Real::NEG_INFINITY, Real::max);
+        stats
+    }
+}
+
+/// Aggregated results of an ensemble.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct EnsembleResults {
+    /// `metadata.name` of the configuration
+    pub simulation_name: String,
+    /// Configuration every run started from (`simulation.seed` aside)
+    pub config: SimulationConfig,
+    /// Every run, in seed order as given
+    pub runs: Vec<EnsembleRun>,
+    /// Final-value statistics of each of [`Metrics::FIELD_NAMES`], in order
+    pub metrics: Vec<SummaryStats>,
+    /// Statistics of the consciousness score
+    pub consciousness_score: SummaryStats,
+    /// Fraction of runs in which consciousness was achieved, in [0, 1]
+    pub consciousness_fraction: Real,
+}
+
+impl EnsembleResults {
+    /// Aggregate finished runs.
+    ///
+    /// # Arguments
+    /// * `config` - Configuration the runs started from
+    /// * `runs` - One outcome per seed
+    pub fn from_runs(config: SimulationConfig, runs: Vec<EnsembleRun>) -> Self {
+        let metrics = Metrics::FIELD_NAMES
+            .iter()
+            .enumerate()
+            .map(|(i, name)| {
+                let values: Vec<Real> = runs.iter().map(|run| run.final_metrics[i]).collect();
+                SummaryStats::of(name, &values)
+            })
+            .collect();
+        let scores: Vec<Real> = runs.iter().map(|run| run.consciousness_score).collect();
+        let achieved = runs.iter().filter(|run| run.consciousness_achieved).count();
+        let consciousness_fraction = if runs.is_empty() {
+            0.0
+        } else {
+            achieved as Real / runs.len() as Real
+        };
+        EnsembleResults {
+            simulation_name: config.metadata.name.clone(),
+            config,
+            metrics,
+            consciousness_score: SummaryStats::of("consciousness_score", &scores),
+            consciousness_fraction,
+            runs,
+        }
+    }
+
+    /// Statistics of one metric by its field name.
+    pub fn metric(&self, name: &str) -> Option<&SummaryStats> {
+        self.metrics.iter().find(|stats| stats.name == name)
+    }
+
+    /// Save the results as pretty-printed JSON.
+    ///
+    /// # Arguments
+    /// * `path` - Output file
+    pub fn save_json(&self, path: &str) -> Result<(), Box<dyn std::error::Error>> {
+        std::fs::write(path, serde_json::to_string_pretty(self)?)?;
+        Ok(())
+    }
+
+    /// Write the ensemble statistics and the per-run outcomes as text.
+    ///
+    /// # Arguments
+    /// * `filename` - Output file
+    pub fn generate_text_report(&self, filename: &str) -> Result<(), Box<dyn std::error::Error>> {
+        use std::fs::File;
+        use std::io::Write;
+
+        let mut file = File::create(filename)?;
+
+        writeln!(file, "╔════════════════════════════════════════════════════════════════╗")?;
+        writeln!(file, "║         SYNTHETIC CONSCIOUSNESS ENSEMBLE REPORT               ║")?;
+        writeln!(file, "╚════════════════════════════════════════════════════════════════╝")?;
+        writeln!(file)?;
+
+        writeln!(file, "ENSEMBLE")?;
+        writeln!(file, "─────────────────────────────────────────────────────────────────")?;
+        writeln!(file, "Name:                {}", self.simulation_name)?;
+        writeln!(file, "Runs:                {}", self.runs.len())?;
+        writeln!(file, "Entities:            {}", self.config.simulation.num_entities)?;
+        writeln!(file, "Steps per Run:       {}", self.config.simulation.num_steps)?;
+        let achieved = self.runs.iter().filter(|run| run.consciousness_achieved).count();
+        writeln!(
+            file,
+            "Consciousness:       achieved in {}/{} runs ({:.1}%)",
+            achieved,
+            self.runs.len(),
+            100.0 * to_f64(self.consciousness_fraction)
+        )?;
+        writeln!(file)?;
+
+        writeln!(file, "ENSEMBLE STATISTICS (final values)")?;
+        writeln!(file, "─────────────────────────────────────────────────────────────────")?;
+        writeln!(file, "{:<22} {:>10} {:>10} {:>10} {:>10}", "Metric", "Mean", "Std", "Min", "Max")?;
+        for stats in self.metrics.iter().chain(std::iter::once(&self.consciousness_score)) {
+            writeln!(
+                file,
+                "{:<22} {:>10.4} {:>10.4} {:>10.4} {:>10.4}",
+                stats.name, stats.mean, stats.std, stats.min, stats.max
+            )?;
+        }
+        writeln!(file)?;
+
+        writeln!(file, "RUNS")?;
+        writeln!(file, "─────────────────────────────────────────────────────────────────")?;
+        writeln!(file, "{:<20} {:>8} {:>9} {:>8}  Conscious", "Seed", "Steps", "Entities", "Score")?;
+        for run in &self.runs {
+            writeln!(
+                file,
+                "{:<20} {:>8} {:>9} {:>8.4}  {}",
+                run.seed,
+                run.steps,
+                run.entities,
+                run.consciousness_score,
+                if run.consciousness_achieved { "yes" } else { "no" }
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// Run `config` once per seed, one run after another, and aggregate.
+///
+/// # Arguments
+/// * `config` - Configuration of every run; `simulation.seed` is replaced
+/// * `seeds` - Seed of each run
+///
+/// # Returns
+/// The aggregated results, or the first error creating a simulation
+pub fn run_ensemble(config: &SimulationConfig, seeds: &[u64]) -> Result<EnsembleResults, String> {
+    let runs = seeds.iter().map(|&seed| run_one(config, seed)).collect::<Result<Vec<_>, _>>()?;
+    Ok(EnsembleResults::from_runs(config.clone(), runs))
+}
+
+/// Run `config` once per seed on a thread pool and aggregate.
+///
+/// Runs do not share state, so the results equal those of [`run_ensemble`].
+///
+/// # Arguments
+/// * `config` - Configuration of every run; `simulation.seed` is replaced
+/// * `seeds` - Seed of each run
+/// * `jobs` - Parallel runs; 0 means one per available CPU
+///
+/// # Returns
+/// The aggregated results, or the first error creating a simulation
+pub fn run_ensemble_with_jobs(
+    config: &SimulationConfig,
+    seeds: &[u64],
+    jobs: usize,
+) -> Result<EnsembleResults, String> {
+    let jobs = match jobs {
+        0 => std::thread::available_parallelism().map(|n| n.get()).unwrap_or(1),
+        jobs => jobs,
+    };
+    let pool = rayon::ThreadPoolBuilder::new()
+        .num_threads(jobs)
+        .build()
+        .map_err(|e| e.to_string())?;
+    let runs = pool.install(|| {
+        seeds
+            .par_iter()
+            .with_max_len(1)
+            .map(|&seed| run_one(config, seed))
+            .collect::<Result<Vec<_>, _>>()
+    })?;
+    Ok(EnsembleResults::from_runs(config.clone(), runs))
+}
+
+/// Run `config` to completion under `seed`.
+fn run_one(config: &SimulationConfig, seed: u64) -> Result<EnsembleRun, String> {
+    let mut config = config.clone();
+    config.simulation.seed = seed;
+    let mut sim = Simulation::new(config)?;
+    sim.run();
+    sim.finalize_results();
+    let analysis = &sim.results.consciousness_analysis;
+    Ok(EnsembleRun {
+        seed,
+        steps: sim.timestamp,
+        entities: sim.entities.count(),
+        consciousness_score: analysis.consciousness_score,
+        consciousness_achieved: analysis.consciousness_achieved,
+        final_metrics: sim.metrics_history.last().map(Metrics::field_values).unwrap_or_default(),
+    })
+}
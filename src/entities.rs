@@ -7,6 +7,8 @@
 //! - State vector (memory, context, traits)
 //! - Memory graph (belief clusters with affective signals)
 //! - Essence index (well-being tracker)
+//! - Energy reserve (metabolic cost of motion)
+//! - Signals (explicit messages to nearby entities)
 //!
 //! ## Entity Lifecycle
 //!
@@ -25,17 +27,30 @@
 //! Ayomide I. Daniels (Morningstar)
 
 use serde::{Deserialize, Serialize};
-use crate::geometry::Pose;
-use crate::state::EntityStateVector;
+use crate::real::Real;
+use crate::geometry::{
+    forward_vector, periodic_distance, turn_orientation, Coords, GeometryConfig, Pose, SpatialGrid,
+};
+use crate::config::SimulationConfig;
+use crate::state::{EntityStateVector, TRAIT_DIM};
 use crate::memory::MemoryGraph;
 use crate::essence::EssenceIndex;
-use std::collections::HashMap;
+use crate::energy::EnergyConfig;
+use crate::roles::Role;
+use crate::signaling::SignalingConfig;
+use crate::species::{SpeciesConfig, SpeciesId, DEFAULT_STIMULUS_AMPLITUDE};
+use rand::Rng;
+use std::collections::{BTreeMap, HashMap};
+
+/// Similarity threshold for assigning events and merged clusters to
+/// existing belief clusters.
+pub const CLUSTER_TAU: Real = 0.7;
 
 /// Unique identifier for an entity.
 /// 
 /// Wraps a u32 to provide type safety and prevent confusion with
 /// other numeric values.
-#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash, Serialize, Deserialize)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Ord, PartialOrd, Hash, Serialize, Deserialize)]
 pub struct EntityId(pub u32);
 
 /// Embodied agent combining geometry, state, memory, and essence.
@@ -47,18 +62,75 @@ pub struct EntityId(pub u32);
 /// - State vector (internal representation)
 /// - Memory graph (belief structures)
 /// - Essence (affective well-being)
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct Entity {
     pub id: EntityId,
     pub pose: Pose,
-    pub velocity: Vec<f32>,
+    pub velocity: Coords,
     pub state: EntityStateVector,
     pub memory_graph: MemoryGraph,
     pub essence: EssenceIndex,
-    pub baseline_drives: (f32, f32), // (self-preservation, curiosity)
+    pub baseline_drives: (Real, Real), // (self-preservation, curiosity)
+    /// Innate drives the effective baseline drives respond around.
+    pub innate_drives: (Real, Real),
+    /// Species this entity belongs to (0 = default).
+    pub species: SpeciesId,
+    /// Inertial mass; acceleration is divided by it during integration.
+    pub mass: Real,
+    /// Amplitude of sensed stimulus noise.
+    pub stimulus_amplitude: Real,
+    /// Multiplier on this entity's contribution to the attraction field the
+    /// others feel; recorded pair attractions use the mean of both weights.
+    pub attraction_weight: Real,
+    /// Current energy reserve.
+    pub energy: Real,
+    /// Cumulative energy gained from positive experiences.
+    pub energy_gained: Real,
+    /// Cumulative energy spent on motion and action.
+    pub energy_spent: Real,
+    /// Consecutive steps spent with zero energy.
+    pub exhausted_steps: u32,
+    /// Dormant entities emit no attraction until they recover.
+    pub dormant: bool,
+    /// Consecutive steps with essence above the reproduction threshold.
+    pub high_essence_steps: u32,
+    /// Action chosen in the most recent decision step.
+    pub last_action: Vec<Real>,
+    /// Forces accumulated for the next integration step.
+    pub force: Vec<Real>,
+    /// User-assigned labels for analysis groups (e.g. "treatment").
+    #[serde(default)]
+    pub labels: Vec<String>,
+    /// Step at which the entity was created.
+    #[serde(default)]
+    pub birth_step: u64,
+    /// Parent entity (None for the initial population and manual spawns).
+    #[serde(default)]
+    pub parent: Option<EntityId>,
+    /// Current social role.
+    #[serde(default)]
+    pub role: Role,
+    /// Decaying leadership level (leader while at or above `LEADER_RETENTION`).
+    #[serde(default)]
+    pub leadership: Real,
+    /// Leader this entity follows, if it is a follower.
+    #[serde(default)]
+    pub follows: Option<EntityId>,
+    /// Relative attraction boost granted by the leader role.
+    #[serde(default)]
+    pub leader_boost: Real,
+    /// Attention gradient of the attraction field at the last attention
+    /// step; integration turns it into acceleration.
+    #[serde(default)]
+    pub attention_gradient: Vec<Real>,
 }
 
 impl Entity {
+    /// Start a fluent [`EntityBuilder`] for a `dimension`-dimensional world.
+    pub fn builder(dimension: usize) -> EntityBuilder {
+        EntityBuilder::new(dimension)
+    }
+
     /// Create a new entity.
     /// 
     /// Initializes an entity with zero velocity and default baseline drives.
@@ -83,14 +155,68 @@ impl Entity {
         Entity {
             id,
             pose,
-            velocity: vec![0.0; dim],
+            velocity: Coords::zeros(dim),
             state,
             memory_graph,
             essence,
             baseline_drives: (0.5, 0.5),
+            innate_drives: (0.5, 0.5),
+            species: SpeciesId(0),
+            mass: 1.0,
+            stimulus_amplitude: DEFAULT_STIMULUS_AMPLITUDE,
+            attraction_weight: 1.0,
+            energy: EnergyConfig::default().initial,
+            energy_gained: 0.0,
+            energy_spent: 0.0,
+            exhausted_steps: 0,
+            dormant: false,
+            high_essence_steps: 0,
+            last_action: vec![0.0; dim],
+            force: vec![0.0; dim],
+            labels: Vec::new(),
+            birth_step: 0,
+            parent: None,
+            role: Role::Member,
+            leadership: 0.0,
+            follows: None,
+            leader_boost: 0.0,
+            attention_gradient: vec![0.0; dim],
         }
     }
 
+    /// Apply species overrides to this entity.
+    /// 
+    /// Sets the species tag and replaces drives, mass, stimulus amplitude
+    /// and attraction weight wherever the species config provides a value.
+    /// 
+    /// # Arguments
+    /// * `species` - Species identifier
+    /// * `config` - Species parameter overrides
+    pub fn apply_species(&mut self, species: SpeciesId, config: &SpeciesConfig) {
+        self.species = species;
+        if let Some(preservation) = config.preservation {
+            self.innate_drives.0 = preservation;
+        }
+        if let Some(curiosity) = config.curiosity {
+            self.innate_drives.1 = curiosity;
+        }
+        self.baseline_drives = self.innate_drives;
+        if let Some(mass) = config.mass {
+            self.mass = mass;
+        }
+        if let Some(amplitude) = config.stimulus_amplitude {
+            self.stimulus_amplitude = amplitude;
+        }
+        if let Some(weight) = config.attraction_weight {
+            self.attraction_weight = weight;
+        }
+        config.essence.apply_to(&mut self.essence.config);
+        if let Some(baseline) = config.essence.baseline {
+            self.essence.value = baseline;
+        }
+        config.state.apply_to(&mut self.state.config);
+    }
+
     /// Sense the local environment.
     /// 
     /// Records a stimulus as a memory node and performs clustering
@@ -99,31 +225,61 @@ impl Entity {
     /// # Arguments
     /// * `stimulus` - Sensory input vector
     /// * `_timestamp` - Current simulation time
-    pub fn sense(&mut self, stimulus: Vec<f32>, _timestamp: u64) {
+    pub fn sense(&mut self, stimulus: &[Real], _timestamp: u64) {
         // Record stimulus as memory node
-        let node = crate::memory::MemoryNode::new(stimulus.clone(), _timestamp);
-        let idx = self.memory_graph.add_node(node);
-        self.memory_graph.cluster_event(&stimulus, idx, 0.7);
+        self.memory_graph.add_clustered_event(stimulus, _timestamp, CLUSTER_TAU);
+    }
+
+    /// Merge another entity's beliefs into this entity's memory.
+    /// 
+    /// Models horizontal, gene-transfer-like belief sharing: each of the
+    /// other entity's belief clusters is either folded into the closest
+    /// matching local cluster (prototype similarity above `CLUSTER_TAU`)
+    /// or added as a new cluster scaled by `blend_factor`. Used for entity
+    /// division/merge events and when cloning entities.
+    /// 
+    /// # Arguments
+    /// * `other` - Entity whose memory is merged in
+    /// * `blend_factor` - Weight of the incoming beliefs (0 = none, 1 = full)
+    pub fn merge_memory_from(&mut self, other: &Entity, blend_factor: Real) {
+        self.memory_graph
+            .merge_from(&other.memory_graph, blend_factor, CLUSTER_TAU);
     }
 
-    /// Update internal state based on affective signals.
+    /// Update internal state from attention and memory.
     /// 
-    /// Integrates attention gradients into the state vector and
-    /// updates essence based on average affective signals from
-    /// belief clusters. This is where external forces shape
-    /// internal representation.
+    /// Applies the state update equation of [`EntityStateVector::update`]
+    /// (α·s + β·g(F) + γ·m) and then updates essence from the average
+    /// affective signal of the belief clusters. This is where external
+    /// forces and remembered experience shape internal representation.
     /// 
     /// # Arguments
-    /// * `attention_gradient` - Gradient from attraction field
-    pub fn update_state(&mut self, attention_gradient: &[f32]) {
-        // Update state vector with gradient influence
+    /// * `attention_force` - Attention gradient from the attraction field
+    /// * `memory_input` - Memory input from the belief clusters
+    ///   (see [`MemoryGraph::memory_input_into`])
+    pub fn update_state(&mut self, attention_force: &[Real], memory_input: &[Real]) {
+        self.state.update(attention_force, memory_input);
+        self.update_essence_from_memory();
+    }
+
+    /// Update internal state with the legacy placeholder blend (deprecated).
+    /// 
+    /// Blends `gradient` into the state vector at a fixed 5% instead of
+    /// applying the state update equation; kept for `dynamics.legacy_pipeline`.
+    /// 
+    /// # Arguments
+    /// * `gradient` - Placeholder gradient (state memory dimension)
+    pub fn update_state_legacy(&mut self, gradient: &[Real]) {
         let len = self.state.memory.len();
         for i in 0..len {
-            let grad = attention_gradient.get(i).copied().unwrap_or(0.0);
+            let grad = gradient.get(i).copied().unwrap_or(0.0);
             self.state.memory[i] = self.state.memory[i] * 0.95 + grad * 0.05;
         }
+        self.update_essence_from_memory();
+    }
 
-        // Update essence based on memory affective signals
+    /// Update essence from the average affective signal of the belief clusters.
+    fn update_essence_from_memory(&mut self) {
         let mut total_affective = 0.0;
         for cluster in self.memory_graph.clusters.values() {
             total_affective += cluster.affective_signal;
@@ -131,7 +287,7 @@ impl Entity {
         let avg_affective = if self.memory_graph.clusters.is_empty() {
             0.0
         } else {
-            total_affective / self.memory_graph.clusters.len() as f32
+            total_affective / self.memory_graph.clusters.len() as Real
         };
 
         self.essence.update(&[avg_affective]);
@@ -145,86 +301,598 @@ impl Entity {
     /// 
     /// # Returns
     /// Action vector to be converted to acceleration
-    pub fn decide(&self) -> Vec<f32> {
+    pub fn decide(&self) -> Vec<Real> {
+        let mut action = Vec::with_capacity(self.state.memory.len());
+        self.decide_into(&mut action);
+        action
+    }
+
+    /// Decide on an action, writing it into an existing buffer.
+    /// 
+    /// Same as [`Entity::decide`] but reuses `action`'s allocation.
+    /// 
+    /// # Arguments
+    /// * `action` - Buffer replaced by the action vector
+    pub fn decide_into(&self, action: &mut Vec<Real>) {
         let preservation = self.baseline_drives.0;
         let curiosity = self.baseline_drives.1;
         let essence_influence = self.essence.influence_factor();
 
         // Simplified decision: stochastic combination of drives
-        let mut action = self.state.memory.clone();
-        for a in &mut action {
-            *a = *a * (preservation + curiosity) * essence_influence;
+        action.clear();
+        action.extend(
+            self.state
+                .memory
+                .iter()
+                .map(|m| m * (preservation + curiosity) * essence_influence),
+        );
+    }
+
+    /// Apply action as a force.
+    /// 
+    /// Converts the action vector to a spatial force (its leading
+    /// components, one per spatial axis), limits its magnitude and adds it
+    /// to the force accumulator consumed by the next integration.
+    /// 
+    /// # Arguments
+    /// * `action` - Action vector from decision
+    /// * `max_force` - Largest allowed force magnitude
+    pub fn act(&mut self, action: &[Real], max_force: Real) {
+        let component = |i: usize| action.get(i).copied().unwrap_or(0.0);
+        let magnitude = (0..self.force.len()).map(|i| component(i) * component(i)).sum::<Real>().sqrt();
+        let scale = crate::energy::magnitude_scale(magnitude, max_force);
+        for (i, f) in self.force.iter_mut().enumerate() {
+            *f += component(i) * scale;
+        }
+    }
+
+    /// Possibly emit a signal for nearby entities.
+    /// 
+    /// Only entities whose essence deviates from baseline by at least
+    /// `essence_extremity` may signal, and then only with probability
+    /// `emit_probability`. The message is the prototype of the entity's
+    /// dominant belief cluster. Dormant entities stay silent.
+    /// 
+    /// # Arguments
+    /// * `config` - Signaling configuration
+    /// * `rng` - Random source for the emission draw
+    /// 
+    /// # Returns
+    /// Message vector, or None if no signal is emitted
+    pub fn emit_signal(&self, config: &SignalingConfig, rng: &mut impl Rng) -> Option<Vec<Real>> {
+        if self.dormant {
+            return None;
+        }
+        let extremity = (self.essence.value - self.essence.config.baseline).abs();
+        if extremity < config.essence_extremity {
+            return None;
+        }
+        if rng.gen::<Real>() >= config.emit_probability {
+            return None;
+        }
+        let cluster_id = self.memory_graph.dominant_cluster()?;
+        self.memory_graph.cluster_prototype(cluster_id)
+    }
+
+    /// Recompute effective drives from the current situation.
+    /// 
+    /// # Arguments
+    /// * `nearest_distance` - Distance to the nearest other entity
+    /// * `responsiveness` - Weight of the situational response
+    pub fn update_drives(&mut self, nearest_distance: Real, responsiveness: Real) {
+        let attention_magnitude = self.state.memory.iter().map(|m| m * m).sum::<Real>().sqrt();
+        let (preservation, curiosity) =
+            crate::dynamics::compute_baseline_drives(nearest_distance, attention_magnitude);
+        self.baseline_drives = (
+            self.innate_drives.0 + responsiveness * preservation,
+            self.innate_drives.1 + responsiveness * curiosity,
+        );
+    }
+
+    /// Mean absolute affective signal over belief clusters.
+    pub fn affective_strength(&self) -> Real {
+        if self.memory_graph.clusters.is_empty() {
+            0.0
+        } else {
+            self.memory_graph
+                .clusters
+                .values()
+                .map(|c| c.affective_signal.abs())
+                .sum::<Real>()
+                / self.memory_graph.clusters.len() as Real
+        }
+    }
+
+    /// Mutual attention strength between this entity and another.
+    /// 
+    /// Mean emitted attraction weight of the pair, attenuated by distance
+    /// as `1 / (1 + d)`. This is the pairwise attraction recorded per step.
+    pub fn attention_strength(&self, other: &Entity) -> Real {
+        let distance = self.pose.distance_to(&other.pose);
+        let weight = 0.5 * (self.emitted_attraction_weight() + other.emitted_attraction_weight());
+        weight / (1.0 + distance)
+    }
+
+    /// Age of the entity in steps.
+    /// 
+    /// # Arguments
+    /// * `current_step` - Current simulation step
+    pub fn age(&self, current_step: u64) -> u64 {
+        current_step.saturating_sub(self.birth_step)
+    }
+
+    /// Add a label unless it is already present.
+    pub fn add_label(&mut self, label: &str) {
+        if !self.has_label(label) {
+            self.labels.push(label.to_string());
         }
-        action
     }
 
-    /// Apply action as acceleration.
+    /// Whether the entity carries the given label.
+    pub fn has_label(&self, label: &str) -> bool {
+        self.labels.iter().any(|l| l == label)
+    }
+
+    /// Whether the entity has run out of energy.
+    pub fn is_exhausted(&self) -> bool {
+        self.energy <= 0.0
+    }
+
+    /// Attraction weight this entity currently emits (zero while dormant,
+    /// boosted while leading).
+    pub fn emitted_attraction_weight(&self) -> Real {
+        if self.dormant {
+            0.0
+        } else {
+            self.attraction_weight * (1.0 + self.leader_boost)
+        }
+    }
+
+    /// Apply one step of metabolism.
     /// 
-    /// Converts action vector to velocity changes.
-    /// Limited to prevent unbounded speeds.
+    /// Drains energy proportionally to speed² and action magnitude and
+    /// replenishes it from positive affective signals of belief clusters.
+    /// Energy is clamped to [0, capacity]; only the energy actually moved
+    /// is recorded in the gained/spent ledger. Tracks exhaustion and
+    /// dormancy transitions.
     /// 
     /// # Arguments
-    /// * `acceleration` - Acceleration vector from decision
-    pub fn act(&mut self, acceleration: Vec<f32>) {
-        // Apply acceleration via velocity update
-        for i in 0..self.velocity.len() {
-            if i < acceleration.len() {
-                self.velocity[i] += acceleration[i];
+    /// * `action_magnitude` - Magnitude of the acceleration applied this step
+    /// * `config` - Energy configuration
+    pub fn metabolize(&mut self, action_magnitude: Real, config: &EnergyConfig) {
+        let speed_sq = self.velocity.norm_squared();
+        let cost = config.speed_drain * speed_sq + config.action_drain * action_magnitude;
+        let spent = cost.min(self.energy).max(0.0);
+        self.energy -= spent;
+        self.energy_spent += spent;
+
+        let positive_affect: Real = self
+            .memory_graph
+            .clusters
+            .values()
+            .map(|c| c.affective_signal.max(0.0))
+            .sum();
+        let gain = (config.replenish_rate * positive_affect)
+            .min(config.capacity - self.energy)
+            .max(0.0);
+        self.energy += gain;
+        self.energy_gained += gain;
+
+        if self.is_exhausted() {
+            self.exhausted_steps += 1;
+            if config.dormancy_steps > 0 && self.exhausted_steps >= config.dormancy_steps {
+                self.dormant = true;
             }
+        } else {
+            self.exhausted_steps = 0;
+            self.dormant = false;
+        }
+    }
+
+    /// Turn to face the direction of motion.
+    /// 
+    /// The heading rotates toward the velocity by at most `max_angle`
+    /// radians; entities at rest keep their orientation.
+    /// 
+    /// # Arguments
+    /// * `max_angle` - Largest heading change allowed this step
+    pub fn update_heading(&mut self, max_angle: Real) {
+        if self.velocity.norm() < 1e-6 {
+            return;
         }
+        self.pose.orientation = turn_orientation(&self.pose.orientation, &self.velocity, max_angle);
+    }
+
+    /// Unit vector the entity is facing, derived from its orientation.
+    pub fn forward(&self) -> Vec<Real> {
+        forward_vector(&self.pose.orientation, self.pose.position.len())
     }
 
     /// Integration step with perpetual velocity.
     pub fn integrate(
         &mut self,
-        acceleration: Vec<f32>,
-        dt: f32,
-        min_speed: f32,
-        damping: f32,
+        acceleration: &[Real],
+        dt: Real,
+        min_speed: Real,
+        damping: Real,
     ) {
         // Apply acceleration and damping
-        for i in 0..self.velocity.len() {
-            let acc = acceleration.get(i).copied().unwrap_or(0.0);
-            self.velocity[i] = (self.velocity[i] + dt * acc) * damping;
-        }
+        let dimension = self.velocity.len();
+        let acceleration = Coords::from_slice(&acceleration[..dimension.min(acceleration.len())]);
+        self.velocity.add_scaled(dt, &acceleration);
+        self.velocity.scale(damping);
 
         // Enforce perpetual velocity
-        let speed_sq: f32 = self.velocity.iter().map(|v| v * v).sum();
-        let speed = speed_sq.sqrt();
-
+        let speed = self.velocity.norm();
         if speed < min_speed && speed > 1e-6 {
-            let scale = min_speed / speed;
-            for v in &mut self.velocity {
-                *v *= scale;
-            }
+            self.velocity.scale(min_speed / speed);
         } else if speed <= 1e-6 {
             self.velocity[0] = min_speed;
         }
 
         // Update position
-        for i in 0..self.pose.position.len() {
-            if i < self.velocity.len() {
-                self.pose.position[i] += dt * self.velocity[i];
+        self.pose.position.add_scaled(dt, &self.velocity);
+    }
+}
+
+/// Error returned when an [`EntityBuilder`] is given inconsistent parts.
+#[derive(Clone, Debug, PartialEq)]
+pub enum BuildError {
+    /// Builder dimension differs from the configured world dimension.
+    WorldDimension { expected: usize, found: usize },
+    /// Position length differs from the builder dimension.
+    Position { expected: usize, found: usize },
+    /// Velocity length differs from the builder dimension.
+    Velocity { expected: usize, found: usize },
+    /// A memory event's length differs from the builder dimension.
+    MemoryEvent { index: usize, expected: usize, found: usize },
+    /// Trait vector length differs from `TRAIT_DIM`.
+    Traits { expected: usize, found: usize },
+    /// Essence value outside [0, 10].
+    Essence(Real),
+}
+
+impl std::fmt::Display for BuildError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            BuildError::WorldDimension { expected, found } => write!(
+                f,
+                "entity dimension {} does not match world dimension {}",
+                found, expected
+            ),
+            BuildError::Position { expected, found } => {
+                write!(f, "position has {} components, expected {}", found, expected)
+            }
+            BuildError::Velocity { expected, found } => {
+                write!(f, "velocity has {} components, expected {}", found, expected)
+            }
+            BuildError::MemoryEvent { index, expected, found } => write!(
+                f,
+                "memory event {} has {} components, expected {}",
+                index, found, expected
+            ),
+            BuildError::Traits { expected, found } => {
+                write!(f, "traits have {} components, expected {}", found, expected)
+            }
+            BuildError::Essence(value) => {
+                write!(f, "essence {} is outside [0, 10]", value)
+            }
+        }
+    }
+}
+
+impl std::error::Error for BuildError {}
+
+/// Fluent builder for constructing entities programmatically.
+/// 
+/// Unset parts fall back to the configured defaults: the origin, zero
+/// velocity, baseline essence, zero traits, (0.5, 0.5) drives and an
+/// empty memory. Dimensions are checked in [`EntityBuilder::build`].
+/// 
+/// # Examples
+/// ```
+/// use synthetic_consciousness::config::SimulationConfig;
+/// use synthetic_consciousness::entities::Entity;
+/// 
+/// let config = SimulationConfig::default_2d();
+/// let entity = Entity::builder(2)
+///     .position(vec![1.0, 2.0])
+///     .velocity(vec![0.1, 0.0])
+///     .essence(7.0)
+///     .drives(0.3, 0.8)
+///     .with_memory_events(vec![vec![1.0, 0.0], vec![0.9, 0.1], vec![0.0, 1.0]])
+///     .build(&config)
+///     .unwrap();
+/// 
+/// assert_eq!(entity.pose.position, vec![1.0, 2.0]);
+/// assert_eq!(entity.memory_graph.nodes.len(), 3);
+/// assert_eq!(entity.memory_graph.clusters.len(), 2);
+/// 
+/// // Mismatched dimensions are rejected
+/// assert!(Entity::builder(2).position(vec![1.0]).build(&config).is_err());
+/// ```
+#[derive(Clone, Debug)]
+pub struct EntityBuilder {
+    dimension: usize,
+    position: Option<Vec<Real>>,
+    orientation: [Real; 4],
+    velocity: Option<Vec<Real>>,
+    essence: Option<Real>,
+    traits: Option<Vec<Real>>,
+    drives: (Real, Real),
+    memory_events: Vec<Vec<Real>>,
+}
+
+impl EntityBuilder {
+    /// Start building an entity in a `dimension`-dimensional world.
+    pub fn new(dimension: usize) -> Self {
+        EntityBuilder {
+            dimension,
+            position: None,
+            orientation: [1.0, 0.0, 0.0, 0.0],
+            velocity: None,
+            essence: None,
+            traits: None,
+            drives: (0.5, 0.5),
+            memory_events: Vec::new(),
+        }
+    }
+
+    /// Set the initial position.
+    pub fn position(mut self, position: Vec<Real>) -> Self {
+        self.position = Some(position);
+        self
+    }
+
+    /// Set the initial orientation quaternion.
+    pub fn orientation(mut self, orientation: [Real; 4]) -> Self {
+        self.orientation = orientation;
+        self
+    }
+
+    /// Set the initial velocity.
+    pub fn velocity(mut self, velocity: Vec<Real>) -> Self {
+        self.velocity = Some(velocity);
+        self
+    }
+
+    /// Set the initial essence value.
+    pub fn essence(mut self, value: Real) -> Self {
+        self.essence = Some(value);
+        self
+    }
+
+    /// Set the persistent trait vector (length `TRAIT_DIM`).
+    pub fn traits(mut self, traits: Vec<Real>) -> Self {
+        self.traits = Some(traits);
+        self
+    }
+
+    /// Set the innate (and initial baseline) drives.
+    pub fn drives(mut self, preservation: Real, curiosity: Real) -> Self {
+        self.drives = (preservation, curiosity);
+        self
+    }
+
+    /// Pre-load memory with events, clustered as if sensed in order.
+    pub fn with_memory_events(mut self, events: Vec<Vec<Real>>) -> Self {
+        self.memory_events.extend(events);
+        self
+    }
+
+    /// Validate the parts and construct the entity.
+    /// 
+    /// The entity gets id 0; the pool assigns the real id on insertion.
+    /// 
+    /// # Arguments
+    /// * `config` - Simulation config supplying state, essence and energy defaults
+    /// 
+    /// # Returns
+    /// The entity, or the first inconsistency found
+    pub fn build(self, config: &SimulationConfig) -> Result<Entity, BuildError> {
+        let dim = self.dimension;
+        if dim != config.geometry.dimension {
+            return Err(BuildError::WorldDimension {
+                expected: config.geometry.dimension,
+                found: dim,
+            });
+        }
+        let position = self.position.unwrap_or_else(|| vec![0.0; dim]);
+        if position.len() != dim {
+            return Err(BuildError::Position { expected: dim, found: position.len() });
+        }
+        let velocity = self.velocity.unwrap_or_else(|| vec![0.0; dim]);
+        if velocity.len() != dim {
+            return Err(BuildError::Velocity { expected: dim, found: velocity.len() });
+        }
+        for (index, event) in self.memory_events.iter().enumerate() {
+            if event.len() != dim {
+                return Err(BuildError::MemoryEvent { index, expected: dim, found: event.len() });
+            }
+        }
+
+        let mut state = EntityStateVector::new(config.state.clone());
+        if let Some(traits) = self.traits {
+            if traits.len() != TRAIT_DIM {
+                return Err(BuildError::Traits { expected: TRAIT_DIM, found: traits.len() });
+            }
+            state.traits = traits;
+        }
+
+        let mut essence = EssenceIndex::new(config.essence.clone());
+        if let Some(value) = self.essence {
+            if !(0.0..=10.0).contains(&value) {
+                return Err(BuildError::Essence(value));
             }
+            essence.value = value;
+        }
+
+        let pose = Pose {
+            position: position.into(),
+            orientation: self.orientation,
+        };
+        let mut memory_graph = MemoryGraph::new();
+        memory_graph.dedup_threshold = config.state.dedup_threshold;
+        let mut entity = Entity::new(EntityId(0), pose, state, memory_graph, essence);
+        entity.velocity = velocity.into();
+        entity.energy = config.energy.initial;
+        entity.innate_drives = self.drives;
+        entity.baseline_drives = self.drives;
+        for event in self.memory_events {
+            entity.sense(&event, 0);
+        }
+        Ok(entity)
+    }
+}
+
+/// Per-entity configuration section.
+/// 
+/// Entries refer to entities by id (initial entities are numbered from 1
+/// in creation order). Parameter overrides take precedence over the
+/// entity's species; unset values are left unchanged.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct EntityConfig {
+    /// Id of the entity to configure.
+    pub id: u32,
+    /// Labels assigned at initialization.
+    #[serde(default)]
+    pub labels: Vec<String>,
+    /// Self-preservation drive override.
+    #[serde(default)]
+    pub preservation: Option<Real>,
+    /// Curiosity drive override.
+    #[serde(default)]
+    pub curiosity: Option<Real>,
+    /// Inertial mass override.
+    #[serde(default)]
+    pub mass: Option<Real>,
+    /// Stimulus noise amplitude override.
+    #[serde(default)]
+    pub stimulus_amplitude: Option<Real>,
+    /// Attraction weight override.
+    #[serde(default)]
+    pub attraction_weight: Option<Real>,
+}
+
+impl EntityConfig {
+    /// Check that overrides are within meaningful ranges.
+    pub fn is_valid(&self) -> bool {
+        self.mass.is_none_or(|m| m > 0.0)
+            && self.stimulus_amplitude.is_none_or(|a| a >= 0.0)
+            && self.attraction_weight.is_none_or(|w| w >= 0.0)
+    }
+
+    /// Apply the labels and parameter overrides to an entity.
+    /// 
+    /// A drive override also resets the corresponding baseline drive.
+    /// 
+    /// # Arguments
+    /// * `entity` - Entity to modify (its id is not checked)
+    pub fn apply_to(&self, entity: &mut Entity) {
+        for label in &self.labels {
+            entity.add_label(label);
+        }
+        if let Some(preservation) = self.preservation {
+            entity.innate_drives.0 = preservation;
+            entity.baseline_drives.0 = preservation;
+        }
+        if let Some(curiosity) = self.curiosity {
+            entity.innate_drives.1 = curiosity;
+            entity.baseline_drives.1 = curiosity;
+        }
+        if let Some(mass) = self.mass {
+            entity.mass = mass;
+        }
+        if let Some(amplitude) = self.stimulus_amplitude {
+            entity.stimulus_amplitude = amplitude;
+        }
+        if let Some(weight) = self.attraction_weight {
+            entity.attraction_weight = weight;
         }
     }
 }
 
 /// Collection of entities in simulation.
+/// 
+/// Entities are kept ordered by id, so every iteration (and any sum or
+/// recording built from one) visits them in the same order in every run.
+/// 
+/// Serializes as a list of entities ordered by id plus the id counter, so
+/// ids are preserved across a round trip. On deserialization the counter
+/// is raised to `max(id) + 1` if needed, so new entities never reuse an id.
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(into = "EntityPoolData", from = "EntityPoolData")]
 pub struct EntityPool {
-    entities: HashMap<EntityId, Entity>,
+    entities: BTreeMap<EntityId, Entity>,
     next_id: u32,
+    /// Grid over the positions; kept between rebuilds for its buffers
+    neighbor_grid: NeighborGrid,
+    /// Whether `neighbor_grid` matches the positions; cleared by any mutable
+    /// access to the pool
+    indexed: bool,
+}
+
+/// Spatial grid over the pool's positions, with the entity of each point.
+#[derive(Clone, Default)]
+struct NeighborGrid {
+    grid: SpatialGrid,
+    ids: Vec<EntityId>,
+}
+
+/// Serialized form of an entity pool.
+#[derive(Serialize, Deserialize)]
+struct EntityPoolData {
+    entities: Vec<Entity>,
+    #[serde(default)]
+    next_id: u32,
+}
+
+impl From<EntityPool> for EntityPoolData {
+    fn from(pool: EntityPool) -> Self {
+        let entities: Vec<Entity> = pool.entities.into_values().collect();
+        EntityPoolData {
+            entities,
+            next_id: pool.next_id,
+        }
+    }
+}
+
+impl From<EntityPoolData> for EntityPool {
+    fn from(data: EntityPoolData) -> Self {
+        let next_free = data.entities.iter().map(|e| e.id.0 + 1).max().unwrap_or(1);
+        EntityPool {
+            next_id: data.next_id.max(next_free),
+            entities: data.entities.into_iter().map(|e| (e.id, e)).collect(),
+            neighbor_grid: NeighborGrid::default(),
+            indexed: false,
+        }
+    }
 }
 
 impl EntityPool {
     pub fn new() -> Self {
         EntityPool {
-            entities: HashMap::new(),
+            entities: BTreeMap::new(),
             next_id: 1,
+            neighbor_grid: NeighborGrid::default(),
+            indexed: false,
         }
     }
 
+    /// Pool holding `entities` under their own ids (e.g. entities taken
+    /// from another simulation); new entities get ids after the highest.
+    /// Ids must be distinct.
+    pub fn with_entities(entities: Vec<Entity>) -> Self {
+        EntityPoolData { entities, next_id: 0 }.into()
+    }
+
+    /// All entities of the pool, in id order.
+    pub fn into_entities(self) -> Vec<Entity> {
+        EntityPoolData::from(self).entities
+    }
+
     pub fn add_entity(&mut self, mut entity: Entity) -> EntityId {
+        self.indexed = false;
         let id = EntityId(self.next_id);
         entity.id = id;
         self.entities.insert(id, entity);
@@ -232,23 +900,307 @@ impl EntityPool {
         id
     }
 
+    /// Remove every entity and restart ids from 1, keeping the allocation.
+    pub fn clear(&mut self) {
+        self.indexed = false;
+        self.entities.clear();
+        self.next_id = 1;
+    }
+
+    /// Remove an entity from the pool; its id is never reused.
+    /// 
+    /// # Returns
+    /// The removed entity, or None if no entity has this id
+    pub fn remove_entity(&mut self, id: EntityId) -> Option<Entity> {
+        self.indexed = false;
+        self.entities.remove(&id)
+    }
+
     pub fn get_entity(&self, id: EntityId) -> Option<&Entity> {
         self.entities.get(&id)
     }
 
     pub fn get_entity_mut(&mut self, id: EntityId) -> Option<&mut Entity> {
+        self.indexed = false;
         self.entities.get_mut(&id)
     }
 
+    /// Collect references to all entities, in id order.
+    /// 
+    /// Allocates; step phases use [`EntityPool::iter`] instead.
     pub fn all_entities(&self) -> Vec<&Entity> {
         self.entities.values().collect()
     }
 
+    /// Collect mutable references to all entities, in id order.
+    /// 
+    /// Allocates; step phases use [`EntityPool::iter_mut`] instead.
     pub fn all_entities_mut(&mut self) -> Vec<&mut Entity> {
+        self.indexed = false;
         self.entities.values_mut().collect()
     }
 
+    /// Iterate over all entities in id order without allocating.
+    pub fn iter(&self) -> impl ExactSizeIterator<Item = &Entity> {
+        self.entities.values()
+    }
+
+    /// Iterate mutably over all entities in id order without allocating.
+    pub fn iter_mut(&mut self) -> impl ExactSizeIterator<Item = &mut Entity> {
+        self.indexed = false;
+        self.entities.values_mut()
+    }
+
     pub fn count(&self) -> usize {
         self.entities.len()
     }
+
+    /// Mean position of all entities (center of mass with unit weights).
+    /// 
+    /// # Returns
+    /// Component-wise mean position, or an empty vector if the pool is empty
+    pub fn centroid_position(&self) -> Vec<Real> {
+        Self::mean_vector(self.iter().map(|e| e.pose.position.as_slice()))
+    }
+
+    /// Mean velocity of all entities.
+    /// 
+    /// # Returns
+    /// Component-wise mean velocity, or an empty vector if the pool is empty
+    pub fn centroid_velocity(&self) -> Vec<Real> {
+        Self::mean_vector(self.iter().map(|e| e.velocity.as_slice()))
+    }
+
+    fn mean_vector<'a>(vectors: impl Iterator<Item = &'a [Real]>) -> Vec<Real> {
+        let mut sum: Vec<Real> = Vec::new();
+        let mut count = 0;
+        for v in vectors {
+            if sum.len() < v.len() {
+                sum.resize(v.len(), 0.0);
+            }
+            for (s, x) in sum.iter_mut().zip(v.iter()) {
+                *s += x;
+            }
+            count += 1;
+        }
+        if count > 0 {
+            for s in &mut sum {
+                *s /= count as Real;
+            }
+        }
+        sum
+    }
+
+    /// Ids of the entities carrying a label, in ascending order.
+    pub fn with_label(&self, label: &str) -> Vec<EntityId> {
+        self.entities.values().filter(|e| e.has_label(label)).map(|e| e.id).collect()
+    }
+
+    /// Current role of every entity.
+    pub fn roles(&self) -> HashMap<EntityId, Role> {
+        self.entities.values().map(|e| (e.id, e.role)).collect()
+    }
+
+    /// Group entities by role.
+    /// 
+    /// Entities missing from `roles` count as `Role::Member`. Each group is
+    /// ordered by entity id.
+    /// 
+    /// # Arguments
+    /// * `roles` - Role of each entity (e.g. from [`EntityPool::roles`])
+    /// 
+    /// # Returns
+    /// Entities of each role present in the pool
+    pub fn partition_by_role(&self, roles: &HashMap<EntityId, Role>) -> HashMap<Role, Vec<&Entity>> {
+        let mut groups: HashMap<Role, Vec<&Entity>> = HashMap::new();
+        for entity in self.entities.values() {
+            let role = roles.get(&entity.id).copied().unwrap_or_default();
+            groups.entry(role).or_default().push(entity);
+        }
+        groups
+    }
+
+    /// Index the current positions in a spatial grid with cells
+    /// `cell_size` wide.
+    /// 
+    /// [`EntityPool::neighbors_within`] and [`EntityPool::nearest`] then
+    /// only visit nearby cells instead of scanning the pool. Any mutable
+    /// access to the pool drops the grid, and queries fall back to brute
+    /// force until it is rebuilt.
+    /// 
+    /// # Arguments
+    /// * `cell_size` - Cell edge length; queries are cheapest with cells
+    ///   about as wide as their radius
+    pub fn index_positions(&mut self, cell_size: Real) {
+        let index = &mut self.neighbor_grid;
+        // Rebuilding a grid of the same cell size reuses its cell vectors
+        if index.grid.cell_size() != cell_size {
+            index.grid = SpatialGrid::new(cell_size);
+        }
+        index.grid.rebuild(self.entities.values().map(|e| e.pose.position.as_slice()));
+        index.ids.clear();
+        index.ids.extend(self.entities.keys().copied());
+        self.indexed = true;
+    }
+
+    /// Whether neighbor queries are currently backed by a spatial grid.
+    pub fn has_position_index(&self) -> bool {
+        self.indexed
+    }
+
+    /// The spatial grid, if it matches the current positions.
+    fn position_index(&self) -> Option<&NeighborGrid> {
+        self.indexed.then_some(&self.neighbor_grid)
+    }
+
+    /// Find all entities within `radius` of entity `id`.
+    /// 
+    /// Distances respect periodic boundaries (minimum image). The query
+    /// entity itself is excluded. Only borrows the pool immutably, so it can
+    /// be used while iterating entities during a step.
+    /// 
+    /// Complexity: O(m log m) for the m entities in the grid cells within
+    /// `radius` after [`EntityPool::index_positions`], otherwise an O(n)
+    /// brute-force scan over the pool.
+    /// 
+    /// # Arguments
+    /// * `id` - Query entity
+    /// * `radius` - Inclusive search radius
+    /// * `geometry` - Geometry configuration (bounds and periodicity)
+    /// 
+    /// # Returns
+    /// (neighbor id, distance) pairs sorted by ascending distance; empty if
+    /// `id` is not in the pool
+    pub fn neighbors_within(
+        &self,
+        id: EntityId,
+        radius: Real,
+        geometry: &GeometryConfig,
+    ) -> Vec<(EntityId, Real)> {
+        let (Some(origin), Some(index)) = (self.entities.get(&id), self.position_index()) else {
+            let mut neighbors = self.distances_from(id, geometry);
+            neighbors.retain(|&(_, dist)| dist <= radius);
+            return neighbors;
+        };
+
+        let mut neighbors = Vec::new();
+        index.grid.for_each_candidate(&origin.pose.position, radius, geometry, |i| {
+            let other_id = index.ids[i];
+            if other_id == id {
+                return;
+            }
+            let other = &self.entities[&other_id];
+            let dist = periodic_distance(&origin.pose.position, &other.pose.position, geometry);
+            if dist <= radius {
+                neighbors.push((other_id, dist));
+            }
+        });
+        // Periodic images can find the same entity twice
+        neighbors.sort_by(by_distance);
+        neighbors.dedup_by_key(|(other_id, _)| *other_id);
+        neighbors
+    }
+
+    /// Find the `k` nearest entities to entity `id`.
+    /// 
+    /// Distances respect periodic boundaries (minimum image). The query
+    /// entity itself is excluded.
+    /// 
+    /// Complexity: after [`EntityPool::index_positions`], grid searches of
+    /// doubling radius until `k` entities are found (typically O(k log k));
+    /// otherwise an O(n log n) brute-force scan and sort.
+    /// 
+    /// # Arguments
+    /// * `id` - Query entity
+    /// * `k` - Maximum number of neighbors to return
+    /// * `geometry` - Geometry configuration (bounds and periodicity)
+    /// 
+    /// # Returns
+    /// Up to `k` (neighbor id, distance) pairs sorted by ascending distance
+    pub fn nearest(&self, id: EntityId, k: usize, geometry: &GeometryConfig) -> Vec<(EntityId, Real)> {
+        if let Some(index) = self.position_index() {
+            // Everything closer than the k-th entity found lies within the
+            // searched radius, so the first k are the nearest
+            let span = geometry.bounds.iter().map(|b| b * b).sum::<Real>().sqrt();
+            let mut radius = index.grid.cell_size();
+            while radius > 0.0 && radius < span {
+                let mut neighbors = self.neighbors_within(id, radius, geometry);
+                if neighbors.len() >= k {
+                    neighbors.truncate(k);
+                    return neighbors;
+                }
+                radius *= 2.0;
+            }
+        }
+        let mut neighbors = self.distances_from(id, geometry);
+        neighbors.truncate(k);
+        neighbors
+    }
+
+    /// Distance from entity `id` to its nearest neighbor.
+    /// 
+    /// Same as the distance of `nearest(id, 1, ..)` but without allocating.
+    /// Complexity: after [`EntityPool::index_positions`], grid searches of
+    /// doubling radius until a neighbor is found; otherwise O(n).
+    /// 
+    /// # Returns
+    /// The smallest periodic distance to another entity, or None if `id`
+    /// is not in the pool or has no neighbors
+    pub fn nearest_distance(&self, id: EntityId, geometry: &GeometryConfig) -> Option<Real> {
+        let origin = self.entities.get(&id)?;
+        if let Some(index) = self.position_index() {
+            let span = geometry.bounds.iter().map(|b| b * b).sum::<Real>().sqrt();
+            let mut radius = index.grid.cell_size();
+            while radius > 0.0 && radius < span {
+                let mut nearest: Option<Real> = None;
+                index.grid.for_each_candidate(&origin.pose.position, radius, geometry, |i| {
+                    if index.ids[i] == id {
+                        return;
+                    }
+                    let other = &self.entities[&index.ids[i]];
+                    let dist = periodic_distance(&origin.pose.position, &other.pose.position, geometry);
+                    if dist <= radius && nearest.is_none_or(|best| dist < best) {
+                        nearest = Some(dist);
+                    }
+                });
+                if nearest.is_some() {
+                    return nearest;
+                }
+                radius *= 2.0;
+            }
+        }
+        self.entities
+            .values()
+            .filter(|other| other.id != id)
+            .map(|other| periodic_distance(&origin.pose.position, &other.pose.position, geometry))
+            .min_by(|a, b| a.total_cmp(b))
+    }
+
+    /// Distances from entity `id` to every other entity, sorted by distance
+    /// (ties broken by id for determinism).
+    fn distances_from(&self, id: EntityId, geometry: &GeometryConfig) -> Vec<(EntityId, Real)> {
+        let Some(origin) = self.entities.get(&id) else {
+            return Vec::new();
+        };
+
+        let mut distances: Vec<(EntityId, Real)> = self
+            .entities
+            .values()
+            .filter(|other| other.id != id)
+            .map(|other| {
+                (
+                    other.id,
+                    periodic_distance(&origin.pose.position, &other.pose.position, geometry),
+                )
+            })
+            .collect();
+        distances.sort_by(by_distance);
+        distances
+    }
+}
+
+/// Order (id, distance) pairs by distance, ties broken by id for
+/// determinism.
+fn by_distance(a: &(EntityId, Real), b: &(EntityId, Real)) -> std::cmp::Ordering {
+    a.1.total_cmp(&b.1).then(a.0 .0.cmp(&b.0 .0))
 }
@@ -0,0 +1,181 @@
+//! Environment module: fixed places in the simulated world.
+//!
+//! Without an environment the world is featureless and entities move only
+//! under each other's influence and their own noise. The `environment`
+//! config section lists fixed points that give space a structure:
+//!
+//! ```toml
+//! # A meaningful place entities are drawn to
+//! [[environment.points]]
+//! position = [2.5, 2.5]
+//! radius = 0.5
+//! strength = 2.0
+//!
+//! # A wall entities cannot enter
+//! [[environment.points]]
+//! position = [7.5, 5.0]
+//! radius = 1.0
+//! obstacle = true
+//! ```
+//!
+//! ## Field
+//!
+//! Each point adds to the attraction field like an extra entity weighted by
+//! its `strength`, with the kernel evaluated at the distance from its edge
+//! (`radius`). Like the entities' prompt it is the negative gradient of the
+//! field, `F_env(x) = -Σ s_k ∇K(max(‖x - p_k‖ - r_k, 0), σ)`, and entities
+//! accelerate against it: positive strengths draw them toward the point and
+//! negative ones push them away.
+//!
+//! ## Obstacles
+//!
+//! Entities that end a step inside an obstacle are moved back onto its edge
+//! during the boundary phase, and the part of their velocity pointing into
+//! it is removed. An obstacle's `strength` still shapes the field, so a
+//! negative strength keeps entities from running into it.
+//!
+//! ## Author
+//! Ayomide I. Daniels (Morningstar)
+
+use crate::attraction::{compute_kernel, AttractionConfig};
+use crate::real::Real;
+use serde::{Deserialize, Serialize};
+
+/// Finite-difference step of the field gradient (as for entities).
+const GRADIENT_STEP: Real = 1e-5;
+
+/// A fixed point in space.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct EnvironmentPoint {
+    /// Center, one coordinate per spatial dimension
+    pub position: Vec<Real>,
+    /// Extent; the field is measured from the edge (0 = a bare point)
+    #[serde(default)]
+    pub radius: Real,
+    /// Signed pull on entities: positive attracts, negative repels
+    #[serde(default)]
+    pub strength: Real,
+    /// Whether entities are kept out of the point's radius
+    #[serde(default)]
+    pub obstacle: bool,
+}
+
+impl EnvironmentPoint {
+    /// Distance from `position` to the point's edge, zero inside it.
+    pub fn distance_from_edge(&self, position: &[Real]) -> Real {
+        (distance(position, &self.position) - self.radius).max(0.0)
+    }
+}
+
+/// Configuration of the fixed environment (empty by default).
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct EnvironmentConfig {
+    /// Attractors, repellers and obstacles
+    #[serde(default)]
+    pub points: Vec<EnvironmentPoint>,
+}
+
+impl EnvironmentConfig {
+    /// Whether the world has no fixed points.
+    pub fn is_empty(&self) -> bool {
+        self.points.is_empty()
+    }
+
+    /// Check every point against the world's dimensionality.
+    ///
+    /// # Returns
+    /// Index and description of the first invalid point
+    pub fn check(&self, dimension: usize) -> Result<(), (usize, String)> {
+        for (i, point) in self.points.iter().enumerate() {
+            if point.position.len() != dimension {
+                return Err((i, format!("position must have {} coordinates", dimension)));
+            }
+            if !point.position.iter().all(|p| p.is_finite()) {
+                return Err((i, "position must be finite".to_string()));
+            }
+            if !(point.radius.is_finite() && point.radius >= 0.0) {
+                return Err((i, "radius must be non-negative".to_string()));
+            }
+            if !point.strength.is_finite() {
+                return Err((i, "strength must be finite".to_string()));
+            }
+            if point.obstacle && point.radius <= 0.0 {
+                return Err((i, "obstacles need a positive radius".to_string()));
+            }
+        }
+        Ok(())
+    }
+
+    /// Strength-weighted kernel sum of all points at `position`, with
+    /// coordinate `shift.0` moved by `shift.1`.
+    fn field(&self, position: &[Real], shift: (usize, Real), attraction: &AttractionConfig) -> Real {
+        let mut field = 0.0;
+        for point in &self.points {
+            let squared: Real = position
+                .iter()
+                .zip(&point.position)
+                .enumerate()
+                .map(|(d, (x, p))| {
+                    let x = if d == shift.0 { x + shift.1 } else { *x };
+                    (x - p).powi(2)
+                })
+                .sum();
+            let from_edge = (squared.sqrt() - point.radius).max(0.0);
+            field += point.strength * compute_kernel(&attraction.kernel, from_edge, attraction.sigma);
+        }
+        field
+    }
+
+    /// Add the environment's attention prompt at `position` to `gradient`.
+    ///
+    /// The prompt is `-∇Φ_env`, following the sign of
+    /// [`crate::attraction::attention_gradient`].
+    ///
+    /// # Arguments
+    /// * `position` - Entity position
+    /// * `attraction` - Kernel shared with the entities' field
+    /// * `gradient` - Attention gradient of the entity (same dimensionality)
+    pub fn add_gradient(&self, position: &[Real], attraction: &AttractionConfig, gradient: &mut [Real]) {
+        for (d, g) in gradient.iter_mut().enumerate() {
+            let plus = self.field(position, (d, GRADIENT_STEP), attraction);
+            let minus = self.field(position, (d, -GRADIENT_STEP), attraction);
+            *g -= (plus - minus) / (2.0 * GRADIENT_STEP);
+        }
+    }
+
+    /// Move a position out of every obstacle containing it.
+    ///
+    /// The position is placed on the obstacle's edge (along the first axis
+    /// if it sits exactly at the center) and the inward part of the
+    /// velocity is removed.
+    ///
+    /// # Returns
+    /// Whether the position was inside an obstacle
+    pub fn push_out(&self, position: &mut [Real], velocity: &mut [Real]) -> bool {
+        let mut pushed = false;
+        for point in self.points.iter().filter(|p| p.obstacle) {
+            let d = distance(position, &point.position);
+            if d >= point.radius {
+                continue;
+            }
+            pushed = true;
+            for (k, x) in position.iter_mut().enumerate() {
+                let normal = if d > 0.0 { (*x - point.position[k]) / d } else if k == 0 { 1.0 } else { 0.0 };
+                *x = point.position[k] + normal * point.radius;
+            }
+            let normal = |k: usize| (position[k] - point.position[k]) / point.radius;
+            let inward: Real = velocity.iter().enumerate().map(|(k, v)| v * normal(k)).sum();
+            if inward < 0.0 {
+                for (k, v) in velocity.iter_mut().enumerate() {
+                    *v -= inward * normal(k);
+                }
+            }
+        }
+        pushed
+    }
+}
+
+/// Euclidean distance between two positions.
+fn distance(a: &[Real], b: &[Real]) -> Real {
+    a.iter().zip(b).map(|(x, y)| (x - y).powi(2)).sum::<Real>().sqrt()
+}
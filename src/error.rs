@@ -0,0 +1,37 @@
+//! Error module: typed errors for configuration and simulation setup.
+//!
+//! Most of the crate still reports failures as `String` or boxed errors;
+//! `SimulationError` is used where callers need to tell failure kinds apart
+//! (e.g. command-line overrides that name an unknown key versus ones whose
+//! value does not parse).
+//!
+//! ## Author
+//! Ayomide I. Daniels (Morningstar)
+
+use std::fmt;
+
+/// Error raised while building or validating a simulation.
+#[derive(Clone, Debug, PartialEq)]
+pub enum SimulationError {
+    /// A parameter is unknown or its value is out of range
+    ValidationError(String),
+    /// A parameter value could not be parsed as the expected type
+    ParseError {
+        key: String,
+        value: String,
+        expected: &'static str,
+    },
+}
+
+impl fmt::Display for SimulationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SimulationError::ValidationError(msg) => write!(f, "validation error: {}", msg),
+            SimulationError::ParseError { key, value, expected } => {
+                write!(f, "cannot parse '{}' for {}: expected {}", value, key, expected)
+            }
+        }
+    }
+}
+
+impl std::error::Error for SimulationError {}
@@ -22,6 +22,7 @@
 //! ## Author
 //! Ayomide I. Daniels (Morningstar)
 
+use crate::real::Real;
 use serde::{Deserialize, Serialize};
 
 /// Configuration for Essence Index behavior.
@@ -30,11 +31,11 @@ use serde::{Deserialize, Serialize};
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct EssenceConfig {
     /// Baseline value (typically 5.0, midpoint).
-    pub baseline: f32,
+    pub baseline: Real,
     /// Decay rate toward baseline per step.
-    pub decay: f32,
+    pub decay: Real,
     /// Scaling factor for experience delta.
-    pub experience_scale: f32,
+    pub experience_scale: Real,
 }
 
 impl EssenceConfig {
@@ -54,7 +55,7 @@ impl EssenceConfig {
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct EssenceIndex {
     /// Current value (0.0 to 10.0).
-    pub value: f32,
+    pub value: Real,
     /// Configuration parameters.
     pub config: EssenceConfig,
 }
@@ -86,11 +87,11 @@ impl EssenceIndex {
     /// 
     /// # Arguments
     /// * `affective_signals` - Array of affective values from belief clusters
-    pub fn update(&mut self, affective_signals: &[f32]) {
+    pub fn update(&mut self, affective_signals: &[Real]) {
         let avg_signal = if affective_signals.is_empty() {
             0.0
         } else {
-            affective_signals.iter().sum::<f32>() / affective_signals.len() as f32
+            affective_signals.iter().sum::<Real>() / affective_signals.len() as Real
         };
 
         // Clamp signal to [-5, +5] and rescale to affect index change
@@ -107,12 +108,12 @@ impl EssenceIndex {
     }
 
     /// Compute influence factor (extremity modulates response decisiveness).
-    pub fn influence_factor(&self) -> f32 {
+    pub fn influence_factor(&self) -> Real {
         2.0 * (self.value - self.config.baseline).abs()
     }
 
     /// Get extremity (distance from baseline).
-    pub fn extremity(&self) -> f32 {
+    pub fn extremity(&self) -> Real {
         (self.value - self.config.baseline).abs()
     }
 }
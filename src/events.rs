@@ -0,0 +1,387 @@
+//! Events module: qualitative event tracing for simulation runs.
+//!
+//! The event log complements the step-by-step metrics history with discrete,
+//! qualitative events. Every simulation records these while stepping, into
+//! `SimulationResults::events`:
+//! - **ClusterBorn**: An entity forming a new belief cluster
+//! - **EssenceCrisis / EssencePeak**: Essence falling below the crisis level
+//!   or rising above the peak level
+//! - **BoundaryWrap**: An entity leaving the world and wrapping around
+//!
+//! [`EventTracker`] adds events detected by comparing consecutive states:
+//! - **ClusterDied**: Belief clusters vanishing
+//! - **EntityReset**: An entity's memory graph being wiped
+//! - **ConsciousnessThresholdCrossed**: All criteria met for the first time
+//!   after not being met
+//! - **AnomalyDetected**: Non-finite positions, velocities or essence
+//! - **ConfigPatched**: Parameters changed mid-run by `apply_config_patch`
+//!
+//! Together these allow a post-hoc reconstruction of the causal narrative of
+//! a run, e.g. which entity formed its fifth cluster right before an essence
+//! spike:
+//!
+//! ```no_run
+//! # use synthetic_consciousness::{entities::EntityId, Simulation, SimulationConfig};
+//! # let mut sim = Simulation::new(SimulationConfig::default_2d()).unwrap();
+//! sim.run();
+//! let events = &sim.results.events;
+//! let fifth = events.for_entity(EntityId(3)).filter(|e| e.event_type.name() == "ClusterBorn").nth(4);
+//! let peaks = events.of_type("EssencePeak").count();
+//! ```
+//!
+//! The log exports as a JSON array of events.
+//!
+//! [`Simulation::run_with_control`] runs with a control law that may patch
+//! the configuration after every step, for adaptive experiments.
+//!
+//! ## Author
+//! Ayomide I. Daniels (Morningstar)
+
+use crate::real::Real;
+use crate::config::SimulationConfigPatch;
+use crate::entities::{EntityId, EntityPool};
+use crate::results::{meets_consciousness_thresholds, SimulationResults};
+use crate::simulation::Simulation;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+
+/// Essence level below which an entity is considered in crisis.
+pub const ESSENCE_CRISIS_LEVEL: Real = 2.0;
+
+/// Essence level above which an entity is considered at a peak.
+pub const ESSENCE_PEAK_LEVEL: Real = 8.0;
+
+/// Kind of a discrete simulation event.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub enum EventType {
+    /// A belief cluster was formed.
+    ClusterBorn,
+    /// A belief cluster disappeared.
+    ClusterDied,
+    /// An entity's memory graph was emptied.
+    EntityReset,
+    /// Essence dropped below `ESSENCE_CRISIS_LEVEL` (carries the new value).
+    EssenceCrisis(Real),
+    /// Essence rose above `ESSENCE_PEAK_LEVEL` (carries the new value).
+    EssencePeak(Real),
+    /// An entity crossed the world's edge and wrapped (carries the axis).
+    BoundaryWrap(usize),
+    /// All consciousness criteria became satisfied.
+    ConsciousnessThresholdCrossed,
+    /// Something looks wrong (carries a short description).
+    AnomalyDetected(String),
+    /// Parameters were changed mid-run (carries the patch).
+    ConfigPatched(SimulationConfigPatch),
+}
+
+impl EventType {
+    /// Name of the kind of event, as serialized (e.g. `"ClusterBorn"`).
+    pub fn name(&self) -> &'static str {
+        match self {
+            EventType::ClusterBorn => "ClusterBorn",
+            EventType::ClusterDied => "ClusterDied",
+            EventType::EntityReset => "EntityReset",
+            EventType::EssenceCrisis(_) => "EssenceCrisis",
+            EventType::EssencePeak(_) => "EssencePeak",
+            EventType::BoundaryWrap(_) => "BoundaryWrap",
+            EventType::ConsciousnessThresholdCrossed => "ConsciousnessThresholdCrossed",
+            EventType::AnomalyDetected(_) => "AnomalyDetected",
+            EventType::ConfigPatched(_) => "ConfigPatched",
+        }
+    }
+}
+
+/// A discrete event recorded during a run.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct SimulationEvent {
+    /// Step at which the event was detected.
+    pub step: u64,
+    /// Kind of event.
+    pub event_type: EventType,
+    /// Entity concerned, if any.
+    pub entity_id: Option<EntityId>,
+    /// Human-readable details.
+    pub details: String,
+}
+
+/// Ordered list of events of a run.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct EventLog {
+    pub events: Vec<SimulationEvent>,
+}
+
+impl EventLog {
+    /// Create an empty event log.
+    pub fn new() -> Self {
+        EventLog { events: Vec::new() }
+    }
+
+    /// Append an event.
+    pub fn record(
+        &mut self,
+        step: u64,
+        event_type: EventType,
+        entity_id: Option<EntityId>,
+        details: String,
+    ) {
+        self.events.push(SimulationEvent {
+            step,
+            event_type,
+            entity_id,
+            details,
+        });
+    }
+
+    /// Whether no event was recorded.
+    pub fn is_empty(&self) -> bool {
+        self.events.is_empty()
+    }
+
+    /// Events of one kind, in order.
+    ///
+    /// # Arguments
+    /// * `name` - Kind of event (see [`EventType::name`])
+    pub fn of_type<'a>(&'a self, name: &'a str) -> impl Iterator<Item = &'a SimulationEvent> + 'a {
+        self.events.iter().filter(move |e| e.event_type.name() == name)
+    }
+
+    /// Events concerning one entity, in order.
+    pub fn for_entity(&self, id: EntityId) -> impl Iterator<Item = &SimulationEvent> + '_ {
+        self.events.iter().filter(move |e| e.entity_id == Some(id))
+    }
+
+    /// Number of events of each kind, by name.
+    pub fn counts(&self) -> BTreeMap<&'static str, usize> {
+        let mut counts = BTreeMap::new();
+        for event in &self.events {
+            *counts.entry(event.event_type.name()).or_insert(0) += 1;
+        }
+        counts
+    }
+
+    /// Serialize the events as a JSON array.
+    pub fn to_json(&self) -> Result<String, serde_json::Error> {
+        serde_json::to_string_pretty(&self.events)
+    }
+
+    /// Export the events as a JSON array file.
+    pub fn export_json(&self, path: &str) -> Result<(), Box<dyn std::error::Error>> {
+        std::fs::write(path, self.to_json()?)?;
+        Ok(())
+    }
+}
+
+/// Per-entity state compared between steps to detect events.
+struct EntitySnapshot {
+    clusters: BTreeSet<u32>,
+    memory_nodes: usize,
+}
+
+fn snapshot(entities: &EntityPool) -> HashMap<EntityId, EntitySnapshot> {
+    entities
+        .all_entities()
+        .iter()
+        .map(|e| {
+            (
+                e.id,
+                EntitySnapshot {
+                    clusters: e.memory_graph.clusters.keys().copied().collect(),
+                    memory_nodes: e.memory_graph.nodes.len(),
+                },
+            )
+        })
+        .collect()
+}
+
+/// Incremental event detector.
+///
+/// Keeps the previous step's entity snapshot so that events can be detected
+/// one step at a time, e.g. while streaming a run. The events the simulation
+/// recorded itself since the previous call are passed on as well.
+pub struct EventTracker {
+    previous: HashMap<EntityId, EntitySnapshot>,
+    conscious: bool,
+    /// Config patches already recorded.
+    patches_seen: usize,
+    /// Simulation-recorded events already passed on.
+    events_seen: usize,
+}
+
+impl EventTracker {
+    /// Start tracking from the simulation's current state.
+    pub fn new(sim: &Simulation) -> Self {
+        EventTracker {
+            previous: snapshot(&sim.entities),
+            conscious: false,
+            patches_seen: sim.config_patches.len(),
+            events_seen: sim.results.events.events.len(),
+        }
+    }
+
+    /// Compare the current state against the previous call and record events.
+    ///
+    /// # Arguments
+    /// * `sim` - Simulation after the step
+    /// * `step` - Step number to attribute the events to
+    /// * `log` - Log receiving the events
+    ///
+    /// # Returns
+    /// Number of events recorded
+    pub fn observe(&mut self, sim: &Simulation, step: u64, log: &mut EventLog) -> usize {
+        let recorded_before = log.events.len();
+        let recorded = &sim.results.events.events;
+        log.events.extend_from_slice(recorded.get(self.events_seen..).unwrap_or_default());
+        self.events_seen = recorded.len();
+        let current = snapshot(&sim.entities);
+
+        let mut ids: Vec<EntityId> = current.keys().copied().collect();
+        ids.sort_by_key(|id| id.0);
+        for id in ids {
+            let now = &current[&id];
+            let before = self.previous.get(&id);
+
+            let old_clusters = before.map(|b| b.clusters.clone()).unwrap_or_default();
+            for cluster in old_clusters.difference(&now.clusters) {
+                log.record(
+                    step,
+                    EventType::ClusterDied,
+                    Some(id),
+                    format!("cluster {} vanished", cluster),
+                );
+            }
+
+            if let Some(before) = before {
+                if before.memory_nodes > 0 && now.memory_nodes == 0 {
+                    log.record(
+                        step,
+                        EventType::EntityReset,
+                        Some(id),
+                        format!("memory wiped ({} nodes lost)", before.memory_nodes),
+                    );
+                }
+            }
+
+            if let Some(entity) = sim.entities.get_entity(id) {
+                let finite = entity
+                    .pose
+                    .position
+                    .iter()
+                    .chain(entity.velocity.iter())
+                    .all(|v| v.is_finite())
+                    && entity.essence.value.is_finite();
+                if !finite {
+                    log.record(
+                        step,
+                        EventType::AnomalyDetected("non-finite state".to_string()),
+                        Some(id),
+                        "position, velocity or essence is NaN or infinite".to_string(),
+                    );
+                }
+            }
+        }
+
+        if let Some(metrics) = sim.metrics_history.last() {
+            let now_conscious = meets_consciousness_thresholds(metrics);
+            if now_conscious && !self.conscious {
+                log.record(
+                    step,
+                    EventType::ConsciousnessThresholdCrossed,
+                    None,
+                    "all consciousness criteria met".to_string(),
+                );
+            }
+            self.conscious = now_conscious;
+        }
+
+        for (patch_step, patch) in &sim.config_patches[self.patches_seen..] {
+            log.record(
+                *patch_step,
+                EventType::ConfigPatched(patch.clone()),
+                None,
+                format!("config patched: {}", patch.describe()),
+            );
+        }
+        self.patches_seen = sim.config_patches.len();
+
+        self.previous = current;
+        log.events.len() - recorded_before
+    }
+}
+
+impl Simulation {
+    /// Run the configured number of steps while tracing discrete events.
+    ///
+    /// After each step the entity states are compared against the previous
+    /// step to detect cluster deaths, memory resets, anomalies and crossings
+    /// of the consciousness threshold; the events the simulation records
+    /// itself are included in order. Results are finalized at the end.
+    ///
+    /// # Returns
+    /// (finalized results, event log)
+    pub fn run_with_event_log(&mut self) -> (SimulationResults, EventLog) {
+        let mut log = EventLog::new();
+        let mut tracker = EventTracker::new(self);
+
+        for _ in 0..self.config.simulation.num_steps {
+            let step = self.timestamp;
+            self.step();
+            tracker.observe(self, step, &mut log);
+        }
+
+        self.finalize_results();
+        (self.results.clone(), log)
+    }
+
+    /// Run the configured number of steps under an adaptive control law.
+    /// 
+    /// After each step, `control` sees the simulation (e.g. the latest
+    /// metrics) and may return a patch, which is applied before the next
+    /// step and recorded as a `ConfigPatched` event. Invalid patches are
+    /// recorded as `AnomalyDetected` and ignored. Results are finalized at
+    /// the end.
+    /// 
+    /// ```no_run
+    /// # use synthetic_consciousness::{Simulation, SimulationConfig, SimulationConfigPatch};
+    /// # let mut sim = Simulation::new(SimulationConfig::default_2d()).unwrap();
+    /// // Widen the kernel whenever attention collapses onto few neighbors
+    /// let (results, log) = sim.run_with_control(|sim| {
+    ///     let entropy = sim.metrics_history.last()?.attention_entropy;
+    ///     (entropy < 0.5).then(|| SimulationConfigPatch {
+    ///         sigma: Some(sim.config.attraction.sigma * 1.1),
+    ///         ..Default::default()
+    ///     })
+    /// });
+    /// ```
+    /// 
+    /// # Returns
+    /// (finalized results, event log)
+    pub fn run_with_control<F>(&mut self, mut control: F) -> (SimulationResults, EventLog)
+    where
+        F: FnMut(&Simulation) -> Option<SimulationConfigPatch>,
+    {
+        let mut log = EventLog::new();
+        let mut tracker = EventTracker::new(self);
+
+        for _ in 0..self.config.simulation.num_steps {
+            if self.stop_requested() {
+                break;
+            }
+            let step = self.timestamp;
+            self.step();
+            if let Some(patch) = control(self) {
+                if let Err(e) = self.apply_config_patch(patch) {
+                    log.record(
+                        self.timestamp,
+                        EventType::AnomalyDetected("rejected config patch".to_string()),
+                        None,
+                        e.to_string(),
+                    );
+                }
+            }
+            tracker.observe(self, step, &mut log);
+        }
+
+        self.finalize_results();
+        (self.results.clone(), log)
+    }
+}
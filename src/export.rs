@@ -0,0 +1,113 @@
+//! Export module: selectable output formats for finished runs.
+//!
+//! [`Simulation::export_reports`](crate::Simulation::export_reports) writes
+//! any subset of:
+//!
+//! | Format    | File                                    |
+//! |-----------|-----------------------------------------|
+//! | `csv`     | `metrics_<fingerprint>.csv`             |
+//! | `txt`     | `<prefix>_report_<fingerprint>.txt`     |
+//! | `html`    | `<prefix>_report_<fingerprint>.html`    |
+//! | `json`    | `<prefix>_results_<fingerprint>.json`   |
+//! | `md`      | `<prefix>_report_<fingerprint>.md`      |
+//! | `parquet` | `<prefix>_<fingerprint>_<table>.parquet` (`parquet` feature) |
+//!
+//! The binary selects them with `--export=csv,json` (`none` writes
+//! nothing); the default is the former fixed trio csv, txt, html.
+//!
+//! ## Author
+//! Ayomide I. Daniels (Morningstar)
+
+use std::fmt;
+use std::str::FromStr;
+
+/// One output format of a finished run.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ExportFormat {
+    /// Per-step metrics table.
+    Csv,
+    /// Plain-text report.
+    Txt,
+    /// Styled HTML report.
+    Html,
+    /// Full serialized results.
+    Json,
+    /// Markdown report.
+    Md,
+    /// Metrics, trajectory and attraction tables (`parquet` feature).
+    #[cfg(feature = "parquet")]
+    Parquet,
+}
+
+impl ExportFormat {
+    /// Formats written when none are selected.
+    pub const DEFAULT: [ExportFormat; 3] = [ExportFormat::Csv, ExportFormat::Txt, ExportFormat::Html];
+
+    /// Every format available in this build.
+    pub const ALL: &'static [ExportFormat] = &[
+        ExportFormat::Csv,
+        ExportFormat::Txt,
+        ExportFormat::Html,
+        ExportFormat::Json,
+        ExportFormat::Md,
+        #[cfg(feature = "parquet")]
+        ExportFormat::Parquet,
+    ];
+
+    /// Lowercase name, as accepted by `--export`.
+    pub fn name(self) -> &'static str {
+        match self {
+            ExportFormat::Csv => "csv",
+            ExportFormat::Txt => "txt",
+            ExportFormat::Html => "html",
+            ExportFormat::Json => "json",
+            ExportFormat::Md => "md",
+            #[cfg(feature = "parquet")]
+            ExportFormat::Parquet => "parquet",
+        }
+    }
+
+    /// Parse a comma-separated list such as `"csv,json"`.
+    /// 
+    /// `none` selects no format; duplicates are dropped, keeping the first.
+    /// 
+    /// # Arguments
+    /// * `list` - Format names separated by commas
+    /// 
+    /// # Returns
+    /// The formats in the given order, or an error naming the valid options
+    pub fn parse_list(list: &str) -> Result<Vec<ExportFormat>, String> {
+        let mut formats = Vec::new();
+        for name in list.split(',').map(str::trim) {
+            if name == "none" {
+                continue;
+            }
+            let format = name.parse()?;
+            if !formats.contains(&format) {
+                formats.push(format);
+            }
+        }
+        Ok(formats)
+    }
+}
+
+impl fmt::Display for ExportFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl FromStr for ExportFormat {
+    type Err = String;
+
+    fn from_str(name: &str) -> Result<Self, Self::Err> {
+        ExportFormat::ALL
+            .iter()
+            .copied()
+            .find(|format| format.name() == name.to_ascii_lowercase())
+            .ok_or_else(|| {
+                let valid: Vec<&str> = ExportFormat::ALL.iter().map(|format| format.name()).collect();
+                format!("Unknown export format '{}' (valid: {}, none)", name, valid.join(", "))
+            })
+    }
+}
@@ -8,6 +8,13 @@
 //!
 //! - **Pose**: Combines position vector and quaternion orientation
 //! - **GeometryConfig**: Defines dimensionality, bounds, and topology
+//! - **Coords**: Inline 2D/3D vector of positions and velocities, backed by
+//!   a fixed-size nalgebra vector so their arithmetic compiles to
+//!   straight-line (vectorizable) code without heap traffic
+//! - **Heading helpers**: Forward vectors, heading quaternions and
+//!   turn-rate-limited rotation toward a target direction
+//! - **SpatialGrid**: Cell hashing for the pairs of entities within a
+//!   cutoff radius
 //!
 //! ## Architectural Role
 //!
@@ -18,7 +25,12 @@
 //! ## Author
 //! Ayomide I. Daniels (Morningstar)
 
-use serde::{Deserialize, Serialize};
+use crate::real::Real;
+use nalgebra::SVector;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::collections::HashMap;
+use std::fmt;
+use std::ops::{Deref, DerefMut};
 
 /// Represents the pose (position and orientation) of an entity.
 /// 
@@ -27,9 +39,9 @@ use serde::{Deserialize, Serialize};
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct Pose {
     /// Position in d-dimensional space (d=2 or d=3)
-    pub position: Vec<f32>,
+    pub position: Coords,
     /// Orientation as quaternion [w, x, y, z]
-    pub orientation: [f32; 4],
+    pub orientation: [Real; 4],
 }
 
 impl Pose {
@@ -42,19 +54,19 @@ impl Pose {
     /// Pose at origin with identity orientation
     pub fn new(dimension: usize) -> Self {
         Pose {
-            position: vec![0.0; dimension],
+            position: Coords::zeros(dimension),
             orientation: [1.0, 0.0, 0.0, 0.0],
         }
     }
 
-    /// Set position with a vector.
-    pub fn with_position(mut self, pos: Vec<f32>) -> Self {
-        self.position = pos;
+    /// Set position with a vector (a `Vec<Real>` or [`Coords`]).
+    pub fn with_position(mut self, pos: impl Into<Coords>) -> Self {
+        self.position = pos.into();
         self
     }
 
     /// Set orientation with a quaternion.
-    pub fn with_orientation(mut self, quat: [f32; 4]) -> Self {
+    pub fn with_orientation(mut self, quat: [Real; 4]) -> Self {
         self.orientation = quat;
         self
     }
@@ -66,13 +78,204 @@ impl Pose {
     /// 
     /// # Returns
     /// Euclidean distance in space
-    pub fn distance_to(&self, other: &Pose) -> f32 {
-        self.position
-            .iter()
-            .zip(other.position.iter())
-            .map(|(a, b)| (a - b).powi(2))
-            .sum::<f32>()
-            .sqrt()
+    pub fn distance_to(&self, other: &Pose) -> Real {
+        self.position.distance(&other.position)
+    }
+}
+
+/// Forward (heading) vector of an orientation quaternion.
+/// 
+/// Rotates the body x-axis by `orientation`; the quaternion is normalized
+/// first. In 2D the result is projected onto the plane.
+/// 
+/// # Arguments
+/// * `orientation` - Quaternion [w, x, y, z]
+/// * `dimension` - Spatial dimensionality (2 or 3)
+/// 
+/// # Returns
+/// Unit forward vector with `dimension` components
+pub fn forward_vector(orientation: &[Real; 4], dimension: usize) -> Vec<Real> {
+    let mut forward = vec![0.0; dimension.min(3)];
+    if forward_into(orientation, &mut forward) {
+        forward
+    } else {
+        unit_x(dimension)
+    }
+}
+
+/// Write the unit forward vector of `orientation` into `out` (at most
+/// three components).
+/// 
+/// # Returns
+/// False (leaving `out` unspecified) if the orientation or its projection
+/// is degenerate
+fn forward_into(orientation: &[Real; 4], out: &mut [Real]) -> bool {
+    let norm = orientation.iter().map(|c| c * c).sum::<Real>().sqrt();
+    if norm < 1e-9 {
+        return false;
+    }
+    let [w, x, y, z] = orientation.map(|c| c / norm);
+    let forward = [
+        1.0 - 2.0 * (y * y + z * z),
+        2.0 * (x * y + w * z),
+        2.0 * (x * z - w * y),
+    ];
+    out.copy_from_slice(&forward[..out.len()]);
+    normalize_in_place(out)
+}
+
+/// Quaternion facing along `direction` (yaw in 2D, yaw + pitch in 3D).
+/// 
+/// Roll is always zero. Returns the identity for a zero direction.
+/// 
+/// # Arguments
+/// * `direction` - Heading vector (need not be normalized)
+/// 
+/// # Returns
+/// Unit quaternion [w, x, y, z]
+pub fn heading_quaternion(direction: &[Real]) -> [Real; 4] {
+    let dx = direction.first().copied().unwrap_or(0.0);
+    let dy = direction.get(1).copied().unwrap_or(0.0);
+    let dz = direction.get(2).copied().unwrap_or(0.0);
+    if dx == 0.0 && dy == 0.0 && dz == 0.0 {
+        return [1.0, 0.0, 0.0, 0.0];
+    }
+    let yaw = dy.atan2(dx);
+    let pitch = dz.atan2((dx * dx + dy * dy).sqrt());
+
+    // Yaw about z composed with pitch about -y (positive pitch looks up)
+    let (sz, cz) = (yaw / 2.0).sin_cos();
+    let (sy, cy) = (pitch / 2.0).sin_cos();
+    [cz * cy, sz * sy, -cz * sy, sz * cy]
+}
+
+/// Rotate a heading toward a target direction by at most `max_angle`.
+/// 
+/// # Arguments
+/// * `current` - Current heading
+/// * `target` - Desired heading (only the components shared with
+///   `current` are used)
+/// * `max_angle` - Largest allowed rotation in radians
+/// 
+/// # Returns
+/// Unit vector between `current` and `target`, or `target` itself if it
+/// is within `max_angle`
+pub fn turn_towards(current: &[Real], target: &[Real], max_angle: Real) -> Vec<Real> {
+    let n = current.len().min(target.len());
+    let mut unit = vec![0.0; n];
+    let mut heading = vec![0.0; n];
+    turn_towards_into(&current[..n], &target[..n], max_angle, &mut unit, &mut heading);
+    heading
+}
+
+/// Orientation after turning `orientation` toward `direction` by at most
+/// `max_angle`.
+/// 
+/// Same as `heading_quaternion(&turn_towards(&forward_vector(..), direction, max_angle))`,
+/// but allocation-free in two and three dimensions (it runs for every
+/// entity each step).
+/// 
+/// # Arguments
+/// * `orientation` - Current orientation quaternion [w, x, y, z]
+/// * `direction` - Desired heading (e.g. the velocity)
+/// * `max_angle` - Largest allowed rotation in radians
+pub fn turn_orientation(orientation: &[Real; 4], direction: &[Real], max_angle: Real) -> [Real; 4] {
+    let dimension = direction.len();
+    if dimension > 3 {
+        let current = forward_vector(orientation, dimension);
+        return heading_quaternion(&turn_towards(&current, direction, max_angle));
+    }
+
+    let mut current = [0.0; 3];
+    let mut unit = [0.0; 3];
+    let mut heading = [0.0; 3];
+    let current = &mut current[..dimension];
+    if !forward_into(orientation, current) {
+        unit_x_into(current);
+    }
+    turn_towards_into(current, direction, max_angle, &mut unit[..dimension], &mut heading[..dimension]);
+    heading_quaternion(&heading[..dimension])
+}
+
+/// Core of [`turn_towards`] for equal-length vectors.
+/// 
+/// `unit` is scratch space and `out` receives the new heading; both have
+/// the length of `current`.
+fn turn_towards_into(current: &[Real], target: &[Real], max_angle: Real, unit: &mut [Real], out: &mut [Real]) {
+    out.copy_from_slice(target);
+    if !normalize_in_place(out) {
+        out.copy_from_slice(current);
+        return;
+    }
+    unit.copy_from_slice(current);
+    if !normalize_in_place(unit) {
+        return;
+    }
+    let cos = unit.iter().zip(out.iter()).map(|(a, b)| a * b).sum::<Real>().clamp(-1.0, 1.0);
+    if cos.acos() <= max_angle {
+        return;
+    }
+
+    // Unit vector perpendicular to `current` in the turning plane
+    for (t, c) in out.iter_mut().zip(unit.iter()) {
+        *t -= cos * c;
+    }
+    if !normalize_in_place(out) {
+        // Reversal: any perpendicular direction will do
+        out.fill(0.0);
+        match unit.len() {
+            2 => {
+                out[0] = -unit[1];
+                out[1] = unit[0];
+            }
+            n if n >= 3 => {
+                let k = if unit[2].abs() < 0.9 { 2 } else { 0 };
+                out[k] = 1.0;
+                let along = unit[k];
+                for (a, c) in out.iter_mut().zip(unit.iter()) {
+                    *a -= along * c;
+                }
+            }
+            _ => {}
+        }
+        if !normalize_in_place(out) {
+            unit_x_into(out);
+        }
+    }
+
+    let (sin, cos) = max_angle.sin_cos();
+    for (p, c) in out.iter_mut().zip(unit.iter()) {
+        *p = cos * c + sin * *p;
+    }
+}
+
+/// Normalize a vector in place.
+/// 
+/// # Returns
+/// False (leaving `v` unchanged) if it is (nearly) zero
+fn normalize_in_place(v: &mut [Real]) -> bool {
+    let norm = v.iter().map(|x| x * x).sum::<Real>().sqrt();
+    if norm < 1e-9 {
+        return false;
+    }
+    for x in v.iter_mut() {
+        *x /= norm;
+    }
+    true
+}
+
+/// Unit vector along the first axis.
+fn unit_x(dimension: usize) -> Vec<Real> {
+    let mut v = vec![0.0; dimension];
+    unit_x_into(&mut v);
+    v
+}
+
+/// Overwrite `v` with the unit vector along the first axis.
+fn unit_x_into(v: &mut [Real]) {
+    v.fill(0.0);
+    if let Some(first) = v.first_mut() {
+        *first = 1.0;
     }
 }
 
@@ -82,7 +285,7 @@ pub struct GeometryConfig {
     /// Dimension of the world (2 for 2D plane, 3 for 3D space).
     pub dimension: usize,
     /// Bounds of the world (one value per dimension).
-    pub bounds: Vec<f32>,
+    pub bounds: Vec<Real>,
     /// Optional periodic boundary conditions (wrapping).
     pub periodic: bool,
 }
@@ -112,8 +315,216 @@ impl GeometryConfig {
     }
 }
 
+/// Largest supported spatial dimensionality.
+pub const MAX_DIMENSION: usize = 3;
+
+/// Fixed-size vector backing [`Coords`]; components past the dimension are zero.
+pub type Vector = SVector<Real, MAX_DIMENSION>;
+
+/// Position or velocity with up to `MAX_DIMENSION` components, stored inline.
+///
+/// Dereferences to the slice of its components and serializes as a plain
+/// list, so it reads and writes like a `Vec<Real>`, but copying one never
+/// touches the heap. The arithmetic runs on the backing [`Vector`] (see
+/// [`Coords::as_vector`]); its unused components stay zero, which leaves
+/// every sum, norm and product bitwise equal to a loop over the slice.
+#[derive(Clone, Copy, Default, PartialEq)]
+pub struct Coords {
+    vector: Vector,
+    len: u8,
+}
+
+impl Coords {
+    /// The origin of a `dimension`-dimensional space.
+    ///
+    /// # Panics
+    /// If `dimension` exceeds `MAX_DIMENSION`
+    pub fn zeros(dimension: usize) -> Self {
+        assert!(dimension <= MAX_DIMENSION, "dimension {} exceeds the maximum {}", dimension, MAX_DIMENSION);
+        Coords { vector: Vector::zeros(), len: dimension as u8 }
+    }
+
+    /// Copy the components of `values`.
+    ///
+    /// # Panics
+    /// If `values` has more than `MAX_DIMENSION` components
+    pub fn from_slice(values: &[Real]) -> Self {
+        let mut coords = Coords::zeros(values.len());
+        coords.copy_from_slice(values);
+        coords
+    }
+
+    /// The components as a slice.
+    pub fn as_slice(&self) -> &[Real] {
+        self
+    }
+
+    /// The backing vector, zero past the dimension.
+    pub fn as_vector(&self) -> &Vector {
+        &self.vector
+    }
+
+    /// Euclidean norm.
+    pub fn norm(&self) -> Real {
+        self.vector.norm()
+    }
+
+    /// Squared Euclidean norm.
+    pub fn norm_squared(&self) -> Real {
+        self.vector.norm_squared()
+    }
+
+    /// Dot product with `other`.
+    pub fn dot(&self, other: &Coords) -> Real {
+        self.vector.dot(&other.vector)
+    }
+
+    /// Euclidean distance to `other`.
+    pub fn distance(&self, other: &Coords) -> Real {
+        (self.vector - other.vector).norm()
+    }
+
+    /// Add `factor * other` in place.
+    pub fn add_scaled(&mut self, factor: Real, other: &Coords) {
+        self.vector.axpy(factor, &other.vector, 1.0);
+    }
+
+    /// Multiply every component by `factor`.
+    pub fn scale(&mut self, factor: Real) {
+        self.vector *= factor;
+    }
+}
+
+impl Deref for Coords {
+    type Target = [Real];
+
+    fn deref(&self) -> &[Real] {
+        &self.vector.as_slice()[..self.len as usize]
+    }
+}
+
+impl DerefMut for Coords {
+    fn deref_mut(&mut self) -> &mut [Real] {
+        &mut self.vector.as_mut_slice()[..self.len as usize]
+    }
+}
+
+impl<'a> IntoIterator for &'a Coords {
+    type Item = &'a Real;
+    type IntoIter = std::slice::Iter<'a, Real>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<'a> IntoIterator for &'a mut Coords {
+    type Item = &'a mut Real;
+    type IntoIter = std::slice::IterMut<'a, Real>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_mut()
+    }
+}
+
+impl From<&[Real]> for Coords {
+    fn from(values: &[Real]) -> Self {
+        Coords::from_slice(values)
+    }
+}
+
+impl From<Vec<Real>> for Coords {
+    fn from(values: Vec<Real>) -> Self {
+        Coords::from_slice(&values)
+    }
+}
+
+impl PartialEq<Vec<Real>> for Coords {
+    fn eq(&self, other: &Vec<Real>) -> bool {
+        **self == **other
+    }
+}
+
+impl fmt::Debug for Coords {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+impl Serialize for Coords {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(self.iter())
+    }
+}
+
+impl<'de> Deserialize<'de> for Coords {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let values = Vec::<Real>::deserialize(deserializer)?;
+        if values.len() > MAX_DIMENSION {
+            return Err(serde::de::Error::invalid_length(values.len(), &"at most 3 components"));
+        }
+        Ok(Coords::from_slice(&values))
+    }
+}
+
+/// Distance between two positions respecting the world topology.
+/// 
+/// With periodic boundaries the minimum-image convention is used: along
+/// each axis the separation is wrapped so it never exceeds half the bound.
+/// 
+/// # Arguments
+/// * `a` - First position
+/// * `b` - Second position
+/// * `config` - Geometry configuration (bounds and periodicity)
+/// 
+/// # Returns
+/// Euclidean distance under the configured topology
+pub fn periodic_distance(a: &[Real], b: &[Real], config: &GeometryConfig) -> Real {
+    a.iter()
+        .zip(b.iter())
+        .enumerate()
+        .map(|(d, (x, y))| {
+            let mut delta = (x - y).abs();
+            if config.periodic {
+                if let Some(&bound) = config.bounds.get(d) {
+                    if bound > 0.0 {
+                        delta %= bound;
+                        delta = delta.min(bound - delta);
+                    }
+                }
+            }
+            delta * delta
+        })
+        .sum::<Real>()
+        .sqrt()
+}
+
+/// Displacement vector from `from` to `to` under the configured topology.
+/// 
+/// With periodic boundaries each component is the minimum-image offset.
+/// 
+/// # Returns
+/// `to - from`, wrapped into [-bound/2, bound/2] per axis when periodic
+pub fn periodic_displacement(from: &[Real], to: &[Real], config: &GeometryConfig) -> Vec<Real> {
+    from.iter()
+        .zip(to.iter())
+        .enumerate()
+        .map(|(d, (a, b))| {
+            let mut delta = b - a;
+            if config.periodic {
+                if let Some(&bound) = config.bounds.get(d) {
+                    if bound > 0.0 {
+                        delta -= bound * (delta / bound).round();
+                    }
+                }
+            }
+            delta
+        })
+        .collect()
+}
+
 /// Apply periodic boundary conditions if enabled.
-pub fn apply_periodic_bounds(position: &mut [f32], bounds: &[f32], periodic: bool) {
+pub fn apply_periodic_bounds(position: &mut [Real], bounds: &[Real], periodic: bool) {
     if !periodic {
         return;
     }
@@ -126,3 +537,195 @@ pub fn apply_periodic_bounds(position: &mut [f32], bounds: &[f32], periodic: boo
         }
     }
 }
+
+/// Uniform grid hashing points into cubic cells, for finding the pairs of
+/// points within a radius without testing every pair.
+/// 
+/// Pair distances are plain Euclidean (no periodic wrap), as for
+/// [`Pose::distance_to`]; searches around a single point
+/// ([`SpatialGrid::for_each_candidate`]) also cover periodic images. With
+/// cells at least as wide as the radius, only the 3^d cells around a point
+/// can hold its neighbors, so a query costs O(n · neighbors) instead of
+/// O(n²).
+#[derive(Clone, Debug, Default)]
+pub struct SpatialGrid {
+    cell_size: Real,
+    /// Point indices per occupied cell (2D cells have a zero third coordinate)
+    cells: HashMap<[i64; 3], Vec<usize>>,
+    /// Cell of each point, by index
+    point_cells: Vec<[i64; 3]>,
+}
+
+impl SpatialGrid {
+    /// Empty grid with cells `cell_size` wide.
+    /// 
+    /// # Arguments
+    /// * `cell_size` - Cell edge length; queries are cheapest with cells as
+    ///   wide as their radius
+    pub fn new(cell_size: Real) -> Self {
+        SpatialGrid {
+            cell_size,
+            cells: HashMap::new(),
+            point_cells: Vec::new(),
+        }
+    }
+
+    /// Cell edge length.
+    pub fn cell_size(&self) -> Real {
+        self.cell_size
+    }
+
+    /// Number of points in the grid.
+    pub fn len(&self) -> usize {
+        self.point_cells.len()
+    }
+
+    /// Whether the grid holds no points.
+    pub fn is_empty(&self) -> bool {
+        self.point_cells.is_empty()
+    }
+
+    /// Replace the contents with `positions`, indexed in iteration order.
+    /// 
+    /// Cell vectors are kept between rebuilds, so rebuilding every step
+    /// allocates little once the population is stable.
+    pub fn rebuild<'a>(&mut self, positions: impl IntoIterator<Item = &'a [Real]>) {
+        for members in self.cells.values_mut() {
+            members.clear();
+        }
+        self.point_cells.clear();
+        for (index, position) in positions.into_iter().enumerate() {
+            let cell = self.cell_of(position);
+            self.cells.entry(cell).or_default().push(index);
+            self.point_cells.push(cell);
+        }
+        self.cells.retain(|_, members| !members.is_empty());
+    }
+
+    /// Pairs of points at most `radius` apart.
+    /// 
+    /// # Arguments
+    /// * `positions` - The positions the grid was last rebuilt from
+    /// * `radius` - Largest distance between the points of a pair
+    /// 
+    /// # Returns
+    /// Index pairs `(i, j)` with `i < j`, sorted, so results come in the
+    /// same order as a nested loop over all pairs would give them
+    pub fn pairs_within(&self, positions: &[&[Real]], radius: Real) -> Vec<(usize, usize)> {
+        let radius_sq = radius * radius;
+        let reach = if self.cell_size > 0.0 {
+            (radius / self.cell_size).ceil().max(1.0) as i64
+        } else {
+            0
+        };
+
+        let mut pairs = Vec::new();
+        for (i, (cell, position)) in self.point_cells.iter().zip(positions).enumerate() {
+            let z_reach = if position.len() < 3 { 0 } else { reach };
+            for dx in -reach..=reach {
+                for dy in -reach..=reach {
+                    for dz in -z_reach..=z_reach {
+                        let neighbor = [cell[0] + dx, cell[1] + dy, cell[2] + dz];
+                        let Some(members) = self.cells.get(&neighbor) else {
+                            continue;
+                        };
+                        for &j in members {
+                            if j <= i {
+                                continue;
+                            }
+                            let distance_sq: Real = position
+                                .iter()
+                                .zip(positions[j].iter())
+                                .map(|(a, b)| (a - b).powi(2))
+                                .sum();
+                            if distance_sq <= radius_sq {
+                                pairs.push((i, j));
+                            }
+                        }
+                    }
+                }
+            }
+        }
+        pairs.sort_unstable();
+        pairs
+    }
+
+    /// Visit every point in the cells that can hold points within `radius`
+    /// of `point`.
+    /// 
+    /// The visited points are a superset of those within `radius`; callers
+    /// filter them by distance. With periodic geometry the wrapped images of
+    /// `point` are searched as well, so a point may be visited more than
+    /// once. When the search would span more cells than are occupied, every
+    /// point is visited instead.
+    /// 
+    /// # Arguments
+    /// * `point` - Query position
+    /// * `radius` - Search radius
+    /// * `geometry` - Bounds and periodicity of the world
+    /// * `visit` - Called with the index of each candidate point
+    pub fn for_each_candidate(
+        &self,
+        point: &[Real],
+        radius: Real,
+        geometry: &GeometryConfig,
+        mut visit: impl FnMut(usize),
+    ) {
+        let dimension = point.len().min(3);
+        let reach = if self.cell_size > 0.0 {
+            (radius / self.cell_size).ceil().max(1.0) as i64
+        } else {
+            0
+        };
+        let span = (2 * reach + 1).pow(dimension as u32);
+        if span > self.cells.len() as i64 {
+            for members in self.cells.values() {
+                members.iter().for_each(|&i| visit(i));
+            }
+            return;
+        }
+
+        // The point and, with periodic bounds, its images shifted by a
+        // bound along each axis: at most 3^3, kept on the stack
+        let mut images = [Coords::default(); 27];
+        images[0] = Coords::from_slice(&point[..dimension]);
+        let mut count = 1;
+        if geometry.periodic {
+            for (axis, bound) in geometry.bounds.iter().enumerate().take(dimension) {
+                for k in 0..count {
+                    for (slot, shift) in [-bound, *bound].into_iter().enumerate() {
+                        let mut image = images[k];
+                        image[axis] += shift;
+                        images[count + 2 * k + slot] = image;
+                    }
+                }
+                count *= 3;
+            }
+        }
+
+        let z_reach = if dimension < 3 { 0 } else { reach };
+        for image in &images[..count] {
+            let cell = self.cell_of(image);
+            for dx in -reach..=reach {
+                for dy in -reach..=reach {
+                    for dz in -z_reach..=z_reach {
+                        if let Some(members) = self.cells.get(&[cell[0] + dx, cell[1] + dy, cell[2] + dz]) {
+                            members.iter().for_each(|&i| visit(i));
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    /// Cell holding a position.
+    fn cell_of(&self, position: &[Real]) -> [i64; 3] {
+        let mut cell = [0; 3];
+        if self.cell_size > 0.0 {
+            for (axis, coordinate) in position.iter().take(3).enumerate() {
+                cell[axis] = (coordinate / self.cell_size).floor() as i64;
+            }
+        }
+        cell
+    }
+}
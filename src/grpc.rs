@@ -0,0 +1,353 @@
+//! gRPC module: remote stepping and state queries over gRPC.
+//!
+//! Enabled by the `grpc` feature. The service is defined in
+//! `proto/simulation.proto` and compiled by `build.rs` with tonic/prost.
+//! A [`GrpcService`] keeps a mutex-protected registry of simulations keyed
+//! by UUID; every RPC except `CreateSimulation` names its simulation by id.
+//!
+//! ## RPCs
+//!
+//! | RPC | Effect |
+//! |-----|--------|
+//! | `CreateSimulation` | Build a simulation from JSON/TOML config text (empty = default 2D) |
+//! | `Step` | Advance `n` steps; returns the last step's metrics |
+//! | `StreamSteps` | Advance `n` steps, streaming the metrics of each step |
+//! | `GetMetrics` | Metrics of the most recent step |
+//! | `GetEntities` | Position, velocity, essence, energy, role and labels per entity |
+//! | `GetSummary` | Finalize results so far and return the consciousness verdict |
+//! | `InjectStimulus` | Deliver an external stimulus to one entity |
+//! | `Shutdown` | Drop a simulation, or stop the server when the id is empty |
+//!
+//! Errors map to gRPC codes: `NOT_FOUND` (unknown simulation or entity),
+//! `INVALID_ARGUMENT` (bad config or stimulus), `RESOURCE_EXHAUSTED`
+//! (registry full). Stepping runs on blocking threads; calls on the same
+//! simulation are serialized by its mutex. `StreamSteps` advances in
+//! lockstep with the client: it waits while the client's buffer is full
+//! and stops early if the client disconnects.
+//!
+//! ## Author
+//! Ayomide I. Daniels (Morningstar)
+
+// `tonic::Status` is the error type tonic handlers must return.
+#![allow(clippy::result_large_err)]
+
+use crate::config::SimulationConfig;
+use crate::entities::EntityId;
+use crate::metrics::Metrics;
+use crate::simulation::Simulation;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex, MutexGuard};
+use tokio::net::TcpListener;
+use tokio::sync::{mpsc, Notify};
+use tokio_stream::wrappers::{ReceiverStream, TcpListenerStream};
+use tonic::{Request, Response, Status};
+use uuid::Uuid;
+
+/// Code generated from `proto/simulation.proto`.
+#[allow(clippy::all)]
+pub mod proto {
+    tonic::include_proto!("synthetic_consciousness.v1");
+}
+
+use proto::simulation_service_server::{SimulationService, SimulationServiceServer};
+
+/// Default limit on registered simulations.
+pub const DEFAULT_MAX_SIMULATIONS: usize = 16;
+
+/// Step responses buffered per `StreamSteps` client.
+const STREAM_BUFFER: usize = 16;
+
+/// A registered simulation.
+type SharedSimulation = Arc<Mutex<Simulation>>;
+
+/// gRPC service managing a registry of simulations.
+pub struct GrpcService {
+    simulations: Mutex<HashMap<Uuid, SharedSimulation>>,
+    max_simulations: usize,
+    shutdown: Arc<Notify>,
+}
+
+impl GrpcService {
+    /// Create an empty registry.
+    ///
+    /// # Arguments
+    /// * `max_simulations` - Simulations that may be registered at once
+    pub fn new(max_simulations: usize) -> Self {
+        GrpcService {
+            simulations: Mutex::new(HashMap::new()),
+            max_simulations: max_simulations.max(1),
+            shutdown: Arc::new(Notify::new()),
+        }
+    }
+
+    /// Signal notified when a client calls `Shutdown` with an empty id.
+    pub fn shutdown_signal(&self) -> Arc<Notify> {
+        Arc::clone(&self.shutdown)
+    }
+
+    /// Look up a simulation by id.
+    fn simulation(&self, id: &str) -> Result<SharedSimulation, Status> {
+        let uuid = Uuid::parse_str(id).map_err(|_| Status::not_found("unknown simulation id"))?;
+        let simulations = self
+            .simulations
+            .lock()
+            .map_err(|_| Status::internal("simulation registry unavailable"))?;
+        simulations
+            .get(&uuid)
+            .cloned()
+            .ok_or_else(|| Status::not_found("unknown simulation id"))
+    }
+}
+
+/// Serve the gRPC API on `listener` until a client calls `Shutdown` with an empty id.
+///
+/// # Arguments
+/// * `listener` - Bound listener (port 0 picks a free port)
+/// * `max_simulations` - Simulations that may be registered at once
+pub async fn serve(listener: TcpListener, max_simulations: usize) -> Result<(), tonic::transport::Error> {
+    let service = GrpcService::new(max_simulations);
+    let shutdown = service.shutdown_signal();
+    tonic::transport::Server::builder()
+        .add_service(SimulationServiceServer::new(service))
+        .serve_with_incoming_shutdown(TcpListenerStream::new(listener), async move {
+            shutdown.notified().await
+        })
+        .await
+}
+
+/// Lock a simulation, mapping a poisoned mutex to `INTERNAL`.
+fn lock(sim: &SharedSimulation) -> Result<MutexGuard<'_, Simulation>, Status> {
+    sim.lock().map_err(|_| Status::internal("simulation state poisoned by an earlier panic"))
+}
+
+/// Run `f` on a blocking thread with the simulation locked.
+async fn with_simulation<T, F>(sim: SharedSimulation, f: F) -> Result<T, Status>
+where
+    T: Send + 'static,
+    F: FnOnce(&mut Simulation) -> Result<T, Status> + Send + 'static,
+{
+    tokio::task::spawn_blocking(move || f(&mut *lock(&sim)?))
+        .await
+        .map_err(|e| Status::internal(format!("simulation task failed: {}", e)))?
+}
+
+impl From<&Metrics> for proto::Metrics {
+    fn from(m: &Metrics) -> Self {
+        proto::Metrics {
+            step: m.timestamp,
+            attention_entropy: m.attention_entropy,
+            memory_diversity: m.memory_diversity,
+            velocity_stability: m.velocity_stability,
+            identity_coherence: m.identity_coherence,
+            cluster_stability: m.cluster_stability,
+            affective_strength: m.affective_strength,
+            essence_trajectory: m.essence_trajectory,
+            average_essence: m.average_essence,
+            phase_synchrony: m.phase_synchrony,
+            mean_energy: m.mean_energy,
+            role_stability: m.role_stability,
+        }
+    }
+}
+
+/// Metrics of the most recent step, or of the current state before any step.
+fn latest_metrics(sim: &Simulation) -> proto::Metrics {
+    match sim.metrics_history.last() {
+        Some(metrics) => metrics.into(),
+        None => (&Metrics::compute(&sim.entities, sim.timestamp)).into(),
+    }
+}
+
+fn step_response(sim: &Simulation) -> proto::StepResponse {
+    proto::StepResponse {
+        step: sim.timestamp,
+        metrics: Some(latest_metrics(sim)),
+    }
+}
+
+/// Parse the configuration of a `CreateSimulation` request.
+fn parse_config(request: &proto::CreateSimulationRequest) -> Result<SimulationConfig, Status> {
+    if !request.config_json.is_empty() {
+        serde_json::from_str(&request.config_json)
+            .map_err(|e| Status::invalid_argument(format!("invalid config JSON: {}", e)))
+    } else if !request.config_toml.is_empty() {
+        toml::from_str(&request.config_toml)
+            .map_err(|e| Status::invalid_argument(format!("invalid config TOML: {}", e)))
+    } else {
+        Ok(SimulationConfig::default_2d())
+    }
+}
+
+#[tonic::async_trait]
+impl SimulationService for GrpcService {
+    type StreamStepsStream = ReceiverStream<Result<proto::StepResponse, Status>>;
+
+    async fn create_simulation(
+        &self,
+        request: Request<proto::CreateSimulationRequest>,
+    ) -> Result<Response<proto::CreateSimulationResponse>, Status> {
+        let config = parse_config(request.get_ref())?;
+        let sim = tokio::task::spawn_blocking(move || Simulation::new(config))
+            .await
+            .map_err(|e| Status::internal(format!("simulation task failed: {}", e)))?
+            .map_err(Status::invalid_argument)?;
+
+        let response = proto::CreateSimulationResponse {
+            simulation_id: String::new(),
+            num_entities: sim.entities.count() as u32,
+            dimension: sim.config.geometry.dimension as u32,
+            num_steps: sim.config.simulation.num_steps,
+        };
+        let mut simulations = self
+            .simulations
+            .lock()
+            .map_err(|_| Status::internal("simulation registry unavailable"))?;
+        if simulations.len() >= self.max_simulations {
+            return Err(Status::resource_exhausted(format!(
+                "{} simulations already registered",
+                simulations.len()
+            )));
+        }
+        let id = Uuid::new_v4();
+        simulations.insert(id, Arc::new(Mutex::new(sim)));
+        Ok(Response::new(proto::CreateSimulationResponse {
+            simulation_id: id.to_string(),
+            ..response
+        }))
+    }
+
+    async fn step(&self, request: Request<proto::StepRequest>) -> Result<Response<proto::StepResponse>, Status> {
+        let request = request.into_inner();
+        let sim = self.simulation(&request.simulation_id)?;
+        let response = with_simulation(sim, move |sim| {
+            for _ in 0..request.n {
+                sim.step();
+            }
+            Ok(step_response(sim))
+        })
+        .await?;
+        Ok(Response::new(response))
+    }
+
+    async fn stream_steps(
+        &self,
+        request: Request<proto::StepRequest>,
+    ) -> Result<Response<Self::StreamStepsStream>, Status> {
+        let request = request.into_inner();
+        let sim = self.simulation(&request.simulation_id)?;
+        let (tx, rx) = mpsc::channel(STREAM_BUFFER);
+        tokio::task::spawn_blocking(move || {
+            let mut sim = match lock(&sim) {
+                Ok(sim) => sim,
+                Err(status) => {
+                    let _ = tx.blocking_send(Err(status));
+                    return;
+                }
+            };
+            for _ in 0..request.n {
+                sim.step();
+                if tx.blocking_send(Ok(step_response(&sim))).is_err() {
+                    // Client went away
+                    return;
+                }
+            }
+        });
+        Ok(Response::new(ReceiverStream::new(rx)))
+    }
+
+    async fn get_metrics(&self, request: Request<proto::SimulationRef>) -> Result<Response<proto::Metrics>, Status> {
+        let sim = self.simulation(&request.get_ref().simulation_id)?;
+        let metrics = with_simulation(sim, |sim| Ok(latest_metrics(sim))).await?;
+        Ok(Response::new(metrics))
+    }
+
+    async fn get_entities(
+        &self,
+        request: Request<proto::SimulationRef>,
+    ) -> Result<Response<proto::EntitiesResponse>, Status> {
+        let sim = self.simulation(&request.get_ref().simulation_id)?;
+        let response = with_simulation(sim, |sim| {
+            let entities: Vec<proto::Entity> = sim
+                .entities
+                .iter()
+                .map(|e| proto::Entity {
+                    id: e.id.0,
+                    position: e.pose.position.to_vec(),
+                    velocity: e.velocity.to_vec(),
+                    essence: e.essence.value,
+                    energy: e.energy,
+                    role: format!("{:?}", e.role),
+                    labels: e.labels.clone(),
+                })
+                .collect();
+            Ok(proto::EntitiesResponse {
+                step: sim.timestamp,
+                entities,
+            })
+        })
+        .await?;
+        Ok(Response::new(response))
+    }
+
+    async fn get_summary(
+        &self,
+        request: Request<proto::SimulationRef>,
+    ) -> Result<Response<proto::SummaryResponse>, Status> {
+        let sim = self.simulation(&request.get_ref().simulation_id)?;
+        let summary = with_simulation(sim, |sim| {
+            sim.finalize_results();
+            let analysis = &sim.results.consciousness_analysis;
+            Ok(proto::SummaryResponse {
+                step: sim.timestamp,
+                consciousness_score: analysis.consciousness_score,
+                consciousness_achieved: analysis.consciousness_achieved,
+                passed_metrics: analysis.passed_metrics.clone(),
+                failed_metrics: analysis.failed_metrics.clone(),
+                narrative: sim.results.narrative_summary(),
+            })
+        })
+        .await?;
+        Ok(Response::new(summary))
+    }
+
+    async fn inject_stimulus(
+        &self,
+        request: Request<proto::InjectStimulusRequest>,
+    ) -> Result<Response<proto::InjectStimulusResponse>, Status> {
+        let request = request.into_inner();
+        let sim = self.simulation(&request.simulation_id)?;
+        let memory_nodes = with_simulation(sim, move |sim| {
+            let id = EntityId(request.entity_id);
+            if sim.entities.get_entity(id).is_none() {
+                return Err(Status::not_found(format!("no entity with id {}", id.0)));
+            }
+            sim.inject_stimulus(id, request.stimulus).map_err(Status::invalid_argument)?;
+            Ok(sim
+                .entities
+                .get_entity(id)
+                .map_or(0, |e| e.memory_graph.nodes.len() as u32))
+        })
+        .await?;
+        Ok(Response::new(proto::InjectStimulusResponse { memory_nodes }))
+    }
+
+    async fn shutdown(
+        &self,
+        request: Request<proto::SimulationRef>,
+    ) -> Result<Response<proto::ShutdownResponse>, Status> {
+        let id = &request.get_ref().simulation_id;
+        if id.is_empty() {
+            self.shutdown.notify_one();
+            return Ok(Response::new(proto::ShutdownResponse { removed: false }));
+        }
+        let uuid = Uuid::parse_str(id).map_err(|_| Status::not_found("unknown simulation id"))?;
+        let mut simulations = self
+            .simulations
+            .lock()
+            .map_err(|_| Status::internal("simulation registry unavailable"))?;
+        match simulations.remove(&uuid) {
+            Some(_) => Ok(Response::new(proto::ShutdownResponse { removed: true })),
+            None => Err(Status::not_found("unknown simulation id")),
+        }
+    }
+}
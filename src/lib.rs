@@ -30,22 +30,63 @@
 //! ## Project
 //! GitHub: https://github.com/Alchymia-AI/synthetic-consciousness
 
+pub mod real;
 pub mod geometry;
 pub mod attraction;
+pub mod barnes_hut;
 pub mod state;
 pub mod dynamics;
+pub mod environment;
 pub mod memory;
+pub mod sensing;
+pub mod schedule;
 pub mod essence;
+pub mod energy;
+pub mod events;
+pub mod reproduction;
+pub mod roles;
+pub mod signaling;
 pub mod metrics;
 pub mod entities;
+pub mod species;
 pub mod config;
+pub mod error;
 pub mod simulation;
+pub mod progress;
+pub mod profiling;
+pub mod checkpoint;
 pub mod results;
+pub mod export;
+pub mod cli;
+#[cfg(feature = "gui")]
 pub mod visualization;
+pub mod analysis;
+#[cfg(feature = "http")]
+pub mod api;
+pub mod replay;
+pub mod plugin;
+pub mod notebook;
+pub mod sweep;
+pub mod ensemble;
+#[cfg(feature = "arrow")]
+pub mod columnar;
+#[cfg(feature = "polars")]
+pub mod dataframe;
+#[cfg(feature = "grpc")]
+pub mod grpc;
+#[cfg(feature = "prometheus")]
+pub mod telemetry;
+#[cfg(feature = "server")]
+pub mod server;
+pub mod bindings;
 
-pub use config::SimulationConfig;
-pub use simulation::Simulation;
+pub use config::{SimulationConfig, SimulationConfigPatch};
+pub use error::SimulationError;
+pub use export::ExportFormat;
+pub use simulation::{Simulation, SimulationBuilder};
 pub use entities::Entity;
 pub use metrics::Metrics;
+pub use real::Real;
 pub use results::{SimulationResults, SimulationStep};
+#[cfg(feature = "gui")]
 pub use visualization::{VisualizationState, EntityState, MetricsHistory};
@@ -13,107 +13,586 @@
 //! # Run with custom configuration
 //! cargo run --release -- config.toml
 //!
+//! # Read the configuration (TOML or JSON) from a pipe; `-` may be omitted
+//! gen_config.py | cargo run --release -- run - --export json --format json
+//!
 //! # Run with real-time visualization
 //! cargo run --release -- config.toml --visualize
-//! cargo run --release -- -v
+//!
+//! # Errors only, or step-by-step diagnostics as well (default: summary plus a
+//! # progress line with ETA on stderr)
+//! cargo run --release -- config.toml --quiet
+//! cargo run --release -- config.toml -v
+//!
+//! # Override individual parameters (after the config file, if any)
+//! cargo run --release -- config.toml sigma=2.0 num_entities=20
+//! cargo run --release -- geometry.dimension=3 dynamics.dt=0.005
+//!
+//! # Override the run size and seed (these win over key=value overrides)
+//! cargo run --release -- config.toml --steps 5000 --entities 50 --seed 7
+//!
+//! # Serve the HTTP control API (requires the `http` feature)
+//! cargo run --release --features http -- --serve=0.0.0.0:8080
+//!
+//! # Serve the gRPC API (requires the `grpc` feature)
+//! cargo run --release --features grpc -- --grpc=0.0.0.0:50051
+//!
+//! # Expose Prometheus metrics at /metrics during a run (requires the `prometheus` feature)
+//! cargo run --release --features prometheus -- config.toml --metrics=0.0.0.0:9100
+//!
+//! # Choose the output files (csv, txt, html, json, md, parquet, none; default csv,txt,html)
+//! cargo run --release -- config.toml --export=json,md
+//!
+//! # Exit code 0 even when the consciousness criteria are not met (default: 3)
+//! cargo run --release -- config.toml --no-verdict-exit-code
+//!
+//! # Play back saved results (plain or gzip-compressed JSON) in the GUI
+//! cargo run --release -- replay results.json.gz --speed 2.0 --start-step 1000
+//!
+//! # Sweep parameters over a base config: 4 cells at a time, summaries in sweep_results/
+//! cargo run --release -- sweep base.toml --param attraction.sigma=0.5,1.0,2.0 \\
+//!     --param dynamics.min_speed=0.01,0.05 --jobs 4 --out sweep_results/
+//!
+//! # Check config files without running them (OK plus a summary, or every problem)
+//! cargo run --release -- validate config.toml example_config_3d.toml
+//!
+//! # Save a checkpoint every 10000 steps, and continue from it after a crash
+//! cargo run --release -- long_run.toml --checkpoint-every 10000 --checkpoint long_run.ckpt.json.gz
+//! cargo run --release -- --resume long_run.ckpt.json.gz
+//!
+//! # Load plugins named in the config from a library (requires the `unsafe-plugins` feature)
+//! cargo run --release --features unsafe-plugins -- config.toml --plugin-lib=target/release/libpulse_stimulus.so
 //! ```
 //!
+//! ## Exit Codes
+//!
+//! | Code | Meaning                                                                  |
+//! |------|--------------------------------------------------------------------------|
+//! | 0    | Run completed and consciousness was achieved, or a server shut down      |
+//! | 1    | Invalid command line or configuration (`validate`: semantic problems)    |
+//! | 2    | Failure while running, serving or writing results; an interrupted sweep  |
+//! | 3    | Run completed without meeting the consciousness criteria                 |
+//! | 4    | `validate`: a configuration file could not be read or parsed             |
+//!
+//! The mapping is `RunOutcome::exit_code` in the `cli` module; `-v` prints
+//! the chosen code.
+//!
 //! ## Author
 //! Ayomide I. Daniels (Morningstar)
 
-use synthetic_consciousness::config::SimulationConfig;
+use log::{debug, error, info};
+use synthetic_consciousness::cli::{
+    init_logging, CliArgs, Command, ReplayArgs, RunOutcome, SweepArgs, ValidateArgs, Verbosity,
+    EXIT_CONFIG_ERROR, EXIT_RUNTIME_ERROR,
+};
+use synthetic_consciousness::checkpoint::Checkpointer;
+use synthetic_consciousness::config::{ConfigFormat, SimulationConfig, STDIN_PATH};
+use synthetic_consciousness::export::ExportFormat;
+use synthetic_consciousness::plugin::PluginRegistry;
+use synthetic_consciousness::progress::{LogProgress, TerminalProgress};
+use synthetic_consciousness::real::to_f32;
+use synthetic_consciousness::results::SimulationResults;
 use synthetic_consciousness::simulation::Simulation;
-use synthetic_consciousness::visualization::{VisualizationState, launch_visualization};
+use synthetic_consciousness::sweep::{expand_grid, CellStatus, SweepRunner};
+use synthetic_consciousness::visualization::{Playback, SimulationControl, VisualizationState, launch_visualization};
 use std::env;
+use std::io::{self, IsTerminal, Read};
+use std::sync::atomic::Ordering;
 use std::sync::{Arc, Mutex};
 use std::thread;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
 fn main() {
-    let args: Vec<String> = env::args().collect();
-    
-    // Check for visualization flag
-    let visualize = args.contains(&"--visualize".to_string()) || args.contains(&"-v".to_string());
-    
-    // Get config file path (skip --visualize flag)
-    let config_path = args.iter()
-        .skip(1)
-        .find(|arg| !arg.starts_with('-'));
-
-    let config = if let Some(path) = config_path {
-        // Load configuration from file
-        match SimulationConfig::from_toml(path) {
-            Ok(cfg) => cfg,
-            Err(e) => {
-                eprintln!("Error loading config: {}", e);
-                std::process::exit(1);
+    let args: Vec<String> = env::args().skip(1).collect();
+    match Command::parse(&args) {
+        Ok(Command::Run(cli)) => run_simulation(cli),
+        Ok(Command::Replay(replay)) => {
+            init_logging(replay.verbosity);
+            exit_with(run_replay(&replay), true);
+        }
+        Ok(Command::Sweep(sweep)) => {
+            init_logging(sweep.verbosity);
+            exit_with(run_sweep(&sweep), true);
+        }
+        Ok(Command::Validate(validate)) => {
+            init_logging(validate.verbosity);
+            exit_with(run_validate(&validate), true);
+        }
+        Err(e) => {
+            eprintln!("Error in command line: {}", e);
+            std::process::exit(EXIT_CONFIG_ERROR);
+        }
+    }
+}
+
+/// Load (or default) the configuration, or resume a checkpoint, run it and
+/// exit with its outcome.
+fn run_simulation(cli: CliArgs) -> ! {
+    init_logging(cli.verbosity);
+
+    #[cfg(feature = "http")]
+    if let Some(addr) = &cli.serve_addr {
+        exit_with(serve_api(addr), cli.verdict_exit_code);
+    }
+
+    #[cfg(feature = "grpc")]
+    if let Some(addr) = &cli.grpc_addr {
+        exit_with(serve_grpc(addr), cli.verdict_exit_code);
+    }
+
+    let registry = match plugin_registry(&cli.plugin_libs) {
+        Ok(registry) => registry,
+        Err(e) => {
+            error!("Error loading plugin library: {}", e);
+            exit_with(RunOutcome::ConfigError, cli.verdict_exit_code);
+        }
+    };
+
+    let created = match &cli.resume {
+        Some(path) => resume_simulation(path, &registry),
+        None => new_simulation(&cli, &registry),
+    };
+
+    // Run the simulation
+    let outcome = match created {
+        Ok(mut sim) => {
+            match cli.verbosity {
+                Verbosity::Quiet => {}
+                Verbosity::Normal => sim.set_progress_reporter(Box::new(TerminalProgress::new())),
+                Verbosity::Verbose => sim.set_progress_reporter(Box::new(LogProgress::default())),
+            }
+            if let Some((path, every)) = cli.checkpoint() {
+                info!("Checkpoints: every {} steps to {}", every, path);
+                sim.add_observer(Box::new(Checkpointer::new(path, every as u64)));
+            }
+            if cli.visualize {
+                info!("Visualization: ENABLED");
+            }
+            info!("");
+            if cli.visualize {
+                run_with_visualization(sim, &cli.export)
+            } else if let Some(addr) = &cli.metrics_addr {
+                run_with_metrics(sim, addr, &cli.export)
+            } else {
+                run_without_visualization(sim, &cli.export)
             }
         }
-    } else {
-        // Use default 2D configuration
-        println!("No config specified, using default 2D configuration");
-        SimulationConfig::default_2d()
+        Err(outcome) => outcome,
     };
+    exit_with(outcome, cli.verdict_exit_code);
+}
+
+/// Load (or default) the configuration, apply the overrides and create the
+/// simulation.
+fn new_simulation(cli: &CliArgs, registry: &PluginRegistry) -> Result<Simulation, RunOutcome> {
+    let (mut config, config_source) = load_config(cli.config_path.as_deref(), cli.config_format).map_err(|e| {
+        error!("Error loading config: {}", e);
+        RunOutcome::ConfigError
+    })?;
+
+    // key=value overrides, then --steps/--entities/--seed; validated together
+    let overrides = cli.config_overrides();
+    if let Err(e) = config.apply_cli_args(&overrides) {
+        error!("Error in command-line overrides: {}", e);
+        return Err(RunOutcome::ConfigError);
+    }
+
+    log_banner(&config);
+    if config_source == STDIN_SOURCE {
+        info!("Config: read from standard input");
+    }
+    if !overrides.is_empty() {
+        info!("Overridden via CLI: {}", overrides.join(", "));
+    }
+
+    let mut sim = Simulation::with_plugins(config, registry).map_err(|e| {
+        error!("Error creating simulation: {}", e);
+        RunOutcome::ConfigError
+    })?;
+    sim.results.cli_overrides = overrides;
+    sim.results.config_source = config_source;
+    Ok(sim)
+}
 
-    println!("===== Synthetic Consciousness Simulation =====");
-    println!("Name: {}", config.metadata.name);
-    println!("Description: {}", config.metadata.description);
-    println!("Dimensionality: {}D", config.geometry.dimension);
-    println!("Entities: {}", config.simulation.num_entities);
-    println!("Steps: {}", config.simulation.num_steps);
-    if visualize {
-        println!("Visualization: ENABLED");
-    }
-    println!();
-
-    // Create and run simulation
-    match Simulation::new(config.clone()) {
-        Ok(sim) => {
-            if visualize {
-                run_with_visualization(sim, config);
+/// Continue a run saved with `--checkpoint-every`.
+fn resume_simulation(path: &str, registry: &PluginRegistry) -> Result<Simulation, RunOutcome> {
+    let sim = Simulation::load_checkpoint_with_plugins(path, registry).map_err(|e| {
+        error!("Error loading checkpoint: {}", e);
+        RunOutcome::ConfigError
+    })?;
+    log_banner(&sim.config);
+    info!("Resuming: {} at step {}", path, sim.timestamp);
+    Ok(sim)
+}
+
+/// Print the name and size of the run.
+fn log_banner(config: &SimulationConfig) {
+    info!("===== Synthetic Consciousness Simulation =====");
+    info!("Name: {}", config.metadata.name);
+    info!("Description: {}", config.metadata.description);
+    info!("Dimensionality: {}D", config.geometry.dimension);
+    info!("Entities: {}", config.simulation.num_entities);
+    info!("Steps: {}", config.simulation.num_steps);
+}
+
+/// `config_source` of a configuration read from standard input.
+const STDIN_SOURCE: &str = "stdin";
+
+/// Load the configuration named on the command line.
+///
+/// Without a path, a piped standard input is read; if it is a terminal or
+/// empty, the default 2D configuration is used.
+///
+/// # Returns
+/// The configuration and where it came from (its path, `stdin` or `default`)
+fn load_config(path: Option<&str>, format: Option<ConfigFormat>) -> Result<(SimulationConfig, String), String> {
+    match path {
+        Some(STDIN_PATH) => SimulationConfig::load(STDIN_PATH, format).map(|config| (config, STDIN_SOURCE.to_string())),
+        Some(path) => SimulationConfig::load(path, format).map(|config| (config, path.to_string())),
+        None => {
+            let mut contents = String::new();
+            if !io::stdin().is_terminal() {
+                io::stdin()
+                    .read_to_string(&mut contents)
+                    .map_err(|e| format!("cannot read standard input: {}", e))?;
+            }
+            if contents.trim().is_empty() {
+                info!("No config specified, using default 2D configuration");
+                Ok((SimulationConfig::default_2d(), "default".to_string()))
             } else {
-                run_without_visualization(sim);
+                SimulationConfig::parse(&contents, format).map(|config| (config, STDIN_SOURCE.to_string()))
+            }
+        }
+    }
+}
+
+/// Play back saved results in the GUI until the window is closed.
+fn run_replay(args: &ReplayArgs) -> RunOutcome {
+    let results = match SimulationResults::load(&args.results_path) {
+        Ok(results) => results,
+        Err(e) => {
+            error!("Error loading results: {}", e);
+            return RunOutcome::ConfigError;
+        }
+    };
+    let name = results.simulation_name.clone();
+    let playback = match Playback::new(results, args.speed, args.start_step) {
+        Ok(playback) => playback,
+        Err(e) => {
+            error!("Cannot replay '{}': {}", args.results_path, e);
+            return RunOutcome::ConfigError;
+        }
+    };
+
+    info!("===== Replay =====");
+    info!("Results: {} ({})", args.results_path, name);
+    info!("Recorded steps: {}", playback.frame_count());
+    info!("Speed: {}x", args.speed);
+
+    let viz_state = Arc::new(Mutex::new(playback.initial_state()));
+    let playing = Arc::clone(&viz_state);
+    thread::spawn(move || {
+        thread::sleep(Duration::from_millis(500)); // Let GUI initialize first
+        playback.play(&playing);
+        info!("Replay finished. Close the window to exit...");
+    });
+
+    match launch_visualization(viz_state, None) {
+        Ok(()) => RunOutcome::NoVerdict,
+        Err(e) => {
+            error!("Visualization error: {}", e);
+            RunOutcome::RuntimeError
+        }
+    }
+}
+
+/// Check every configuration file and report OK with a summary or its problems.
+///
+/// A file that cannot be parsed outranks one with semantic problems.
+fn run_validate(args: &ValidateArgs) -> RunOutcome {
+    let mut paths = args.config_paths.clone();
+    if paths.is_empty() {
+        if io::stdin().is_terminal() {
+            error!("validate needs a config file, '-' or a piped configuration");
+            return RunOutcome::ConfigError;
+        }
+        paths.push(STDIN_PATH.to_string());
+    }
+
+    let mut outcome = RunOutcome::NoVerdict;
+    for path in &paths {
+        let path = path.as_str();
+        let checked = SimulationConfig::check_file(path, args.config_format);
+        let path = if path == STDIN_PATH { STDIN_SOURCE } else { path };
+        match checked {
+            Ok((config, problems)) if problems.is_empty() => {
+                info!("{}: OK", path);
+                for line in config.summary().lines() {
+                    info!("  {}", line);
+                }
             }
+            Ok((_, problems)) => {
+                error!("{}: {} problem{}", path, problems.len(), if problems.len() == 1 { "" } else { "s" });
+                for problem in &problems {
+                    error!("  {}", problem);
+                }
+                if outcome != RunOutcome::ParseError {
+                    outcome = RunOutcome::ConfigError;
+                }
+            }
+            Err(e) => {
+                error!("{}: parse error", path);
+                for line in e.trim_end().lines() {
+                    error!("  {}", line);
+                }
+                outcome = RunOutcome::ParseError;
+            }
+        }
+    }
+    outcome
+}
+
+/// Number of cells listed in the table printed after a sweep.
+const BEST_CELLS: usize = 5;
+
+/// Run every cell of a parameter sweep and summarize the best ones.
+fn run_sweep(args: &SweepArgs) -> RunOutcome {
+    let base = match SimulationConfig::load(&args.config_path, None) {
+        Ok(config) => config,
+        Err(e) => {
+            error!("Error loading config: {}", e);
+            return RunOutcome::ConfigError;
+        }
+    };
+    let cells = match expand_grid(&base, &args.params) {
+        Ok(cells) => cells,
+        Err(e) => {
+            error!("Error in sweep parameters: {}", e);
+            return RunOutcome::ConfigError;
+        }
+    };
+    let runner = SweepRunner::new(&args.out_dir, args.jobs.unwrap_or(0) as usize);
+
+    info!("===== Parameter Sweep =====");
+    info!("Base config: {} ({})", args.config_path, base.metadata.name);
+    for param in &args.params {
+        info!("Parameter: {} = {}", param.path, param.values.join(", "));
+    }
+    info!("Cells: {}", cells.len());
+    info!("Jobs: {}", runner.jobs());
+    info!("Output: {}", args.out_dir);
+    info!("");
+
+    // First Ctrl-C lets running cells finish; a second one aborts
+    let stop = runner.stop_flag();
+    if let Err(e) = ctrlc::set_handler(move || {
+        if stop.swap(true, Ordering::SeqCst) {
+            std::process::exit(EXIT_RUNTIME_ERROR);
+        }
+        log::warn!("Interrupted: finishing running cells (Ctrl-C again to abort)");
+    }) {
+        log::warn!("Cannot handle Ctrl-C: {}", e);
+    }
+
+    let report = runner.run(cells, |outcome, finished, total| match &outcome.status {
+        CellStatus::Completed { score, consciousness_achieved, .. } => info!(
+            "[{}/{}] cell {}: score {:.4}{} ({})",
+            finished,
+            total,
+            outcome.index,
+            to_f32(*score),
+            if *consciousness_achieved { ", conscious" } else { "" },
+            outcome.label()
+        ),
+        CellStatus::Failed(e) => error!("[{}/{}] cell {} failed: {}", finished, total, outcome.index, e),
+        CellStatus::Skipped => {}
+    });
+    let report = match report {
+        Ok(report) => report,
+        Err(e) => {
+            error!("Error running sweep: {}", e);
+            return RunOutcome::RuntimeError;
+        }
+    };
+
+    info!("");
+    info!("===== Best Cells =====");
+    info!("{}", report.best_table(BEST_CELLS));
+    info!("");
+    info!("Summary: {}", report.csv_path.display());
+
+    if report.interrupted {
+        log::warn!(
+            "Sweep interrupted: {} of {} cells ran; the summary is partial",
+            report.outcomes.len() - report.count("skipped"),
+            report.outcomes.len()
+        );
+        RunOutcome::RuntimeError
+    } else if report.count("failed") > 0 {
+        error!("{} of {} cells failed", report.count("failed"), report.outcomes.len());
+        RunOutcome::RuntimeError
+    } else {
+        RunOutcome::NoVerdict
+    }
+}
+
+/// Exit the process with the code of `outcome` (see `RunOutcome::exit_code`).
+fn exit_with(outcome: RunOutcome, verdict_exit_code: bool) -> ! {
+    let code = outcome.exit_code(verdict_exit_code);
+    debug!("Exit code {} ({:?})", code, outcome);
+    std::process::exit(code)
+}
+
+/// Link-time plugins plus the libraries given with `--plugin-lib=`.
+#[cfg(feature = "unsafe-plugins")]
+fn plugin_registry(libraries: &[String]) -> Result<PluginRegistry, String> {
+    let mut registry = PluginRegistry::with_registered();
+    for path in libraries {
+        // SAFETY: the user asked for this library's code to run
+        unsafe { registry.load_library(path) }.map_err(|e| e.to_string())?;
+    }
+    Ok(registry)
+}
+
+/// Link-time plugins (`--plugin-lib` needs the `unsafe-plugins` feature).
+#[cfg(not(feature = "unsafe-plugins"))]
+fn plugin_registry(libraries: &[String]) -> Result<PluginRegistry, String> {
+    if !libraries.is_empty() {
+        log::warn!("--plugin-lib requires the `unsafe-plugins` feature; ignoring it");
+    }
+    Ok(PluginRegistry::with_registered())
+}
+
+/// Run the HTTP control API until the process is stopped.
+#[cfg(feature = "http")]
+fn serve_api(addr: &str) -> RunOutcome {
+    use synthetic_consciousness::api::{ApiServer, DEFAULT_MAX_ACTIVE_RUNS};
+
+    match ApiServer::bind(addr, DEFAULT_MAX_ACTIVE_RUNS) {
+        Ok(server) => {
+            info!("Serving simulation API on http://{}", addr);
+            server.serve();
+            RunOutcome::NoVerdict
         }
         Err(e) => {
-            eprintln!("Error creating simulation: {}", e);
-            std::process::exit(1);
+            error!("Error starting API server: {}", e);
+            RunOutcome::RuntimeError
         }
     }
 }
 
-fn run_with_visualization(mut sim: Simulation, config: SimulationConfig) {
+/// Run the gRPC API until a client requests shutdown.
+#[cfg(feature = "grpc")]
+fn serve_grpc(addr: &str) -> RunOutcome {
+    use synthetic_consciousness::grpc::{serve, DEFAULT_MAX_SIMULATIONS};
+
+    let runtime = match tokio::runtime::Runtime::new() {
+        Ok(runtime) => runtime,
+        Err(e) => {
+            error!("Error starting async runtime: {}", e);
+            return RunOutcome::RuntimeError;
+        }
+    };
+    let result: Result<(), Box<dyn std::error::Error>> = runtime.block_on(async {
+        let listener = tokio::net::TcpListener::bind(addr).await?;
+        info!("Serving gRPC simulation API on {}", addr);
+        serve(listener, DEFAULT_MAX_SIMULATIONS).await?;
+        Ok(())
+    });
+    match result {
+        Ok(()) => RunOutcome::NoVerdict,
+        Err(e) => {
+            error!("Error in gRPC server: {}", e);
+            RunOutcome::RuntimeError
+        }
+    }
+}
+
+/// Run headless while serving Prometheus metrics at `addr`.
+#[cfg(feature = "prometheus")]
+fn run_with_metrics(mut sim: Simulation, addr: &str, formats: &[ExportFormat]) -> RunOutcome {
+    use synthetic_consciousness::telemetry::{MetricsEndpoint, MetricsObserver};
+
+    let observer = match MetricsObserver::new() {
+        Ok(observer) => Arc::new(observer),
+        Err(e) => {
+            error!("Error creating metrics registry: {}", e);
+            return RunOutcome::RuntimeError;
+        }
+    };
+    let endpoint = match MetricsEndpoint::bind(addr, Arc::clone(&observer)) {
+        Ok(endpoint) => Arc::new(endpoint),
+        Err(e) => {
+            error!("Error starting metrics endpoint: {}", e);
+            return RunOutcome::RuntimeError;
+        }
+    };
+    let serving = Arc::clone(&endpoint);
+    let server_thread = thread::spawn(move || serving.serve());
+    info!("Serving Prometheus metrics on http://{}/metrics", addr);
+
+    info!("Starting simulation...");
+    sim.run_observed(&observer);
+    sim.finalize_results();
+    endpoint.shutdown();
+    let _ = server_thread.join();
+
+    info!("Simulation complete!");
+    log_wall_clock(&sim);
+    info!("");
+
+    print_final_results(&sim, formats)
+}
+
+/// Without the `prometheus` feature `--metrics` is ignored.
+#[cfg(not(feature = "prometheus"))]
+fn run_with_metrics(sim: Simulation, _addr: &str, formats: &[ExportFormat]) -> RunOutcome {
+    log::warn!("--metrics requires the `prometheus` feature; running without it");
+    run_without_visualization(sim, formats)
+}
+
+/// Minimum interval between visualization updates of a live run.
+const VISUALIZATION_FRAME: Duration = Duration::from_millis(20);
+
+fn run_with_visualization(mut sim: Simulation, formats: &[ExportFormat]) -> RunOutcome {
     // Create shared visualization state
     let viz_state = Arc::new(Mutex::new(VisualizationState {
-        dimension: config.geometry.dimension,
-        bounds: config.geometry.bounds.clone(),
+        dimension: sim.config.geometry.dimension,
+        bounds: sim.config.geometry.bounds.iter().map(|&b| to_f32(b)).collect(),
         ..Default::default()
     }));
     
     let viz_state_clone = Arc::clone(&viz_state);
+    let control = Arc::new(SimulationControl::new());
+    let sim_control = Arc::clone(&control);
     
     // Run simulation in separate thread
     let sim_thread = thread::spawn(move || {
         thread::sleep(Duration::from_millis(500)); // Let GUI initialize first
         
-        println!("Starting simulation with real-time visualization...");
+        info!("Starting simulation with real-time visualization...");
         
         // Initial visualization update BEFORE stepping
         sim.update_visualization(&viz_state_clone);
-        println!("[DEBUG] Initial visualization update sent");
+        debug!("Initial visualization update sent");
         
-        // Run simulation with visualization updates
-        for step in 0..config.simulation.num_steps {
+        // Run simulation with visualization updates (the effective step count,
+        // including any CLI override, less the steps of a resumed checkpoint)
+        let mut last_step = Instant::now();
+        let mut last_update = Instant::now();
+        for _ in 0..sim.remaining_steps() {
+            if sim.stop_requested() || !sim_control.wait_for_step(|| sim.stop_requested()) {
+                break;
+            }
+            sim_control.pace(&mut last_step);
+            sim.pull_manual_overrides(&viz_state_clone);
             sim.step();
             
-            // Update visualization every N steps
-            if step % 10 == 0 {
+            // Update visualization at frame rate, and after every step while
+            // paused so single steps show
+            if sim_control.is_paused() || last_update.elapsed() >= VISUALIZATION_FRAME {
                 sim.update_visualization(&viz_state_clone);
-                thread::sleep(Duration::from_millis(10)); // Slow down for visibility
-            }
-            
-            if step % 100 == 0 {
-                println!("Step {}/{}", step, config.simulation.num_steps);
+                last_update = Instant::now();
             }
         }
         
@@ -123,61 +602,98 @@ fn run_with_visualization(mut sim: Simulation, config: SimulationConfig) {
         // Finalize results
         sim.finalize_results();
         
-        println!();
-        println!("Simulation complete! Visualization window will remain open.");
-        println!("Close the window to exit...");
-        println!();
+        info!("");
+        info!("Simulation complete! Visualization window will remain open.");
+        info!("Close the window to exit...");
+        info!("");
         
         sim
     });
     
     // Launch visualization on main thread (required for macOS)
-    if let Err(e) = launch_visualization(viz_state) {
-        eprintln!("Visualization error: {}", e);
+    let viz_result = launch_visualization(viz_state, Some(Arc::clone(&control)));
+    if let Err(e) = &viz_result {
+        error!("Visualization error: {}", e);
     }
+
+    // A run paused or slowed down when the window closed finishes at full speed
+    control.set_paused(false);
+    control.set_steps_per_second(0);
     
     // Wait for simulation to complete
-    if let Ok(sim) = sim_thread.join() {
-        print_final_results(&sim);
+    match sim_thread.join() {
+        Ok(sim) => match print_final_results(&sim, formats) {
+            RunOutcome::Completed { .. } if viz_result.is_err() => RunOutcome::RuntimeError,
+            outcome => outcome,
+        },
+        Err(_) => RunOutcome::RuntimeError,
     }
 }
 
-fn run_without_visualization(mut sim: Simulation) {
-    println!("Starting simulation...");
+fn run_without_visualization(mut sim: Simulation, formats: &[ExportFormat]) -> RunOutcome {
+    info!("Starting simulation...");
     sim.run();
     
     // Finalize results and analyze consciousness
     sim.finalize_results();
     
-    println!("Simulation complete!");
-    println!();
+    info!("Simulation complete!");
+    log_wall_clock(&sim);
+    info!("");
     
-    print_final_results(&sim);
+    print_final_results(&sim, formats)
 }
 
-fn print_final_results(sim: &Simulation) {
+/// Log how long the steps took in real time and their average speed.
+fn log_wall_clock(sim: &Simulation) {
+    let progress = sim.progress();
+    match progress.average_steps_per_second {
+        Some(rate) => info!("Wall-clock time: {:.2}s ({:.0} steps/s)", sim.results.wall_clock_seconds, rate),
+        None => info!("Wall-clock time: {:.2}s", sim.results.wall_clock_seconds),
+    }
+}
+
+/// Print the results, write the exports and return the run's outcome.
+fn print_final_results(sim: &Simulation, formats: &[ExportFormat]) -> RunOutcome {
     // Print final metrics
     if let Some(final_metrics) = sim.metrics_history.last() {
-        println!("===== Final Metrics =====");
-        println!("Attention Entropy: {:.4}", final_metrics.attention_entropy);
-        println!("Memory Diversity: {:.4}", final_metrics.memory_diversity);
-        println!("Velocity Stability: {:.4}", final_metrics.velocity_stability);
-        println!("Identity Coherence: {:.4}", final_metrics.identity_coherence);
-        println!("Cluster Stability: {:.4}", final_metrics.cluster_stability);
-        println!("Affective Strength: {:.4}", final_metrics.affective_strength);
-        println!("Average Essence: {:.4}", final_metrics.average_essence);
-        println!();
-    }
-
-    // Export metrics
-    match sim.export_metrics_csv("metrics.csv") {
-        Ok(_) => println!("Metrics exported to metrics.csv"),
-        Err(e) => eprintln!("Error exporting metrics: {}", e),
+        info!("===== Final Metrics =====");
+        info!("Attention Entropy: {:.4}", final_metrics.attention_entropy);
+        info!("Memory Diversity: {:.4}", final_metrics.memory_diversity);
+        info!("Velocity Stability: {:.4}", final_metrics.velocity_stability);
+        info!("Identity Coherence: {:.4}", final_metrics.identity_coherence);
+        info!("Cluster Stability: {:.4}", final_metrics.cluster_stability);
+        info!("Affective Strength: {:.4}", final_metrics.affective_strength);
+        info!("Average Essence: {:.4}", final_metrics.average_essence);
+        info!("");
     }
-    
-    // Generate detailed report
-    match sim.generate_report("simulation") {
-        Ok(_) => {},
-        Err(e) => eprintln!("Error generating report: {}", e),
+
+    info!("===== Summary =====");
+    info!("{}", sim.results.narrative_summary());
+    info!("");
+
+    let (written, exported) = match sim.export_reports("simulation", formats) {
+        Ok(written) => (written, true),
+        Err(e) => {
+            error!("Error exporting results: {}", e);
+            (Vec::new(), false)
+        }
+    };
+    info!("{}", sim.consciousness_summary(&written));
+
+    // Machine-readable list of what was written, last on stdout
+    info!("===== Exports =====");
+    let names: Vec<&str> = formats.iter().map(|format| format.name()).collect();
+    info!("formats: {}", if names.is_empty() { "none".to_string() } else { names.join(",") });
+    for (format, path) in written {
+        info!("{}: {}", format, path);
+    }
+
+    if exported {
+        RunOutcome::Completed {
+            consciousness_achieved: sim.consciousness_achieved(),
+        }
+    } else {
+        RunOutcome::RuntimeError
     }
 }
@@ -24,26 +24,114 @@
 //! Belief clusters are essential for consciousness emergence as they provide
 //! the organizational structure for coherent self-representation.
 //!
+//! ## Motifs
+//!
+//! The directed edge structure can be summarized by a triad census: every
+//! connected 3-node induced subgraph is classified into one of the 13
+//! connected directed triad types (standard MAN labels such as `021C` for a
+//! feedforward chain, `030C` for a feedback cycle, `021D` for a fan-out hub).
+//!
+//! ## Information Content
+//!
+//! The activation of a belief cluster is the summed activation of its
+//! nodes. A softmax over cluster activations gives the probability that
+//! each cluster is the most active one; a node's event is as probable as
+//! its cluster, so its surprisal is `I = −ln P` (in nats). Nodes outside
+//! any cluster count as singleton clusters. Rarely active beliefs carry
+//! more information than dominant ones.
+//!
 //! ## Author
 //! Ayomide I. Daniels (Morningstar)
 
+use crate::real::{from_f64, to_f64, Real};
+use ndarray::{s, ArrayView1};
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+/// Vector length from which `cosine_similarity` uses ndarray's unrolled dot
+/// product; shorter vectors sum in order, bit for bit like a plain loop.
+const UNROLLED_DOT_MIN_LEN: usize = 8;
+
+/// Decays between full recomputations of the activation entropy sums.
+pub const ACTIVATION_RESYNC_INTERVAL: u32 = 256;
+
+/// Representative edge sets of the 13 connected directed triad types,
+/// keyed by their MAN (mutual/asymmetric/null) label.
+const TRIAD_TYPES: [(&str, &[(usize, usize)]); 13] = [
+    ("021D", &[(1, 0), (1, 2)]),
+    ("021U", &[(0, 1), (2, 1)]),
+    ("021C", &[(0, 1), (1, 2)]),
+    ("111D", &[(0, 1), (1, 0), (2, 1)]),
+    ("111U", &[(0, 1), (1, 0), (1, 2)]),
+    ("030T", &[(0, 1), (1, 2), (0, 2)]),
+    ("030C", &[(0, 1), (1, 2), (2, 0)]),
+    ("201", &[(0, 1), (1, 0), (1, 2), (2, 1)]),
+    ("120D", &[(1, 0), (1, 2), (0, 2), (2, 0)]),
+    ("120U", &[(0, 1), (2, 1), (0, 2), (2, 0)]),
+    ("120C", &[(0, 1), (1, 2), (0, 2), (2, 0)]),
+    ("210", &[(0, 1), (1, 2), (2, 1), (0, 2), (2, 0)]),
+    ("300", &[(0, 1), (1, 0), (1, 2), (2, 1), (0, 2), (2, 0)]),
+];
+
+/// Canonical (permutation-invariant) code of a directed 3-node subgraph.
+///
+/// Each of the six ordered pairs contributes one bit; the canonical code
+/// is the minimum over all node relabelings.
+fn triad_code(has_edge: impl Fn(usize, usize) -> bool) -> u8 {
+    const PERMUTATIONS: [[usize; 3]; 6] =
+        [[0, 1, 2], [0, 2, 1], [1, 0, 2], [1, 2, 0], [2, 0, 1], [2, 1, 0]];
+    const PAIRS: [(usize, usize); 6] = [(0, 1), (1, 0), (0, 2), (2, 0), (1, 2), (2, 1)];
+
+    PERMUTATIONS
+        .iter()
+        .map(|perm| {
+            PAIRS.iter().enumerate().fold(0u8, |code, (bit, &(a, b))| {
+                if has_edge(perm[a], perm[b]) {
+                    code | (1 << bit)
+                } else {
+                    code
+                }
+            })
+        })
+        .min()
+        .unwrap_or(0)
+}
+
+/// Location of an event vector in a memory graph's event arena.
+/// 
+/// Handles are only meaningful for the graph that issued them; read the
+/// event through [`MemoryGraph::event`] or [`MemoryGraph::node_event`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct EventHandle {
+    /// First component in the arena.
+    pub offset: usize,
+    /// Number of components.
+    pub len: usize,
+}
+
+impl EventHandle {
+    /// The handle's components in `arena` (empty if out of range).
+    fn slice(self, arena: &[Real]) -> &[Real] {
+        arena.get(self.offset..self.offset + self.len).unwrap_or(&[])
+    }
+}
 
 /// A single memory node representing an event in an entity's history.
 /// 
 /// Memory nodes encode experiences and decay over time.
 /// They are clustered into belief structures based on semantic similarity.
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug)]
 pub struct MemoryNode {
-    /// Event vector encoding the experience.
-    pub event: Vec<f32>,
+    /// Event vector encoding the experience, stored in the graph's arena.
+    pub event: EventHandle,
     /// Activation level (decays over time, ranges from 0 to 1).
-    pub activation: f32,
+    pub activation: Real,
     /// Timestamp when the node was created.
     pub timestamp: u64,
     /// Assigned belief cluster ID.
     pub cluster_id: Option<u32>,
+    /// Near-identical events folded into this node instead of being stored.
+    pub reinforcements: u32,
 }
 
 impl MemoryNode {
@@ -52,17 +140,18 @@ impl MemoryNode {
     /// Initialized with full activation (1.0) and no cluster assignment.
     /// 
     /// # Arguments
-    /// * `event` - Event vector encoding the experience
+    /// * `event` - Arena handle of the event vector encoding the experience
     /// * `timestamp` - Creation time
     /// 
     /// # Returns
     /// New MemoryNode
-    pub fn new(event: Vec<f32>, timestamp: u64) -> Self {
+    pub fn new(event: EventHandle, timestamp: u64) -> Self {
         MemoryNode {
             event,
             activation: 1.0,
             timestamp,
             cluster_id: None,
+            reinforcements: 0,
         }
     }
 }
@@ -78,9 +167,9 @@ pub struct BeliefCluster {
     /// Node indices belonging to this cluster.
     pub node_indices: Vec<usize>,
     /// Affective signal strength (-5 to +5).
-    pub affective_signal: f32,
+    pub affective_signal: Real,
     /// Cluster weight (higher for frequently activated clusters).
-    pub weight: f32,
+    pub weight: Real,
 }
 
 impl BeliefCluster {
@@ -101,16 +190,114 @@ impl BeliefCluster {
 /// - Edges: Associative links between memories
 /// - Clusters: Semantic belief structures
 /// 
+/// Event vectors live back to back in one arena rather than in a small
+/// allocation per node, so clustering scans contiguous memory. Snapshots
+/// store each node's event inline and the arena is rebuilt on load.
+/// 
 /// Provides methods for adding, clustering, and maintaining memories.
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Deserialize)]
+#[serde(from = "OwnedGraphData")]
 pub struct MemoryGraph {
     pub nodes: Vec<MemoryNode>,
     /// Edges as (source_idx, target_idx) pairs.
     pub edges: Vec<(usize, usize)>,
-    /// Belief clusters.
-    pub clusters: HashMap<u32, BeliefCluster>,
+    /// Belief clusters, by id (ordered, so sums over clusters are reproducible).
+    pub clusters: BTreeMap<u32, BeliefCluster>,
+    /// Cosine similarity to the matched belief cluster above which a new
+    /// event reinforces the cluster's closest node instead of being stored
+    /// (`None` stores every event).
+    pub dedup_threshold: Option<Real>,
     /// Next cluster ID to assign.
     next_cluster_id: u32,
+    /// Event vectors of all nodes, addressed by `EventHandle`.
+    events: Vec<Real>,
+    /// Running activation aggregates behind `activation_entropy`.
+    activation_sums: ActivationSums,
+}
+
+/// Serialized form of a memory node, with its event inline.
+#[derive(Serialize, Deserialize)]
+struct MemoryNodeData<E> {
+    event: E,
+    activation: Real,
+    timestamp: u64,
+    cluster_id: Option<u32>,
+    #[serde(default)]
+    reinforcements: u32,
+}
+
+/// Serialized form of a memory graph.
+/// 
+/// The cluster id counter is optional so that it can be recomputed as
+/// `max(cluster id) + 1` when absent or stale.
+#[derive(Serialize, Deserialize)]
+struct MemoryGraphData<N, E, C> {
+    nodes: N,
+    edges: E,
+    clusters: C,
+    #[serde(default)]
+    next_cluster_id: u32,
+    #[serde(default)]
+    dedup_threshold: Option<Real>,
+}
+
+/// Owned form read from snapshots.
+type OwnedGraphData =
+    MemoryGraphData<Vec<MemoryNodeData<Vec<Real>>>, Vec<(usize, usize)>, BTreeMap<u32, BeliefCluster>>;
+
+/// Serializes the nodes of a graph with their events read from the arena.
+struct NodesWithEvents<'a>(&'a MemoryGraph);
+
+impl Serialize for NodesWithEvents<'_> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let graph = self.0;
+        serializer.collect_seq(graph.nodes.iter().map(|node| MemoryNodeData {
+            event: graph.node_event(node),
+            activation: node.activation,
+            timestamp: node.timestamp,
+            cluster_id: node.cluster_id,
+            reinforcements: node.reinforcements,
+        }))
+    }
+}
+
+impl Serialize for MemoryGraph {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        MemoryGraphData {
+            nodes: NodesWithEvents(self),
+            edges: &self.edges,
+            clusters: &self.clusters,
+            next_cluster_id: self.next_cluster_id,
+            dedup_threshold: self.dedup_threshold,
+        }
+        .serialize(serializer)
+    }
+}
+
+impl From<OwnedGraphData> for MemoryGraph {
+    fn from(data: OwnedGraphData) -> Self {
+        let next_free = data.clusters.keys().max().map_or(0, |id| id + 1);
+        let mut graph = MemoryGraph {
+            nodes: Vec::with_capacity(data.nodes.len()),
+            edges: data.edges,
+            clusters: data.clusters,
+            next_cluster_id: data.next_cluster_id.max(next_free),
+            dedup_threshold: data.dedup_threshold,
+            events: Vec::with_capacity(data.nodes.iter().map(|node| node.event.len()).sum()),
+            activation_sums: ActivationSums::default(),
+        };
+        for node in data.nodes {
+            let handle = graph.store_event(&node.event);
+            graph.add_node(MemoryNode {
+                event: handle,
+                activation: node.activation,
+                timestamp: node.timestamp,
+                cluster_id: node.cluster_id,
+                reinforcements: node.reinforcements,
+            });
+        }
+        graph
+    }
 }
 
 impl MemoryGraph {
@@ -122,13 +309,64 @@ impl MemoryGraph {
         MemoryGraph {
             nodes: vec![],
             edges: vec![],
-            clusters: HashMap::new(),
+            clusters: BTreeMap::new(),
+            dedup_threshold: None,
             next_cluster_id: 0,
+            events: vec![],
+            activation_sums: ActivationSums::default(),
         }
     }
 
+    /// ID the next new belief cluster will get; IDs are never reused, so
+    /// clusters with IDs at or above an earlier value are newer.
+    pub fn next_cluster_id(&self) -> u32 {
+        self.next_cluster_id
+    }
+
+    /// Copy an event vector into the arena.
+    /// 
+    /// # Arguments
+    /// * `event` - Event vector
+    /// 
+    /// # Returns
+    /// Handle for a node of this graph
+    pub fn store_event(&mut self, event: &[Real]) -> EventHandle {
+        let handle = EventHandle {
+            offset: self.events.len(),
+            len: event.len(),
+        };
+        self.events.extend_from_slice(event);
+        handle
+    }
+
+    /// Event vector of a node (empty if the index is out of range).
+    pub fn event(&self, idx: usize) -> &[Real] {
+        self.nodes.get(idx).map_or(&[], |node| self.node_event(node))
+    }
+
+    /// Event vector behind a node's handle (empty if the handle is stale).
+    pub fn node_event(&self, node: &MemoryNode) -> &[Real] {
+        node.event.slice(&self.events)
+    }
+
+    /// Add a memory node for a new event, with full activation.
+    /// 
+    /// # Arguments
+    /// * `event` - Event vector encoding the experience
+    /// * `timestamp` - Creation time
+    /// 
+    /// # Returns
+    /// Index of the added node
+    pub fn add_event(&mut self, event: &[Real], timestamp: u64) -> usize {
+        let handle = self.store_event(event);
+        self.add_node(MemoryNode::new(handle, timestamp))
+    }
+
     /// Add a memory node.
     /// 
+    /// The node's event handle must come from [`MemoryGraph::store_event`]
+    /// on this graph.
+    /// 
     /// # Arguments
     /// * `node` - Memory node to add
     /// 
@@ -136,10 +374,24 @@ impl MemoryGraph {
     /// Index of the added node
     pub fn add_node(&mut self, node: MemoryNode) -> usize {
         let idx = self.nodes.len();
+        self.activation_sums.add(node.activation);
         self.nodes.push(node);
         idx
     }
 
+    /// Set the activation of a node, keeping the entropy aggregates current.
+    /// 
+    /// # Arguments
+    /// * `idx` - Node index (ignored if out of range)
+    /// * `activation` - New activation level
+    pub fn set_activation(&mut self, idx: usize, activation: Real) {
+        if let Some(node) = self.nodes.get_mut(idx) {
+            self.activation_sums.remove(node.activation);
+            self.activation_sums.add(activation);
+            node.activation = activation;
+        }
+    }
+
     /// Add an edge between two nodes.
     /// 
     /// Creates an associative link between memory nodes.
@@ -154,16 +406,98 @@ impl MemoryGraph {
         }
     }
 
+    /// Rewrite the event arena in node order, dropping regions no node
+    /// refers to any more.
+    /// 
+    /// Nodes removed from `nodes` leave their events behind until the
+    /// next compaction; this also runs automatically whenever the arena
+    /// holds more than twice the live event data.
+    pub fn compact_events(&mut self) {
+        let live = self.nodes.iter().map(|node| node.event.len).sum();
+        let mut events = Vec::with_capacity(live);
+        for node in &mut self.nodes {
+            let start = node.event.offset.min(self.events.len());
+            let end = (node.event.offset + node.event.len).min(self.events.len());
+            node.event = EventHandle {
+                offset: events.len(),
+                len: end - start,
+            };
+            events.extend_from_slice(&self.events[start..end]);
+        }
+        self.events = events;
+    }
+
     /// Decay all node activations.
     /// 
-    /// Implements forgetting by reducing activation levels.
+    /// Implements forgetting by reducing activation levels. The entropy
+    /// aggregates are scaled in closed form, and recomputed from scratch
+    /// every `ACTIVATION_RESYNC_INTERVAL` decays to bound drift.
     /// 
     /// # Arguments
     /// * `factor` - Decay factor (e.g., 0.99 for 1% decay per step)
-    pub fn decay(&mut self, factor: f32) {
+    pub fn decay(&mut self, factor: Real) {
         for node in &mut self.nodes {
             node.activation *= factor;
         }
+        self.activation_sums.scale(factor);
+        self.activation_sums.decays_since_sync += 1;
+        if self.activation_sums.decays_since_sync >= ACTIVATION_RESYNC_INTERVAL {
+            self.resync_activation_sums();
+        }
+    }
+
+    /// Shannon entropy of the activation distribution, in O(1).
+    /// 
+    /// With `S = Σa` and `T = Σ a·ln a` over the node activations, the
+    /// entropy of `p = a / S` is `ln S - T / S`. Both sums are maintained
+    /// incrementally; if `nodes` was edited directly (node count differs
+    /// from the tracked one) the exact value is computed instead. Direct
+    /// edits of activations are picked up at the next resynchronization.
+    /// 
+    /// # Returns
+    /// Entropy in nats, or 0 when the total activation is negligible
+    pub fn activation_entropy(&self) -> Real {
+        if self.activation_sums.nodes != self.nodes.len() {
+            return self.exact_activation_entropy();
+        }
+        self.activation_sums.entropy()
+    }
+
+    /// Activation entropy recomputed from every node (O(nodes)).
+    pub fn exact_activation_entropy(&self) -> Real {
+        ActivationSums::of(&self.nodes).entropy()
+    }
+
+    /// Recompute the entropy aggregates from the nodes.
+    /// 
+    /// Debug builds check that the incremental value had stayed close to
+    /// the exact one. Being a periodic full pass anyway, this also
+    /// compacts the event arena if pruned nodes left it half empty.
+    pub fn resync_activation_sums(&mut self) {
+        let live: usize = self.nodes.iter().map(|node| node.event.len).sum();
+        if self.events.len() > 2 * live {
+            self.compact_events();
+        }
+        let exact = ActivationSums::of(&self.nodes);
+        debug_assert!(
+            self.activation_sums.nodes != exact.nodes
+                || (self.activation_sums.entropy() - exact.entropy()).abs() <= 1e-3 * exact.entropy().max(1.0),
+            "activation entropy drifted: incremental {} vs exact {}",
+            self.activation_sums.entropy(),
+            exact.entropy()
+        );
+        self.activation_sums = exact;
+    }
+
+    /// Running activation sums, saved by checkpoints so a resumed run
+    /// continues from the same (not the freshly recomputed) values.
+    pub(crate) fn activation_sums(&self) -> &ActivationSums {
+        &self.activation_sums
+    }
+
+    /// Restore sums saved with [`MemoryGraph::activation_sums`].
+    pub(crate) fn restore_activation_sums(&mut self, sums: ActivationSums) {
+        self.activation_sums = sums;
     }
 
     /// Compute cosine similarity between two event vectors.
@@ -176,13 +510,22 @@ impl MemoryGraph {
     /// 
     /// # Returns
     /// Similarity in range [-1, 1], or 0 if either vector is empty
-    pub fn cosine_similarity(vec1: &[f32], vec2: &[f32]) -> f32 {
+    pub fn cosine_similarity(vec1: &[Real], vec2: &[Real]) -> Real {
         if vec1.is_empty() || vec2.is_empty() {
             return 0.0;
         }
-        let dot: f32 = vec1.iter().zip(vec2.iter()).map(|(a, b)| a * b).sum();
-        let norm1: f32 = vec1.iter().map(|x| x * x).sum::<f32>().sqrt();
-        let norm2: f32 = vec2.iter().map(|x| x * x).sum::<f32>().sqrt();
+        let (dot, norm1, norm2) = if vec1.len().min(vec2.len()) < UNROLLED_DOT_MIN_LEN {
+            // Short event vectors: plain loops beat the setup of array views
+            let dot: Real = vec1.iter().zip(vec2.iter()).map(|(a, b)| a * b).sum();
+            let norm1: Real = vec1.iter().map(|x| x * x).sum::<Real>().sqrt();
+            let norm2: Real = vec2.iter().map(|x| x * x).sum::<Real>().sqrt();
+            (dot, norm1, norm2)
+        } else {
+            // ndarray's dot is unrolled eightfold, so it vectorizes
+            let len = vec1.len().min(vec2.len());
+            let (a, b) = (ArrayView1::from(vec1), ArrayView1::from(vec2));
+            (a.slice(s![..len]).dot(&b.slice(s![..len])), a.dot(&a).sqrt(), b.dot(&b).sqrt())
+        };
 
         if norm1 > 0.0 && norm2 > 0.0 {
             dot / (norm1 * norm2)
@@ -201,11 +544,70 @@ impl MemoryGraph {
     /// * `event` - Event vector
     /// * `node_idx` - Index of the memory node
     /// * `tau` - Similarity threshold for cluster membership
-    pub fn cluster_event(&mut self, event: &[f32], node_idx: usize, tau: f32) {
+    pub fn cluster_event(&mut self, event: &[Real], node_idx: usize, tau: Real) {
+        let best_cluster_id = self.best_cluster(event, tau).map(|(id, _)| id);
+        self.assign_cluster(node_idx, best_cluster_id);
+    }
+
+    /// Add a memory node for a new event and cluster it.
+    /// 
+    /// Equivalent to [`MemoryGraph::add_event`] followed by
+    /// [`MemoryGraph::cluster_event`] with the node's own event. If the
+    /// event's similarity to the matched cluster exceeds `dedup_threshold`,
+    /// no node is added: the cluster member closest to the event is
+    /// reinforced instead (activation back to 1, one more reinforcement,
+    /// cluster weight +1).
+    /// 
+    /// # Arguments
+    /// * `event` - Event vector encoding the experience
+    /// * `timestamp` - Creation time
+    /// * `tau` - Similarity threshold for cluster membership
+    /// 
+    /// # Returns
+    /// Index of the added (or reinforced) node
+    pub fn add_clustered_event(&mut self, event: &[Real], timestamp: u64, tau: Real) -> usize {
+        let best = self.best_cluster(event, tau);
+        if let (Some((cluster_id, similarity)), Some(threshold)) = (best, self.dedup_threshold) {
+            if similarity > threshold {
+                if let Some(idx) = self.reinforce(cluster_id, event) {
+                    return idx;
+                }
+            }
+        }
+        let idx = self.add_event(event, timestamp);
+        self.assign_cluster(idx, best.map(|(id, _)| id));
+        idx
+    }
+
+    /// Reinforce the member of a cluster closest to `event`.
+    /// 
+    /// # Returns
+    /// Index of the reinforced node, or None if the cluster has no members
+    fn reinforce(&mut self, cluster_id: u32, event: &[Real]) -> Option<usize> {
+        let cluster = self.clusters.get(&cluster_id)?;
+        let idx = cluster
+            .node_indices
+            .iter()
+            .copied()
+            .filter(|&idx| idx < self.nodes.len())
+            .map(|idx| (idx, Self::cosine_similarity(event, self.event(idx))))
+            .max_by(|a, b| a.1.partial_cmp(&b.1).unwrap_or(std::cmp::Ordering::Equal))?
+            .0;
+
+        self.set_activation(idx, 1.0);
+        self.nodes[idx].reinforcements += 1;
+        if let Some(cluster) = self.clusters.get_mut(&cluster_id) {
+            cluster.weight += 1.0;
+        }
+        Some(idx)
+    }
+
+    /// Cluster whose members are on average most similar to `event`, with
+    /// that similarity, if it exceeds `tau`.
+    fn best_cluster(&self, event: &[Real], tau: Real) -> Option<(u32, Real)> {
         let mut best_cluster_id = None;
         let mut best_similarity = tau;
 
-        // Find best matching cluster
         for (cluster_id, cluster) in &self.clusters {
             if cluster.node_indices.is_empty() {
                 continue;
@@ -214,7 +616,7 @@ impl MemoryGraph {
             for &node_idx_in_cluster in &cluster.node_indices {
                 if let Some(node) = self.nodes.get(node_idx_in_cluster) {
                     total_similarity +=
-                        Self::cosine_similarity(event, &node.event) / cluster.node_indices.len() as f32;
+                        Self::cosine_similarity(event, self.node_event(node)) / cluster.node_indices.len() as Real;
                 }
             }
             if total_similarity > best_similarity {
@@ -222,9 +624,12 @@ impl MemoryGraph {
                 best_cluster_id = Some(*cluster_id);
             }
         }
+        best_cluster_id.map(|id| (id, best_similarity))
+    }
 
-        // Assign to existing cluster or create new one
-        let cluster_id = if let Some(cid) = best_cluster_id {
+    /// Add a node to an existing cluster, or to a new one if None.
+    fn assign_cluster(&mut self, node_idx: usize, cluster_id: Option<u32>) {
+        let cluster_id = if let Some(cid) = cluster_id {
             cid
         } else {
             let cid = self.next_cluster_id;
@@ -239,23 +644,370 @@ impl MemoryGraph {
         }
     }
 
+    /// Compute the prototype (mean event vector) of a belief cluster.
+    /// 
+    /// # Arguments
+    /// * `cluster_id` - Cluster to summarize
+    /// 
+    /// # Returns
+    /// Mean of the member event vectors, or None if the cluster is unknown or empty
+    pub fn cluster_prototype(&self, cluster_id: u32) -> Option<Vec<Real>> {
+        let cluster = self.clusters.get(&cluster_id)?;
+        let members: Vec<&MemoryNode> = cluster
+            .node_indices
+            .iter()
+            .filter_map(|&idx| self.nodes.get(idx))
+            .collect();
+        if members.is_empty() {
+            return None;
+        }
+
+        let dim = members.iter().map(|n| n.event.len).max().unwrap_or(0);
+        let mut prototype = vec![0.0; dim];
+        for node in &members {
+            for (p, e) in prototype.iter_mut().zip(self.node_event(node)) {
+                *p += e;
+            }
+        }
+        for p in &mut prototype {
+            *p /= members.len() as Real;
+        }
+        Some(prototype)
+    }
+
+    /// Id of the dominant (highest-weight) belief cluster.
+    /// 
+    /// Ties are broken by the lower cluster id.
+    pub fn dominant_cluster(&self) -> Option<u32> {
+        self.clusters
+            .values()
+            .max_by(|a, b| {
+                a.weight
+                    .partial_cmp(&b.weight)
+                    .unwrap_or(std::cmp::Ordering::Equal)
+                    .then(b.id.cmp(&a.id))
+            })
+            .map(|c| c.id)
+    }
+
+    /// Merge the belief clusters of another graph into this one.
+    /// 
+    /// Each cluster of `other` is matched against the prototypes of this
+    /// graph's clusters. If the best cosine similarity exceeds `tau`, the
+    /// foreign nodes join that cluster and its weight and affective signal
+    /// move toward the foreign values by `blend_factor`. Otherwise a new
+    /// cluster is created whose weight and affective signal are the foreign
+    /// values scaled by `blend_factor`, unless none of its memories is new.
+    /// 
+    /// Imported nodes carry their activation scaled by `blend_factor`, and
+    /// edges between imported nodes are preserved. Nodes already present
+    /// anywhere in this graph (same event and timestamp) are not imported
+    /// again: they keep their cluster and take the higher of the two
+    /// activations. Merging the same source repeatedly, or the same memory
+    /// through different sources, therefore never grows the graph beyond
+    /// its distinct memories.
+    /// 
+    /// # Arguments
+    /// * `other` - Source memory graph
+    /// * `blend_factor` - Influence of the source, clamped to [0, 1]
+    /// * `tau` - Prototype similarity threshold for joining an existing cluster
+    pub fn merge_from(&mut self, other: &MemoryGraph, blend_factor: Real, tau: Real) {
+        let blend = blend_factor.clamp(0.0, 1.0);
+        let mut index_map: HashMap<usize, usize> = HashMap::new();
+        // Every node of this graph by timestamp, for deduplication
+        let mut by_timestamp: HashMap<u64, Vec<usize>> = HashMap::new();
+        for (idx, node) in self.nodes.iter().enumerate() {
+            by_timestamp.entry(node.timestamp).or_default().push(idx);
+        }
+
+        let mut other_ids: Vec<u32> = other.clusters.keys().copied().collect();
+        other_ids.sort_unstable();
+
+        for other_id in other_ids {
+            let other_cluster = &other.clusters[&other_id];
+            let Some(other_prototype) = other.cluster_prototype(other_id) else {
+                continue;
+            };
+
+            // Find closest matching local cluster by prototype similarity
+            let mut best_cluster_id = None;
+            let mut best_similarity = tau;
+            for &cluster_id in self.clusters.keys() {
+                if let Some(prototype) = self.cluster_prototype(cluster_id) {
+                    let similarity = Self::cosine_similarity(&other_prototype, &prototype);
+                    if similarity > best_similarity {
+                        best_similarity = similarity;
+                        best_cluster_id = Some(cluster_id);
+                    }
+                }
+            }
+
+            let target_id = match best_cluster_id {
+                Some(cid) => {
+                    if let Some(cluster) = self.clusters.get_mut(&cid) {
+                        cluster.weight += (other_cluster.weight - cluster.weight) * blend;
+                        cluster.affective_signal +=
+                            (other_cluster.affective_signal - cluster.affective_signal) * blend;
+                    }
+                    cid
+                }
+                None => {
+                    let cid = self.next_cluster_id;
+                    self.next_cluster_id += 1;
+                    let mut cluster = BeliefCluster::new(cid);
+                    cluster.weight = other_cluster.weight * blend;
+                    cluster.affective_signal = other_cluster.affective_signal * blend;
+                    self.clusters.insert(cid, cluster);
+                    cid
+                }
+            };
+
+            for &other_idx in &other_cluster.node_indices {
+                let Some(other_node) = other.nodes.get(other_idx) else {
+                    continue;
+                };
+
+                let same_time = by_timestamp.entry(other_node.timestamp).or_default();
+                let existing = same_time
+                    .iter()
+                    .copied()
+                    .find(|&idx| self.node_event(&self.nodes[idx]) == other.node_event(other_node));
+
+                let idx = match existing {
+                    Some(idx) => {
+                        let activation = self.nodes[idx].activation.max(other_node.activation * blend);
+                        self.set_activation(idx, activation);
+                        idx
+                    }
+                    None => {
+                        let mut node = other_node.clone();
+                        node.event = self.store_event(other.node_event(other_node));
+                        node.activation *= blend;
+                        node.cluster_id = Some(target_id);
+                        let idx = self.add_node(node);
+                        same_time.push(idx);
+                        if let Some(cluster) = self.clusters.get_mut(&target_id) {
+                            cluster.node_indices.push(idx);
+                        }
+                        idx
+                    }
+                };
+                index_map.insert(other_idx, idx);
+            }
+
+            // A new cluster whose memories were all known already stays out
+            if best_cluster_id.is_none() && self.clusters[&target_id].node_indices.is_empty() {
+                self.clusters.remove(&target_id);
+                self.next_cluster_id -= 1;
+            }
+        }
+
+        // Carry over associative links between imported nodes
+        for &(src, dst) in &other.edges {
+            if let (Some(&s), Some(&d)) = (index_map.get(&src), index_map.get(&dst)) {
+                if !self.edges.contains(&(s, d)) {
+                    self.add_edge(s, d);
+                }
+            }
+        }
+    }
+
+    /// Count structural motifs in the memory graph.
+    /// 
+    /// For `motif_size == 3`, enumerates every connected induced subgraph
+    /// of three nodes (triad) and classifies it into one of the 13 connected
+    /// directed triad types, labelled with MAN codes (e.g. `021C` chain,
+    /// `030C` cycle, `021D` fan-out, `300` fully mutual). Self-loops and
+    /// duplicate edges are ignored. Other motif sizes are not supported and
+    /// yield an empty map.
+    /// 
+    /// # Arguments
+    /// * `motif_size` - Number of nodes per motif (only 3 is supported)
+    /// 
+    /// # Returns
+    /// Count per motif label (types with no occurrence are omitted)
+    pub fn motif_detection(&self, motif_size: usize) -> HashMap<String, usize> {
+        let mut counts = HashMap::new();
+        if motif_size != 3 {
+            return counts;
+        }
+
+        let directed: HashSet<(usize, usize)> = self
+            .edges
+            .iter()
+            .copied()
+            .filter(|(s, d)| s != d)
+            .collect();
+        let mut neighbors: HashMap<usize, Vec<usize>> = HashMap::new();
+        for &(s, d) in &directed {
+            neighbors.entry(s).or_default().push(d);
+            neighbors.entry(d).or_default().push(s);
+        }
+        for list in neighbors.values_mut() {
+            list.sort_unstable();
+            list.dedup();
+        }
+        let adjacent = |a: usize, b: usize| directed.contains(&(a, b)) || directed.contains(&(b, a));
+
+        let labels: HashMap<u8, &str> = TRIAD_TYPES
+            .iter()
+            .map(|(label, edges)| (triad_code(|a, b| edges.contains(&(a, b))), *label))
+            .collect();
+
+        // Every connected triad has a center adjacent to both other nodes.
+        // Triangles have three centers and are counted only from the smallest.
+        for (&center, list) in &neighbors {
+            for (i, &u) in list.iter().enumerate() {
+                for &w in &list[i + 1..] {
+                    if adjacent(u, w) && (center > u || center > w) {
+                        continue;
+                    }
+                    let nodes = [center, u, w];
+                    let code = triad_code(|a, b| directed.contains(&(nodes[a], nodes[b])));
+                    if let Some(label) = labels.get(&code) {
+                        *counts.entry(label.to_string()).or_insert(0) += 1;
+                    }
+                }
+            }
+        }
+        counts
+    }
+
+    /// Memory input of the state update: affective signals weighted by activation.
+    /// 
+    /// Each non-empty belief cluster, in id order, adds its affective signal
+    /// times its share of the softmax over cluster activations to one
+    /// component (the k-th cluster to component `k mod len`). The shares
+    /// sum to at most one, so every component stays within the affective
+    /// range. An empty graph gives all zeros.
+    /// 
+    /// # Arguments
+    /// * `input` - Buffer overwritten with the input (state memory dimension)
+    pub fn memory_input_into(&self, input: &mut [Real]) {
+        input.fill(0.0);
+        let log_partition = self.log_partition();
+        if input.is_empty() || log_partition == Real::NEG_INFINITY {
+            return;
+        }
+        let len = input.len();
+        let clusters = self.clusters.values().filter(|cluster| !cluster.node_indices.is_empty());
+        for (k, cluster) in clusters.enumerate() {
+            let share = (self.cluster_activation(cluster) - log_partition).exp();
+            input[k % len] += cluster.affective_signal * share;
+        }
+    }
+
+    /// Summed activation of a cluster's member nodes.
+    fn cluster_activation(&self, cluster: &BeliefCluster) -> Real {
+        cluster
+            .node_indices
+            .iter()
+            .filter_map(|&idx| self.nodes.get(idx))
+            .map(|node| node.activation)
+            .sum()
+    }
+
+    /// Log normalizer `ln Σ exp(a)` of the softmax over cluster activations,
+    /// with unclustered nodes as singletons (`-inf` for an empty graph).
+    fn log_partition(&self) -> Real {
+        let logits = || {
+            self.clusters
+                .values()
+                .filter(|cluster| !cluster.node_indices.is_empty())
+                .map(|cluster| self.cluster_activation(cluster))
+                .chain(self.nodes.iter().filter(|node| node.cluster_id.is_none()).map(|node| node.activation))
+        };
+        let max = logits().fold(Real::NEG_INFINITY, Real::max);
+        if max == Real::NEG_INFINITY {
+            return max;
+        }
+        max + logits().map(|a| (a - max).exp()).sum::<Real>().ln()
+    }
+
+    /// Surprisal of an event whose cluster (or singleton) has activation
+    /// `logit`, given the normalizer from `log_partition`.
+    fn surprisal(logit: Real, log_partition: Real) -> Real {
+        (log_partition - logit).max(0.0)
+    }
+
+    /// Shannon information content (surprisal) of one memory node.
+    /// 
+    /// See the module docs for the probability model.
+    /// 
+    /// # Arguments
+    /// * `node_idx` - Index of the node
+    /// 
+    /// # Returns
+    /// `−ln P(event)` in nats, or 0 if the index is out of range
+    pub fn information_content(&self, node_idx: usize) -> Real {
+        let Some(node) = self.nodes.get(node_idx) else {
+            return 0.0;
+        };
+        let logit = node
+            .cluster_id
+            .and_then(|id| self.clusters.get(&id))
+            .map(|cluster| self.cluster_activation(cluster))
+            .unwrap_or(node.activation);
+        Self::surprisal(logit, self.log_partition())
+    }
+
+    /// Mean information content over all nodes (0 for an empty graph).
+    /// 
+    /// Every member of a cluster has the same surprisal, so this visits
+    /// each cluster once and allocates nothing (it runs every step).
+    pub fn mean_information_content(&self) -> Real {
+        if self.nodes.is_empty() {
+            return 0.0;
+        }
+        let log_partition = self.log_partition();
+        let clustered: Real = self
+            .clusters
+            .values()
+            .map(|cluster| {
+                let members = cluster.node_indices.iter().filter(|&&idx| idx < self.nodes.len()).count();
+                members as Real * Self::surprisal(self.cluster_activation(cluster), log_partition)
+            })
+            .sum();
+        let singletons: Real = self
+            .nodes
+            .iter()
+            .filter(|node| node.cluster_id.is_none())
+            .map(|node| Self::surprisal(node.activation, log_partition))
+            .sum();
+        (clustered + singletons) / self.nodes.len() as Real
+    }
+
+    /// Largest information content of any node (0 for an empty graph).
+    pub fn max_information_content(&self) -> Real {
+        let log_partition = self.log_partition();
+        self.clusters
+            .values()
+            .filter(|cluster| !cluster.node_indices.is_empty())
+            .map(|cluster| self.cluster_activation(cluster))
+            .chain(self.nodes.iter().filter(|node| node.cluster_id.is_none()).map(|node| node.activation))
+            .map(|logit| Self::surprisal(logit, log_partition))
+            .fold(0.0, Real::max)
+    }
+
     /// Update affective signals for all clusters
     pub fn update_affective_signals(&mut self) {
+        let (nodes, events) = (&self.nodes, &self.events);
         for cluster in self.clusters.values_mut() {
             let mut signal = 0.0;
             let mut count = 0;
 
             for &node_idx in &cluster.node_indices {
-                if let Some(node) = self.nodes.get(node_idx) {
+                if let Some(node) = nodes.get(node_idx) {
                     if node.activation > 0.01 {
                         // Compute valence from event (simplified: assuming raw event first element encodes valence)
-                        let valence = if node.event.is_empty() {
+                        let event = node.event.slice(events);
+                        let valence = if event.is_empty() {
                             0.0
                         } else {
                             // Map event[0] to [-1, 0, +1] valence
-                            if node.event[0] > 0.5 {
+                            if event[0] > 0.5 {
                                 1.0
-                            } else if node.event[0] < -0.5 {
+                            } else if event[0] < -0.5 {
                                 -1.0
                             } else {
                                 0.0
@@ -267,7 +1019,7 @@ impl MemoryGraph {
                 }
             }
 
-            cluster.affective_signal = if count > 0 { signal / count as f32 } else { 0.0 };
+            cluster.affective_signal = if count > 0 { signal / count as Real } else { 0.0 };
         }
     }
 }
@@ -277,3 +1029,69 @@ impl Default for MemoryGraph {
         Self::new()
     }
 }
+
+/// Running sums over node activations: `Σa` and `Σ a·ln a`.
+/// 
+/// Kept in f64 so the closed-form updates add little error of their own.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub(crate) struct ActivationSums {
+    total: f64,
+    weighted_log: f64,
+    /// Node count the sums cover
+    nodes: usize,
+    decays_since_sync: u32,
+}
+
+impl ActivationSums {
+    /// Exact sums over `nodes`.
+    fn of(nodes: &[MemoryNode]) -> Self {
+        let mut sums = ActivationSums::default();
+        for node in nodes {
+            sums.add(node.activation);
+        }
+        sums
+    }
+
+    fn add(&mut self, activation: Real) {
+        let a = to_f64(activation).max(0.0);
+        self.total += a;
+        self.weighted_log += a_ln_a(a);
+        self.nodes += 1;
+    }
+
+    fn remove(&mut self, activation: Real) {
+        let a = to_f64(activation).max(0.0);
+        self.total -= a;
+        self.weighted_log -= a_ln_a(a);
+        self.nodes -= 1;
+    }
+
+    /// Scale every activation by `factor`:
+    /// `Σ(ca) = cS` and `Σ ca·ln(ca) = c(T + S ln c)`.
+    fn scale(&mut self, factor: Real) {
+        let c = to_f64(factor);
+        if c > 0.0 {
+            self.weighted_log = c * (self.weighted_log + self.total * c.ln());
+            self.total *= c;
+        } else {
+            self.total = 0.0;
+            self.weighted_log = 0.0;
+        }
+    }
+
+    fn entropy(&self) -> Real {
+        if self.total <= 1e-6 {
+            return 0.0;
+        }
+        from_f64((self.total.ln() - self.weighted_log / self.total).max(0.0))
+    }
+}
+
+/// `a·ln a`, with the limit 0 at `a = 0`.
+fn a_ln_a(a: f64) -> f64 {
+    if a > 0.0 {
+        a * a.ln()
+    } else {
+        0.0
+    }
+}
@@ -5,7 +5,9 @@
 //!
 //! ## The 7 Consciousness Metrics
 //!
-//! 1. **Attention Entropy** (≥2.0): Shannon entropy of memory activation
+//! 1. **Attention Entropy** (≥2.0): Shannon entropy of memory activation,
+//!    blended with the mean information content of memory nodes
+//!    (weight `INFORMATION_CONTENT_WEIGHT`)
 //!    - Measures diversity of awareness across memory space
 //!    - Higher = more distributed attention
 //!
@@ -33,6 +35,19 @@
 //!    - Provides context on subjective experience quality
 //!    - Not pass/fail, but informative for system health
 //!
+//! ## Auxiliary Metrics
+//!
+//! **Phase Synchrony** (mean pairwise velocity phase coherence) is recorded
+//! alongside the criteria but does not take part in the determination. It
+//! needs motion history, so it is filled in by the simulation rather than
+//! by `Metrics::compute`.
+//!
+//! **Mean Energy** (population-mean metabolic reserve) is likewise
+//! informative only.
+//!
+//! **Role Stability** (Jaccard similarity of consecutive leader sets) is
+//! filled in by the simulation when role dynamics are enabled.
+//!
 //! ## Consciousness Determination
 //!
 //! ALL metrics (except Average Essence) must meet their thresholds for
@@ -42,152 +57,187 @@
 //! ## Author
 //! Ayomide I. Daniels (Morningstar)
 
-use crate::entities::EntityPool;
+use crate::real::Real;
+use crate::entities::{Entity, EntityId, EntityPool};
+use crate::roles::Role;
+use rayon::prelude::*;
 use std::collections::HashMap;
+
+/// Weight of mean memory information content in attention entropy
+/// (the rest is the Shannon entropy of activations).
+pub const INFORMATION_CONTENT_WEIGHT: Real = 0.25;
+
+/// Population size from which [`Metrics::compute_for`] evaluates entities
+/// in parallel; smaller populations don't repay the thread overhead.
+pub const PARALLEL_MIN_ENTITIES: usize = 512;
 use serde::{Serialize, Deserialize};
 
+/// One entity's share of the population metrics.
+///
+/// Computing these is the per-entity work of [`Metrics::compute_with`];
+/// the metrics themselves are sequential reductions over them.
+struct EntityContribution {
+    /// Activation entropy blended with memory information content (0 without memory)
+    attention_entropy: Real,
+    /// Standard deviation of cluster affective signals (0 with fewer than two clusters)
+    memory_diversity: Real,
+    speed: Real,
+    state_norm: Real,
+    cluster_count: usize,
+    /// Sum of absolute cluster affective signals
+    affective_total: Real,
+    essence: Real,
+    energy: Real,
+}
+
+impl EntityContribution {
+    /// Evaluate one entity.
+    ///
+    /// Attention entropy is the Shannon entropy of normalized memory
+    /// activations (kept incrementally, see `MemoryGraph::activation_entropy`),
+    /// blended with the mean information content of the memory nodes
+    /// (`INFORMATION_CONTENT_WEIGHT`).
+    fn of(entity: &Entity) -> Self {
+        let graph = &entity.memory_graph;
+
+        let mut attention_entropy = 0.0;
+        if !graph.nodes.is_empty() {
+            let entropy = graph.activation_entropy();
+            let surprisal = graph.mean_information_content();
+            attention_entropy = (1.0 - INFORMATION_CONTENT_WEIGHT) * entropy + INFORMATION_CONTENT_WEIGHT * surprisal;
+        }
+
+        let clusters = &graph.clusters;
+        let affective_signals = || clusters.values().map(|c| c.affective_signal);
+        let mut memory_diversity = 0.0;
+        if clusters.len() >= 2 {
+            let mean: Real = affective_signals().sum::<Real>() / clusters.len() as Real;
+            let variance: Real = affective_signals()
+                .map(|s| (s - mean).powi(2))
+                .sum::<Real>()
+                / clusters.len() as Real;
+            memory_diversity = variance.sqrt();
+        }
+
+        EntityContribution {
+            attention_entropy,
+            memory_diversity,
+            speed: entity.velocity.norm(),
+            state_norm: entity.state.norm(),
+            cluster_count: clusters.len(),
+            affective_total: affective_signals().map(Real::abs).sum(),
+            essence: entity.essence.value,
+            energy: entity.energy,
+        }
+    }
+}
+
 /// Comprehensive metrics for consciousness evaluation.
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct Metrics {
     pub timestamp: u64,
-    pub attention_entropy: f32,
-    pub memory_diversity: f32,
-    pub velocity_stability: f32,
-    pub identity_coherence: f32,
-    pub cluster_stability: f32,
-    pub affective_strength: f32,
-    pub essence_trajectory: f32,
-    pub average_essence: f32,
+    pub attention_entropy: Real,
+    pub memory_diversity: Real,
+    pub velocity_stability: Real,
+    pub identity_coherence: Real,
+    pub cluster_stability: Real,
+    pub affective_strength: Real,
+    pub essence_trajectory: Real,
+    pub average_essence: Real,
+    /// Kuramoto order parameter of the velocity phases over the synchrony
+    /// window (set by the simulation; see `Simulation::kuramoto_order`).
+    #[serde(default)]
+    pub phase_synchrony: Real,
+    /// Mean energy reserve across entities.
+    #[serde(default)]
+    pub mean_energy: Real,
+    /// Jaccard similarity of consecutive leader sets (set by the simulation)
+    #[serde(default)]
+    pub role_stability: Real,
 }
 
 impl Metrics {
     /// Compute all metrics from entity pool.
     pub fn compute(entities: &EntityPool, timestamp: u64) -> Self {
-        let attention_entropy = Self::compute_attention_entropy(entities);
-        let memory_diversity = Self::compute_memory_diversity(entities);
-        let velocity_stability = Self::compute_velocity_stability(entities);
-        let identity_coherence = Self::compute_identity_coherence(entities);
-        let cluster_stability = Self::compute_cluster_stability(entities);
-        let affective_strength = Self::compute_affective_strength(entities);
-        let essence_trajectory = Self::compute_essence_trajectory(entities);
-        let average_essence = Self::compute_average_essence(entities);
+        let entities = entities.all_entities();
+        Self::compute_for(&entities, timestamp)
+    }
+
+    /// Compute all metrics over an arbitrary subset of entities.
+    /// 
+    /// Used for group breakdowns (e.g. per species) where only part of
+    /// the population should contribute. Populations of at least
+    /// `PARALLEL_MIN_ENTITIES` are evaluated in parallel.
+    pub fn compute_for(entities: &[&Entity], timestamp: u64) -> Self {
+        Self::compute_with(entities, timestamp, entities.len() >= PARALLEL_MIN_ENTITIES)
+    }
+
+    /// Compute all metrics, choosing serial or parallel evaluation.
+    /// 
+    /// The per-entity work runs on the rayon pool when `parallel` is set;
+    /// the contributions are then reduced sequentially in entity order, so
+    /// both paths give bit-identical results.
+    /// 
+    /// # Arguments
+    /// * `entities` - Entities to evaluate
+    /// * `timestamp` - Step stamped on the metrics
+    /// * `parallel` - Whether to spread the per-entity work over threads
+    pub fn compute_with(entities: &[&Entity], timestamp: u64, parallel: bool) -> Self {
+        let contributions: Vec<EntityContribution> = if parallel {
+            entities.par_iter().map(|entity| EntityContribution::of(entity)).collect()
+        } else {
+            entities.iter().map(|entity| EntityContribution::of(entity)).collect()
+        };
 
         Metrics {
             timestamp,
-            attention_entropy,
-            memory_diversity,
-            velocity_stability,
-            identity_coherence,
-            cluster_stability,
-            affective_strength,
-            essence_trajectory,
-            average_essence,
+            attention_entropy: Self::compute_attention_entropy(&contributions),
+            memory_diversity: Self::compute_memory_diversity(&contributions),
+            velocity_stability: Self::compute_velocity_stability(&contributions),
+            identity_coherence: Self::compute_identity_coherence(&contributions),
+            cluster_stability: Self::compute_cluster_stability(&contributions),
+            affective_strength: Self::compute_affective_strength(&contributions),
+            essence_trajectory: Self::compute_essence_trajectory(&contributions),
+            average_essence: Self::compute_average_essence(&contributions),
+            phase_synchrony: 0.0,
+            mean_energy: Self::compute_mean_energy(&contributions),
+            role_stability: 1.0,
         }
     }
 
-    /// Compute attention entropy: Shannon entropy over active memory nodes.
-    fn compute_attention_entropy(entities: &EntityPool) -> f32 {
-        let all_entities = entities.all_entities();
-        if all_entities.is_empty() {
+    /// Compute attention entropy: mean over entities of the blended
+    /// activation entropy (see [`EntityContribution::of`]).
+    fn compute_attention_entropy(contributions: &[EntityContribution]) -> Real {
+        if contributions.is_empty() {
             return 0.0;
         }
 
-        let mut total_entropy = 0.0;
-
-        for entity in &all_entities {
-            let activations: Vec<f32> = entity
-                .memory_graph
-                .nodes
-                .iter()
-                .map(|n| n.activation)
-                .collect();
-
-            if activations.is_empty() {
-                continue;
-            }
-
-            // Normalize activations to probabilities
-            let sum: f32 = activations.iter().sum();
-            if sum > 1e-6 {
-                let mut entropy = 0.0;
-                for a in activations {
-                    let p = a / sum;
-                    if p > 1e-6 {
-                        entropy -= p * p.ln();
-                    }
-                }
-                total_entropy += entropy;
-            }
-        }
-
-        if all_entities.is_empty() {
-            0.0
-        } else {
-            total_entropy / all_entities.len() as f32
-        }
+        let total_entropy: Real = contributions.iter().map(|c| c.attention_entropy).sum();
+        total_entropy / contributions.len() as Real
     }
 
     /// Compute memory diversity: variance in belief cluster affective signals.
-    fn compute_memory_diversity(entities: &EntityPool) -> f32 {
-        let all_entities = entities.all_entities();
-        if all_entities.is_empty() {
+    fn compute_memory_diversity(contributions: &[EntityContribution]) -> Real {
+        if contributions.is_empty() {
             return 0.0;
         }
 
-        let mut total_diversity = 0.0;
-
-        for entity in &all_entities {
-            let affective_signals: Vec<f32> = entity
-                .memory_graph
-                .clusters
-                .values()
-                .map(|c| c.affective_signal)
-                .collect();
-
-            if affective_signals.len() < 2 {
-                continue;
-            }
-
-            let mean: f32 = affective_signals.iter().sum::<f32>() / affective_signals.len() as f32;
-            let variance: f32 = affective_signals
-                .iter()
-                .map(|s| (s - mean).powi(2))
-                .sum::<f32>()
-                / affective_signals.len() as f32;
-
-            total_diversity += variance.sqrt();
-        }
-
-        if all_entities.is_empty() {
-            0.0
-        } else {
-            total_diversity / all_entities.len() as f32
-        }
+        let total_diversity: Real = contributions.iter().map(|c| c.memory_diversity).sum();
+        total_diversity / contributions.len() as Real
     }
 
     /// Compute velocity stability: inverse of velocity variance across entities.
-    fn compute_velocity_stability(entities: &EntityPool) -> f32 {
-        let all_entities = entities.all_entities();
-        if all_entities.is_empty() {
-            return 1.0;
-        }
-
-        let mut speeds = Vec::new();
-        for entity in all_entities {
-            let speed_sq: f32 = entity.velocity.iter().map(|v| v * v).sum();
-            speeds.push(speed_sq.sqrt());
-        }
-
-        if speeds.is_empty() {
+    fn compute_velocity_stability(contributions: &[EntityContribution]) -> Real {
+        if contributions.is_empty() {
             return 1.0;
         }
 
-        let mean: f32 = speeds.iter().sum::<f32>() / speeds.len() as f32;
-        let variance: f32 = speeds
-            .iter()
+        let speeds = || contributions.iter().map(|c| c.speed);
+        let mean: Real = speeds().sum::<Real>() / contributions.len() as Real;
+        let variance: Real = speeds()
             .map(|s| (s - mean).powi(2))
-            .sum::<f32>()
-            / speeds.len() as f32;
+            .sum::<Real>()
+            / contributions.len() as Real;
 
         let std_dev = variance.sqrt();
 
@@ -199,27 +249,17 @@ impl Metrics {
     }
 
     /// Compute identity coherence: state vector norm consistency.
-    fn compute_identity_coherence(entities: &EntityPool) -> f32 {
-        let all_entities = entities.all_entities();
-        if all_entities.is_empty() {
+    fn compute_identity_coherence(contributions: &[EntityContribution]) -> Real {
+        if contributions.is_empty() {
             return 0.0;
         }
 
-        let mut norms = Vec::new();
-        for entity in all_entities {
-            norms.push(entity.state.norm());
-        }
-
-        if norms.is_empty() {
-            return 0.0;
-        }
-
-        let mean: f32 = norms.iter().sum::<f32>() / norms.len() as f32;
-        let variance: f32 = norms
-            .iter()
+        let norms = || contributions.iter().map(|c| c.state_norm);
+        let mean: Real = norms().sum::<Real>() / contributions.len() as Real;
+        let variance: Real = norms()
             .map(|n| (n - mean).powi(2))
-            .sum::<f32>()
-            / norms.len() as f32;
+            .sum::<Real>()
+            / contributions.len() as Real;
 
         if mean > 1e-6 {
             1.0 / (1.0 + variance.sqrt() / mean)
@@ -229,76 +269,149 @@ impl Metrics {
     }
 
     /// Compute cluster stability: number of stable belief clusters.
-    fn compute_cluster_stability(entities: &EntityPool) -> f32 {
-        let all_entities = entities.all_entities();
-        if all_entities.is_empty() {
+    fn compute_cluster_stability(contributions: &[EntityContribution]) -> Real {
+        if contributions.is_empty() {
             return 0.0;
         }
 
-        let mut cluster_counts = Vec::new();
-        for entity in all_entities {
-            cluster_counts.push(entity.memory_graph.clusters.len() as f32);
-        }
-
-        let mean: f32 = cluster_counts.iter().sum::<f32>() / cluster_counts.len() as f32;
+        let total: Real = contributions.iter().map(|c| c.cluster_count as Real).sum();
+        let mean = total / contributions.len() as Real;
         mean / 10.0 // Normalize to [0, 1] assuming max ~10 clusters
     }
 
     /// Compute affective strength: average magnitude of affective signals.
-    fn compute_affective_strength(entities: &EntityPool) -> f32 {
-        let all_entities = entities.all_entities();
-        if all_entities.is_empty() {
-            return 0.0;
-        }
-
-        let mut total_strength = 0.0;
-        let mut count = 0;
-
-        for entity in all_entities {
-            for cluster in entity.memory_graph.clusters.values() {
-                total_strength += cluster.affective_signal.abs();
-                count += 1;
-            }
-        }
+    fn compute_affective_strength(contributions: &[EntityContribution]) -> Real {
+        let total_strength: Real = contributions.iter().map(|c| c.affective_total).sum();
+        let count: usize = contributions.iter().map(|c| c.cluster_count).sum();
 
         if count == 0 {
             0.0
         } else {
-            total_strength / count as f32
+            total_strength / count as Real
         }
     }
 
     /// Compute essence trajectory: stability of essence indices over time.
-    fn compute_essence_trajectory(entities: &EntityPool) -> f32 {
-        let all_entities = entities.all_entities();
-        if all_entities.is_empty() {
+    fn compute_essence_trajectory(contributions: &[EntityContribution]) -> Real {
+        if contributions.is_empty() {
             return 5.0; // Baseline
         }
 
-        let mut essences = Vec::new();
-        for entity in all_entities {
-            essences.push(entity.essence.value);
-        }
-
-        let mean: f32 = essences.iter().sum::<f32>() / essences.len() as f32;
-        mean
+        let sum: Real = contributions.iter().map(|c| c.essence).sum();
+        sum / contributions.len() as Real
     }
 
     /// Compute average essence: mean well-being across entities.
-    fn compute_average_essence(entities: &EntityPool) -> f32 {
-        let all_entities = entities.all_entities();
-        if all_entities.is_empty() {
+    fn compute_average_essence(contributions: &[EntityContribution]) -> Real {
+        if contributions.is_empty() {
             return 5.0;
         }
 
-        let sum: f32 = all_entities.iter().map(|e| e.essence.value).sum();
-        sum / all_entities.len() as f32
+        let sum: Real = contributions.iter().map(|c| c.essence).sum();
+        sum / contributions.len() as Real
+    }
+
+    /// Compute mean energy: average metabolic reserve across entities.
+    fn compute_mean_energy(contributions: &[EntityContribution]) -> Real {
+        if contributions.is_empty() {
+            return 0.0;
+        }
+
+        let sum: Real = contributions.iter().map(|c| c.energy).sum();
+        sum / contributions.len() as Real
+    }
+
+    /// Compute all metrics separately for each role group.
+    /// 
+    /// Reveals whether entities in different roles develop distinct
+    /// profiles. Groups come from [`EntityPool::partition_by_role`].
+    /// 
+    /// # Arguments
+    /// * `entities` - Entity pool
+    /// * `roles` - Role of each entity
+    /// * `timestamp` - Step stamped on the metrics
+    /// 
+    /// # Returns
+    /// Metrics of every role present in the pool
+    pub fn compute_by_role(
+        entities: &EntityPool,
+        roles: &HashMap<EntityId, Role>,
+        timestamp: u64,
+    ) -> HashMap<Role, Metrics> {
+        entities
+            .partition_by_role(roles)
+            .into_iter()
+            .map(|(role, members)| (role, Self::compute_for(&members, timestamp)))
+            .collect()
+    }
+
+    /// Field names of [`Metrics::core_vector`], in order.
+    pub const CORE_NAMES: [&'static str; 8] = [
+        "attention_entropy",
+        "memory_diversity",
+        "velocity_stability",
+        "identity_coherence",
+        "cluster_stability",
+        "affective_strength",
+        "essence_trajectory",
+        "average_essence",
+    ];
+
+    /// The eight primary consciousness metrics as a vector.
+    /// 
+    /// # Returns
+    /// `[attention_entropy, memory_diversity, velocity_stability,
+    /// identity_coherence, cluster_stability, affective_strength,
+    /// essence_trajectory, average_essence]`
+    pub fn core_vector(&self) -> [Real; 8] {
+        [
+            self.attention_entropy,
+            self.memory_diversity,
+            self.velocity_stability,
+            self.identity_coherence,
+            self.cluster_stability,
+            self.affective_strength,
+            self.essence_trajectory,
+            self.average_essence,
+        ]
+    }
+
+    /// Names of every metric field (core and auxiliary), in order.
+    pub const FIELD_NAMES: [&'static str; 11] = [
+        "attention_entropy",
+        "memory_diversity",
+        "velocity_stability",
+        "identity_coherence",
+        "cluster_stability",
+        "affective_strength",
+        "essence_trajectory",
+        "average_essence",
+        "phase_synchrony",
+        "mean_energy",
+        "role_stability",
+    ];
+
+    /// Values of [`Metrics::FIELD_NAMES`], in order.
+    pub fn field_values(&self) -> [Real; 11] {
+        [
+            self.attention_entropy,
+            self.memory_diversity,
+            self.velocity_stability,
+            self.identity_coherence,
+            self.cluster_stability,
+            self.affective_strength,
+            self.essence_trajectory,
+            self.average_essence,
+            self.phase_synchrony,
+            self.mean_energy,
+            self.role_stability,
+        ]
     }
 
     /// Return metrics as a HashMap for easy serialization.
-    pub fn to_map(&self) -> HashMap<String, f32> {
+    pub fn to_map(&self) -> HashMap<String, Real> {
         let mut map = HashMap::new();
-        map.insert("timestamp".to_string(), self.timestamp as f32);
+        map.insert("timestamp".to_string(), self.timestamp as Real);
         map.insert("attention_entropy".to_string(), self.attention_entropy);
         map.insert("memory_diversity".to_string(), self.memory_diversity);
         map.insert("velocity_stability".to_string(), self.velocity_stability);
@@ -307,6 +420,9 @@ impl Metrics {
         map.insert("affective_strength".to_string(), self.affective_strength);
         map.insert("essence_trajectory".to_string(), self.essence_trajectory);
         map.insert("average_essence".to_string(), self.average_essence);
+        map.insert("phase_synchrony".to_string(), self.phase_synchrony);
+        map.insert("mean_energy".to_string(), self.mean_energy);
+        map.insert("role_stability".to_string(), self.role_stability);
         map
     }
 }
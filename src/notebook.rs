@@ -0,0 +1,286 @@
+//! Notebook module: quick-look helpers for evcxr / Jupyter sessions.
+//!
+//! Everything here is plain text over the core types, so it never pulls in
+//! the GUI stack (use `default-features = false` in the notebook's
+//! `:dep` line). The intended flow:
+//!
+//! ```
+//! use synthetic_consciousness::SimulationConfig;
+//! use synthetic_consciousness::notebook::{metrics_table, quick_run, sparkline};
+//!
+//! let mut config = SimulationConfig::default_2d();
+//! config.simulation.num_entities = 5;
+//! config.simulation.num_steps = 20;
+//!
+//! let summary = quick_run(config)?;
+//! println!("{}", sparkline(&summary.metric_series("attention_entropy").unwrap()));
+//! println!("{}", metrics_table(&summary.metrics[summary.metrics.len() - 3..]));
+//! # Ok::<(), String>(())
+//! ```
+//!
+//! ## Rich Display
+//!
+//! With the `evcxr` feature, [`Metrics`], [`SimulationSummary`] and
+//! [`ConsciousnessAnalysis`] gain an `evcxr_display` method, which evcxr
+//! calls to render a cell's value as a small HTML table instead of its
+//! `Debug` output.
+//!
+//! ## Author
+//! Ayomide I. Daniels (Morningstar)
+
+use crate::real::Real;
+use crate::config::SimulationConfig;
+use crate::metrics::Metrics;
+use crate::results::ConsciousnessAnalysis;
+use crate::simulation::Simulation;
+use serde::Serialize;
+
+/// Block characters used by [`sparkline`], lowest first.
+pub const SPARK_LEVELS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+
+/// Condensed outcome of a finished run.
+#[derive(Clone, Debug, Serialize)]
+pub struct SimulationSummary {
+    /// Configured run name
+    pub name: String,
+    /// Steps executed
+    pub steps: u64,
+    /// Entities alive at the end
+    pub entities: usize,
+    /// SHA-256 fingerprint of the configuration
+    pub config_fingerprint: String,
+    /// Metrics of every step
+    pub metrics: Vec<Metrics>,
+    /// Consciousness verdict
+    pub analysis: ConsciousnessAnalysis,
+    /// Plain-English summary of the run
+    pub narrative: String,
+}
+
+impl SimulationSummary {
+    /// Summarize a simulation whose results have been finalized.
+    pub fn from_simulation(sim: &Simulation) -> Self {
+        SimulationSummary {
+            name: sim.config.metadata.name.clone(),
+            steps: sim.timestamp,
+            entities: sim.entities.all_entities().len(),
+            config_fingerprint: sim.results.config_fingerprint.clone(),
+            metrics: sim.metrics_history.clone(),
+            analysis: sim.results.consciousness_analysis.clone(),
+            narrative: sim.results.narrative_summary(),
+        }
+    }
+
+    /// Metrics of the last step, if any step ran.
+    pub fn final_metrics(&self) -> Option<&Metrics> {
+        self.metrics.last()
+    }
+
+    /// History of one metric (a name from [`Metrics::FIELD_NAMES`]).
+    ///
+    /// # Returns
+    /// One value per step, or None for an unknown metric name
+    pub fn metric_series(&self, name: &str) -> Option<Vec<Real>> {
+        let index = Metrics::FIELD_NAMES.iter().position(|field| *field == name)?;
+        Some(self.metrics.iter().map(|m| m.field_values()[index]).collect())
+    }
+}
+
+/// Run a configuration to completion and summarize it.
+///
+/// # Arguments
+/// * `config` - Configuration to run (`simulation.num_steps` steps)
+///
+/// # Returns
+/// The summary, or an error if the configuration is invalid
+pub fn quick_run(config: SimulationConfig) -> Result<SimulationSummary, String> {
+    let mut sim = Simulation::new(config)?;
+    sim.run();
+    sim.finalize_results();
+    Ok(SimulationSummary::from_simulation(&sim))
+}
+
+/// Render metrics as an aligned Markdown table, one row per entry.
+///
+/// Columns are the step followed by [`Metrics::FIELD_NAMES`]; pass a slice
+/// of the history (e.g. the last few steps) to keep the table short.
+///
+/// ```
+/// # use synthetic_consciousness::{Simulation, SimulationConfig};
+/// # use synthetic_consciousness::notebook::metrics_table;
+/// # let mut config = SimulationConfig::default_2d();
+/// # config.simulation.num_steps = 3;
+/// # let mut sim = Simulation::new(config).unwrap();
+/// # sim.run();
+/// let table = metrics_table(&sim.metrics_history);
+/// assert!(table.contains("| attention_entropy |"));
+/// assert_eq!(table.lines().count(), 2 + sim.metrics_history.len());
+/// ```
+pub fn metrics_table(metrics: &[Metrics]) -> String {
+    let header: Vec<String> = std::iter::once("step")
+        .chain(Metrics::FIELD_NAMES)
+        .map(str::to_string)
+        .collect();
+    let rows: Vec<Vec<String>> = metrics
+        .iter()
+        .map(|m| {
+            std::iter::once(m.timestamp.to_string())
+                .chain(m.field_values().iter().map(|v| format!("{:.4}", v)))
+                .collect()
+        })
+        .collect();
+
+    let widths: Vec<usize> = header
+        .iter()
+        .enumerate()
+        .map(|(i, name)| rows.iter().map(|row| row[i].len()).fold(name.len(), usize::max))
+        .collect();
+
+    // Every column is numeric, so cells are right-aligned
+    let format_row = |cells: &[String]| {
+        let padded: Vec<String> = cells
+            .iter()
+            .zip(&widths)
+            .map(|(cell, width)| format!("{:>width$}", cell, width = width))
+            .collect();
+        format!("| {} |", padded.join(" | "))
+    };
+
+    let mut table = vec![
+        format_row(&header),
+        format!("|{}|", widths.iter().map(|w| format!("{}:", "-".repeat(w + 1))).collect::<Vec<_>>().join("|")),
+    ];
+    table.extend(rows.iter().map(|row| format_row(row)));
+    table.join("\n")
+}
+
+/// Render a series as a one-line sparkline of Unicode block characters.
+///
+/// Values are scaled between the series minimum and maximum; a flat series
+/// renders at the lowest level and non-finite values as spaces.
+///
+/// ```
+/// # use synthetic_consciousness::notebook::sparkline;
+/// assert_eq!(sparkline(&[0.0, 0.5, 1.0]), "▁▅█");
+/// assert_eq!(sparkline(&[]), "");
+/// ```
+pub fn sparkline(values: &[Real]) -> String {
+    let finite = values.iter().copied().filter(|v| v.is_finite());
+    let min = finite.clone().fold(Real::INFINITY, Real::min);
+    let max = finite.fold(Real::NEG_INFINITY, Real::max);
+    let range = max - min;
+    let top = (SPARK_LEVELS.len() - 1) as Real;
+
+    values
+        .iter()
+        .map(|&v| {
+            if !v.is_finite() {
+                ' '
+            } else if range <= Real::EPSILON {
+                SPARK_LEVELS[0]
+            } else {
+                SPARK_LEVELS[(((v - min) / range) * top).round() as usize]
+            }
+        })
+        .collect()
+}
+
+/// Escape text for inclusion in HTML.
+#[cfg(feature = "evcxr")]
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+/// Render rows of cells as an HTML table with a header row.
+#[cfg(feature = "evcxr")]
+fn html_table(header: &[&str], rows: &[Vec<String>]) -> String {
+    let mut html = String::from("<table><tr>");
+    for name in header {
+        html.push_str(&format!("<th>{}</th>", escape_html(name)));
+    }
+    html.push_str("</tr>");
+    for row in rows {
+        html.push_str("<tr>");
+        for cell in row {
+            html.push_str(&format!("<td>{}</td>", escape_html(cell)));
+        }
+        html.push_str("</tr>");
+    }
+    html.push_str("</table>");
+    html
+}
+
+/// Print HTML in the form evcxr renders as a cell's output.
+#[cfg(feature = "evcxr")]
+fn emit_html(html: &str) {
+    println!("EVCXR_BEGIN_CONTENT text/html\n{}\nEVCXR_END_CONTENT", html);
+}
+
+#[cfg(feature = "evcxr")]
+impl Metrics {
+    /// Display as an HTML table of metric values (called by evcxr).
+    pub fn evcxr_display(&self) {
+        let rows: Vec<Vec<String>> = Metrics::FIELD_NAMES
+            .iter()
+            .zip(self.field_values())
+            .map(|(name, value)| vec![name.to_string(), format!("{:.4}", value)])
+            .collect();
+        emit_html(&html_table(&["metric", &format!("step {}", self.timestamp)], &rows));
+    }
+}
+
+#[cfg(feature = "evcxr")]
+impl ConsciousnessAnalysis {
+    /// Display as an HTML table of values against thresholds (called by evcxr).
+    pub fn evcxr_display(&self) {
+        let mut names: Vec<&String> = self.metric_thresholds.keys().collect();
+        names.sort();
+        let rows: Vec<Vec<String>> = names
+            .into_iter()
+            .map(|name| {
+                let value = self.metric_values.get(name).copied().unwrap_or(0.0);
+                let threshold = self.metric_thresholds[name];
+                vec![
+                    name.clone(),
+                    format!("{:.4}", value),
+                    format!("{:.4}", threshold),
+                    if value >= threshold { "✓" } else { "✗" }.to_string(),
+                ]
+            })
+            .collect();
+        emit_html(&format!(
+            "<p><b>Score {:.0}%</b> — consciousness {}</p>{}",
+            self.consciousness_score * 100.0,
+            if self.consciousness_achieved { "achieved" } else { "not achieved" },
+            html_table(&["metric", "value", "threshold", "passed"], &rows)
+        ));
+    }
+}
+
+#[cfg(feature = "evcxr")]
+impl SimulationSummary {
+    /// Display as an HTML table of final metrics with trend sparklines (called by evcxr).
+    pub fn evcxr_display(&self) {
+        let rows: Vec<Vec<String>> = Metrics::FIELD_NAMES
+            .iter()
+            .enumerate()
+            .map(|(i, name)| {
+                let series: Vec<Real> = self.metrics.iter().map(|m| m.field_values()[i]).collect();
+                let last = series.last().map(|v| format!("{:.4}", v)).unwrap_or_default();
+                vec![name.to_string(), last, sparkline(&series)]
+            })
+            .collect();
+        emit_html(&format!(
+            "<p><b>{}</b>: {} steps, {} entities, score {:.0}%</p><p>{}</p>{}",
+            escape_html(&self.name),
+            self.steps,
+            self.entities,
+            self.analysis.consciousness_score * 100.0,
+            escape_html(&self.narrative),
+            html_table(&["metric", "final", "trend"], &rows)
+        ));
+    }
+}
@@ -0,0 +1,273 @@
+//! Plugin module: external stimulus generators and step observers.
+//!
+//! Out-of-tree code extends a simulation through two object-safe traits:
+//! - [`StimulusSource`]: produces the environmental stimulus each entity
+//!   senses per step, replacing the simulation's sense model (by default
+//!   uniform noise; see [`crate::sensing`])
+//! - [`SimulationObserver`]: is called after every step and when results
+//!   are finalized, and can end the run early by returning
+//!   [`StepControl::Stop`]
+//!
+//! ## Registration
+//!
+//! Implementations are registered under a name with a constructor taking
+//! an opaque parameter table. Crates linked into the binary register at
+//! link time with [`register_stimulus_source!`] / [`register_observer!`]
+//! (collected by the `inventory` crate); [`PluginRegistry::with_registered`]
+//! gathers them. With the `unsafe-plugins` feature,
+//! [`PluginRegistry::load_library`] also loads a cdylib and calls its
+//! exported [`PLUGIN_ENTRY_SYMBOL`] function to register more plugins.
+//!
+//! ## Configuration
+//!
+//! ```toml
+//! [plugins.stimulus]
+//! name = "pulse"
+//! params = { period = 10, amplitude = 0.5 }
+//!
+//! [[plugins.observers]]
+//! name = "my-logger"
+//! ```
+//!
+//! `params` is passed to the constructor untouched. `Simulation::new`
+//! resolves names against the link-time registry; use
+//! `Simulation::with_plugins` to resolve against a registry with loaded
+//! libraries. Plugins are not carried into forks, so what-if analyses
+//! (sensitivity, counterfactuals) use the built-in stimulus.
+//!
+//! ## Author
+//! Ayomide I. Daniels (Morningstar)
+
+use crate::real::Real;
+use crate::entities::Entity;
+use crate::results::SimulationResults;
+use crate::simulation::Simulation;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+#[doc(hidden)]
+pub use inventory;
+
+/// Opaque plugin parameters, passed through from the configuration.
+pub type PluginParams = toml::Table;
+
+/// Source of per-entity environmental stimuli.
+pub trait StimulusSource: Send {
+    /// Stimulus sensed by `entity` at `step`.
+    ///
+    /// Entities are visited in id order each step.
+    ///
+    /// # Returns
+    /// A vector with one component per spatial dimension, or None to fall
+    /// back to the sense model for this entity (vectors of the wrong
+    /// length are also ignored)
+    fn stimulus(&mut self, entity: &Entity, step: u64) -> Option<Vec<Real>>;
+}
+
+/// What a run should do after an observer has seen a step.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum StepControl {
+    /// Keep stepping
+    #[default]
+    Continue,
+    /// End the run after this step (results can still be finalized)
+    Stop,
+}
+
+/// Callback invoked as a simulation progresses.
+pub trait SimulationObserver: Send {
+    /// Called at the end of every step (after metrics are recorded).
+    ///
+    /// `sim.timestamp` is the number of completed steps; the entities and
+    /// `sim.metrics_history` reflect the step just taken.
+    ///
+    /// # Returns
+    /// [`StepControl::Stop`] to end the run; every observer still sees the
+    /// step, and `run()` returns before the next one
+    fn on_step(&mut self, sim: &Simulation) -> StepControl;
+
+    /// Called once results are finalized.
+    fn on_finish(&mut self, _results: &SimulationResults) {}
+}
+
+/// Constructor of a named stimulus source.
+pub type StimulusConstructor = fn(&PluginParams) -> Result<Box<dyn StimulusSource>, String>;
+
+/// Constructor of a named observer.
+pub type ObserverConstructor = fn(&PluginParams) -> Result<Box<dyn SimulationObserver>, String>;
+
+/// Link-time registration of a stimulus source (see [`register_stimulus_source!`]).
+pub struct StimulusPlugin {
+    pub name: &'static str,
+    pub create: StimulusConstructor,
+}
+
+/// Link-time registration of an observer (see [`register_observer!`]).
+pub struct ObserverPlugin {
+    pub name: &'static str,
+    pub create: ObserverConstructor,
+}
+
+inventory::collect!(StimulusPlugin);
+inventory::collect!(ObserverPlugin);
+
+/// Register a stimulus source constructor under a name at link time.
+///
+/// ```ignore
+/// synthetic_consciousness::register_stimulus_source!("pulse", PulseStimulus::from_params);
+/// ```
+#[macro_export]
+macro_rules! register_stimulus_source {
+    ($name:expr, $create:expr) => {
+        $crate::plugin::inventory::submit! {
+            $crate::plugin::StimulusPlugin { name: $name, create: $create }
+        }
+    };
+}
+
+/// Register an observer constructor under a name at link time.
+#[macro_export]
+macro_rules! register_observer {
+    ($name:expr, $create:expr) => {
+        $crate::plugin::inventory::submit! {
+            $crate::plugin::ObserverPlugin { name: $name, create: $create }
+        }
+    };
+}
+
+/// Name of the function a plugin library exports to register its plugins.
+///
+/// The function must have the signature of [`PluginEntry`] and be declared
+/// `#[no_mangle] pub fn synthetic_consciousness_register(registry: &mut PluginRegistry)`.
+pub const PLUGIN_ENTRY_SYMBOL: &str = "synthetic_consciousness_register";
+
+/// Signature of the exported registration function of a plugin library.
+pub type PluginEntry = fn(&mut PluginRegistry);
+
+/// Named plugin constructors.
+#[derive(Clone, Default)]
+pub struct PluginRegistry {
+    stimulus_sources: HashMap<String, StimulusConstructor>,
+    observers: HashMap<String, ObserverConstructor>,
+}
+
+impl PluginRegistry {
+    /// Create an empty registry.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create a registry holding every plugin registered at link time.
+    pub fn with_registered() -> Self {
+        let mut registry = Self::new();
+        for plugin in inventory::iter::<StimulusPlugin> {
+            registry.register_stimulus_source(plugin.name, plugin.create);
+        }
+        for plugin in inventory::iter::<ObserverPlugin> {
+            registry.register_observer(plugin.name, plugin.create);
+        }
+        registry
+    }
+
+    /// Register (or replace) a stimulus source constructor.
+    pub fn register_stimulus_source(&mut self, name: &str, create: StimulusConstructor) {
+        self.stimulus_sources.insert(name.to_string(), create);
+    }
+
+    /// Register (or replace) an observer constructor.
+    pub fn register_observer(&mut self, name: &str, create: ObserverConstructor) {
+        self.observers.insert(name.to_string(), create);
+    }
+
+    /// Registered stimulus source names, sorted.
+    pub fn stimulus_source_names(&self) -> Vec<&str> {
+        let mut names: Vec<&str> = self.stimulus_sources.keys().map(String::as_str).collect();
+        names.sort_unstable();
+        names
+    }
+
+    /// Registered observer names, sorted.
+    pub fn observer_names(&self) -> Vec<&str> {
+        let mut names: Vec<&str> = self.observers.keys().map(String::as_str).collect();
+        names.sort_unstable();
+        names
+    }
+
+    /// Instantiate a stimulus source by name.
+    ///
+    /// # Returns
+    /// The source, or an error if the name is unknown or the constructor
+    /// rejects the parameters
+    pub fn create_stimulus_source(&self, spec: &PluginSpec) -> Result<Box<dyn StimulusSource>, String> {
+        let create = self
+            .stimulus_sources
+            .get(&spec.name)
+            .ok_or_else(|| format!("Unknown stimulus source plugin '{}'", spec.name))?;
+        create(&spec.params).map_err(|e| format!("Stimulus source plugin '{}': {}", spec.name, e))
+    }
+
+    /// Instantiate an observer by name.
+    ///
+    /// # Returns
+    /// The observer, or an error if the name is unknown or the constructor
+    /// rejects the parameters
+    pub fn create_observer(&self, spec: &PluginSpec) -> Result<Box<dyn SimulationObserver>, String> {
+        let create = self
+            .observers
+            .get(&spec.name)
+            .ok_or_else(|| format!("Unknown observer plugin '{}'", spec.name))?;
+        create(&spec.params).map_err(|e| format!("Observer plugin '{}': {}", spec.name, e))
+    }
+
+    /// Load a plugin library and let it register its plugins.
+    ///
+    /// The library stays loaded for the rest of the process, since objects
+    /// it creates may outlive the registry.
+    ///
+    /// # Safety
+    /// Runs arbitrary code from the library. Its [`PLUGIN_ENTRY_SYMBOL`]
+    /// function must have the [`PluginEntry`] signature, and the library
+    /// must be built by the same compiler against the same version of this
+    /// crate, since trait objects cross the boundary with the Rust ABI.
+    ///
+    /// # Returns
+    /// Error if the library cannot be loaded or lacks the entry symbol
+    #[cfg(feature = "unsafe-plugins")]
+    pub unsafe fn load_library(&mut self, path: &str) -> Result<(), String> {
+        let library = libloading::Library::new(path).map_err(|e| format!("Cannot load plugin '{}': {}", path, e))?;
+        let entry = *library
+            .get::<PluginEntry>(PLUGIN_ENTRY_SYMBOL.as_bytes())
+            .map_err(|e| format!("Plugin '{}' has no {} symbol: {}", path, PLUGIN_ENTRY_SYMBOL, e))?;
+        std::mem::forget(library);
+        entry(self);
+        Ok(())
+    }
+}
+
+/// A plugin selected by name, with its parameters.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct PluginSpec {
+    /// Registered plugin name.
+    pub name: String,
+    /// Parameters passed to the constructor.
+    #[serde(default)]
+    pub params: PluginParams,
+}
+
+/// Plugin selection section of the configuration.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct PluginsConfig {
+    /// Replaces the built-in stimulus noise if set.
+    #[serde(default)]
+    pub stimulus: Option<PluginSpec>,
+    /// Observers called after every step, in order.
+    #[serde(default)]
+    pub observers: Vec<PluginSpec>,
+}
+
+impl PluginsConfig {
+    /// Check that every selected plugin is named.
+    pub fn is_valid(&self) -> bool {
+        self.stimulus.iter().chain(&self.observers).all(|spec| !spec.name.is_empty())
+    }
+}
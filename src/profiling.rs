@@ -0,0 +1,147 @@
+//! Profiling module: wall-clock timing of the phases of a step.
+//!
+//! With `simulation.profile_phases` set, [`Simulation::step`](crate::Simulation::step)
+//! times each of its phases and accumulates the durations into
+//! [`PhaseTimings`] (count, total, min, mean and max per phase). The
+//! timings are returned by
+//! [`Simulation::phase_timings`](crate::Simulation::phase_timings), carried
+//! into the results by `finalize_results()` and printed as a table in the
+//! text report. Left unset, the only cost is one flag check per phase.
+//!
+//! On wasm32 in a browser there is no clock, so no phase is ever timed.
+//!
+//! ## Author
+//! Ayomide I. Daniels (Morningstar)
+
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::time::Duration;
+
+/// A phase of [`Simulation::step`](crate::Simulation::step), in the order run.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Phase {
+    Sense,
+    Attention,
+    State,
+    Affective,
+    Essence,
+    Decision,
+    Act,
+    Integrate,
+    Boundary,
+    Decay,
+    Metrics,
+    Reproduction,
+}
+
+impl Phase {
+    /// Every phase, in the order a step runs them.
+    pub const ALL: [Phase; 12] = [
+        Phase::Sense,
+        Phase::Attention,
+        Phase::State,
+        Phase::Affective,
+        Phase::Essence,
+        Phase::Decision,
+        Phase::Act,
+        Phase::Integrate,
+        Phase::Boundary,
+        Phase::Decay,
+        Phase::Metrics,
+        Phase::Reproduction,
+    ];
+
+    /// Short lowercase name used in reports.
+    pub fn name(self) -> &'static str {
+        match self {
+            Phase::Sense => "sense",
+            Phase::Attention => "attention",
+            Phase::State => "state",
+            Phase::Affective => "affective",
+            Phase::Essence => "essence",
+            Phase::Decision => "decision",
+            Phase::Act => "act",
+            Phase::Integrate => "integrate",
+            Phase::Boundary => "boundary",
+            Phase::Decay => "decay",
+            Phase::Metrics => "metrics",
+            Phase::Reproduction => "reproduction",
+        }
+    }
+}
+
+impl fmt::Display for Phase {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// Accumulated durations of one phase.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct PhaseStats {
+    /// Number of times the phase was timed
+    pub count: u64,
+    /// Sum of all durations
+    pub total: Duration,
+    /// Shortest duration (zero before the first)
+    pub min: Duration,
+    /// Longest duration
+    pub max: Duration,
+}
+
+impl PhaseStats {
+    /// Add one duration.
+    pub fn record(&mut self, duration: Duration) {
+        self.min = if self.count == 0 { duration } else { self.min.min(duration) };
+        self.max = self.max.max(duration);
+        self.total += duration;
+        self.count += 1;
+    }
+
+    /// Mean duration, zero before the first.
+    pub fn mean(&self) -> Duration {
+        match u32::try_from(self.count) {
+            Ok(0) => Duration::ZERO,
+            Ok(count) => self.total / count,
+            Err(_) => Duration::from_secs_f64(self.total.as_secs_f64() / self.count as f64),
+        }
+    }
+}
+
+/// Timings of every phase, indexed by [`Phase`].
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct PhaseTimings {
+    stats: [PhaseStats; 12],
+}
+
+impl PhaseTimings {
+    /// Add one duration of `phase`.
+    pub fn record(&mut self, phase: Phase, duration: Duration) {
+        self.stats[phase as usize].record(duration);
+    }
+
+    /// Statistics of one phase.
+    pub fn get(&self, phase: Phase) -> &PhaseStats {
+        &self.stats[phase as usize]
+    }
+
+    /// Every phase with its statistics, in step order.
+    pub fn iter(&self) -> impl Iterator<Item = (Phase, &PhaseStats)> {
+        Phase::ALL.into_iter().zip(&self.stats)
+    }
+
+    /// Time spent in all phases together.
+    pub fn total(&self) -> Duration {
+        self.stats.iter().map(|stats| stats.total).sum()
+    }
+
+    /// Whether no phase has been timed yet.
+    pub fn is_empty(&self) -> bool {
+        self.stats.iter().all(|stats| stats.count == 0)
+    }
+
+    /// Forget every duration recorded so far.
+    pub fn clear(&mut self) {
+        self.stats = Default::default();
+    }
+}
@@ -0,0 +1,379 @@
+//! Progress module: step-by-step progress callbacks for long runs.
+//!
+//! The library never prints progress on its own. A [`ProgressReporter`] set
+//! with [`Simulation::set_progress_reporter`](crate::Simulation::set_progress_reporter)
+//! is called after every step (from `run()`, `run_observed()` or a caller's
+//! own loop over `step()`) and once when results are finalized. Embedders
+//! supply their own reporter, or none to stay silent; for a single run,
+//! [`Simulation::run_with_progress`](crate::Simulation::run_with_progress)
+//! takes a closure instead.
+//!
+//! Every simulation times its steps with a [`ProgressTracker`], whether or
+//! not a reporter is set; [`Simulation::progress`](crate::Simulation::progress)
+//! returns the current [`ProgressSnapshot`] (percent complete, recent and
+//! average steps per second, elapsed time and an ETA from the recent rate).
+//!
+//! Two reporters are provided:
+//! - [`LogProgress`] reports through the `log` facade, so the application's
+//!   logger decides whether and where the lines appear.
+//! - [`TerminalProgress`] writes to stderr: one line rewritten in place on
+//!   a terminal, or a plain line every few seconds when redirected.
+//!
+//! ## Author
+//! Ayomide I. Daniels (Morningstar)
+
+use std::collections::VecDeque;
+use std::fmt;
+#[cfg(not(target_arch = "wasm32"))]
+use std::io::{self, IsTerminal, Write};
+use std::time::{Duration, Instant};
+
+/// Current time, or `None` where `Instant` is unavailable (wasm32 in a browser).
+pub(crate) fn now() -> Option<Instant> {
+    #[cfg(not(target_arch = "wasm32"))]
+    {
+        Some(Instant::now())
+    }
+    #[cfg(target_arch = "wasm32")]
+    {
+        None
+    }
+}
+
+/// Format a duration as `m:ss`, or `h:mm:ss` from an hour on.
+fn format_duration(duration: Duration) -> String {
+    let seconds = duration.as_secs();
+    let (hours, minutes, seconds) = (seconds / 3600, seconds / 60 % 60, seconds % 60);
+    if hours > 0 {
+        format!("{}:{:02}:{:02}", hours, minutes, seconds)
+    } else {
+        format!("{}:{:02}", minutes, seconds)
+    }
+}
+
+/// Progress of a run at one point in time.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ProgressSnapshot {
+    /// Steps completed so far
+    pub step: u64,
+    /// Configured number of steps (`simulation.num_steps`)
+    pub total_steps: u64,
+    /// Wall-clock time from the start of the first step to the end of the latest
+    pub elapsed: Duration,
+    /// Steps per second over the last [`ProgressTracker::RATE_WINDOW`] steps
+    pub steps_per_second: Option<f64>,
+    /// Steps per second since the first step
+    pub average_steps_per_second: Option<f64>,
+    /// Estimated time to `total_steps` at the recent rate
+    pub eta: Option<Duration>,
+}
+
+impl ProgressSnapshot {
+    /// Completed fraction of the configured steps, in [0, 1].
+    pub fn fraction(&self) -> f64 {
+        if self.total_steps == 0 {
+            1.0
+        } else {
+            (self.step as f64 / self.total_steps as f64).min(1.0)
+        }
+    }
+
+    /// Completed percentage of the configured steps, in [0, 100].
+    pub fn percent(&self) -> f64 {
+        100.0 * self.fraction()
+    }
+
+    /// Whether the configured number of steps has been reached.
+    pub fn is_complete(&self) -> bool {
+        self.step >= self.total_steps
+    }
+}
+
+/// One line such as `42.0% 420/1000 | 523 steps/s (avg 498) | elapsed 0:01 | ETA 0:02`.
+///
+/// Rates and the ETA are left out until they are known.
+impl fmt::Display for ProgressSnapshot {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:5.1}% {}/{}", self.percent(), self.step, self.total_steps)?;
+        if let Some(rate) = self.steps_per_second {
+            write!(f, " | {:.0} steps/s", rate)?;
+            if let Some(average) = self.average_steps_per_second {
+                write!(f, " (avg {:.0})", average)?;
+            }
+        }
+        write!(f, " | elapsed {}", format_duration(self.elapsed))?;
+        if let Some(eta) = self.eta {
+            write!(f, " | ETA {}", format_duration(eta))?;
+        }
+        Ok(())
+    }
+}
+
+/// Times the steps of a run.
+///
+/// The clock starts when the first step starts, so time spent building the
+/// simulation or waiting before `run()` is not counted. Recent speed is
+/// measured over a rolling window of the last [`Self::RATE_WINDOW`] steps,
+/// which lets the ETA follow runs that speed up or slow down (for example
+/// as memory graphs grow).
+#[derive(Clone, Debug, Default)]
+pub struct ProgressTracker {
+    /// When the first step started, and the step count at that time
+    started: Option<(Instant, u64)>,
+    /// Step count and end time of recent steps, oldest first
+    samples: VecDeque<(u64, Instant)>,
+    step: u64,
+    total_steps: u64,
+}
+
+impl ProgressTracker {
+    /// Number of recent steps the current rate and ETA are measured over.
+    pub const RATE_WINDOW: usize = 64;
+
+    /// Tracker that has not timed any step yet.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Note that a step is starting; the first call starts the clock.
+    ///
+    /// # Arguments
+    /// * `step` - Steps completed before this one
+    pub fn start_step(&mut self, step: u64) {
+        if self.started.is_none() {
+            if let Some(time) = now() {
+                self.started = Some((time, step));
+                self.samples.push_back((step, time));
+            }
+            self.step = step;
+        }
+    }
+
+    /// Note that a step has finished.
+    ///
+    /// # Arguments
+    /// * `step` - Steps completed so far
+    /// * `total_steps` - Configured number of steps
+    pub fn finish_step(&mut self, step: u64, total_steps: u64) {
+        self.step = step;
+        self.total_steps = total_steps;
+        if let Some(time) = now() {
+            self.samples.push_back((step, time));
+            while self.samples.len() > Self::RATE_WINDOW + 1 {
+                self.samples.pop_front();
+            }
+        }
+    }
+
+    /// Wall-clock time from the start of the first step to the end of the latest.
+    pub fn elapsed(&self) -> Duration {
+        match (self.started, self.samples.back()) {
+            (Some((start, _)), Some((_, last))) => last.duration_since(start),
+            _ => Duration::ZERO,
+        }
+    }
+
+    /// Current progress, rates and ETA.
+    pub fn snapshot(&self) -> ProgressSnapshot {
+        let rate = |from: (u64, Instant), to: (u64, Instant)| {
+            let seconds = to.1.duration_since(from.1).as_secs_f64();
+            (to.0 > from.0 && seconds > 0.0).then(|| (to.0 - from.0) as f64 / seconds)
+        };
+        let (steps_per_second, average_steps_per_second) = match (self.started, self.samples.front(), self.samples.back()) {
+            (Some((start, start_step)), Some(&first), Some(&last)) => {
+                (rate(first, last), rate((start_step, start), last))
+            }
+            _ => (None, None),
+        };
+        let eta = steps_per_second.map(|rate| {
+            let remaining = self.total_steps.saturating_sub(self.step);
+            Duration::from_secs_f64(remaining as f64 / rate)
+        });
+        ProgressSnapshot {
+            step: self.step,
+            total_steps: self.total_steps,
+            elapsed: self.elapsed(),
+            steps_per_second,
+            average_steps_per_second,
+            eta,
+        }
+    }
+}
+
+/// Receives progress of a running simulation.
+pub trait ProgressReporter: Send {
+    /// Called at the end of every step.
+    ///
+    /// # Arguments
+    /// * `step` - Steps completed so far
+    /// * `total_steps` - Configured number of steps (`simulation.num_steps`)
+    fn on_step(&mut self, step: u64, total_steps: u64);
+
+    /// Called at the end of every step with timing; forwards to `on_step` by default.
+    ///
+    /// # Arguments
+    /// * `progress` - Progress, rates and ETA after the step
+    fn on_progress(&mut self, progress: &ProgressSnapshot) {
+        self.on_step(progress.step, progress.total_steps);
+    }
+
+    /// Called once results are finalized.
+    ///
+    /// # Arguments
+    /// * `steps_run` - Steps completed in total
+    fn on_finish(&mut self, _steps_run: u64) {}
+}
+
+/// Reports `Step X/Y` at `debug` level every `interval` steps.
+///
+/// With timing available the line also carries the percentage, the recent
+/// rate and the ETA.
+#[derive(Clone, Debug)]
+pub struct LogProgress {
+    interval: u64,
+}
+
+impl LogProgress {
+    /// Default number of steps between progress lines.
+    pub const DEFAULT_INTERVAL: u64 = 100;
+
+    /// Reporter logging every `interval` steps (at least every step).
+    pub fn new(interval: u64) -> Self {
+        LogProgress {
+            interval: interval.max(1),
+        }
+    }
+
+    fn is_due(&self, step: u64, total_steps: u64) -> bool {
+        step.is_multiple_of(self.interval) || step == total_steps
+    }
+}
+
+impl Default for LogProgress {
+    fn default() -> Self {
+        Self::new(Self::DEFAULT_INTERVAL)
+    }
+}
+
+impl ProgressReporter for LogProgress {
+    fn on_step(&mut self, step: u64, total_steps: u64) {
+        if self.is_due(step, total_steps) {
+            log::debug!("Step {}/{}", step, total_steps);
+        }
+    }
+
+    fn on_progress(&mut self, progress: &ProgressSnapshot) {
+        if !self.is_due(progress.step, progress.total_steps) {
+            return;
+        }
+        match (progress.steps_per_second, progress.eta) {
+            (Some(rate), Some(eta)) => log::debug!(
+                "Step {}/{} ({:.1}%, {:.0} steps/s, ETA {})",
+                progress.step,
+                progress.total_steps,
+                progress.percent(),
+                rate,
+                format_duration(eta)
+            ),
+            _ => log::debug!("Step {}/{}", progress.step, progress.total_steps),
+        }
+    }
+
+    fn on_finish(&mut self, steps_run: u64) {
+        log::debug!("Finished after {} steps", steps_run);
+    }
+}
+
+/// Progress display on stderr for interactive runs.
+///
+/// On a terminal a single line is redrawn in place (at most every
+/// [`Self::REDRAW_INTERVAL`]) and ended when results are finalized. When
+/// stderr is redirected a plain line is written every
+/// [`Self::LINE_INTERVAL`] instead, so log files are not flooded.
+#[cfg(not(target_arch = "wasm32"))]
+#[derive(Clone, Debug)]
+pub struct TerminalProgress {
+    /// Redraw one line in place rather than print new ones
+    interactive: bool,
+    /// Minimum time between two updates
+    interval: Duration,
+    /// When the last update was written
+    last_shown: Option<Instant>,
+    /// Latest progress received, and whether it has been written
+    latest: Option<(ProgressSnapshot, bool)>,
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl TerminalProgress {
+    /// Time between redraws on a terminal.
+    pub const REDRAW_INTERVAL: Duration = Duration::from_secs(1);
+    /// Time between lines when stderr is not a terminal.
+    pub const LINE_INTERVAL: Duration = Duration::from_secs(10);
+
+    /// Reporter choosing its mode from whether stderr is a terminal.
+    pub fn new() -> Self {
+        Self::with_mode(io::stderr().is_terminal())
+    }
+
+    /// Reporter redrawing one line (`interactive`) or printing plain lines.
+    pub fn with_mode(interactive: bool) -> Self {
+        TerminalProgress {
+            interactive,
+            interval: if interactive { Self::REDRAW_INTERVAL } else { Self::LINE_INTERVAL },
+            last_shown: None,
+            latest: None,
+        }
+    }
+
+    /// Write `progress` as a new line or over the current one.
+    fn show(&mut self, progress: &ProgressSnapshot) {
+        let mut stderr = io::stderr().lock();
+        let _ = if self.interactive {
+            // Carriage return, then clear to the end of the line
+            write!(stderr, "\r{}\x1b[K", progress)
+        } else {
+            writeln!(stderr, "{}", progress)
+        };
+        let _ = stderr.flush();
+        self.last_shown = Some(Instant::now());
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl Default for TerminalProgress {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl ProgressReporter for TerminalProgress {
+    fn on_step(&mut self, step: u64, total_steps: u64) {
+        self.on_progress(&ProgressSnapshot {
+            step,
+            total_steps,
+            ..ProgressSnapshot::default()
+        });
+    }
+
+    fn on_progress(&mut self, progress: &ProgressSnapshot) {
+        let due = self.last_shown.is_none_or(|shown| shown.elapsed() >= self.interval);
+        let shown = due || (self.interactive && progress.is_complete());
+        if shown {
+            self.show(progress);
+        }
+        self.latest = Some((progress.clone(), shown));
+    }
+
+    fn on_finish(&mut self, _steps_run: u64) {
+        // Final elapsed time on the terminal line; the last state if it was skipped
+        if let Some((progress, shown)) = self.latest.take() {
+            if !shown || self.interactive {
+                self.show(&progress);
+            }
+            if self.interactive {
+                eprintln!();
+            }
+        }
+    }
+}
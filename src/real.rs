@@ -0,0 +1,57 @@
+//! Real module: the floating-point type of the model.
+//!
+//! Positions, velocities, state vectors, memory, essence and metrics are all
+//! [`Real`], which is `f32` by default to keep memory usage down. The `f64`
+//! feature switches the whole model to double precision, for very long runs
+//! with small `dt` where f32 accumulation error becomes visible, or for
+//! comparisons against f64 reference implementations.
+//!
+//! The C FFI, WebAssembly bindings and GUI keep `f32` at their boundaries
+//! regardless of this choice.
+//!
+//! ## Author
+//! Ayomide I. Daniels (Morningstar)
+
+/// Floating-point type of the model (`f32`, or `f64` with the `f64` feature).
+#[cfg(not(feature = "f64"))]
+pub type Real = f32;
+
+/// Floating-point type of the model (`f32`, or `f64` with the `f64` feature).
+#[cfg(feature = "f64")]
+pub type Real = f64;
+
+/// Mathematical constants of [`Real`].
+#[cfg(not(feature = "f64"))]
+pub use std::f32::consts;
+
+/// Mathematical constants of [`Real`].
+#[cfg(feature = "f64")]
+pub use std::f64::consts;
+
+/// Narrow a model value to `f32` (a no-op unless the `f64` feature is on).
+#[allow(clippy::unnecessary_cast)]
+#[inline]
+pub fn to_f32(value: Real) -> f32 {
+    value as f32
+}
+
+/// Widen an `f32` to a model value (a no-op unless the `f64` feature is on).
+#[allow(clippy::useless_conversion)]
+#[inline]
+pub fn from_f32(value: f32) -> Real {
+    Real::from(value)
+}
+
+/// Widen a model value to `f64` (a no-op with the `f64` feature).
+#[allow(clippy::useless_conversion)]
+#[inline]
+pub fn to_f64(value: Real) -> f64 {
+    f64::from(value)
+}
+
+/// Convert an `f64` to a model value (a no-op with the `f64` feature).
+#[allow(clippy::unnecessary_cast)]
+#[inline]
+pub fn from_f64(value: f64) -> Real {
+    value as Real
+}
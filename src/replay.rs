@@ -0,0 +1,378 @@
+//! Replay module: standalone interactive HTML replays of a simulation run.
+//!
+//! Every recorded step is embedded as a JSON array in a `<script>` tag of a
+//! single HTML file, together with a D3.js animation:
+//! - **Entities** are circles colored by essence (red = low, green = high)
+//! - **Attractions** are SVG paths whose width follows attention strength
+//! - **Belief clusters** are concentric rings, one per cluster (up to 5)
+//! - **Metrics** are sparklines with a marker at the current step
+//!
+//! A scrubber and play button replay any step. Positions are projected
+//! onto the x/y plane for 3D worlds.
+//!
+//! ## D3 Source
+//!
+//! The online replay loads D3 from a CDN and falls back to an embedded
+//! minimal D3 subset if the CDN is unreachable. The offline replay uses the
+//! embedded subset only, so it makes no network requests at all.
+//!
+//! ## Author
+//! Ayomide I. Daniels (Morningstar)
+
+use crate::simulation::Simulation;
+use serde_json::json;
+
+/// D3 bundle loaded by the online replay.
+pub const D3_CDN_URL: &str = "https://cdn.jsdelivr.net/npm/d3@7/dist/d3.min.js";
+
+/// Maximum number of cluster rings drawn around an entity.
+pub const MAX_CLUSTER_RINGS: usize = 5;
+
+/// Metrics drawn as sparklines, as (field name, label) pairs.
+const SPARKLINE_METRICS: [(&str, &str); 5] = [
+    ("attention_entropy", "Attention Entropy"),
+    ("memory_diversity", "Memory Diversity"),
+    ("identity_coherence", "Identity Coherence"),
+    ("affective_strength", "Affective Strength"),
+    ("average_essence", "Average Essence"),
+];
+
+/// Minimal D3 subset (select, data join, linear scale, line) used when the
+/// full library is unavailable. Only the calls made by `REPLAY_SCRIPT` are
+/// supported.
+const D3_SHIM: &str = r##"
+window.d3 = window.d3 || (function () {
+  var SVG_NS = "http://www.w3.org/2000/svg";
+  function create(tag, parent) {
+    var svg = tag === "svg" || (parent && parent.namespaceURI === SVG_NS);
+    return svg ? document.createElementNS(SVG_NS, tag) : document.createElement(tag);
+  }
+  function value(v, n, i) { return typeof v === "function" ? v(n.__data__, i) : v; }
+  function Selection(nodes, parent, selector) {
+    this.nodes = nodes; this.parent = parent; this.selector = selector;
+  }
+  Selection.prototype.node = function () { return this.nodes[0]; };
+  Selection.prototype.attr = function (k, v) {
+    this.nodes.forEach(function (n, i) { n.setAttribute(k, value(v, n, i)); });
+    return this;
+  };
+  Selection.prototype.style = function (k, v) {
+    this.nodes.forEach(function (n, i) { n.style.setProperty(k, value(v, n, i)); });
+    return this;
+  };
+  Selection.prototype.text = function (v) {
+    this.nodes.forEach(function (n, i) { n.textContent = value(v, n, i); });
+    return this;
+  };
+  Selection.prototype.property = function (k, v) {
+    if (arguments.length < 2) return this.nodes[0][k];
+    this.nodes.forEach(function (n, i) { n[k] = value(v, n, i); });
+    return this;
+  };
+  Selection.prototype.on = function (type, listener) {
+    this.nodes.forEach(function (n) {
+      n.addEventListener(type, function (event) { listener.call(n, event, n.__data__); });
+    });
+    return this;
+  };
+  Selection.prototype.append = function (tag) {
+    return new Selection(this.nodes.map(function (n) {
+      var child = create(tag, n);
+      child.__data__ = n.__data__;
+      n.appendChild(child);
+      return child;
+    }));
+  };
+  Selection.prototype.selectAll = function (selector) {
+    return new Selection([], this.nodes[0], selector);
+  };
+  Selection.prototype.data = function (data) { this.pending = data; return this; };
+  Selection.prototype.join = function (tag) {
+    var parent = this.parent, data = this.pending || [];
+    var existing = Array.prototype.slice.call(parent.querySelectorAll(":scope > " + this.selector));
+    var nodes = data.map(function (d, i) {
+      var n = existing[i] || parent.appendChild(create(tag, parent));
+      n.__data__ = d;
+      return n;
+    });
+    existing.slice(data.length).forEach(function (n) { parent.removeChild(n); });
+    return new Selection(nodes);
+  };
+  function scaleLinear() {
+    var d0 = 0, d1 = 1, r0 = 0, r1 = 1;
+    function scale(x) { return r0 + (x - d0) / ((d1 - d0) || 1) * (r1 - r0); }
+    scale.domain = function (d) { d0 = d[0]; d1 = d[1]; return scale; };
+    scale.range = function (r) { r0 = r[0]; r1 = r[1]; return scale; };
+    return scale;
+  }
+  function line() {
+    var fx = function (d) { return d[0]; }, fy = function (d) { return d[1]; };
+    function path(points) {
+      return points.map(function (p, i) {
+        return (i ? "L" : "M") + fx(p, i) + "," + fy(p, i);
+      }).join("");
+    }
+    path.x = function (f) { fx = f; return path; };
+    path.y = function (f) { fy = f; return path; };
+    return path;
+  }
+  return {
+    select: function (target) {
+      var node = typeof target === "string" ? document.querySelector(target) : target;
+      return new Selection([node]);
+    },
+    scaleLinear: scaleLinear,
+    line: line
+  };
+})();
+"##;
+
+/// Replay renderer; expects `d3` and a `#replay-data` JSON script tag.
+const REPLAY_SCRIPT: &str = r##"
+(function () {
+  var data = JSON.parse(document.getElementById("replay-data").textContent);
+  var frames = data.frames;
+  var size = 560, margin = 20;
+  var x = d3.scaleLinear().domain([0, data.bounds[0]]).range([margin, size - margin]);
+  var y = d3.scaleLinear().domain([0, data.bounds[1] || data.bounds[0]]).range([size - margin, margin]);
+
+  var world = d3.select("#world").append("svg").attr("width", size).attr("height", size);
+  world.append("rect").attr("width", size).attr("height", size).attr("fill", "#101820");
+  var links = world.append("g"), rings = world.append("g"), nodes = world.append("g");
+
+  var sparkWidth = 260, sparkHeight = 44;
+  var sparks = data.metrics.map(function (metric) {
+    var values = frames.map(function (f) { return f.metrics[metric.key]; });
+    var lo = Math.min.apply(null, values), hi = Math.max.apply(null, values);
+    var sx = d3.scaleLinear().domain([0, Math.max(frames.length - 1, 1)]).range([4, sparkWidth - 4]);
+    var sy = d3.scaleLinear().domain([lo, hi === lo ? lo + 1 : hi]).range([sparkHeight - 4, 4]);
+    var row = d3.select("#sparklines").append("div").attr("class", "spark");
+    row.append("div").attr("class", "spark-label").text(metric.label);
+    var svg = row.append("svg").attr("width", sparkWidth).attr("height", sparkHeight);
+    svg.append("path")
+      .attr("d", d3.line().x(function (d, i) { return sx(i); }).y(function (d) { return sy(d); })(values))
+      .attr("fill", "none").attr("stroke", "#4a90d9").attr("stroke-width", 1.5);
+    var marker = svg.append("circle").attr("r", 3).attr("fill", "#e94560");
+    var readout = row.append("div").attr("class", "spark-value");
+    return { values: values, sx: sx, sy: sy, marker: marker, readout: readout };
+  });
+
+  function essenceColor(e) {
+    var t = Math.max(0, Math.min(1, e / 10));
+    return "hsl(" + Math.round(t * 120) + ", 70%, 50%)";
+  }
+
+  function render(index) {
+    var frame = frames[index];
+    var positions = {};
+    frame.entities.forEach(function (e) { positions[e.id] = e.position; });
+
+    links.selectAll("path.link")
+      .data(frame.attractions.filter(function (a) { return positions[a[0]] && positions[a[1]]; }))
+      .join("path")
+      .attr("class", "link")
+      .attr("d", function (a) {
+        var p = positions[a[0]], q = positions[a[1]];
+        return "M" + x(p[0]) + "," + y(p[1]) + "L" + x(q[0]) + "," + y(q[1]);
+      })
+      .attr("stroke", "#8fb3d9")
+      .attr("stroke-opacity", function (a) { return Math.min(1, 0.2 + a[2]); })
+      .attr("stroke-width", function (a) { return 0.5 + 3 * Math.min(1, a[2]); });
+
+    var ringData = [];
+    frame.entities.forEach(function (e) {
+      for (var k = 0; k < e.rings; k++) ringData.push({ position: e.position, k: k });
+    });
+    rings.selectAll("circle.ring")
+      .data(ringData)
+      .join("circle")
+      .attr("class", "ring")
+      .attr("cx", function (r) { return x(r.position[0]); })
+      .attr("cy", function (r) { return y(r.position[1]); })
+      .attr("r", function (r) { return 8 + 3 * (r.k + 1); })
+      .attr("fill", "none")
+      .attr("stroke", "#c8a2ff")
+      .attr("stroke-opacity", 0.5);
+
+    nodes.selectAll("circle.entity")
+      .data(frame.entities)
+      .join("circle")
+      .attr("class", "entity")
+      .attr("cx", function (e) { return x(e.position[0]); })
+      .attr("cy", function (e) { return y(e.position[1]); })
+      .attr("r", 7)
+      .attr("fill", function (e) { return essenceColor(e.essence); })
+      .attr("stroke", "#ffffff");
+
+    sparks.forEach(function (s) {
+      s.marker.attr("cx", s.sx(index)).attr("cy", s.sy(s.values[index]));
+      s.readout.text(Number(s.values[index]).toFixed(3));
+    });
+    d3.select("#step-label").text("Step " + frame.step + " (" + (index + 1) + "/" + frames.length + ")");
+  }
+
+  var scrubber = d3.select("#scrubber").attr("max", Math.max(frames.length - 1, 0));
+  var timer = null;
+  function stop() {
+    clearInterval(timer); timer = null;
+    d3.select("#play").text("Play");
+  }
+  scrubber.on("input", function () { stop(); render(+this.value); });
+  d3.select("#play").on("click", function () {
+    if (timer) { stop(); return; }
+    d3.select("#play").text("Pause");
+    timer = setInterval(function () {
+      var next = (+scrubber.property("value") + 1) % frames.length;
+      scrubber.property("value", next);
+      render(next);
+    }, 100);
+  });
+
+  if (frames.length > 0) render(0);
+})();
+"##;
+
+/// Page skeleton; `{{TITLE}}`, `{{D3}}`, `{{DATA}}` and `{{SCRIPT}}` are
+/// substituted on export.
+const PAGE_TEMPLATE: &str = r##"<!DOCTYPE html>
+<html>
+<head>
+<meta charset="utf-8">
+<title>{{TITLE}} - Replay</title>
+<style>
+body { font-family: Arial, sans-serif; background: #0b1118; color: #e0e6ed; margin: 20px; }
+h1 { font-size: 20px; }
+#layout { display: flex; gap: 24px; align-items: flex-start; }
+#controls { margin: 12px 0; display: flex; gap: 12px; align-items: center; }
+#scrubber { width: 420px; }
+.spark { margin-bottom: 10px; }
+.spark-label { font-size: 12px; color: #9fb0c3; }
+.spark-value { font-size: 12px; }
+</style>
+{{D3}}
+</head>
+<body>
+<h1>{{TITLE}}</h1>
+<div id="controls">
+<button id="play">Play</button>
+<input id="scrubber" type="range" min="0" max="0" value="0">
+<span id="step-label"></span>
+</div>
+<div id="layout">
+<div id="world"></div>
+<div id="sparklines"></div>
+</div>
+<script id="replay-data" type="application/json">{{DATA}}</script>
+<script>{{SCRIPT}}</script>
+</body>
+</html>
+"##;
+
+impl Simulation {
+    /// Export an interactive HTML+D3.js replay of all recorded steps.
+    ///
+    /// D3 is loaded from a CDN with an embedded fallback, so the file works
+    /// with or without network access.
+    ///
+    /// # Arguments
+    /// * `path` - Output HTML file
+    pub fn export_d3_visualization(&self, path: &str) -> Result<(), Box<dyn std::error::Error>> {
+        self.write_replay(path, true)
+    }
+
+    /// Export the replay without any external requests.
+    ///
+    /// Identical to [`Simulation::export_d3_visualization`] except that only
+    /// the embedded D3 subset is used.
+    ///
+    /// # Arguments
+    /// * `path` - Output HTML file
+    pub fn export_d3_visualization_offline(
+        &self,
+        path: &str,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        self.write_replay(path, false)
+    }
+
+    /// Render the replay page and write it to `path`.
+    fn write_replay(&self, path: &str, online: bool) -> Result<(), Box<dyn std::error::Error>> {
+        let d3 = if online {
+            format!(
+                "<script src=\"{}\"></script>\n<script>{}</script>",
+                D3_CDN_URL, D3_SHIM
+            )
+        } else {
+            format!("<script>{}</script>", D3_SHIM)
+        };
+
+        let html = PAGE_TEMPLATE
+            .replace("{{TITLE}}", &html_escape(&self.results.simulation_name))
+            .replace("{{D3}}", &d3)
+            .replace("{{SCRIPT}}", REPLAY_SCRIPT)
+            .replace("{{DATA}}", &self.replay_data_json()?);
+
+        std::fs::write(path, html)?;
+        Ok(())
+    }
+
+    /// Serialize the recorded steps into the replay's frame format.
+    ///
+    /// The JSON is escaped so it cannot terminate the enclosing script tag.
+    fn replay_data_json(&self) -> Result<String, serde_json::Error> {
+        let frames: Vec<serde_json::Value> = self
+            .results
+            .steps
+            .iter()
+            .map(|step| {
+                let entities: Vec<serde_json::Value> = step
+                    .entity_positions
+                    .iter()
+                    .map(|(id, position)| {
+                        let essence = step
+                            .entity_essence
+                            .iter()
+                            .find(|(e, _)| e == id)
+                            .map_or(0.0, |(_, v)| *v);
+                        let rings = step
+                            .belief_clusters
+                            .iter()
+                            .find(|(e, _)| e == id)
+                            .map_or(0, |(_, clusters)| clusters.len().min(MAX_CLUSTER_RINGS));
+                        json!({
+                            "id": id,
+                            "position": position,
+                            "essence": essence,
+                            "rings": rings,
+                        })
+                    })
+                    .collect();
+                json!({
+                    "step": step.step_number,
+                    "entities": entities,
+                    "attractions": step.attractions,
+                    "metrics": step.metrics,
+                })
+            })
+            .collect();
+
+        let metrics: Vec<serde_json::Value> = SPARKLINE_METRICS
+            .iter()
+            .map(|(key, label)| json!({ "key": key, "label": label }))
+            .collect();
+
+        let data = json!({
+            "bounds": self.config.geometry.bounds,
+            "metrics": metrics,
+            "frames": frames,
+        });
+        Ok(serde_json::to_string(&data)?.replace("</", "<\\/"))
+    }
+}
+
+/// Escape text for inclusion in HTML.
+fn html_escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
@@ -0,0 +1,82 @@
+//! Reproduction module: offspring spawning for open-ended runs.
+//!
+//! Entities that sustain a high essence and hold enough energy can spawn
+//! an offspring nearby. The offspring inherits:
+//! - A mutated copy of the parent's traits
+//! - Mutated baseline drives
+//! - The parent's species parameters (mass, stimulus amplitude, attraction)
+//! - A sampled subset of the parent's belief-cluster prototypes (not the
+//!   full episodic history)
+//!
+//! Part of the parent's energy is transferred to the offspring. The
+//! population is capped at a configurable maximum.
+//!
+//! ## Author
+//! Ayomide I. Daniels (Morningstar)
+
+use crate::real::Real;
+use serde::{Deserialize, Serialize};
+
+/// Configuration for entity reproduction.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ReproductionConfig {
+    /// Enable the reproduction phase.
+    pub enabled: bool,
+    /// Essence an entity must stay at or above to reproduce.
+    pub essence_threshold: Real,
+    /// Consecutive steps above the essence threshold required.
+    pub sustain_steps: u32,
+    /// Minimum energy required to reproduce.
+    pub energy_threshold: Real,
+    /// Fraction of the parent's energy transferred to the offspring.
+    pub energy_transfer: Real,
+    /// Maximum distance of the offspring from its parent.
+    pub spawn_radius: Real,
+    /// Uniform mutation amplitude applied to inherited traits.
+    pub trait_mutation: Real,
+    /// Uniform mutation amplitude applied to inherited drives.
+    pub drive_mutation: Real,
+    /// Probability that each parent belief-cluster prototype is inherited.
+    pub prototype_inheritance: Real,
+    /// Population cap; no offspring are spawned at or above it.
+    pub max_population: u32,
+}
+
+impl Default for ReproductionConfig {
+    fn default() -> Self {
+        ReproductionConfig {
+            enabled: false,
+            essence_threshold: 7.0,
+            sustain_steps: 100,
+            energy_threshold: 50.0,
+            energy_transfer: 0.5,
+            spawn_radius: 1.0,
+            trait_mutation: 0.05,
+            drive_mutation: 0.05,
+            prototype_inheritance: 0.5,
+            max_population: 100,
+        }
+    }
+}
+
+impl ReproductionConfig {
+    /// Check parameter ranges.
+    pub fn is_valid(&self) -> bool {
+        (0.0..=1.0).contains(&self.energy_transfer)
+            && (0.0..=1.0).contains(&self.prototype_inheritance)
+            && self.spawn_radius >= 0.0
+            && self.trait_mutation >= 0.0
+            && self.drive_mutation >= 0.0
+    }
+}
+
+/// Lineage record of an entity, kept in the simulation results.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct LineageRecord {
+    /// Entity id.
+    pub entity_id: u32,
+    /// Parent entity id (None for the initial population).
+    pub parent_id: Option<u32>,
+    /// Step at which the entity was born.
+    pub birth_step: u64,
+}
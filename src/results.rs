@@ -17,75 +17,325 @@
 //!
 //! ## Report Formats
 //!
+//! - **Narrative**: Short plain-English paragraph for non-experts
 //! - **Text**: Formatted console-friendly output
 //! - **HTML**: Styled web page with sections and styling
+//! - **Markdown**: Metadata, verdict, criteria table and final metrics
+//!
+//! ## Saved Results
+//!
+//! [`SimulationResults::save_json`] and [`SimulationResults::load`] store the
+//! full results as JSON, optionally gzip-compressed (`.json.gz`). World
+//! bounds and periodicity are part of the results, so a saved file can be
+//! replayed without its configuration. The effective configuration itself
+//! is embedded as well (`config`, with `config_source` naming the file or
+//! `stdin` it came from), so a run from a piped configuration can be
+//! repeated.
+//!
+//! ## Trajectory Export
+//!
+//! [`SimulationResults::export_xyz`] writes the recorded steps as an
+//! extended-XYZ trajectory for molecular viewers such as OVITO or VMD. Each
+//! frame is the entity count, a comment line, then one line per entity:
+//!
+//! ```text
+//! 10
+//! Lattice="10 0 0 0 10 0 0 0 1" Properties=species:S:1:pos:R:3:essence:R:1 pbc="T T F" step=0 Time=0
+//! E5 1.25 3.5 0 5.02
+//! ```
+//!
+//! The species column is the essence band `E0`–`E9` (integer part of the
+//! essence, so viewers color by well-being) and the raw essence follows
+//! the coordinates. The lattice is the world box; 2D runs have `z = 0`, a
+//! unit third lattice vector and no periodicity along it.
 //!
 //! ## Consciousness Analysis
 //!
 //! The system evaluates whether ALL required metrics meet their thresholds.
 //! If any critical metric fails, consciousness is not achieved.
 //! Detailed reasoning explains which primitives succeeded or failed.
+//! The thresholds are defined once, in [`CONSCIOUSNESS_THRESHOLDS`], and
+//! the reports also give the first step at which all of them were met.
 //!
 //! ## Author
 //! Ayomide I. Daniels (Morningstar)
 
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
+use crate::real::Real;
 use crate::metrics::Metrics;
+use crate::config::SimulationConfig;
+use crate::analysis::{RQA, RQA_THRESHOLD_FRACTION, SensitivityReport, TOP_LEADERS, TVD_STRIDE};
+use crate::reproduction::LineageRecord;
+use crate::roles::Role;
+use crate::events::{EventLog, EventType, SimulationEvent};
+use crate::profiling::PhaseTimings;
+use crate::geometry::Coords;
+
+/// Thresholds every metric must meet for consciousness to be achieved.
+pub const CONSCIOUSNESS_THRESHOLDS: [(&str, Real); 6] = [
+    ("attention_entropy", 2.0),
+    ("memory_diversity", 0.1),
+    ("velocity_stability", 0.8),
+    ("identity_coherence", 0.7),
+    ("cluster_stability", 0.5),
+    ("affective_strength", 0.01),
+];
+
+/// Threshold of a consciousness criterion.
+///
+/// # Returns
+/// The minimum value of `metric`, or None if it is not one of
+/// [`CONSCIOUSNESS_THRESHOLDS`]
+pub fn consciousness_threshold(metric: &str) -> Option<Real> {
+    CONSCIOUSNESS_THRESHOLDS
+        .iter()
+        .find(|(name, _)| *name == metric)
+        .map(|(_, threshold)| *threshold)
+}
+
+/// Report label of a metric field: `attention_entropy` → `Attention Entropy`.
+fn metric_label(metric: &str) -> String {
+    metric
+        .split('_')
+        .map(|word| {
+            let mut chars = word.chars();
+            chars.next().map_or(String::new(), |first| first.to_uppercase().chain(chars).collect())
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Whether a metrics snapshot meets all consciousness thresholds.
+pub fn meets_consciousness_thresholds(metrics: &Metrics) -> bool {
+    meets_thresholds(metrics, &CONSCIOUSNESS_THRESHOLDS)
+}
+
+/// Whether a metrics snapshot meets every `(metric, minimum)` pair.
+fn meets_thresholds(metrics: &Metrics, thresholds: &[(&str, Real)]) -> bool {
+    let values = metrics.to_map();
+    thresholds
+        .iter()
+        .all(|(name, threshold)| values.get(*name).is_some_and(|v| v >= threshold))
+}
+
+/// Minimum number of recorded steps before center-of-mass drift is reported.
+pub const MIN_DRIFT_STEPS: usize = 10;
+
+/// Number of entities listed in the report's emergent-leader table.
+const LEADER_TABLE_ROWS: usize = 10;
+
+/// Number of notable events listed in the reports (boundary wraps are only
+/// counted).
+const EVENT_REPORT_ROWS: usize = 20;
+
+/// Which steps are recorded into [`SimulationResults::steps`], and with
+/// which per-entity fields.
+///
+/// Metrics are computed every step either way (`Simulation::metrics_history`
+/// stays complete); only the recorded snapshots are thinned. The final step
+/// of a run is always recorded.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RecordingConfig {
+    /// Record every this many steps (1 = every step).
+    pub record_every: u32,
+    /// Record entity positions and velocities.
+    pub positions: bool,
+    /// Record pairwise attractions.
+    pub attractions: bool,
+    /// Record belief clusters.
+    pub clusters: bool,
+    /// Record attention distributions (also subject to
+    /// `simulation.attention_capture_interval`).
+    pub attentions: bool,
+}
+
+impl Default for RecordingConfig {
+    fn default() -> Self {
+        RecordingConfig {
+            record_every: 1,
+            positions: true,
+            attractions: true,
+            clusters: true,
+            attentions: true,
+        }
+    }
+}
 
 /// Data captured at each simulation step.
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct SimulationStep {
     pub step_number: u64,
-    pub timestamp: f32,
+    pub timestamp: Real,
     /// Pairwise attractions computed (entity_id_a, entity_id_b, attraction_force)
-    pub attractions: Vec<(u32, u32, f32)>,
-    /// Attention activations per entity
-    pub attentions: Vec<(u32, Vec<f32>)>,
+    pub attractions: Vec<(u32, u32, Real)>,
+    /// Softmax attention of each entity over all entities of the step, in
+    /// id order (as `entity_positions`), zero toward itself
+    pub attentions: Vec<(u32, Vec<Real>)>,
     /// Belief clusters per entity with affective signals
-    pub belief_clusters: Vec<(u32, Vec<(u32, f32, i32)>)>, // (entity_id, (cluster_id, affective_signal, size))
+    pub belief_clusters: Vec<(u32, Vec<(u32, Real, i32)>)>, // (entity_id, (cluster_id, affective_signal, size))
     /// Entity positions
-    pub entity_positions: Vec<(u32, Vec<f32>)>,
+    pub entity_positions: Vec<(u32, Coords)>,
     /// Entity velocities
-    pub entity_velocities: Vec<(u32, Vec<f32>)>,
+    pub entity_velocities: Vec<(u32, Coords)>,
     /// Entity essence values
-    pub entity_essence: Vec<(u32, f32)>,
+    pub entity_essence: Vec<(u32, Real)>,
+    /// Entity energy reserves
+    #[serde(default)]
+    pub entity_energy: Vec<(u32, Real)>,
     /// Metrics snapshot
     pub metrics: Metrics,
+    /// Per-species metrics (only when `per_species_metrics` is enabled)
+    #[serde(default)]
+    pub species_metrics: Vec<(String, Metrics)>,
+    /// External stimuli injected this step (entity id, stimulus), in the
+    /// order they were delivered
+    #[serde(default)]
+    pub injected_stimuli: Vec<(u32, Vec<Real>)>,
+    /// Values of the scheduled parameters in effect during this step
+    /// (parameter path, value), in path order
+    #[serde(default)]
+    pub scheduled_values: Vec<(String, Real)>,
+    /// Signals emitted during this step
+    #[serde(default)]
+    pub signals_emitted: usize,
+    /// Signal deliveries (emitter-listener pairs) during this step
+    #[serde(default)]
+    pub signals_received: usize,
+    /// Population center of mass
+    #[serde(default)]
+    pub center_of_mass: Vec<Real>,
+    /// Population mean velocity
+    #[serde(default)]
+    pub mean_velocity: Vec<Real>,
+    /// Role per entity (only when role dynamics are enabled)
+    #[serde(default)]
+    pub entity_roles: Vec<(u32, Role)>,
+}
+
+/// One entity at one recorded step, joined across the per-step vectors.
+#[cfg(any(feature = "arrow", feature = "polars"))]
+pub(crate) struct TrajectoryRow<'a> {
+    pub step: u64,
+    pub entity_id: u32,
+    pub position: &'a [Real],
+    /// Empty if the step has no velocity for the entity
+    pub velocity: &'a [Real],
+    pub essence: Real,
+    pub energy: Real,
+}
+
+/// Current wall-clock time as an RFC 3339 string.
+/// 
+/// wasm32 has no local timezone database, so UTC is used there.
+pub(crate) fn timestamp_now() -> String {
+    #[cfg(not(target_arch = "wasm32"))]
+    {
+        chrono::Local::now().to_rfc3339()
+    }
+    #[cfg(target_arch = "wasm32")]
+    {
+        chrono::Utc::now().to_rfc3339()
+    }
 }
 
 /// Complete simulation results including all steps and analysis.
-#[derive(Clone, Debug, Serialize)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct SimulationResults {
     pub simulation_name: String,
     pub num_entities: u32,
+    /// Steps run: `simulation.num_steps` until finalized, then the steps
+    /// actually completed (fewer after an early stop)
     pub num_steps: u32,
-    pub duration_seconds: f32,
+    /// Simulated time (steps run × `dynamics.dt`)
+    pub duration_seconds: Real,
+    /// Real time spent stepping, in seconds (0 if not measured)
+    #[serde(default)]
+    pub wall_clock_seconds: f64,
     pub start_time: String,
     pub end_time: String,
+    /// SHA-256 fingerprint of the configuration that produced the run
+    #[serde(default)]
+    pub config_fingerprint: String,
+    /// World size along each axis
+    #[serde(default)]
+    pub world_bounds: Vec<Real>,
+    /// Whether the world wraps at its bounds
+    #[serde(default)]
+    pub periodic: bool,
+    /// Seed of the run's random stream; the same configuration and seed
+    /// reproduce the run exactly
+    #[serde(default)]
+    pub seed: u64,
+    /// Configuration fields overridden via CLI (`key=value`, in order)
+    #[serde(default)]
+    pub cli_overrides: Vec<String>,
+    /// Where the configuration came from: its file, `stdin` or `default`
+    /// (empty when not recorded, e.g. for library runs)
+    #[serde(default)]
+    pub config_source: String,
+    /// Effective configuration of the run, so it can be reproduced
+    #[serde(default)]
+    pub config: Option<SimulationConfig>,
     /// All captured steps
     pub steps: Vec<SimulationStep>,
+    /// Lineage of every entity that existed during the run
+    pub lineage: Vec<LineageRecord>,
+    /// Mean age (in steps) of the entities alive at finalization
+    pub mean_entity_age: Real,
+    /// Memory-graph triad motif counts summed over entities (at finalization)
+    pub motif_counts: HashMap<String, usize>,
+    /// Sensitivity to initial conditions, if it was measured
+    pub sensitivity: Option<SensitivityReport>,
+    /// Total variation distance to the step `TVD_STRIDE` earlier, per step
+    pub state_distance_series: Vec<(u64, Real)>,
+    /// Step numbers sampled for the self-similarity matrix
+    pub self_similarity_steps: Vec<u64>,
+    /// Cosine similarity of core metrics between sampled steps
+    pub self_similarity: Vec<Vec<Real>>,
+    /// Recurrence quantification per core metric (name, threshold, measures)
+    pub recurrence: Vec<(String, Real, RQA)>,
+    /// PageRank influence per entity at finalization, highest first
+    pub emergent_leaders: Vec<(u32, Real)>,
+    /// Algebraic connectivity of the attention graph at finalization
+    pub algebraic_connectivity: Real,
+    /// Metrics per role at finalization (role, entity count, metrics);
+    /// empty unless role dynamics are enabled
+    pub role_metrics: Vec<(Role, usize, Metrics)>,
+    /// Discrete events recorded while stepping (cluster births, essence
+    /// extremes, boundary wraps), in order
+    #[serde(default)]
+    pub events: EventLog,
+    /// Wall-clock timings of the step phases (only when `profile_phases`
+    /// is enabled)
+    #[serde(default)]
+    pub phase_timings: Option<PhaseTimings>,
     /// Analysis of consciousness emergence
     pub consciousness_analysis: ConsciousnessAnalysis,
 }
 
 /// Analysis determining if consciousness was achieved.
-#[derive(Clone, Debug, Serialize)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct ConsciousnessAnalysis {
     /// Minimum required values for each metric
-    pub metric_thresholds: HashMap<String, f32>,
+    pub metric_thresholds: HashMap<String, Real>,
     /// Actual values for each metric
-    pub metric_values: HashMap<String, f32>,
+    pub metric_values: HashMap<String, Real>,
     /// Passed metrics
     pub passed_metrics: Vec<String>,
     /// Failed metrics
     pub failed_metrics: Vec<String>,
     /// Overall consciousness score (0.0 to 1.0)
-    pub consciousness_score: f32,
+    pub consciousness_score: Real,
     /// Determined if consciousness was achieved
     pub consciousness_achieved: bool,
     /// Detailed reasoning
     pub reasoning: String,
+    /// First recorded step at which every criterion was met, if any (the
+    /// verdict itself is based on the final step)
+    #[serde(default)]
+    pub first_achieved_step: Option<u64>,
 }
 
 impl SimulationResults {
@@ -100,20 +350,196 @@ impl SimulationResults {
             num_entities,
             num_steps,
             duration_seconds: 0.0,
+            wall_clock_seconds: 0.0,
             start_time,
-            end_time: chrono::Local::now().to_rfc3339(),
+            end_time: timestamp_now(),
+            config_fingerprint: String::new(),
+            world_bounds: Vec::new(),
+            periodic: false,
+            seed: 0,
+            cli_overrides: Vec::new(),
+            config_source: String::new(),
+            config: None,
             steps: Vec::new(),
+            lineage: Vec::new(),
+            mean_entity_age: 0.0,
+            motif_counts: HashMap::new(),
+            sensitivity: None,
+            state_distance_series: Vec::new(),
+            self_similarity_steps: Vec::new(),
+            self_similarity: Vec::new(),
+            recurrence: Vec::new(),
+            emergent_leaders: Vec::new(),
+            algebraic_connectivity: 0.0,
+            role_metrics: Vec::new(),
+            events: EventLog::new(),
+            phase_timings: None,
             consciousness_analysis: ConsciousnessAnalysis::default(),
         }
     }
 
+    /// Entity rows of every recorded step, in step order (entities by id).
+    ///
+    /// Shared by the tabular exporters. Missing essence or energy reads 0.
+    #[cfg(any(feature = "arrow", feature = "polars"))]
+    pub(crate) fn trajectory_rows(&self) -> Vec<TrajectoryRow<'_>> {
+        let mut rows = Vec::new();
+        for step in &self.steps {
+            let velocities: HashMap<u32, &[Real]> =
+                step.entity_velocities.iter().map(|(id, v)| (*id, v.as_slice())).collect();
+            let essence: HashMap<u32, Real> = step.entity_essence.iter().copied().collect();
+            let energy: HashMap<u32, Real> = step.entity_energy.iter().copied().collect();
+
+            let mut positions: Vec<&(u32, Coords)> = step.entity_positions.iter().collect();
+            positions.sort_by_key(|(id, _)| *id);
+            for (id, position) in positions {
+                rows.push(TrajectoryRow {
+                    step: step.step_number,
+                    entity_id: *id,
+                    position,
+                    velocity: velocities.get(id).copied().unwrap_or(&[]),
+                    essence: essence.get(id).copied().unwrap_or(0.0),
+                    energy: energy.get(id).copied().unwrap_or(0.0),
+                });
+            }
+        }
+        rows
+    }
+
     /// Add a captured step.
     pub fn add_step(&mut self, step: SimulationStep) {
         self.steps.push(step);
     }
 
+    /// Save the results as JSON, gzip-compressed if `path` ends in `.gz`.
+    ///
+    /// # Arguments
+    /// * `path` - Output file (`.json` or `.json.gz`)
+    pub fn save_json(&self, path: &str) -> Result<(), Box<dyn std::error::Error>> {
+        use std::io::Write;
+
+        let json = serde_json::to_vec(self)?;
+        if path.ends_with(".gz") {
+            let mut encoder = flate2::write::GzEncoder::new(std::fs::File::create(path)?, flate2::Compression::default());
+            encoder.write_all(&json)?;
+            encoder.finish()?;
+        } else {
+            std::fs::write(path, json)?;
+        }
+        Ok(())
+    }
+
+    /// Threshold of a criterion as shown in reports, e.g. `≥2.0`, from the
+    /// latest analysis (the default before any).
+    fn threshold_text(&self, metric: &str) -> String {
+        self.consciousness_analysis
+            .metric_thresholds
+            .get(metric)
+            .copied()
+            .or_else(|| consciousness_threshold(metric))
+            .map_or(String::new(), |threshold| format!("≥{:?}", threshold))
+    }
+
+    /// Load results saved as JSON (`save_json`, or the `json` export).
+    ///
+    /// Gzip-compressed files are detected from their content, whatever
+    /// their extension.
+    ///
+    /// # Arguments
+    /// * `path` - Results file
+    ///
+    /// # Returns
+    /// The results, or an error naming the file and what is wrong with it
+    pub fn load(path: &str) -> Result<Self, Box<dyn std::error::Error>> {
+        use std::io::Read;
+
+        let bytes = std::fs::read(path).map_err(|e| format!("cannot read results file '{}': {}", path, e))?;
+        let json = if bytes.starts_with(&[0x1f, 0x8b]) {
+            let mut json = Vec::new();
+            flate2::read::GzDecoder::new(bytes.as_slice())
+                .read_to_end(&mut json)
+                .map_err(|e| format!("'{}' is not a valid gzip file: {}", path, e))?;
+            json
+        } else {
+            bytes
+        };
+        let results = serde_json::from_slice(&json)
+            .map_err(|e| format!("'{}' is not a simulation results file: {}", path, e))?;
+        Ok(results)
+    }
+
+    /// Write the recorded steps as an extended-XYZ trajectory.
+    ///
+    /// One frame per recorded step, entities ordered by id; see the module
+    /// docs for the layout.
+    ///
+    /// # Arguments
+    /// * `path` - Output file (conventionally `.xyz`)
+    pub fn export_xyz(&self, path: &str) -> Result<(), Box<dyn std::error::Error>> {
+        use std::fs::File;
+        use std::io::{BufWriter, Write};
+
+        let mut file = BufWriter::new(File::create(path)?);
+        let lattice: Vec<String> = (0..3)
+            .flat_map(|i| {
+                (0..3).map(move |j| {
+                    let length = self.world_bounds.get(i).copied().unwrap_or(1.0);
+                    if i == j { length.to_string() } else { "0".to_string() }
+                })
+            })
+            .collect();
+        let pbc: Vec<&str> = (0..3)
+            .map(|i| if self.periodic && i < self.world_bounds.len() { "T" } else { "F" })
+            .collect();
+
+        for step in &self.steps {
+            let essence: HashMap<u32, Real> = step.entity_essence.iter().copied().collect();
+            let mut positions: Vec<&(u32, Coords)> = step.entity_positions.iter().collect();
+            positions.sort_by_key(|(id, _)| *id);
+
+            writeln!(file, "{}", positions.len())?;
+            writeln!(
+                file,
+                "Lattice=\"{}\" Properties=species:S:1:pos:R:3:essence:R:1 pbc=\"{}\" step={} Time={}",
+                lattice.join(" "),
+                pbc.join(" "),
+                step.step_number,
+                step.timestamp
+            )?;
+            for (id, position) in positions {
+                let value = essence.get(id).copied().unwrap_or(0.0);
+                let band = (value.floor().max(0.0) as u32).min(9);
+                let coordinate = |axis: usize| position.get(axis).copied().unwrap_or(0.0);
+                writeln!(
+                    file,
+                    "E{} {} {} {} {}",
+                    band,
+                    coordinate(0),
+                    coordinate(1),
+                    coordinate(2),
+                    value
+                )?;
+            }
+        }
+        file.flush()?;
+        Ok(())
+    }
+
     /// Analyze consciousness emergence based on results.
     pub fn analyze_consciousness(&mut self) {
+        self.analyze_consciousness_with(&CONSCIOUSNESS_THRESHOLDS);
+    }
+
+    /// Analyze consciousness emergence against custom criteria.
+    /// 
+    /// Replaces `consciousness_analysis`, so loaded results can be
+    /// re-evaluated without re-running the simulation; the reports then
+    /// quote these thresholds.
+    /// 
+    /// # Arguments
+    /// * `thresholds` - `(metric field, minimum)` pairs that must all be
+    ///   met; see [`CONSCIOUSNESS_THRESHOLDS`] for the defaults
+    pub fn analyze_consciousness_with(&mut self, thresholds: &[(&str, Real)]) {
         let mut analysis = ConsciousnessAnalysis {
             metric_thresholds: HashMap::new(),
             metric_values: HashMap::new(),
@@ -122,6 +548,7 @@ impl SimulationResults {
             consciousness_score: 0.0,
             consciousness_achieved: false,
             reasoning: String::new(),
+            first_achieved_step: None,
         };
 
         if self.steps.is_empty() {
@@ -131,99 +558,41 @@ impl SimulationResults {
         }
 
         // Thresholds for consciousness emergence
-        analysis.metric_thresholds.insert("attention_entropy".to_string(), 2.0);
-        analysis.metric_thresholds.insert("memory_diversity".to_string(), 0.1);
-        analysis.metric_thresholds.insert("velocity_stability".to_string(), 0.8);
-        analysis.metric_thresholds.insert("identity_coherence".to_string(), 0.7);
-        analysis.metric_thresholds.insert("cluster_stability".to_string(), 0.5);
-        analysis.metric_thresholds.insert("affective_strength".to_string(), 0.01);
+        for &(name, threshold) in thresholds {
+            analysis.metric_thresholds.insert(name.to_string(), threshold);
+        }
+        analysis.first_achieved_step = self
+            .steps
+            .iter()
+            .find(|step| meets_thresholds(&step.metrics, thresholds))
+            .map(|step| step.step_number);
 
         // Get final metrics
-        let final_metrics = &self.steps.last().unwrap().metrics;
+        let final_metrics = self.steps.last().unwrap().metrics.to_map();
 
         // Evaluate each metric
         let mut total_score = 0.0;
-        let num_metrics = analysis.metric_thresholds.len() as f32;
-
-        // Attention Entropy
-        let ae = final_metrics.attention_entropy;
-        analysis.metric_values.insert("attention_entropy".to_string(), ae);
-        if ae >= 2.0 {
-            analysis.passed_metrics.push("Attention Entropy".to_string());
-            total_score += 1.0;
-        } else {
-            analysis.failed_metrics.push(format!(
-                "Attention Entropy: {:.2} < 2.0",
-                ae
-            ));
-        }
-
-        // Memory Diversity
-        let md = final_metrics.memory_diversity;
-        analysis.metric_values.insert("memory_diversity".to_string(), md);
-        if md >= 0.1 {
-            analysis.passed_metrics.push("Memory Diversity".to_string());
-            total_score += 1.0;
-        } else {
-            analysis.failed_metrics.push(format!(
-                "Memory Diversity: {:.4} < 0.1",
-                md
-            ));
-        }
-
-        // Velocity Stability
-        let vs = final_metrics.velocity_stability;
-        analysis.metric_values.insert("velocity_stability".to_string(), vs);
-        if vs >= 0.8 {
-            analysis.passed_metrics.push("Velocity Stability".to_string());
-            total_score += 1.0;
-        } else {
-            analysis.failed_metrics.push(format!(
-                "Velocity Stability: {:.2} < 0.8",
-                vs
-            ));
-        }
-
-        // Identity Coherence
-        let ic = final_metrics.identity_coherence;
-        analysis.metric_values.insert("identity_coherence".to_string(), ic);
-        if ic >= 0.7 {
-            analysis.passed_metrics.push("Identity Coherence".to_string());
-            total_score += 1.0;
-        } else {
-            analysis.failed_metrics.push(format!(
-                "Identity Coherence: {:.2} < 0.7",
-                ic
-            ));
-        }
-
-        // Cluster Stability
-        let cs = final_metrics.cluster_stability;
-        analysis.metric_values.insert("cluster_stability".to_string(), cs);
-        if cs >= 0.5 {
-            analysis.passed_metrics.push("Cluster Stability".to_string());
-            total_score += 1.0;
-        } else {
-            analysis.failed_metrics.push(format!(
-                "Cluster Stability: {:.2} < 0.5",
-                cs
-            ));
-        }
-
-        // Affective Strength
-        let afs = final_metrics.affective_strength;
-        analysis.metric_values.insert("affective_strength".to_string(), afs);
-        if afs >= 0.01 {
-            analysis.passed_metrics.push("Affective Strength".to_string());
-            total_score += 1.0;
-        } else {
-            analysis.failed_metrics.push(format!(
-                "Affective Strength: {:.4} < 0.01",
-                afs
-            ));
+        let num_metrics = analysis.metric_thresholds.len() as Real;
+        for &(name, threshold) in thresholds {
+            let value = final_metrics.get(name).copied().unwrap_or(0.0);
+            analysis.metric_values.insert(name.to_string(), value);
+            if value >= threshold {
+                analysis.passed_metrics.push(metric_label(name));
+                total_score += 1.0;
+            } else {
+                // Small thresholds need more decimals to show the gap
+                let decimals = if threshold < 0.5 { 4 } else { 2 };
+                analysis.failed_metrics.push(format!(
+                    "{}: {:.*} < {:?}",
+                    metric_label(name),
+                    decimals,
+                    value,
+                    threshold
+                ));
+            }
         }
 
-        analysis.consciousness_score = total_score / num_metrics;
+        analysis.consciousness_score = total_score / num_metrics.max(1.0);
         // ALL criteria must pass for consciousness to be achieved
         analysis.consciousness_achieved = analysis.consciousness_score >= 1.0;
 
@@ -236,9 +605,15 @@ impl SimulationResults {
             passed, passed + failed,
             analysis.consciousness_score * 100.0
         );
-        reasoning.push_str("⚠️  STRICT REQUIREMENT: ALL 6 METRICS MUST PASS FOR CONSCIOUSNESS\n\n");
+        reasoning.push_str(&format!(
+            "⚠️  STRICT REQUIREMENT: ALL {} METRICS MUST PASS FOR CONSCIOUSNESS\n\n",
+            thresholds.len()
+        ));
 
         if analysis.consciousness_achieved {
+            let value = |name: &str| analysis.metric_values.get(name).copied().unwrap_or(0.0);
+            let (ae, md, vs) = (value("attention_entropy"), value("memory_diversity"), value("velocity_stability"));
+            let (ic, cs, afs) = (value("identity_coherence"), value("cluster_stability"), value("affective_strength"));
             reasoning.push_str("✓✓✓ CONSCIOUSNESS FULLY ACHIEVED ✓✓✓\n\n");
             reasoning.push_str("ALL consciousness indicators present:\n");
             reasoning.push_str(&format!("• Attention Diversity (Entropy {:.2}): System shows varied awareness\n", ae));
@@ -263,6 +638,85 @@ impl SimulationResults {
         self.consciousness_analysis = analysis;
     }
 
+    /// Generate a short plain-English summary of the run.
+    ///
+    /// Produced procedurally from the consciousness analysis and the final
+    /// recorded step: run size, criteria met, the standout metric (the
+    /// lowest relative to its threshold if any failed, otherwise the
+    /// highest), belief cluster count and overall affective character, and
+    /// the verdict. Call after `analyze_consciousness()`.
+    ///
+    /// # Returns
+    /// Paragraph of 3-5 sentences
+    pub fn narrative_summary(&self) -> String {
+        let analysis = &self.consciousness_analysis;
+        let passed = analysis.passed_metrics.len();
+        let total = analysis.metric_thresholds.len();
+
+        let mut sentences = vec![format!(
+            "The simulation ran {} entities for {} steps ({:.1}s).",
+            self.num_entities, self.num_steps, self.duration_seconds
+        )];
+        sentences.push(format!("{}/{} consciousness criteria were met.", passed, total));
+
+        // Standout metric, measured relative to its threshold
+        let mut ratios: Vec<(&str, Real, Real)> = analysis
+            .metric_values
+            .iter()
+            .filter_map(|(name, value)| {
+                let threshold = *analysis.metric_thresholds.get(name)?;
+                let ratio = if threshold > 0.0 { value / threshold } else { *value };
+                Some((name.as_str(), *value, ratio))
+            })
+            .collect();
+        ratios.sort_by(|a, b| a.2.partial_cmp(&b.2).unwrap_or(std::cmp::Ordering::Equal).then(a.0.cmp(b.0)));
+        let standout = if analysis.consciousness_achieved {
+            ratios.last().map(|m| ("highest", m))
+        } else {
+            ratios.first().map(|m| ("lowest", m))
+        };
+        if let Some((rank, (name, value, _))) = standout {
+            sentences.push(format!(
+                "The {} performing metric was {} ({:.2}).",
+                rank,
+                name.replace('_', " "),
+                value
+            ));
+        }
+
+        if let Some(last) = self.steps.last() {
+            let signals: Vec<Real> = last
+                .belief_clusters
+                .iter()
+                .flat_map(|(_, clusters)| clusters.iter().map(|(_, signal, _)| *signal))
+                .collect();
+            let mean_signal = if signals.is_empty() {
+                0.0
+            } else {
+                signals.iter().sum::<Real>() / signals.len() as Real
+            };
+            let valence = if mean_signal > 0.05 {
+                "a positive"
+            } else if mean_signal < -0.05 {
+                "a negative"
+            } else {
+                "a neutral"
+            };
+            sentences.push(format!(
+                "Entities organized into {} distinct belief clusters with {} affective character.",
+                signals.len(),
+                valence
+            ));
+        }
+
+        sentences.push(format!(
+            "The system {} achieve the consciousness threshold.",
+            if analysis.consciousness_achieved { "did" } else { "did not" }
+        ));
+
+        sentences.join(" ")
+    }
+
     /// Generate PDF report.
     pub fn generate_pdf_report(&self, filename: &str) -> Result<(), Box<dyn std::error::Error>> {
         // For now, just generate the text report
@@ -298,13 +752,33 @@ impl SimulationResults {
         writeln!(file, "─────────────────────────────────────────────────────────────────")?;
         writeln!(file, "Name:                {}", self.simulation_name)?;
         writeln!(file, "Start Time:          {}", self.start_time)?;
+        writeln!(file, "Config Fingerprint:  {}", self.config_fingerprint)?;
+        writeln!(file, "Seed:                {}", self.seed)?;
+        if !self.config_source.is_empty() {
+            writeln!(file, "Config Source:       {}", self.config_source)?;
+        }
+        if !self.cli_overrides.is_empty() {
+            writeln!(file, "Overridden via CLI:  {}", self.cli_overrides.join(", "))?;
+        }
         writeln!(file, "End Time:            {}", self.end_time)?;
         writeln!(file, "Number of Entities:  {}", self.num_entities)?;
         writeln!(file, "Number of Steps:     {}", self.num_steps)?;
         writeln!(file, "Duration:            {:.2} seconds", self.duration_seconds)?;
+        if self.wall_clock_seconds > 0.0 {
+            writeln!(file, "Wall-Clock Time:     {:.2} seconds", self.wall_clock_seconds)?;
+        }
         writeln!(file, "Total Interactions:  {}", self.count_total_attractions())?;
+        writeln!(file, "Max Lineage Depth:   {}", self.max_lineage_depth())?;
+        writeln!(file, "Mean Entity Age:     {:.1} steps", self.mean_entity_age)?;
         writeln!(file)?;
 
+        // Center-of-mass drift alert
+        for (axis, drift) in self.center_of_mass_drift() {
+            writeln!(file, "⚠️  CENTER-OF-MASS DRIFT: monotonic along axis {} (net {:+.3})", axis, drift)?;
+            writeln!(file, "   → May indicate a broken periodic boundary or unbalanced attraction")?;
+            writeln!(file)?;
+        }
+
         // Consciousness Analysis
         writeln!(file, "CONSCIOUSNESS ANALYSIS")?;
         writeln!(file, "─────────────────────────────────────────────────────────────────")?;
@@ -322,6 +796,7 @@ impl SimulationResults {
                 "✗ NOT ACHIEVED"
             }
         )?;
+        writeln!(file, "First achieved: {}", self.first_achieved_text())?;
         writeln!(file)?;
         writeln!(file, "Reasoning:")?;
         writeln!(file, "{}", self.consciousness_analysis.reasoning)?;
@@ -339,28 +814,28 @@ impl SimulationResults {
         if let Some(final_step) = self.steps.last() {
             let m = &final_step.metrics;
             writeln!(file, "1. Attention Entropy: {:.4}", m.attention_entropy)?;
-            writeln!(file, "   → Measures diversity of memory activation (threshold: ≥2.0)")?;
+            writeln!(file, "   → Measures diversity of memory activation (threshold: {})", self.threshold_text("attention_entropy"))?;
             writeln!(file, "   → Higher = more diverse focus, better consciousness marker")?;
             writeln!(file)?;
             writeln!(file, "2. Memory Diversity: {:.4}", m.memory_diversity)?;
-            writeln!(file, "   → Variance in belief cluster affective signals (threshold: ≥0.1)")?;
+            writeln!(file, "   → Variance in belief cluster affective signals (threshold: {})", self.threshold_text("memory_diversity"))?;
             writeln!(file, "   → Higher = richer emotional response patterns")?;
             writeln!(file)?;
             writeln!(file, "3. Velocity Stability: {:.4}", m.velocity_stability)?;
-            writeln!(file, "   → Consistency of perpetual motion (threshold: ≥0.8)")?;
+            writeln!(file, "   → Consistency of perpetual motion (threshold: {})", self.threshold_text("velocity_stability"))?;
             writeln!(file, "   → Enforces minimum speed to prevent static equilibrium")?;
             writeln!(file, "   → Velocity tracked per entity at each step")?;
             writeln!(file)?;
             writeln!(file, "4. Identity Coherence: {:.4}", m.identity_coherence)?;
-            writeln!(file, "   → State vector consistency across time (threshold: ≥0.7)")?;
+            writeln!(file, "   → State vector consistency across time (threshold: {})", self.threshold_text("identity_coherence"))?;
             writeln!(file, "   → Higher = stable self-representation")?;
             writeln!(file)?;
             writeln!(file, "5. Cluster Stability: {:.4}", m.cluster_stability)?;
-            writeln!(file, "   → Number of belief clusters formed (threshold: ≥0.5)")?;
+            writeln!(file, "   → Number of belief clusters formed (threshold: {})", self.threshold_text("cluster_stability"))?;
             writeln!(file, "   → Indicates semantic memory organization")?;
             writeln!(file)?;
             writeln!(file, "6. Affective Strength: {:.4}", m.affective_strength)?;
-            writeln!(file, "   → Magnitude of emotional signals (-5 to +5 range) (threshold: ≥0.01)")?;
+            writeln!(file, "   → Magnitude of emotional signals (-5 to +5 range) (threshold: {})", self.threshold_text("affective_strength"))?;
             writeln!(file, "   → Shows emotional responsiveness of entities")?;
             writeln!(file)?;
             writeln!(file, "7. Essence Trajectory: {:.4}", m.essence_trajectory)?;
@@ -381,6 +856,44 @@ impl SimulationResults {
         writeln!(file, "Peak Affective Signal:   {:.4}", self.max_affective_signal())?;
         writeln!(file)?;
 
+        // Event log
+        if !self.events.is_empty() {
+            writeln!(file, "EVENTS")?;
+            writeln!(file, "─────────────────────────────────────────────────────────────────")?;
+            for (name, count) in self.events.counts() {
+                writeln!(file, "{:<31} {}", format!("{}:", name), count)?;
+            }
+            writeln!(file)?;
+            writeln!(file, "First notable events:")?;
+            for event in self.notable_events() {
+                let entity = event.entity_id.map(|id| format!("entity {}", id.0)).unwrap_or_else(|| "-".to_string());
+                writeln!(file, "  Step {:<6} {:<10} {:<14} {}", event.step, entity, event.event_type.name(), event.details)?;
+            }
+            writeln!(file)?;
+        }
+
+        // Phase timings
+        if let Some(timings) = self.phase_timings.as_ref().filter(|timings| !timings.is_empty()) {
+            let total = timings.total().as_secs_f64();
+            writeln!(file, "PHASE TIMINGS")?;
+            writeln!(file, "─────────────────────────────────────────────────────────────────")?;
+            writeln!(file, "{:<14} {:>10} {:>10} {:>10} {:>10} {:>7}", "Phase", "Mean µs", "Min µs", "Max µs", "Total ms", "Share")?;
+            for (phase, stats) in timings.iter() {
+                let share = if total > 0.0 { 100.0 * stats.total.as_secs_f64() / total } else { 0.0 };
+                writeln!(
+                    file,
+                    "{:<14} {:>10.1} {:>10.1} {:>10.1} {:>10.2} {:>6.1}%",
+                    phase.name(),
+                    stats.mean().as_secs_f64() * 1e6,
+                    stats.min.as_secs_f64() * 1e6,
+                    stats.max.as_secs_f64() * 1e6,
+                    stats.total.as_secs_f64() * 1e3,
+                    share
+                )?;
+            }
+            writeln!(file)?;
+        }
+
         // Detailed Step Information (sample every Nth step)
         let sample_rate = if self.steps.len() > 100 {
             self.steps.len() / 10
@@ -399,7 +912,7 @@ impl SimulationResults {
                 writeln!(file, "  Total Belief Clusters: {}", step.belief_clusters.len())?;
 
                 // Show affective signals
-                let mut total_affective = 0.0f32;
+                let mut total_affective = 0.0 as Real;
                 let mut count = 0;
                 for (_, clusters) in &step.belief_clusters {
                     for (_, signal, _) in clusters {
@@ -411,7 +924,7 @@ impl SimulationResults {
                     writeln!(
                         file,
                         "  Avg Affective Signal: {:.4}",
-                        total_affective / count as f32
+                        total_affective / count as Real
                     )?;
                 }
 
@@ -462,6 +975,170 @@ impl SimulationResults {
         Ok(())
     }
 
+    /// Generate a Markdown report: metadata, verdict, per-metric criteria
+    /// and the final step's metrics.
+    pub fn generate_markdown_report(&self, filename: &str) -> Result<(), Box<dyn std::error::Error>> {
+        use std::fs::File;
+        use std::io::Write;
+
+        let mut file = File::create(filename)?;
+        let analysis = &self.consciousness_analysis;
+
+        writeln!(file, "# Synthetic Consciousness Simulation Report")?;
+        writeln!(file)?;
+        writeln!(file, "- **Name:** {}", self.simulation_name)?;
+        writeln!(file, "- **Config fingerprint:** `{}`", self.config_fingerprint)?;
+        writeln!(file, "- **Seed:** {}", self.seed)?;
+        if !self.config_source.is_empty() {
+            writeln!(file, "- **Config source:** `{}`", self.config_source)?;
+        }
+        if !self.cli_overrides.is_empty() {
+            writeln!(file, "- **Overridden via CLI:** `{}`", self.cli_overrides.join("`, `"))?;
+        }
+        writeln!(file, "- **Start / end:** {} / {}", self.start_time, self.end_time)?;
+        writeln!(file, "- **Entities:** {}", self.num_entities)?;
+        writeln!(file, "- **Steps:** {}", self.num_steps)?;
+        writeln!(file, "- **Duration:** {:.2} seconds", self.duration_seconds)?;
+        if self.wall_clock_seconds > 0.0 {
+            writeln!(file, "- **Wall-clock time:** {:.2} seconds", self.wall_clock_seconds)?;
+        }
+        writeln!(file)?;
+
+        writeln!(file, "## Consciousness Analysis")?;
+        writeln!(file)?;
+        writeln!(
+            file,
+            "**Score:** {:.1}% — {}",
+            analysis.consciousness_score * 100.0,
+            if analysis.consciousness_achieved { "achieved" } else { "not achieved" }
+        )?;
+        writeln!(file)?;
+        writeln!(file, "**First achieved:** {}", self.first_achieved_text())?;
+        writeln!(file)?;
+        writeln!(file, "| Metric | Value | Threshold | Passed |")?;
+        writeln!(file, "|---|---:|---:|:---:|")?;
+        for (name, threshold) in CONSCIOUSNESS_THRESHOLDS {
+            let value = analysis.metric_values.get(name).copied().unwrap_or(0.0);
+            writeln!(
+                file,
+                "| {} | {:.4} | {:.4} | {} |",
+                name,
+                value,
+                threshold,
+                if value >= threshold { "✓" } else { "✗" }
+            )?;
+        }
+        writeln!(file)?;
+        writeln!(file, "{}", analysis.reasoning)?;
+        writeln!(file)?;
+
+        let injections: Vec<&SimulationStep> = self.steps.iter().filter(|s| !s.injected_stimuli.is_empty()).collect();
+        if !injections.is_empty() {
+            let final_essence = |id: u32| {
+                self.steps.last().and_then(|last| last.entity_essence.iter().find(|(e, _)| *e == id)).map(|(_, v)| *v)
+            };
+            let essence_text = |essence: Option<Real>| essence.map_or("—".to_string(), |v| format!("{:.4}", v));
+            writeln!(file, "## Injected Stimuli")?;
+            writeln!(file)?;
+            writeln!(file, "| Step | Entity | Stimulus | Essence at step | Final essence |")?;
+            writeln!(file, "|---:|---:|---|---:|---:|")?;
+            for step in injections {
+                for (id, stimulus) in &step.injected_stimuli {
+                    let components: Vec<String> = stimulus.iter().map(|v| format!("{:.3}", v)).collect();
+                    let essence = step.entity_essence.iter().find(|(e, _)| e == id).map(|(_, v)| *v);
+                    writeln!(
+                        file,
+                        "| {} | {} | [{}] | {} | {} |",
+                        step.step_number,
+                        id,
+                        components.join(", "),
+                        essence_text(essence),
+                        essence_text(final_essence(*id))
+                    )?;
+                }
+            }
+            writeln!(file)?;
+        }
+
+        let scheduled: Vec<&SimulationStep> = self.steps.iter().filter(|s| !s.scheduled_values.is_empty()).collect();
+        if let (Some(first), Some(last)) = (scheduled.first(), scheduled.last()) {
+            writeln!(file, "## Parameter Schedules")?;
+            writeln!(file)?;
+            writeln!(file, "| Parameter | Schedule | Step {} | Step {} |", first.step_number, last.step_number)?;
+            writeln!(file, "|---|---|---:|---:|")?;
+            for (path, value) in &first.scheduled_values {
+                let schedule = self
+                    .config
+                    .as_ref()
+                    .and_then(|config| config.schedules.get(path))
+                    .map_or("—".to_string(), |schedule| schedule.describe());
+                let end = last.scheduled_values.iter().find(|(p, _)| p == path).map(|(_, v)| *v);
+                writeln!(
+                    file,
+                    "| {} | {} | {:.4} | {} |",
+                    path,
+                    schedule,
+                    value,
+                    end.map_or("—".to_string(), |v| format!("{:.4}", v))
+                )?;
+            }
+            writeln!(file)?;
+        }
+
+        let metabolism = self.config.as_ref().is_some_and(|config| config.energy.enabled);
+        if let (true, Some(first), Some(last)) = (metabolism, self.steps.first(), self.steps.last()) {
+            let lowest = self
+                .steps
+                .iter()
+                .min_by(|a, b| a.metrics.mean_energy.total_cmp(&b.metrics.mean_energy))
+                .unwrap_or(last);
+            writeln!(file, "## Energy")?;
+            writeln!(file)?;
+            writeln!(file, "| | Step | Mean energy | Exhausted entities |")?;
+            writeln!(file, "|---|---:|---:|---:|")?;
+            for (label, step) in [("Start", first), ("Lowest", lowest), ("End", last)] {
+                let exhausted = step.entity_energy.iter().filter(|(_, energy)| *energy <= 0.0).count();
+                writeln!(
+                    file,
+                    "| {} | {} | {:.4} | {} / {} |",
+                    label,
+                    step.step_number,
+                    step.metrics.mean_energy,
+                    exhausted,
+                    step.entity_energy.len()
+                )?;
+            }
+            writeln!(file)?;
+        }
+
+        if let Some(last) = self.steps.last().filter(|step| !step.species_metrics.is_empty()) {
+            writeln!(file, "## Per-Species Metrics")?;
+            writeln!(file)?;
+            writeln!(file, "At step {}:", last.step_number)?;
+            writeln!(file)?;
+            writeln!(file, "| Species | {} |", Metrics::FIELD_NAMES.join(" | "))?;
+            writeln!(file, "|---|{}", "---:|".repeat(Metrics::FIELD_NAMES.len()))?;
+            for (name, metrics) in &last.species_metrics {
+                let values: Vec<String> = metrics.field_values().iter().map(|v| format!("{:.4}", v)).collect();
+                writeln!(file, "| {} | {} |", name, values.join(" | "))?;
+            }
+            writeln!(file)?;
+        }
+
+        if let Some(last) = self.steps.last() {
+            writeln!(file, "## Final Metrics")?;
+            writeln!(file)?;
+            writeln!(file, "{}", crate::notebook::metrics_table(std::slice::from_ref(&last.metrics)))?;
+            writeln!(file)?;
+        }
+
+        writeln!(file, "## Summary")?;
+        writeln!(file)?;
+        writeln!(file, "{}", self.narrative_summary())?;
+
+        Ok(())
+    }
+
     /// Generate HTML report (detailed and informative).
     pub fn generate_html_report(&self, filename: &str) -> Result<(), Box<dyn std::error::Error>> {
         use std::fs::File;
@@ -503,6 +1180,13 @@ impl SimulationResults {
         writeln!(file, "    a:hover {{ text-decoration: underline; }}")?;
         writeln!(file, "    .repo-link {{ text-align: left; color: #7f8c8d; font-size: 14px; margin-top: 10px; }}")?;
         writeln!(file, "    hr {{ border: none; border-top: 2px solid #ecf0f1; margin: 30px 0; }}")?;
+        writeln!(file, "    table {{ border-collapse: collapse; width: 100%; font-size: 14px; }}")?;
+        writeln!(file, "    .tabs > input {{ display: none; }}")?;
+        writeln!(file, "    .tabs > label {{ display: inline-block; padding: 8px 16px; cursor: pointer; border: 1px solid #ddd; border-bottom: none; border-radius: 5px 5px 0 0; background: #ecf0f1; }}")?;
+        writeln!(file, "    .tabs > input:checked + label {{ background: white; font-weight: bold; color: #3498db; }}")?;
+        writeln!(file, "    .tab-panel {{ display: none; border: 1px solid #ddd; padding: 15px; }}")?;
+        writeln!(file, "    #role-tab-0:checked ~ #role-panel-0, #role-tab-1:checked ~ #role-panel-1, #role-tab-2:checked ~ #role-panel-2 {{ display: block; }}")?;
+        writeln!(file, "    th, td {{ padding: 6px 10px; text-align: left; border-bottom: 1px solid #ddd; }}")?;
         writeln!(file, "    @media print {{ body {{ background: white; }} .container {{ box-shadow: none; }} }}")?;
         writeln!(file, "  </style>")?;
         writeln!(file, "</head>")?;
@@ -510,12 +1194,22 @@ impl SimulationResults {
         writeln!(file, "  <div class=\"container\">")?;
         writeln!(file, "    <h2 class=\"underlined\">Synthetic Consciousness Analysis Report</h2>")?;
         writeln!(file, "    <p class=\"repo-link\"><a href=\"https://github.com/Alchymia-AI/synthetic-consciousness\" target=\"_blank\">https://github.com/Alchymia-AI/synthetic-consciousness</a></p>")?;
+        writeln!(file, "    <p class=\"repo-link\">Config fingerprint: <code>{}</code> · Seed: <code>{}</code></p>", self.config_fingerprint, self.seed)?;
+        if !self.config_source.is_empty() {
+            writeln!(file, "    <p class=\"repo-link\">Config source: <code>{}</code></p>", self.config_source)?;
+        }
+        if !self.cli_overrides.is_empty() {
+            writeln!(file, "    <p class=\"repo-link\">Overridden via CLI: <code>{}</code></p>", self.cli_overrides.join(", "))?;
+        }
 
         // Simulation Overview
         //writeln!(file, "    <h2>Simulation Overview</h2>")?;
         writeln!(file, "    <p><strong>Purpose:</strong> Test whether synthetic digital entities can achieve consciousness through simulated interactions.</p>")?;
         writeln!(file, "    <div class=\"summary-box\">")?;
         writeln!(file, "      <strong>Configuration:</strong> {} entities interacting over {} simulation steps ({:.1}s duration)", self.num_entities, self.num_steps, self.duration_seconds)?;
+        if self.wall_clock_seconds > 0.0 {
+            writeln!(file, "      <br>Wall-clock time: {:.2}s", self.wall_clock_seconds)?;
+        }
         writeln!(file, "      <br>Total pairwise interactions tracked: {}", self.count_total_attractions())?;
         writeln!(file, "    </div>")?;
 
@@ -529,6 +1223,7 @@ impl SimulationResults {
             writeln!(file, "      <div class=\"score-status not-achieved\">✗ CONSCIOUSNESS NOT ACHIEVED</div>")?;
         }
         writeln!(file, "    </div>")?;
+        writeln!(file, "    <p><strong>First achieved:</strong> {}</p>", self.first_achieved_text())?;
 
         writeln!(file, "    <p><strong>Requirement:</strong> <span class=\"critical\">ALL 6 criteria must pass for consciousness</span>. This is a strict standard reflecting the complexity of consciousness.</p>")?;
 
@@ -580,7 +1275,7 @@ impl SimulationResults {
             
             writeln!(file, "    <div class=\"metric-box\">")?;
             writeln!(file, "      <div class=\"metric-name\">1. Attention Entropy (Awareness Diversity)</div>")?;
-            writeln!(file, "      <div class=\"metric-value\">{:.4} (threshold: ≥2.0)</div>", m.attention_entropy)?;
+            writeln!(file, "      <div class=\"metric-value\">{:.4} (threshold: {})</div>", m.attention_entropy, self.threshold_text("attention_entropy"))?;
             writeln!(file, "      <div class=\"metric-description\">")?;
             writeln!(file, "        <strong>What it measures:</strong> How varied the system's focus and attention is<br>")?;
             writeln!(file, "        <strong>What it Means:</strong> Consciousness requires awareness of multiple things.")?;
@@ -589,7 +1284,7 @@ impl SimulationResults {
 
             writeln!(file, "    <div class=\"metric-box\">")?;
             writeln!(file, "      <div class=\"metric-name\">2. Memory Diversity (Emotional Variance)</div>")?;
-            writeln!(file, "      <div class=\"metric-value\">{:.4} (threshold: ≥0.1)</div>", m.memory_diversity)?;
+            writeln!(file, "      <div class=\"metric-value\">{:.4} (threshold: {})</div>", m.memory_diversity, self.threshold_text("memory_diversity"))?;
             writeln!(file, "      <div class=\"metric-description\">")?;
             writeln!(file, "        <strong>What it measures:</strong> Variation in emotional responses to memories<br>")?;
             writeln!(file, "        <strong>What it Means:</strong> Conscious beings feel different emotions about different things.")?;
@@ -598,7 +1293,7 @@ impl SimulationResults {
 
             writeln!(file, "    <div class=\"metric-box\">")?;
             writeln!(file, "      <div class=\"metric-name\">3. Velocity Stability (Purposeful Motion)</div>")?;
-            writeln!(file, "      <div class=\"metric-value\">{:.4} (threshold: ≥0.8)</div>", m.velocity_stability)?;
+            writeln!(file, "      <div class=\"metric-value\">{:.4} (threshold: {})</div>", m.velocity_stability, self.threshold_text("velocity_stability"))?;
             writeln!(file, "      <div class=\"metric-description\">")?;
             writeln!(file, "        <strong>What it measures:</strong> Consistency of motion and action<br>")?;
             writeln!(file, "        <strong>What it Means:</strong> Consciousness requires agency and purposeful action.")?;
@@ -607,7 +1302,7 @@ impl SimulationResults {
 
             writeln!(file, "    <div class=\"metric-box\">")?;
             writeln!(file, "      <div class=\"metric-name\">4. Identity Coherence (Self Continuity)</div>")?;
-            writeln!(file, "      <div class=\"metric-value\">{:.4} (threshold: ≥0.7)</div>", m.identity_coherence)?;
+            writeln!(file, "      <div class=\"metric-value\">{:.4} (threshold: {})</div>", m.identity_coherence, self.threshold_text("identity_coherence"))?;
             writeln!(file, "      <div class=\"metric-description\">")?;
             writeln!(file, "        <strong>What it measures:</strong> Consistency of 'self' over time<br>")?;
             writeln!(file, "        <strong>What it Means:</strong> You're still 'you' tomorrow because you have continuity.")?;
@@ -616,7 +1311,7 @@ impl SimulationResults {
 
             writeln!(file, "    <div class=\"metric-box\">")?;
             writeln!(file, "      <div class=\"metric-name\">5. Cluster Stability (Memory Organization)</div>")?;
-            writeln!(file, "      <div class=\"metric-value\">{:.4} (threshold: ≥0.5)</div>", m.cluster_stability)?;
+            writeln!(file, "      <div class=\"metric-value\">{:.4} (threshold: {})</div>", m.cluster_stability, self.threshold_text("cluster_stability"))?;
             writeln!(file, "      <div class=\"metric-description\">")?;
             writeln!(file, "        <strong>What it measures:</strong> Organization of related memories/beliefs<br>")?;
             writeln!(file, "        <strong>What it Means:</strong> Organized thoughts allow reasoning and understanding.")?;
@@ -625,7 +1320,7 @@ impl SimulationResults {
 
             writeln!(file, "    <div class=\"metric-box\">")?;
             writeln!(file, "      <div class=\"metric-name\">6. Affective Strength (Emotional Capacity)</div>")?;
-            writeln!(file, "      <div class=\"metric-value\">{:.4} (threshold: ≥0.01)</div>", m.affective_strength)?;
+            writeln!(file, "      <div class=\"metric-value\">{:.4} (threshold: {})</div>", m.affective_strength, self.threshold_text("affective_strength"))?;
             writeln!(file, "      <div class=\"metric-description\">")?;
             writeln!(file, "        <strong>What it measures:</strong> Magnitude of emotional responses<br>")?;
             writeln!(file, "        <strong>What it Means:</strong> Emotional capacity not detected.")?;
@@ -653,8 +1348,180 @@ impl SimulationResults {
         writeln!(file, "      <p><strong>Total Interactions:</strong> {} pairwise attractions</p>", self.count_total_attractions())?;
         writeln!(file, "      <p><strong>Memory Formation:</strong> {} belief clusters formed</p>", self.count_total_clusters())?;
         writeln!(file, "      <p><strong>Peak Emotional Response:</strong> {:.4}</p>", self.max_affective_signal())?;
+        writeln!(file, "      <p><strong>Max Lineage Depth:</strong> {} generations</p>", self.max_lineage_depth())?;
+        writeln!(file, "      <p><strong>Mean Entity Age:</strong> {:.1} steps</p>", self.mean_entity_age)?;
         writeln!(file, "    </div>")?;
 
+        // Center-of-mass drift alert
+        for (axis, drift) in self.center_of_mass_drift() {
+            writeln!(file, "    <div class=\"summary-box\">")?;
+            writeln!(file, "      <p><strong>⚠️ Center-of-mass drift:</strong> the population moved monotonically along axis {} (net {:+.3}). This may indicate a broken periodic boundary or unbalanced attraction.</p>", axis, drift)?;
+            writeln!(file, "    </div>")?;
+        }
+
+        // Memory graph motifs
+        writeln!(file, "    <h2>Belief Structure Topology</h2>")?;
+        writeln!(file, "    <div class=\"summary-box\">")?;
+        match self.dominant_motif() {
+            Some((label, count)) => {
+                let total: usize = self.motif_counts.values().sum();
+                writeln!(file, "      <p><strong>Dominant Motif:</strong> {} ({} of {} connected triads)</p>", label, count, total)?;
+                let mut motifs: Vec<_> = self.motif_counts.iter().collect();
+                motifs.sort_by(|a, b| b.1.cmp(a.1).then(a.0.cmp(b.0)));
+                for (label, count) in motifs {
+                    writeln!(file, "      <p>{}: {}</p>", label, count)?;
+                }
+            }
+            None => writeln!(file, "      <p>No connected triads in the memory graphs.</p>")?,
+        }
+        writeln!(file, "    </div>")?;
+
+        // Sensitivity to initial conditions
+        if let Some(sensitivity) = &self.sensitivity {
+            let max_exponent = sensitivity.lyapunov_exponents.iter().cloned().fold(Real::MIN, Real::max);
+            let mean_exponent = if sensitivity.lyapunov_exponents.is_empty() {
+                0.0
+            } else {
+                sensitivity.lyapunov_exponents.iter().sum::<Real>() / sensitivity.lyapunov_exponents.len() as Real
+            };
+            writeln!(file, "    <h2>Sensitivity to Initial Conditions</h2>")?;
+            writeln!(file, "    <div class=\"summary-box\">")?;
+            if !sensitivity.lyapunov_exponents.is_empty() {
+                writeln!(file, "      <p><strong>Maximal Lyapunov Exponent:</strong> {:.4}</p>", max_exponent)?;
+            }
+            writeln!(file, "      <p><strong>Mean Lyapunov Exponent:</strong> {:.4}</p>", mean_exponent)?;
+            writeln!(file, "      <p><strong>Mean Divergence Time:</strong> {:.4}</p>", sensitivity.mean_divergence_time)?;
+            writeln!(file, "      <p><strong>Regime:</strong> {}</p>", if sensitivity.chaos_indicator { "Chaotic (positive maximal exponent)" } else { "Stable (no positive exponent)" })?;
+            writeln!(file, "    </div>")?;
+        }
+
+        // State-space transitions
+        if !self.state_distance_series.is_empty() {
+            writeln!(file, "    <h2>State-Space Transitions</h2>")?;
+            writeln!(file, "    <div class=\"summary-box\">")?;
+            writeln!(file, "      <p>Total variation distance between the position-velocity distributions of each step and the step {} earlier. Peaks mark dynamical event boundaries.</p>", TVD_STRIDE)?;
+            writeln!(file, "      {}", self.state_distance_svg(600.0, 150.0))?;
+            if let Some((step, distance)) = self
+                .state_distance_series
+                .iter()
+                .copied()
+                .max_by(|a, b| a.1.total_cmp(&b.1))
+            {
+                writeln!(file, "      <p><strong>Largest Transition:</strong> {:.3} at step {}</p>", distance, step)?;
+            }
+            writeln!(file, "    </div>")?;
+        }
+
+        // Temporal self-similarity
+        if self.self_similarity.len() > 1 {
+            writeln!(file, "    <h2>Temporal Self-Similarity</h2>")?;
+            writeln!(file, "    <div class=\"summary-box\">")?;
+            writeln!(file, "      <p>Cosine similarity of the eight core metrics between steps {} and {} (time runs left to right and top to bottom; blue = dissimilar, red = identical). Blocks along the diagonal are stable phases; bright off-diagonal cells are recurrences.</p>", self.self_similarity_steps.first().copied().unwrap_or(0), self.self_similarity_steps.last().copied().unwrap_or(0))?;
+            writeln!(file, "      {}", self.self_similarity_svg(400.0))?;
+            writeln!(file, "    </div>")?;
+        }
+
+        // Nonlinear dynamics
+        if !self.recurrence.is_empty() {
+            writeln!(file, "    <h2>Nonlinear Dynamics Analysis</h2>")?;
+            writeln!(file, "    <div class=\"summary-box\">")?;
+            writeln!(file, "      <p>Recurrence quantification of each metric's time series (threshold ε = {} × standard deviation). High determinism indicates structured, non-random dynamics; high laminarity indicates the metric lingers in states.</p>", RQA_THRESHOLD_FRACTION)?;
+            writeln!(file, "      <table>")?;
+            writeln!(file, "        <tr><th>Metric</th><th>ε</th><th>Recurrence Rate</th><th>Determinism</th><th>Laminarity</th><th>Trapping Time</th><th>Longest Diagonal</th></tr>")?;
+            for (name, threshold, rqa) in &self.recurrence {
+                writeln!(
+                    file,
+                    "        <tr><td>{}</td><td>{:.4}</td><td>{:.3}</td><td>{:.3}</td><td>{:.3}</td><td>{:.2}</td><td>{}</td></tr>",
+                    name, threshold, rqa.recurrence_rate, rqa.determinism, rqa.laminarity, rqa.trapping_time, rqa.max_diagonal_line
+                )?;
+            }
+            writeln!(file, "      </table>")?;
+            writeln!(file, "    </div>")?;
+        }
+
+        // Algebraic connectivity
+        if self.num_entities > 1 {
+            writeln!(file, "    <h2>Algebraic Connectivity</h2>")?;
+            writeln!(file, "    <div class=\"summary-box\">")?;
+            writeln!(file, "      <p><strong>λ₂:</strong> {:.4}</p>", self.algebraic_connectivity)?;
+            writeln!(file, "      <p>Second smallest eigenvalue of the Laplacian of the attention-weighted social graph at the end of the run. Zero means the network has split into disconnected groups; higher values mean it is well integrated and hard to disconnect.</p>")?;
+            writeln!(file, "    </div>")?;
+        }
+
+        // Emergent leaders
+        if !self.emergent_leaders.is_empty() {
+            let uniform = 1.0 / self.emergent_leaders.len() as Real;
+            writeln!(file, "    <h2>Emergent Leaders</h2>")?;
+            writeln!(file, "    <div class=\"summary-box\">")?;
+            writeln!(file, "      <p>PageRank of each entity on the social interaction graph (cumulative attraction as edge weight). A uniform population would score {:.4} each; the top {} are marked ★.</p>", uniform, TOP_LEADERS)?;
+            writeln!(file, "      <table>")?;
+            writeln!(file, "        <tr><th>Rank</th><th>Entity</th><th>PageRank</th><th>× Uniform</th></tr>")?;
+            for (rank, (id, score)) in self.emergent_leaders.iter().take(LEADER_TABLE_ROWS).enumerate() {
+                let (star, style) = if rank < TOP_LEADERS {
+                    ("★ ", " style=\"background: #fff3cd; font-weight: bold;\"")
+                } else {
+                    ("", "")
+                };
+                writeln!(
+                    file,
+                    "        <tr{}><td>{}</td><td>{}Entity {}</td><td>{:.4}</td><td>{:.2}</td></tr>",
+                    style, rank + 1, star, id, score, score / uniform
+                )?;
+            }
+            writeln!(file, "      </table>")?;
+            writeln!(file, "    </div>")?;
+        }
+
+        // Per-role breakdown
+        if !self.role_metrics.is_empty() {
+            writeln!(file, "    <h2>Metrics by Role</h2>")?;
+            writeln!(file, "    <p>Consciousness metrics computed separately over the entities holding each role at the end of the run.</p>")?;
+            writeln!(file, "    <div class=\"tabs\">")?;
+            for (i, (role, count, _)) in self.role_metrics.iter().enumerate() {
+                let checked = if i == 0 { " checked" } else { "" };
+                writeln!(file, "      <input type=\"radio\" name=\"role-tabs\" id=\"role-tab-{}\"{}>", i, checked)?;
+                writeln!(file, "      <label for=\"role-tab-{}\">{:?} ({})</label>", i, role, count)?;
+            }
+            for (i, (_, _, metrics)) in self.role_metrics.iter().enumerate() {
+                writeln!(file, "      <div class=\"tab-panel\" id=\"role-panel-{}\">", i)?;
+                writeln!(file, "        <table>")?;
+                writeln!(file, "          <tr><th>Metric</th><th>Value</th><th>Population</th></tr>")?;
+                let population = self.steps.last().map(|s| s.metrics.core_vector());
+                for (k, (name, value)) in Metrics::CORE_NAMES.iter().zip(metrics.core_vector()).enumerate() {
+                    let overall = population.map(|p| format!("{:.4}", p[k])).unwrap_or_default();
+                    writeln!(file, "          <tr><td>{}</td><td>{:.4}</td><td>{}</td></tr>", name, value, overall)?;
+                }
+                writeln!(file, "        </table>")?;
+                writeln!(file, "      </div>")?;
+            }
+            writeln!(file, "    </div>")?;
+        }
+
+        // Event log
+        if !self.events.is_empty() {
+            writeln!(file, "    <h2>Events</h2>")?;
+            writeln!(file, "    <div class=\"summary-box\">")?;
+            writeln!(file, "      <p>Discrete events recorded while stepping. Boundary wraps are counted but not listed; the first {} other events are shown in order.</p>", EVENT_REPORT_ROWS)?;
+            writeln!(file, "      <table>")?;
+            writeln!(file, "        <tr><th>Event</th><th>Count</th></tr>")?;
+            for (name, count) in self.events.counts() {
+                writeln!(file, "        <tr><td>{}</td><td>{}</td></tr>", name, count)?;
+            }
+            writeln!(file, "      </table>")?;
+            writeln!(file, "      <table>")?;
+            writeln!(file, "        <tr><th>Step</th><th>Entity</th><th>Event</th><th>Details</th></tr>")?;
+            for event in self.notable_events() {
+                let entity = event.entity_id.map(|id| id.0.to_string()).unwrap_or_default();
+                writeln!(
+                    file,
+                    "        <tr><td>{}</td><td>{}</td><td>{}</td><td>{}</td></tr>",
+                    event.step, entity, event.event_type.name(), event.details
+                )?;
+            }
+            writeln!(file, "      </table>")?;
+            writeln!(file, "    </div>")?;
+        }
+
         // Analysis
         writeln!(file, "    <h2>Detailed Analysis</h2>")?;
         writeln!(file, "    <div class=\"explanation\">")?;
@@ -684,25 +1551,230 @@ impl SimulationResults {
         Ok(())
     }
 
+    /// Ancestor chain of an entity, from its parent up to its founder.
+    ///
+    /// # Arguments
+    /// * `entity_id` - Entity whose ancestry is traced
+    pub fn ancestors(&self, entity_id: u32) -> Vec<u32> {
+        let parents = self.lineage_parents();
+        let mut chain = Vec::new();
+        let mut seen = HashSet::new();
+        let mut current = parents.get(&entity_id).copied().flatten();
+        while let Some(id) = current {
+            if !seen.insert(id) {
+                break;
+            }
+            chain.push(id);
+            current = parents.get(&id).copied().flatten();
+        }
+        chain
+    }
+
+    /// Parent of every entity in the lineage.
+    fn lineage_parents(&self) -> HashMap<u32, Option<u32>> {
+        self.lineage.iter().map(|r| (r.entity_id, r.parent_id)).collect()
+    }
+
+    /// The first events worth listing in a report, skipping boundary wraps.
+    fn notable_events(&self) -> impl Iterator<Item = &SimulationEvent> {
+        self.events
+            .events
+            .iter()
+            .filter(|e| !matches!(e.event_type, EventType::BoundaryWrap(_)))
+            .take(EVENT_REPORT_ROWS)
+    }
+
+    /// Depth of the deepest lineage (0 = only founders, 1 = children, ...).
+    pub fn max_lineage_depth(&self) -> usize {
+        self.lineage_depths().into_values().max().unwrap_or(0)
+    }
+
+    /// Number of ancestors of every entity in the lineage (and of parents
+    /// missing from it, which count as founders).
+    ///
+    /// Each chain is walked once: the walk stops at the first entity whose
+    /// depth is already known, so the whole lineage costs O(L). A cycle
+    /// (only possible in hand-edited results) gives its members its length.
+    fn lineage_depths(&self) -> HashMap<u32, usize> {
+        let parents = self.lineage_parents();
+        let mut depths: HashMap<u32, usize> = HashMap::with_capacity(parents.len());
+        let mut path = Vec::new();
+        let mut on_path = HashMap::new();
+        for record in &self.lineage {
+            path.clear();
+            on_path.clear();
+            // Depth of the parent of the last entity on the path, if known
+            let mut known = None;
+            let mut current = record.entity_id;
+            loop {
+                if let Some(&depth) = depths.get(&current) {
+                    known = Some(depth);
+                    break;
+                }
+                if let Some(&start) = on_path.get(&current) {
+                    let cycle = path.len() - start;
+                    for id in path.drain(start..) {
+                        depths.insert(id, cycle);
+                    }
+                    known = Some(cycle);
+                    break;
+                }
+                on_path.insert(current, path.len());
+                path.push(current);
+                match parents.get(&current).copied().flatten() {
+                    Some(parent) => current = parent,
+                    None => break,
+                }
+            }
+            let first = known.map_or(0, |d| d + 1);
+            for (depth, &id) in (first..).zip(path.iter().rev()) {
+                depths.insert(id, depth);
+            }
+        }
+        depths
+    }
+
+    /// Detect a monotonic drift of the center of mass.
+    ///
+    /// An axis drifts when, over at least `MIN_DRIFT_STEPS` recorded steps,
+    /// every non-zero change of its center-of-mass coordinate has the same
+    /// sign. This usually indicates a broken periodic boundary or unbalanced
+    /// attraction.
+    ///
+    /// # Returns
+    /// (axis, net displacement) for each drifting axis
+    pub fn center_of_mass_drift(&self) -> Vec<(usize, Real)> {
+        let track: Vec<&Vec<Real>> = self
+            .steps
+            .iter()
+            .map(|s| &s.center_of_mass)
+            .filter(|c| !c.is_empty())
+            .collect();
+        if track.len() < MIN_DRIFT_STEPS {
+            return Vec::new();
+        }
+
+        let dim = track.iter().map(|c| c.len()).min().unwrap_or(0);
+        (0..dim)
+            .filter_map(|axis| {
+                let deltas: Vec<Real> = track
+                    .windows(2)
+                    .map(|w| w[1][axis] - w[0][axis])
+                    .filter(|d| d.abs() > 1e-6)
+                    .collect();
+                let monotonic = deltas.iter().all(|d| *d > 0.0) || deltas.iter().all(|d| *d < 0.0);
+                if deltas.is_empty() || !monotonic {
+                    return None;
+                }
+                Some((axis, track[track.len() - 1][axis] - track[0][axis]))
+            })
+            .collect()
+    }
+
+    /// Render the state-distance series as an inline SVG line chart.
+    ///
+    /// The y axis spans the full [0, 1] range of the distance.
+    fn state_distance_svg(&self, width: Real, height: Real) -> String {
+        let first = self.state_distance_series.first().map_or(0, |p| p.0) as Real;
+        let last = self.state_distance_series.last().map_or(0, |p| p.0) as Real;
+        let span = (last - first).max(1.0);
+        let points: Vec<String> = self
+            .state_distance_series
+            .iter()
+            .map(|(step, distance)| {
+                let x = (*step as Real - first) / span * width;
+                let y = (1.0 - distance.clamp(0.0, 1.0)) * height;
+                format!("{:.1},{:.1}", x, y)
+            })
+            .collect();
+        format!(
+            "<svg width=\"{w}\" height=\"{h}\" style=\"background:#fff;border:1px solid #ddd\"><polyline fill=\"none\" stroke=\"#4a90d9\" stroke-width=\"1.5\" points=\"{p}\"/></svg>",
+            w = width,
+            h = height,
+            p = points.join(" ")
+        )
+    }
+
+    /// Render the self-similarity matrix as an inline SVG heatmap.
+    ///
+    /// Similarities are clamped to [0, 1] and mapped from blue to red.
+    fn self_similarity_svg(&self, size: Real) -> String {
+        let n = self.self_similarity.len();
+        let cell = size / n as Real;
+        let mut svg = format!(
+            "<svg width=\"{s}\" height=\"{s}\" shape-rendering=\"crispEdges\" style=\"border:1px solid #ddd\">",
+            s = size
+        );
+        for (i, row) in self.self_similarity.iter().enumerate() {
+            for (j, similarity) in row.iter().enumerate() {
+                let hue = 240.0 * (1.0 - similarity.clamp(0.0, 1.0));
+                svg.push_str(&format!(
+                    "<rect x=\"{:.2}\" y=\"{:.2}\" width=\"{:.2}\" height=\"{:.2}\" fill=\"hsl({:.0},75%,50%)\"/>",
+                    j as Real * cell,
+                    i as Real * cell,
+                    cell,
+                    cell,
+                    hue
+                ));
+            }
+        }
+        svg.push_str("</svg>");
+        svg
+    }
+
+    /// Most frequent memory-graph motif and its count.
+    pub fn dominant_motif(&self) -> Option<(&str, usize)> {
+        self.motif_counts
+            .iter()
+            .max_by(|a, b| a.1.cmp(b.1).then(b.0.cmp(a.0)))
+            .map(|(label, count)| (label.as_str(), *count))
+    }
+
+    /// When every criterion was first met, for the reports.
+    fn first_achieved_text(&self) -> String {
+        match self.consciousness_analysis.first_achieved_step {
+            Some(step) => format!("step {} of {}", step, self.steps_run()),
+            None => "never".to_string(),
+        }
+    }
+
+    /// Number of steps the run executed (the last recorded step is the
+    /// final one, whatever the recording cadence).
+    pub fn steps_run(&self) -> u64 {
+        self.steps.last().map_or(0, |step| step.step_number + 1)
+    }
+
+    /// Scale a total over the recorded steps to an estimate over every
+    /// step run (exact when every step is recorded).
+    fn per_run_total(&self, recorded: usize) -> usize {
+        if self.steps.is_empty() {
+            return 0;
+        }
+        let scale = self.steps_run() as f64 / self.steps.len() as f64;
+        (recorded as f64 * scale).round() as usize
+    }
+
     fn count_total_attractions(&self) -> usize {
-        self.steps.iter().map(|s| s.attractions.len()).sum()
+        self.per_run_total(self.steps.iter().map(|s| s.attractions.len()).sum())
     }
 
     fn count_total_clusters(&self) -> usize {
-        self.steps
-            .iter()
-            .map(|s| s.belief_clusters.iter().map(|(_, c)| c.len()).sum::<usize>())
-            .sum()
+        self.per_run_total(
+            self.steps
+                .iter()
+                .map(|s| s.belief_clusters.iter().map(|(_, c)| c.len()).sum::<usize>())
+                .sum(),
+        )
     }
 
-    fn average_clusters_per_entity(&self) -> f32 {
+    fn average_clusters_per_entity(&self) -> Real {
         if self.num_entities == 0 {
             return 0.0;
         }
-        self.count_total_clusters() as f32 / (self.num_steps as f32 * self.num_entities as f32)
+        self.count_total_clusters() as Real / (self.num_steps as Real * self.num_entities as Real)
     }
 
-    fn max_affective_signal(&self) -> f32 {
+    fn max_affective_signal(&self) -> Real {
         self.steps
             .iter()
             .flat_map(|s| {
@@ -710,7 +1782,7 @@ impl SimulationResults {
                     bc.iter().map(|(_, signal, _)| signal.abs())
                 })
             })
-            .fold(0.0, f32::max)
+            .fold(0.0, Real::max)
     }
 
     fn write_primitives_contribution_text(&self, file: &mut std::fs::File) -> Result<(), Box<dyn std::error::Error>> {
@@ -862,22 +1934,45 @@ impl Default for ConsciousnessAnalysis {
             consciousness_score: 0.0,
             consciousness_achieved: false,
             reasoning: String::new(),
+            first_achieved_step: None,
         }
     }
 }
 
 impl SimulationStep {
     pub fn new(step_number: u64, metrics: Metrics) -> Self {
+        Self::with_capacity(step_number, metrics, 0, 0)
+    }
+
+    /// Create a step with its per-entity vectors pre-sized.
+    ///
+    /// # Arguments
+    /// * `step_number` - Step being recorded
+    /// * `metrics` - Metrics snapshot of the step
+    /// * `entities` - Capacity of the per-entity vectors (positions,
+    ///   velocities, essence, energy, belief clusters)
+    /// * `attractions` - Capacity of the attraction list, e.g. the length
+    ///   of the previous step's list
+    pub fn with_capacity(step_number: u64, metrics: Metrics, entities: usize, attractions: usize) -> Self {
         SimulationStep {
             step_number,
-            timestamp: step_number as f32 * 0.01, // Assuming dt=0.01
-            attractions: Vec::new(),
+            timestamp: step_number as Real * 0.01, // Assuming dt=0.01
+            attractions: Vec::with_capacity(attractions),
             attentions: Vec::new(),
-            belief_clusters: Vec::new(),
-            entity_positions: Vec::new(),
-            entity_velocities: Vec::new(),
-            entity_essence: Vec::new(),
+            belief_clusters: Vec::with_capacity(entities),
+            entity_positions: Vec::with_capacity(entities),
+            entity_velocities: Vec::with_capacity(entities),
+            entity_essence: Vec::with_capacity(entities),
+            entity_energy: Vec::with_capacity(entities),
             metrics,
+            species_metrics: Vec::new(),
+            injected_stimuli: Vec::new(),
+            scheduled_values: Vec::new(),
+            signals_emitted: 0,
+            signals_received: 0,
+            center_of_mass: Vec::new(),
+            mean_velocity: Vec::new(),
+            entity_roles: Vec::new(),
         }
     }
 }
@@ -0,0 +1,130 @@
+//! Roles module: emergent leader/follower dynamics.
+//!
+//! Each step every entity distributes its attention over the others with a
+//! softmax of their mutual attention strengths. The total attention an
+//! entity *receives* measures how central it is:
+//! - Entities at or above a percentile of received attention (and above the
+//!   mean) are (re)confirmed as leaders, which boosts their attraction weight
+//! - Leadership decays geometrically, so a leader keeps its role for a few
+//!   steps after it stops being central
+//! - Every other entity becomes a follower of the leader it attends to most
+//!   and biases its motion toward that leader
+//!
+//! ## Role Stability
+//!
+//! Role stability is the Jaccard similarity between the leader sets of
+//! consecutive steps: 1 when leadership never changes hands, 0 when the
+//! leaders are replaced entirely.
+//!
+//! ## Per-Role Metrics
+//!
+//! `EntityPool::partition_by_role` and `Metrics::compute_by_role` compare
+//! the metric profiles of the role groups; finalized results keep the
+//! breakdown and the HTML report shows it with one tab per role.
+//!
+//! ## Author
+//! Ayomide I. Daniels (Morningstar)
+
+use crate::real::Real;
+use crate::entities::EntityId;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
+
+/// Leadership level at or above which an entity holds the leader role.
+pub const LEADER_RETENTION: Real = 0.5;
+
+/// Configuration for role dynamics.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct RoleConfig {
+    /// Enable role assignment.
+    pub enabled: bool,
+    /// Percentile (0-1) of received attention required to become leader.
+    pub leader_percentile: Real,
+    /// Relative attraction weight boost of leaders (0.5 = +50%).
+    pub attraction_boost: Real,
+    /// Magnitude of the force pulling followers toward their leader.
+    pub follower_bias: Real,
+    /// Per-step multiplicative decay of leadership.
+    pub decay: Real,
+}
+
+impl Default for RoleConfig {
+    fn default() -> Self {
+        RoleConfig {
+            enabled: false,
+            leader_percentile: 0.9,
+            attraction_boost: 0.5,
+            follower_bias: 0.02,
+            decay: 0.8,
+        }
+    }
+}
+
+impl RoleConfig {
+    /// Check parameter ranges.
+    pub fn is_valid(&self) -> bool {
+        (0.0..=1.0).contains(&self.leader_percentile)
+            && (0.0..=1.0).contains(&self.decay)
+            && self.attraction_boost >= 0.0
+            && self.follower_bias >= 0.0
+    }
+}
+
+/// Social role of an entity.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum Role {
+    /// No role (role dynamics disabled or no leader present).
+    #[default]
+    Member,
+    /// Highly attended entity with boosted attraction.
+    Leader,
+    /// Entity biased toward its most-attended leader.
+    Follower,
+}
+
+/// Attention received by each entity.
+///
+/// # Arguments
+/// * `attention` - Square matrix where row `i` is the attention entity `i`
+///   pays to every other entity (each row sums to 1 over `j != i`)
+///
+/// # Returns
+/// Column sums (excluding the diagonal)
+pub fn received_attention(attention: &[Vec<Real>]) -> Vec<Real> {
+    let n = attention.len();
+    (0..n)
+        .map(|j| (0..n).filter(|&i| i != j).map(|i| attention[i][j]).sum())
+        .collect()
+}
+
+/// Indices of entities qualifying for leadership this step.
+///
+/// An entity qualifies if its received attention is at or above the given
+/// percentile of all values and strictly above the mean (so a perfectly
+/// uniform population has no leader).
+pub fn leader_candidates(received: &[Real], percentile: Real) -> Vec<usize> {
+    if received.is_empty() {
+        return Vec::new();
+    }
+    let mut sorted = received.to_vec();
+    sorted.sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
+    let rank = (percentile * (sorted.len() - 1) as Real).ceil() as usize;
+    let threshold = sorted[rank.min(sorted.len() - 1)];
+    let mean = received.iter().sum::<Real>() / received.len() as Real;
+
+    received
+        .iter()
+        .enumerate()
+        .filter(|(_, &r)| r >= threshold && r > mean)
+        .map(|(i, _)| i)
+        .collect()
+}
+
+/// Jaccard similarity of two leader sets (1 if both are empty).
+pub fn role_stability(previous: &BTreeSet<EntityId>, current: &BTreeSet<EntityId>) -> Real {
+    let union = previous.union(current).count();
+    if union == 0 {
+        return 1.0;
+    }
+    previous.intersection(current).count() as Real / union as Real
+}
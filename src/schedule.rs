@@ -0,0 +1,164 @@
+//! Schedule module: parameters that change over the course of a run.
+//!
+//! The `schedules` config section maps a parameter path to a
+//! [`ParameterSchedule`], e.g. to anneal damping or ramp up the response to
+//! experiences:
+//!
+//! ```toml
+//! [schedules."dynamics.damping"]
+//! kind = "linear"
+//! start = 0.9
+//! end = 0.99
+//! to_step = 2000
+//!
+//! [schedules."essence.experience_scale"]
+//! kind = "step"
+//! start = 1.0
+//! end = 2.0
+//! to_step = 1000
+//! ```
+//!
+//! Every schedule holds `start` up to `from_step` (default 0) and `end`
+//! from `to_step` on. In between, `linear` interpolates, `exponential`
+//! interpolates geometrically (both values must be positive) and `step`
+//! keeps `start`, switching to `end` at `to_step`.
+//!
+//! The simulation sets every scheduled parameter at the start of each step,
+//! before the phases run, and records the values in effect with the step.
+//! The paths that can be scheduled are [`SCHEDULABLE_PARAMETERS`]; besides
+//! configuration fields, `stimulus_amplitude` sets the sensory noise
+//! amplitude of every entity.
+//!
+//! ## Author
+//! Ayomide I. Daniels (Morningstar)
+
+use crate::config::SimulationConfig;
+use crate::real::Real;
+use serde::{Deserialize, Serialize};
+
+/// Path of the per-entity sensory noise amplitude.
+pub const STIMULUS_AMPLITUDE: &str = "stimulus_amplitude";
+
+/// Parameter paths that may be scheduled.
+pub const SCHEDULABLE_PARAMETERS: [&str; 17] = [
+    "attraction.sigma",
+    "attraction.lambda",
+    "attraction.theta",
+    "state.decay_alpha",
+    "state.beta_attention",
+    "state.gamma_memory",
+    "dynamics.min_speed",
+    "dynamics.damping",
+    "dynamics.max_action_force",
+    "dynamics.max_turn_rate",
+    "essence.baseline",
+    "essence.decay",
+    "essence.experience_scale",
+    "signaling.emit_probability",
+    "signaling.essence_extremity",
+    "signaling.hearing_range",
+    STIMULUS_AMPLITUDE,
+];
+
+/// Shape of a schedule between its start and end step.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ScheduleKind {
+    /// Straight line from `start` to `end`
+    Linear,
+    /// Constant ratio per step from `start` to `end`
+    Exponential,
+    /// `start` until `to_step`, then `end`
+    Step,
+}
+
+/// Value of one parameter over the steps of a run.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ParameterSchedule {
+    pub kind: ScheduleKind,
+    /// Value up to `from_step`
+    pub start: Real,
+    /// Value from `to_step` on
+    pub end: Real,
+    /// First step of the transition
+    #[serde(default)]
+    pub from_step: u64,
+    /// Step at which `end` is reached
+    pub to_step: u64,
+}
+
+impl ParameterSchedule {
+    /// Value in effect at `step`.
+    pub fn value_at(&self, step: u64) -> Real {
+        if step >= self.to_step {
+            return self.end;
+        }
+        if step <= self.from_step || self.kind == ScheduleKind::Step {
+            return self.start;
+        }
+        let progress = (step - self.from_step) as Real / (self.to_step - self.from_step) as Real;
+        match self.kind {
+            ScheduleKind::Linear => self.start + (self.end - self.start) * progress,
+            ScheduleKind::Exponential => self.start * (self.end / self.start).powf(progress),
+            ScheduleKind::Step => self.start,
+        }
+    }
+
+    /// Check the schedule is well formed.
+    ///
+    /// # Returns
+    /// What is wrong: non-finite values, a step range ending before it
+    /// starts, or an exponential schedule without positive values
+    pub fn check(&self) -> Result<(), String> {
+        if !(self.start.is_finite() && self.end.is_finite()) {
+            return Err("start and end must be finite".to_string());
+        }
+        if self.to_step < self.from_step {
+            return Err(format!("to_step {} is before from_step {}", self.to_step, self.from_step));
+        }
+        if self.kind == ScheduleKind::Exponential && !(self.start > 0.0 && self.end > 0.0) {
+            return Err("exponential schedules need positive start and end".to_string());
+        }
+        Ok(())
+    }
+
+    /// Short description, e.g. `linear 0.9 → 0.99 over steps 0-2000`.
+    pub fn describe(&self) -> String {
+        let kind = match self.kind {
+            ScheduleKind::Linear => "linear",
+            ScheduleKind::Exponential => "exponential",
+            ScheduleKind::Step => "step",
+        };
+        match self.kind {
+            ScheduleKind::Step => format!("{} {} → {} at step {}", kind, self.start, self.end, self.to_step),
+            _ => format!("{} {} → {} over steps {}-{}", kind, self.start, self.end, self.from_step, self.to_step),
+        }
+    }
+}
+
+/// Configuration field at a schedulable path.
+///
+/// # Returns
+/// None for unknown paths and for [`STIMULUS_AMPLITUDE`], which is not a
+/// configuration field
+pub fn parameter_mut<'a>(config: &'a mut SimulationConfig, path: &str) -> Option<&'a mut Real> {
+    Some(match path {
+        "attraction.sigma" => &mut config.attraction.sigma,
+        "attraction.lambda" => &mut config.attraction.lambda,
+        "attraction.theta" => &mut config.attraction.theta,
+        "state.decay_alpha" => &mut config.state.decay_alpha,
+        "state.beta_attention" => &mut config.state.beta_attention,
+        "state.gamma_memory" => &mut config.state.gamma_memory,
+        "dynamics.min_speed" => &mut config.dynamics.min_speed,
+        "dynamics.damping" => &mut config.dynamics.damping,
+        "dynamics.max_action_force" => &mut config.dynamics.max_action_force,
+        "dynamics.max_turn_rate" => &mut config.dynamics.max_turn_rate,
+        "essence.baseline" => &mut config.essence.baseline,
+        "essence.decay" => &mut config.essence.decay,
+        "essence.experience_scale" => &mut config.essence.experience_scale,
+        "signaling.emit_probability" => &mut config.signaling.emit_probability,
+        "signaling.essence_extremity" => &mut config.signaling.essence_extremity,
+        "signaling.hearing_range" => &mut config.signaling.hearing_range,
+        _ => return None,
+    })
+}
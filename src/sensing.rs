@@ -0,0 +1,156 @@
+//! Sensing module: how entities perceive their environment each step.
+//!
+//! During the sense phase every entity receives one stimulus vector (one
+//! component per spatial dimension), which is stored in its memory graph
+//! and clustered into its beliefs. A [`SenseModel`] produces that vector:
+//! - [`NoiseSense`] (default): uniform noise scaled by the entity's
+//!   `stimulus_amplitude`, independent of the surroundings
+//! - [`NeighborSense`]: encodes the essence and distance of nearby entities,
+//!   so that what an entity experiences depends on who is around it
+//!
+//! Custom models are passed to `Simulation::with_sense_model`. A configured
+//! stimulus plugin (`plugin::StimulusSource`) still takes precedence; the
+//! model supplies the stimulus whenever the plugin returns none.
+//!
+//! ## Neighbor Encoding
+//!
+//! With neighbors at distances `d_i` within `radius`, weighted by
+//! proximity `w_i = 1 - d_i / radius`:
+//! - component 0: valence, `Σ w_i (e_i - b_i) / b_i / Σ w_i` for essence
+//!   `e_i` and baseline `b_i`, clamped to [-1, 1]; above 0.5 the memory
+//!   graph reads the event as positive, below -0.5 as negative
+//! - component 1: proximity of the nearest neighbor, `max w_i`
+//! - component 2: crowding, `W / (1 + W)` with `W = Σ w_i`, which grows
+//!   toward 1 as more entities come close
+//!
+//! Further components are zero. The entity's own noise is added on top.
+//!
+//! ## Author
+//! Ayomide I. Daniels (Morningstar)
+
+use crate::entities::Entity;
+use crate::geometry::{periodic_distance, GeometryConfig};
+use crate::real::Real;
+use crate::simulation::SimulationRng;
+use rand::Rng;
+
+/// Model producing the stimulus an entity senses each step.
+pub trait SenseModel: Send {
+    /// Stimulus sensed by `entity`.
+    ///
+    /// Entities are visited in id order each step, and every random draw
+    /// must come from `rng` so that runs stay reproducible.
+    ///
+    /// # Arguments
+    /// * `entity` - Sensing entity
+    /// * `neighbors` - Other entities within [`SenseModel::neighbor_radius`],
+    ///   in id order (empty when the model asks for none)
+    /// * `rng` - The simulation's random stream
+    ///
+    /// # Returns
+    /// A vector with one component per spatial dimension
+    fn sense(&mut self, entity: &Entity, neighbors: &[&Entity], rng: &mut SimulationRng) -> Vec<Real>;
+
+    /// Write the stimulus sensed by `entity` into `stimulus`.
+    ///
+    /// The simulation calls this with a buffer it reuses across steps. The
+    /// default replaces the buffer with the result of [`SenseModel::sense`];
+    /// models that can fill it in place override this to avoid allocating.
+    fn sense_into(&mut self, entity: &Entity, neighbors: &[&Entity], rng: &mut SimulationRng, stimulus: &mut Vec<Real>) {
+        *stimulus = self.sense(entity, neighbors, rng);
+    }
+
+    /// Distance within which other entities are passed as neighbors.
+    ///
+    /// Collecting neighbors indexes every position in a spatial grid each
+    /// step, so models that do not need them return None (the default).
+    fn neighbor_radius(&self) -> Option<Real> {
+        None
+    }
+}
+
+/// Uniform noise in `(-amplitude, amplitude)` per component.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct NoiseSense;
+
+impl SenseModel for NoiseSense {
+    fn sense(&mut self, entity: &Entity, neighbors: &[&Entity], rng: &mut SimulationRng) -> Vec<Real> {
+        let mut stimulus = Vec::new();
+        self.sense_into(entity, neighbors, rng, &mut stimulus);
+        stimulus
+    }
+
+    fn sense_into(&mut self, entity: &Entity, _neighbors: &[&Entity], rng: &mut SimulationRng, stimulus: &mut Vec<Real>) {
+        noise_into(entity, rng, stimulus);
+    }
+}
+
+/// Stimulus encoding the essence and distance of nearby entities (see the
+/// module docs), plus the entity's own noise.
+#[derive(Clone, Debug)]
+pub struct NeighborSense {
+    /// Distance beyond which entities are not perceived
+    pub radius: Real,
+    /// World geometry, for distances under periodic boundaries
+    pub geometry: GeometryConfig,
+}
+
+impl NeighborSense {
+    /// Neighbor-aware sensing within `radius`.
+    ///
+    /// # Arguments
+    /// * `radius` - Perception range (positive)
+    /// * `geometry` - Geometry of the simulated world
+    pub fn new(radius: Real, geometry: &GeometryConfig) -> Self {
+        NeighborSense { radius, geometry: geometry.clone() }
+    }
+
+    /// Proximity weight of a neighbor, zero at or beyond the radius.
+    fn proximity(&self, entity: &Entity, neighbor: &Entity) -> Real {
+        let distance = periodic_distance(&entity.pose.position, &neighbor.pose.position, &self.geometry);
+        (1.0 - distance / self.radius).max(0.0)
+    }
+}
+
+impl SenseModel for NeighborSense {
+    fn sense(&mut self, entity: &Entity, neighbors: &[&Entity], rng: &mut SimulationRng) -> Vec<Real> {
+        let mut stimulus = Vec::new();
+        self.sense_into(entity, neighbors, rng, &mut stimulus);
+        stimulus
+    }
+
+    fn sense_into(&mut self, entity: &Entity, neighbors: &[&Entity], rng: &mut SimulationRng, stimulus: &mut Vec<Real>) {
+        noise_into(entity, rng, stimulus);
+        let (mut total, mut valence, mut nearest) = (0.0, 0.0, 0.0 as Real);
+        for neighbor in neighbors {
+            let weight = self.proximity(entity, neighbor);
+            let baseline = neighbor.essence.config.baseline;
+            if baseline > 0.0 {
+                valence += weight * (neighbor.essence.value - baseline) / baseline;
+            }
+            total += weight;
+            nearest = nearest.max(weight);
+        }
+
+        let encoded = [
+            if total > 0.0 { (valence / total).clamp(-1.0, 1.0) } else { 0.0 },
+            nearest,
+            total / (1.0 + total),
+        ];
+        for (component, value) in stimulus.iter_mut().zip(encoded) {
+            *component += value;
+        }
+    }
+
+    fn neighbor_radius(&self) -> Option<Real> {
+        Some(self.radius)
+    }
+}
+
+/// Overwrite `stimulus` with uniform noise scaled by the entity's stimulus
+/// amplitude.
+fn noise_into(entity: &Entity, rng: &mut SimulationRng, stimulus: &mut Vec<Real>) {
+    let amplitude = entity.stimulus_amplitude;
+    stimulus.clear();
+    stimulus.extend((0..entity.pose.position.len()).map(|_| rng.gen_range(-1.0..1.0) * amplitude));
+}
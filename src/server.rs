@@ -0,0 +1,303 @@
+//! Server module: live streaming of simulation state over WebSocket.
+//!
+//! Enabled by the `server` feature. An [`UpdateServer`] accepts any number
+//! of WebSocket clients and broadcasts one JSON frame per sampled step.
+//! Publishing never blocks the simulation: each client has a small bounded
+//! queue served by its own writer thread, and frames that do not fit in a
+//! slow client's queue are dropped for that client.
+//!
+//! ## Protocol
+//!
+//! Clients connect with a plain WebSocket handshake to `ws://<addr>/` (any
+//! path is accepted) and only receive; messages sent by clients are ignored.
+//! Every message is a UTF-8 text frame holding one JSON object:
+//!
+//! ```json
+//! {
+//!   "type": "step",
+//!   "step": 120,
+//!   "dimension": 2,
+//!   "entities": [{ "id": 1, "position": [3.2, 7.9], "essence": 5.1 }],
+//!   "metrics": { "timestamp": 120, "attention_entropy": 2.3, ... },
+//!   "events": [{ "step": 118, "event_type": "ClusterBorn", "entity_id": 4, "details": "..." }]
+//! }
+//! ```
+//!
+//! - `entities` is ordered by id; `metrics` is `null` before the first step
+//! - `events` holds the events detected since the previous sampled frame
+//!   (events in a frame dropped for a slow client are not re-sent)
+//! - `step` increases between frames but skips by the sample interval and
+//!   by any dropped frames
+//!
+//! ## Author
+//! Ayomide I. Daniels (Morningstar)
+
+use crate::real::Real;
+use crate::entities::EntityId;
+use crate::events::{EventLog, EventTracker, SimulationEvent};
+use crate::metrics::Metrics;
+use crate::results::SimulationResults;
+use crate::simulation::Simulation;
+use serde::{Deserialize, Serialize};
+use std::io;
+use std::net::{SocketAddr, TcpListener, TcpStream};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{self, SyncSender, TrySendError};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::Duration;
+
+/// How often the acceptor checks for shutdown while idle.
+const ACCEPT_POLL_INTERVAL: Duration = Duration::from_millis(50);
+
+/// Streaming parameters.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(default)]
+pub struct StreamConfig {
+    /// Broadcast every n-th step
+    pub sample_interval: u64,
+    /// Frames buffered per client before new frames are dropped
+    pub queue_capacity: usize,
+}
+
+impl Default for StreamConfig {
+    fn default() -> Self {
+        StreamConfig {
+            sample_interval: 1,
+            queue_capacity: 8,
+        }
+    }
+}
+
+impl StreamConfig {
+    /// Check that the interval and queue size are positive.
+    pub fn is_valid(&self) -> bool {
+        self.sample_interval > 0 && self.queue_capacity > 0
+    }
+}
+
+/// One entity in a frame.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct EntityFrame {
+    pub id: EntityId,
+    pub position: Vec<Real>,
+    pub essence: Real,
+}
+
+/// One broadcast message (see the module docs for the wire format).
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct StepFrame {
+    #[serde(rename = "type")]
+    pub kind: String,
+    pub step: u64,
+    pub dimension: usize,
+    pub entities: Vec<EntityFrame>,
+    pub metrics: Option<Metrics>,
+    pub events: Vec<SimulationEvent>,
+}
+
+impl StepFrame {
+    /// Capture the simulation's current state.
+    ///
+    /// # Arguments
+    /// * `sim` - Simulation to capture
+    /// * `events` - Events to attach to the frame
+    pub fn capture(sim: &Simulation, events: &[SimulationEvent]) -> Self {
+        let entities: Vec<EntityFrame> = sim
+            .entities
+            .iter()
+            .map(|e| EntityFrame {
+                id: e.id,
+                position: e.pose.position.to_vec(),
+                essence: e.essence.value,
+            })
+            .collect();
+        StepFrame {
+            kind: "step".to_string(),
+            step: sim.timestamp,
+            dimension: sim.config.geometry.dimension,
+            entities,
+            metrics: sim.metrics_history.last().cloned(),
+            events: events.to_vec(),
+        }
+    }
+}
+
+/// Counters of a streaming session.
+#[derive(Clone, Debug, Default)]
+pub struct StreamStats {
+    /// Frames queued for at least one client
+    pub frames_sent: u64,
+    /// Per-client frames dropped because the client's queue was full
+    pub frames_dropped: u64,
+}
+
+/// WebSocket broadcaster of simulation frames.
+///
+/// The listener runs on a background thread until the server is dropped.
+pub struct UpdateServer {
+    local_addr: SocketAddr,
+    clients: Arc<Mutex<Vec<SyncSender<String>>>>,
+    shutdown: Arc<AtomicBool>,
+    queue_capacity: usize,
+    stats: StreamStats,
+}
+
+impl UpdateServer {
+    /// Bind to `addr` and start accepting clients.
+    ///
+    /// # Arguments
+    /// * `addr` - Listen address, e.g. `"0.0.0.0:9001"` (port 0 picks a free port)
+    /// * `queue_capacity` - Frames buffered per client
+    ///
+    /// # Returns
+    /// The running server, or the bind error
+    pub fn bind(addr: &str, queue_capacity: usize) -> io::Result<Self> {
+        let listener = TcpListener::bind(addr)?;
+        listener.set_nonblocking(true)?;
+        let local_addr = listener.local_addr()?;
+        let clients = Arc::new(Mutex::new(Vec::new()));
+        let shutdown = Arc::new(AtomicBool::new(false));
+
+        let accept_clients = Arc::clone(&clients);
+        let accept_shutdown = Arc::clone(&shutdown);
+        let capacity = queue_capacity.max(1);
+        thread::spawn(move || {
+            while !accept_shutdown.load(Ordering::Relaxed) {
+                match listener.accept() {
+                    Ok((stream, _)) => {
+                        let (tx, rx) = mpsc::sync_channel(capacity);
+                        if let Ok(mut clients) = accept_clients.lock() {
+                            clients.push(tx);
+                        }
+                        thread::spawn(move || serve_client(stream, rx));
+                    }
+                    // WouldBlock while idle; transient accept errors are retried
+                    Err(_) => thread::sleep(ACCEPT_POLL_INTERVAL),
+                }
+            }
+        });
+
+        Ok(UpdateServer {
+            local_addr,
+            clients,
+            shutdown,
+            queue_capacity: capacity,
+            stats: StreamStats::default(),
+        })
+    }
+
+    /// Address the server is listening on.
+    pub fn local_addr(&self) -> SocketAddr {
+        self.local_addr
+    }
+
+    /// Number of connected clients.
+    pub fn client_count(&self) -> usize {
+        self.clients.lock().map(|c| c.len()).unwrap_or(0)
+    }
+
+    /// Frames buffered per client.
+    pub fn queue_capacity(&self) -> usize {
+        self.queue_capacity
+    }
+
+    /// Counters so far.
+    pub fn stats(&self) -> &StreamStats {
+        &self.stats
+    }
+
+    /// Broadcast a frame to every client without blocking.
+    ///
+    /// Clients whose queue is full miss this frame; disconnected clients
+    /// are removed.
+    pub fn publish(&mut self, frame: &StepFrame) {
+        let Ok(message) = serde_json::to_string(frame) else {
+            return;
+        };
+        let Ok(mut clients) = self.clients.lock() else {
+            return;
+        };
+        let mut delivered = false;
+        let mut dropped = 0;
+        clients.retain(|client| match client.try_send(message.clone()) {
+            Ok(()) => {
+                delivered = true;
+                true
+            }
+            Err(TrySendError::Full(_)) => {
+                dropped += 1;
+                true
+            }
+            Err(TrySendError::Disconnected(_)) => false,
+        });
+        if delivered {
+            self.stats.frames_sent += 1;
+        }
+        self.stats.frames_dropped += dropped;
+    }
+}
+
+impl Drop for UpdateServer {
+    fn drop(&mut self) {
+        self.shutdown.store(true, Ordering::Relaxed);
+    }
+}
+
+/// Complete the handshake and forward queued frames until either side closes.
+fn serve_client(stream: TcpStream, frames: mpsc::Receiver<String>) {
+    if stream.set_nonblocking(false).is_err() {
+        return;
+    }
+    let Ok(mut socket) = tungstenite::accept(stream) else {
+        return;
+    };
+    for frame in frames {
+        if socket.send(tungstenite::Message::Text(frame)).is_err() {
+            return;
+        }
+    }
+    let _ = socket.close(None);
+}
+
+impl Simulation {
+    /// Run the configured number of steps while streaming frames over WebSocket.
+    ///
+    /// Binds an [`UpdateServer`] on `addr` and broadcasts every
+    /// `config.sample_interval`-th step with the events detected since the
+    /// previous frame. The run never waits for clients. Results are
+    /// finalized at the end.
+    ///
+    /// # Arguments
+    /// * `addr` - Listen address, e.g. `"0.0.0.0:9001"`
+    /// * `config` - Sample interval and per-client queue size
+    ///
+    /// # Returns
+    /// (finalized results, event log, streaming counters), or a bind error
+    pub fn serve_updates(
+        &mut self,
+        addr: &str,
+        config: &StreamConfig,
+    ) -> io::Result<(SimulationResults, EventLog, StreamStats)> {
+        if !config.is_valid() {
+            return Err(io::Error::new(io::ErrorKind::InvalidInput, "sample_interval and queue_capacity must be positive"));
+        }
+        let mut server = UpdateServer::bind(addr, config.queue_capacity)?;
+        let mut log = EventLog::new();
+        let mut tracker = EventTracker::new(self);
+        let mut pending_from = 0;
+
+        for _ in 0..self.config.simulation.num_steps {
+            let step = self.timestamp;
+            self.step();
+            tracker.observe(self, step, &mut log);
+            if self.timestamp.is_multiple_of(config.sample_interval) {
+                server.publish(&StepFrame::capture(self, &log.events[pending_from..]));
+                pending_from = log.events.len();
+            }
+        }
+
+        self.finalize_results();
+        Ok((self.results.clone(), log, server.stats().clone()))
+    }
+}
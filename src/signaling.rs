@@ -0,0 +1,106 @@
+//! Signaling module: explicit typed messages between entities.
+//!
+//! Beyond the implicit pull of the attraction field, entities can emit a
+//! small message vector derived from their dominant belief-cluster
+//! prototype. Messages are:
+//! - Emitted with a configurable probability, only by entities whose
+//!   essence is far enough from baseline (strong affect)
+//! - Queued on the simulation and delivered during the next sense step
+//! - Received by every other entity within hearing range as a distinct
+//!   stimulus, linearly attenuated by distance
+//!
+//! ## Attenuation
+//!
+//! A message heard at distance `d` from its origin is scaled by
+//! `1 - d / range`; entities beyond `range` receive nothing.
+//!
+//! ## Author
+//! Ayomide I. Daniels (Morningstar)
+
+use crate::real::Real;
+use serde::{Deserialize, Serialize};
+
+/// Configuration for direct signaling.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct SignalingConfig {
+    /// Enable signal emission and delivery.
+    pub enabled: bool,
+    /// Per-step probability that an eligible entity emits a signal.
+    pub emit_probability: Real,
+    /// Minimum |essence - baseline| required to emit.
+    pub essence_extremity: Real,
+    /// Maximum distance at which a signal is heard.
+    pub hearing_range: Real,
+}
+
+impl Default for SignalingConfig {
+    fn default() -> Self {
+        SignalingConfig {
+            enabled: false,
+            emit_probability: 0.05,
+            essence_extremity: 1.0,
+            hearing_range: 3.0,
+        }
+    }
+}
+
+impl SignalingConfig {
+    /// Check parameter ranges.
+    pub fn is_valid(&self) -> bool {
+        (0.0..=1.0).contains(&self.emit_probability)
+            && self.essence_extremity >= 0.0
+            && self.hearing_range > 0.0
+    }
+}
+
+/// A message in flight between an emitter and its listeners.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Signal {
+    /// Emitting entity id.
+    pub sender: u32,
+    /// Emitter position at emission time.
+    pub origin: Vec<Real>,
+    /// Message vector (emitter's dominant cluster prototype).
+    pub payload: Vec<Real>,
+    /// Step at which the signal was emitted.
+    pub emitted_at: u64,
+}
+
+/// Distance attenuation of a signal.
+///
+/// # Arguments
+/// * `distance` - Distance between emitter and listener
+/// * `range` - Hearing range
+///
+/// # Returns
+/// Gain in [0, 1]: `1 - distance / range` within range, 0 beyond it
+///
+/// # Example
+/// ```
+/// use synthetic_consciousness::signaling::attenuation;
+///
+/// assert_eq!(attenuation(0.0, 4.0), 1.0);
+/// assert_eq!(attenuation(1.0, 4.0), 0.75);
+/// assert_eq!(attenuation(4.0, 4.0), 0.0);
+/// assert_eq!(attenuation(5.0, 4.0), 0.0);
+/// ```
+pub fn attenuation(distance: Real, range: Real) -> Real {
+    if range <= 0.0 || distance >= range {
+        return 0.0;
+    }
+    1.0 - distance / range
+}
+
+/// Attenuated payload as heard at `distance`, or None if out of range.
+///
+/// # Arguments
+/// * `signal` - Signal being received
+/// * `distance` - Distance from the signal origin to the listener
+/// * `range` - Hearing range
+pub fn receive(signal: &Signal, distance: Real, range: Real) -> Option<Vec<Real>> {
+    let gain = attenuation(distance, range);
+    if gain <= 0.0 {
+        return None;
+    }
+    Some(signal.payload.iter().map(|v| v * gain).collect())
+}
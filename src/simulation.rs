@@ -15,29 +15,122 @@
 //! 1. Compute pairwise attractions between entities
 //! 2. Calculate attention gradients
 //! 3. Generate stimuli and update entity states
-//! 4. Integrate motion with perpetual velocity
-//! 5. Update memory graphs and belief clusters
-//! 6. Compute consciousness metrics
-//! 7. Record step data for analysis
+//! 4. Decide on actions and convert them to bounded forces
+//! 5. Integrate motion (forces plus perpetual velocity)
+//! 6. Update memory graphs and belief clusters
+//! 7. Compute consciousness metrics
+//! 8. Record step data for analysis
 //!
 //! ## Visualization Integration
 //!
 //! When visualization is enabled, the simulation periodically updates a shared
 //! state structure that the GUI thread reads for real-time rendering.
 //!
+//! ## Construction
+//!
+//! [`Simulation::new`] runs a complete [`SimulationConfig`]; for programmatic
+//! runs, [`SimulationBuilder`] starts from the 2D defaults and sets what
+//! differs:
+//!
+//! ```
+//! use synthetic_consciousness::attraction::KernelType;
+//! use synthetic_consciousness::Simulation;
+//!
+//! let sim = Simulation::builder()
+//!     .entities(50)
+//!     .steps(5000)
+//!     .dimension(3)
+//!     .bounds([20.0, 20.0, 20.0])
+//!     .kernel(KernelType::InverseDistance)
+//!     .seed(7)
+//!     .build()
+//!     .unwrap();
+//! assert_eq!(sim.entities.count(), 50);
+//! ```
+//!
+//! ## Randomness
+//!
+//! Every random draw comes from one generator seeded with
+//! `simulation.seed`, and draws are made in entity id order, so two runs of
+//! the same configuration are bit-identical. [`Simulation::rng_state`]
+//! reports the position in the stream.
+//!
 //! ## Author
 //! Ayomide I. Daniels (Morningstar)
 
-use crate::config::SimulationConfig;
-use crate::entities::{Entity, EntityId, EntityPool};
-use crate::geometry::Pose;
-use crate::state::EntityStateVector;
-use crate::memory::MemoryGraph;
-use crate::essence::EssenceIndex;
+use crate::real::Real;
+#[cfg(feature = "gui")]
+use crate::real::{from_f32, to_f32};
+use crate::attraction::KernelType;
+use crate::config::{InitialLayout, SimulationConfig, SimulationConfigPatch};
+use crate::error::SimulationError;
+use crate::events::{EventType, ESSENCE_CRISIS_LEVEL, ESSENCE_PEAK_LEVEL};
+use crate::export::ExportFormat;
+use crate::barnes_hut::AttractionTree;
+use crate::entities::{BuildError, Entity, EntityId, EntityPool};
+use crate::geometry::{Coords, Pose, SpatialGrid};
 use crate::metrics::Metrics;
-use crate::results::{SimulationResults, SimulationStep};
-use rand::Rng;
-use chrono::Local;
+use crate::results::{meets_consciousness_thresholds, SimulationResults, SimulationStep};
+use crate::species::{self, SpeciesConfig, SpeciesId};
+use crate::reproduction::{LineageRecord, ReproductionConfig};
+use crate::roles::{self, Role};
+use crate::signaling::{self, Signal};
+use crate::plugin::{PluginRegistry, SimulationObserver, StepControl, StimulusSource};
+use crate::sensing::{NoiseSense, SenseModel};
+use crate::schedule::{self, STIMULUS_AMPLITUDE};
+use crate::profiling::{Phase, PhaseTimings};
+use crate::progress::{self, ProgressReporter, ProgressSnapshot, ProgressTracker};
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet, HashMap, VecDeque};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+/// Random generator driving a simulation: entity placement, innate
+/// drives, sensory noise, signal emission and reproduction.
+pub type SimulationRng = ChaCha8Rng;
+
+/// Position of a simulation's random stream.
+/// 
+/// A generator seeded with `seed` and advanced to `word_pos` continues the
+/// stream exactly; see [`Simulation::rng_state`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RngState {
+    /// Seed the stream started from (`simulation.seed`)
+    pub seed: u64,
+    /// 32-bit words drawn since seeding
+    pub word_pos: u128,
+}
+
+impl RngState {
+    /// Generator positioned at this state.
+    pub fn to_rng(self) -> SimulationRng {
+        let mut rng = SimulationRng::seed_from_u64(self.seed);
+        rng.set_word_pos(self.word_pos);
+        rng
+    }
+}
+
+/// Outcome of [`Simulation::run_until_conscious`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ConsciousnessRun {
+    /// Whether the criteria were sustained, ending the run before its step limit
+    pub stopped_early: bool,
+    /// Steps completed when the run ended (the simulation's `timestamp`)
+    pub stopped_at: u64,
+    /// First step of the sustained window (numbered like `Metrics::timestamp`)
+    pub sustained_from: Option<u64>,
+}
+
+/// Outcome of [`Simulation::run_until`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct PredicateRun {
+    /// Steps run in this call
+    pub steps_run: u64,
+    /// Whether the predicate held when the run ended
+    pub predicate_met: bool,
+}
 
 /// Main simulation instance.
 pub struct Simulation {
@@ -46,16 +139,363 @@ pub struct Simulation {
     pub timestamp: u64,
     pub metrics_history: Vec<Metrics>,
     pub results: SimulationResults,
+    /// Recent velocity phases per entity (ring buffer for synchrony analysis).
+    pub(crate) phase_history: HashMap<EntityId, VecDeque<Real>>,
+    /// Signals emitted this step, delivered during the next sense step.
+    pub(crate) pending_signals: Vec<Signal>,
+    /// Step of each entity's most recent signal emission.
+    pub(crate) last_emission: HashMap<EntityId, u64>,
+    /// (emitted, received) signal counts for the current step.
+    pub(crate) signal_counts: (usize, usize),
+    /// Leader set of the previous step (for role stability).
+    pub(crate) previous_leaders: BTreeSet<EntityId>,
+    /// Role stability of the current step.
+    pub(crate) role_stability: Real,
+    /// Softmax attention of each entity over all entities (id order, zero
+    /// for itself) from the current step; only computed when roles or
+    /// attention capture need it.
+    pub(crate) attention_weights: Vec<(EntityId, Vec<Real>)>,
+    /// Positions set by the user, applied at the next integration step.
+    manual_overrides: HashMap<EntityId, Vec<Real>>,
+    /// Stimuli queued by `schedule_stimulus`, keyed by delivery step.
+    pub(crate) scheduled_stimuli: BTreeMap<u64, Vec<(EntityId, Vec<Real>)>>,
+    /// Stimuli injected during the current step, recorded with it.
+    injected_stimuli: Vec<(u32, Vec<Real>)>,
+    /// Scheduled parameter values in effect for the current step.
+    scheduled_values: Vec<(String, Real)>,
+    /// Configured values of the parameters schedules have overwritten,
+    /// restored by `reset` and in the fingerprinted configuration.
+    configured_values: Vec<(String, Real)>,
+    /// Cooperative cancellation flag checked between steps by `run()`.
+    stop_flag: Option<Arc<AtomicBool>>,
+    /// Set once an observer returned `StepControl::Stop`.
+    observer_stop: bool,
+    /// Patches applied during the run, with the step they took effect at.
+    pub(crate) config_patches: Vec<(u64, SimulationConfigPatch)>,
+    /// Cumulative recorded attraction per entity pair (lower id first).
+    pub(crate) interaction_weights: HashMap<(EntityId, EntityId), Real>,
+    /// Plugin replacing the sense model's stimuli, if any.
+    stimulus_source: Option<Box<dyn StimulusSource>>,
+    /// How entities perceive their surroundings (uniform noise by default).
+    sense_model: Box<dyn SenseModel>,
+    /// Plugins called after every step.
+    observers: Vec<Box<dyn SimulationObserver>>,
+    /// Receives progress after every step (silent if unset).
+    progress: Option<Box<dyn ProgressReporter>>,
+    /// Wall-clock timing of the steps run so far.
+    tracker: ProgressTracker,
+    /// Per-phase timing of the steps (only with `simulation.profile_phases`).
+    phase_timings: PhaseTimings,
+    /// Spatial hash of entity positions, rebuilt each step to find the
+    /// pairs within `attraction.cutoff`.
+    pair_grid: SpatialGrid,
+    /// Seeded random stream; every random draw of the simulation comes from it.
+    rng: SimulationRng,
+    /// Seed `rng` was created from.
+    seed: u64,
+    /// Wall-clock seconds spent stepping before a checkpoint was resumed.
+    prior_wall_clock: f64,
+    /// Buffers reused by the step phases.
+    scratch: StepScratch,
+}
+
+/// Working buffers reused across steps so phases do not allocate per entity.
+/// 
+/// Stimulus vectors are overwritten in place every step; sensing copies
+/// each into the entity's memory arena rather than taking ownership.
+#[derive(Default)]
+struct StepScratch {
+    /// Legacy state-update gradient (state memory dimension)
+    gradient: Vec<Real>,
+    /// Memory input of one entity's state update (state memory dimension)
+    memory_input: Vec<Real>,
+    /// Integration acceleration (spatial dimension)
+    acceleration: Vec<Real>,
+    /// Affective signals of one entity's belief clusters
+    affective_signals: Vec<Real>,
+    /// Distance from each entity to its nearest neighbor
+    nearest: Vec<(EntityId, Real)>,
+    /// Entity positions and emitted attraction weights, in id order
+    positions: Vec<Vec<Real>>,
+    attraction_weights: Vec<Real>,
+    /// Attention gradient of each entity, in id order
+    attention_gradients: Vec<Vec<Real>>,
+    /// Attention scores of one entity toward the others
+    attention_scores: Vec<Real>,
+    /// Stimulus perceived by each entity, in id order
+    stimuli: Vec<(EntityId, Vec<Real>)>,
+    /// Next belief cluster ID and essence of each entity at the start of
+    /// the step, in id order, for detecting events
+    step_start: Vec<(EntityId, u32, Real)>,
+}
+
+impl StepScratch {
+    /// Buffers sized for a configuration.
+    fn new(config: &SimulationConfig) -> Self {
+        StepScratch {
+            gradient: Vec::with_capacity(config.state.memory_dim),
+            memory_input: Vec::with_capacity(config.state.memory_dim),
+            acceleration: Vec::with_capacity(config.geometry.dimension),
+            affective_signals: Vec::new(),
+            nearest: Vec::with_capacity(config.simulation.num_entities as usize),
+            positions: Vec::new(),
+            attraction_weights: Vec::new(),
+            attention_gradients: Vec::new(),
+            attention_scores: Vec::new(),
+            stimuli: Vec::with_capacity(config.simulation.num_entities as usize),
+            step_start: Vec::with_capacity(config.simulation.num_entities as usize),
+        }
+    }
+}
+
+/// Fluent construction of a [`Simulation`].
+/// 
+/// Starts from [`SimulationConfig::default_2d`] (or a given configuration),
+/// and validates the configuration only in [`SimulationBuilder::build`].
+/// Pre-built entities are added after the ones the configuration generates;
+/// use `.entities(0)` for a population of pre-built entities only.
+pub struct SimulationBuilder {
+    config: SimulationConfig,
+    entities: Vec<Entity>,
+    sense_model: Option<Box<dyn SenseModel>>,
+}
+
+impl Default for SimulationBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SimulationBuilder {
+    /// Builder starting from the default 2D configuration.
+    pub fn new() -> Self {
+        Self::from_config(SimulationConfig::default_2d())
+    }
+
+    /// Builder starting from `config` (e.g. [`SimulationConfig::default_3d`]
+    /// or a preset).
+    pub fn from_config(config: SimulationConfig) -> Self {
+        SimulationBuilder {
+            config,
+            entities: Vec::new(),
+            sense_model: None,
+        }
+    }
+
+    /// Configuration built so far, e.g. to build entities against.
+    pub fn config(&self) -> &SimulationConfig {
+        &self.config
+    }
+
+    /// Set the number of generated entities.
+    pub fn entities(mut self, count: u32) -> Self {
+        self.config.simulation.num_entities = count;
+        self
+    }
+
+    /// Set the number of steps.
+    pub fn steps(mut self, steps: u32) -> Self {
+        self.config.simulation.num_steps = steps;
+        self
+    }
+
+    /// Set the spatial dimension; bounds are extended with the first bound
+    /// or truncated.
+    pub fn dimension(mut self, dimension: usize) -> Self {
+        let bound = self.config.geometry.bounds.first().copied().unwrap_or(10.0);
+        self.config.geometry.dimension = dimension;
+        self.config.geometry.bounds.resize(dimension, bound);
+        self
+    }
+
+    /// Set the world bounds, one per dimension.
+    pub fn bounds(mut self, bounds: impl Into<Vec<Real>>) -> Self {
+        self.config.geometry.bounds = bounds.into();
+        self
+    }
+
+    /// Set the attraction kernel.
+    pub fn kernel(mut self, kernel: KernelType) -> Self {
+        self.config.attraction.kernel = kernel;
+        self
+    }
+
+    /// Set the random seed.
+    pub fn seed(mut self, seed: u64) -> Self {
+        self.config.simulation.seed = seed;
+        self
+    }
+
+    /// Change any other part of the configuration.
+    pub fn configure(mut self, change: impl FnOnce(&mut SimulationConfig)) -> Self {
+        change(&mut self.config);
+        self
+    }
+
+    /// Add a pre-built entity; the pool assigns its id.
+    pub fn with_entity(mut self, entity: Entity) -> Self {
+        self.entities.push(entity);
+        self
+    }
+
+    /// Perceive through `model` instead of the built-in uniform noise.
+    pub fn sense_model(mut self, model: Box<dyn SenseModel>) -> Self {
+        self.sense_model = Some(model);
+        self
+    }
+
+    /// Validate the configuration and create the simulation.
+    /// 
+    /// # Returns
+    /// The simulation, or an error if the configuration is invalid, a
+    /// plugin is unknown or a pre-built entity has the wrong dimension
+    pub fn build(self) -> Result<Simulation, String> {
+        let dimension = self.config.geometry.dimension;
+        if let Some(entity) = self
+            .entities
+            .iter()
+            .find(|e| e.pose.position.len() != dimension || e.velocity.len() != dimension)
+        {
+            return Err(format!(
+                "Pre-built entity has {} position and {} velocity components, expected {}",
+                entity.pose.position.len(),
+                entity.velocity.len(),
+                dimension
+            ));
+        }
+
+        let mut sim = Simulation::new(self.config)?;
+        if let Some(model) = self.sense_model {
+            sim.sense_model = model;
+        }
+        for entity in self.entities {
+            sim.insert_entity(entity, None);
+        }
+        sim.results.num_entities = sim.entities.count() as u32;
+        Ok(sim)
+    }
 }
 
 impl Simulation {
+    /// Start building a simulation from the default 2D configuration.
+    pub fn builder() -> SimulationBuilder {
+        SimulationBuilder::new()
+    }
+
     /// Create new simulation with configuration.
+    /// 
+    /// Plugins named in the configuration are resolved against the
+    /// plugins registered at link time.
     pub fn new(config: SimulationConfig) -> Result<Self, String> {
-        config.validate()?;
+        Self::with_plugins(config, &PluginRegistry::with_registered())
+    }
 
-        let start_time = Local::now().to_rfc3339();
+    /// Create a simulation, resolving configured plugins against `registry`.
+    /// 
+    /// # Arguments
+    /// * `config` - Simulation configuration
+    /// * `registry` - Plugin constructors (e.g. with libraries loaded)
+    /// 
+    /// # Returns
+    /// The simulation, or an error if the configuration is invalid or a
+    /// plugin is unknown or rejects its parameters
+    pub fn with_plugins(config: SimulationConfig, registry: &PluginRegistry) -> Result<Self, String> {
+        Self::create(config, registry, None)
+    }
+
+    /// Create a simulation continuing with entities from a previous run.
+    /// 
+    /// The entities keep their ids, memory graphs, state vectors, essence
+    /// values, energy and their own parameters (essence and state settings,
+    /// drives, mass, stimulus amplitude); the sections of `config` that govern the simulation
+    /// as a whole (sensing, dynamics, attraction, ...) apply from the first
+    /// step, so a population trained under one regime can continue under
+    /// another. `simulation.num_entities` is set to the number of entities,
+    /// none are generated, and the results, metrics history and step count
+    /// start fresh.
+    /// 
+    /// ```no_run
+    /// # use synthetic_consciousness::{Simulation, SimulationConfig};
+    /// let mut training = Simulation::new(SimulationConfig::default_2d()).unwrap();
+    /// training.run();
+    /// 
+    /// // Sense more noisily once beliefs have formed, in a wider field
+    /// let mut entities = training.take_entities();
+    /// for entity in &mut entities {
+    ///     entity.stimulus_amplitude *= 2.0;
+    /// }
+    /// let mut config = SimulationConfig::default_2d();
+    /// config.attraction.sigma = 2.0;
+    /// let mut sim = Simulation::from_entities(config, entities).unwrap();
+    /// sim.run();
+    /// ```
+    /// 
+    /// # Arguments
+    /// * `config` - Configuration of the new run
+    /// * `entities` - Entities to continue with (see [`Simulation::take_entities`])
+    /// 
+    /// # Returns
+    /// The simulation, or an error if the configuration is invalid, an
+    /// entity does not match the world's dimension or ids repeat
+    pub fn from_entities(mut config: SimulationConfig, entities: Vec<Entity>) -> Result<Self, String> {
+        let dimension = config.geometry.dimension;
+        let mut ids = BTreeSet::new();
+        for entity in &entities {
+            if entity.pose.position.len() != dimension || entity.velocity.len() != dimension {
+                return Err(format!(
+                    "Entity {} has {} position and {} velocity components, expected {}",
+                    entity.id.0,
+                    entity.pose.position.len(),
+                    entity.velocity.len(),
+                    dimension
+                ));
+            }
+            let graph = &entity.memory_graph;
+            if let Some(node) = graph.nodes.iter().find(|n| graph.node_event(n).len() != dimension) {
+                return Err(format!(
+                    "Entity {} remembers {}-dimensional events, expected {}",
+                    entity.id.0,
+                    graph.node_event(node).len(),
+                    dimension
+                ));
+            }
+            if !ids.insert(entity.id) {
+                return Err(format!("Entity id {} appears more than once", entity.id.0));
+            }
+        }
+
+        config.simulation.num_entities = entities.len() as u32;
+        Self::create(config, &PluginRegistry::with_registered(), Some(entities))
+    }
+
+    /// Hand over the entities, in id order, e.g. to continue them in a new
+    /// simulation with [`Simulation::from_entities`].
+    pub fn take_entities(self) -> Vec<Entity> {
+        self.entities.into_entities()
+    }
+
+    /// Create a simulation with the given entities, or the ones the
+    /// configuration generates if `None`.
+    fn create(config: SimulationConfig, registry: &PluginRegistry, entities: Option<Vec<Entity>>) -> Result<Self, String> {
+        config.validate()?;
+        let stimulus_source = config
+            .plugins
+            .stimulus
+            .as_ref()
+            .map(|spec| registry.create_stimulus_source(spec))
+            .transpose()?;
+        let observers = config
+            .plugins
+            .observers
+            .iter()
+            .map(|spec| registry.create_observer(spec))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let start_time = crate::results::timestamp_now();
+        let seed = config.simulation.seed;
 
         let mut sim = Simulation {
+            scratch: StepScratch::new(&config),
             config,
             entities: EntityPool::new(),
             timestamp: 0,
@@ -66,187 +506,1145 @@ impl Simulation {
                 0,
                 start_time,
             ),
+            phase_history: HashMap::new(),
+            pending_signals: Vec::new(),
+            last_emission: HashMap::new(),
+            signal_counts: (0, 0),
+            previous_leaders: BTreeSet::new(),
+            role_stability: 1.0,
+            attention_weights: Vec::new(),
+            manual_overrides: HashMap::new(),
+            scheduled_stimuli: BTreeMap::new(),
+            injected_stimuli: Vec::new(),
+            scheduled_values: Vec::new(),
+            configured_values: Vec::new(),
+            stop_flag: None,
+            observer_stop: false,
+            config_patches: Vec::new(),
+            interaction_weights: HashMap::new(),
+            stimulus_source,
+            sense_model: Box::new(NoiseSense),
+            observers,
+            progress: None,
+            tracker: ProgressTracker::new(),
+            phase_timings: PhaseTimings::default(),
+            pair_grid: SpatialGrid::default(),
+            rng: SimulationRng::seed_from_u64(seed),
+            seed,
+            prior_wall_clock: 0.0,
         };
 
         // Initialize entities
-        sim.initialize_entities()?;
-        sim.results.num_entities = sim.config.simulation.num_entities;
-        sim.results.num_steps = sim.config.simulation.num_steps;
+        match entities {
+            Some(entities) => sim.adopt_entities(entities),
+            None => sim.initialize_entities()?,
+        }
+        sim.describe_run();
 
         Ok(sim)
     }
 
+    /// Restart the run from step 0, as if the simulation were created anew.
+    /// 
+    /// Entities are regenerated from the configuration (pre-built or
+    /// carried-over entities are not restored), the random stream restarts
+    /// from `seed` (stored as `simulation.seed`) or the configured seed, and
+    /// the metrics history, results and all per-run bookkeeping are cleared.
+    /// A run after a reset is identical to one of a freshly created
+    /// simulation with the same configuration and seed, but the history
+    /// buffers keep their allocations, which makes repetition loops cheap:
+    /// 
+    /// ```no_run
+    /// # use synthetic_consciousness::{Simulation, SimulationConfig};
+    /// let mut sim = Simulation::new(SimulationConfig::default_2d()).unwrap();
+    /// let mut scores = Vec::new();
+    /// for seed in 0..100 {
+    ///     sim.reset(Some(seed)).unwrap();
+    ///     sim.run();
+    ///     sim.finalize_results();
+    ///     scores.push(sim.results.consciousness_analysis.consciousness_score);
+    /// }
+    /// ```
+    /// 
+    /// Plugins, the sense model, observers, the progress reporter and the
+    /// stop flag stay attached; config patches applied during the previous
+    /// run stay in the configuration, while scheduled parameters return to
+    /// their configured values.
+    /// 
+    /// # Arguments
+    /// * `seed` - Seed of the new run, or `None` for `simulation.seed`
+    /// 
+    /// # Returns
+    /// An error if the entities cannot be generated from the configuration
+    pub fn reset(&mut self, seed: Option<u64>) -> Result<(), String> {
+        for (path, value) in std::mem::take(&mut self.configured_values) {
+            if let Some(parameter) = schedule::parameter_mut(&mut self.config, &path) {
+                *parameter = value;
+            }
+        }
+        if let Some(seed) = seed {
+            self.config.simulation.seed = seed;
+        }
+        let seed = self.config.simulation.seed;
+
+        // Recorded steps keep their buffer; where the config came from still holds
+        let mut steps = std::mem::take(&mut self.results.steps);
+        steps.clear();
+        let mut results = SimulationResults::new(
+            std::mem::take(&mut self.results.simulation_name),
+            0,
+            0,
+            crate::results::timestamp_now(),
+        );
+        results.steps = steps;
+        results.cli_overrides = std::mem::take(&mut self.results.cli_overrides);
+        results.config_source = std::mem::take(&mut self.results.config_source);
+        self.results = results;
+
+        self.entities.clear();
+        self.timestamp = 0;
+        self.metrics_history.clear();
+        self.phase_history.clear();
+        self.pending_signals.clear();
+        self.last_emission.clear();
+        self.signal_counts = (0, 0);
+        self.previous_leaders.clear();
+        self.role_stability = 1.0;
+        self.attention_weights.clear();
+        self.manual_overrides.clear();
+        self.scheduled_stimuli.clear();
+        self.injected_stimuli.clear();
+        self.scheduled_values.clear();
+        self.observer_stop = false;
+        self.config_patches.clear();
+        self.interaction_weights.clear();
+        self.tracker = ProgressTracker::new();
+        self.phase_timings.clear();
+        self.rng = SimulationRng::seed_from_u64(seed);
+        self.seed = seed;
+        self.prior_wall_clock = 0.0;
+
+        self.initialize_entities()?;
+        self.describe_run();
+        Ok(())
+    }
+
+    /// Record the population, world and configuration of a new run in the results.
+    fn describe_run(&mut self) {
+        self.results.num_entities = self.entities.count() as u32;
+        self.results.num_steps = self.config.simulation.num_steps;
+        self.results.world_bounds = self.config.geometry.bounds.clone();
+        self.results.periodic = self.config.geometry.periodic;
+        self.results.config_fingerprint = self.export_config_fingerprint();
+        self.results.config = Some(self.configured().into_owned());
+        self.results.seed = self.seed;
+    }
+
+    /// Create a simulation whose entities perceive through `model`.
+    /// 
+    /// # Arguments
+    /// * `config` - Simulation configuration
+    /// * `model` - Sense model replacing the built-in uniform noise
+    /// 
+    /// # Returns
+    /// The simulation, or an error if the configuration is invalid or a
+    /// configured plugin is unknown
+    pub fn with_sense_model(config: SimulationConfig, model: Box<dyn SenseModel>) -> Result<Self, String> {
+        let mut sim = Self::new(config)?;
+        sim.sense_model = model;
+        Ok(sim)
+    }
+
+    /// Create a simulation and step it past its start-up transient.
+    /// 
+    /// Benchmarks use this so they measure a realistic state (populated
+    /// memory graphs, recorded history) rather than an empty one. Nothing
+    /// is printed or written to disk.
+    /// 
+    /// # Arguments
+    /// * `config` - Simulation configuration
+    /// * `steps_to_warm` - Steps to run before returning
+    /// 
+    /// # Returns
+    /// The warmed-up simulation, or an error if the configuration is invalid
+    pub fn bench_prepare(config: SimulationConfig, steps_to_warm: usize) -> Result<Self, String> {
+        let mut sim = Self::new(config)?;
+        for _ in 0..steps_to_warm {
+            sim.step();
+        }
+        Ok(sim)
+    }
+
+    /// Copy the live simulation state into an independent simulation.
+    /// 
+    /// Entities, phase history, pending signals, scheduled stimuli and
+    /// cumulative interaction weights are cloned; the step
+    /// history and results start empty, so forks are cheap to run for
+    /// what-if analyses. Plugins and custom sense models are not carried
+    /// over. The fork continues the random stream from the same position,
+    /// so it draws the same sensory noise as the original would.
+    pub(crate) fn fork(&self) -> Simulation {
+        let mut results = SimulationResults::new(
+            self.results.simulation_name.clone(),
+            self.entities.count() as u32,
+            self.config.simulation.num_steps,
+            crate::results::timestamp_now(),
+        );
+        results.lineage = self.results.lineage.clone();
+        results.world_bounds = self.results.world_bounds.clone();
+        results.periodic = self.results.periodic;
+        results.seed = self.seed;
+
+        Simulation {
+            config: self.config.clone(),
+            entities: self.entities.clone(),
+            timestamp: self.timestamp,
+            metrics_history: Vec::new(),
+            results,
+            phase_history: self.phase_history.clone(),
+            pending_signals: self.pending_signals.clone(),
+            last_emission: self.last_emission.clone(),
+            signal_counts: (0, 0),
+            previous_leaders: BTreeSet::new(),
+            role_stability: 1.0,
+            attention_weights: Vec::new(),
+            manual_overrides: HashMap::new(),
+            scheduled_stimuli: self.scheduled_stimuli.clone(),
+            injected_stimuli: Vec::new(),
+            scheduled_values: Vec::new(),
+            configured_values: self.configured_values.clone(),
+            stop_flag: None,
+            observer_stop: false,
+            config_patches: Vec::new(),
+            interaction_weights: self.interaction_weights.clone(),
+            stimulus_source: None,
+            sense_model: Box::new(NoiseSense),
+            observers: Vec::new(),
+            progress: None,
+            tracker: ProgressTracker::new(),
+            phase_timings: PhaseTimings::default(),
+            pair_grid: SpatialGrid::default(),
+            rng: self.rng.clone(),
+            seed: self.seed,
+            prior_wall_clock: 0.0,
+            scratch: StepScratch::new(&self.config),
+        }
+    }
+
     /// Initialize entities with random positions.
+    /// 
+    /// Species entities are created first (in species name order), followed
+    /// by default-species entities filling up to `num_entities`.
     fn initialize_entities(&mut self) -> Result<(), String> {
-        let mut rng = rand::thread_rng();
         let dim = self.config.geometry.dimension;
-        let n = self.config.simulation.num_entities;
-        let bounds = &self.config.geometry.bounds;
+        let bounds = self.config.geometry.bounds.clone();
 
-        for _i in 0..n {
-            let mut position = vec![0.0; dim];
-            for d in 0..dim {
-                let bound = bounds[d];
-                position[d] = rng.gen_range(0.0..bound);
+        let mut assignments: Vec<(SpeciesId, SpeciesConfig)> = Vec::new();
+        for (id, _name, cfg) in species::enumerate_species(&self.config.species) {
+            for _ in 0..cfg.count {
+                assignments.push((id, cfg.clone()));
             }
+        }
+        while (assignments.len() as u32) < self.config.simulation.num_entities {
+            assignments.push((SpeciesId(0), SpeciesConfig::default()));
+        }
 
-            let mut orientation = [1.0, 0.0, 0.0, 0.0];
-            orientation[1] = rng.gen_range(-1.0..1.0);
-
-            let pose = Pose {
-                position,
-                orientation,
+        // Lattice resolution per axis for grid layouts
+        let total = assignments.len();
+        let per_axis = (1..).find(|k: &usize| k.pow(dim as u32) >= total).unwrap_or(1);
+
+        for (index, (species_id, species_config)) in assignments.into_iter().enumerate() {
+            let pose = match self.config.simulation.initial_layout {
+                InitialLayout::Random => random_pose(&self.config, &mut self.rng),
+                InitialLayout::Grid => {
+                    let mut position = vec![0.0; dim];
+                    let mut cell = index;
+                    for d in 0..dim {
+                        let spacing = bounds[d] / per_axis as Real;
+                        position[d] = ((cell % per_axis) as Real + 0.5) * spacing;
+                        cell /= per_axis;
+                    }
+                    let mut orientation = [1.0, 0.0, 0.0, 0.0];
+                    orientation[1] = self.rng.gen_range(-1.0..1.0);
+                    Pose {
+                        position: position.into(),
+                        orientation,
+                    }
+                }
             };
 
-            let state = EntityStateVector::new(self.config.state.clone());
+            let mut entity = new_entity(&self.config, pose, &mut self.rng).map_err(|e| e.to_string())?;
+            entity.apply_species(species_id, &species_config);
+            self.insert_entity(entity, None);
+        }
 
-            let memory_graph = MemoryGraph::new();
+        // Per-entity config section
+        for entity_config in &self.config.entities {
+            let entity = self
+                .entities
+                .get_entity_mut(EntityId(entity_config.id))
+                .ok_or_else(|| format!("No entity with id {}", entity_config.id))?;
+            entity_config.apply_to(entity);
+        }
 
-            let essence = EssenceIndex::new(self.config.essence.clone());
+        Ok(())
+    }
 
-            let entity = Entity::new(EntityId(0), pose, state, memory_graph, essence);
-            self.entities.add_entity(entity);
+    /// Take over entities from another run under their own ids, born at
+    /// the start of this one.
+    fn adopt_entities(&mut self, mut entities: Vec<Entity>) {
+        entities.sort_by_key(|e| e.id);
+        for entity in &mut entities {
+            entity.birth_step = self.timestamp;
+            self.results.lineage.push(LineageRecord {
+                entity_id: entity.id.0,
+                parent_id: entity.parent.map(|p| p.0),
+                birth_step: self.timestamp,
+            });
         }
+        self.entities = EntityPool::with_entities(entities);
+    }
 
+    /// Add an entity to the pool and record its lineage.
+    fn insert_entity(&mut self, mut entity: Entity, parent: Option<EntityId>) -> EntityId {
+        entity.birth_step = self.timestamp;
+        entity.parent = parent;
+        let id = self.entities.add_entity(entity);
+        self.results.lineage.push(LineageRecord {
+            entity_id: id.0,
+            parent_id: parent.map(|p| p.0),
+            birth_step: self.timestamp,
+        });
+        self.results.num_entities = self.entities.count() as u32;
+        id
+    }
+
+    /// Add a naive entity at runtime.
+    /// 
+    /// The entity is built from the configured defaults (default species)
+    /// and placed at `pose`, or at a random position if none is given.
+    /// 
+    /// # Arguments
+    /// * `pose` - Optional initial pose
+    /// 
+    /// # Returns
+    /// Id of the new entity, or an error if the pose dimension does not
+    /// match the world
+    pub fn spawn_entity(&mut self, pose: Option<Pose>) -> Result<EntityId, String> {
+        let pose = pose.unwrap_or_else(|| random_pose(&self.config, &mut self.rng));
+        let entity = new_entity(&self.config, pose, &mut self.rng).map_err(|e| e.to_string())?;
+        Ok(self.insert_entity(entity, None))
+    }
+
+    /// Remove an entity at runtime.
+    /// 
+    /// Takes effect from the next step: the entity leaves the pool and the
+    /// per-entity history of the simulation (phases, signal timing, roles),
+    /// and its followers lose their leader. Steps recorded so far keep it.
+    /// 
+    /// # Arguments
+    /// * `id` - Entity to remove
+    /// 
+    /// # Returns
+    /// Whether an entity with this id existed
+    pub fn remove_entity(&mut self, id: EntityId) -> bool {
+        if self.entities.remove_entity(id).is_none() {
+            return false;
+        }
+        self.phase_history.remove(&id);
+        self.last_emission.remove(&id);
+        self.manual_overrides.remove(&id);
+        self.previous_leaders.remove(&id);
+        self.attention_weights.clear();
+        for entity in self.entities.iter_mut() {
+            if entity.follows == Some(id) {
+                entity.follows = None;
+                entity.role = Role::Member;
+            }
+        }
+        self.results.num_entities = self.entities.count() as u32;
+        true
+    }
+
+    /// Attach a label to an entity.
+    /// 
+    /// # Arguments
+    /// * `id` - Entity to tag
+    /// * `label` - Label to add (ignored if already present)
+    /// 
+    /// # Returns
+    /// Error if the entity does not exist
+    pub fn tag_entity(&mut self, id: EntityId, label: &str) -> Result<(), String> {
+        let entity = self
+            .entities
+            .get_entity_mut(id)
+            .ok_or_else(|| format!("No entity with id {}", id.0))?;
+        entity.add_label(label);
+        Ok(())
+    }
+
+    /// Deliver an external stimulus to an entity.
+    /// 
+    /// The stimulus is sensed like the per-step environmental input: it is
+    /// stored as a memory node and clustered into the entity's beliefs. It
+    /// is sensed in addition to the next step's noise and recorded in that
+    /// step's `injected_stimuli`.
+    /// 
+    /// # Arguments
+    /// * `id` - Receiving entity
+    /// * `stimulus` - Stimulus vector, one component per spatial dimension
+    /// 
+    /// # Returns
+    /// Error if the entity does not exist or the stimulus has the wrong length
+    pub fn inject_stimulus(&mut self, id: EntityId, stimulus: Vec<Real>) -> Result<(), String> {
+        self.check_stimulus(&stimulus)?;
+        let timestamp = self.timestamp;
+        let entity = self
+            .entities
+            .get_entity_mut(id)
+            .ok_or_else(|| format!("No entity with id {}", id.0))?;
+        entity.sense(&stimulus, timestamp);
+        self.injected_stimuli.push((id.0, stimulus));
+        Ok(())
+    }
+
+    /// Queue a stimulus for delivery to an entity at a given step.
+    /// 
+    /// During that step's sensing the entity senses the stimulus instead of
+    /// what it perceives. Its perception (e.g. noise) is still drawn, so the
+    /// other entities see the same random stream as in a run without the
+    /// stimulus. Several
+    /// stimuli for the same entity and step are all sensed, in the order
+    /// they were scheduled. Stimuli for entities that no longer exist at
+    /// delivery are dropped.
+    /// 
+    /// # Arguments
+    /// * `step` - Step at which the stimulus is delivered (current or later)
+    /// * `id` - Receiving entity
+    /// * `stimulus` - Stimulus vector, one component per spatial dimension
+    /// 
+    /// # Returns
+    /// Error if the step has already run or the stimulus has the wrong length
+    pub fn schedule_stimulus(&mut self, step: u64, id: EntityId, stimulus: Vec<Real>) -> Result<(), String> {
+        self.check_stimulus(&stimulus)?;
+        if step < self.timestamp {
+            return Err(format!("Step {} has already run (now at step {})", step, self.timestamp));
+        }
+        self.scheduled_stimuli.entry(step).or_default().push((id, stimulus));
+        Ok(())
+    }
+
+    /// Check an external stimulus has one component per spatial dimension.
+    fn check_stimulus(&self, stimulus: &[Real]) -> Result<(), String> {
+        let dimension = self.config.geometry.dimension;
+        if stimulus.len() != dimension {
+            return Err(format!("Stimulus has {} components, expected {}", stimulus.len(), dimension));
+        }
         Ok(())
     }
 
     /// Execute one simulation step.
     pub fn step(&mut self) {
+        self.tracker.start_step(self.timestamp);
+
+        // Scheduled parameters take their value for this step
+        self.apply_schedules();
+
+        // Events are detected against the state at the start of the step
+        self.scratch.step_start.clear();
+        self.scratch
+            .step_start
+            .extend(self.entities.iter().map(|e| (e.id, e.memory_graph.next_cluster_id(), e.essence.value)));
+
         // Step 1: Sense environment (input stimulus)
-        self.sense_step();
+        self.timed(Phase::Sense, Self::sense_step);
 
         // Step 2: Compute attention fields
-        self.attention_step();
+        self.timed(Phase::Attention, Self::attention_step);
 
         // Step 3: Update state vectors
-        self.state_update_step();
+        self.timed(Phase::State, Self::state_update_step);
 
         // Step 4: Compute affective signals
-        self.affective_step();
+        self.timed(Phase::Affective, Self::affective_step);
 
         // Step 5: Update essence indices
-        self.essence_step();
+        self.timed(Phase::Essence, Self::essence_step);
 
         // Step 6: Decide on actions
-        self.decision_step();
+        self.timed(Phase::Decision, Self::decision_step);
+
+        // Step 7: Convert actions to forces
+        self.timed(Phase::Act, Self::act_step);
+
+        // Step 8: Integrate dynamics (perpetual velocity)
+        self.timed(Phase::Integrate, Self::integration_step);
 
-        // Step 7: Integrate dynamics (perpetual velocity)
-        self.integration_step();
+        // Step 9: Apply periodic boundaries
+        self.timed(Phase::Boundary, Self::boundary_step);
 
-        // Step 8: Apply periodic boundaries
-        self.boundary_step();
+        // Step 10: Update memory decay
+        self.timed(Phase::Decay, Self::memory_decay_step);
 
-        // Step 9: Update memory decay
-        self.memory_decay_step();
+        // Step 11: Compute metrics
+        self.timed(Phase::Metrics, Self::metrics_step);
 
-        // Step 10: Compute metrics
-        self.metrics_step();
+        // Step 12: Spawn offspring (post-step)
+        self.timed(Phase::Reproduction, Self::reproduction_step);
 
         self.timestamp += 1;
+
+        // Observers see the completed step; any of them can end the run
+        let mut observers = std::mem::take(&mut self.observers);
+        for observer in &mut observers {
+            if observer.on_step(self) == StepControl::Stop {
+                self.observer_stop = true;
+            }
+        }
+        self.observers = observers;
+
+        self.tracker.finish_step(self.timestamp, u64::from(self.config.simulation.num_steps));
+        if let Some(progress) = &mut self.progress {
+            progress.on_progress(&self.tracker.snapshot());
+        }
+    }
+
+    /// Replace the built-in stimulus noise with a stimulus source.
+    pub fn set_stimulus_source(&mut self, source: Box<dyn StimulusSource>) {
+        self.stimulus_source = Some(source);
+    }
+
+    /// Add an observer called after every step.
+    /// 
+    /// Observers run in the order added, after those named in the
+    /// configuration. One returning [`StepControl::Stop`] ends `run()`
+    /// after the current step (see [`Simulation::stop_requested`]).
+    pub fn add_observer(&mut self, observer: Box<dyn SimulationObserver>) {
+        self.observers.push(observer);
+    }
+
+    /// Report progress to `reporter` after every step and at finalization.
+    pub fn set_progress_reporter(&mut self, reporter: Box<dyn ProgressReporter>) {
+        self.progress = Some(reporter);
+    }
+
+    /// Stop reporting progress.
+    pub fn clear_progress_reporter(&mut self) {
+        self.progress = None;
+    }
+
+    /// Seed the random stream started from (`simulation.seed` at creation).
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    /// Current position of the random stream.
+    /// 
+    /// Two simulations created from the same configuration draw the same
+    /// numbers, so they stay identical step for step; the state lets a run
+    /// be cited and continued from any point.
+    pub fn rng_state(&self) -> RngState {
+        RngState {
+            seed: self.seed,
+            word_pos: self.rng.get_word_pos(),
+        }
+    }
+
+    /// Continue the random stream from `state` (used when resuming a checkpoint).
+    pub(crate) fn restore_rng(&mut self, state: RngState) {
+        self.seed = state.seed;
+        self.results.seed = state.seed;
+        self.rng = state.to_rng();
+    }
+
+    /// Count `seconds` of earlier stepping in the wall-clock time (used when
+    /// resuming a checkpoint).
+    pub(crate) fn set_prior_wall_clock(&mut self, seconds: f64) {
+        self.prior_wall_clock = seconds;
+    }
+
+    /// Real time spent stepping so far, in seconds, including time before
+    /// a resumed checkpoint.
+    pub fn wall_clock_seconds(&self) -> f64 {
+        self.prior_wall_clock + self.tracker.elapsed().as_secs_f64()
+    }
+
+    /// Steps left until `simulation.num_steps` is reached.
+    pub fn remaining_steps(&self) -> u64 {
+        u64::from(self.config.simulation.num_steps).saturating_sub(self.timestamp)
+    }
+
+    /// Progress of the run: steps done, recent and average speed, elapsed time and ETA.
+    /// 
+    /// Available whether or not a progress reporter is set; rates and the
+    /// ETA are `None` until steps have been timed.
+    pub fn progress(&self) -> ProgressSnapshot {
+        let mut progress = self.tracker.snapshot();
+        progress.step = self.timestamp;
+        progress.total_steps = u64::from(self.config.simulation.num_steps);
+        progress
+    }
+
+    /// Per-phase wall-clock timings of the steps run so far.
+    /// 
+    /// # Returns
+    /// The timings, or `None` unless `simulation.profile_phases` is set
+    pub fn phase_timings(&self) -> Option<&PhaseTimings> {
+        self.config.simulation.profile_phases.then_some(&self.phase_timings)
+    }
+
+    /// Run one phase of the step, timing it when profiling is enabled.
+    fn timed(&mut self, phase: Phase, run: fn(&mut Self)) {
+        let start = if self.config.simulation.profile_phases { progress::now() } else { None };
+        run(self);
+        if let Some(start) = start {
+            self.phase_timings.record(phase, start.elapsed());
+        }
+    }
+
+    /// Set every scheduled parameter to its value for the current step.
+    ///
+    /// State and essence parameters are copied into every entity, and
+    /// `stimulus_amplitude` sets each entity's noise amplitude. The values
+    /// are kept for the step record.
+    fn apply_schedules(&mut self) {
+        if self.config.schedules.is_empty() {
+            return;
+        }
+        let step = self.timestamp;
+        let mut values = std::mem::take(&mut self.scheduled_values);
+        values.clear();
+        for (path, schedule) in &self.config.schedules {
+            values.push((path.clone(), schedule.value_at(step)));
+        }
+
+        for (path, value) in &values {
+            if path == STIMULUS_AMPLITUDE {
+                for entity in self.entities.all_entities_mut() {
+                    entity.stimulus_amplitude = *value;
+                }
+                continue;
+            }
+            let Some(parameter) = schedule::parameter_mut(&mut self.config, path) else {
+                continue;
+            };
+            if *parameter == *value {
+                continue;
+            }
+            if !self.configured_values.iter().any(|(configured, _)| configured == path) {
+                self.configured_values.push((path.clone(), *parameter));
+            }
+            *parameter = *value;
+            // Only the scheduled field is copied, so other species
+            // overrides stay in place
+            if path.starts_with("state.") || path.starts_with("essence.") {
+                for entity in self.entities.all_entities_mut() {
+                    let field = match path.as_str() {
+                        "state.decay_alpha" => &mut entity.state.config.decay_alpha,
+                        "state.beta_attention" => &mut entity.state.config.beta_attention,
+                        "state.gamma_memory" => &mut entity.state.config.gamma_memory,
+                        "essence.baseline" => &mut entity.essence.config.baseline,
+                        "essence.decay" => &mut entity.essence.config.decay,
+                        "essence.experience_scale" => &mut entity.essence.config.experience_scale,
+                        _ => continue,
+                    };
+                    *field = *value;
+                }
+            }
+        }
+        self.scheduled_values = values;
     }
 
     /// Sensing: receive input stimulus
     fn sense_step(&mut self) {
-        let mut rng = rand::thread_rng();
-        let entities = self.entities.all_entities_mut();
+        let timestamp = self.timestamp;
+
+        // Entities perceive (and plugins see them) in a reproducible order,
+        // all from the same snapshot of the population
+        // Neighbor-aware models search a grid with cells as wide as their range
+        let radius = self.sense_model.neighbor_radius();
+        if let Some(radius) = radius {
+            self.entities.index_positions(radius);
+        }
 
-        for entity in entities {
-            let dim = entity.pose.position.len();
-            let stimulus: Vec<f32> = (0..dim)
-                .map(|_| rng.gen_range(-0.1..0.1))
-                .collect();
+        // The stimulus buffers are kept across steps and overwritten in place
+        let mut stimuli = std::mem::take(&mut self.scratch.stimuli);
+        let entities = self.entities.all_entities();
+        stimuli.resize_with(entities.len(), || (EntityId(0), Vec::new()));
+        let mut neighbors = Vec::new();
+        for (entity, (id, stimulus)) in entities.iter().zip(stimuli.iter_mut()) {
+            *id = entity.id;
+            let source_stimulus = self.stimulus_source.as_mut().and_then(|source| {
+                source
+                    .stimulus(entity, timestamp)
+                    .filter(|stimulus| stimulus.len() == entity.pose.position.len())
+            });
+            match source_stimulus {
+                Some(source_stimulus) => *stimulus = source_stimulus,
+                None => {
+                    neighbors.clear();
+                    if let Some(radius) = radius {
+                        let within = self.entities.neighbors_within(entity.id, radius, &self.config.geometry);
+                        neighbors.extend(within.iter().filter_map(|(id, _)| self.entities.get_entity(*id)));
+                        neighbors.sort_by_key(|other| other.id);
+                    }
+                    self.sense_model.sense_into(entity, &neighbors, &mut self.rng, stimulus);
+                }
+            }
+        }
+
+        // Scheduled stimuli replace what the entity perceived
+        let mut scheduled = self.scheduled_stimuli.remove(&timestamp).unwrap_or_default();
+        for (own, stimulus) in &stimuli {
+            let Some(entity) = self.entities.get_entity_mut(*own) else {
+                continue;
+            };
+            if !scheduled.iter().any(|(id, _)| id == own) {
+                entity.sense(stimulus, timestamp);
+                continue;
+            }
+            for (_, injected) in scheduled.extract_if(.., |(id, _)| id == own) {
+                entity.sense(&injected, timestamp);
+                self.injected_stimuli.push((own.0, injected));
+            }
+        }
+        self.scratch.stimuli = stimuli;
 
-            entity.sense(stimulus, self.timestamp);
+        self.signal_step();
+
+        // Clusters formed by what was perceived
+        for &(id, next_cluster, _) in &self.scratch.step_start {
+            let Some(entity) = self.entities.get_entity(id) else {
+                continue;
+            };
+            for &cluster in entity.memory_graph.clusters.range(next_cluster..).map(|(cluster, _)| cluster) {
+                self.results.events.record(
+                    timestamp,
+                    EventType::ClusterBorn,
+                    Some(id),
+                    format!("cluster {} formed", cluster),
+                );
+            }
         }
     }
 
-    /// Attention: compute attraction fields
+    /// Signaling: deliver last step's signals, then collect new emissions
+    fn signal_step(&mut self) {
+        self.signal_counts = (0, 0);
+        let config = self.config.signaling.clone();
+        if !config.enabled {
+            self.pending_signals.clear();
+            return;
+        }
+
+        // Delivery: each listener hears attenuated payloads as a distinct stimulus
+        let inbox = std::mem::take(&mut self.pending_signals);
+        let geometry = &self.config.geometry;
+        let mut received = 0;
+        for entity in self.entities.iter_mut() {
+            for signal in &inbox {
+                if signal.sender == entity.id.0 {
+                    continue;
+                }
+                let distance =
+                    crate::geometry::periodic_distance(&signal.origin, &entity.pose.position, geometry);
+                if let Some(message) = signaling::receive(signal, distance, config.hearing_range) {
+                    entity.sense(&message, self.timestamp);
+                    received += 1;
+                }
+            }
+        }
+
+        // Emission: queued for delivery next step, drawn in id order
+        let senders = self.entities.all_entities();
+        let mut outbox = Vec::new();
+        for entity in senders {
+            if let Some(payload) = entity.emit_signal(&config, &mut self.rng) {
+                outbox.push(Signal {
+                    sender: entity.id.0,
+                    origin: entity.pose.position.to_vec(),
+                    payload,
+                    emitted_at: self.timestamp,
+                });
+            }
+        }
+        outbox.sort_by_key(|s| s.sender);
+        for signal in &outbox {
+            self.last_emission.insert(EntityId(signal.sender), self.timestamp);
+        }
+
+        self.signal_counts = (outbox.len(), received);
+        self.pending_signals = outbox;
+    }
+
+    /// Attention: compute attraction fields and assign leader/follower roles
     fn attention_step(&mut self) {
-        // Placeholder: in full implementation, would compute pairwise attraction
-        // For now, entities maintain their attention state from memory
+        let ids = self.sorted_entity_ids();
+        let capture = self.captures_attentions();
+        let entities: Vec<&Entity> = ids.iter().filter_map(|id| self.entities.get_entity(*id)).collect();
+        let n = entities.len();
+        let scratch = &mut self.scratch;
+        scratch.positions.resize_with(n, Vec::new);
+        for (position, entity) in scratch.positions.iter_mut().zip(&entities) {
+            position.clear();
+            position.extend_from_slice(&entity.pose.position);
+        }
+        scratch.attraction_weights.clear();
+        scratch.attraction_weights.extend(entities.iter().map(|e| e.emitted_attraction_weight()));
+
+        // Each entity spreads its attention over the others by softmax of
+        // their attention strengths (`attention_matrix()`)
+        let assign_roles = self.config.roles.enabled;
+        if assign_roles || capture {
+            let lambda = self.config.attraction.lambda;
+            self.attention_weights.resize_with(n, || (EntityId(0), Vec::new()));
+            for (i, (id, row)) in self.attention_weights.iter_mut().enumerate() {
+                let scores = &mut scratch.attention_scores;
+                scores.clear();
+                scores.extend((0..n).filter(|&j| j != i).map(|j| entities[i].attention_strength(entities[j])));
+                crate::attraction::softmax_attention_in_place(scores, lambda);
+                *id = ids[i];
+                row.clear();
+                row.extend_from_slice(&scores[..i]);
+                row.push(0.0);
+                row.extend_from_slice(&scores[i..]);
+            }
+        } else {
+            self.attention_weights.clear();
+        }
+
+        // Attention gradient of every entity in the field of the others
+        let config = &self.config.attraction;
+        if config.theta > 0.0 {
+            let tree = AttractionTree::build(&scratch.positions, &scratch.attraction_weights);
+            scratch.attention_gradients.resize_with(n, Vec::new);
+            for (i, gradient) in scratch.attention_gradients.iter_mut().enumerate() {
+                *gradient = tree.gradient(&scratch.positions[i], Some(i), config);
+            }
+        } else {
+            crate::attraction::attention_gradients(
+                &scratch.positions,
+                &scratch.attraction_weights,
+                config,
+                &mut scratch.attention_gradients,
+            );
+        }
+        let environment = &self.config.environment;
+        if !environment.is_empty() {
+            for (position, gradient) in scratch.positions.iter().zip(scratch.attention_gradients.iter_mut()) {
+                environment.add_gradient(position, config, gradient);
+            }
+        }
+        for (id, gradient) in ids.iter().zip(&scratch.attention_gradients) {
+            if let Some(entity) = self.entities.get_entity_mut(*id) {
+                entity.attention_gradient.clone_from(gradient);
+            }
+        }
+
+        if assign_roles {
+            let attention: Vec<Vec<Real>> = self.attention_weights.iter().map(|(_, row)| row.clone()).collect();
+            self.assign_roles(&ids, &attention);
+        }
+    }
+
+    /// Whether this step records `SimulationStep::attentions`.
+    fn captures_attentions(&self) -> bool {
+        let interval = self.config.simulation.attention_capture_interval as u64;
+        self.config.recording.attentions
+            && self.records_step()
+            && interval > 0
+            && self.timestamp.is_multiple_of(interval)
+    }
+
+    /// Whether this step is recorded into `results.steps`: every
+    /// `recording.record_every` steps, the last configured step and any
+    /// step an external stimulus was injected at.
+    fn records_step(&self) -> bool {
+        let every = self.config.recording.record_every.max(1) as u64;
+        self.timestamp.is_multiple_of(every)
+            || self.timestamp + 1 == self.config.simulation.num_steps as u64
+            || !self.injected_stimuli.is_empty()
+    }
+
+    /// Assign leader/follower roles from the attention distribution.
+    /// 
+    /// # Arguments
+    /// * `ids` - Entity ids in row order of `attention`
+    /// * `attention` - Softmax attention of each entity over the others
+    fn assign_roles(&mut self, ids: &[EntityId], attention: &[Vec<Real>]) {
+        let config = self.config.roles.clone();
+        let n = ids.len();
+        let received = roles::received_attention(attention);
+        let candidates = roles::leader_candidates(&received, config.leader_percentile);
+
+        // Refresh or decay leadership
+        let mut leaders = BTreeSet::new();
+        for (i, id) in ids.iter().enumerate() {
+            if let Some(entity) = self.entities.get_entity_mut(*id) {
+                if candidates.contains(&i) {
+                    entity.leadership = 1.0;
+                } else {
+                    entity.leadership *= config.decay;
+                }
+                if entity.leadership >= roles::LEADER_RETENTION {
+                    leaders.insert(*id);
+                }
+            }
+        }
+
+        // Followers attach to the leader they attend to most
+        for (i, id) in ids.iter().enumerate() {
+            let follows = if leaders.contains(id) {
+                None
+            } else {
+                (0..n)
+                    .filter(|&j| leaders.contains(&ids[j]))
+                    .max_by(|&a, &b| {
+                        attention[i][a]
+                            .partial_cmp(&attention[i][b])
+                            .unwrap_or(std::cmp::Ordering::Equal)
+                            .then(b.cmp(&a))
+                    })
+                    .map(|j| ids[j])
+            };
+            if let Some(entity) = self.entities.get_entity_mut(*id) {
+                let leading = leaders.contains(id);
+                entity.role = if leading {
+                    Role::Leader
+                } else if follows.is_some() {
+                    Role::Follower
+                } else {
+                    Role::Member
+                };
+                entity.leader_boost = if leading { config.attraction_boost } else { 0.0 };
+                entity.follows = follows;
+            }
+        }
+
+        self.role_stability = roles::role_stability(&self.previous_leaders, &leaders);
+        self.previous_leaders = leaders;
     }
 
     /// State update: integrate state changes
     fn state_update_step(&mut self) {
-        let entities = self.entities.all_entities_mut();
-
-        for entity in entities {
-            let mut gradient = vec![0.0; entity.state.memory.len()];
-            for i in 0..gradient.len().min(3) {
-                gradient[i] = entity.memory_graph.nodes.len() as f32 * 0.01;
+        let scratch = &mut self.scratch;
+
+        for entity in self.entities.iter_mut() {
+            let dim = entity.state.memory.len();
+            if self.config.dynamics.legacy_pipeline {
+                scratch.gradient.clear();
+                scratch.gradient.resize(dim, 0.0);
+                for g in scratch.gradient.iter_mut().take(3) {
+                    *g = entity.memory_graph.nodes.len() as Real * 0.01;
+                }
+                entity.update_state_legacy(&scratch.gradient);
+                continue;
             }
-            entity.update_state(&gradient);
+            // s = α·s + β·g(F) + γ·m with the gradient from attention_step
+            scratch.memory_input.clear();
+            scratch.memory_input.resize(dim, 0.0);
+            entity.memory_graph.memory_input_into(&mut scratch.memory_input);
+            let attention_force = std::mem::take(&mut entity.attention_gradient);
+            entity.update_state(&attention_force, &scratch.memory_input);
+            entity.attention_gradient = attention_force;
         }
     }
 
     /// Affective: update affective signals from memory
     fn affective_step(&mut self) {
-        let entities = self.entities.all_entities_mut();
-
-        for entity in entities {
+        for entity in self.entities.iter_mut() {
             entity.memory_graph.update_affective_signals();
         }
     }
 
     /// Essence: update well-being tracking
     fn essence_step(&mut self) {
-        let entities = self.entities.all_entities_mut();
+        let signals = &mut self.scratch.affective_signals;
 
-        for entity in entities {
-            let mut signals = Vec::new();
-            for cluster in entity.memory_graph.clusters.values() {
-                signals.push(cluster.affective_signal);
-            }
+        for entity in self.entities.iter_mut() {
+            signals.clear();
+            signals.extend(entity.memory_graph.clusters.values().map(|c| c.affective_signal));
             entity.essence.update(signals.as_slice());
         }
+
+        // Essence crossing an extreme level during the step
+        for &(id, _, before) in &self.scratch.step_start {
+            let Some(entity) = self.entities.get_entity(id) else {
+                continue;
+            };
+            let after = entity.essence.value;
+            let (event, verb) = if before >= ESSENCE_CRISIS_LEVEL && after < ESSENCE_CRISIS_LEVEL {
+                (EventType::EssenceCrisis(after), "fell")
+            } else if before <= ESSENCE_PEAK_LEVEL && after > ESSENCE_PEAK_LEVEL {
+                (EventType::EssencePeak(after), "rose")
+            } else {
+                continue;
+            };
+            self.results.events.record(self.timestamp, event, Some(id), format!("essence {} to {:.2}", verb, after));
+        }
     }
 
     /// Decision: compute actions based on state and essence
     fn decision_step(&mut self) {
-        let entities = self.entities.all_entities_mut();
+        // Drives respond to crowding and attention before deciding; the
+        // nearest neighbors come from a grid with cells about as wide as
+        // the mean spacing (or the sensing range, whose grid it reuses)
+        let geometry = &self.config.geometry;
+        let cell_size = self.sense_model.neighbor_radius().unwrap_or_else(|| {
+            let volume: Real = geometry.bounds.iter().product();
+            (volume / self.entities.count().max(1) as Real).powf(1.0 / geometry.bounds.len().max(1) as Real)
+        });
+        self.entities.index_positions(cell_size);
+        let nearest = &mut self.scratch.nearest;
+        nearest.clear();
+        nearest.extend(self.entities.iter().map(|e| {
+            let distance = self.entities.nearest_distance(e.id, geometry).unwrap_or(Real::INFINITY);
+            (e.id, distance)
+        }));
+        let responsiveness = self.config.drives.responsiveness;
+        for &(id, distance) in nearest.iter() {
+            if let Some(entity) = self.entities.get_entity_mut(id) {
+                entity.update_drives(distance, responsiveness);
+            }
+        }
 
-        for entity in entities {
-            let _action = entity.decide();
-            // Actions are implicitly applied in integration step
+        for entity in self.entities.iter_mut() {
+            // Reuse the previous action's allocation
+            let mut action = std::mem::take(&mut entity.last_action);
+            entity.decide_into(&mut action);
+            entity.last_action = action;
+        }
+    }
+
+    /// Action: turn decided actions into bounded forces
+    fn act_step(&mut self) {
+        if self.config.dynamics.legacy_pipeline {
+            return;
+        }
+        let max_force = self.config.dynamics.max_action_force;
+        for entity in self.entities.iter_mut() {
+            let action = std::mem::take(&mut entity.last_action);
+            entity.act(&action, max_force);
+            entity.last_action = action;
+        }
+
+        // Followers are pulled toward their leader
+        if self.config.roles.enabled {
+            let bias = self.config.roles.follower_bias;
+            let pulls: Vec<(EntityId, Vec<Real>)> = self
+                .entities
+                .iter()
+                .filter_map(|e| {
+                    let leader = self.entities.get_entity(e.follows?)?;
+                    let offset = crate::geometry::periodic_displacement(
+                        &e.pose.position,
+                        &leader.pose.position,
+                        &self.config.geometry,
+                    );
+                    let norm = offset.iter().map(|o| o * o).sum::<Real>().sqrt();
+                    if norm <= 1e-6 {
+                        return None;
+                    }
+                    Some((e.id, offset.iter().map(|o| bias * o / norm).collect()))
+                })
+                .collect();
+            for (id, pull) in pulls {
+                if let Some(entity) = self.entities.get_entity_mut(id) {
+                    for (f, p) in entity.force.iter_mut().zip(pull.iter()) {
+                        *f += p;
+                    }
+                }
+            }
         }
     }
 
     /// Integration: advance positions and velocities
     fn integration_step(&mut self) {
-        let entities = self.entities.all_entities_mut();
+        // Positions dragged in the visualization replace the current ones
+        for (id, position) in std::mem::take(&mut self.manual_overrides) {
+            if let Some(entity) = self.entities.get_entity_mut(id) {
+                if position.len() == entity.pose.position.len() {
+                    entity.pose.position = position.into();
+                }
+            }
+        }
+
+        let acceleration = &mut self.scratch.acceleration;
+
+        for entity in self.entities.iter_mut() {
+            acceleration.clear();
+            acceleration.resize(entity.pose.position.len(), 0.0);
+            if self.config.dynamics.legacy_pipeline {
+                for i in 0..acceleration.len().min(2) {
+                    acceleration[i] = 0.01 / entity.mass; // Small constant acceleration
+                }
+            } else {
+                // Pull of the attraction field
+                crate::dynamics::add_acceleration_from_gradient(&entity.attention_gradient, acceleration);
+            }
+
+            // Forces from the last action (a = F / m)
+            for (a, f) in acceleration.iter_mut().zip(entity.force.iter_mut()) {
+                *a += *f / entity.mass;
+                *f = 0.0;
+            }
 
-        for entity in entities {
-            let mut acceleration = vec![0.0; entity.pose.position.len()];
-            for i in 0..acceleration.len().min(2) {
-                acceleration[i] = 0.01; // Small constant acceleration
+            // Exhausted entities can barely accelerate and may come to rest
+            let energy = &self.config.energy;
+            let exhausted = energy.enabled && entity.is_exhausted();
+            if exhausted {
+                crate::energy::clamp_magnitude(acceleration, energy.exhausted_max_acceleration);
             }
+            let action_magnitude = acceleration.iter().map(|a| a * a).sum::<Real>().sqrt();
 
             entity.integrate(
                 acceleration,
                 self.config.dynamics.dt,
-                self.config.dynamics.min_speed,
+                if exhausted { 0.0 } else { self.config.dynamics.min_speed },
                 self.config.dynamics.damping,
             );
+            if energy.enabled {
+                entity.metabolize(action_magnitude, energy);
+            }
+            if self.config.dynamics.auto_heading {
+                entity.update_heading(self.config.dynamics.max_turn_rate * self.config.dynamics.dt);
+            }
         }
     }
 
-    /// Boundaries: apply periodic boundary conditions
+    /// Boundaries: push entities out of obstacles, then apply periodic
+    /// boundary conditions
     fn boundary_step(&mut self) {
-        let entities = self.entities.all_entities_mut();
+        let environment = &self.config.environment;
+        if !environment.is_empty() {
+            for entity in self.entities.iter_mut() {
+                environment.push_out(&mut entity.pose.position, &mut entity.velocity);
+            }
+        }
+
         let config = &self.config.geometry;
         let bounds = &config.bounds;
 
         if config.periodic {
-            for entity in entities {
+            for entity in self.entities.iter_mut() {
                 for d in 0..entity.pose.position.len() {
                     let bound = bounds[d];
 
-                    if entity.pose.position[d] < 0.0 {
+                    let side = if entity.pose.position[d] < 0.0 {
                         entity.pose.position[d] += bound;
+                        "lower"
                     } else if entity.pose.position[d] >= bound {
                         entity.pose.position[d] -= bound;
-                    }
+                        "upper"
+                    } else {
+                        continue;
+                    };
+                    self.results.events.record(
+                        self.timestamp,
+                        EventType::BoundaryWrap(d),
+                        Some(entity.id),
+                        format!("left through the {} edge of axis {}", side, d),
+                    );
                 }
             }
         }
@@ -254,34 +1652,123 @@ impl Simulation {
 
     /// Memory decay: apply forgetting
     fn memory_decay_step(&mut self) {
-        let entities = self.entities.all_entities_mut();
-
-        for entity in entities {
+        for entity in self.entities.iter_mut() {
             entity.memory_graph.decay(self.config.state.decay_alpha);
         }
     }
 
     /// Metrics: compute evaluation metrics
     fn metrics_step(&mut self) {
-        let metrics = Metrics::compute(&self.entities, self.timestamp);
+        self.record_phases();
+
+        let mut metrics = Metrics::compute(&self.entities, self.timestamp);
+        metrics.phase_synchrony = self.kuramoto_order();
+        metrics.role_stability = self.role_stability;
         self.metrics_history.push(metrics.clone());
 
-        // Capture detailed step information
-        let mut step = SimulationStep::new(self.timestamp, metrics);
+        // Attractions feed the interaction weights every step, recorded or not
+        let attractions = self.significant_attractions();
+        for &(a, b, attraction) in &attractions {
+            let (a, b) = (EntityId(a), EntityId(b));
+            *self.interaction_weights.entry((a.min(b), a.max(b))).or_insert(0.0) += attraction;
+        }
+
+        if self.records_step() {
+            self.record_step(metrics, attractions);
+        }
+    }
+
+    /// Significant pairwise attractions (simplified: based on distances)
+    /// among the pairs within the cutoff, found through the spatial grid.
+    /// 
+    /// # Returns
+    /// (lower id, higher id, attraction) in id order, sized like the
+    /// previous recorded step's list
+    fn significant_attractions(&mut self) -> Vec<(u32, u32, Real)> {
+        let cutoff = self.config.attraction.effective_cutoff();
+        if self.pair_grid.cell_size() != cutoff {
+            self.pair_grid = SpatialGrid::new(cutoff);
+        }
+        let ordered = self.entities.all_entities();
+        let positions: Vec<&[Real]> = ordered.iter().map(|e| e.pose.position.as_slice()).collect();
+        self.pair_grid.rebuild(positions.iter().copied());
+
+        let hint = self.results.steps.last().map_or(0, |previous| previous.attractions.len());
+        let mut attractions = Vec::with_capacity(hint);
+        for (i, j) in self.pair_grid.pairs_within(&positions, cutoff) {
+            let (first, second) = (ordered[i], ordered[j]);
+            let attraction = first.attention_strength(second);
+            if attraction > 0.01 {
+                // Only record significant attractions
+                attractions.push((first.id.0, second.id.0, attraction));
+            }
+        }
+        attractions
+    }
 
-        let all_entities = self.entities.all_entities();
+    /// Record the current state into `results.steps`, with the fields
+    /// selected by `recording`.
+    /// 
+    /// # Arguments
+    /// * `metrics` - Metrics of the step (its timestamp is the step number)
+    /// * `attractions` - Significant attractions of the step
+    fn record_step(&mut self, metrics: Metrics, attractions: Vec<(u32, u32, Real)>) {
+        let recording = &self.config.recording;
+
+        // Per-entity vectors are sized for the population up front
+        let count = self.entities.count();
+        let mut step = SimulationStep::with_capacity(metrics.timestamp, metrics, count, 0);
+        if recording.attractions {
+            step.attractions = attractions;
+        }
+        step.center_of_mass = self.entities.centroid_position();
+        step.mean_velocity = self.entities.centroid_velocity();
+        if self.config.roles.enabled {
+            step.entity_roles = self
+                .sorted_entity_ids()
+                .iter()
+                .filter_map(|id| self.entities.get_entity(*id).map(|e| (id.0, e.role)))
+                .collect();
+        }
+        step.injected_stimuli = std::mem::take(&mut self.injected_stimuli);
+        step.scheduled_values = self.scheduled_values.clone();
+        step.signals_emitted = self.signal_counts.0;
+        step.signals_received = self.signal_counts.1;
 
-        // Capture entity positions, velocities, essence, and belief clusters
-        for entity in all_entities {
-            step.entity_positions
-                .push((entity.id.0, entity.pose.position.clone()));
-            step.entity_velocities
-                .push((entity.id.0, entity.velocity.clone()));
+        if self.config.simulation.per_species_metrics {
+            step.species_metrics = self.compute_species_metrics();
+        }
+
+        // Attention distributions are only recorded on capture steps
+        if self.captures_attentions() {
+            step.attentions = self
+                .attention_weights
+                .iter()
+                .map(|(id, weights)| (id.0, weights.clone()))
+                .collect();
+        }
+
+        // Capture entity positions, velocities, essence, and belief clusters,
+        // in id order so identical runs record identical steps
+        let ordered = self.entities.all_entities();
+        for &entity in &ordered {
+            if recording.positions {
+                step.entity_positions
+                    .push((entity.id.0, Coords::from_slice(&entity.pose.position)));
+                step.entity_velocities
+                    .push((entity.id.0, Coords::from_slice(&entity.velocity)));
+            }
             step.entity_essence
                 .push((entity.id.0, entity.essence.value));
+            step.entity_energy
+                .push((entity.id.0, entity.energy));
+
+            if !recording.clusters {
+                continue;
+            }
 
             // Capture belief clusters with affective signals
-            let mut clusters_for_entity = Vec::new();
+            let mut clusters_for_entity = Vec::with_capacity(entity.memory_graph.clusters.len());
             for (cluster_id, cluster) in &entity.memory_graph.clusters {
                 clusters_for_entity.push((
                     *cluster_id,
@@ -293,66 +1780,402 @@ impl Simulation {
                 step.belief_clusters
                     .push((entity.id.0, clusters_for_entity));
             }
+        }
+
+        // Add step to results
+        self.results.add_step(step);
+    }
+
+    /// Reproduction: spawn offspring from thriving entities
+    fn reproduction_step(&mut self) {
+        let config = self.config.reproduction.clone();
+        if !config.enabled {
+            return;
+        }
 
-            // Capture attention activation
-            let mut attention_values = Vec::new();
-            for node in &entity.memory_graph.nodes {
-                attention_values.push(node.activation);
+        let mut parents = Vec::new();
+        for entity in self.entities.iter_mut() {
+            if entity.essence.value >= config.essence_threshold {
+                entity.high_essence_steps += 1;
+            } else {
+                entity.high_essence_steps = 0;
             }
-            if !attention_values.is_empty() {
-                step.attentions.push((entity.id.0, attention_values));
+            if entity.high_essence_steps >= config.sustain_steps
+                && entity.energy >= config.energy_threshold
+            {
+                parents.push(entity.id);
             }
         }
 
-        // Compute pairwise attractions (simplified: based on distances)
-        let entities_vec = self.entities.all_entities();
-        for i in 0..entities_vec.len() {
-            for j in (i + 1)..entities_vec.len() {
-                let dist = entities_vec[i]
-                    .pose
-                    .distance_to(&entities_vec[j].pose);
-                let attraction = 1.0 / (1.0 + dist); // Simple inverse distance
-                if attraction > 0.01 {
-                    // Only record significant attractions
-                    step.attractions.push((
-                        entities_vec[i].id.0,
-                        entities_vec[j].id.0,
-                        attraction,
-                    ));
-                }
+        for parent_id in parents {
+            if self.entities.count() as u32 >= config.max_population {
+                break;
             }
+            self.spawn_offspring(parent_id, &config);
         }
+    }
 
-        // Add step to results
-        self.results.add_step(step);
+    /// Spawn one offspring of `parent_id` near its parent.
+    /// 
+    /// The offspring inherits mutated traits and drives, the parent's
+    /// species parameters, and a sampled subset of its belief-cluster
+    /// prototypes. Energy is transferred from parent to offspring.
+    fn spawn_offspring(
+        &mut self,
+        parent_id: EntityId,
+        config: &ReproductionConfig,
+    ) -> Option<EntityId> {
+        let parent = self.entities.get_entity(parent_id)?;
+        let rng = &mut self.rng;
+
+        let mut position = parent.pose.position;
+        for p in &mut position {
+            *p += rng.gen_range(-1.0..=1.0) * config.spawn_radius;
+        }
+        let geometry = &self.config.geometry;
+        if geometry.periodic {
+            crate::geometry::apply_periodic_bounds(&mut position, &geometry.bounds, true);
+        } else {
+            for (p, bound) in position.iter_mut().zip(geometry.bounds.iter()) {
+                *p = p.clamp(0.0, *bound);
+            }
+        }
+
+        let mut child = new_entity(&self.config, Pose::new(position.len()).with_position(position), rng).ok()?;
+        child.species = parent.species;
+        child.mass = parent.mass;
+        child.stimulus_amplitude = parent.stimulus_amplitude;
+        child.attraction_weight = parent.attraction_weight;
+        child.essence.config = parent.essence.config.clone();
+        child.essence.value = parent.essence.config.baseline;
+        child.state.config = parent.state.config.clone();
+
+        child.state.traits = parent
+            .state
+            .traits
+            .iter()
+            .map(|t| t + rng.gen_range(-1.0..=1.0) * config.trait_mutation)
+            .collect();
+        child.innate_drives = (
+            (parent.innate_drives.0 + rng.gen_range(-1.0..=1.0) * config.drive_mutation).max(0.0),
+            (parent.innate_drives.1 + rng.gen_range(-1.0..=1.0) * config.drive_mutation).max(0.0),
+        );
+        child.baseline_drives = child.innate_drives;
+
+        // Inherit a sample of belief prototypes rather than episodic history
+        let mut cluster_ids: Vec<u32> = parent.memory_graph.clusters.keys().copied().collect();
+        cluster_ids.sort_unstable();
+        for cluster_id in cluster_ids {
+            if rng.gen::<Real>() >= config.prototype_inheritance {
+                continue;
+            }
+            if let Some(prototype) = parent.memory_graph.cluster_prototype(cluster_id) {
+                child.sense(&prototype, self.timestamp);
+            }
+        }
+
+        // The child starts from the configured initial energy like any
+        // entity; the transfer is booked against that in its ledger
+        let transfer = parent.energy * config.energy_transfer;
+        let difference = transfer - child.energy;
+        if difference >= 0.0 {
+            child.energy_gained += difference;
+        } else {
+            child.energy_spent -= difference;
+        }
+        child.energy = transfer;
+
+        if let Some(parent) = self.entities.get_entity_mut(parent_id) {
+            parent.energy -= transfer;
+            parent.energy_spent += transfer;
+            parent.high_essence_steps = 0;
+        }
+
+        Some(self.insert_entity(child, Some(parent_id)))
+    }
+
+    /// Record each entity's current velocity phase into its ring buffer.
+    fn record_phases(&mut self) {
+        for entity in self.entities.iter() {
+            let history = self.phase_history.entry(entity.id).or_default();
+            if history.len() >= crate::analysis::SYNCHRONY_WINDOW {
+                history.pop_front();
+            }
+            history.push_back(crate::analysis::velocity_phase(&entity.velocity));
+        }
+    }
+
+    /// Compute metrics separately for each species present in the pool.
+    /// 
+    /// # Returns
+    /// (species name, metrics) pairs in species id order
+    pub fn compute_species_metrics(&self) -> Vec<(String, Metrics)> {
+        let mut groups: std::collections::BTreeMap<SpeciesId, Vec<&Entity>> =
+            std::collections::BTreeMap::new();
+        for entity in self.entities.all_entities() {
+            groups.entry(entity.species).or_default().push(entity);
+        }
+
+        groups
+            .into_iter()
+            .map(|(id, members)| {
+                (
+                    species::species_name(&self.config.species, id).to_string(),
+                    Metrics::compute_for(&members, self.timestamp),
+                )
+            })
+            .collect()
+    }
+
+    /// Compute metrics restricted to the entities carrying a label.
+    /// 
+    /// # Returns
+    /// Metrics of the label group, or None if no entity carries the label
+    pub fn compute_label_metrics(&self, label: &str) -> Option<Metrics> {
+        let members: Vec<&Entity> = self
+            .entities
+            .with_label(label)
+            .into_iter()
+            .filter_map(|id| self.entities.get_entity(id))
+            .collect();
+        if members.is_empty() {
+            return None;
+        }
+        Some(Metrics::compute_for(&members, self.timestamp))
     }
 
-    /// Run simulation for configured number of steps.
+    /// Run the steps left until `simulation.num_steps` is reached.
+    /// 
+    /// A fresh simulation runs all configured steps; one resumed from a
+    /// checkpoint runs the rest. Ends early once [`Simulation::stop_requested`].
     pub fn run(&mut self) {
-        let num_steps = self.config.simulation.num_steps;
-        for _ in 0..num_steps {
+        for _ in 0..self.remaining_steps() {
+            if self.stop_requested() {
+                break;
+            }
+            self.step();
+        }
+    }
+
+    /// Run the steps left, like [`Simulation::run`], calling `callback`
+    /// with the progress after every step.
+    /// 
+    /// The callback sees the same snapshot as [`Simulation::progress`]
+    /// (steps done, recent and average steps per second, elapsed time and
+    /// ETA) and is called in addition to any progress reporter. It decides
+    /// itself how often to show anything:
+    /// 
+    /// ```no_run
+    /// # use synthetic_consciousness::{Simulation, SimulationConfig};
+    /// # use std::time::{Duration, Instant};
+    /// # let mut sim = Simulation::new(SimulationConfig::default_2d()).unwrap();
+    /// let mut shown = Instant::now();
+    /// sim.run_with_progress(|progress| {
+    ///     if shown.elapsed() >= Duration::from_secs(1) || progress.is_complete() {
+    ///         eprint!("\r{}", progress);
+    ///         shown = Instant::now();
+    ///     }
+    /// });
+    /// ```
+    pub fn run_with_progress<F: FnMut(&ProgressSnapshot)>(&mut self, mut callback: F) {
+        for _ in 0..self.remaining_steps() {
+            if self.stop_requested() {
+                break;
+            }
             self.step();
+            callback(&self.progress());
         }
     }
 
+    /// Run until every consciousness criterion has held for `sustain_window`
+    /// consecutive steps, or `max_steps` steps have run.
+    /// 
+    /// Criteria are the thresholds of
+    /// [`CONSCIOUSNESS_THRESHOLDS`](crate::results::CONSCIOUSNESS_THRESHOLDS), checked
+    /// against each step's metrics. `simulation.num_steps` is not consulted;
+    /// the run also ends once [`Simulation::stop_requested`]. Call
+    /// `finalize_results` afterwards as usual.
+    /// 
+    /// # Arguments
+    /// * `max_steps` - Most steps to run in this call
+    /// * `sustain_window` - Consecutive passing steps required (at least 1)
+    /// 
+    /// # Returns
+    /// Whether the criteria were sustained, and at which steps
+    pub fn run_until_conscious(&mut self, max_steps: u64, sustain_window: u64) -> ConsciousnessRun {
+        let sustain_window = sustain_window.max(1);
+        let mut streak = 0;
+        for _ in 0..max_steps {
+            if self.stop_requested() {
+                break;
+            }
+            self.step();
+            let met = self.metrics_history.last().is_some_and(meets_consciousness_thresholds);
+            streak = if met { streak + 1 } else { 0 };
+            if streak >= sustain_window {
+                return ConsciousnessRun {
+                    stopped_early: true,
+                    stopped_at: self.timestamp,
+                    sustained_from: Some(self.timestamp - streak),
+                };
+            }
+        }
+        ConsciousnessRun {
+            stopped_early: false,
+            stopped_at: self.timestamp,
+            sustained_from: None,
+        }
+    }
+
+    /// Run until `predicate` holds, or `max_steps` steps have run.
+    /// 
+    /// The predicate is checked before every step and once after the last,
+    /// so a simulation that already satisfies it does not step at all.
+    /// `simulation.num_steps` is not consulted; the run also ends once
+    /// [`Simulation::stop_requested`]. Call `finalize_results` afterwards
+    /// as usual; results then report the steps actually run.
+    /// 
+    /// ```
+    /// use synthetic_consciousness::Simulation;
+    ///
+    /// let mut sim = Simulation::builder().entities(5).build().unwrap();
+    /// let run = sim.run_until(|sim| sim.metrics_history.last().is_some_and(|m| m.average_essence > 8.0), 50);
+    /// assert_eq!(sim.timestamp, run.steps_run);
+    /// ```
+    /// 
+    /// # Arguments
+    /// * `predicate` - Condition on the simulation ending the run
+    /// * `max_steps` - Most steps to run in this call
+    /// 
+    /// # Returns
+    /// How many steps ran and whether the predicate was met
+    pub fn run_until<F>(&mut self, mut predicate: F, max_steps: u64) -> PredicateRun
+    where
+        F: FnMut(&Simulation) -> bool,
+    {
+        let mut steps_run = 0;
+        loop {
+            if predicate(self) {
+                return PredicateRun { steps_run, predicate_met: true };
+            }
+            if steps_run >= max_steps || self.stop_requested() {
+                return PredicateRun { steps_run, predicate_met: false };
+            }
+            self.step();
+            steps_run += 1;
+        }
+    }
+
+    /// Install a flag that stops `run()` before its next step once set.
+    /// 
+    /// Another thread can set the flag to cancel a run cooperatively; the
+    /// simulation is left in a consistent state after the last full step.
+    pub fn set_stop_flag(&mut self, flag: Arc<AtomicBool>) {
+        self.stop_flag = Some(flag);
+    }
+
+    /// Whether the stop flag (if any) has been set or an observer returned
+    /// [`StepControl::Stop`].
+    pub fn stop_requested(&self) -> bool {
+        self.observer_stop || self.stop_flag.as_ref().is_some_and(|flag| flag.load(Ordering::Relaxed))
+    }
+
+    /// Change parameters of the running simulation.
+    /// 
+    /// Sets the non-`None` fields of `patch` on the current configuration;
+    /// the next step uses the new values. A new `experience_scale` also
+    /// applies to existing entities, except those whose species overrides
+    /// it (`[species.*] essence.experience_scale`). Applied patches are
+    /// recorded as `ConfigPatched` events by the event tracker.
+    /// 
+    /// # Arguments
+    /// * `patch` - Parameters to change
+    /// 
+    /// # Returns
+    /// `ValidationError` (and no change) if a value is out of range
+    pub fn apply_config_patch(&mut self, patch: SimulationConfigPatch) -> Result<(), SimulationError> {
+        if !patch.is_valid() {
+            return Err(SimulationError::ValidationError(format!(
+                "config patch out of range: {}",
+                patch.describe()
+            )));
+        }
+        if patch.is_empty() {
+            return Ok(());
+        }
+        patch.apply_to(&mut self.config);
+        if let Some(scale) = patch.experience_scale {
+            let overridden: Vec<SpeciesId> = species::enumerate_species(&self.config.species)
+                .into_iter()
+                .filter(|(_, _, cfg)| cfg.essence.experience_scale.is_some())
+                .map(|(id, _, _)| id)
+                .collect();
+            for entity in self.entities.all_entities_mut() {
+                if !overridden.contains(&entity.species) {
+                    entity.essence.config.experience_scale = scale;
+                }
+            }
+        }
+        self.config_patches.push((self.timestamp, patch));
+        Ok(())
+    }
+
+    /// Patches applied so far, with the step they took effect at.
+    pub fn config_patches(&self) -> &[(u64, SimulationConfigPatch)] {
+        &self.config_patches
+    }
+
+    /// SHA-256 fingerprint of the current configuration (hex).
+    /// 
+    /// See [`SimulationConfig::fingerprint`]; it is written into reports
+    /// and CSV exports and used as a filename suffix by `generate_report`.
+    /// Config patches change it, schedules do not.
+    pub fn export_config_fingerprint(&self) -> String {
+        self.configured().fingerprint()
+    }
+
+    /// The configuration with scheduled parameters at their configured
+    /// values rather than the values of the current step, so a run keeps
+    /// the fingerprint it started with.
+    fn configured(&self) -> std::borrow::Cow<'_, SimulationConfig> {
+        if self.configured_values.is_empty() {
+            return std::borrow::Cow::Borrowed(&self.config);
+        }
+        let mut config = self.config.clone();
+        for (path, value) in &self.configured_values {
+            if let Some(parameter) = schedule::parameter_mut(&mut config, path) {
+                *parameter = *value;
+            }
+        }
+        std::borrow::Cow::Owned(config)
+    }
+
+    /// Short fingerprint used as a filename suffix.
+    pub fn fingerprint_suffix(&self) -> String {
+        self.export_config_fingerprint()[..crate::config::FINGERPRINT_SUFFIX_LEN].to_string()
+    }
+
     /// Export metrics as CSV.
+    /// 
+    /// The first line is a `# config_fingerprint: <hex>` comment.
     pub fn export_metrics_csv(&self, path: &str) -> Result<(), Box<dyn std::error::Error>> {
         use std::fs::File;
         use std::io::Write;
 
         let mut file = File::create(path)?;
+        writeln!(file, "# config_fingerprint: {}", self.export_config_fingerprint())?;
 
         // Header
         writeln!(
             file,
-            "timestamp,attention_entropy,memory_diversity,velocity_stability,identity_coherence,cluster_stability,affective_strength,essence_trajectory,average_essence"
+            "timestamp,attention_entropy,memory_diversity,velocity_stability,identity_coherence,cluster_stability,affective_strength,essence_trajectory,average_essence,phase_synchrony,mean_energy,role_stability"
         )?;
 
         // Data
         for metrics in &self.metrics_history {
             writeln!(
                 file,
-                "{},{},{},{},{},{},{},{},{}",
+                "{},{},{},{},{},{},{},{},{},{},{},{}",
                 metrics.timestamp,
                 metrics.attention_entropy,
                 metrics.memory_diversity,
@@ -361,7 +2184,58 @@ impl Simulation {
                 metrics.cluster_stability,
                 metrics.affective_strength,
                 metrics.essence_trajectory,
-                metrics.average_essence
+                metrics.average_essence,
+                metrics.phase_synchrony,
+                metrics.mean_energy,
+                metrics.role_stability
+            )?;
+        }
+
+        Ok(())
+    }
+
+    /// Export the current per-entity state as CSV.
+    /// 
+    /// Writes one row per entity (ordered by id) with position, speed,
+    /// essence, energy and memory statistics, after a
+    /// `# config_fingerprint: <hex>` comment line.
+    pub fn export_entities_csv(&self, path: &str) -> Result<(), Box<dyn std::error::Error>> {
+        use std::fs::File;
+        use std::io::Write;
+
+        let mut file = File::create(path)?;
+        writeln!(file, "# config_fingerprint: {}", self.export_config_fingerprint())?;
+        let dim = self.config.geometry.dimension;
+        let axes = ["x", "y", "z"];
+
+        let position_header: Vec<&str> = axes.iter().take(dim).copied().collect();
+        writeln!(
+            file,
+            "entity_id,species,labels,{},speed,essence,preservation,curiosity,energy,energy_gained,energy_spent,dormant,num_clusters,num_memory_nodes,mean_information_content",
+            position_header.join(",")
+        )?;
+
+        for entity in self.entities.iter() {
+            let position: Vec<String> = entity.pose.position.iter().map(|p| p.to_string()).collect();
+            let speed = entity.velocity.norm();
+            writeln!(
+                file,
+                "{},{},{},{},{},{},{},{},{},{},{},{},{},{},{}",
+                entity.id.0,
+                species::species_name(&self.config.species, entity.species),
+                entity.labels.join(";"),
+                position.join(","),
+                speed,
+                entity.essence.value,
+                entity.baseline_drives.0,
+                entity.baseline_drives.1,
+                entity.energy,
+                entity.energy_gained,
+                entity.energy_spent,
+                entity.dormant,
+                entity.memory_graph.clusters.len(),
+                entity.memory_graph.nodes.len(),
+                entity.memory_graph.mean_information_content()
             )?;
         }
 
@@ -370,56 +2244,195 @@ impl Simulation {
 
     /// Finalize simulation results and analyze consciousness.
     pub fn finalize_results(&mut self) {
-        let end_time = Local::now().to_rfc3339();
+        // A run stopped between recorded steps still records its final state
+        if let Some(last) = self.metrics_history.last().cloned() {
+            if self.results.steps.last().is_none_or(|step| step.step_number < last.timestamp) {
+                let attractions = self.significant_attractions();
+                self.record_step(last, attractions);
+            }
+        }
+
+        let end_time = crate::results::timestamp_now();
         self.results.end_time = end_time;
-        self.results.duration_seconds = self.timestamp as f32 * self.config.dynamics.dt;
+        self.results.config_fingerprint = self.export_config_fingerprint();
+        self.results.config = Some(self.configured().into_owned());
+        self.results.num_steps = u32::try_from(self.timestamp).unwrap_or(u32::MAX);
+        self.results.duration_seconds = self.timestamp as Real * self.config.dynamics.dt;
+        self.results.wall_clock_seconds = self.wall_clock_seconds();
+        self.results.phase_timings = self.phase_timings().cloned();
+        let mut motif_counts = HashMap::new();
+        for entity in self.entities.all_entities() {
+            for (label, count) in entity.memory_graph.motif_detection(3) {
+                *motif_counts.entry(label).or_insert(0) += count;
+            }
+        }
+        self.results.motif_counts = motif_counts;
+        // A stride that lands on recorded steps
+        let record_every = self.config.recording.record_every.max(1) as u64;
+        self.results.state_distance_series =
+            self.total_variation_series(crate::analysis::TVD_STRIDE.next_multiple_of(record_every));
+        let (similarity_steps, similarity) =
+            self.sampled_self_similarity(crate::analysis::SELF_SIMILARITY_REPORT_SIZE);
+        self.results.self_similarity_steps = similarity_steps;
+        self.results.self_similarity = similarity;
+        self.results.recurrence = self.core_recurrence_quantification();
+        self.results.emergent_leaders = self
+            .detect_emergent_leaders()
+            .into_iter()
+            .map(|(id, score)| (id.0, score))
+            .collect();
+        self.results.algebraic_connectivity =
+            self.compute_integration_information_spectral(crate::analysis::SPECTRAL_ITERATIONS);
+        self.results.role_metrics = if self.config.roles.enabled {
+            let roles = self.entities.roles();
+            let mut groups: Vec<(Role, usize, Metrics)> = Metrics::compute_by_role(&self.entities, &roles, self.timestamp)
+                .into_iter()
+                .map(|(role, metrics)| (role, roles.values().filter(|r| **r == role).count(), metrics))
+                .collect();
+            groups.sort_by_key(|(role, _, _)| *role);
+            groups
+        } else {
+            Vec::new()
+        };
+
+        let entities = self.entities.all_entities();
+        self.results.mean_entity_age = if entities.is_empty() {
+            0.0
+        } else {
+            entities.iter().map(|e| e.age(self.timestamp) as Real).sum::<Real>() / entities.len() as Real
+        };
         self.results.analyze_consciousness();
+        // Every step has metrics, so sparse recording does not delay this
+        let first_met = self
+            .metrics_history
+            .iter()
+            .find(|metrics| meets_consciousness_thresholds(metrics))
+            .map(|metrics| metrics.timestamp);
+        let analysis = &mut self.results.consciousness_analysis;
+        analysis.first_achieved_step = match (analysis.first_achieved_step, first_met) {
+            (Some(recorded), Some(met)) => Some(recorded.min(met)),
+            (recorded, met) => recorded.or(met),
+        };
+        for observer in &mut self.observers {
+            observer.on_finish(&self.results);
+        }
+        if let Some(progress) = &mut self.progress {
+            progress.on_finish(self.timestamp);
+        }
     }
 
     /// Generate detailed report files (text and summary).
+    /// 
+    /// Files are named `<prefix>_report_<fingerprint>.{txt,html}` with the
+    /// short configuration fingerprint from `fingerprint_suffix`.
+    /// The consciousness summary is printed to stdout.
     pub fn generate_report(&self, prefix: &str) -> Result<(), Box<dyn std::error::Error>> {
-        let txt_file = format!("{}_report.txt", prefix);
-        let html_file = format!("{}_report.html", prefix);
-        
-        self.results.generate_text_report(&txt_file)?;
-        self.results.generate_html_report(&html_file)?;
-        
-        println!("Report generated: {}", txt_file);
-        println!("Report generated: {}", html_file);
-        
-        // Print consciousness summary to console
-        println!("\n╔════════════════════════════════════════════════════════════════╗");
-        println!("║              CONSCIOUSNESS ANALYSIS SUMMARY                   ║");
-        println!("╚════════════════════════════════════════════════════════════════╝");
-        println!(
-            "Consciousness Score: {:.1}%",
-            self.results.consciousness_analysis.consciousness_score * 100.0
-        );
-        println!(
-            "Status: {}",
-            if self.results.consciousness_analysis.consciousness_achieved {
+        let written = self.export_reports(prefix, &[ExportFormat::Txt, ExportFormat::Html])?;
+        print!("{}", self.consciousness_summary(&written));
+        Ok(())
+    }
+
+    /// Write the selected output formats.
+    /// 
+    /// File names carry the short configuration fingerprint; see the
+    /// [`crate::export`] module for the name of each format.
+    /// 
+    /// # Arguments
+    /// * `prefix` - Prefix of the report files
+    /// * `formats` - Formats to write (may be empty)
+    /// 
+    /// # Returns
+    /// `(format, path)` for every file written, in the order given
+    pub fn export_reports(
+        &self,
+        prefix: &str,
+        formats: &[ExportFormat],
+    ) -> Result<Vec<(ExportFormat, String)>, Box<dyn std::error::Error>> {
+        let suffix = self.fingerprint_suffix();
+        let mut written = Vec::new();
+        for &format in formats {
+            match format {
+                ExportFormat::Csv => {
+                    let path = format!("metrics_{}.csv", suffix);
+                    self.export_metrics_csv(&path)?;
+                    written.push((format, path));
+                }
+                ExportFormat::Txt => {
+                    let path = format!("{}_report_{}.txt", prefix, suffix);
+                    self.results.generate_text_report(&path)?;
+                    written.push((format, path));
+                }
+                ExportFormat::Html => {
+                    let path = format!("{}_report_{}.html", prefix, suffix);
+                    self.results.generate_html_report(&path)?;
+                    written.push((format, path));
+                }
+                ExportFormat::Json => {
+                    let path = format!("{}_results_{}.json", prefix, suffix);
+                    std::fs::write(&path, serde_json::to_string_pretty(&self.results)?)?;
+                    written.push((format, path));
+                }
+                ExportFormat::Md => {
+                    let path = format!("{}_report_{}.md", prefix, suffix);
+                    self.results.generate_markdown_report(&path)?;
+                    written.push((format, path));
+                }
+                #[cfg(feature = "parquet")]
+                ExportFormat::Parquet => {
+                    for path in self.results.export_parquet(&format!("{}_{}", prefix, suffix))? {
+                        written.push((format, path));
+                    }
+                }
+            }
+        }
+
+        Ok(written)
+    }
+
+    /// Console summary of the consciousness analysis.
+    /// 
+    /// # Arguments
+    /// * `written` - Files written by `export_reports`; report files are
+    ///   listed as "Details in"
+    /// 
+    /// # Returns
+    /// Multi-line summary: score, status and passed/failed criteria
+    pub fn consciousness_summary(&self, written: &[(ExportFormat, String)]) -> String {
+        let analysis = &self.results.consciousness_analysis;
+        let mut summary = String::new();
+        summary.push_str("\n╔════════════════════════════════════════════════════════════════╗\n");
+        summary.push_str("║              CONSCIOUSNESS ANALYSIS SUMMARY                   ║\n");
+        summary.push_str("╚════════════════════════════════════════════════════════════════╝\n");
+        summary.push_str(&format!("Consciousness Score: {:.1}%\n", analysis.consciousness_score * 100.0));
+        summary.push_str(&format!(
+            "Status: {}\n\n",
+            if analysis.consciousness_achieved {
                 "✓ CONSCIOUSNESS LIKELY ACHIEVED"
             } else {
                 "✗ CONSCIOUSNESS NOT ACHIEVED"
             }
-        );
-        println!();
-        println!("Passed Criteria: {}/{}", 
-            self.results.consciousness_analysis.passed_metrics.len(),
-            self.results.consciousness_analysis.passed_metrics.len() + 
-            self.results.consciousness_analysis.failed_metrics.len()
-        );
-        for metric in &self.results.consciousness_analysis.passed_metrics {
-            println!("  ✓ {}", metric);
+        ));
+        summary.push_str(&format!(
+            "Passed Criteria: {}/{}\n",
+            analysis.passed_metrics.len(),
+            analysis.passed_metrics.len() + analysis.failed_metrics.len()
+        ));
+        for metric in &analysis.passed_metrics {
+            summary.push_str(&format!("  ✓ {}\n", metric));
         }
-        for failure in &self.results.consciousness_analysis.failed_metrics {
-            println!("  ✗ {}", failure);
+        for failure in &analysis.failed_metrics {
+            summary.push_str(&format!("  ✗ {}\n", failure));
         }
-        println!();
-        println!("Details in: {} or {}", txt_file, html_file);
-        println!();
-
-        Ok(())
+        summary.push('\n');
+        let reports: Vec<&str> = written
+            .iter()
+            .filter(|(format, _)| matches!(format, ExportFormat::Txt | ExportFormat::Html | ExportFormat::Md))
+            .map(|(_, path)| path.as_str())
+            .collect();
+        if !reports.is_empty() {
+            summary.push_str(&format!("Details in: {}\n\n", reports.join(" or ")));
+        }
+        summary
     }
 
     /// Get the consciousness analysis result.
@@ -428,61 +2441,92 @@ impl Simulation {
     }
 
     /// Get the consciousness score (0.0 to 1.0).
-    pub fn consciousness_score(&self) -> f32 {
+    pub fn consciousness_score(&self) -> Real {
         self.results.consciousness_analysis.consciousness_score
     }
     
+    /// Take the manual position overrides queued by the visualization.
+    /// 
+    /// The overrides are cleared from the shared state and applied at the
+    /// start of the next integration step.
+    #[cfg(feature = "gui")]
+    pub fn pull_manual_overrides(&mut self, viz_state: &std::sync::Arc<std::sync::Mutex<crate::visualization::VisualizationState>>) {
+        if let Ok(mut state) = viz_state.lock() {
+            for (id, position) in state.manual_overrides.drain() {
+                self.manual_overrides.insert(EntityId(id as u32), position.into_iter().map(from_f32).collect());
+            }
+        }
+    }
+
     /// Update visualization state with current simulation data
+    #[cfg(feature = "gui")]
     pub fn update_visualization(&self, viz_state: &std::sync::Arc<std::sync::Mutex<crate::visualization::VisualizationState>>) {
         use crate::visualization::EntityState;
         
+        // In id order: attraction pairs index this list, as does the GUI
         let entities = self.entities.all_entities();
         let mut entity_states = Vec::new();
+        let top_leaders: BTreeSet<EntityId> = self
+            .detect_emergent_leaders()
+            .into_iter()
+            .take(crate::analysis::TOP_LEADERS)
+            .map(|(id, _)| id)
+            .collect();
         
         for entity in &entities {
             // Use memory state vector as attention proxy
-            let attention_vals: Vec<f32> = entity.state.memory.iter().take(10).cloned().collect();
+            let attention_vals: Vec<Real> = entity.state.memory.iter().take(10).cloned().collect();
             let num_clusters = entity.memory_graph.clusters.len();
             
             // Compute affective strength from clusters
-            let affective_strength: f32 = if entity.memory_graph.clusters.is_empty() {
-                0.0
-            } else {
-                entity.memory_graph.clusters.values()
-                    .map(|c| c.affective_signal.abs())
-                    .sum::<f32>() / entity.memory_graph.clusters.len() as f32
-            };
+            let affective_strength = entity.affective_strength();
             
             entity_states.push(EntityState {
                 id: entity.id.0,
-                position: entity.pose.position.clone(),
-                velocity: entity.velocity.clone(),
-                essence: entity.essence.value,
-                affective_strength,
-                attention: attention_vals,
+                position: gui_values(&entity.pose.position),
+                velocity: gui_values(&entity.velocity),
+                essence: to_f32(entity.essence.value),
+                affective_strength: to_f32(affective_strength),
+                attention: gui_values(&attention_vals),
                 num_clusters,
+                species: entity.species.0,
+                energy: to_f32(entity.energy),
+                dormant: entity.dormant,
+                labels: entity.labels.clone(),
+                leader: entity.role == Role::Leader,
+                top_leader: top_leaders.contains(&entity.id),
+                heading: gui_values(&entity.forward()),
+                signal_age: self
+                    .last_emission
+                    .get(&entity.id)
+                    .map(|&emitted| self.timestamp.saturating_sub(emitted) as u32),
             });
         }
         
-        // Compute attractions between entities (simplified pairwise)
+        // Compute attractions between entities within the pair cutoff
         let mut attractions = Vec::new();
-        let max_bound = self.config.geometry.bounds.iter().cloned().fold(0.0f32, f32::max).max(1.0);
+        let max_bound = self.config.geometry.bounds.iter().cloned().fold(0.0 as Real, Real::max).max(1.0);
         let scale_factor = max_bound / 10.0; // Normalize to ~10 unit space
         
-        for i in 0..entities.len() {
-            for j in (i+1)..entities.len() {
-                let dist_sq: f32 = entities[i].pose.position.iter()
-                    .zip(entities[j].pose.position.iter())
-                    .map(|(a, b)| (a - b).powi(2))
-                    .sum();
-                
-                if dist_sq > 0.0 {
-                    // Scale strength based on bounds size - larger space = lower threshold
-                    let normalized_dist_sq = dist_sq / (scale_factor * scale_factor);
-                    let strength = 1.0 / (normalized_dist_sq + 1.0);
-                    if strength > 0.001 {  // Lower threshold for larger spaces
-                        attractions.push((i, j, strength));
-                    }
+        let cutoff = self.config.attraction.effective_cutoff();
+        let positions: Vec<&[Real]> = entities.iter().map(|e| e.pose.position.as_slice()).collect();
+        let mut grid = SpatialGrid::new(cutoff);
+        grid.rebuild(positions.iter().copied());
+        for (i, j) in grid.pairs_within(&positions, cutoff) {
+            let dist_sq: Real = positions[i]
+                .iter()
+                .zip(positions[j].iter())
+                .map(|(a, b)| (a - b).powi(2))
+                .sum();
+
+            if dist_sq > 0.0 {
+                // Scale strength based on bounds size - larger space = lower threshold
+                let normalized_dist_sq = dist_sq / (scale_factor * scale_factor);
+                let weight = 0.5
+                    * (entities[i].emitted_attraction_weight() + entities[j].emitted_attraction_weight());
+                let strength = weight / (normalized_dist_sq + 1.0);
+                if strength > 0.001 {  // Lower threshold for larger spaces
+                    attractions.push((i, j, to_f32(strength)));
                 }
             }
         }
@@ -493,23 +2537,70 @@ impl Simulation {
             state.entities = entity_states;
             state.attractions = attractions;
             state.dimension = self.config.geometry.dimension;
-            state.bounds = self.config.geometry.bounds.clone();
+            state.bounds = gui_values(&self.config.geometry.bounds);
+            state.species_names = std::iter::once(species::DEFAULT_SPECIES_NAME.to_string())
+                .chain(self.config.species.keys().cloned())
+                .collect();
+            state.environment = crate::visualization::environment_markers(&self.config.environment);
             
             // Update metrics history
             if let Some(metrics) = self.metrics_history.last() {
                 state.metrics.push(self.timestamp, metrics);
             }
+            state.metrics.push_centroid(
+                &self.entities.centroid_position(),
+                &self.entities.centroid_velocity(),
+            );
             
-            // Debug: print first update info
             if self.timestamp == 0 {
-                println!("[DEBUG] First visualization update:");
-                println!("  Entities: {}", state.entities.len());
-                println!("  Dimension: {}", state.dimension);
-                println!("  Bounds: {:?}", state.bounds);
-                if !state.entities.is_empty() {
-                    println!("  First entity pos: {:?}", state.entities[0].position);
-                }
+                log::debug!(
+                    "First visualization update: {} entities, {}D, bounds {:?}, first position {:?}",
+                    state.entities.len(),
+                    state.dimension,
+                    state.bounds,
+                    state.entities.first().map(|e| &e.position)
+                );
             }
         }
     }
 }
+
+/// Sample a uniformly random pose within the world bounds.
+fn random_pose(config: &SimulationConfig, rng: &mut impl Rng) -> Pose {
+    let dim = config.geometry.dimension;
+    let bounds = &config.geometry.bounds;
+
+    let mut position = vec![0.0; dim];
+    for d in 0..dim {
+        let bound = bounds[d];
+        position[d] = rng.gen_range(0.0..bound);
+    }
+
+    let mut orientation = [1.0, 0.0, 0.0, 0.0];
+    orientation[1] = rng.gen_range(-1.0..1.0);
+
+    Pose {
+        position: position.into(),
+        orientation,
+    }
+}
+
+/// Build a naive entity at the given pose from the configured defaults.
+/// 
+/// Innate drives are sampled from the configured distributions.
+fn new_entity(config: &SimulationConfig, pose: Pose, rng: &mut impl Rng) -> Result<Entity, BuildError> {
+    Entity::builder(pose.position.len())
+        .position(pose.position.to_vec())
+        .orientation(pose.orientation)
+        .drives(
+            config.drives.preservation.sample(rng),
+            config.drives.curiosity.sample(rng),
+        )
+        .build(config)
+}
+
+/// Narrow model values to the `f32` the GUI works in.
+#[cfg(feature = "gui")]
+fn gui_values(values: &[Real]) -> Vec<f32> {
+    values.iter().map(|&v| to_f32(v)).collect()
+}
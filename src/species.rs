@@ -0,0 +1,210 @@
+//! Species module: entity types with per-type behavioral parameters.
+//!
+//! This module defines species, named entity types that override the
+//! population defaults for drives, mass, stimulus amplitude, attraction
+//! weight, essence and state parameters. Mixed populations make it possible
+//! to study how heterogeneous agents (e.g. heavy "anchors" and light
+//! "scouts", or optimists and pessimists) shape collective dynamics.
+//!
+//! ## Configuration
+//!
+//! Species are declared in a `[species]` table keyed by name:
+//!
+//! ```toml
+//! [species.anchor]
+//! count = 5
+//! mass = 4.0
+//! curiosity = 0.1
+//! attraction_weight = 2.0
+//!
+//! [species.scout]
+//! count = 15
+//! mass = 0.5
+//! curiosity = 0.9
+//! attraction_weight = 0.5
+//!
+//! # Sub-populations with their own essence and state parameters
+//! [species.optimist]
+//! count = 10
+//! essence = { baseline = 7.5 }
+//!
+//! [species.pessimist]
+//! count = 10
+//! essence = { baseline = 2.5, experience_scale = 1.5 }
+//! state = { decay_alpha = 0.8 }
+//! ```
+//!
+//! An essence baseline override is also the starting essence of the
+//! species' entities. With `simulation.per_species_metrics` set, metrics are
+//! also computed per species every step and the reports break them down.
+//!
+//! Species ids are assigned in name order starting at 1; id 0 is reserved
+//! for entities without a species.
+//!
+//! ## Author
+//! Ayomide I. Daniels (Morningstar)
+
+use crate::essence::EssenceConfig;
+use crate::real::Real;
+use crate::state::StateConfig;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Identifier of an entity species.
+///
+/// `SpeciesId(0)` denotes the default population (no species overrides).
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Hash, Ord, PartialOrd, Serialize, Deserialize)]
+pub struct SpeciesId(pub u32);
+
+/// Name reported for entities without a species.
+pub const DEFAULT_SPECIES_NAME: &str = "default";
+
+/// Default stimulus amplitude for sensing noise.
+pub const DEFAULT_STIMULUS_AMPLITUDE: Real = 0.1;
+
+/// Per-species overrides of entity parameters.
+///
+/// Every override is optional; unset values fall back to the
+/// population defaults.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct SpeciesConfig {
+    /// Number of entities of this species.
+    pub count: u32,
+    /// Self-preservation drive override.
+    #[serde(default)]
+    pub preservation: Option<Real>,
+    /// Curiosity drive override.
+    #[serde(default)]
+    pub curiosity: Option<Real>,
+    /// Inertial mass (acceleration is divided by mass).
+    #[serde(default)]
+    pub mass: Option<Real>,
+    /// Amplitude of sensed stimulus noise.
+    #[serde(default)]
+    pub stimulus_amplitude: Option<Real>,
+    /// Multiplier on each member's contribution to the attraction field the
+    /// others feel; recorded pair attractions use the mean of both weights.
+    #[serde(default)]
+    pub attraction_weight: Option<Real>,
+    /// Essence parameter overrides.
+    #[serde(default)]
+    pub essence: EssenceOverrides,
+    /// State update parameter overrides.
+    #[serde(default)]
+    pub state: StateOverrides,
+}
+
+impl SpeciesConfig {
+    /// Check that overrides are within meaningful ranges.
+    pub fn is_valid(&self) -> bool {
+        self.mass.is_none_or(|m| m > 0.0)
+            && self.stimulus_amplitude.is_none_or(|a| a >= 0.0)
+            && self.attraction_weight.is_none_or(|w| w >= 0.0)
+            && self.essence.is_valid()
+            && self.state.is_valid()
+    }
+}
+
+/// Per-species overrides of [`EssenceConfig`].
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct EssenceOverrides {
+    /// Baseline (and starting) essence, within [0, 10].
+    #[serde(default)]
+    pub baseline: Option<Real>,
+    /// Decay toward the baseline per step, within [0, 1].
+    #[serde(default)]
+    pub decay: Option<Real>,
+    /// Scaling of experiences (non-negative).
+    #[serde(default)]
+    pub experience_scale: Option<Real>,
+}
+
+impl EssenceOverrides {
+    /// Check that overrides are within meaningful ranges.
+    pub fn is_valid(&self) -> bool {
+        self.baseline.is_none_or(|b| (0.0..=10.0).contains(&b))
+            && self.decay.is_none_or(|d| (0.0..=1.0).contains(&d))
+            && self.experience_scale.is_none_or(|s| s >= 0.0)
+    }
+
+    /// Replace the overridden fields of `config`.
+    pub fn apply_to(&self, config: &mut EssenceConfig) {
+        if let Some(baseline) = self.baseline {
+            config.baseline = baseline;
+        }
+        if let Some(decay) = self.decay {
+            config.decay = decay;
+        }
+        if let Some(scale) = self.experience_scale {
+            config.experience_scale = scale;
+        }
+    }
+}
+
+/// Per-species overrides of the [`StateConfig`] update rates.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct StateOverrides {
+    /// Retention of the previous state, within [0, 1].
+    #[serde(default)]
+    pub decay_alpha: Option<Real>,
+    /// Weight of the attention input (non-negative).
+    #[serde(default)]
+    pub beta_attention: Option<Real>,
+    /// Weight of the memory input (non-negative).
+    #[serde(default)]
+    pub gamma_memory: Option<Real>,
+}
+
+impl StateOverrides {
+    /// Check that overrides are within meaningful ranges.
+    pub fn is_valid(&self) -> bool {
+        self.decay_alpha.is_none_or(|a| (0.0..=1.0).contains(&a))
+            && self.beta_attention.is_none_or(|b| b >= 0.0)
+            && self.gamma_memory.is_none_or(|g| g >= 0.0)
+    }
+
+    /// Replace the overridden fields of `config`.
+    pub fn apply_to(&self, config: &mut StateConfig) {
+        if let Some(alpha) = self.decay_alpha {
+            config.decay_alpha = alpha;
+        }
+        if let Some(beta) = self.beta_attention {
+            config.beta_attention = beta;
+        }
+        if let Some(gamma) = self.gamma_memory {
+            config.gamma_memory = gamma;
+        }
+    }
+}
+
+/// Resolve species names to ids in deterministic (name) order.
+///
+/// # Arguments
+/// * `species` - Species table from the configuration
+///
+/// # Returns
+/// (id, name, config) triples with ids starting at 1
+pub fn enumerate_species(
+    species: &BTreeMap<String, SpeciesConfig>,
+) -> Vec<(SpeciesId, &str, &SpeciesConfig)> {
+    species
+        .iter()
+        .enumerate()
+        .map(|(i, (name, cfg))| (SpeciesId(i as u32 + 1), name.as_str(), cfg))
+        .collect()
+}
+
+/// Look up the name of a species id.
+///
+/// # Returns
+/// Species name, or `DEFAULT_SPECIES_NAME` for id 0 and unknown ids
+pub fn species_name(species: &BTreeMap<String, SpeciesConfig>, id: SpeciesId) -> &str {
+    if id.0 == 0 {
+        return DEFAULT_SPECIES_NAME;
+    }
+    species
+        .keys()
+        .nth(id.0 as usize - 1)
+        .map(|name| name.as_str())
+        .unwrap_or(DEFAULT_SPECIES_NAME)
+}
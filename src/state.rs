@@ -22,8 +22,13 @@
 //! ## Author
 //! Ayomide I. Daniels (Morningstar)
 
+use crate::real::Real;
+use ndarray::{ArrayView1, ArrayViewMut1, Zip};
 use serde::{Deserialize, Serialize};
 
+/// Length of the persistent trait vector.
+pub const TRAIT_DIM: usize = 10;
+
 /// Configuration for state dimensionality and decay.
 /// 
 /// Controls the size and update dynamics of entity state vectors.
@@ -31,9 +36,14 @@ use serde::{Deserialize, Serialize};
 pub struct StateConfig {
     pub memory_dim: usize,
     pub context_dim: usize,
-    pub decay_alpha: f32,
-    pub beta_attention: f32,
-    pub gamma_memory: f32,
+    pub decay_alpha: Real,
+    pub beta_attention: Real,
+    pub gamma_memory: Real,
+    /// Similarity to a matched belief cluster above which a sensed event
+    /// reinforces an existing memory node instead of adding one (`None`
+    /// stores every event).
+    #[serde(default)]
+    pub dedup_threshold: Option<Real>,
 }
 
 impl StateConfig {
@@ -44,6 +54,7 @@ impl StateConfig {
             decay_alpha: 0.995,
             beta_attention: 0.5,
             gamma_memory: 0.3,
+            dedup_threshold: None,
         }
     }
 }
@@ -57,11 +68,11 @@ impl StateConfig {
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct EntityStateVector {
     /// Long-term memory representation.
-    pub memory: Vec<f32>,
+    pub memory: Vec<Real>,
     /// Current context vector.
-    pub context: Vec<f32>,
+    pub context: Vec<Real>,
     /// Persistent traits.
-    pub traits: Vec<f32>,
+    pub traits: Vec<Real>,
     /// Configuration.
     pub config: StateConfig,
 }
@@ -80,7 +91,7 @@ impl EntityStateVector {
         EntityStateVector {
             memory: vec![0.0; config.memory_dim],
             context: vec![0.0; config.context_dim],
-            traits: vec![0.0; 10], // Fixed trait vector
+            traits: vec![0.0; TRAIT_DIM], // Fixed trait vector
             config,
         }
     }
@@ -102,48 +113,57 @@ impl EntityStateVector {
     /// * `memory_input` - Memory signals from belief clusters
     pub fn update(
         &mut self,
-        attention_force: &[f32],
-        memory_input: &[f32],
+        attention_force: &[Real],
+        memory_input: &[Real],
     ) {
         // Update memory: s_i(t+dt) = alpha * s_i(t) + beta * g(F_i) + gamma * m_i
         let alpha = self.config.decay_alpha;
         let beta = self.config.beta_attention;
         let gamma = self.config.gamma_memory;
 
-        for i in 0..self.memory.len() {
-            let att_contrib = if i < attention_force.len() {
-                beta * attention_force[i]
-            } else {
-                0.0
-            };
-            let mem_contrib = if i < memory_input.len() {
-                gamma * memory_input[i]
-            } else {
-                0.0
-            };
-
-            self.memory[i] = alpha * self.memory[i] + att_contrib + mem_contrib;
+        // The memory input spans the memory; the attention force only its
+        // first (spatial) components
+        let step = |i: usize, m: &mut Real, input: Real| {
+            let att_contrib = attention_force.get(i).map_or(0.0, |a| beta * a);
+            *m = alpha * *m + att_contrib + gamma * input;
+        };
+        let n = self.memory.len();
+        let inputs = n.min(memory_input.len());
+        Zip::indexed(ArrayViewMut1::from(&mut self.memory[..inputs]))
+            .and(ArrayView1::from(&memory_input[..inputs]))
+            .for_each(|i, m, &input| step(i, m, input));
+        for (i, m) in self.memory.iter_mut().enumerate().skip(inputs) {
+            step(i, m, 0.0);
         }
 
         // Optionally update context (simplified: use portion of memory)
-        for i in 0..self.context.len() {
-            if i < self.memory.len() {
-                self.context[i] = 0.5 * self.context[i] + 0.5 * self.memory[i];
-            }
-        }
+        let shared = self.context.len().min(n);
+        Zip::from(ArrayViewMut1::from(&mut self.context[..shared]))
+            .and(ArrayView1::from(&self.memory[..shared]))
+            .for_each(|c, &m| *c = 0.5 * *c + 0.5 * m);
+    }
+
+    /// Memory vector as an ndarray view.
+    pub fn memory_view(&self) -> ArrayView1<'_, Real> {
+        ArrayView1::from(&self.memory[..])
+    }
+
+    /// Context vector as an ndarray view.
+    pub fn context_view(&self) -> ArrayView1<'_, Real> {
+        ArrayView1::from(&self.context[..])
     }
 
     /// Compute state vector norm for identity coherence.
-    pub fn norm(&self) -> f32 {
+    pub fn norm(&self) -> Real {
         self.memory
             .iter()
             .map(|x| x * x)
-            .sum::<f32>()
+            .sum::<Real>()
             .sqrt()
     }
 
     /// Compute dot product with another state for coherence.
-    pub fn dot(&self, other: &EntityStateVector) -> f32 {
+    pub fn dot(&self, other: &EntityStateVector) -> Real {
         self.memory
             .iter()
             .zip(other.memory.iter())
@@ -0,0 +1,568 @@
+//! Sweep module: run a grid of configurations derived from one base config.
+//!
+//! A [`SweepParam`] names a configuration field by its TOML path and lists
+//! the values to try (`attraction.sigma=0.5,1.0,2.0`). [`expand_grid`]
+//! takes the cartesian product of all parameters, applies each combination
+//! to the base configuration with [`set_config_value`] and validates the
+//! result, so a bad path or value is reported before anything runs.
+//!
+//! [`SweepRunner`] runs the cells on a thread pool of `jobs` workers. Each
+//! finished cell writes `cell_NNN.json` (a [`CellSummary`]) to the output
+//! directory; when the sweep ends the [`SweepReport`] writes one row per
+//! cell (swept values, score and the final value of every metric) to
+//! `sweep.csv`. Setting the runner's stop flag (the binary does so
+//! on Ctrl-C) lets running cells finish and skips the rest, and the CSV is
+//! still written with the skipped cells marked as such.
+//!
+//! ## Parameter Paths
+//!
+//! Paths are the dotted TOML keys of [`SimulationConfig`]
+//! (`dynamics.min_speed`, `simulation.num_entities`, ...); a numeric segment
+//! indexes an array (`geometry.bounds.0`). Values are coerced to the type
+//! the field already has: integers must parse as integers, numbers accept
+//! integer literals, booleans take `true` or `false`, and enums take their
+//! variant name. Optional fields that are unset take the type the value
+//! looks like.
+//!
+//! ## Author
+//! Ayomide I. Daniels (Morningstar)
+
+use crate::config::SimulationConfig;
+use crate::metrics::Metrics;
+use crate::real::{to_f64, Real};
+use crate::results::ConsciousnessAnalysis;
+use crate::simulation::Simulation;
+use rayon::prelude::*;
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::Arc;
+
+/// File name of the per-cell summary table inside the output directory.
+pub const SUMMARY_CSV: &str = "sweep.csv";
+
+/// One swept parameter: a configuration path and the values to try.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SweepParam {
+    /// Dotted TOML path of the field (e.g. `attraction.sigma`)
+    pub path: String,
+    /// Values in sweep order, as written on the command line
+    pub values: Vec<String>,
+}
+
+impl FromStr for SweepParam {
+    type Err = String;
+
+    /// Parse `path=value1,value2,...`.
+    fn from_str(spec: &str) -> Result<Self, String> {
+        let (path, values) = spec
+            .split_once('=')
+            .ok_or_else(|| format!("expected path=value1,value2,..., got '{}'", spec))?;
+        let path = path.trim();
+        if path.is_empty() || path.split('.').any(|key| key.trim().is_empty()) {
+            return Err(format!("'{}' is not a valid parameter path", path));
+        }
+        let values: Vec<String> = values.split(',').map(|value| value.trim().to_string()).collect();
+        if values.iter().any(String::is_empty) {
+            return Err(format!("empty value in '{}'", spec));
+        }
+        Ok(SweepParam {
+            path: path.to_string(),
+            values,
+        })
+    }
+}
+
+/// Set one configuration field by its dotted TOML path.
+///
+/// The value is coerced to the type the field already has (see the module
+/// documentation); `config` is left unchanged on error.
+///
+/// # Arguments
+/// * `config` - Configuration to update
+/// * `path` - Dotted path such as `attraction.sigma` or `geometry.bounds.0`
+/// * `value` - Value as written on the command line
+///
+/// # Returns
+/// A message naming the path for unknown fields or values of the wrong type
+pub fn set_config_value(config: &mut SimulationConfig, path: &str, value: &str) -> Result<(), String> {
+    let keys: Vec<&str> = path.split('.').map(str::trim).collect();
+    let (leaf, parents) = match keys.split_last() {
+        Some((leaf, parents)) if keys.iter().all(|key| !key.is_empty()) => (*leaf, parents),
+        _ => return Err(format!("'{}' is not a valid parameter path", path)),
+    };
+
+    let mut root = toml::Value::try_from(&*config).map_err(|e| e.to_string())?;
+    let mut node = &mut root;
+    for (depth, key) in parents.iter().enumerate() {
+        let name = keys[..=depth].join(".");
+        node = match node {
+            toml::Value::Table(table) => table.get_mut(*key),
+            toml::Value::Array(items) => key.parse::<usize>().ok().and_then(|i| items.get_mut(i)),
+            _ => return Err(format!("'{}' is not a section", name)),
+        }
+        .ok_or_else(|| format!("unknown parameter '{}'", name))?;
+    }
+
+    let existed = match node {
+        toml::Value::Table(table) => {
+            let coerced = coerce_value(path, value, table.get(leaf))?;
+            table.insert(leaf.to_string(), coerced).is_some()
+        }
+        toml::Value::Array(items) => {
+            let index = leaf
+                .parse::<usize>()
+                .ok()
+                .filter(|i| *i < items.len())
+                .ok_or_else(|| format!("unknown parameter '{}'", path))?;
+            items[index] = coerce_value(path, value, Some(&items[index]))?;
+            true
+        }
+        _ => return Err(format!("'{}' is not a section", parents.join("."))),
+    };
+
+    let updated: SimulationConfig = root
+        .try_into()
+        .map_err(|e: toml::de::Error| format!("{}={}: {}", path, value, e.message()))?;
+    // Unknown keys are ignored when deserializing; make sure the field took
+    if !existed {
+        let check = toml::Value::try_from(&updated).map_err(|e| e.to_string())?;
+        if lookup(&check, &keys).is_none() {
+            return Err(format!("unknown parameter '{}'", path));
+        }
+    }
+    *config = updated;
+    Ok(())
+}
+
+/// Convert a command-line value to the TOML type of `existing`.
+fn coerce_value(path: &str, value: &str, existing: Option<&toml::Value>) -> Result<toml::Value, String> {
+    let expects = |expected: &str| format!("{} expects {}, got '{}'", path, expected, value);
+    match existing {
+        Some(toml::Value::Integer(_)) => value.parse().map(toml::Value::Integer).map_err(|_| expects("an integer")),
+        Some(toml::Value::Float(_)) => value.parse().map(toml::Value::Float).map_err(|_| expects("a number")),
+        Some(toml::Value::Boolean(_)) => value.parse().map(toml::Value::Boolean).map_err(|_| expects("true or false")),
+        Some(toml::Value::String(_)) => Ok(toml::Value::String(value.to_string())),
+        Some(_) => Err(format!("'{}' is not a single value; sweep one of its fields instead", path)),
+        None => Ok(if let Ok(integer) = value.parse() {
+            toml::Value::Integer(integer)
+        } else if let Ok(number) = value.parse() {
+            toml::Value::Float(number)
+        } else if let Ok(flag) = value.parse() {
+            toml::Value::Boolean(flag)
+        } else {
+            toml::Value::String(value.to_string())
+        }),
+    }
+}
+
+/// Value at a dotted path, if present.
+fn lookup<'a>(root: &'a toml::Value, keys: &[&str]) -> Option<&'a toml::Value> {
+    keys.iter().try_fold(root, |node, key| match node {
+        toml::Value::Table(table) => table.get(*key),
+        toml::Value::Array(items) => items.get(key.parse::<usize>().ok()?),
+        _ => None,
+    })
+}
+
+/// One combination of parameter values and the configuration it yields.
+#[derive(Clone, Debug)]
+pub struct SweepCell {
+    /// Position in the grid (0-based; the last parameter varies fastest)
+    pub index: usize,
+    /// `(path, value)` for every swept parameter, in parameter order
+    pub assignments: Vec<(String, String)>,
+    /// Base configuration with the assignments applied
+    pub config: SimulationConfig,
+}
+
+impl SweepCell {
+    /// Assignments as `path=value, ...`.
+    pub fn label(&self) -> String {
+        label(&self.assignments)
+    }
+}
+
+/// Expand the cartesian product of `params` over `base`.
+///
+/// # Arguments
+/// * `base` - Configuration every cell starts from
+/// * `params` - Swept parameters; the first varies slowest
+///
+/// # Returns
+/// One validated cell per combination, or the first invalid path or value
+pub fn expand_grid(base: &SimulationConfig, params: &[SweepParam]) -> Result<Vec<SweepCell>, String> {
+    let mut combinations: Vec<Vec<(String, String)>> = vec![Vec::new()];
+    for param in params {
+        combinations = combinations
+            .into_iter()
+            .flat_map(|prefix| {
+                param.values.iter().map(move |value| {
+                    let mut assignments = prefix.clone();
+                    assignments.push((param.path.clone(), value.clone()));
+                    assignments
+                })
+            })
+            .collect();
+    }
+
+    combinations
+        .into_iter()
+        .enumerate()
+        .map(|(index, assignments)| {
+            let mut config = base.clone();
+            for (path, value) in &assignments {
+                set_config_value(&mut config, path, value)?;
+            }
+            let cell = SweepCell {
+                index,
+                assignments,
+                config,
+            };
+            cell.config
+                .validate()
+                .map_err(|e| format!("cell {} ({}): {}", cell.index, cell.label(), e))?;
+            Ok(cell)
+        })
+        .collect()
+}
+
+/// Contents of a cell's `cell_NNN.json`.
+#[derive(Clone, Debug, Serialize)]
+pub struct CellSummary {
+    /// Position in the grid
+    pub cell: usize,
+    /// Swept values of this cell
+    pub parameters: BTreeMap<String, String>,
+    /// Steps executed
+    pub steps: u64,
+    /// Entities alive at the end
+    pub entities: usize,
+    /// SHA-256 fingerprint of the cell's configuration
+    pub config_fingerprint: String,
+    /// Consciousness verdict
+    pub analysis: ConsciousnessAnalysis,
+    /// Plain-English summary of the run
+    pub narrative: String,
+    /// Effective configuration of the cell
+    pub config: SimulationConfig,
+}
+
+/// What happened to a cell.
+#[derive(Clone, Debug, PartialEq)]
+pub enum CellStatus {
+    /// Ran to completion; its summary was written to `summary_path`
+    Completed {
+        /// Overall consciousness score
+        score: Real,
+        /// Whether the consciousness criteria were met
+        consciousness_achieved: bool,
+        /// Steps executed
+        steps: u64,
+        /// Final value of each of [`Metrics::FIELD_NAMES`]
+        final_metrics: [Real; 11],
+        /// The cell's summary JSON
+        summary_path: PathBuf,
+    },
+    /// Could not be created or its summary could not be written
+    Failed(String),
+    /// Not started because the sweep was stopped
+    Skipped,
+}
+
+impl CellStatus {
+    /// Short name used in the summary table.
+    pub fn name(&self) -> &'static str {
+        match self {
+            CellStatus::Completed { .. } => "done",
+            CellStatus::Failed(_) => "failed",
+            CellStatus::Skipped => "skipped",
+        }
+    }
+}
+
+/// A cell and what happened to it.
+#[derive(Clone, Debug)]
+pub struct CellOutcome {
+    /// Position in the grid
+    pub index: usize,
+    /// `(path, value)` for every swept parameter
+    pub assignments: Vec<(String, String)>,
+    /// Result of the cell
+    pub status: CellStatus,
+}
+
+impl CellOutcome {
+    /// Assignments as `path=value, ...`.
+    pub fn label(&self) -> String {
+        label(&self.assignments)
+    }
+
+    /// Consciousness score of a completed cell.
+    pub fn score(&self) -> Option<Real> {
+        match self.status {
+            CellStatus::Completed { score, .. } => Some(score),
+            _ => None,
+        }
+    }
+}
+
+/// Runs sweep cells in parallel and writes their summaries.
+pub struct SweepRunner {
+    out_dir: PathBuf,
+    jobs: usize,
+    stop: Arc<AtomicBool>,
+}
+
+impl SweepRunner {
+    /// Runner writing to `out_dir` with at most `jobs` cells at a time.
+    ///
+    /// # Arguments
+    /// * `out_dir` - Output directory (created if missing)
+    /// * `jobs` - Parallel cells; 0 means one per available CPU
+    pub fn new(out_dir: impl Into<PathBuf>, jobs: usize) -> Self {
+        let jobs = match jobs {
+            0 => std::thread::available_parallelism().map(|n| n.get()).unwrap_or(1),
+            jobs => jobs,
+        };
+        SweepRunner {
+            out_dir: out_dir.into(),
+            jobs,
+            stop: Arc::new(AtomicBool::new(false)),
+        }
+    }
+
+    /// Number of cells run at a time.
+    pub fn jobs(&self) -> usize {
+        self.jobs
+    }
+
+    /// Flag that, once set, skips every cell not yet started.
+    pub fn stop_flag(&self) -> Arc<AtomicBool> {
+        Arc::clone(&self.stop)
+    }
+
+    /// Run `cells`, write their summaries and the summary CSV.
+    ///
+    /// # Arguments
+    /// * `cells` - Cells from [`expand_grid`]
+    /// * `on_cell` - Called as each cell finishes with the outcome, the
+    ///   number of cells finished so far and the total
+    ///
+    /// # Returns
+    /// The report, or an error if the output directory or CSV cannot be written
+    pub fn run<F>(&self, cells: Vec<SweepCell>, on_cell: F) -> Result<SweepReport, String>
+    where
+        F: Fn(&CellOutcome, usize, usize) + Sync,
+    {
+        fs::create_dir_all(&self.out_dir)
+            .map_err(|e| format!("cannot create output directory '{}': {}", self.out_dir.display(), e))?;
+        let parameters: Vec<String> = cells
+            .first()
+            .map(|cell| cell.assignments.iter().map(|(path, _)| path.clone()).collect())
+            .unwrap_or_default();
+        let total = cells.len();
+        let finished = AtomicUsize::new(0);
+
+        let pool = rayon::ThreadPoolBuilder::new()
+            .num_threads(self.jobs)
+            .build()
+            .map_err(|e| e.to_string())?;
+        let outcomes: Vec<CellOutcome> = pool.install(|| {
+            cells
+                .into_par_iter()
+                .with_max_len(1)
+                .map(|cell| {
+                    if self.stop.load(Ordering::SeqCst) {
+                        return CellOutcome {
+                            index: cell.index,
+                            assignments: cell.assignments,
+                            status: CellStatus::Skipped,
+                        };
+                    }
+                    let status = self.run_cell(&cell);
+                    let outcome = CellOutcome {
+                        index: cell.index,
+                        assignments: cell.assignments,
+                        status,
+                    };
+                    on_cell(&outcome, finished.fetch_add(1, Ordering::SeqCst) + 1, total);
+                    outcome
+                })
+                .collect()
+        });
+
+        let report = SweepReport {
+            parameters,
+            interrupted: outcomes.iter().any(|outcome| outcome.status == CellStatus::Skipped),
+            outcomes,
+            csv_path: self.out_dir.join(SUMMARY_CSV),
+        };
+        report
+            .write_csv(&report.csv_path)
+            .map_err(|e| format!("cannot write '{}': {}", report.csv_path.display(), e))?;
+        Ok(report)
+    }
+
+    /// Run one cell to completion and write its summary JSON.
+    fn run_cell(&self, cell: &SweepCell) -> CellStatus {
+        let mut sim = match Simulation::new(cell.config.clone()) {
+            Ok(sim) => sim,
+            Err(e) => return CellStatus::Failed(e),
+        };
+        sim.run();
+        sim.finalize_results();
+
+        let analysis = sim.results.consciousness_analysis.clone();
+        let summary = CellSummary {
+            cell: cell.index,
+            parameters: cell.assignments.iter().cloned().collect(),
+            steps: sim.timestamp,
+            entities: sim.entities.all_entities().len(),
+            config_fingerprint: sim.results.config_fingerprint.clone(),
+            analysis: analysis.clone(),
+            narrative: sim.results.narrative_summary(),
+            config: cell.config.clone(),
+        };
+        let summary_path = self.out_dir.join(format!("cell_{:03}.json", cell.index));
+        let written = serde_json::to_string_pretty(&summary)
+            .map_err(|e| e.to_string())
+            .and_then(|json| fs::write(&summary_path, json).map_err(|e| e.to_string()));
+        match written {
+            Ok(()) => CellStatus::Completed {
+                score: analysis.consciousness_score,
+                consciousness_achieved: analysis.consciousness_achieved,
+                steps: summary.steps,
+                final_metrics: sim.metrics_history.last().map(Metrics::field_values).unwrap_or_default(),
+                summary_path,
+            },
+            Err(e) => CellStatus::Failed(format!("cannot write '{}': {}", summary_path.display(), e)),
+        }
+    }
+}
+
+/// Outcome of every cell of a sweep.
+#[derive(Clone, Debug)]
+pub struct SweepReport {
+    /// Swept parameter paths, in order
+    pub parameters: Vec<String>,
+    /// One outcome per cell, in grid order
+    pub outcomes: Vec<CellOutcome>,
+    /// Whether the sweep was stopped before every cell ran
+    pub interrupted: bool,
+    /// Where the summary CSV was written
+    pub csv_path: PathBuf,
+}
+
+impl SweepReport {
+    /// Number of cells with the given status name (`done`, `failed`, `skipped`).
+    pub fn count(&self, status: &str) -> usize {
+        self.outcomes.iter().filter(|outcome| outcome.status.name() == status).count()
+    }
+
+    /// Completed cells with the highest consciousness scores, best first.
+    pub fn best(&self, n: usize) -> Vec<&CellOutcome> {
+        let mut completed: Vec<&CellOutcome> = self.outcomes.iter().filter(|o| o.score().is_some()).collect();
+        completed.sort_by(|a, b| b.score().partial_cmp(&a.score()).unwrap_or(std::cmp::Ordering::Equal));
+        completed.truncate(n);
+        completed
+    }
+
+    /// Aligned text table of the [`best`](Self::best) `n` cells.
+    pub fn best_table(&self, n: usize) -> String {
+        let mut rows = vec![["rank", "cell", "score", "conscious"]
+            .iter()
+            .map(|s| s.to_string())
+            .chain(self.parameters.iter().cloned())
+            .collect::<Vec<String>>()];
+        for (rank, outcome) in self.best(n).into_iter().enumerate() {
+            let achieved = matches!(outcome.status, CellStatus::Completed { consciousness_achieved: true, .. });
+            let mut row = vec![
+                (rank + 1).to_string(),
+                outcome.index.to_string(),
+                format!("{:.4}", to_f64(outcome.score().unwrap_or_default())),
+                if achieved { "yes" } else { "no" }.to_string(),
+            ];
+            row.extend(outcome.assignments.iter().map(|(_, value)| value.clone()));
+            rows.push(row);
+        }
+
+        let widths: Vec<usize> = (0..rows[0].len())
+            .map(|column| rows.iter().map(|row| row[column].chars().count()).max().unwrap_or(0))
+            .collect();
+        rows.iter()
+            .map(|row| {
+                let cells: Vec<String> = row
+                    .iter()
+                    .zip(&widths)
+                    .map(|(cell, width)| format!("{:<width$}", cell, width = width))
+                    .collect();
+                cells.join("  ").trim_end().to_string()
+            })
+            .collect::<Vec<String>>()
+            .join("\n")
+    }
+
+    /// Write one row per cell: the swept values, status, score and final
+    /// metrics.
+    ///
+    /// # Arguments
+    /// * `path` - Destination CSV file
+    pub fn write_csv(&self, path: &Path) -> std::io::Result<()> {
+        let mut header = vec!["cell".to_string()];
+        header.extend(self.parameters.iter().cloned());
+        header.extend(["status", "steps", "consciousness_score", "consciousness_achieved"].iter().map(|s| s.to_string()));
+        header.extend(Metrics::FIELD_NAMES.iter().map(|name| format!("final_{}", name)));
+        header.extend(["summary", "error"].iter().map(|s| s.to_string()));
+        let blank = |row: &mut Vec<String>, count: usize| row.extend(std::iter::repeat_n(String::new(), count));
+        let mut lines = vec![header.iter().map(|field| csv_field(field)).collect::<Vec<_>>().join(",")];
+
+        for outcome in &self.outcomes {
+            let mut row = vec![outcome.index.to_string()];
+            row.extend(outcome.assignments.iter().map(|(_, value)| value.clone()));
+            row.push(outcome.status.name().to_string());
+            match &outcome.status {
+                CellStatus::Completed {
+                    score,
+                    consciousness_achieved,
+                    steps,
+                    final_metrics,
+                    summary_path,
+                } => {
+                    row.extend([steps.to_string(), format!("{:.6}", to_f64(*score)), consciousness_achieved.to_string()]);
+                    row.extend(final_metrics.iter().map(|value| format!("{:.6}", to_f64(*value))));
+                    row.extend([
+                        summary_path.file_name().map(|name| name.to_string_lossy().into_owned()).unwrap_or_default(),
+                        String::new(),
+                    ]);
+                }
+                CellStatus::Failed(e) => {
+                    blank(&mut row, 3 + Metrics::FIELD_NAMES.len() + 1);
+                    row.push(e.clone());
+                }
+                CellStatus::Skipped => blank(&mut row, 3 + Metrics::FIELD_NAMES.len() + 2),
+            }
+            lines.push(row.iter().map(|field| csv_field(field)).collect::<Vec<_>>().join(","));
+        }
+        lines.push(String::new());
+        fs::write(path, lines.join("\n"))
+    }
+}
+
+/// Join assignments as `path=value, ...`.
+fn label(assignments: &[(String, String)]) -> String {
+    let pairs: Vec<String> = assignments.iter().map(|(path, value)| format!("{}={}", path, value)).collect();
+    pairs.join(", ")
+}
+
+/// Quote a CSV field if it contains a separator, quote or newline.
+fn csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\n']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
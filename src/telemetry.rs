@@ -0,0 +1,233 @@
+//! Telemetry module: Prometheus metrics for long-running simulations.
+//!
+//! Enabled by the `prometheus` feature. A [`MetricsObserver`] owns a
+//! Prometheus registry and refreshes it from a [`Simulation`] after each
+//! step; a [`MetricsEndpoint`] serves the registry in the text exposition
+//! format at `GET /metrics` for a Prometheus server to scrape.
+//!
+//! ## Metric Families
+//!
+//! Every name is prefixed `synthetic_consciousness_`.
+//!
+//! | Name | Type | Value |
+//! |------|------|-------|
+//! | `<metric>` | gauge | Each field of `Metrics` at the latest step (e.g. `attention_entropy`) |
+//! | `step` | gauge | Steps completed |
+//! | `steps_per_second` | gauge | Step rate since the previous observation |
+//! | `entities` | gauge | Live entities |
+//! | `memory_nodes` | gauge | Memory nodes summed over entities |
+//! | `belief_clusters` | gauge | Belief clusters summed over entities |
+//! | `spawned_entities_total` | counter | Entities added after the initial population |
+//! | `config_patches_total` | counter | Runtime configuration patches applied |
+//!
+//! Memory graphs only decay activations and never drop nodes, so there is
+//! no pruning counter.
+//!
+//! ## Author
+//! Ayomide I. Daniels (Morningstar)
+
+use crate::metrics::Metrics;
+use crate::real::to_f64;
+use crate::simulation::Simulation;
+use prometheus::{Encoder, Gauge, IntCounter, IntGauge, Opts, Registry, TextEncoder};
+use std::io;
+use std::net::SocketAddr;
+use std::sync::{Arc, Mutex};
+use std::time::Instant;
+use tiny_http::{Header, Method, Response, Server};
+
+/// Prefix of every exported metric name.
+pub const METRIC_PREFIX: &str = "synthetic_consciousness";
+
+/// Prometheus registry mirroring the state of a simulation.
+pub struct MetricsObserver {
+    registry: Registry,
+    metric_gauges: Vec<Gauge>,
+    step: IntGauge,
+    steps_per_second: Gauge,
+    entities: IntGauge,
+    memory_nodes: IntGauge,
+    belief_clusters: IntGauge,
+    spawned_entities: IntCounter,
+    config_patches: IntCounter,
+    /// Time and step of the previous observation
+    last: Mutex<Option<(Instant, u64)>>,
+}
+
+impl MetricsObserver {
+    /// Create an observer with every metric family registered.
+    ///
+    /// # Returns
+    /// The observer, or an error if metric registration fails
+    pub fn new() -> Result<Self, prometheus::Error> {
+        let registry = Registry::new_custom(Some(METRIC_PREFIX.to_string()), None)?;
+        let metric_gauges = Metrics::FIELD_NAMES
+            .iter()
+            .map(|name| gauge(&registry, name, &format!("Consciousness metric {} at the latest step", name)))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(MetricsObserver {
+            metric_gauges,
+            step: int_gauge(&registry, "step", "Steps completed")?,
+            steps_per_second: gauge(&registry, "steps_per_second", "Step rate since the previous observation")?,
+            entities: int_gauge(&registry, "entities", "Live entities")?,
+            memory_nodes: int_gauge(&registry, "memory_nodes", "Memory nodes summed over entities")?,
+            belief_clusters: int_gauge(&registry, "belief_clusters", "Belief clusters summed over entities")?,
+            spawned_entities: int_counter(
+                &registry,
+                "spawned_entities_total",
+                "Entities added after the initial population",
+            )?,
+            config_patches: int_counter(&registry, "config_patches_total", "Runtime configuration patches applied")?,
+            last: Mutex::new(None),
+            registry,
+        })
+    }
+
+    /// The underlying registry (to add application metrics or gather directly).
+    pub fn registry(&self) -> &Registry {
+        &self.registry
+    }
+
+    /// Refresh every metric from the current simulation state.
+    ///
+    /// Call after each step (or at any cadence; rates and counters account
+    /// for skipped steps).
+    ///
+    /// # Arguments
+    /// * `sim` - Simulation to read
+    pub fn observe(&self, sim: &Simulation) {
+        if let Some(metrics) = sim.metrics_history.last() {
+            for (gauge, value) in self.metric_gauges.iter().zip(metrics.field_values()) {
+                gauge.set(to_f64(value));
+            }
+        }
+
+        let now = Instant::now();
+        if let Ok(mut last) = self.last.lock() {
+            if let Some((time, step)) = *last {
+                let elapsed = now.duration_since(time).as_secs_f64();
+                if elapsed > 0.0 && sim.timestamp > step {
+                    self.steps_per_second.set((sim.timestamp - step) as f64 / elapsed);
+                }
+            }
+            *last = Some((now, sim.timestamp));
+        }
+
+        let entities = sim.entities.all_entities();
+        self.step.set(sim.timestamp as i64);
+        self.entities.set(entities.len() as i64);
+        self.memory_nodes.set(entities.iter().map(|e| e.memory_graph.nodes.len() as i64).sum());
+        self.belief_clusters.set(entities.iter().map(|e| e.memory_graph.clusters.len() as i64).sum());
+
+        let spawned = sim
+            .results
+            .lineage
+            .len()
+            .saturating_sub(sim.config.simulation.num_entities as usize) as u64;
+        advance_counter(&self.spawned_entities, spawned);
+        advance_counter(&self.config_patches, sim.config_patches().len() as u64);
+    }
+
+    /// Render the registry in the Prometheus text exposition format.
+    pub fn render(&self) -> String {
+        let mut buffer = Vec::new();
+        // Encoding into a Vec only fails for malformed metric families
+        let _ = TextEncoder::new().encode(&self.registry.gather(), &mut buffer);
+        String::from_utf8(buffer).unwrap_or_default()
+    }
+}
+
+impl Simulation {
+    /// Run the remaining configured steps, refreshing `observer` after each one.
+    ///
+    /// Stops early like `run()` when the stop flag is set.
+    ///
+    /// # Arguments
+    /// * `observer` - Metrics to keep up to date
+    pub fn run_observed(&mut self, observer: &MetricsObserver) {
+        observer.observe(self);
+        for _ in 0..self.remaining_steps() {
+            if self.stop_requested() {
+                break;
+            }
+            self.step();
+            observer.observe(self);
+        }
+    }
+}
+
+/// HTTP endpoint serving an observer's metrics at `GET /metrics`.
+pub struct MetricsEndpoint {
+    server: Server,
+    observer: Arc<MetricsObserver>,
+}
+
+impl MetricsEndpoint {
+    /// Bind to `addr`.
+    ///
+    /// # Arguments
+    /// * `addr` - Listen address, e.g. `"0.0.0.0:9100"` (port 0 picks a free port)
+    /// * `observer` - Observer whose registry is served
+    ///
+    /// # Returns
+    /// The bound endpoint (call [`MetricsEndpoint::serve`] to handle scrapes)
+    pub fn bind(addr: &str, observer: Arc<MetricsObserver>) -> io::Result<Self> {
+        let server = Server::http(addr).map_err(io::Error::other)?;
+        Ok(MetricsEndpoint { server, observer })
+    }
+
+    /// Address the endpoint is listening on.
+    pub fn local_addr(&self) -> Option<SocketAddr> {
+        self.server.server_addr().to_ip()
+    }
+
+    /// Handle scrapes until [`MetricsEndpoint::shutdown`] is called.
+    pub fn serve(&self) {
+        for request in self.server.incoming_requests() {
+            let path = request.url().split('?').next().unwrap_or("");
+            let response = if *request.method() == Method::Get && path == "/metrics" {
+                let content_type = Header::from_bytes("Content-Type", TextEncoder::new().format_type())
+                    .expect("static header is valid");
+                Response::from_string(self.observer.render()).with_header(content_type)
+            } else {
+                Response::from_string("not found").with_status_code(404)
+            };
+            let _ = request.respond(response);
+        }
+    }
+
+    /// Stop [`MetricsEndpoint::serve`].
+    pub fn shutdown(&self) {
+        self.server.unblock();
+    }
+}
+
+/// Register a float gauge.
+fn gauge(registry: &Registry, name: &str, help: &str) -> Result<Gauge, prometheus::Error> {
+    let gauge = Gauge::with_opts(Opts::new(name, help))?;
+    registry.register(Box::new(gauge.clone()))?;
+    Ok(gauge)
+}
+
+/// Register an integer gauge.
+fn int_gauge(registry: &Registry, name: &str, help: &str) -> Result<IntGauge, prometheus::Error> {
+    let gauge = IntGauge::with_opts(Opts::new(name, help))?;
+    registry.register(Box::new(gauge.clone()))?;
+    Ok(gauge)
+}
+
+/// Register an integer counter.
+fn int_counter(registry: &Registry, name: &str, help: &str) -> Result<IntCounter, prometheus::Error> {
+    let counter = IntCounter::with_opts(Opts::new(name, help))?;
+    registry.register(Box::new(counter.clone()))?;
+    Ok(counter)
+}
+
+/// Raise a counter to `total` (counters never decrease).
+fn advance_counter(counter: &IntCounter, total: u64) {
+    let current = counter.get();
+    if total > current {
+        counter.inc_by(total - current);
+    }
+}
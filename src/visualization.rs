@@ -8,8 +8,11 @@
 //! - 2D/3D geometric space rendering with entity positions
 //! - Pairwise attraction force visualization
 //! - Entity state indicators (essence, attention, clusters, velocity)
+//! - Fixed environment points: attractors, repellers and obstacles
 //! - Real-time metric plots with dynamic status descriptions
 //! - Interactive controls for toggling visualization layers
+//! - Pause, single-step and speed control of a live run ([`SimulationControl`])
+//! - Playback of saved results ([`Playback`]) without re-running the simulation
 //!
 //! ## Author
 //! Ayomide I. Daniels (Morningstar)
@@ -17,12 +20,38 @@
 use eframe::egui;
 use egui::{Color32, Pos2, Stroke, Vec2};
 use egui_plot::{Line, Plot, PlotPoints};
+use crate::environment::EnvironmentConfig;
+use crate::geometry::Coords;
+use crate::real::{to_f32, to_f64, Real};
+use crate::results::{consciousness_threshold, SimulationResults, SimulationStep};
+use crate::roles::Role;
+use crate::species::DEFAULT_SPECIES_NAME;
+use std::sync::atomic::{AtomicBool, AtomicU32, Ordering};
 use std::sync::{Arc, Mutex};
-use std::collections::VecDeque;
+use std::collections::{HashMap, VecDeque};
+use std::time::{Duration, Instant};
 
 /// Maximum number of historical data points to retain for metric plots
 const MAX_HISTORY: usize = 500;
 
+/// Number of steps a signal-emission ring stays visible
+const SIGNAL_RING_STEPS: u32 = 10;
+
+/// Number of center-of-mass samples drawn as a trail
+const COM_TRAIL_LENGTH: usize = 60;
+
+/// Fill color of entities holding the leader role
+const LEADER_COLOR: Color32 = Color32::from_rgb(255, 215, 0);
+
+/// Outline of environment points that attract entities
+const ATTRACTOR_COLOR: Color32 = Color32::from_rgb(80, 220, 160);
+
+/// Outline of environment points that repel entities
+const REPELLER_COLOR: Color32 = Color32::from_rgb(230, 90, 170);
+
+/// Fill of obstacles
+const OBSTACLE_COLOR: Color32 = Color32::from_rgb(90, 90, 110);
+
 /// Shared state between simulation and GUI.
 /// 
 /// This structure is wrapped in `Arc<Mutex<>>` to allow safe concurrent access
@@ -35,6 +64,131 @@ pub struct VisualizationState {
     pub metrics: MetricsHistory,
     pub dimension: usize,
     pub bounds: Vec<f32>, // Spatial bounds from geometry config
+    /// Species names indexed by species id (index 0 = default species)
+    pub species_names: Vec<String>,
+    /// Positions set by dragging, keyed by entity id; the simulation
+    /// applies and clears them at its next integration step
+    pub manual_overrides: HashMap<usize, Vec<f32>>,
+    /// Whether saved results are being played back (dragging is disabled)
+    pub replay: bool,
+    /// Fixed environment points
+    pub environment: Vec<EnvironmentMarker>,
+}
+
+/// Fixed environment point as drawn (see `environment::EnvironmentPoint`).
+#[derive(Clone, Debug, Default)]
+pub struct EnvironmentMarker {
+    pub position: Vec<f32>,
+    pub radius: f32,
+    /// Signed pull: positive attracts, negative repels
+    pub strength: f32,
+    pub obstacle: bool,
+}
+
+/// Markers for every point of an environment.
+pub fn environment_markers(environment: &EnvironmentConfig) -> Vec<EnvironmentMarker> {
+    environment
+        .points
+        .iter()
+        .map(|point| EnvironmentMarker {
+            position: point.position.iter().map(|&p| to_f32(p)).collect(),
+            radius: to_f32(point.radius),
+            strength: to_f32(point.strength),
+            obstacle: point.obstacle,
+        })
+        .collect()
+}
+
+/// Run control shared between the simulation thread and the GUI.
+/// 
+/// The GUI sets the flags; the simulation thread polls them between steps
+/// with [`SimulationControl::wait_for_step`] and
+/// [`SimulationControl::pace`]. Everything is atomic, so neither side ever
+/// blocks the other.
+#[derive(Debug, Default)]
+pub struct SimulationControl {
+    /// Whether the simulation waits between steps
+    pub paused: AtomicBool,
+    /// Maximum steps per second (0 = as fast as possible)
+    pub steps_per_second: AtomicU32,
+    /// Set to let a paused simulation run exactly one more step
+    pub single_step: AtomicBool,
+}
+
+impl SimulationControl {
+    /// Interval at which a paused simulation checks for changes.
+    const POLL_INTERVAL: Duration = Duration::from_millis(10);
+
+    /// Control of a running simulation at full speed.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Whether the simulation is paused.
+    pub fn is_paused(&self) -> bool {
+        self.paused.load(Ordering::Relaxed)
+    }
+
+    /// Pause or resume the simulation.
+    pub fn set_paused(&self, paused: bool) {
+        self.paused.store(paused, Ordering::Relaxed);
+    }
+
+    /// Let a paused simulation advance one step.
+    pub fn request_single_step(&self) {
+        self.single_step.store(true, Ordering::Relaxed);
+    }
+
+    /// Speed limit in steps per second (0 = unlimited).
+    pub fn steps_per_second(&self) -> u32 {
+        self.steps_per_second.load(Ordering::Relaxed)
+    }
+
+    /// Limit the speed to `steps_per_second` (0 = unlimited).
+    pub fn set_steps_per_second(&self, steps_per_second: u32) {
+        self.steps_per_second.store(steps_per_second, Ordering::Relaxed);
+    }
+
+    /// Wait until the simulation may run its next step.
+    /// 
+    /// Returns immediately when running; while paused, sleeps until the
+    /// simulation is resumed or a single step is requested (which is
+    /// consumed).
+    /// 
+    /// # Arguments
+    /// * `stop_requested` - Polled while paused to abandon the wait
+    /// 
+    /// # Returns
+    /// `false` if `stop_requested` returned true while paused
+    pub fn wait_for_step(&self, stop_requested: impl Fn() -> bool) -> bool {
+        while self.is_paused() {
+            if self.single_step.swap(false, Ordering::Relaxed) {
+                return true;
+            }
+            if stop_requested() {
+                return false;
+            }
+            std::thread::sleep(Self::POLL_INTERVAL);
+        }
+        // A step requested just before resuming is not run twice
+        self.single_step.store(false, Ordering::Relaxed);
+        true
+    }
+
+    /// Sleep out the rest of the step interval of the speed limit.
+    /// 
+    /// # Arguments
+    /// * `last_step` - When the previous step started; set to now on return
+    pub fn pace(&self, last_step: &mut Instant) {
+        let limit = self.steps_per_second();
+        if limit > 0 {
+            let interval = Duration::from_secs_f64(1.0 / limit as f64);
+            if let Some(remaining) = interval.checked_sub(last_step.elapsed()) {
+                std::thread::sleep(remaining);
+            }
+        }
+        *last_step = Instant::now();
+    }
 }
 
 /// Snapshot of a single entity's state for visualization.
@@ -57,6 +211,22 @@ pub struct EntityState {
     pub attention: Vec<f32>,
     /// Number of belief clusters in memory
     pub num_clusters: usize,
+    /// Species id (0 = default species)
+    pub species: u32,
+    /// Metabolic energy reserve
+    pub energy: f32,
+    /// Whether the entity is dormant (exhausted for too long)
+    pub dormant: bool,
+    /// Steps since the entity last emitted a signal, if ever
+    pub signal_age: Option<u32>,
+    /// User-assigned analysis labels
+    pub labels: Vec<String>,
+    /// Whether the entity currently holds the leader role
+    pub leader: bool,
+    /// Whether the entity is among the top PageRank leaders (`TOP_LEADERS`)
+    pub top_leader: bool,
+    /// Unit vector the entity is facing
+    pub heading: Vec<f32>,
 }
 
 /// Time-series history of consciousness metrics.
@@ -81,6 +251,10 @@ pub struct MetricsHistory {
     pub affective_strength: VecDeque<f64>,
     /// Average essence metric history
     pub average_essence: VecDeque<f64>,
+    /// Population center of mass history
+    pub center_of_mass: VecDeque<Vec<f64>>,
+    /// Population mean velocity history
+    pub mean_velocity: VecDeque<Vec<f64>>,
 }
 
 impl MetricsHistory {
@@ -105,13 +279,29 @@ impl MetricsHistory {
         }
         
         self.steps.push_back(step as f64);
-        self.attention_entropy.push_back(metrics.attention_entropy as f64);
-        self.memory_diversity.push_back(metrics.memory_diversity as f64);
-        self.velocity_stability.push_back(metrics.velocity_stability as f64);
-        self.identity_coherence.push_back(metrics.identity_coherence as f64);
-        self.cluster_stability.push_back(metrics.cluster_stability as f64);
-        self.affective_strength.push_back(metrics.affective_strength as f64);
-        self.average_essence.push_back(metrics.average_essence as f64);
+        self.attention_entropy.push_back(to_f64(metrics.attention_entropy));
+        self.memory_diversity.push_back(to_f64(metrics.memory_diversity));
+        self.velocity_stability.push_back(to_f64(metrics.velocity_stability));
+        self.identity_coherence.push_back(to_f64(metrics.identity_coherence));
+        self.cluster_stability.push_back(to_f64(metrics.cluster_stability));
+        self.affective_strength.push_back(to_f64(metrics.affective_strength));
+        self.average_essence.push_back(to_f64(metrics.average_essence));
+    }
+
+    /// Add a center-of-mass and mean-velocity sample.
+    /// 
+    /// Kept in a separate rolling window of `MAX_HISTORY` samples.
+    /// 
+    /// # Arguments
+    /// * `center_of_mass` - Population centroid position
+    /// * `mean_velocity` - Population mean velocity
+    pub fn push_centroid(&mut self, center_of_mass: &[Real], mean_velocity: &[Real]) {
+        if self.center_of_mass.len() >= MAX_HISTORY {
+            self.center_of_mass.pop_front();
+            self.mean_velocity.pop_front();
+        }
+        self.center_of_mass.push_back(center_of_mass.iter().map(|&c| to_f64(c)).collect());
+        self.mean_velocity.push_back(mean_velocity.iter().map(|&v| to_f64(v)).collect());
     }
 }
 
@@ -134,8 +324,14 @@ pub struct VisualizationApp {
     show_entity_labels: bool,
     /// Toggle: show grid lines for spatial reference
     show_grid: bool,
+    /// Toggle: color entities by species instead of essence
+    color_by_species: bool,
     /// Zoom level for geometric space (0.1 to 5.0)
     zoom: f32,
+    /// Id of the entity selected by clicking, if any
+    selected_entity: Option<usize>,
+    /// Run control of a live simulation (none during replay)
+    control: Option<Arc<SimulationControl>>,
 }
 
 impl VisualizationApp {
@@ -155,9 +351,18 @@ impl VisualizationApp {
             show_velocity: true,
             show_entity_labels: true,
             show_grid: true,
+            color_by_species: false,
             zoom: 1.0,
+            selected_entity: None,
+            control: None,
         }
     }
+
+    /// Show pause, step and speed controls driving `control`.
+    pub fn with_control(mut self, control: Arc<SimulationControl>) -> Self {
+        self.control = Some(control);
+        self
+    }
 }
 
 impl eframe::App for VisualizationApp {
@@ -171,6 +376,10 @@ impl eframe::App for VisualizationApp {
             ui.horizontal(|ui| {
                 ui.heading("🧠 Synthetic Consciousness Visualization");
                 ui.separator();
+                if state.replay {
+                    ui.label(egui::RichText::new("▶ Replay").color(Color32::LIGHT_BLUE));
+                    ui.separator();
+                }
                 ui.label(format!("Step: {}", state.step));
                 ui.separator();
                 ui.label(format!("Entities: {}", state.entities.len()));
@@ -179,6 +388,28 @@ impl eframe::App for VisualizationApp {
                     let avg_essence: f32 = state.entities.iter().map(|e| e.essence).sum::<f32>() / state.entities.len() as f32;
                     ui.label(format!("Avg Essence: {:.1}", avg_essence));
                 }
+                if state.species_names.len() > 1 {
+                    ui.separator();
+                    ui.checkbox(&mut self.color_by_species, "Color by species");
+                }
+                if let Some(control) = &self.control {
+                    ui.separator();
+                    let paused = control.is_paused();
+                    if ui.button(if paused { "▶ Resume" } else { "⏸ Pause" }).clicked() {
+                        control.set_paused(!paused);
+                    }
+                    if ui.add_enabled(paused, egui::Button::new("⏭ Step")).clicked() {
+                        control.request_single_step();
+                    }
+                    let mut speed = control.steps_per_second();
+                    let slider = egui::Slider::new(&mut speed, 0..=1000)
+                        .logarithmic(true)
+                        .text("steps/s")
+                        .custom_formatter(|v, _| if v == 0.0 { "max".to_string() } else { format!("{:.0}", v) });
+                    if ui.add(slider).changed() {
+                        control.set_steps_per_second(speed);
+                    }
+                }
             });
         });
         
@@ -187,10 +418,37 @@ impl eframe::App for VisualizationApp {
                 // Legend in compact horizontal format
                 ui.label(egui::RichText::new("Legend:").strong());
                 ui.separator();
-                ui.colored_label(Color32::from_rgb(100, 255, 100), "●");
-                ui.label("High");
-                ui.colored_label(Color32::from_rgb(255, 100, 100), "●");
-                ui.label("Low");
+                if self.color_by_species {
+                    for (id, name) in state.species_names.iter().enumerate() {
+                        ui.colored_label(species_color(id as u32), "●");
+                        ui.label(name);
+                    }
+                } else {
+                    ui.colored_label(Color32::from_rgb(100, 255, 100), "●");
+                    ui.label("High");
+                    ui.colored_label(Color32::from_rgb(255, 100, 100), "●");
+                    ui.label("Low");
+                }
+                if state.entities.iter().any(|e| e.leader) {
+                    ui.colored_label(LEADER_COLOR, "●");
+                    ui.label("Leader");
+                }
+                if state.entities.iter().any(|e| e.top_leader) {
+                    ui.colored_label(LEADER_COLOR, "★");
+                    ui.label("Top influence");
+                }
+                if state.environment.iter().any(|p| !p.obstacle && p.strength > 0.0) {
+                    ui.colored_label(ATTRACTOR_COLOR, "⊕");
+                    ui.label("Attractor");
+                }
+                if state.environment.iter().any(|p| !p.obstacle && p.strength < 0.0) {
+                    ui.colored_label(REPELLER_COLOR, "⊖");
+                    ui.label("Repeller");
+                }
+                if state.environment.iter().any(|p| p.obstacle) {
+                    ui.colored_label(OBSTACLE_COLOR, "⬤");
+                    ui.label("Obstacle");
+                }
                 ui.separator();
                 ui.colored_label(Color32::from_rgb(100, 200, 255), "━");
                 ui.label("Attraction");
@@ -231,10 +489,10 @@ impl eframe::App for VisualizationApp {
                                 let points: PlotPoints = state.metrics.steps.iter().zip(state.metrics.attention_entropy.iter())
                                     .map(|(x, y)| [*x, *y]).collect();
                                 plot_ui.line(Line::new(points).color(Color32::from_rgb(100, 200, 255)));
-                                plot_ui.hline(egui_plot::HLine::new(2.0).color(Color32::GREEN));
+                                plot_ui.hline(egui_plot::HLine::new(criterion_threshold("attention_entropy")).color(Color32::GREEN));
                             });
                         if let Some(&value) = state.metrics.attention_entropy.back() {
-                            let (status, color) = if value >= 2.0 {
+                            let (status, color) = if value >= criterion_threshold("attention_entropy") {
                                 ("✓ High awareness diversity", Color32::GREEN)
                             } else if value >= 1.0 {
                                 ("⚠ Moderate spread", Color32::YELLOW)
@@ -259,10 +517,10 @@ impl eframe::App for VisualizationApp {
                                 let points: PlotPoints = state.metrics.steps.iter().zip(state.metrics.memory_diversity.iter())
                                     .map(|(x, y)| [*x, *y]).collect();
                                 plot_ui.line(Line::new(points).color(Color32::from_rgb(255, 200, 100)));
-                                plot_ui.hline(egui_plot::HLine::new(0.1).color(Color32::GREEN));
+                                plot_ui.hline(egui_plot::HLine::new(criterion_threshold("memory_diversity")).color(Color32::GREEN));
                             });
                         if let Some(&value) = state.metrics.memory_diversity.back() {
-                            let (status, color) = if value >= 0.1 {
+                            let (status, color) = if value >= criterion_threshold("memory_diversity") {
                                 ("✓ Rich variance", Color32::GREEN)
                             } else if value >= 0.05 {
                                 ("⚠ Limited range", Color32::YELLOW)
@@ -287,10 +545,10 @@ impl eframe::App for VisualizationApp {
                                 let points: PlotPoints = state.metrics.steps.iter().zip(state.metrics.velocity_stability.iter())
                                     .map(|(x, y)| [*x, *y]).collect();
                                 plot_ui.line(Line::new(points).color(Color32::from_rgb(100, 255, 100)));
-                                plot_ui.hline(egui_plot::HLine::new(0.8).color(Color32::GREEN));
+                                plot_ui.hline(egui_plot::HLine::new(criterion_threshold("velocity_stability")).color(Color32::GREEN));
                             });
                         if let Some(&value) = state.metrics.velocity_stability.back() {
-                            let (status, color) = if value >= 0.8 {
+                            let (status, color) = if value >= criterion_threshold("velocity_stability") {
                                 ("✓ Purposeful motion", Color32::GREEN)
                             } else if value >= 0.5 {
                                 ("⚠ Irregular", Color32::YELLOW)
@@ -315,10 +573,10 @@ impl eframe::App for VisualizationApp {
                                 let points: PlotPoints = state.metrics.steps.iter().zip(state.metrics.identity_coherence.iter())
                                     .map(|(x, y)| [*x, *y]).collect();
                                 plot_ui.line(Line::new(points).color(Color32::from_rgb(255, 100, 255)));
-                                plot_ui.hline(egui_plot::HLine::new(0.7).color(Color32::GREEN));
+                                plot_ui.hline(egui_plot::HLine::new(criterion_threshold("identity_coherence")).color(Color32::GREEN));
                             });
                         if let Some(&value) = state.metrics.identity_coherence.back() {
-                            let (status, color) = if value >= 0.7 {
+                            let (status, color) = if value >= criterion_threshold("identity_coherence") {
                                 ("✓ Strong self-continuity", Color32::GREEN)
                             } else if value >= 0.4 {
                                 ("⚠ Fragmented", Color32::YELLOW)
@@ -343,10 +601,10 @@ impl eframe::App for VisualizationApp {
                                 let points: PlotPoints = state.metrics.steps.iter().zip(state.metrics.cluster_stability.iter())
                                     .map(|(x, y)| [*x, *y]).collect();
                                 plot_ui.line(Line::new(points).color(Color32::from_rgb(200, 100, 255)));
-                                plot_ui.hline(egui_plot::HLine::new(0.5).color(Color32::GREEN));
+                                plot_ui.hline(egui_plot::HLine::new(criterion_threshold("cluster_stability")).color(Color32::GREEN));
                             });
                         if let Some(&value) = state.metrics.cluster_stability.back() {
-                            let (status, color) = if value >= 0.5 {
+                            let (status, color) = if value >= criterion_threshold("cluster_stability") {
                                 ("✓ Well-organized", Color32::GREEN)
                             } else if value >= 0.3 {
                                 ("⚠ Loosely structured", Color32::YELLOW)
@@ -371,10 +629,10 @@ impl eframe::App for VisualizationApp {
                                 let points: PlotPoints = state.metrics.steps.iter().zip(state.metrics.affective_strength.iter())
                                     .map(|(x, y)| [*x, *y]).collect();
                                 plot_ui.line(Line::new(points).color(Color32::from_rgb(255, 50, 50)));
-                                plot_ui.hline(egui_plot::HLine::new(0.01).color(Color32::GREEN));
+                                plot_ui.hline(egui_plot::HLine::new(criterion_threshold("affective_strength")).color(Color32::GREEN));
                             });
                         if let Some(&value) = state.metrics.affective_strength.back() {
-                            let (status, color) = if value >= 0.01 {
+                            let (status, color) = if value >= criterion_threshold("affective_strength") {
                                 ("✓ Emotional capacity", Color32::GREEN)
                             } else if value >= 0.001 {
                                 ("⚠ Weak signals", Color32::YELLOW)
@@ -423,7 +681,7 @@ impl eframe::App for VisualizationApp {
             // Geometric space visualization (full remaining space)
             ui.heading("Geometric Space");
             let size = ui.available_size();
-                    let (response, painter) = ui.allocate_painter(size, egui::Sense::hover());
+                    let (response, painter) = ui.allocate_painter(size, egui::Sense::click_and_drag());
                     let rect = response.rect;
                     let center = rect.center();
                     
@@ -445,6 +703,42 @@ impl eframe::App for VisualizationApp {
                         let offset_x = state.bounds.get(0).copied().unwrap_or(0.0) / 2.0;
                         let offset_y = state.bounds.get(1).copied().unwrap_or(0.0) / 2.0;
                         
+                        // Click selects the nearest entity under the pointer; dragging
+                        // moves it and queues the new position for the simulation
+                        let to_screen = |p: &[f32]| Pos2::new(
+                            center.x + (p[0] - offset_x) * effective_scale,
+                            center.y + (p[1] - offset_y) * effective_scale,
+                        );
+                        if response.clicked() || response.drag_started() {
+                            if let Some(pointer) = response.interact_pointer_pos() {
+                                let pick_radius = 12.0 * self.zoom + 5.0;
+                                self.selected_entity = state
+                                    .entities
+                                    .iter()
+                                    .filter(|e| e.position.len() >= 2)
+                                    .map(|e| (e.id as usize, to_screen(&e.position).distance(pointer)))
+                                    .filter(|(_, d)| *d <= pick_radius)
+                                    .min_by(|a, b| a.1.total_cmp(&b.1))
+                                    .map(|(id, _)| id);
+                            }
+                        }
+                        if response.dragged() && !state.replay {
+                            if let (Some(id), Some(pointer)) = (self.selected_entity, response.interact_pointer_pos()) {
+                                if let Some(entity) = state.entities.iter().find(|e| e.id as usize == id) {
+                                    let mut position = entity.position.clone();
+                                    let bound_y = state.bounds.get(1).copied().unwrap_or(state.bounds[0]);
+                                    position[0] = ((pointer.x - center.x) / effective_scale + offset_x).clamp(0.0, state.bounds[0]);
+                                    position[1] = ((pointer.y - center.y) / effective_scale + offset_y).clamp(0.0, bound_y);
+                                    if let Ok(mut shared) = self.state.lock() {
+                                        if let Some(shown) = shared.entities.iter_mut().find(|e| e.id as usize == id) {
+                                            shown.position = position.clone();
+                                        }
+                                        shared.manual_overrides.insert(id, position);
+                                    }
+                                }
+                            }
+                        }
+                        
                         // Draw grid lines to show the plane of existence
                         if self.show_grid {
                             let grid_spacing = auto_scale * 5.0; // Grid every 5 units in world space
@@ -481,6 +775,26 @@ impl eframe::App for VisualizationApp {
                             );
                         }
                         
+                        // Draw environment points beneath the entities: obstacles
+                        // filled, attractors and repellers as marked rings
+                        for point in state.environment.iter().filter(|p| p.position.len() >= 2) {
+                            let pos = to_screen(&point.position);
+                            let radius = (point.radius * effective_scale).max(4.0);
+                            if point.obstacle {
+                                painter.circle_filled(pos, radius, OBSTACLE_COLOR);
+                                painter.circle_stroke(pos, radius, Stroke::new(1.5, Color32::from_rgb(160, 160, 180)));
+                                continue;
+                            }
+                            let color = if point.strength >= 0.0 { ATTRACTOR_COLOR } else { REPELLER_COLOR };
+                            painter.circle_filled(pos, radius, color.gamma_multiply(0.15));
+                            painter.circle_stroke(pos, radius, Stroke::new(2.0, color));
+                            let arm = 5.0;
+                            painter.line_segment([pos - Vec2::new(arm, 0.0), pos + Vec2::new(arm, 0.0)], Stroke::new(2.0, color));
+                            if point.strength >= 0.0 {
+                                painter.line_segment([pos - Vec2::new(0.0, arm), pos + Vec2::new(0.0, arm)], Stroke::new(2.0, color));
+                            }
+                        }
+
                         // Draw attractions
                         if self.show_attractions {
                             for (idx_a, idx_b, strength) in &state.attractions {
@@ -519,9 +833,23 @@ impl eframe::App for VisualizationApp {
                             }
                         }
                         
+                        // Draw center-of-mass trail (older samples fade out)
+                        let trail: Vec<&Vec<f64>> = state.metrics.center_of_mass.iter().rev().take(COM_TRAIL_LENGTH).collect();
+                        for (age, com) in trail.iter().enumerate() {
+                            if com.len() >= 2 {
+                                let pos = Pos2::new(
+                                    center.x + (com[0] as f32 - offset_x) * effective_scale,
+                                    center.y + (com[1] as f32 - offset_y) * effective_scale,
+                                );
+                                let fade = 1.0 - age as f32 / COM_TRAIL_LENGTH as f32;
+                                let radius = if age == 0 { 5.0 } else { 2.0 };
+                                painter.circle_filled(pos, radius, Color32::from_rgba_unmultiplied(255, 200, 60, (220.0 * fade) as u8));
+                            }
+                        }
+                        
                         // Draw entities
                         if state.step == 0 || state.step % 100 == 0 {
-                            println!("[VIZ] Step {}: Drawing {} entities, bounds={:?}, effective_scale={:.2}", 
+                            log::trace!("Step {}: drawing {} entities, bounds={:?}, effective_scale={:.2}",
                                 state.step, state.entities.len(), state.bounds.iter().take(2).collect::<Vec<_>>(), effective_scale);
                         }
                         
@@ -532,9 +860,13 @@ impl eframe::App for VisualizationApp {
                                     center.y + (entity.position[1] - offset_y) * effective_scale,
                                 );
                                 
-                                // Color by essence (0-10 scale)
+                                // Leaders in gold; otherwise color by species, or by essence (0-10 scale)
                                 let essence_norm = (entity.essence / 10.0).clamp(0.0, 1.0);
-                                let color = if essence_norm > 0.5 {
+                                let color = if entity.leader {
+                                    LEADER_COLOR
+                                } else if self.color_by_species {
+                                    species_color(entity.species)
+                                } else if essence_norm > 0.5 {
                                     Color32::from_rgb(
                                         (255.0 * (1.0 - essence_norm)) as u8,
                                         (255.0 * essence_norm) as u8,
@@ -564,6 +896,39 @@ impl eframe::App for VisualizationApp {
                                 // Draw inner ring for more visual interest
                                 painter.circle_stroke(pos, radius * 0.6, Stroke::new(1.0, Color32::from_rgba_unmultiplied(255, 255, 255, 80)));
                                 
+                                // Gold star above the top PageRank leaders
+                                if entity.top_leader {
+                                    painter.text(
+                                        pos + Vec2::new(radius * 0.8, -radius * 0.8),
+                                        egui::Align2::CENTER_CENTER,
+                                        "★",
+                                        egui::FontId::proportional(radius.max(12.0)),
+                                        LEADER_COLOR,
+                                    );
+                                }
+                                
+                                // Highlight the selected entity
+                                if self.selected_entity == Some(entity.id as usize) {
+                                    painter.circle_stroke(pos, radius + 6.0, Stroke::new(2.0, Color32::from_rgb(0, 255, 255)));
+                                }
+                                
+                                // Heading tick from the rim outward
+                                if entity.heading.len() >= 2 {
+                                    let heading = Vec2::new(entity.heading[0], entity.heading[1]);
+                                    painter.line_segment(
+                                        [pos + heading * radius, pos + heading * radius * 1.6],
+                                        Stroke::new(2.5, Color32::WHITE),
+                                    );
+                                }
+                                
+                                // Expanding, fading ring shortly after a signal emission
+                                if let Some(age) = entity.signal_age.filter(|&a| a < SIGNAL_RING_STEPS) {
+                                    let progress = age as f32 / SIGNAL_RING_STEPS as f32;
+                                    let ring_radius = radius * (1.3 + 2.0 * progress);
+                                    let alpha = (200.0 * (1.0 - progress)) as u8;
+                                    painter.circle_stroke(pos, ring_radius, Stroke::new(2.0, Color32::from_rgba_unmultiplied(120, 200, 255, alpha)));
+                                }
+                                
                                 // Show entity ID label - larger and more visible
                                 if self.show_entity_labels {
                                     // Emotional status on top (based on essence)
@@ -592,7 +957,11 @@ impl eframe::App for VisualizationApp {
                                     painter.text(
                                         pos + Vec2::new(0.0, -radius - 15.0),
                                         egui::Align2::CENTER_BOTTOM,
-                                        format!("Agent {}", entity.id),
+                                        if entity.labels.is_empty() {
+                                            format!("Agent {}", entity.id)
+                                        } else {
+                                            format!("Agent {} [{}]", entity.id, entity.labels.join(", "))
+                                        },
                                         egui::FontId::proportional(13.0),
                                         Color32::from_rgb(220, 220, 255),
                                     );
@@ -601,7 +970,12 @@ impl eframe::App for VisualizationApp {
                                     painter.text(
                                         pos + Vec2::new(0.0, radius + 12.0),
                                         egui::Align2::CENTER_TOP,
-                                        format!("E:{:.1}", entity.essence),
+                                        format!(
+                                            "E:{:.1} ⚡{:.0}{}",
+                                            entity.essence,
+                                            entity.energy,
+                                            if entity.dormant { " (dormant)" } else { "" }
+                                        ),
                                         egui::FontId::proportional(10.0),
                                         Color32::from_rgb(200, 200, 200),
                                     );
@@ -685,6 +1059,31 @@ impl eframe::App for VisualizationApp {
     }
 }
 
+/// Pass threshold of a consciousness criterion, for plot lines and status labels.
+fn criterion_threshold(metric: &str) -> f64 {
+    consciousness_threshold(metric).map_or(0.0, to_f64)
+}
+
+/// Distinct display color for a species id.
+/// 
+/// The default species (id 0) is drawn in neutral grey; other species
+/// cycle through a fixed qualitative palette.
+fn species_color(species: u32) -> Color32 {
+    const PALETTE: [Color32; 6] = [
+        Color32::from_rgb(80, 160, 255),
+        Color32::from_rgb(255, 140, 60),
+        Color32::from_rgb(120, 220, 120),
+        Color32::from_rgb(220, 100, 220),
+        Color32::from_rgb(240, 220, 80),
+        Color32::from_rgb(90, 220, 220),
+    ];
+    if species == 0 {
+        Color32::from_rgb(170, 170, 170)
+    } else {
+        PALETTE[(species as usize - 1) % PALETTE.len()]
+    }
+}
+
 /// Launch the visualization window.
 /// 
 /// Creates and runs the eframe application with the visualization GUI.
@@ -692,13 +1091,18 @@ impl eframe::App for VisualizationApp {
 /// 
 /// # Arguments
 /// * `state` - Shared visualization state to display
+/// * `control` - Run control of a live simulation, shown as pause, step
+///   and speed controls (`None` for replays)
 /// 
 /// # Returns
 /// `Ok(())` if the window closes successfully, or an error if initialization fails
 /// 
 /// # Platform Notes
 /// On macOS, this MUST be called from the main thread due to EventLoop restrictions.
-pub fn launch_visualization(state: Arc<Mutex<VisualizationState>>) -> Result<(), eframe::Error> {
+pub fn launch_visualization(
+    state: Arc<Mutex<VisualizationState>>,
+    control: Option<Arc<SimulationControl>>,
+) -> Result<(), eframe::Error> {
     let options = eframe::NativeOptions {
         viewport: egui::ViewportBuilder::default()
             .with_inner_size([1400.0, 900.0])
@@ -709,6 +1113,194 @@ pub fn launch_visualization(state: Arc<Mutex<VisualizationState>>) -> Result<(),
     eframe::run_native(
         "synthetic-consciousness-viz",
         options,
-        Box::new(|_cc| Ok(Box::new(VisualizationApp::new(state)))),
+        Box::new(|_cc| {
+            let app = VisualizationApp::new(state);
+            Ok(Box::new(match control {
+                Some(control) => app.with_control(control),
+                None => app,
+            }))
+        }),
     )
 }
+
+/// Playback of saved results in the visualization window.
+/// 
+/// Feeds the recorded steps of a [`SimulationResults`] into a shared
+/// [`VisualizationState`] in real time, so a finished run can be watched
+/// again without its configuration: the world geometry comes from the
+/// results. Species, labels and signals are not recorded per step and are
+/// not shown.
+pub struct Playback {
+    results: SimulationResults,
+    /// Playback speed (1.0 = `STEPS_PER_SECOND`)
+    speed: f64,
+    /// Index of the first recorded step shown
+    start: usize,
+    dimension: usize,
+    bounds: Vec<f32>,
+}
+
+impl Playback {
+    /// Simulation steps shown per second at speed 1.0.
+    pub const STEPS_PER_SECOND: f64 = 100.0;
+
+    /// Interval between frame updates.
+    const FRAME_INTERVAL: Duration = Duration::from_millis(20);
+
+    /// Prepare playback of `results`.
+    /// 
+    /// # Arguments
+    /// * `results` - Loaded results (see `SimulationResults::load`)
+    /// * `speed` - Speed multiplier (positive)
+    /// * `start_step` - First step to show; playback starts at the first
+    ///   recorded step at or after it
+    /// 
+    /// # Returns
+    /// The playback, or an error if there is nothing to play from `start_step`
+    pub fn new(results: SimulationResults, speed: f64, start_step: u64) -> Result<Self, String> {
+        if !(speed.is_finite() && speed > 0.0) {
+            return Err(format!("playback speed must be a positive number, got {}", speed));
+        }
+        let last = match results.steps.last() {
+            Some(last) => last.step_number,
+            None => return Err("the results contain no recorded steps".to_string()),
+        };
+        let start = results
+            .steps
+            .iter()
+            .position(|step| step.step_number >= start_step)
+            .ok_or_else(|| format!("start step {} is after the last recorded step {}", start_step, last))?;
+
+        // Results saved before world bounds were recorded: use the extent of
+        // the recorded positions instead
+        let (dimension, bounds) = if results.world_bounds.is_empty() {
+            let mut bounds: Vec<f32> = Vec::new();
+            for (_, position) in results.steps.iter().flat_map(|step| &step.entity_positions) {
+                bounds.resize(bounds.len().max(position.len()), 0.0);
+                for (bound, &value) in bounds.iter_mut().zip(position) {
+                    *bound = bound.max(to_f32(value));
+                }
+            }
+            (bounds.len(), bounds)
+        } else {
+            (results.world_bounds.len(), results.world_bounds.iter().map(|&b| to_f32(b)).collect())
+        };
+
+        Ok(Playback { results, speed, start, dimension, bounds })
+    }
+
+    /// Number of recorded steps played, from the start step to the end.
+    pub fn frame_count(&self) -> usize {
+        self.results.steps.len() - self.start
+    }
+
+    /// Visualization state showing the start step.
+    /// 
+    /// The metric plots already hold the history up to the start step.
+    pub fn initial_state(&self) -> VisualizationState {
+        let mut state = VisualizationState {
+            dimension: self.dimension,
+            bounds: self.bounds.clone(),
+            species_names: vec![DEFAULT_SPECIES_NAME.to_string()],
+            replay: true,
+            environment: self
+                .results
+                .config
+                .as_ref()
+                .map(|config| environment_markers(&config.environment))
+                .unwrap_or_default(),
+            ..Default::default()
+        };
+        for step in &self.results.steps[..=self.start] {
+            push_history(&mut state.metrics, step);
+        }
+        show_step(&mut state, &self.results.steps[self.start]);
+        state
+    }
+
+    /// Play the recorded steps into `state` in real time.
+    /// 
+    /// Blocks until the last recorded step is shown; `state` should start
+    /// as [`Playback::initial_state`].
+    pub fn play(&self, state: &Arc<Mutex<VisualizationState>>) {
+        let steps = &self.results.steps;
+        let first_step = steps[self.start].step_number;
+        let started = Instant::now();
+        let mut shown = self.start;
+        while shown + 1 < steps.len() {
+            std::thread::sleep(Self::FRAME_INTERVAL);
+            let elapsed = started.elapsed().as_secs_f64();
+            let target = first_step + (elapsed * Self::STEPS_PER_SECOND * self.speed) as u64;
+            let mut next = shown;
+            while next + 1 < steps.len() && steps[next + 1].step_number <= target {
+                next += 1;
+            }
+            if next == shown {
+                continue;
+            }
+            if let Ok(mut state) = state.lock() {
+                for step in &steps[shown + 1..=next] {
+                    push_history(&mut state.metrics, step);
+                }
+                show_step(&mut state, &steps[next]);
+            }
+            shown = next;
+        }
+    }
+}
+
+/// Append a recorded step to the metric and centroid history.
+fn push_history(history: &mut MetricsHistory, step: &SimulationStep) {
+    history.push(step.step_number, &step.metrics);
+    if !step.center_of_mass.is_empty() {
+        history.push_centroid(&step.center_of_mass, &step.mean_velocity);
+    }
+}
+
+/// Show the entities and attractions of a recorded step.
+fn show_step(state: &mut VisualizationState, step: &SimulationStep) {
+    let values = |v: &[Real]| v.iter().map(|&x| to_f32(x)).collect::<Vec<f32>>();
+    let velocities: HashMap<u32, &Coords> = step.entity_velocities.iter().map(|(id, v)| (*id, v)).collect();
+    let essence: HashMap<u32, Real> = step.entity_essence.iter().copied().collect();
+    let energy: HashMap<u32, Real> = step.entity_energy.iter().copied().collect();
+    let attentions: HashMap<u32, &Vec<Real>> = step.attentions.iter().map(|(id, a)| (*id, a)).collect();
+    let clusters: HashMap<u32, &Vec<(u32, Real, i32)>> = step.belief_clusters.iter().map(|(id, c)| (*id, c)).collect();
+    let roles: HashMap<u32, Role> = step.entity_roles.iter().copied().collect();
+
+    let mut positions: Vec<&(u32, Coords)> = step.entity_positions.iter().collect();
+    positions.sort_by_key(|(id, _)| *id);
+    state.entities = positions
+        .into_iter()
+        .map(|(id, position)| {
+            let velocity = velocities.get(id).map_or_else(Vec::new, |v| values(v));
+            let speed = velocity.iter().map(|v| v * v).sum::<f32>().sqrt();
+            let cluster_list = clusters.get(id).map_or(&[][..], |c| c.as_slice());
+            let affective_strength = if cluster_list.is_empty() {
+                0.0
+            } else {
+                cluster_list.iter().map(|(_, signal, _)| to_f32(*signal).abs()).sum::<f32>() / cluster_list.len() as f32
+            };
+            EntityState {
+                id: *id,
+                position: values(position),
+                heading: if speed > 0.0 { velocity.iter().map(|v| v / speed).collect() } else { Vec::new() },
+                velocity,
+                essence: essence.get(id).map_or(0.0, |&e| to_f32(e)),
+                affective_strength,
+                attention: attentions.get(id).map_or_else(Vec::new, |a| values(&a[..a.len().min(10)])),
+                num_clusters: cluster_list.len(),
+                energy: energy.get(id).map_or(0.0, |&e| to_f32(e)),
+                leader: roles.get(id) == Some(&Role::Leader),
+                ..Default::default()
+            }
+        })
+        .collect();
+
+    let index: HashMap<u32, usize> = state.entities.iter().enumerate().map(|(i, e)| (e.id, i)).collect();
+    state.attractions = step
+        .attractions
+        .iter()
+        .filter_map(|(a, b, strength)| Some((*index.get(a)?, *index.get(b)?, to_f32(*strength))))
+        .collect();
+    state.step = step.step_number;
+}
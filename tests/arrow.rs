@@ -0,0 +1,89 @@
+//! Checks the Arrow tables against the results they were built from.
+//!
+//! `cargo test --features parquet --test arrow`
+
+#![cfg(feature = "arrow")]
+
+use arrow::array::{Array, UInt32Array, UInt64Array};
+#[cfg(not(feature = "f64"))]
+use arrow::array::Float32Array as RealArray;
+#[cfg(feature = "f64")]
+use arrow::array::Float64Array as RealArray;
+use arrow::datatypes::DataType;
+use synthetic_consciousness::columnar::{self, to_ipc_stream};
+use synthetic_consciousness::{Simulation, SimulationConfig};
+
+/// Arrow type of floating-point columns.
+const REAL_TYPE: DataType = if cfg!(feature = "f64") { DataType::Float64 } else { DataType::Float32 };
+
+fn small_run() -> Simulation {
+    let mut config = SimulationConfig::default_2d();
+    config.simulation.num_entities = 6;
+    config.simulation.num_steps = 30;
+    let mut sim = Simulation::new(config).unwrap();
+    sim.run();
+    sim.finalize_results();
+    sim
+}
+
+#[test]
+fn tables_match_results() {
+    let sim = small_run();
+    let results = &sim.results;
+    let tables = results.to_arrow().unwrap();
+
+    let metrics = &tables.metrics;
+    assert_eq!(metrics.schema(), columnar::metrics_schema());
+    assert_eq!(metrics.schema().field(0).name(), "step");
+    assert_eq!(metrics.schema().field(0).data_type(), &DataType::UInt64);
+    assert_eq!(metrics.schema().field_with_name("average_essence").unwrap().data_type(), &REAL_TYPE);
+    assert_eq!(metrics.num_rows(), results.steps.len());
+    let essence = metrics
+        .column_by_name("average_essence")
+        .unwrap()
+        .as_any()
+        .downcast_ref::<RealArray>()
+        .unwrap();
+    assert_eq!(essence.value(7), results.steps[7].metrics.average_essence);
+
+    let trajectories = &tables.trajectories;
+    let schema = trajectories.schema();
+    let names: Vec<&str> = schema.fields().iter().map(|f| f.name().as_str()).collect();
+    assert_eq!(names, ["step", "entity_id", "x", "y", "z", "vx", "vy", "vz", "essence", "energy"]);
+    let expected_rows: usize = results.steps.iter().map(|s| s.entity_positions.len()).sum();
+    assert_eq!(trajectories.num_rows(), expected_rows);
+    let z = trajectories.column_by_name("z").unwrap();
+    assert_eq!(z.null_count(), expected_rows, "z is null in 2D");
+    let ids = trajectories.column(1).as_any().downcast_ref::<UInt32Array>().unwrap();
+    let steps = trajectories.column(0).as_any().downcast_ref::<UInt64Array>().unwrap();
+    let x = trajectories.column(2).as_any().downcast_ref::<RealArray>().unwrap();
+    let first = &results.steps[0];
+    let (_, position) = first.entity_positions.iter().find(|(id, _)| *id == ids.value(0)).unwrap();
+    assert_eq!(steps.value(0), first.step_number);
+    assert_eq!(x.value(0), position[0]);
+
+    let attractions = &tables.attractions;
+    assert_eq!(attractions.schema(), columnar::attractions_schema());
+    let pairs: usize = results.steps.iter().map(|s| s.attractions.len()).sum();
+    assert_eq!(attractions.num_rows(), pairs);
+
+    assert!(!to_ipc_stream(metrics).unwrap().is_empty());
+}
+
+#[cfg(feature = "parquet")]
+#[test]
+fn parquet_files_share_the_schema() {
+    use parquet::arrow::arrow_reader::ParquetRecordBatchReaderBuilder;
+
+    let sim = small_run();
+    let prefix = std::env::temp_dir().join(format!("sc_arrow_{}", std::process::id()));
+    let paths = sim.results.export_parquet(prefix.to_str().unwrap()).unwrap();
+    assert_eq!(paths.len(), 3);
+
+    let file = std::fs::File::open(&paths[0]).unwrap();
+    let reader = ParquetRecordBatchReaderBuilder::try_new(file).unwrap();
+    assert_eq!(reader.schema().fields(), columnar::metrics_schema().fields());
+    for path in paths {
+        std::fs::remove_file(path).unwrap();
+    }
+}
@@ -0,0 +1,128 @@
+//! Checks the attention layer of the step: gradients of the attraction
+//! field stored on each entity and applied as acceleration (so a pair of
+//! entities drifts together), and softmax attention distributions recorded
+//! per step.
+//!
+//! ```bash
+//! cargo test --test attention
+//! ```
+
+use synthetic_consciousness::attraction::{attention_gradient, softmax_attention, KernelType};
+use synthetic_consciousness::{Entity, Real, Simulation, SimulationConfig};
+
+fn config(sigma: Real) -> SimulationConfig {
+    let mut config = SimulationConfig::default_2d();
+    config.simulation.num_entities = 6;
+    config.geometry.bounds = vec![4.0, 4.0];
+    config.attraction.sigma = sigma;
+    config
+}
+
+#[test]
+fn entities_store_the_gradient_of_the_others_field() {
+    let mut sim = Simulation::new(config(1.0)).unwrap();
+    let ids = sim.sorted_entity_ids();
+    let positions: Vec<Vec<Real>> = ids
+        .iter()
+        .map(|id| sim.entities.get_entity(*id).unwrap().pose.position.to_vec())
+        .collect();
+    sim.step();
+
+    for (i, id) in ids.iter().enumerate() {
+        let others: Vec<Vec<Real>> = (0..ids.len()).filter(|&j| j != i).map(|j| positions[j].clone()).collect();
+        let weights = vec![1.0; others.len()];
+        let expected = attention_gradient(&positions[i], &others, &weights, &config(1.0).attraction);
+        let entity = sim.entities.get_entity(*id).unwrap();
+        assert_eq!(entity.attention_gradient, expected);
+        assert!(expected.iter().any(|g| *g != 0.0));
+    }
+}
+
+#[test]
+fn the_gradient_accelerates_entities() {
+    // A vanishing kernel width leaves no field to follow
+    let mut pulled = Simulation::new(config(1.0)).unwrap();
+    let mut free = Simulation::new(config(1e-3)).unwrap();
+    pulled.step();
+    free.step();
+
+    for id in pulled.sorted_entity_ids() {
+        let entity = pulled.entities.get_entity(id).unwrap();
+        let other = free.entities.get_entity(id).unwrap();
+        assert!(other.attention_gradient.iter().all(|g| *g == 0.0));
+        let change: Vec<Real> = entity.velocity.iter().zip(&other.velocity).map(|(a, b)| a - b).collect();
+        // The stored prompt is -∇Φ; entities climb the potential
+        let along: Real = change.iter().zip(&entity.attention_gradient).map(|(c, g)| c * g).sum();
+        assert!(along < 0.0, "entity {} moved down the attraction potential", id.0);
+    }
+
+    // The legacy pipeline ignores the field
+    let legacy = |sigma| {
+        let mut config = config(sigma);
+        config.dynamics.legacy_pipeline = true;
+        let mut sim = Simulation::new(config).unwrap();
+        sim.step();
+        sim.results.steps[0].entity_positions.clone()
+    };
+    assert_eq!(legacy(1.0), legacy(1e-3));
+}
+
+#[test]
+fn two_entities_drift_together_under_a_gaussian_kernel() {
+    // At rest and without drives, only the attraction field moves them
+    let run = |legacy: bool| {
+        let builder = Simulation::builder().entities(0).steps(200).configure(|config| {
+            config.attraction.kernel = KernelType::Gaussian;
+            config.attraction.sigma = 2.0;
+            config.dynamics.min_speed = 0.0;
+            config.dynamics.legacy_pipeline = legacy;
+            config.drives.responsiveness = 0.0;
+        });
+        let entity = |x: Real| Entity::builder(2).position(vec![x, 5.0]).drives(0.0, 0.0).build(builder.config()).unwrap();
+        let (left, right) = (entity(4.0), entity(6.0));
+        let mut sim = builder.with_entity(left).with_entity(right).build().unwrap();
+        sim.run();
+        let xs: Vec<Real> = sim.entities.iter().map(|e| e.pose.position[0]).collect();
+        (xs[0], xs[1])
+    };
+
+    let (left, right) = run(false);
+    assert!(left > 4.0 && right < 6.0, "entities at {} and {}", left, right);
+    assert!(right > left, "entities passed each other");
+
+    // The constant legacy acceleration pushes both the same way
+    let (left, right) = run(true);
+    assert!(left > 4.0 && right > 6.0);
+    assert!((right - left - 2.0).abs() < 1e-4);
+}
+
+#[test]
+fn recorded_attentions_are_softmax_distributions_over_the_others() {
+    let mut sim = Simulation::new(config(1.0)).unwrap();
+    let ids = sim.sorted_entity_ids();
+    let strengths = sim.attention_matrix();
+    sim.step();
+
+    let step = &sim.results.steps[0];
+    assert_eq!(step.attentions.len(), ids.len());
+    let lambda = sim.config.attraction.lambda;
+    for (i, (id, row)) in step.attentions.iter().enumerate() {
+        assert_eq!(*id, ids[i].0);
+        assert_eq!(row.len(), ids.len());
+        assert_eq!(row[i], 0.0);
+        assert!((row.iter().sum::<Real>() - 1.0).abs() < 1e-5);
+
+        let scores: Vec<Real> = (0..ids.len()).filter(|&j| j != i).map(|j| strengths[i][j]).collect();
+        let others: Vec<Real> = (0..ids.len()).filter(|&j| j != i).map(|j| row[j]).collect();
+        assert_eq!(others, softmax_attention(&scores, lambda));
+    }
+
+    // Nothing is recorded between capture steps
+    let mut config = config(1.0);
+    config.simulation.attention_capture_interval = 2;
+    let mut sim = Simulation::new(config).unwrap();
+    sim.step();
+    sim.step();
+    assert_eq!(sim.results.steps[0].attentions.len(), ids.len());
+    assert!(sim.results.steps[1].attentions.is_empty());
+}
@@ -0,0 +1,77 @@
+//! Compares the incrementally maintained activation entropy with a full
+//! recomputation over randomized memory updates.
+//!
+//! ```bash
+//! cargo test --test attention_entropy
+//! ```
+
+use rand::{rngs::StdRng, Rng, SeedableRng};
+use synthetic_consciousness::memory::{MemoryGraph, MemoryNode, ACTIVATION_RESYNC_INTERVAL};
+use synthetic_consciousness::Real;
+
+const UPDATES: usize = 5000;
+
+fn assert_close(graph: &MemoryGraph, update: usize) {
+    let (incremental, exact) = (graph.activation_entropy(), graph.exact_activation_entropy());
+    assert!(
+        (incremental - exact).abs() <= 1e-4 * exact.max(1.0),
+        "update {}: incremental {} vs exact {}",
+        update,
+        incremental,
+        exact
+    );
+}
+
+#[test]
+fn incremental_entropy_tracks_exact_value() {
+    let mut rng = StdRng::seed_from_u64(42);
+    let mut graph = MemoryGraph::new();
+    assert_eq!(graph.activation_entropy(), 0.0);
+
+    for update in 0..UPDATES {
+        match rng.gen_range(0..10) {
+            0..=4 => {
+                let event = graph.store_event(&[rng.gen(), rng.gen()]);
+                let mut node = MemoryNode::new(event, update as u64);
+                node.activation = rng.gen_range(0.0..1.0);
+                graph.add_node(node);
+            }
+            5..=7 => graph.decay(rng.gen_range(0.9..1.0) as Real),
+            _ if !graph.nodes.is_empty() => {
+                let idx = rng.gen_range(0..graph.nodes.len());
+                graph.set_activation(idx, rng.gen_range(0.0..1.0));
+            }
+            _ => {}
+        }
+        assert_close(&graph, update);
+    }
+    assert!(graph.nodes.len() > 1000);
+}
+
+#[test]
+fn long_decay_runs_stay_synchronized() {
+    let mut graph = MemoryGraph::new();
+    for i in 0..200 {
+        let mut node = MemoryNode::new(graph.store_event(&[i as Real]), i);
+        node.activation = 1.0 / (1 + i) as Real;
+        graph.add_node(node);
+    }
+    for step in 0..4 * ACTIVATION_RESYNC_INTERVAL as usize {
+        graph.decay(0.995);
+        assert_close(&graph, step);
+    }
+}
+
+#[test]
+fn direct_node_edits_fall_back_to_exact_value() {
+    let mut graph = MemoryGraph::new();
+    graph.add_event(&[0.0], 0);
+    // Bypasses the aggregates; the node count mismatch is detected
+    let event = graph.store_event(&[1.0]);
+    graph.nodes.push(MemoryNode::new(event, 1));
+    assert_eq!(graph.activation_entropy(), graph.exact_activation_entropy());
+    assert!((graph.activation_entropy() - (2.0 as Real).ln()).abs() < 1e-6);
+
+    graph.resync_activation_sums();
+    assert!((graph.activation_entropy() - (2.0 as Real).ln()).abs() < 1e-6);
+}
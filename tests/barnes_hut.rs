@@ -0,0 +1,133 @@
+//! Compares Barnes–Hut potentials and gradients against brute force.
+//!
+//! The tolerance on the relative potential error grows with the opening
+//! angle as θ²/4; θ = 0 must reproduce brute force exactly.
+//!
+//! ```bash
+//! cargo test --test barnes_hut
+//! ```
+
+use rand::{rngs::StdRng, Rng, SeedableRng};
+use synthetic_consciousness::attraction::{attention_gradient, attraction_potential, AttractionConfig, KernelType};
+use synthetic_consciousness::barnes_hut::AttractionTree;
+use synthetic_consciousness::{Real, SimulationConfig};
+
+const ENTITIES: usize = 2000;
+const QUERY_STRIDE: usize = 40;
+const THETAS: [Real; 4] = [0.1, 0.3, 0.5, 0.8];
+
+struct Population {
+    positions: Vec<Vec<Real>>,
+    weights: Vec<Real>,
+}
+
+impl Population {
+    fn random(dim: usize, seed: u64) -> Self {
+        let mut rng = StdRng::seed_from_u64(seed);
+        Population {
+            positions: (0..ENTITIES).map(|_| (0..dim).map(|_| rng.gen_range(0.0..20.0)).collect()).collect(),
+            weights: (0..ENTITIES).map(|_| rng.gen_range(0.5..1.5)).collect(),
+        }
+    }
+
+    /// Positions and weights of every entity except `skip`.
+    fn without(&self, skip: usize) -> (Vec<Vec<Real>>, Vec<Real>) {
+        self.positions
+            .iter()
+            .zip(&self.weights)
+            .enumerate()
+            .filter(|(idx, _)| *idx != skip)
+            .map(|(_, (position, &weight))| (position.clone(), weight))
+            .unzip()
+    }
+}
+
+fn config(kernel: KernelType, theta: Real) -> AttractionConfig {
+    AttractionConfig {
+        kernel,
+        sigma: 2.0,
+        theta,
+        ..SimulationConfig::default_2d().attraction
+    }
+}
+
+/// Largest relative potential error over the sampled entities.
+fn max_potential_error(population: &Population, tree: &AttractionTree, config: &AttractionConfig) -> Real {
+    (0..ENTITIES)
+        .step_by(QUERY_STRIDE)
+        .map(|i| {
+            let (others, weights) = population.without(i);
+            let exact = attraction_potential(&population.positions[i], &others, &weights, config);
+            let approx = tree.potential(&population.positions[i], Some(i), config);
+            ((approx - exact) / exact).abs()
+        })
+        .fold(0.0, Real::max)
+}
+
+#[test]
+fn theta_zero_reproduces_brute_force() {
+    for dim in [2, 3] {
+        let population = Population::random(dim, 1);
+        let tree = AttractionTree::build(&population.positions, &population.weights);
+        for kernel in [KernelType::Gaussian, KernelType::InverseDistance] {
+            let config = config(kernel, 0.0);
+            for i in (0..ENTITIES).step_by(QUERY_STRIDE) {
+                let (others, weights) = population.without(i);
+                let position = &population.positions[i];
+                assert_eq!(
+                    tree.potential(position, Some(i), &config),
+                    attraction_potential(position, &others, &weights, &config)
+                );
+                assert_eq!(
+                    tree.gradient(position, Some(i), &config),
+                    attention_gradient(position, &others, &weights, &config)
+                );
+            }
+        }
+    }
+}
+
+#[test]
+fn potential_error_is_bounded_by_theta() {
+    for dim in [2, 3] {
+        let population = Population::random(dim, 2);
+        let tree = AttractionTree::build(&population.positions, &population.weights);
+        for kernel in [KernelType::Gaussian, KernelType::InverseDistance] {
+            let errors: Vec<Real> = THETAS
+                .iter()
+                .map(|&theta| max_potential_error(&population, &tree, &config(kernel.clone(), theta)))
+                .collect();
+            for (&theta, &error) in THETAS.iter().zip(&errors) {
+                assert!(error <= theta * theta / 4.0, "{:?} {}D θ={}: error {}", kernel, dim, theta, error);
+            }
+            assert!(errors[0] < errors[THETAS.len() - 1], "{:?} {}D: {:?}", kernel, dim, errors);
+        }
+    }
+}
+
+#[test]
+fn far_field_gradient_tracks_exact_gradient() {
+    let population = Population::random(2, 3);
+    let tree = AttractionTree::build(&population.positions, &population.weights);
+    // A vanishing θ sums every term with the analytic kernel gradient
+    let exact = config(KernelType::InverseDistance, 1e-9);
+    let approx = config(KernelType::InverseDistance, 0.3);
+    for i in (0..ENTITIES).step_by(QUERY_STRIDE) {
+        let position = &population.positions[i];
+        let reference = tree.gradient(position, Some(i), &exact);
+        let gradient = tree.gradient(position, Some(i), &approx);
+        let norm = reference.iter().map(|g| g * g).sum::<Real>().sqrt();
+        let error = reference.iter().zip(&gradient).map(|(a, b)| (a - b).powi(2)).sum::<Real>().sqrt();
+        assert!(error <= 0.05 * norm, "entity {}: error {} of {}", i, error, norm);
+    }
+}
+
+#[test]
+fn querying_entity_is_excluded() {
+    let positions = vec![vec![0.0, 0.0], vec![3.0, 0.0]];
+    let tree = AttractionTree::build(&positions, &[]);
+    let config = config(KernelType::Gaussian, 0.5);
+    let alone = tree.potential(&positions[0], Some(0), &config);
+    assert!((alone - (-9.0 / 8.0 as Real).exp()).abs() < 1e-6);
+    assert!(tree.potential(&positions[0], None, &config) > alone + 0.99);
+}
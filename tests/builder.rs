@@ -0,0 +1,82 @@
+//! Builds simulations with `SimulationBuilder` and checks the resulting
+//! configuration, validation and pre-built entities.
+//!
+//! ```bash
+//! cargo test --test builder
+//! ```
+
+use synthetic_consciousness::attraction::KernelType;
+use synthetic_consciousness::sensing::NoiseSense;
+use synthetic_consciousness::{Entity, Simulation, SimulationBuilder, SimulationConfig};
+
+#[test]
+fn fluent_settings_reach_the_configuration() {
+    let sim = Simulation::builder()
+        .entities(12)
+        .steps(300)
+        .dimension(3)
+        .bounds([20.0, 20.0, 20.0])
+        .kernel(KernelType::InverseDistance)
+        .seed(7)
+        .configure(|config| config.attraction.sigma = 2.0)
+        .build()
+        .unwrap();
+
+    let config = &sim.config;
+    assert_eq!(config.simulation.num_entities, 12);
+    assert_eq!(config.simulation.num_steps, 300);
+    assert_eq!(config.geometry.dimension, 3);
+    assert_eq!(config.geometry.bounds, [20.0, 20.0, 20.0]);
+    assert!(matches!(config.attraction.kernel, KernelType::InverseDistance));
+    assert_eq!(config.attraction.sigma, 2.0);
+    assert_eq!(sim.results.seed, 7);
+    assert_eq!(sim.entities.count(), 12);
+    assert!(sim.entities.iter().all(|e| e.pose.position.len() == 3));
+}
+
+#[test]
+fn builds_the_same_run_as_the_equivalent_config() {
+    let mut config = SimulationConfig::default_2d();
+    config.simulation.num_entities = 6;
+    config.simulation.num_steps = 40;
+    config.simulation.seed = 3;
+    let mut expected = Simulation::new(config).unwrap();
+    let mut built = SimulationBuilder::new().entities(6).steps(40).seed(3).sense_model(Box::new(NoiseSense)).build().unwrap();
+    expected.run();
+    built.run();
+
+    let values = |sim: &Simulation| sim.metrics_history.iter().map(|m| m.field_values()).collect::<Vec<_>>();
+    assert_eq!(values(&built), values(&expected));
+}
+
+#[test]
+fn invalid_settings_fail_at_build() {
+    let dimension = Simulation::builder().dimension(3).bounds([10.0, 10.0]).build();
+    assert!(dimension.is_err());
+    let cadence = Simulation::builder().configure(|config| config.recording.record_every = 0).build();
+    assert_eq!(cadence.err().unwrap(), "Recording cadence must be at least 1");
+}
+
+#[test]
+fn pre_built_entities_join_the_population() {
+    let builder = Simulation::builder().entities(0).steps(20);
+    let joyful = Entity::builder(2).position(vec![2.0, 2.0]).essence(9.0).build(builder.config()).unwrap();
+    let gloomy = Entity::builder(2).position(vec![3.0, 2.0]).essence(1.0).build(builder.config()).unwrap();
+    let mut sim = builder.with_entity(joyful).with_entity(gloomy).build().unwrap();
+
+    assert_eq!(sim.entities.count(), 2);
+    assert_eq!(sim.results.num_entities, 2);
+    let ids = sim.sorted_entity_ids();
+    let first = sim.entities.get_entity(ids[0]).unwrap();
+    assert_eq!((first.pose.position.to_vec(), first.essence.value), (vec![2.0, 2.0], 9.0));
+    sim.run();
+    assert_eq!(sim.metrics_history.len(), 20);
+
+    // Generated entities come first
+    let extra = Entity::builder(2).build(&SimulationConfig::default_2d()).unwrap();
+    let sim = Simulation::builder().entities(3).with_entity(extra).build().unwrap();
+    assert_eq!(sim.entities.count(), 4);
+
+    let flat = Entity::builder(3).build(&SimulationConfig::default_3d()).unwrap();
+    assert!(Simulation::builder().with_entity(flat).build().is_err());
+}
@@ -0,0 +1,50 @@
+//! Centroid of the population: two mirror-image entities average to the
+//! midpoint at rest, and an empty pool has no centroid.
+//!
+//! ```bash
+//! cargo test --test centroid
+//! ```
+
+use synthetic_consciousness::entities::EntityPool;
+use synthetic_consciousness::{Entity, Real, SimulationConfig};
+
+fn pool(config: &SimulationConfig, entities: &[(Vec<Real>, Vec<Real>)]) -> EntityPool {
+    let mut pool = EntityPool::new();
+    for (position, velocity) in entities {
+        let entity = Entity::builder(config.geometry.dimension)
+            .position(position.clone())
+            .velocity(velocity.clone())
+            .build(config)
+            .unwrap();
+        pool.add_entity(entity);
+    }
+    pool
+}
+
+#[test]
+fn symmetric_entities_center_on_the_midpoint_at_rest() {
+    let config = SimulationConfig::default_2d();
+    let pair = pool(&config, &[(vec![3.0, 4.0], vec![0.5, -0.25]), (vec![7.0, 6.0], vec![-0.5, 0.25])]);
+    assert_eq!(pair.centroid_position(), vec![5.0, 5.0]);
+    assert_eq!(pair.centroid_velocity(), vec![0.0, 0.0]);
+
+    let config = SimulationConfig::default_3d();
+    let pair = pool(&config, &[(vec![1.0, 2.0, 3.0], vec![0.1, 0.2, 0.3]), (vec![3.0, 2.0, 1.0], vec![-0.1, -0.2, -0.3])]);
+    assert_eq!(pair.centroid_position(), vec![2.0, 2.0, 2.0]);
+    assert_eq!(pair.centroid_velocity(), vec![0.0, 0.0, 0.0]);
+}
+
+#[test]
+fn a_single_entity_is_its_own_centroid() {
+    let config = SimulationConfig::default_2d();
+    let single = pool(&config, &[(vec![1.5, 8.0], vec![0.3, -0.1])]);
+    assert_eq!(single.centroid_position(), vec![1.5, 8.0]);
+    assert_eq!(single.centroid_velocity(), vec![0.3, -0.1]);
+}
+
+#[test]
+fn an_empty_pool_has_no_centroid() {
+    let empty = EntityPool::new();
+    assert!(empty.centroid_position().is_empty());
+    assert!(empty.centroid_velocity().is_empty());
+}
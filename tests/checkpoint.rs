@@ -0,0 +1,96 @@
+//! Saves simulations mid-run, resumes them and checks they finish exactly
+//! like uninterrupted runs.
+//!
+//! ```bash
+//! cargo test --test checkpoint
+//! ```
+
+use std::path::PathBuf;
+use synthetic_consciousness::checkpoint::{Checkpointer, CHECKPOINT_VERSION};
+use synthetic_consciousness::{Simulation, SimulationConfig};
+
+fn config() -> SimulationConfig {
+    let mut config = SimulationConfig::default_2d();
+    config.simulation.num_entities = 6;
+    config.simulation.num_steps = 40;
+    config.simulation.seed = 5;
+    config.signaling.enabled = true;
+    config.roles.enabled = true;
+    config
+}
+
+fn temp_file(name: &str) -> PathBuf {
+    std::env::temp_dir().join(format!("sc_checkpoint_{}_{}", std::process::id(), name))
+}
+
+/// Results as JSON without the fields that legitimately differ between runs.
+fn comparable_results(sim: &Simulation) -> serde_json::Value {
+    let mut results = serde_json::to_value(&sim.results).unwrap();
+    let object = results.as_object_mut().unwrap();
+    for volatile in ["start_time", "end_time", "wall_clock_seconds"] {
+        object.remove(volatile);
+    }
+    results
+}
+
+#[test]
+fn resumed_run_ends_like_an_uninterrupted_one() {
+    let mut uninterrupted = Simulation::new(config()).unwrap();
+    uninterrupted.run();
+    uninterrupted.finalize_results();
+
+    for name in ["plain.json", "compressed.json.gz"] {
+        let path = temp_file(name);
+        let path = path.to_str().unwrap();
+        let mut first_half = Simulation::new(config()).unwrap();
+        for _ in 0..17 {
+            first_half.step();
+        }
+        first_half.save_checkpoint(path).unwrap();
+        drop(first_half);
+
+        let mut resumed = Simulation::load_checkpoint(path).unwrap();
+        assert_eq!(resumed.timestamp, 17);
+        assert_eq!(resumed.remaining_steps(), 23);
+        resumed.run();
+        resumed.finalize_results();
+        std::fs::remove_file(path).unwrap();
+
+        assert_eq!(resumed.timestamp, 40);
+        assert_eq!(resumed.metrics_history.len(), uninterrupted.metrics_history.len());
+        let last = |sim: &Simulation| serde_json::to_value(sim.metrics_history.last().unwrap()).unwrap();
+        assert_eq!(last(&resumed), last(&uninterrupted), "{}", name);
+        assert_eq!(comparable_results(&resumed), comparable_results(&uninterrupted), "{}", name);
+        assert_eq!(resumed.rng_state(), uninterrupted.rng_state());
+    }
+}
+
+#[test]
+fn checkpointer_saves_every_n_steps() {
+    let path = temp_file("periodic.json.gz");
+    let mut sim = Simulation::new(config()).unwrap();
+    sim.add_observer(Box::new(Checkpointer::new(path.to_str().unwrap(), 15)));
+    sim.run();
+
+    // The last save happened at step 30 and replaced the one from step 15
+    let saved = Simulation::load_checkpoint(path.to_str().unwrap()).unwrap();
+    assert_eq!(saved.timestamp, 30);
+    assert!(!temp_file("periodic.json.gz.partial").exists());
+    std::fs::remove_file(path).unwrap();
+}
+
+#[test]
+fn unusable_files_are_rejected_with_the_reason() {
+    let missing = Simulation::load_checkpoint("no_such_checkpoint.json").err().unwrap().to_string();
+    assert!(missing.contains("cannot read checkpoint 'no_such_checkpoint.json'"), "{}", missing);
+
+    let path = temp_file("future.json");
+    std::fs::write(&path, format!("{{\"version\": {}}}", CHECKPOINT_VERSION + 1)).unwrap();
+    let error = Simulation::load_checkpoint(path.to_str().unwrap()).err().unwrap().to_string();
+    assert!(error.contains(&format!("version {} checkpoint", CHECKPOINT_VERSION + 1)), "{}", error);
+
+    std::fs::write(&path, "{\"simulation_name\": \"results, not a checkpoint\"}").unwrap();
+    let error = Simulation::load_checkpoint(path.to_str().unwrap()).err().unwrap().to_string();
+    assert!(error.contains("no version"), "{}", error);
+    std::fs::remove_file(path).unwrap();
+}
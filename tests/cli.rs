@@ -0,0 +1,229 @@
+//! Drives the command-line parser with argument vectors.
+//!
+//! ```bash
+//! cargo test --test cli
+//! ```
+
+use synthetic_consciousness::cli::{
+    CliArgs, Command, ReplayArgs, RunOutcome, RunOverrides, SweepArgs, ValidateArgs, Verbosity, DEFAULT_CHECKPOINT_PATH,
+    EXIT_CONFIG_ERROR, EXIT_NOT_CONSCIOUS, EXIT_PARSE_ERROR, EXIT_RUNTIME_ERROR, EXIT_SUCCESS,
+};
+use synthetic_consciousness::config::ConfigFormat;
+use synthetic_consciousness::{ExportFormat, SimulationConfig};
+
+#[test]
+fn run_overrides_take_separate_or_inline_values() {
+    let cli = CliArgs::parse(&["config.toml", "--steps", "5000", "--entities=50", "--seed", "7"]).unwrap();
+    assert_eq!(cli.config_path.as_deref(), Some("config.toml"));
+    assert_eq!(
+        cli.run_overrides,
+        RunOverrides {
+            steps: Some(5000),
+            entities: Some(50),
+            seed: Some(7),
+        }
+    );
+}
+
+#[test]
+fn options_mix_with_config_path_and_key_value_overrides() {
+    let cli = CliArgs::parse(&["sigma=2.0", "--visualize", "run.toml", "--export", "json,md", "--plugin-lib=a.so", "--plugin-lib", "b.so"])
+        .unwrap();
+    assert_eq!(cli.config_path.as_deref(), Some("run.toml"));
+    assert_eq!(cli.overrides, vec!["sigma=2.0"]);
+    assert!(cli.visualize);
+    assert_eq!(cli.export, vec![ExportFormat::Json, ExportFormat::Md]);
+    assert_eq!(cli.plugin_libs, vec!["a.so", "b.so"]);
+    assert!(cli.run_overrides.is_empty());
+    assert_eq!(cli.verbosity, Verbosity::Normal);
+}
+
+#[test]
+fn verbosity_flags_select_the_level() {
+    assert_eq!(CliArgs::parse(&["-q"]).unwrap().verbosity, Verbosity::Quiet);
+    assert_eq!(CliArgs::parse(&["--quiet"]).unwrap().verbosity, Verbosity::Quiet);
+    assert_eq!(CliArgs::parse(&["-v"]).unwrap().verbosity, Verbosity::Verbose);
+    let cli = CliArgs::parse(&["--verbose", "--visualize"]).unwrap();
+    assert_eq!(cli.verbosity, Verbosity::Verbose);
+    assert!(cli.visualize);
+}
+
+#[test]
+fn defaults_without_arguments() {
+    let cli = CliArgs::parse::<&str>(&[]).unwrap();
+    assert_eq!(cli, CliArgs::default());
+    assert_eq!(cli.export, ExportFormat::DEFAULT.to_vec());
+}
+
+#[test]
+fn zero_and_malformed_counts_are_rejected() {
+    let error = CliArgs::parse(&["--steps", "0"]).unwrap_err();
+    assert!(error.contains("--steps must be at least 1"), "{}", error);
+    let error = CliArgs::parse(&["--entities=0"]).unwrap_err();
+    assert!(error.contains("--entities must be at least 1"), "{}", error);
+    let error = CliArgs::parse(&["--steps", "-3"]).unwrap_err();
+    assert!(error.contains("a positive integer"), "{}", error);
+    let error = CliArgs::parse(&["--seed", "seven"]).unwrap_err();
+    assert!(error.contains("'seven'"), "{}", error);
+    let error = CliArgs::parse(&["--seed"]).unwrap_err();
+    assert!(error.contains("needs a value"), "{}", error);
+}
+
+#[test]
+fn unknown_options_and_extra_paths_are_rejected() {
+    assert!(CliArgs::parse(&["--stepz", "5"]).unwrap_err().contains("'--stepz'"));
+    assert!(CliArgs::parse(&["a.toml", "b.toml"]).unwrap_err().contains("'b.toml'"));
+    assert!(CliArgs::parse(&["--export=csv,xlsx"]).unwrap_err().contains("'xlsx'"));
+}
+
+#[test]
+fn run_overrides_win_over_key_value_overrides() {
+    let cli = CliArgs::parse(&["num_steps=10", "--steps", "5000", "--entities", "50", "--seed", "7"]).unwrap();
+    assert_eq!(
+        cli.config_overrides(),
+        vec![
+            "num_steps=10",
+            "simulation.num_steps=5000",
+            "simulation.num_entities=50",
+            "simulation.seed=7"
+        ]
+    );
+
+    let mut config = SimulationConfig::default_2d();
+    config.apply_cli_args(&cli.config_overrides()).unwrap();
+    assert_eq!(config.simulation.num_steps, 5000);
+    assert_eq!(config.simulation.num_entities, 50);
+    assert_eq!(config.simulation.seed, 7);
+}
+
+#[test]
+fn outcomes_map_to_documented_exit_codes() {
+    let conscious = RunOutcome::Completed { consciousness_achieved: true };
+    let not_conscious = RunOutcome::Completed { consciousness_achieved: false };
+    assert_eq!(conscious.exit_code(true), 0);
+    assert_eq!(not_conscious.exit_code(true), 3);
+    assert_eq!(RunOutcome::NoVerdict.exit_code(true), 0);
+    assert_eq!(RunOutcome::ConfigError.exit_code(true), 1);
+    assert_eq!(RunOutcome::RuntimeError.exit_code(true), 2);
+    assert_eq!(RunOutcome::ParseError.exit_code(true), 4);
+    assert_eq!(
+        [EXIT_SUCCESS, EXIT_CONFIG_ERROR, EXIT_RUNTIME_ERROR, EXIT_NOT_CONSCIOUS, EXIT_PARSE_ERROR],
+        [0, 1, 2, 3, 4]
+    );
+}
+
+#[test]
+fn no_verdict_exit_code_only_changes_incomplete_verdicts() {
+    let cli = CliArgs::parse(&["--no-verdict-exit-code"]).unwrap();
+    assert!(!cli.verdict_exit_code);
+    assert!(CliArgs::default().verdict_exit_code);
+
+    let not_conscious = RunOutcome::Completed { consciousness_achieved: false };
+    assert_eq!(not_conscious.exit_code(false), EXIT_SUCCESS);
+    assert_eq!(RunOutcome::ConfigError.exit_code(false), EXIT_CONFIG_ERROR);
+    assert_eq!(RunOutcome::RuntimeError.exit_code(false), EXIT_RUNTIME_ERROR);
+}
+
+#[test]
+fn replay_subcommand_parses_its_options() {
+    let command = Command::parse(&["replay", "results.json.gz", "--speed", "2.0", "--start-step=1000"]).unwrap();
+    assert_eq!(
+        command,
+        Command::Replay(ReplayArgs {
+            results_path: "results.json.gz".to_string(),
+            speed: 2.0,
+            start_step: 1000,
+            verbosity: Verbosity::Normal,
+        })
+    );
+    assert!(Command::parse(&["replay"]).unwrap_err().contains("results file"));
+    assert!(Command::parse(&["replay", "r.json", "--speed", "0"]).unwrap_err().contains("--speed"));
+    assert!(Command::parse(&["replay", "r.json", "--steps", "5"]).unwrap_err().contains("'--steps'"));
+}
+
+#[test]
+fn run_is_the_default_subcommand() {
+    let explicit = Command::parse(&["run", "config.toml", "--steps", "10"]).unwrap();
+    let implicit = Command::parse(&["config.toml", "--steps", "10"]).unwrap();
+    assert_eq!(explicit, implicit);
+    assert!(matches!(explicit, Command::Run(cli) if cli.config_path.as_deref() == Some("config.toml")));
+}
+
+#[test]
+fn sweep_subcommand_collects_params() {
+    let command = Command::parse(&[
+        "sweep",
+        "base.toml",
+        "--param",
+        "attraction.sigma=0.5,1.0,2.0",
+        "--param=dynamics.min_speed=0.01,0.05",
+        "--jobs",
+        "4",
+        "--out",
+        "sweep_results/",
+    ])
+    .unwrap();
+    let Command::Sweep(sweep) = command else {
+        panic!("expected the sweep subcommand");
+    };
+    assert_eq!(sweep.config_path, "base.toml");
+    assert_eq!(sweep.params.len(), 2);
+    assert_eq!(sweep.params[1].path, "dynamics.min_speed");
+    assert_eq!(sweep.params[1].values, vec!["0.01", "0.05"]);
+    assert_eq!(sweep.jobs, Some(4));
+    assert_eq!(sweep.out_dir, "sweep_results/");
+
+    let defaults = SweepArgs::parse(&["base.toml", "--param", "sigma=1"]).unwrap();
+    assert_eq!((defaults.jobs, defaults.out_dir.as_str()), (None, SweepArgs::DEFAULT_OUT_DIR));
+    assert!(Command::parse(&["sweep", "base.toml"]).unwrap_err().contains("--param"));
+    assert!(Command::parse(&["sweep", "--param", "sigma=1"]).unwrap_err().contains("base config"));
+    assert!(Command::parse(&["sweep", "b.toml", "--param", "sigma"]).unwrap_err().contains("--param:"));
+    assert!(Command::parse(&["sweep", "b.toml", "--param", "sigma=1", "--jobs", "0"]).unwrap_err().contains("--jobs"));
+}
+
+#[test]
+fn validate_subcommand_takes_several_files() {
+    let command = Command::parse(&["validate", "a.toml", "-q", "-", "--format", "json"]).unwrap();
+    assert_eq!(
+        command,
+        Command::Validate(ValidateArgs {
+            config_paths: vec!["a.toml".to_string(), "-".to_string()],
+            config_format: Some(ConfigFormat::Json),
+            verbosity: Verbosity::Quiet,
+        })
+    );
+    // No file: standard input, if piped (decided when running)
+    assert!(Command::parse(&["validate"]).is_ok());
+    assert!(Command::parse(&["validate", "a.toml", "--steps=5"]).unwrap_err().contains("'--steps'"));
+}
+
+#[test]
+fn dash_is_the_stdin_config_path() {
+    let cli = CliArgs::parse(&["-", "--export", "json", "--format=toml"]).unwrap();
+    assert_eq!(cli.config_path.as_deref(), Some("-"));
+    assert_eq!(cli.config_format, Some(ConfigFormat::Toml));
+    assert!(matches!(Command::parse(&["run", "-"]).unwrap(), Command::Run(cli) if cli.config_path.as_deref() == Some("-")));
+    let error = CliArgs::parse(&["-", "--format", "yaml"]).unwrap_err();
+    assert!(error.contains("--format") && error.contains("'yaml'"), "{}", error);
+}
+
+#[test]
+fn checkpoint_options_need_an_interval_and_resume_stands_alone() {
+    let cli = CliArgs::parse(&["run.toml", "--checkpoint-every", "500"]).unwrap();
+    assert_eq!(cli.checkpoint(), Some((DEFAULT_CHECKPOINT_PATH, 500)));
+    let cli = CliArgs::parse(&["--checkpoint=run.ckpt.json", "--checkpoint-every=10"]).unwrap();
+    assert_eq!(cli.checkpoint(), Some(("run.ckpt.json", 10)));
+    assert_eq!(CliArgs::default().checkpoint(), None);
+
+    let error = CliArgs::parse(&["--checkpoint", "run.ckpt.json"]).unwrap_err();
+    assert!(error.contains("--checkpoint-every"), "{}", error);
+    let error = CliArgs::parse(&["--checkpoint-every", "0"]).unwrap_err();
+    assert!(error.contains("--checkpoint-every must be at least 1"), "{}", error);
+
+    let cli = CliArgs::parse(&["--resume", "run.ckpt.json", "--checkpoint-every", "10", "--export=json"]).unwrap();
+    assert_eq!(cli.resume.as_deref(), Some("run.ckpt.json"));
+    for extra in [&["run.toml"][..], &["sigma=2.0"], &["--steps", "10"]] {
+        let args: Vec<&str> = ["--resume", "run.ckpt.json"].iter().chain(extra).copied().collect();
+        assert!(CliArgs::parse(&args).unwrap_err().contains("--resume"), "{:?}", extra);
+    }
+}
@@ -0,0 +1,201 @@
+//! Runs the binary headless and checks what each verbosity writes to
+//! stdout, and the exit codes.
+//!
+//! ```bash
+//! cargo test --test console_output
+//! ```
+
+#![cfg(feature = "gui")]
+
+use std::path::PathBuf;
+use std::io::Write;
+use std::process::{Command, Output, Stdio};
+
+/// Run a short simulation in a fresh directory with extra arguments.
+fn run(name: &str, args: &[&str]) -> Output {
+    let dir: PathBuf = std::env::temp_dir().join(format!("sc_console_{}_{}", name, std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let output = Command::new(env!("CARGO_BIN_EXE_synthetic-consciousness"))
+        .args(["--steps", "20", "--entities", "3", "--no-verdict-exit-code"])
+        .args(args)
+        .current_dir(&dir)
+        .output()
+        .unwrap();
+    let _ = std::fs::remove_dir_all(&dir);
+    output
+}
+
+#[test]
+fn quiet_run_writes_nothing_to_stdout() {
+    let output = run("quiet", &["--quiet"]);
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+    assert!(output.stdout.is_empty(), "{}", String::from_utf8_lossy(&output.stdout));
+}
+
+#[test]
+fn default_run_prints_the_summary_without_progress() {
+    let output = run("default", &["--export=none"]);
+    assert!(output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("===== Summary ====="), "{}", stdout);
+    assert!(stdout.contains("CONSCIOUSNESS ANALYSIS SUMMARY"), "{}", stdout);
+    assert!(!stdout.contains("Step 20/20"), "{}", stdout);
+    assert!(stdout.contains("Wall-clock time: "), "{}", stdout);
+}
+
+#[test]
+fn default_run_shows_progress_on_stderr_unless_quiet() {
+    let output = run("progress", &["--export=none"]);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    // Not a terminal: plain lines, the last one at 100%
+    assert!(!stderr.contains('\r'), "{:?}", stderr);
+    let last = stderr.lines().last().unwrap_or_default();
+    assert!(last.starts_with("100.0% 20/20 |") && last.contains("steps/s"), "{}", stderr);
+
+    let quiet = run("progress_quiet", &["--quiet"]);
+    assert!(quiet.stderr.is_empty(), "{}", String::from_utf8_lossy(&quiet.stderr));
+}
+
+#[test]
+fn verbose_run_reports_progress() {
+    let output = run("verbose", &["-v", "--export=none"]);
+    assert!(output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("[debug] Step 20/20"), "{}", stdout);
+    assert!(stdout.contains("===== Summary ====="), "{}", stdout);
+    assert!(stdout.contains("Exit code 0"), "{}", stdout);
+}
+
+#[test]
+fn exit_code_reflects_the_verdict() {
+    let dir = std::env::temp_dir().join(format!("sc_console_verdict_{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let output = Command::new(env!("CARGO_BIN_EXE_synthetic-consciousness"))
+        .args(["--steps", "20", "--entities", "3", "--export=json", "--quiet"])
+        .current_dir(&dir)
+        .output()
+        .unwrap();
+    let results = std::fs::read_dir(&dir)
+        .unwrap()
+        .map(|entry| entry.unwrap().path())
+        .find(|path| path.extension().is_some_and(|ext| ext == "json"))
+        .unwrap();
+    let results: serde_json::Value = serde_json::from_str(&std::fs::read_to_string(results).unwrap()).unwrap();
+    let _ = std::fs::remove_dir_all(&dir);
+
+    let achieved = results["consciousness_analysis"]["consciousness_achieved"].as_bool().unwrap();
+    assert_eq!(output.status.code(), Some(if achieved { 0 } else { 3 }));
+}
+
+#[test]
+fn configuration_errors_exit_with_1() {
+    let output = run("config_error", &["--quiet", "missing_config.toml"]);
+    assert_eq!(output.status.code(), Some(1));
+    assert!(output.stdout.is_empty());
+    assert!(!output.stderr.is_empty());
+}
+
+#[test]
+fn validate_exit_code_separates_parse_and_semantic_errors() {
+    let validate = |files: &[&str]| {
+        Command::new(env!("CARGO_BIN_EXE_synthetic-consciousness"))
+            .arg("validate")
+            .args(files)
+            .current_dir(env!("CARGO_MANIFEST_DIR"))
+            .output()
+            .unwrap()
+    };
+
+    let output = validate(&["test_2d.toml", "example_config_3d.toml"]);
+    assert_eq!(output.status.code(), Some(0), "{}", String::from_utf8_lossy(&output.stderr));
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("test_2d.toml: OK") && stdout.contains("example_config_3d.toml: OK"), "{}", stdout);
+
+    let output = validate(&["test_2d.toml", "tests/fixtures/invalid_config.toml"]);
+    assert_eq!(output.status.code(), Some(1));
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("invalid_config.toml: 3 problems"), "{}", stderr);
+    assert!(stderr.contains("dynamics.damping: must be within [0, 1]"), "{}", stderr);
+
+    let output = validate(&["tests/fixtures/malformed_config.toml", "tests/fixtures/invalid_config.toml"]);
+    assert_eq!(output.status.code(), Some(4));
+    assert!(String::from_utf8_lossy(&output.stderr).contains("malformed_config.toml: parse error"));
+}
+
+/// Run the binary with `config` piped to its standard input.
+fn run_piped(dir: &std::path::Path, args: &[&str], config: &str) -> Output {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_synthetic-consciousness"))
+        .args(args)
+        .current_dir(dir)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .unwrap();
+    child.stdin.take().unwrap().write_all(config.as_bytes()).unwrap();
+    child.wait_with_output().unwrap()
+}
+
+#[test]
+fn piped_config_is_run_and_embedded_in_the_results() {
+    let dir = std::env::temp_dir().join(format!("sc_console_stdin_{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let mut config = synthetic_consciousness::SimulationConfig::default_2d();
+    config.metadata.name = "Piped".to_string();
+    config.simulation.num_entities = 3;
+    config.simulation.num_steps = 20;
+
+    let json = serde_json::to_string(&config).unwrap();
+    let output = run_piped(&dir, &["run", "-", "--export", "json", "--no-verdict-exit-code"], &json);
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+    assert!(String::from_utf8_lossy(&output.stdout).contains("Config: read from standard input"));
+
+    let results = std::fs::read_dir(&dir)
+        .unwrap()
+        .map(|entry| entry.unwrap().path())
+        .find(|path| path.extension().is_some_and(|ext| ext == "json"))
+        .unwrap();
+    let results = synthetic_consciousness::SimulationResults::load(results.to_str().unwrap()).unwrap();
+    assert_eq!(results.config_source, "stdin");
+    assert_eq!(results.config.unwrap().fingerprint(), config.fingerprint());
+
+    // Without `-` a piped TOML configuration is picked up as well
+    let toml = toml::to_string(&config).unwrap();
+    let output = run_piped(&dir, &["validate", "--format", "toml"], &toml);
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+    assert!(String::from_utf8_lossy(&output.stdout).contains("stdin: OK"));
+    let output = run_piped(&dir, &["validate", "-"], "sigma = [");
+    assert_eq!(output.status.code(), Some(4));
+    let _ = std::fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn checkpointed_run_resumes_from_the_last_save() {
+    let dir = std::env::temp_dir().join(format!("sc_console_resume_{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let binary = || {
+        let mut command = Command::new(env!("CARGO_BIN_EXE_synthetic-consciousness"));
+        command.current_dir(&dir).args(["--export=none", "--no-verdict-exit-code"]);
+        command
+    };
+
+    let output = binary()
+        .args(["--steps", "20", "--entities", "3", "--checkpoint-every", "15", "--checkpoint", "run.ckpt.json"])
+        .output()
+        .unwrap();
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+    assert!(String::from_utf8_lossy(&output.stdout).contains("Checkpoints: every 15 steps to run.ckpt.json"));
+
+    let output = binary().args(["--resume", "run.ckpt.json"]).output().unwrap();
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("Resuming: run.ckpt.json at step 15"), "{}", stdout);
+    assert!(stdout.contains("===== Summary ====="), "{}", stdout);
+    let last = String::from_utf8_lossy(&output.stderr).lines().last().unwrap_or_default().to_string();
+    assert!(last.starts_with("100.0% 20/20 |"), "{}", last);
+
+    let output = binary().args(["--resume", "missing.ckpt.json"]).output().unwrap();
+    assert_eq!(output.status.code(), Some(1));
+    assert!(String::from_utf8_lossy(&output.stderr).contains("cannot read checkpoint 'missing.ckpt.json'"));
+    let _ = std::fs::remove_dir_all(&dir);
+}
@@ -0,0 +1,91 @@
+//! Drives a simulation loop through the run control shared with the
+//! visualization: pausing, single steps, resuming and the speed limit.
+//!
+//! ```bash
+//! cargo test --test control
+//! ```
+
+#![cfg(feature = "gui")]
+
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::thread;
+use std::time::{Duration, Instant};
+use synthetic_consciousness::visualization::SimulationControl;
+use synthetic_consciousness::{Simulation, SimulationConfig};
+
+/// Wait until `condition` holds, failing after a second.
+fn eventually(condition: impl Fn() -> bool) {
+    let started = Instant::now();
+    while !condition() {
+        assert!(started.elapsed() < Duration::from_secs(1), "condition not reached");
+        thread::sleep(Duration::from_millis(5));
+    }
+}
+
+#[test]
+fn paused_runs_advance_one_step_per_request_and_resume_exactly() {
+    let mut config = SimulationConfig::default_2d();
+    config.simulation.num_entities = 4;
+    config.simulation.num_steps = 40;
+    let mut reference = Simulation::new(config.clone()).unwrap();
+    reference.run();
+
+    let control = Arc::new(SimulationControl::new());
+    let steps = Arc::new(AtomicU64::new(0));
+    control.set_paused(true);
+    let runner = {
+        let (control, steps) = (Arc::clone(&control), Arc::clone(&steps));
+        thread::spawn(move || {
+            let mut sim = Simulation::new(config).unwrap();
+            for _ in 0..sim.remaining_steps() {
+                assert!(control.wait_for_step(|| false));
+                sim.step();
+                steps.store(sim.timestamp, Ordering::SeqCst);
+            }
+            sim
+        })
+    };
+
+    thread::sleep(Duration::from_millis(50));
+    assert_eq!(steps.load(Ordering::SeqCst), 0);
+    for expected in 1..=3 {
+        control.request_single_step();
+        eventually(|| steps.load(Ordering::SeqCst) == expected);
+        thread::sleep(Duration::from_millis(30));
+        assert_eq!(steps.load(Ordering::SeqCst), expected);
+    }
+
+    control.set_paused(false);
+    let sim = runner.join().unwrap();
+    assert_eq!(sim.timestamp, 40);
+    let values = |sim: &Simulation| sim.metrics_history.iter().map(|m| m.field_values()).collect::<Vec<_>>();
+    assert_eq!(values(&sim), values(&reference));
+}
+
+#[test]
+fn a_stop_ends_the_wait() {
+    let control = SimulationControl::new();
+    assert!(control.wait_for_step(|| panic!("not polled while running")));
+    control.set_paused(true);
+    assert!(!control.wait_for_step(|| true));
+}
+
+#[test]
+fn the_speed_limit_spaces_steps_out() {
+    let control = SimulationControl::new();
+    control.set_steps_per_second(100);
+    let started = Instant::now();
+    let mut last_step = started;
+    for _ in 0..6 {
+        control.pace(&mut last_step);
+    }
+    assert!(started.elapsed() >= Duration::from_millis(50));
+
+    control.set_steps_per_second(0);
+    let started = Instant::now();
+    for _ in 0..100 {
+        control.pace(&mut last_step);
+    }
+    assert!(started.elapsed() < Duration::from_millis(50));
+}
@@ -0,0 +1,55 @@
+//! Counterfactual runs: an empty intervention reproduces the baseline bit
+//! for bit, and a real one is compared against it.
+//!
+//! ```bash
+//! cargo test --test counterfactual
+//! ```
+
+use synthetic_consciousness::entities::{EntityConfig, EntityId};
+use synthetic_consciousness::{Simulation, SimulationConfig};
+
+/// A simulation some way into its run, with 40 more steps to compare.
+fn simulation() -> Simulation {
+    let mut config = SimulationConfig::default_2d();
+    config.simulation.num_entities = 8;
+    config.simulation.num_steps = 40;
+    let mut sim = Simulation::new(config).unwrap();
+    for _ in 0..15 {
+        sim.step();
+    }
+    sim
+}
+
+#[test]
+fn empty_intervention_reproduces_the_baseline_bitwise() {
+    let sim = simulation();
+    let empty = EntityConfig { id: 3, ..Default::default() };
+    let result = sim.run_counterfactual(EntityId(3), empty).unwrap();
+
+    assert_eq!(result.baseline_metrics.len(), 40);
+    // Serialized floats round-trip exactly, so equal text is equal bits
+    assert_eq!(
+        serde_json::to_string(&result.counterfactual_metrics).unwrap(),
+        serde_json::to_string(&result.baseline_metrics).unwrap()
+    );
+    assert!(result.metric_deltas.values().all(|delta| delta.to_bits() == 0), "{:?}", result.metric_deltas);
+    assert_eq!(result.counterfactual_consciousness_achieved, result.baseline_consciousness_achieved);
+    // The simulation itself did not move
+    assert_eq!(sim.timestamp, 15);
+}
+
+#[test]
+fn interventions_change_the_run_and_need_an_entity() {
+    let sim = simulation();
+    let silenced = EntityConfig { id: 3, attraction_weight: Some(0.0), ..Default::default() };
+    let result = sim.run_counterfactual(EntityId(3), silenced.clone()).unwrap();
+    assert_ne!(
+        serde_json::to_string(&result.counterfactual_metrics).unwrap(),
+        serde_json::to_string(&result.baseline_metrics).unwrap()
+    );
+    assert!(result.metric_deltas.values().any(|delta| *delta != 0.0));
+
+    assert!(sim.run_counterfactual(EntityId(99), silenced).unwrap_err().contains("99"));
+    let invalid = EntityConfig { id: 3, mass: Some(-1.0), ..Default::default() };
+    assert!(sim.run_counterfactual(EntityId(3), invalid).is_err());
+}
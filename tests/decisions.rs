@@ -0,0 +1,99 @@
+//! Checks that decisions drive motion: an entity's action becomes the force
+//! integrated into its velocity, so without drives nothing but the minimum
+//! speed moves it, entities with different drives or essence move
+//! differently, and pinning essence at baseline (no influence, so no
+//! decided action) changes the trajectories of a whole population.
+//!
+//! ```bash
+//! cargo test --test decisions
+//! ```
+
+use synthetic_consciousness::{Entity, Real, Simulation, SimulationConfig};
+
+const STEPS: u32 = 40;
+
+/// A lone entity (no attraction field) remembering two kinds of pleasant
+/// event (two belief clusters), with equal drives and the given essence,
+/// stepped [`STEPS`] times; returns its velocity after each step. Action
+/// forces are left unbounded so their magnitude shows.
+fn velocities(drive: Real, essence: Real) -> (Simulation, Vec<Vec<Real>>) {
+    let builder = Simulation::builder().entities(0).steps(STEPS).configure(|config| {
+        config.drives.responsiveness = 0.0;
+        config.dynamics.max_action_force = Real::INFINITY;
+    });
+    let entity = Entity::builder(2)
+        .position(vec![5.0, 5.0])
+        .drives(drive, drive)
+        .essence(essence)
+        .with_memory_events(vec![vec![0.9, 0.2], vec![0.9, -3.0], vec![0.9, 0.2], vec![0.9, -3.0]])
+        .build(builder.config())
+        .unwrap();
+    let mut sim = builder.with_entity(entity).build().unwrap();
+    let mut velocities = Vec::new();
+    for _ in 0..STEPS {
+        sim.step();
+        velocities.push(sim.entities.iter().next().unwrap().velocity.to_vec());
+    }
+    (sim, velocities)
+}
+
+#[test]
+fn zeroed_drives_stop_directed_motion() {
+    let (sim, velocities) = velocities(0.0, 9.0);
+    let entity = sim.entities.iter().next().unwrap();
+    assert!(entity.state.memory.iter().any(|&m| m != 0.0), "the memory left no trace in the state");
+    assert!(entity.last_action.iter().all(|&a| a == 0.0));
+
+    // Only the minimum speed keeps it going, always along the first axis
+    let min_speed = sim.config.dynamics.min_speed;
+    for velocity in velocities {
+        assert!((velocity[0] - min_speed).abs() < 1e-6 && velocity[1] == 0.0, "velocity {:?}", velocity);
+    }
+}
+
+#[test]
+fn drives_and_essence_steer_the_entity() {
+    let (sim, driven) = velocities(0.5, 9.0);
+    let entity = sim.entities.iter().next().unwrap();
+    assert!(entity.last_action.iter().any(|&a| a != 0.0));
+    assert!(driven.iter().any(|velocity| velocity[1] != 0.0), "the entity never turned");
+
+    // Stronger drives or a more extreme essence push harder
+    assert_ne!(velocities(1.0, 9.0).1, driven);
+    assert_ne!(velocities(0.5, 7.0).1, driven);
+}
+
+#[test]
+fn pinning_essence_at_baseline_changes_trajectories() {
+    // Without experience, essence never leaves baseline and decide() is zero.
+    // Stimuli strong enough to carry valence let essence move otherwise.
+    let run = |experience_scale: Real| {
+        let mut config = SimulationConfig::default_2d();
+        config.simulation.num_entities = 20;
+        config.simulation.num_steps = 200;
+        config.essence.experience_scale = experience_scale;
+        let mut sim = Simulation::new(config).unwrap();
+        for entity in sim.entities.iter_mut() {
+            entity.stimulus_amplitude = 1.0;
+        }
+        let start: Vec<Vec<Real>> = sim.entities.iter().map(|e| e.pose.position.to_vec()).collect();
+        sim.run();
+        (sim, start)
+    };
+    let ((full, full_start), (pinned, pinned_start)) = (run(1.0), run(0.0));
+
+    assert!(pinned.entities.iter().all(|e| e.essence.influence_factor() == 0.0));
+    assert!(pinned.entities.iter().all(|e| e.last_action.iter().all(|&a| a == 0.0)));
+    assert!(full.entities.iter().any(|e| e.last_action.iter().any(|&a| a != 0.0)));
+
+    // Same seed and start, so every difference is the decided action's
+    assert_eq!(full_start, pinned_start);
+    let drift: Real = full
+        .entities
+        .iter()
+        .zip(pinned.entities.iter())
+        .map(|(a, b)| a.pose.distance_to(&b.pose))
+        .sum::<Real>()
+        / 20.0;
+    assert!(drift > 0.01, "trajectories only drifted {} apart on average", drift);
+}
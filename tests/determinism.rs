@@ -0,0 +1,95 @@
+//! Runs the same configuration twice and checks the runs are identical,
+//! and that the seed is what tells runs apart.
+//!
+//! ```bash
+//! cargo test --test determinism
+//! ```
+
+use rand::Rng;
+use std::path::PathBuf;
+use synthetic_consciousness::simulation::RngState;
+use synthetic_consciousness::{Simulation, SimulationConfig};
+
+fn config(seed: u64) -> SimulationConfig {
+    let mut config = SimulationConfig::default_2d();
+    config.simulation.num_entities = 6;
+    config.simulation.num_steps = 40;
+    config.simulation.seed = seed;
+    config.signaling.enabled = true;
+    config
+}
+
+fn temp_file(name: &str) -> PathBuf {
+    std::env::temp_dir().join(format!("sc_determinism_{}_{}", std::process::id(), name))
+}
+
+/// Run a configuration to the end; returns the simulation and its metrics CSV.
+fn run(config: SimulationConfig, name: &str) -> (Simulation, String) {
+    let mut sim = Simulation::new(config).unwrap();
+    sim.run();
+    sim.finalize_results();
+    let path = temp_file(name);
+    sim.export_metrics_csv(path.to_str().unwrap()).unwrap();
+    let csv = std::fs::read_to_string(&path).unwrap();
+    std::fs::remove_file(path).unwrap();
+    (sim, csv)
+}
+
+/// Results as JSON without the fields that legitimately differ between runs.
+fn comparable_results(sim: &Simulation) -> serde_json::Value {
+    let mut results = serde_json::to_value(&sim.results).unwrap();
+    let object = results.as_object_mut().unwrap();
+    for volatile in ["start_time", "end_time", "wall_clock_seconds"] {
+        object.remove(volatile);
+    }
+    results
+}
+
+#[test]
+fn same_seed_gives_identical_metrics_and_results() {
+    let (first, first_csv) = run(config(7), "first.csv");
+    let (second, second_csv) = run(config(7), "second.csv");
+
+    assert_eq!(first_csv, second_csv);
+    assert_eq!(comparable_results(&first), comparable_results(&second));
+    assert_eq!(first.rng_state(), second.rng_state());
+    assert_eq!(first.results.seed, 7);
+}
+
+#[test]
+fn different_seeds_give_different_runs() {
+    let (_, first_csv) = run(config(1), "seed1.csv");
+    let (_, second_csv) = run(config(2), "seed2.csv");
+    assert_ne!(first_csv, second_csv);
+}
+
+#[test]
+fn rng_state_advances_and_restores_the_stream() {
+    let mut sim = Simulation::new(config(11)).unwrap();
+    let created = sim.rng_state();
+    assert_eq!(created.seed, 11);
+    assert_eq!(sim.seed(), 11);
+    // Placement and innate drives already drew from the stream
+    assert!(created.word_pos > 0);
+
+    sim.step();
+    let stepped = sim.rng_state();
+    assert!(stepped.word_pos > created.word_pos);
+
+    // A generator rebuilt from a state continues the stream from there
+    let mut from_start = RngState { seed: 11, word_pos: 0 }.to_rng();
+    let skipped: Vec<u32> = (0..4).map(|_| from_start.gen()).collect();
+    let mut resumed = RngState { seed: 11, word_pos: 2 }.to_rng();
+    assert_eq!(resumed.gen::<u32>(), skipped[2]);
+    assert_eq!(stepped.to_rng().get_word_pos(), stepped.word_pos);
+}
+
+#[test]
+fn seed_is_written_to_the_reports() {
+    let (sim, _) = run(config(99), "report.csv");
+    let path = temp_file("report.md");
+    sim.results.generate_markdown_report(path.to_str().unwrap()).unwrap();
+    let report = std::fs::read_to_string(&path).unwrap();
+    std::fs::remove_file(path).unwrap();
+    assert!(report.contains("- **Seed:** 99"), "{}", report);
+}
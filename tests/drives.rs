@@ -0,0 +1,58 @@
+//! Checks that drives respond to the situation: entities in a crowd develop
+//! a higher self-preservation drive than isolated ones with the same innate
+//! drives, and without responsiveness the innate drives are kept.
+//!
+//! ```bash
+//! cargo test --test drives
+//! ```
+
+use synthetic_consciousness::{Entity, Real, Simulation};
+
+const CROWD: [[Real; 2]; 4] = [[2.0, 2.0], [2.3, 2.0], [2.0, 2.3], [2.3, 2.3]];
+const ISOLATED: [[Real; 2]; 2] = [[7.0, 7.0], [7.0, 2.0]];
+
+/// A tight crowd and two loners with equal innate drives, stepped a while;
+/// returns the mean preservation drive of (crowd, loners).
+fn preservation(responsiveness: Real) -> (Real, Real) {
+    let builder = Simulation::builder().entities(0).steps(20).configure(|config| {
+        config.drives.responsiveness = responsiveness;
+        config.dynamics.min_speed = 0.0;
+    });
+    let entities: Vec<Entity> = CROWD
+        .iter()
+        .chain(&ISOLATED)
+        .map(|position| {
+            Entity::builder(2)
+                .position(position.to_vec())
+                .velocity(vec![0.0, 0.0])
+                .drives(0.5, 0.5)
+                .build(builder.config())
+                .unwrap()
+        })
+        .collect();
+    let mut sim = entities.into_iter().fold(builder, |b, e| b.with_entity(e)).build().unwrap();
+    sim.run();
+
+    let mean = |entities: &[&Entity]| {
+        entities.iter().map(|e| e.baseline_drives.0).sum::<Real>() / entities.len() as Real
+    };
+    let entities: Vec<&Entity> = sim.entities.iter().collect();
+    let (crowd, loners) = entities.split_at(CROWD.len());
+    (mean(crowd), mean(loners))
+}
+
+#[test]
+fn crowded_entities_develop_higher_preservation() {
+    let (crowd, loners) = preservation(1.0);
+    assert!(crowd > loners, "crowd preservation {} vs isolated {}", crowd, loners);
+    // Loners stay a few units from anyone, so they barely rise above innate
+    assert!(loners < 0.5 + 0.5);
+    assert!(crowd > 0.5 + 1.0);
+}
+
+#[test]
+fn unresponsive_entities_keep_their_innate_drives() {
+    let (crowd, loners) = preservation(0.0);
+    assert_eq!(crowd, 0.5);
+    assert_eq!(loners, 0.5);
+}
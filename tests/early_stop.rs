@@ -0,0 +1,142 @@
+//! Runs simulations until the consciousness criteria are sustained and
+//! checks the early stop, the recorded first-achievement step, the shared
+//! thresholds and re-analysis of saved results against other thresholds.
+//!
+//! ```bash
+//! cargo test --test early_stop
+//! ```
+
+use synthetic_consciousness::plugin::StimulusSource;
+use synthetic_consciousness::results::{
+    consciousness_threshold, meets_consciousness_thresholds, SimulationResults, CONSCIOUSNESS_THRESHOLDS,
+};
+use synthetic_consciousness::{Entity, Real, Simulation, SimulationConfig};
+
+/// Strong stimuli cycling through positive, negative and neutral valence,
+/// which lets entities form clusters with distinct affect.
+struct Valenced;
+
+impl StimulusSource for Valenced {
+    fn stimulus(&mut self, entity: &Entity, step: u64) -> Option<Vec<Real>> {
+        let valence = [1.0, -1.0, 0.0][((step + u64::from(entity.id.0)) % 3) as usize];
+        Some(vec![valence, 0.3 * valence])
+    }
+}
+
+fn simulation(valenced: bool) -> Simulation {
+    let mut config = SimulationConfig::default_2d();
+    config.simulation.num_entities = 4;
+    config.simulation.num_steps = 300;
+    let mut sim = Simulation::new(config).unwrap();
+    if valenced {
+        sim.set_stimulus_source(Box::new(Valenced));
+    }
+    sim
+}
+
+fn report(sim: &Simulation, name: &str) -> String {
+    let path = std::env::temp_dir().join(format!("sc_early_stop_{}_{}", std::process::id(), name));
+    sim.results.generate_markdown_report(path.to_str().unwrap()).unwrap();
+    let report = std::fs::read_to_string(&path).unwrap();
+    std::fs::remove_file(path).unwrap();
+    report
+}
+
+#[test]
+fn stops_once_the_criteria_are_sustained() {
+    let mut sim = simulation(true);
+    let run = sim.run_until_conscious(300, 10);
+
+    assert!(run.stopped_early);
+    let from = run.sustained_from.unwrap();
+    assert_eq!(run.stopped_at, from + 10);
+    assert_eq!(sim.timestamp, run.stopped_at);
+    let window = &sim.metrics_history[from as usize..];
+    assert_eq!(window.len(), 10);
+    assert!(window.iter().all(meets_consciousness_thresholds));
+    if from > 0 {
+        assert!(!meets_consciousness_thresholds(&sim.metrics_history[from as usize - 1]));
+    }
+
+    sim.finalize_results();
+    let analysis = &sim.results.consciousness_analysis;
+    assert!(analysis.consciousness_achieved);
+    let first = analysis.first_achieved_step.unwrap();
+    assert!(first <= from);
+    assert!(sim.metrics_history[..first as usize].iter().all(|m| !meets_consciousness_thresholds(m)));
+    assert!(report(&sim, "sustained.md").contains(&format!("**First achieved:** step {} of {}", first, sim.timestamp)));
+}
+
+#[test]
+fn runs_to_the_limit_when_the_criteria_are_never_met() {
+    let mut sim = simulation(false);
+    let run = sim.run_until_conscious(40, 5);
+    assert!(!run.stopped_early);
+    assert_eq!((run.stopped_at, run.sustained_from), (40, None));
+
+    sim.finalize_results();
+    let analysis = &sim.results.consciousness_analysis;
+    assert_eq!(analysis.first_achieved_step, None);
+    assert!(report(&sim, "never.md").contains("**First achieved:** never"));
+
+    // Failure messages quote the shared thresholds
+    let affect = analysis
+        .failed_metrics
+        .iter()
+        .find(|failure| failure.starts_with("Affective Strength: "))
+        .unwrap();
+    assert!(affect.ends_with("< 0.01"), "{}", affect);
+}
+
+#[test]
+fn thresholds_come_from_one_table() {
+    assert_eq!(consciousness_threshold("attention_entropy"), Some(2.0));
+    assert_eq!(consciousness_threshold("average_essence"), None);
+
+    let mut sim = simulation(false);
+    sim.run_until_conscious(5, 1);
+    sim.finalize_results();
+    let thresholds = &sim.results.consciousness_analysis.metric_thresholds;
+    assert_eq!(thresholds.len(), CONSCIOUSNESS_THRESHOLDS.len());
+    for (name, threshold) in CONSCIOUSNESS_THRESHOLDS {
+        assert_eq!(thresholds[name], threshold);
+    }
+}
+
+#[test]
+fn saved_results_are_reanalyzed_with_other_thresholds() {
+    let mut sim = simulation(true);
+    sim.run_until_conscious(300, 10);
+    sim.finalize_results();
+    let path = std::env::temp_dir().join(format!("sc_early_stop_{}_results.json", std::process::id()));
+    sim.results.save_json(path.to_str().unwrap()).unwrap();
+    let mut loaded = SimulationResults::load(path.to_str().unwrap()).unwrap();
+    std::fs::remove_file(path).unwrap();
+
+    // The default criteria give the original verdict
+    loaded.analyze_consciousness();
+    let original = &sim.results.consciousness_analysis;
+    assert!(loaded.consciousness_analysis.consciousness_achieved);
+    assert_eq!(loaded.consciousness_analysis.first_achieved_step, original.first_achieved_step);
+
+    // A stricter entropy criterion fails, and the reports quote it
+    let mut strict = CONSCIOUSNESS_THRESHOLDS;
+    strict[0].1 = 100.0;
+    loaded.analyze_consciousness_with(&strict);
+    let analysis = &loaded.consciousness_analysis;
+    assert!(!analysis.consciousness_achieved);
+    assert_eq!(analysis.first_achieved_step, None);
+    assert_eq!(analysis.metric_thresholds["attention_entropy"], 100.0);
+    assert_eq!(analysis.failed_metrics.len(), 1);
+    assert!(analysis.failed_metrics[0].starts_with("Attention Entropy: "));
+    let text = std::env::temp_dir().join(format!("sc_early_stop_{}_strict.txt", std::process::id()));
+    loaded.generate_text_report(text.to_str().unwrap()).unwrap();
+    let report = std::fs::read_to_string(&text).unwrap();
+    std::fs::remove_file(text).unwrap();
+    assert!(report.contains("(threshold: ≥100.0)"));
+
+    // Criteria may cover only some metrics
+    loaded.analyze_consciousness_with(&[("velocity_stability", 0.0)]);
+    assert!(loaded.consciousness_analysis.consciousness_achieved);
+    assert_eq!(loaded.consciousness_analysis.first_achieved_step, Some(0));
+}
@@ -0,0 +1,188 @@
+//! Runs with the metabolism enabled and disabled and checks that motion
+//! drains energy, that the ledger balances at every step, that exhausted
+//! entities may come to rest and fall dormant, and that mean energy is
+//! surfaced in the metrics and the report.
+//!
+//! ```bash
+//! cargo test --test energy
+//! ```
+
+use synthetic_consciousness::energy::EnergyConfig;
+use synthetic_consciousness::{Entity, Real, Simulation, SimulationConfig};
+
+fn config(enabled: bool) -> SimulationConfig {
+    let mut config = SimulationConfig::default_2d();
+    config.simulation.num_entities = 6;
+    config.simulation.num_steps = 60;
+    config.energy.enabled = enabled;
+    config.energy.speed_drain = 50.0;
+    config.energy.replenish_rate = 0.1;
+    config
+}
+
+fn speed(velocity: &[Real]) -> Real {
+    velocity.iter().map(|v| v * v).sum::<Real>().sqrt()
+}
+
+#[test]
+fn energy_is_constant_unless_enabled() {
+    let mut sim = Simulation::new(config(false)).unwrap();
+    sim.run();
+    assert!(sim.entities.iter().all(|e| e.energy == 100.0 && e.energy_spent == 0.0));
+    assert!(sim.metrics_history.iter().all(|m| m.mean_energy == 100.0));
+}
+
+#[test]
+fn motion_drains_energy_and_the_ledger_balances() {
+    let mut sim = Simulation::new(config(true)).unwrap();
+    sim.run();
+
+    let entities: Vec<_> = sim.entities.iter().collect();
+    assert!(entities.iter().all(|e| e.energy_spent > 0.0));
+    for entity in &entities {
+        let balance = 100.0 + entity.energy_gained - entity.energy_spent;
+        assert!((entity.energy - balance).abs() < 1e-3);
+        assert!((0.0..=100.0).contains(&entity.energy));
+    }
+    let mean = entities.iter().map(|e| e.energy).sum::<Real>() / entities.len() as Real;
+    assert!((sim.metrics_history.last().unwrap().mean_energy - mean).abs() < 1e-3);
+    assert!(sim.metrics_history.last().unwrap().mean_energy < 100.0);
+}
+
+#[test]
+fn energy_is_conserved_at_every_step() {
+    let mut config = config(true);
+    config.energy.action_drain = 5.0;
+    config.energy.replenish_rate = 2.0;
+    config.energy.capacity = 120.0;
+    let mut sim = Simulation::new(config).unwrap();
+    // Strong stimuli make for valenced memories, and so replenishment
+    for entity in sim.entities.iter_mut() {
+        entity.stimulus_amplitude = 1.0;
+    }
+    for _ in 0..60 {
+        sim.step();
+        for entity in sim.entities.iter() {
+            let balance = 100.0 + entity.energy_gained - entity.energy_spent;
+            assert!((entity.energy - balance).abs() < 1e-3, "entity {} at step {}", entity.id.0, sim.timestamp);
+            assert!((0.0..=120.0).contains(&entity.energy));
+        }
+    }
+    // Both flows were exercised
+    assert!(sim.entities.iter().any(|e| e.energy_gained > 0.0));
+    assert!(sim.entities.iter().all(|e| e.energy_spent > 0.0));
+}
+
+#[test]
+fn one_metabolic_step_moves_exactly_the_cost() {
+    let sim_config = SimulationConfig::default_2d();
+    let mut entity = Entity::builder(2).velocity(vec![0.3, 0.4]).build(&sim_config).unwrap();
+    let energy = EnergyConfig { enabled: true, speed_drain: 10.0, action_drain: 2.0, ..EnergyConfig::default() };
+    // 10 · |v|² + 2 · 0.5 = 10 · 0.25 + 1
+    entity.metabolize(0.5, &energy);
+    assert!((entity.energy - 96.5).abs() < 1e-5);
+    assert!((entity.energy_spent - 3.5).abs() < 1e-5);
+    assert_eq!(entity.energy_gained, 0.0);
+
+    // The cost beyond the reserve is not booked
+    entity.energy = 1.0;
+    entity.energy_spent = 0.0;
+    entity.metabolize(0.5, &energy);
+    assert_eq!((entity.energy, entity.energy_spent), (0.0, 1.0));
+    assert!(entity.is_exhausted());
+}
+
+#[test]
+fn exhausted_entities_fall_dormant_and_wake_when_replenished() {
+    let sim_config = SimulationConfig::default_2d();
+    let mut entity = Entity::builder(2).velocity(vec![0.0, 0.0]).build(&sim_config).unwrap();
+    entity.attraction_weight = 1.0;
+    entity.energy = 0.0;
+    let energy = EnergyConfig { enabled: true, dormancy_steps: 3, ..EnergyConfig::default() };
+
+    for steps in 1..=2 {
+        entity.metabolize(0.0, &energy);
+        assert_eq!(entity.exhausted_steps, steps);
+        assert!(!entity.dormant);
+        assert_eq!(entity.emitted_attraction_weight(), 1.0);
+    }
+    entity.metabolize(0.0, &energy);
+    assert!(entity.dormant);
+    assert_eq!(entity.emitted_attraction_weight(), 0.0);
+    entity.metabolize(0.0, &energy);
+    assert!(entity.dormant && entity.exhausted_steps == 4);
+
+    // Any energy ends exhaustion and dormancy at once
+    entity.energy = 5.0;
+    entity.metabolize(0.0, &energy);
+    assert!(!entity.dormant);
+    assert_eq!(entity.exhausted_steps, 0);
+    assert_eq!(entity.emitted_attraction_weight(), 1.0);
+
+    // Without dormancy steps, exhaustion never leads to dormancy
+    let never = EnergyConfig { dormancy_steps: 0, ..energy };
+    entity.energy = 0.0;
+    for _ in 0..10 {
+        entity.metabolize(0.0, &never);
+    }
+    assert!(entity.is_exhausted() && !entity.dormant);
+}
+
+#[test]
+fn dormant_populations_stop_attracting() {
+    let mut config = config(true);
+    config.energy.initial = 0.0;
+    config.energy.replenish_rate = 0.0;
+    config.energy.dormancy_steps = 5;
+    config.simulation.num_steps = 20;
+    let mut sim = Simulation::new(config).unwrap();
+    for _ in 0..4 {
+        sim.step();
+    }
+    assert!(sim.entities.iter().all(|e| !e.dormant));
+    sim.step();
+    assert!(sim.entities.iter().all(|e| e.dormant));
+    sim.run();
+    assert!(sim.attention_matrix().iter().flatten().all(|strength| *strength == 0.0));
+    assert!(sim.results.steps.last().unwrap().attractions.is_empty());
+}
+
+#[test]
+fn exhausted_entities_are_not_held_at_the_minimum_speed() {
+    let exhausted = |enabled: bool| {
+        let mut config = config(enabled);
+        config.energy.initial = 0.0;
+        config.energy.replenish_rate = 0.0;
+        config.dynamics.damping = 0.8;
+        let mut sim = Simulation::new(config).unwrap();
+        sim.run();
+        sim
+    };
+
+    let resting = exhausted(true);
+    let min_speed = resting.config.dynamics.min_speed;
+    assert!(resting.entities.iter().all(|e| e.is_exhausted() && speed(&e.velocity) < min_speed));
+
+    // Without the metabolism the minimum speed holds regardless of energy
+    let moving = exhausted(false);
+    assert!(moving.entities.iter().all(|e| speed(&e.velocity) >= min_speed * 0.999));
+}
+
+#[test]
+fn reports_show_energy_and_bad_rates_are_rejected() {
+    let mut sim = Simulation::new(config(true)).unwrap();
+    sim.run();
+    sim.finalize_results();
+    let path = std::env::temp_dir().join(format!("sc_energy_{}.md", std::process::id()));
+    sim.results.generate_markdown_report(path.to_str().unwrap()).unwrap();
+    let report = std::fs::read_to_string(&path).unwrap();
+    std::fs::remove_file(path).unwrap();
+    assert!(report.contains("## Energy"));
+    assert!(report.contains("| Start | 0 | "));
+    assert!(report.contains("| End | 59 | "));
+
+    let mut config = config(true);
+    config.energy.speed_drain = -1.0;
+    assert!(config.validate().is_err());
+    assert_eq!(config.problems()[0].path, "energy.speed_drain");
+}
@@ -0,0 +1,95 @@
+//! Runs one configuration under several seeds and checks the aggregated
+//! statistics against the individual runs, that parallel and sequential
+//! ensembles agree, and the JSON and text outputs.
+//!
+//! ```bash
+//! cargo test --test ensemble
+//! ```
+
+use synthetic_consciousness::ensemble::{run_ensemble, run_ensemble_with_jobs, EnsembleResults, SummaryStats};
+use synthetic_consciousness::{Metrics, Simulation, SimulationConfig};
+
+const SEEDS: [u64; 4] = [1, 2, 3, 4];
+
+fn config() -> SimulationConfig {
+    let mut config = SimulationConfig::default_2d();
+    config.simulation.num_entities = 8;
+    config.simulation.num_steps = 40;
+    config
+}
+
+#[test]
+fn summary_stats_use_the_sample_deviation() {
+    let stats = SummaryStats::of("x", &[2.0, 4.0, 4.0, 4.0, 5.0, 5.0, 7.0, 9.0]);
+    assert_eq!((stats.mean, stats.min, stats.max), (5.0, 2.0, 9.0));
+    assert!((stats.std - 2.138).abs() < 1e-3);
+
+    let single = SummaryStats::of("x", &[3.0]);
+    assert_eq!((single.mean, single.std), (3.0, 0.0));
+    assert_eq!(SummaryStats::of("x", &[]), SummaryStats { name: "x".to_string(), ..Default::default() });
+}
+
+#[test]
+fn statistics_aggregate_the_individual_runs() {
+    let ensemble = run_ensemble(&config(), &SEEDS).unwrap();
+    assert_eq!(ensemble.runs.iter().map(|run| run.seed).collect::<Vec<_>>(), SEEDS);
+    assert_eq!(ensemble.metrics.len(), Metrics::FIELD_NAMES.len());
+
+    // Each run matches a simulation created with its seed
+    let mut finals = Vec::new();
+    for (run, seed) in ensemble.runs.iter().zip(SEEDS) {
+        let mut config = config();
+        config.simulation.seed = seed;
+        let mut sim = Simulation::new(config).unwrap();
+        sim.run();
+        sim.finalize_results();
+        assert_eq!(run.steps, 40);
+        assert_eq!(run.consciousness_score, sim.results.consciousness_analysis.consciousness_score);
+        finals.push(sim.metrics_history.last().unwrap().average_essence);
+    }
+
+    let essence = ensemble.metric("average_essence").unwrap();
+    assert_eq!(*essence, SummaryStats::of("average_essence", &finals));
+    assert!(essence.min <= essence.mean && essence.mean <= essence.max);
+
+    let achieved = ensemble.runs.iter().filter(|run| run.consciousness_achieved).count();
+    assert_eq!(ensemble.consciousness_fraction, achieved as synthetic_consciousness::Real / 4.0);
+}
+
+#[test]
+fn parallel_runs_match_sequential_ones() {
+    let sequential = run_ensemble(&config(), &SEEDS).unwrap();
+    let parallel = run_ensemble_with_jobs(&config(), &SEEDS, 3).unwrap();
+    assert_eq!(parallel.runs, sequential.runs);
+    assert_eq!(parallel.metrics, sequential.metrics);
+}
+
+#[test]
+fn invalid_configurations_are_reported() {
+    let mut config = config();
+    config.geometry.dimension = 5;
+    assert!(run_ensemble(&config, &SEEDS).is_err());
+}
+
+#[test]
+fn json_and_text_report() {
+    let ensemble = run_ensemble(&config(), &SEEDS[..2]).unwrap();
+    let dir = std::env::temp_dir();
+    let json = dir.join(format!("sc_ensemble_{}.json", std::process::id()));
+    let text = dir.join(format!("sc_ensemble_{}.txt", std::process::id()));
+    ensemble.save_json(json.to_str().unwrap()).unwrap();
+    ensemble.generate_text_report(text.to_str().unwrap()).unwrap();
+
+    let back: EnsembleResults = serde_json::from_str(&std::fs::read_to_string(&json).unwrap()).unwrap();
+    let report = std::fs::read_to_string(&text).unwrap();
+    std::fs::remove_file(json).unwrap();
+    std::fs::remove_file(text).unwrap();
+
+    assert_eq!(back.runs, ensemble.runs);
+    assert_eq!(back.consciousness_fraction, ensemble.consciousness_fraction);
+    assert!(report.contains("ENSEMBLE STATISTICS"));
+    assert!(report.contains("Runs:                2"));
+    for name in Metrics::FIELD_NAMES {
+        assert!(report.lines().any(|line| line.starts_with(name)), "no {} row", name);
+    }
+}
@@ -0,0 +1,80 @@
+//! Checks that the entity pool is iterated in id order however it was
+//! built, so recordings and the visualization state line up between runs
+//! and between calls.
+//!
+//! ```bash
+//! cargo test --test entity_order
+//! ```
+
+use synthetic_consciousness::entities::{EntityId, EntityPool};
+use synthetic_consciousness::{Simulation, SimulationConfig};
+
+fn simulation(entities: u32) -> Simulation {
+    let mut config = SimulationConfig::default_2d();
+    config.simulation.num_entities = entities;
+    config.simulation.num_steps = 30;
+    Simulation::new(config).unwrap()
+}
+
+fn ids(pool: &EntityPool) -> Vec<u32> {
+    pool.iter().map(|e| e.id.0).collect()
+}
+
+#[test]
+fn pools_iterate_in_id_order() {
+    let mut sim = simulation(40);
+    assert_eq!(ids(&sim.entities), (1..=40).collect::<Vec<_>>());
+
+    // Removals and additions keep the order
+    for id in [3, 17, 28] {
+        sim.remove_entity(EntityId(id));
+    }
+    sim.spawn_entity(None).unwrap();
+    let expected: Vec<u32> = (1..=41).filter(|id| ![3, 17, 28].contains(id)).collect();
+    assert_eq!(ids(&sim.entities), expected);
+    assert_eq!(sim.entities.all_entities().iter().map(|e| e.id.0).collect::<Vec<_>>(), expected);
+    assert_eq!(sim.entities.all_entities_mut().iter().map(|e| e.id.0).collect::<Vec<_>>(), expected);
+    assert_eq!(sim.entities.iter_mut().map(|e| e.id.0).collect::<Vec<_>>(), expected);
+
+    // As does a round trip
+    let json = serde_json::to_string(&sim.entities).unwrap();
+    let loaded: EntityPool = serde_json::from_str(&json).unwrap();
+    assert_eq!(ids(&loaded), expected);
+}
+
+#[test]
+fn identical_runs_record_identical_steps() {
+    let run = || {
+        let mut sim = simulation(25);
+        sim.run();
+        sim.finalize_results();
+        let step = sim.results.steps.last().unwrap();
+        (step.attractions.clone(), step.entity_positions.clone(), sim.metrics_history.last().unwrap().field_values())
+    };
+    assert_eq!(run(), run());
+}
+
+#[cfg(feature = "gui")]
+#[test]
+fn visualization_attractions_index_the_entities_shown() {
+    use std::sync::{Arc, Mutex};
+    use synthetic_consciousness::visualization::VisualizationState;
+
+    let mut sim = simulation(30);
+    for _ in 0..10 {
+        sim.step();
+    }
+    let state = Arc::new(Mutex::new(VisualizationState::default()));
+    sim.update_visualization(&state);
+
+    let state = state.lock().unwrap();
+    let shown: Vec<u32> = state.entities.iter().map(|e| e.id).collect();
+    assert_eq!(shown, ids(&sim.entities));
+    let cutoff = synthetic_consciousness::real::to_f32(sim.config.attraction.effective_cutoff());
+    assert!(!state.attractions.is_empty());
+    for &(a, b, _) in &state.attractions {
+        let (a, b) = (&state.entities[a], &state.entities[b]);
+        let distance: f32 = a.position.iter().zip(&b.position).map(|(x, y)| (x - y).powi(2)).sum::<f32>().sqrt();
+        assert!(distance <= cutoff + 1e-4, "entities {} and {} are {} apart", a.id, b.id, distance);
+    }
+}
@@ -0,0 +1,121 @@
+//! Places fixed attractors, repellers and obstacles in the world and checks
+//! their pull on entities, that obstacles keep entities out, and that
+//! environments are parsed and validated.
+//!
+//! ```bash
+//! cargo test --test environment
+//! ```
+
+use synthetic_consciousness::environment::EnvironmentPoint;
+use synthetic_consciousness::{Entity, Real, Simulation, SimulationConfig};
+
+fn point(position: [Real; 2], radius: Real, strength: Real, obstacle: bool) -> EnvironmentPoint {
+    EnvironmentPoint { position: position.to_vec(), radius, strength, obstacle }
+}
+
+fn distance(a: &[Real], b: &[Real]) -> Real {
+    a.iter().zip(b).map(|(x, y)| (x - y).powi(2)).sum::<Real>().sqrt()
+}
+
+/// A lone entity at `position` in a world with `points`.
+fn lone_entity(position: [Real; 2], points: Vec<EnvironmentPoint>) -> Simulation {
+    let builder = Simulation::builder()
+        .entities(0)
+        .steps(50)
+        .configure(|config| config.environment.points = points);
+    let entity = Entity::builder(2).position(position.to_vec()).build(builder.config()).unwrap();
+    builder.with_entity(entity).build().unwrap()
+}
+
+#[test]
+fn attractors_pull_and_repellers_push() {
+    for (strength, toward) in [(5.0, true), (-5.0, false)] {
+        let target = [6.0, 5.0];
+        // At rest and without drives, only the environment moves it
+        let builder = Simulation::builder().entities(0).configure(|config| {
+            config.environment.points = vec![point(target, 0.2, strength, false)];
+            config.dynamics.min_speed = 0.0;
+            config.drives.responsiveness = 0.0;
+        });
+        let entity = Entity::builder(2)
+            .position(vec![5.0, 5.0])
+            .velocity(vec![0.0, 0.0])
+            .drives(0.0, 0.0)
+            .build(builder.config())
+            .unwrap();
+        let mut sim = builder.with_entity(entity).build().unwrap();
+        sim.step();
+
+        // The stored prompt is -∇Φ_env, like the entities' own
+        let entity = sim.entities.iter().next().unwrap();
+        let along: Real = entity.attention_gradient.iter().zip([1.0, 0.0]).map(|(g, d)| g * d).sum();
+        assert_eq!(along < 0.0, toward, "strength {} prompted the wrong way", strength);
+
+        for _ in 1..30 {
+            sim.step();
+        }
+        let moved = distance(&sim.entities.iter().next().unwrap().pose.position, &target);
+        assert_eq!(moved < 1.0, toward, "strength {} left the entity {} from the point", strength, moved);
+    }
+
+    // Without points a lone entity feels no field
+    let mut alone = lone_entity([5.0, 5.0], Vec::new());
+    alone.step();
+    assert!(alone.entities.iter().next().unwrap().attention_gradient.iter().all(|g| *g == 0.0));
+}
+
+#[test]
+fn obstacles_keep_entities_out() {
+    let obstacle = point([5.0, 5.0], 1.5, 0.0, true);
+    let mut sim = lone_entity([5.2, 5.0], vec![obstacle.clone()]);
+    sim.step();
+    let entity = sim.entities.iter().next().unwrap();
+    assert!((distance(&entity.pose.position, &obstacle.position) - 1.5).abs() < 1e-4);
+
+    let mut config = SimulationConfig::default_2d();
+    config.simulation.num_entities = 20;
+    config.simulation.num_steps = 100;
+    config.environment.points = vec![obstacle.clone(), point([2.0, 8.0], 1.0, -1.0, true)];
+    let mut sim = Simulation::new(config).unwrap();
+    sim.run();
+    for step in &sim.results.steps {
+        for (_, position) in &step.entity_positions {
+            for obstacle in &sim.config.environment.points {
+                assert!(distance(position, &obstacle.position) >= obstacle.radius - 1e-4);
+            }
+        }
+    }
+}
+
+#[test]
+fn invalid_points_are_rejected() {
+    let with = |points: Vec<EnvironmentPoint>| {
+        let mut config = SimulationConfig::default_2d();
+        config.environment.points = points;
+        config
+    };
+
+    let line = EnvironmentPoint { position: vec![1.0], ..point([0.0, 0.0], 0.0, 1.0, false) };
+    let flat = with(vec![point([1.0, 1.0], 0.0, 1.0, false), line]);
+    assert_eq!(flat.validate().unwrap_err(), "Environment point 1: position must have 2 coordinates");
+    assert_eq!(flat.problems()[0].path, "environment.points[1]");
+
+    assert!(with(vec![point([1.0, 1.0], 0.0, 0.0, true)]).validate().is_err());
+    assert!(with(vec![point([1.0, 1.0], -1.0, 1.0, false)]).validate().is_err());
+    assert!(with(vec![point([1.0, 1.0], 1.0, -3.0, true)]).validate().is_ok());
+}
+
+#[test]
+fn environments_parse_from_toml() {
+    let text = format!(
+        "{}\n[[environment.points]]\nposition = [2.5, 2.5]\nradius = 0.5\nstrength = 2.0\n\n[[environment.points]]\nposition = [7.5, 5.0]\nradius = 1.0\nobstacle = true\n",
+        SimulationConfig::default_2d().canonical_toml().unwrap().replace("[environment]\npoints = []\n", "")
+    );
+    let config = SimulationConfig::parse(&text, None).unwrap();
+    assert_eq!(
+        config.environment.points,
+        [point([2.5, 2.5], 0.5, 2.0, false), point([7.5, 5.0], 1.0, 0.0, true)]
+    );
+    assert!(config.validate().is_ok());
+    assert!(config.summary().contains("1 attractors, 0 repellers, 1 obstacles"));
+}
@@ -0,0 +1,151 @@
+//! Records the event log while stepping and checks cluster births, essence
+//! extremes and boundary wraps against the run, the log's filters, and the
+//! event summaries of the reports.
+//!
+//! ```bash
+//! cargo test --test events
+//! ```
+
+use synthetic_consciousness::entities::EntityId;
+use synthetic_consciousness::events::{EventType, ESSENCE_CRISIS_LEVEL, ESSENCE_PEAK_LEVEL};
+use synthetic_consciousness::{Entity, Real, Simulation, SimulationConfig};
+
+fn config() -> SimulationConfig {
+    let mut config = SimulationConfig::default_2d();
+    config.simulation.num_entities = 20;
+    config.simulation.num_steps = 150;
+    config.recording.record_every = 1;
+    config
+}
+
+fn simulation() -> Simulation {
+    Simulation::new(config()).unwrap()
+}
+
+#[test]
+fn every_wrap_is_recorded() {
+    // Fast entities cross the world's edges often
+    let mut config = config();
+    config.dynamics.min_speed = 5.0;
+    let mut sim = Simulation::new(config).unwrap();
+    sim.run();
+    let bounds = sim.config.geometry.bounds.clone();
+
+    // A coordinate jumping by more than half the world wrapped
+    let mut jumps = Vec::new();
+    for pair in sim.results.steps.windows(2) {
+        for (id, position) in &pair[1].entity_positions {
+            let Some((_, before)) = pair[0].entity_positions.iter().find(|(other, _)| other == id) else {
+                continue;
+            };
+            for (axis, (x, b)) in position.iter().zip(before).enumerate() {
+                if (x - b).abs() > bounds[axis] / 2.0 {
+                    jumps.push((pair[1].step_number, *id, axis));
+                }
+            }
+        }
+    }
+    assert!(!jumps.is_empty(), "no entity crossed the edge");
+
+    let mut wraps: Vec<(u64, u32, usize)> = sim
+        .results
+        .events
+        .of_type("BoundaryWrap")
+        .filter(|e| e.step > 0)
+        .map(|e| match e.event_type {
+            EventType::BoundaryWrap(axis) => (e.step, e.entity_id.unwrap().0, axis),
+            _ => unreachable!(),
+        })
+        .collect();
+    jumps.sort();
+    wraps.sort();
+    assert_eq!(wraps, jumps);
+}
+
+#[test]
+fn cluster_births_follow_the_memory_graphs() {
+    let mut sim = simulation();
+    let initial: Vec<(EntityId, u32)> =
+        sim.entities.iter().map(|e| (e.id, e.memory_graph.next_cluster_id())).collect();
+    sim.run();
+
+    for (id, before) in initial {
+        let entity = sim.entities.get_entity(id).unwrap();
+        let born: Vec<&str> = sim
+            .results
+            .events
+            .for_entity(id)
+            .filter(|e| e.event_type == EventType::ClusterBorn)
+            .map(|e| e.details.as_str())
+            .collect();
+        assert_eq!(born.len() as u32, entity.memory_graph.next_cluster_id() - before);
+        assert!(born.first().is_none_or(|details| *details == format!("cluster {} formed", before)));
+    }
+
+    // The filters partition the log
+    let log = &sim.results.events;
+    assert_eq!(log.counts().values().sum::<usize>(), log.events.len());
+    assert_eq!(log.of_type("ClusterBorn").count(), log.counts()["ClusterBorn"]);
+    assert!(log.for_entity(EntityId(3)).all(|e| e.entity_id == Some(EntityId(3))));
+}
+
+#[test]
+fn essence_extremes_are_recorded_once_crossed() {
+    for (start, baseline, peak) in [(7.9, 10.0, true), (2.1, 0.0, false)] {
+        let builder = Simulation::builder().entities(0).steps(10).configure(|config| {
+            config.essence.baseline = baseline;
+            config.essence.decay = 0.5;
+            config.essence.experience_scale = 0.0;
+        });
+        let entity = Entity::builder(2).position(vec![5.0, 5.0]).essence(start).build(builder.config()).unwrap();
+        let mut sim = builder.with_entity(entity).build().unwrap();
+        sim.run();
+
+        let extremes: Vec<(u64, Real)> = sim
+            .results
+            .events
+            .events
+            .iter()
+            .filter_map(|e| match e.event_type {
+                EventType::EssencePeak(value) | EventType::EssenceCrisis(value) => Some((e.step, value)),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(extremes.len(), 1, "essence from {} toward {}", start, baseline);
+        let (step, value) = extremes[0];
+        assert_eq!(step, 0);
+        assert_eq!(value > ESSENCE_PEAK_LEVEL, peak);
+        assert_eq!(value < ESSENCE_CRISIS_LEVEL, !peak);
+    }
+}
+
+#[test]
+fn results_and_reports_carry_the_events() {
+    let mut sim = simulation();
+    let (results, log) = sim.run_with_event_log();
+    assert!(!results.events.is_empty());
+
+    // The tracker passes the simulation's own events on in order
+    let recorded: Vec<_> = log.of_type("ClusterBorn").map(|e| (e.step, e.entity_id)).collect();
+    let born: Vec<_> = results.events.of_type("ClusterBorn").map(|e| (e.step, e.entity_id)).collect();
+    assert_eq!(recorded, born);
+
+    let json = serde_json::to_string(&results).unwrap();
+    let back: synthetic_consciousness::SimulationResults = serde_json::from_str(&json).unwrap();
+    assert_eq!(back.events.events.len(), results.events.events.len());
+
+    let dir = std::env::temp_dir();
+    let text = dir.join(format!("sc_events_{}.txt", std::process::id()));
+    let html = dir.join(format!("sc_events_{}.html", std::process::id()));
+    results.generate_text_report(text.to_str().unwrap()).unwrap();
+    results.generate_html_report(html.to_str().unwrap()).unwrap();
+    let (text_report, html_report) =
+        (std::fs::read_to_string(&text).unwrap(), std::fs::read_to_string(&html).unwrap());
+    std::fs::remove_file(text).unwrap();
+    std::fs::remove_file(html).unwrap();
+
+    assert!(text_report.contains("EVENTS"));
+    assert!(text_report.contains("First notable events:"));
+    assert!(html_report.contains("<h2>Events</h2>"));
+    assert!(html_report.contains(&format!("<tr><td>ClusterBorn</td><td>{}</td></tr>", results.events.counts()["ClusterBorn"])));
+}
@@ -0,0 +1,63 @@
+//! Checks export format selection and the files `export_reports` writes.
+//!
+//! ```bash
+//! cargo test --test export
+//! ```
+
+use synthetic_consciousness::{ExportFormat, Simulation, SimulationConfig};
+
+#[test]
+fn format_lists_parse_in_order() {
+    assert_eq!(
+        ExportFormat::parse_list("json, MD,csv,json").unwrap(),
+        vec![ExportFormat::Json, ExportFormat::Md, ExportFormat::Csv]
+    );
+    assert_eq!(ExportFormat::parse_list("none").unwrap(), vec![]);
+}
+
+#[test]
+fn unknown_formats_list_the_valid_ones() {
+    let error = ExportFormat::parse_list("csv,xlsx").unwrap_err();
+    assert!(error.contains("'xlsx'"), "{}", error);
+    assert!(error.contains("csv, txt, html, json, md"), "{}", error);
+    assert!(error.contains("none"), "{}", error);
+}
+
+#[test]
+fn export_reports_writes_only_the_selected_formats() {
+    let dir = std::env::temp_dir().join(format!("sc_export_{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    std::env::set_current_dir(&dir).unwrap();
+
+    let mut config = SimulationConfig::default_2d();
+    config.simulation.num_steps = 5;
+    let mut sim = Simulation::new(config).unwrap();
+    sim.run();
+    sim.finalize_results();
+
+    let written = sim.export_reports("run", &[ExportFormat::Json, ExportFormat::Md]).unwrap();
+    let suffix = sim.fingerprint_suffix();
+    assert_eq!(
+        written,
+        vec![
+            (ExportFormat::Json, format!("run_results_{}.json", suffix)),
+            (ExportFormat::Md, format!("run_report_{}.md", suffix)),
+        ]
+    );
+    let json: serde_json::Value = serde_json::from_str(&std::fs::read_to_string(&written[0].1).unwrap()).unwrap();
+    assert_eq!(json["num_steps"], 5);
+    let markdown = std::fs::read_to_string(&written[1].1).unwrap();
+    assert!(markdown.contains("| attention_entropy |"));
+
+    let mut files: Vec<String> = std::fs::read_dir(&dir)
+        .unwrap()
+        .map(|entry| entry.unwrap().file_name().into_string().unwrap())
+        .collect();
+    files.sort();
+    let mut expected: Vec<String> = written.into_iter().map(|(_, path)| path).collect();
+    expected.sort();
+    assert_eq!(files, expected);
+
+    assert!(sim.export_reports("run", &[]).unwrap().is_empty());
+    std::fs::remove_dir_all(&dir).unwrap();
+}
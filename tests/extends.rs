@@ -0,0 +1,115 @@
+//! Loads configurations that inherit from other files through `extends`.
+//!
+//! ```bash
+//! cargo test --test extends
+//! ```
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use synthetic_consciousness::config::MAX_EXTENDS_DEPTH;
+use synthetic_consciousness::{Simulation, SimulationConfig};
+
+/// Fresh scratch directory for one test.
+fn scratch(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("sc_extends_{}_{}", name, std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(dir.join("runs")).unwrap();
+    dir
+}
+
+fn write(path: &Path, contents: &str) -> String {
+    fs::write(path, contents).unwrap();
+    path.to_str().unwrap().to_string()
+}
+
+/// A complete base configuration.
+fn base_toml() -> String {
+    let mut config = SimulationConfig::default_2d();
+    config.metadata.name = "Base".to_string();
+    config.simulation.num_entities = 4;
+    config.simulation.num_steps = 10;
+    toml::to_string(&config).unwrap()
+}
+
+#[test]
+fn two_levels_merge_field_by_field_with_the_child_winning() {
+    let dir = scratch("two_levels");
+    write(&dir.join("base.toml"), &base_toml());
+    write(
+        &dir.join("tuned.toml"),
+        "extends = \"base.toml\"\n\n[attraction]\nsigma = 4.0\n\n[simulation]\nnum_steps = 30\n",
+    );
+    let child = write(
+        &dir.join("child.toml"),
+        "extends = \"tuned.toml\"\n\n[metadata]\nname = \"Child\"\n\n[simulation]\nnum_steps = 50\n",
+    );
+
+    let config = SimulationConfig::from_toml(&child).unwrap();
+    assert_eq!(config.metadata.name, "Child");
+    assert_eq!(config.metadata.version, "1.0.0");
+    assert_eq!(config.attraction.sigma, 4.0);
+    assert_eq!(config.simulation.num_steps, 50);
+    assert_eq!(config.simulation.num_entities, 4);
+    let base = SimulationConfig::default_2d();
+    assert_eq!(config.attraction.lambda, base.attraction.lambda);
+
+    // The merged configuration is what runs and is embedded in the results
+    let mut sim = Simulation::new(config.clone()).unwrap();
+    sim.run();
+    sim.finalize_results();
+    let embedded = sim.results.config.as_ref().unwrap();
+    assert_eq!(embedded.fingerprint(), config.fingerprint());
+    assert_eq!(sim.results.config_fingerprint, config.fingerprint());
+    fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn parent_paths_are_relative_to_the_file_naming_them() {
+    let dir = scratch("relative");
+    write(&dir.join("base.toml"), &base_toml());
+    let child = write(&dir.join("runs").join("long.toml"), "extends = \"../base.toml\"\n[simulation]\nnum_steps = 70\n");
+
+    // Loaded from another working directory the parent is still found
+    let config = SimulationConfig::load(&child, None).unwrap();
+    assert_eq!(config.simulation.num_steps, 70);
+    assert_eq!(config.metadata.name, "Base");
+
+    let missing = write(&dir.join("runs").join("orphan.toml"), "extends = \"base.toml\"\n");
+    let error = SimulationConfig::from_toml(&missing).unwrap_err().to_string();
+    assert!(error.contains("runs/base.toml") && error.contains("orphan.toml"), "{}", error);
+    fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn cycles_are_reported_with_the_files_involved() {
+    let dir = scratch("cycle");
+    let a = write(&dir.join("a.toml"), "extends = \"b.toml\"\n");
+    write(&dir.join("b.toml"), "extends = \"a.toml\"\n[simulation]\nnum_steps = 5\n");
+
+    let error = SimulationConfig::from_toml(&a).unwrap_err().to_string();
+    assert!(error.starts_with("`extends` cycle: "), "{}", error);
+    assert!(error.contains("a.toml -> ") && error.contains("b.toml -> ") && error.ends_with("a.toml"), "{}", error);
+
+    let selfish = write(&dir.join("self.toml"), "extends = \"self.toml\"\n");
+    assert!(SimulationConfig::from_toml(&selfish).unwrap_err().to_string().contains("cycle"));
+    fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn chains_deeper_than_the_limit_are_rejected() {
+    let dir = scratch("depth");
+    write(&dir.join("level0.toml"), &base_toml());
+    for level in 1..=MAX_EXTENDS_DEPTH + 1 {
+        write(
+            &dir.join(format!("level{}.toml", level)),
+            &format!("extends = \"level{}.toml\"\n", level - 1),
+        );
+    }
+
+    let deepest_allowed = dir.join(format!("level{}.toml", MAX_EXTENDS_DEPTH));
+    assert!(SimulationConfig::from_toml(deepest_allowed.to_str().unwrap()).is_ok());
+    let too_deep = dir.join(format!("level{}.toml", MAX_EXTENDS_DEPTH + 1));
+    let error = SimulationConfig::from_toml(too_deep.to_str().unwrap()).unwrap_err().to_string();
+    assert!(error.contains("deeper than"), "{}", error);
+    fs::remove_dir_all(dir).unwrap();
+}
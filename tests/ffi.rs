@@ -0,0 +1,78 @@
+//! Integration test: load the cdylib through libloading and drive a small
+//! simulation over the C ABI.
+
+#![cfg(feature = "ffi")]
+
+use libloading::{Library, Symbol};
+use std::ffi::{c_char, c_void, CStr, CString};
+use synthetic_consciousness::bindings::ffi::{ScMetrics, SC_ERR_BUFFER_TOO_SMALL, SC_ERR_NULL_POINTER, SC_OK};
+
+/// Load the cdylib cargo built alongside this test binary (in `deps/`).
+fn load_library() -> Library {
+    let exe = std::env::current_exe().unwrap();
+    let path = exe
+        .parent()
+        .unwrap()
+        .join(libloading::library_filename("synthetic_consciousness"));
+    unsafe { Library::new(&path) }.unwrap_or_else(|e| panic!("cannot load {}: {}", path.display(), e))
+}
+
+#[test]
+fn run_small_simulation_through_c_abi() {
+    let lib = load_library();
+    unsafe {
+        let config_from_toml: Symbol<unsafe extern "C" fn(*const c_char, *mut *mut c_void) -> i32> =
+            lib.get(b"sc_config_from_toml").unwrap();
+        let free_config: Symbol<unsafe extern "C" fn(*mut c_void)> = lib.get(b"sc_free_config").unwrap();
+        let simulation_new: Symbol<unsafe extern "C" fn(*const c_void, *mut *mut c_void) -> i32> =
+            lib.get(b"sc_simulation_new").unwrap();
+        let step: Symbol<unsafe extern "C" fn(*mut c_void, u32) -> i32> = lib.get(b"sc_simulation_step").unwrap();
+        let run: Symbol<unsafe extern "C" fn(*mut c_void) -> i32> = lib.get(b"sc_simulation_run").unwrap();
+        let entity_count: Symbol<unsafe extern "C" fn(*const c_void, *mut usize) -> i32> =
+            lib.get(b"sc_get_entity_count").unwrap();
+        let dimension: Symbol<unsafe extern "C" fn(*const c_void, *mut usize) -> i32> =
+            lib.get(b"sc_get_dimension").unwrap();
+        let positions: Symbol<unsafe extern "C" fn(*const c_void, *mut f32, usize) -> i32> =
+            lib.get(b"sc_get_positions").unwrap();
+        let metrics: Symbol<unsafe extern "C" fn(*const c_void, *mut ScMetrics) -> i32> =
+            lib.get(b"sc_get_metrics").unwrap();
+        let score: Symbol<unsafe extern "C" fn(*const c_void, *mut f32) -> i32> =
+            lib.get(b"sc_get_consciousness_score").unwrap();
+        let last_error: Symbol<unsafe extern "C" fn() -> *const c_char> = lib.get(b"sc_last_error").unwrap();
+        let free_simulation: Symbol<unsafe extern "C" fn(*mut c_void)> = lib.get(b"sc_free_simulation").unwrap();
+
+        let path = CString::new(concat!(env!("CARGO_MANIFEST_DIR"), "/test_2d.toml")).unwrap();
+        let mut config = std::ptr::null_mut();
+        assert_eq!(config_from_toml(path.as_ptr(), &mut config), SC_OK);
+
+        let mut sim = std::ptr::null_mut();
+        assert_eq!(simulation_new(config, &mut sim), SC_OK);
+        assert_eq!(step(sim, 5), SC_OK);
+        assert_eq!(run(sim), SC_OK);
+
+        let (mut count, mut dim) = (0usize, 0usize);
+        assert_eq!(entity_count(sim, &mut count), SC_OK);
+        assert_eq!(dimension(sim, &mut dim), SC_OK);
+        assert!(count > 0);
+        assert_eq!(dim, 2);
+
+        let mut buffer = vec![f32::NAN; count * dim];
+        assert_eq!(positions(sim, buffer.as_mut_ptr(), buffer.len() - 1), SC_ERR_BUFFER_TOO_SMALL);
+        assert!(!CStr::from_ptr(last_error()).to_bytes().is_empty());
+        assert_eq!(positions(sim, buffer.as_mut_ptr(), buffer.len()), SC_OK);
+        assert!(buffer.iter().all(|p| p.is_finite()));
+
+        let mut snapshot = ScMetrics::default();
+        assert_eq!(metrics(sim, &mut snapshot), SC_OK);
+        assert!(snapshot.timestamp > 0);
+
+        let mut value = -1.0f32;
+        assert_eq!(score(sim, &mut value), SC_OK);
+        assert!((0.0..=1.0).contains(&value));
+
+        assert_eq!(entity_count(std::ptr::null(), &mut count), SC_ERR_NULL_POINTER);
+
+        free_simulation(sim);
+        free_config(config);
+    }
+}
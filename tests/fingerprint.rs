@@ -0,0 +1,103 @@
+//! Configuration fingerprints: stable for equal configurations, however
+//! they were built and whatever the run did, and different as soon as a
+//! single field changes.
+//!
+//! ```bash
+//! cargo test --test fingerprint
+//! ```
+
+use std::collections::HashSet;
+use synthetic_consciousness::schedule::{ParameterSchedule, ScheduleKind};
+use synthetic_consciousness::{Simulation, SimulationConfig, SimulationConfigPatch};
+
+fn schedule(end: f64) -> ParameterSchedule {
+    ParameterSchedule { kind: ScheduleKind::Linear, start: 0.1, end: end as _, from_step: 0, to_step: 10 }
+}
+
+fn config() -> SimulationConfig {
+    let mut config = SimulationConfig::default_2d();
+    config.simulation.num_steps = 20;
+    config.schedules.insert("attraction.sigma".to_string(), schedule(0.5));
+    config.schedules.insert("dynamics.damping".to_string(), schedule(0.2));
+    config
+}
+
+#[test]
+fn equal_configs_fingerprint_equally_across_runs() {
+    let first = Simulation::new(config()).unwrap();
+    let before = first.export_config_fingerprint();
+    assert_eq!(before.len(), 64);
+    assert!(before.chars().all(|c| c.is_ascii_hexdigit() && !c.is_ascii_uppercase()), "{}", before);
+
+    // Two independent runs of equal configurations
+    let fingerprints: Vec<String> = (0..2)
+        .map(|_| {
+            let mut sim = Simulation::new(config()).unwrap();
+            sim.run();
+            sim.finalize_results();
+            assert_eq!(sim.results.config_fingerprint, sim.export_config_fingerprint());
+            sim.export_config_fingerprint()
+        })
+        .collect();
+    assert_eq!(fingerprints, vec![before.clone(), before.clone()]);
+
+    // Keys inserted in another order
+    let mut reordered = SimulationConfig::default_2d();
+    reordered.simulation.num_steps = 20;
+    reordered.schedules.insert("dynamics.damping".to_string(), schedule(0.2));
+    reordered.schedules.insert("attraction.sigma".to_string(), schedule(0.5));
+    assert_eq!(reordered.fingerprint(), before);
+}
+
+#[test]
+fn schedules_keep_and_patches_change_the_fingerprint() {
+    let mut sim = Simulation::new(config()).unwrap();
+    let configured = (sim.config.attraction.sigma, sim.export_config_fingerprint());
+    sim.run();
+    // The schedule moved sigma, but the run is still the configured one
+    assert_ne!(sim.config.attraction.sigma, configured.0);
+    assert_eq!(sim.export_config_fingerprint(), configured.1);
+    sim.finalize_results();
+    assert_eq!(sim.results.config.as_ref().unwrap().fingerprint(), configured.1);
+
+    sim.reset(None).unwrap();
+    assert_eq!(sim.config.attraction.sigma, configured.0);
+    assert_eq!(sim.export_config_fingerprint(), configured.1);
+
+    let patch = SimulationConfigPatch { min_speed: Some(0.08), ..Default::default() };
+    sim.apply_config_patch(patch).unwrap();
+    assert_ne!(sim.export_config_fingerprint(), configured.1);
+}
+
+/// A named change of one configuration field.
+type Edit = (&'static str, fn(&mut SimulationConfig));
+
+#[test]
+fn changing_any_one_field_changes_the_fingerprint() {
+    let edits: Vec<Edit> = vec![
+        ("seed", |c| c.simulation.seed += 1),
+        ("num_entities", |c| c.simulation.num_entities += 1),
+        ("dt", |c| c.simulation.dt *= 0.5),
+        ("bounds", |c| c.geometry.bounds[1] += 1.0),
+        ("periodic", |c| c.geometry.periodic = !c.geometry.periodic),
+        ("sigma", |c| c.attraction.sigma += 1e-3),
+        ("damping", |c| c.dynamics.damping += 1e-3),
+        ("essence decay", |c| c.essence.decay += 1e-3),
+        ("name", |c| c.metadata.name.push('!')),
+        ("reproduction", |c| c.reproduction.enabled = !c.reproduction.enabled),
+        ("schedule end", |c| c.schedules.get_mut("attraction.sigma").unwrap().end += 1e-3),
+        ("schedule removed", |c| {
+            c.schedules.remove("dynamics.damping");
+        }),
+    ];
+
+    let base = config().fingerprint();
+    let mut seen = HashSet::from([base.clone()]);
+    for (field, edit) in edits {
+        let mut changed = config();
+        edit(&mut changed);
+        let fingerprint = changed.fingerprint();
+        assert_ne!(fingerprint, base, "{}", field);
+        assert!(seen.insert(fingerprint), "{} collides with another edit", field);
+    }
+}
@@ -0,0 +1,107 @@
+//! Runs a gRPC client against an in-process server over a local socket.
+//!
+//! `cargo test --features grpc --test grpc`
+
+#![cfg(feature = "grpc")]
+
+use synthetic_consciousness::grpc::proto::simulation_service_client::SimulationServiceClient;
+use synthetic_consciousness::grpc::proto::{CreateSimulationRequest, InjectStimulusRequest, SimulationRef, StepRequest};
+use synthetic_consciousness::grpc::serve;
+use synthetic_consciousness::SimulationConfig;
+use tonic::Code;
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn remote_stepping_end_to_end() {
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    let server = tokio::spawn(serve(listener, 2));
+    let mut client = SimulationServiceClient::connect(format!("http://{}", addr)).await.unwrap();
+
+    let mut config = SimulationConfig::default_2d();
+    config.simulation.num_entities = 5;
+    config.simulation.num_steps = 100;
+    let created = client
+        .create_simulation(CreateSimulationRequest {
+            config_json: serde_json::to_string(&config).unwrap(),
+            ..Default::default()
+        })
+        .await
+        .unwrap()
+        .into_inner();
+    assert_eq!(created.num_entities, 5);
+    assert_eq!(created.dimension, 2);
+    let id = created.simulation_id;
+    let sim = || SimulationRef { simulation_id: id.clone() };
+
+    let stepped = client
+        .step(StepRequest { simulation_id: id.clone(), n: 10 })
+        .await
+        .unwrap()
+        .into_inner();
+    assert_eq!(stepped.step, 10);
+    // Metrics carry the zero-based index of the step they were recorded at
+    assert_eq!(stepped.metrics.unwrap().step, 9);
+
+    // Streaming: one message per step, in order
+    let mut stream = client
+        .stream_steps(StepRequest { simulation_id: id.clone(), n: 5 })
+        .await
+        .unwrap()
+        .into_inner();
+    let mut steps = Vec::new();
+    while let Some(message) = stream.message().await.unwrap() {
+        steps.push(message.step);
+    }
+    assert_eq!(steps, [11, 12, 13, 14, 15]);
+
+    let metrics = client.get_metrics(sim()).await.unwrap().into_inner();
+    assert_eq!(metrics.step, 14);
+
+    let entities = client.get_entities(sim()).await.unwrap().into_inner().entities;
+    assert_eq!(entities.len(), 5);
+    assert!(entities.windows(2).all(|w| w[0].id < w[1].id));
+    assert!(entities.iter().all(|e| e.position.len() == 2));
+
+    let target = entities[0].id;
+    let injected = client
+        .inject_stimulus(InjectStimulusRequest {
+            simulation_id: id.clone(),
+            entity_id: target,
+            stimulus: vec![0.5, -0.5],
+        })
+        .await
+        .unwrap()
+        .into_inner();
+    assert!(injected.memory_nodes > 0);
+    let wrong_length = client
+        .inject_stimulus(InjectStimulusRequest {
+            simulation_id: id.clone(),
+            entity_id: target,
+            stimulus: vec![1.0],
+        })
+        .await
+        .unwrap_err();
+    assert_eq!(wrong_length.code(), Code::InvalidArgument);
+
+    let summary = client.get_summary(sim()).await.unwrap().into_inner();
+    assert_eq!(summary.step, 15);
+    assert!((0.0..=1.0).contains(&summary.consciousness_score));
+    assert!(!summary.narrative.is_empty());
+
+    let bad_config = client
+        .create_simulation(CreateSimulationRequest {
+            config_json: "{not json".to_string(),
+            ..Default::default()
+        })
+        .await
+        .unwrap_err();
+    assert_eq!(bad_config.code(), Code::InvalidArgument);
+
+    assert!(client.shutdown(sim()).await.unwrap().into_inner().removed);
+    let gone = client.get_metrics(sim()).await.unwrap_err();
+    assert_eq!(gone.code(), Code::NotFound);
+
+    // An empty id stops the server
+    client.shutdown(SimulationRef::default()).await.unwrap();
+    server.await.unwrap().unwrap();
+}
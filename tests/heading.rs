@@ -0,0 +1,91 @@
+//! Turns moving entities and checks that the forward vector derived from
+//! their orientation quaternion swings toward the new velocity by at most
+//! the turn-rate limit per step, then stays aligned with it.
+//!
+//! ```bash
+//! cargo test --test heading
+//! ```
+
+use synthetic_consciousness::{Entity, Real, Simulation, SimulationConfig};
+
+/// Angle in radians between two vectors.
+fn angle(a: &[Real], b: &[Real]) -> Real {
+    let dot: Real = a.iter().zip(b).map(|(x, y)| x * y).sum();
+    let norm = |v: &[Real]| v.iter().map(|x| x * x).sum::<Real>().sqrt();
+    (dot / (norm(a) * norm(b))).clamp(-1.0, 1.0).acos()
+}
+
+#[test]
+fn forward_turns_toward_the_velocity_within_the_limit() {
+    const MAX_ANGLE: Real = 0.2;
+    for (start, turned) in [
+        (vec![1.0, 0.0], vec![-1.0, 1.0]),
+        (vec![1.0, 0.0, 0.0], vec![0.0, 1.0, 1.0]),
+        (vec![0.0, 1.0, 0.0], vec![0.3, -0.2, -1.0]),
+    ] {
+        let dimension = start.len();
+        let config = if dimension == 2 { SimulationConfig::default_2d() } else { SimulationConfig::default_3d() };
+        let mut entity = Entity::builder(dimension)
+            .position(vec![5.0; dimension])
+            .velocity(start.clone())
+            .build(&config)
+            .unwrap();
+        // Unlimited turn: faces the velocity at once
+        entity.update_heading(Real::INFINITY);
+        assert!(angle(&entity.forward(), &start) < 1e-3);
+
+        entity.velocity = turned.clone().into();
+        let mut remaining = angle(&entity.forward(), &turned);
+        let mut steps = 0;
+        while remaining > 1e-3 {
+            entity.update_heading(MAX_ANGLE);
+            let forward = entity.forward();
+            assert!((forward.iter().map(|f| f * f).sum::<Real>() - 1.0).abs() < 1e-4);
+            let now = angle(&forward, &turned);
+            // Full steps while far away, then exactly onto the velocity
+            let expected = (remaining - MAX_ANGLE).max(0.0);
+            assert!((now - expected).abs() < 2e-3, "{}D: {} -> {} (expected {})", dimension, remaining, now, expected);
+            remaining = now;
+            steps += 1;
+            assert!(steps < 100, "{}D heading never converged", dimension);
+        }
+    }
+}
+
+#[test]
+fn simulated_entities_turn_at_the_configured_rate() {
+    // A lone entity without drives coasts at the minimum speed
+    let builder = Simulation::builder().entities(0).steps(100).configure(|config| {
+        config.dynamics.min_speed = 1.0;
+        config.dynamics.damping = 1.0;
+        config.dynamics.max_turn_rate = 10.0;
+        config.drives.responsiveness = 0.0;
+    });
+    let per_step = builder.config().dynamics.max_turn_rate * builder.config().dynamics.dt;
+    let entity = Entity::builder(2)
+        .position(vec![5.0, 5.0])
+        .velocity(vec![1.0, 0.0])
+        .drives(0.0, 0.0)
+        .build(builder.config())
+        .unwrap();
+    let mut sim = builder.with_entity(entity).build().unwrap();
+    sim.step();
+    let entity = sim.entities.iter_mut().next().unwrap();
+    assert!(angle(&entity.forward(), &entity.velocity) < 1e-3);
+
+    // Reverse course: the heading needs about π / per_step steps to follow
+    entity.velocity = vec![-1.0, 0.0].into();
+    let mut previous = angle(&entity.forward(), &entity.velocity);
+    let mut steps: u32 = 0;
+    while previous > 1e-3 {
+        sim.step();
+        let entity = sim.entities.iter().next().unwrap();
+        let now = angle(&entity.forward(), &entity.velocity);
+        assert!(previous - now <= per_step + 1e-3, "turned {} in one step", previous - now);
+        assert!(now < previous, "the heading stopped turning at {}", now);
+        previous = now;
+        steps += 1;
+    }
+    let expected = (std::f64::consts::PI as Real / per_step).ceil() as u32;
+    assert!(steps.abs_diff(expected) <= 1, "aligned after {} steps, expected about {}", steps, expected);
+}
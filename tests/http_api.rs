@@ -0,0 +1,161 @@
+//! Drives a tiny run through the HTTP API lifecycle, and checks the
+//! active-run limit, failures while building and the eviction of finished
+//! runs.
+//!
+//! `cargo test --features http --test http_api`
+
+#![cfg(feature = "http")]
+
+use std::io::{Read, Write};
+use std::net::{SocketAddr, TcpStream};
+use std::sync::Arc;
+use std::thread;
+use std::time::{Duration, Instant};
+use synthetic_consciousness::api::ApiServer;
+use synthetic_consciousness::plugin::PluginSpec;
+use synthetic_consciousness::SimulationConfig;
+
+/// Send one HTTP/1.0 request (so the body is never chunked) and return
+/// (status code, JSON body).
+fn request(addr: SocketAddr, method: &str, path: &str, body: &str) -> (u16, serde_json::Value) {
+    let mut stream = TcpStream::connect(addr).unwrap();
+    write!(
+        stream,
+        "{} {} HTTP/1.0\r\nHost: {}\r\nConnection: close\r\nContent-Type: application/json\r\nContent-Length: {}\r\n\r\n{}",
+        method,
+        path,
+        addr,
+        body.len(),
+        body
+    )
+    .unwrap();
+    let mut response = String::new();
+    stream.read_to_string(&mut response).unwrap();
+    let status = response[9..12].parse().unwrap();
+    let (_, body) = response.split_once("\r\n\r\n").unwrap();
+    (status, serde_json::from_str(body).unwrap())
+}
+
+/// Poll a run until it is done and return its final status body.
+fn wait_until_done(addr: SocketAddr, id: &str) -> serde_json::Value {
+    let deadline = Instant::now() + Duration::from_secs(60);
+    loop {
+        let (status, run) = request(addr, "GET", &format!("/runs/{}", id), "");
+        assert_eq!(status, 200);
+        if run["done"] == true {
+            return run;
+        }
+        assert!(Instant::now() < deadline, "run {} did not finish", id);
+        thread::sleep(Duration::from_millis(20));
+    }
+}
+
+/// Start a run and return its id.
+fn start(addr: SocketAddr, config: &SimulationConfig) -> String {
+    let (status, created) = request(addr, "POST", "/runs", &serde_json::to_string(config).unwrap());
+    assert_eq!(status, 201, "{}", created);
+    created["id"].as_str().unwrap().to_string()
+}
+
+#[test]
+fn run_lifecycle() {
+    let server = Arc::new(ApiServer::bind("127.0.0.1:0", 1).unwrap());
+    let addr = server.local_addr().unwrap();
+    let serving = Arc::clone(&server);
+    let handle = thread::spawn(move || serving.serve());
+
+    let mut config = SimulationConfig::default_2d();
+    config.simulation.num_entities = 4;
+    config.simulation.num_steps = 20;
+    let (status, created) = request(addr, "POST", "/runs", &serde_json::to_string(&config).unwrap());
+    assert_eq!(status, 201);
+    let id = created["id"].as_str().unwrap().to_string();
+
+    let deadline = Instant::now() + Duration::from_secs(60);
+    loop {
+        let (status, run) = request(addr, "GET", &format!("/runs/{}", id), "");
+        assert_eq!(status, 200);
+        if run["done"] == true {
+            assert_eq!(run["status"], "completed");
+            assert_eq!(run["step"], 20);
+            break;
+        }
+        assert!(Instant::now() < deadline, "run did not finish");
+        thread::sleep(Duration::from_millis(20));
+    }
+
+    let (status, summary) = request(addr, "GET", &format!("/runs/{}/results", id), "");
+    assert_eq!(status, 200);
+    assert!(summary["consciousness_score"].is_number());
+    let (status, full) = request(addr, "GET", &format!("/runs/{}/results?full=true", id), "");
+    assert_eq!(status, 200);
+    assert_eq!(full["steps"].as_array().unwrap().len(), 20);
+
+    // A long run is cancelled cooperatively.
+    config.simulation.num_steps = 1_000_000;
+    let (_, created) = request(addr, "POST", "/runs", &serde_json::to_string(&config).unwrap());
+    let long_id = created["id"].as_str().unwrap().to_string();
+    let (status, _) = request(addr, "POST", "/runs", &serde_json::to_string(&config).unwrap());
+    assert_eq!(status, 429);
+    let (status, _) = request(addr, "DELETE", &format!("/runs/{}", long_id), "");
+    assert_eq!(status, 202);
+    loop {
+        let (_, run) = request(addr, "GET", &format!("/runs/{}", long_id), "");
+        if run["done"] == true {
+            assert_eq!(run["status"], "cancelled");
+            break;
+        }
+        assert!(Instant::now() < deadline, "run was not cancelled");
+        thread::sleep(Duration::from_millis(20));
+    }
+    let (status, _) = request(addr, "GET", &format!("/runs/{}/results", long_id), "");
+    assert_eq!(status, 409);
+    assert_eq!(request(addr, "GET", "/runs/not-a-run", "").0, 404);
+
+    server.shutdown();
+    handle.join().unwrap();
+}
+
+#[test]
+fn limits_failures_and_eviction() {
+    let server = Arc::new(ApiServer::bind("127.0.0.1:0", 1).unwrap().with_max_finished_runs(2));
+    let addr = server.local_addr().unwrap();
+    let serving = Arc::clone(&server);
+    let handle = thread::spawn(move || serving.serve());
+
+    // The limit is checked before the body is even parsed
+    let mut config = SimulationConfig::default_2d();
+    config.simulation.num_entities = 4;
+    config.simulation.num_steps = 1_000_000;
+    let long = start(addr, &config);
+    assert_eq!(request(addr, "POST", "/runs", "not json").0, 429);
+    request(addr, "DELETE", &format!("/runs/{}", long), "");
+    assert_eq!(wait_until_done(addr, &long)["status"], "cancelled");
+    assert_eq!(request(addr, "POST", "/runs", "not json").0, 400);
+
+    // Errors only found while building fail the run with the reason
+    let mut broken = config.clone();
+    broken.plugins.stimulus = Some(PluginSpec { name: "no-such-plugin".to_string(), params: Default::default() });
+    let failed = start(addr, &broken);
+    let run = wait_until_done(addr, &failed);
+    assert_eq!(run["status"], "failed");
+    assert!(run["error"].as_str().unwrap().contains("no-such-plugin"));
+
+    // Only the two most recent finished runs are kept
+    config.simulation.num_steps = 5;
+    let mut finished = vec![long, failed];
+    for _ in 0..3 {
+        let id = start(addr, &config);
+        assert_eq!(wait_until_done(addr, &id)["status"], "completed");
+        finished.push(id);
+    }
+    let (_, list) = request(addr, "GET", "/runs", "");
+    assert_eq!(list.as_array().unwrap().len(), 2);
+    for (k, id) in finished.iter().enumerate() {
+        let expected = if k + 2 >= finished.len() { 200 } else { 404 };
+        assert_eq!(request(addr, "GET", &format!("/runs/{}", id), "").0, expected, "run {}", k);
+    }
+
+    server.shutdown();
+    handle.join().unwrap();
+}
@@ -0,0 +1,71 @@
+//! Introspection report: its totals and means match the entity pool they
+//! summarize, before and after the population changes.
+//!
+//! ```bash
+//! cargo test --test introspect
+//! ```
+
+use synthetic_consciousness::{Real, Simulation, SimulationConfig};
+
+/// Every count and mean of the report, recomputed from the pool.
+fn assert_matches_pool(sim: &Simulation) {
+    let report = sim.introspect();
+    let entities: Vec<_> = sim.entities.iter().collect();
+    assert_eq!(report.timestamp, sim.timestamp);
+    assert_eq!(report.entity_count, entities.len());
+    assert_eq!(report.dominant_kernel_distances.len(), entities.len());
+
+    let nodes: usize = entities.iter().map(|e| e.memory_graph.nodes.len()).sum();
+    assert_eq!(report.total_memory_nodes, nodes);
+    assert_eq!(report.total_clusters, entities.iter().map(|e| e.memory_graph.clusters.len()).sum::<usize>());
+    assert_eq!(report.total_edges, entities.iter().map(|e| e.memory_graph.edges.len()).sum::<usize>());
+
+    let activation: Real =
+        entities.iter().flat_map(|e| e.memory_graph.nodes.iter()).map(|node| node.activation).sum();
+    assert!((report.mean_activation - activation / nodes as Real).abs() < 1e-5);
+    let essence: Real = entities.iter().map(|e| e.essence.value).sum();
+    assert!((report.mean_essence - essence / entities.len() as Real).abs() < 1e-5);
+
+    let floats: usize =
+        entities.iter().map(|e| e.state.memory.len() + e.state.context.len() + e.state.traits.len()).sum();
+    assert_eq!(report.estimated_memory_bytes, floats * std::mem::size_of::<Real>());
+    assert_eq!(report.current_metrics.phase_synchrony, sim.kuramoto_order());
+}
+
+#[test]
+fn counts_match_the_pool() {
+    let mut config = SimulationConfig::default_2d();
+    config.simulation.num_entities = 12;
+    let mut sim = Simulation::new(config).unwrap();
+    for _ in 0..30 {
+        sim.step();
+    }
+    let report = sim.introspect();
+    assert!(report.total_memory_nodes > 0 && report.total_clusters > 0);
+    assert_matches_pool(&sim);
+
+    // Removed entities no longer count
+    let ids = sim.sorted_entity_ids();
+    for id in &ids[..5] {
+        assert!(sim.remove_entity(*id));
+    }
+    assert_eq!(sim.introspect().entity_count, 7);
+    assert!(sim.introspect().total_memory_nodes < report.total_memory_nodes);
+    assert_matches_pool(&sim);
+
+    // Introspection leaves the history alone
+    assert_eq!(sim.metrics_history.len(), 30);
+}
+
+#[test]
+fn an_empty_population_reports_zeros() {
+    let mut sim = Simulation::new(SimulationConfig::default_2d()).unwrap();
+    for id in sim.sorted_entity_ids() {
+        sim.remove_entity(id);
+    }
+    let report = sim.introspect();
+    assert_eq!((report.entity_count, report.total_memory_nodes, report.total_clusters), (0, 0, 0));
+    assert_eq!((report.mean_activation, report.mean_essence), (0.0, 0.0));
+    assert!(report.dominant_kernel_distances.is_empty());
+    assert_eq!(report.estimated_memory_bytes, 0);
+}
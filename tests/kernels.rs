@@ -0,0 +1,230 @@
+//! Checks the vectorized kernels, the position/velocity arithmetic and the
+//! state update against the scalar loops they replaced, and the closed-form attention
+//! gradient against central differences over random fields.
+//!
+//! ```bash
+//! cargo test --test kernels
+//! ```
+
+use rand::{rngs::StdRng, Rng, SeedableRng};
+use synthetic_consciousness::attraction::{
+    analytic_gradient, attention_gradient, attraction_potential, finite_difference_gradient, AttractionConfig, KernelType,
+};
+use synthetic_consciousness::geometry::Coords;
+use synthetic_consciousness::memory::MemoryGraph;
+use synthetic_consciousness::state::EntityStateVector;
+use synthetic_consciousness::real::to_f64;
+use synthetic_consciousness::{Entity, Real, SimulationConfig};
+
+fn random_vectors(rng: &mut StdRng, count: usize, dim: usize) -> Vec<Vec<Real>> {
+    (0..count).map(|_| (0..dim).map(|_| rng.gen_range(-10.0..10.0)).collect()).collect()
+}
+
+/// Finite-difference gradient with a fresh position copy per evaluation.
+fn scalar_gradient(position: &[Real], others: &[Vec<Real>], weights: &[Real], config: &AttractionConfig) -> Vec<Real> {
+    let h = 1e-5;
+    (0..position.len())
+        .map(|d| {
+            let mut plus = position.to_vec();
+            plus[d] += h;
+            let mut minus = position.to_vec();
+            minus[d] -= h;
+            -(attraction_potential(&plus, others, weights, config) - attraction_potential(&minus, others, weights, config))
+                / (2.0 * h)
+        })
+        .collect()
+}
+
+fn scalar_cosine(a: &[Real], b: &[Real]) -> Real {
+    let dot: Real = a.iter().zip(b).map(|(x, y)| x * y).sum();
+    let norm_a = a.iter().map(|x| x * x).sum::<Real>().sqrt();
+    let norm_b = b.iter().map(|x| x * x).sum::<Real>().sqrt();
+    dot / (norm_a * norm_b)
+}
+
+/// Former `Entity::integrate` on plain vectors.
+fn scalar_integrate(position: &mut [Real], velocity: &mut [Real], acceleration: &[Real], dt: Real, min_speed: Real, damping: Real) {
+    for (i, v) in velocity.iter_mut().enumerate() {
+        let acc = acceleration.get(i).copied().unwrap_or(0.0);
+        *v = (*v + dt * acc) * damping;
+    }
+    let speed = velocity.iter().map(|v| v * v).sum::<Real>().sqrt();
+    if speed < min_speed && speed > 1e-6 {
+        let scale = min_speed / speed;
+        for v in velocity.iter_mut() {
+            *v *= scale;
+        }
+    } else if speed <= 1e-6 {
+        velocity[0] = min_speed;
+    }
+    for (p, v) in position.iter_mut().zip(velocity.iter()) {
+        *p += dt * v;
+    }
+}
+
+fn bits(values: &[Real]) -> Vec<u64> {
+    values.iter().map(|&x| to_f64(x).to_bits()).collect()
+}
+
+#[test]
+fn finite_difference_gradient_is_bitwise_identical() {
+    let mut rng = StdRng::seed_from_u64(7);
+    let gaussian = SimulationConfig::default_2d().attraction;
+    let inverse = AttractionConfig { kernel: KernelType::InverseDistance, ..gaussian.clone() };
+    for (config, dim) in [(&gaussian, 2), (&gaussian, 3), (&inverse, 2), (&inverse, 3)] {
+        let others = random_vectors(&mut rng, 200, dim);
+        let weights: Vec<Real> = (0..others.len()).map(|_| rng.gen_range(0.5..1.5)).collect();
+        let position = random_vectors(&mut rng, 1, dim).remove(0);
+
+        let packed = finite_difference_gradient(&position, &others, &weights, config);
+        let scalar = scalar_gradient(&position, &others, &weights, config);
+        assert_eq!(bits(&packed), bits(&scalar), "{:?} {}D", config.kernel, dim);
+    }
+}
+
+/// Random kernel, dimension, population, width, weights and position.
+fn random_field(rng: &mut StdRng) -> (AttractionConfig, Vec<Real>, Vec<Vec<Real>>, Vec<Real>) {
+    let kernel = if rng.gen_bool(0.5) { KernelType::Gaussian } else { KernelType::InverseDistance };
+    let config = AttractionConfig {
+        kernel,
+        sigma: rng.gen_range(0.5..3.0),
+        ..SimulationConfig::default_2d().attraction
+    };
+    let dim = rng.gen_range(2..=3);
+    let count = rng.gen_range(1..40);
+    let position: Vec<Real> = (0..dim).map(|_| rng.gen_range(-2.0..2.0)).collect();
+    // Others keep a little distance; the inverse-distance kernel is steep up close
+    let others: Vec<Vec<Real>> = (0..count)
+        .map(|_| loop {
+            let other: Vec<Real> = position.iter().map(|p| p + rng.gen_range(-4.0..4.0)).collect();
+            let distance = other.iter().zip(&position).map(|(a, b)| (a - b).powi(2)).sum::<Real>().sqrt();
+            if distance > 0.5 {
+                break other;
+            }
+        })
+        .collect();
+    let weights = (0..count).map(|_| rng.gen_range(0.1..2.0)).collect();
+    (config, position, others, weights)
+}
+
+/// Largest gap between the closed-form and central-difference gradients
+/// over random fields, relative to the total weight of the field.
+fn max_gradient_gap() -> f64 {
+    let mut rng = StdRng::seed_from_u64(13);
+    let mut max_gap: f64 = 0.0;
+    for _ in 0..500 {
+        let (config, position, others, weights) = random_field(&mut rng);
+        let analytic = analytic_gradient(&position, &others, &weights, &config);
+        let numeric = finite_difference_gradient(&position, &others, &weights, &config);
+        assert_eq!(attention_gradient(&position, &others, &weights, &config), analytic);
+        let total = to_f64(weights.iter().sum::<Real>());
+        for (a, n) in analytic.iter().zip(&numeric) {
+            max_gap = max_gap.max(to_f64(a - n).abs() / total);
+        }
+    }
+    max_gap
+}
+
+#[cfg(feature = "f64")]
+#[test]
+fn analytic_gradient_matches_finite_differences() {
+    let gap = max_gradient_gap();
+    assert!(gap < 1e-8, "gradients differ by {:e}", gap);
+}
+
+/// In single precision the central differences lose most of their digits
+/// to cancellation, so they only agree to about a percent.
+#[cfg(not(feature = "f64"))]
+#[test]
+fn analytic_gradient_matches_finite_differences() {
+    let gap = max_gradient_gap();
+    assert!(gap < 2e-2, "gradients differ by {:e}", gap);
+}
+
+#[test]
+fn cosine_similarity_matches_scalar_loop() {
+    let mut rng = StdRng::seed_from_u64(11);
+    for dim in [2, 3, 7] {
+        for _ in 0..100 {
+            let pair = random_vectors(&mut rng, 2, dim);
+            let (a, b) = (&pair[0], &pair[1]);
+            assert_eq!(MemoryGraph::cosine_similarity(a, b).to_bits(), scalar_cosine(a, b).to_bits());
+        }
+    }
+    // Longer vectors are summed in eight lanes, so only agree to rounding
+    for _ in 0..100 {
+        let pair = random_vectors(&mut rng, 2, 128);
+        let (a, b) = (&pair[0], &pair[1]);
+        assert!((MemoryGraph::cosine_similarity(a, b) - scalar_cosine(a, b)).abs() < 1e-5);
+    }
+}
+
+#[test]
+fn coords_arithmetic_matches_scalar_loops() {
+    let mut rng = StdRng::seed_from_u64(17);
+    for dim in [2, 3] {
+        for _ in 0..200 {
+            let pair = random_vectors(&mut rng, 2, dim);
+            let (a, b) = (Coords::from_slice(&pair[0]), Coords::from_slice(&pair[1]));
+            let norm_squared: Real = pair[0].iter().map(|x| x * x).sum();
+            let dot: Real = pair[0].iter().zip(&pair[1]).map(|(x, y)| x * y).sum();
+            let distance = pair[0].iter().zip(&pair[1]).map(|(x, y)| (x - y).powi(2)).sum::<Real>().sqrt();
+            assert_eq!(a.norm_squared().to_bits(), norm_squared.to_bits());
+            assert_eq!(a.norm().to_bits(), norm_squared.sqrt().to_bits());
+            assert_eq!(a.dot(&b).to_bits(), dot.to_bits());
+            assert_eq!(a.distance(&b).to_bits(), distance.to_bits());
+        }
+    }
+}
+
+#[test]
+fn integration_matches_scalar_loop() {
+    let mut rng = StdRng::seed_from_u64(19);
+    for (dim, config) in [(2, SimulationConfig::default_2d()), (3, SimulationConfig::default_3d())] {
+        let mut entity = Entity::builder(dim).build(&config).unwrap();
+        let mut position = entity.pose.position.to_vec();
+        let mut velocity = entity.velocity.to_vec();
+        for step in 0..500 {
+            // Every few steps the acceleration cancels the motion, exercising the speed floor
+            let acceleration = if step % 7 == 0 {
+                velocity.iter().map(|v| -v / 0.05).collect()
+            } else {
+                random_vectors(&mut rng, 1, dim).remove(0)
+            };
+            let min_speed = rng.gen_range(0.0..2.0);
+            let damping = rng.gen_range(0.9..1.0);
+            entity.integrate(&acceleration, 0.05, min_speed, damping);
+            scalar_integrate(&mut position, &mut velocity, &acceleration, 0.05, min_speed, damping);
+            assert_eq!(bits(&entity.velocity), bits(&velocity), "{}D step {}", dim, step);
+            assert_eq!(bits(&entity.pose.position), bits(&position), "{}D step {}", dim, step);
+        }
+    }
+}
+
+#[test]
+fn state_update_matches_scalar_loop() {
+    let mut rng = StdRng::seed_from_u64(23);
+    let mut state = EntityStateVector::default();
+    let (alpha, beta, gamma) = (state.config.decay_alpha, state.config.beta_attention, state.config.gamma_memory);
+    let mut memory = state.memory.clone();
+    let mut context = state.context.clone();
+    // A spatial attention force, and memory inputs spanning, exceeding and
+    // falling short of the memory
+    for input_len in [memory.len(), memory.len() + 5, 40] {
+        for _ in 0..20 {
+            let attention = random_vectors(&mut rng, 1, 3).remove(0);
+            let input = random_vectors(&mut rng, 1, input_len).remove(0);
+            state.update(&attention, &input);
+            for (i, m) in memory.iter_mut().enumerate() {
+                let att = attention.get(i).map_or(0.0, |a| beta * a);
+                let inp = input.get(i).map_or(0.0, |x| gamma * x);
+                *m = alpha * *m + att + inp;
+            }
+            for (c, m) in context.iter_mut().zip(&memory) {
+                *c = 0.5 * *c + 0.5 * m;
+            }
+            assert_eq!(bits(&state.memory), bits(&memory), "input of {}", input_len);
+            assert_eq!(bits(&state.context), bits(&context), "input of {}", input_len);
+        }
+    }
+}
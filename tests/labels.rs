@@ -0,0 +1,105 @@
+//! Tags entities from the configuration and at runtime, and checks label
+//! filtering, per-label metrics, and that labels survive serialization,
+//! checkpoints and the per-entity CSV export.
+//!
+//! ```bash
+//! cargo test --test labels
+//! ```
+
+use std::path::PathBuf;
+use synthetic_consciousness::entities::{EntityConfig, EntityId, EntityPool};
+use synthetic_consciousness::{Entity, Real, Simulation, SimulationConfig};
+
+fn labeled(id: u32, labels: &[&str]) -> EntityConfig {
+    EntityConfig { id, labels: labels.iter().map(|l| l.to_string()).collect(), ..Default::default() }
+}
+
+/// Six entities: 1-3 "treatment", 4-5 "control", 2 also "seeded-belief".
+fn sim() -> Simulation {
+    let mut config = SimulationConfig::default_2d();
+    config.simulation.num_entities = 6;
+    config.simulation.num_steps = 10;
+    config.entities = vec![
+        labeled(1, &["treatment"]),
+        labeled(2, &["treatment", "seeded-belief"]),
+        labeled(3, &["treatment"]),
+        labeled(4, &["control"]),
+        labeled(5, &["control"]),
+    ];
+    Simulation::new(config).unwrap()
+}
+
+fn ids(ids: &[u32]) -> Vec<EntityId> {
+    ids.iter().map(|&id| EntityId(id)).collect()
+}
+
+fn temp_file(name: &str) -> PathBuf {
+    std::env::temp_dir().join(format!("sc_labels_{}_{}", std::process::id(), name))
+}
+
+#[test]
+fn pools_filter_entities_by_label() {
+    let mut sim = sim();
+    assert_eq!(sim.entities.with_label("treatment"), ids(&[1, 2, 3]));
+    assert_eq!(sim.entities.with_label("control"), ids(&[4, 5]));
+    assert_eq!(sim.entities.with_label("seeded-belief"), ids(&[2]));
+    assert!(sim.entities.with_label("placebo").is_empty());
+
+    // Runtime tags join the group once, however often they are applied
+    sim.tag_entity(EntityId(6), "control").unwrap();
+    sim.tag_entity(EntityId(6), "control").unwrap();
+    assert_eq!(sim.entities.with_label("control"), ids(&[4, 5, 6]));
+    assert_eq!(sim.entities.get_entity(EntityId(6)).unwrap().labels, vec!["control"]);
+    assert!(sim.tag_entity(EntityId(99), "control").is_err());
+}
+
+#[test]
+fn label_metrics_cover_only_the_group() {
+    let mut sim = sim();
+    sim.run();
+    assert!(sim.compute_label_metrics("placebo").is_none());
+
+    let treatment = sim.compute_label_metrics("treatment").unwrap();
+    let members: Vec<&Entity> = sim.entities.iter().filter(|e| e.has_label("treatment")).collect();
+    let mean_essence = members.iter().map(|e| e.essence.value).sum::<Real>() / members.len() as Real;
+    assert!((treatment.average_essence - mean_essence).abs() < 1e-4);
+}
+
+#[test]
+fn labels_survive_serialization_and_checkpoints() {
+    let mut sim = sim();
+    sim.tag_entity(EntityId(6), "late").unwrap();
+
+    let pool: EntityPool = serde_json::from_str(&serde_json::to_string(&sim.entities).unwrap()).unwrap();
+    for (copy, entity) in pool.iter().zip(sim.entities.iter()) {
+        assert_eq!(copy.labels, entity.labels);
+    }
+    let json = serde_json::to_value(sim.entities.get_entity(EntityId(2)).unwrap()).unwrap();
+    assert_eq!(json["labels"], serde_json::json!(["treatment", "seeded-belief"]));
+
+    let path = temp_file("checkpoint.json");
+    let path = path.to_str().unwrap();
+    sim.step();
+    sim.save_checkpoint(path).unwrap();
+    let resumed = Simulation::load_checkpoint(path).unwrap();
+    std::fs::remove_file(path).unwrap();
+    assert_eq!(resumed.entities.with_label("treatment"), ids(&[1, 2, 3]));
+    assert_eq!(resumed.entities.with_label("late"), ids(&[6]));
+}
+
+#[test]
+fn labels_are_exported_per_entity() {
+    let mut sim = sim();
+    sim.run();
+    let path = temp_file("entities.csv");
+    let path = path.to_str().unwrap();
+    sim.export_entities_csv(path).unwrap();
+    let csv = std::fs::read_to_string(path).unwrap();
+    std::fs::remove_file(path).unwrap();
+
+    let mut lines = csv.lines().filter(|line| !line.starts_with('#'));
+    let header: Vec<&str> = lines.next().unwrap().split(',').collect();
+    let column = header.iter().position(|&h| h == "labels").unwrap();
+    let labels: Vec<String> = lines.map(|line| line.split(',').nth(column).unwrap().to_string()).collect();
+    assert_eq!(labels, ["treatment", "treatment;seeded-belief", "treatment", "control", "control", ""]);
+}
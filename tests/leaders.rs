@@ -0,0 +1,60 @@
+//! Emergent leaders on a star: only the hub emits attraction, so every
+//! recorded interaction involves it and PageRank puts it first, followed by
+//! the spokes in order of closeness and an isolated entity last.
+//!
+//! ```bash
+//! cargo test --test leaders
+//! ```
+
+use synthetic_consciousness::entities::EntityId;
+use synthetic_consciousness::{Entity, Real, Simulation};
+
+/// The hub (id 1) with spokes 1, 2 and 3 away (ids 2 to 4) and an entity
+/// beyond the attraction cutoff (id 5), all at rest and without drives.
+fn star() -> Simulation {
+    let builder = Simulation::builder().entities(0).steps(5).configure(|config| {
+        config.dynamics.min_speed = 0.0;
+        config.drives.responsiveness = 0.0;
+    });
+    let at = |position: [Real; 2], attraction_weight: Real| {
+        let mut entity = Entity::builder(2)
+            .position(position.to_vec())
+            .velocity(vec![0.0, 0.0])
+            .drives(0.0, 0.0)
+            .build(builder.config())
+            .unwrap();
+        entity.attraction_weight = attraction_weight;
+        entity
+    };
+    let entities = [
+        at([5.0, 5.0], 1.0),
+        at([6.0, 5.0], 0.0),
+        at([5.0, 7.0], 0.0),
+        at([2.0, 5.0], 0.0),
+        at([0.5, 0.5], 0.0),
+    ];
+    entities.into_iter().fold(builder, |b, e| b.with_entity(e)).build().unwrap()
+}
+
+fn ids(ranked: &[(EntityId, Real)]) -> Vec<u32> {
+    ranked.iter().map(|(id, _)| id.0).collect()
+}
+
+#[test]
+fn the_hub_leads_and_closer_spokes_follow() {
+    let mut sim = star();
+    sim.run();
+    let ranked = sim.detect_emergent_leaders();
+    assert_eq!(ids(&ranked), vec![1, 2, 3, 4, 5], "{:?}", ranked);
+    assert!(ranked.windows(2).all(|w| w[0].1 > w[1].1), "{:?}", ranked);
+    assert!((ranked.iter().map(|(_, score)| score).sum::<Real>() - 1.0).abs() < 1e-5);
+    // The spokes pass all their rank on to the hub
+    assert!(ranked[0].1 > ranked[1..4].iter().map(|(_, score)| score).sum::<Real>() / 2.0);
+}
+
+#[test]
+fn without_interactions_everyone_ranks_equally_by_id() {
+    let ranked = star().detect_emergent_leaders();
+    assert_eq!(ids(&ranked), vec![1, 2, 3, 4, 5]);
+    assert!(ranked.iter().all(|(_, score)| (score - 0.2).abs() < 1e-6), "{:?}", ranked);
+}
@@ -0,0 +1,94 @@
+//! Follows lineage chains across generations: parents are born before
+//! their children, every chain ends at a founder, and the maximum lineage
+//! depth agrees with the ancestor chains, also for very deep lineages.
+//!
+//! ```bash
+//! cargo test --test lineage
+//! ```
+
+use synthetic_consciousness::reproduction::LineageRecord;
+use synthetic_consciousness::{Simulation, SimulationConfig, SimulationResults};
+
+fn record(entity_id: u32, parent_id: Option<u32>, birth_step: u64) -> LineageRecord {
+    LineageRecord { entity_id, parent_id, birth_step }
+}
+
+fn results(lineage: Vec<LineageRecord>) -> SimulationResults {
+    let mut results = SimulationResults::new("lineage".to_string(), 0, 0, String::new());
+    results.lineage = lineage;
+    results
+}
+
+#[test]
+fn chains_span_several_generations_of_a_run() {
+    let mut config = SimulationConfig::default_2d();
+    config.simulation.num_entities = 2;
+    config.simulation.num_steps = 400;
+    config.reproduction.enabled = true;
+    config.reproduction.max_population = 30;
+    config.reproduction.essence_threshold = 0.0;
+    config.reproduction.energy_threshold = 0.0;
+    config.reproduction.sustain_steps = 20;
+    let mut sim = Simulation::new(config).unwrap();
+    sim.run();
+
+    let results = &sim.results;
+    let birth = |id: u32| results.lineage.iter().find(|r| r.entity_id == id).unwrap().birth_step;
+    let mut deepest = 0;
+    for record in &results.lineage {
+        let chain = results.ancestors(record.entity_id);
+        // Each generation is born after the previous one, back to a founder
+        let mut child = record.entity_id;
+        for &ancestor in &chain {
+            assert!(birth(ancestor) < birth(child), "{} is not older than its child {}", ancestor, child);
+            child = ancestor;
+        }
+        assert!(chain.last().is_none_or(|&founder| founder <= 2));
+        deepest = deepest.max(chain.len());
+    }
+    assert!(deepest >= 2, "only {} generation(s) were born", deepest);
+    assert_eq!(results.max_lineage_depth(), deepest);
+}
+
+#[test]
+fn depths_follow_parents_listed_in_any_order() {
+    // Founders 1 and 2; 1 -> 3 -> 5 -> 6, 2 -> 4; 7 descends from an
+    // unrecorded parent 9; children listed before their parents
+    let results = results(vec![
+        record(6, Some(5), 30),
+        record(5, Some(3), 20),
+        record(7, Some(9), 5),
+        record(1, None, 0),
+        record(3, Some(1), 10),
+        record(4, Some(2), 10),
+        record(2, None, 0),
+    ]);
+    assert_eq!(results.ancestors(6), vec![5, 3, 1]);
+    assert_eq!(results.ancestors(7), vec![9]);
+    assert!(results.ancestors(2).is_empty());
+    assert_eq!(results.max_lineage_depth(), 3);
+
+    assert_eq!(self::results(Vec::new()).max_lineage_depth(), 0);
+    assert_eq!(self::results(vec![record(1, None, 0), record(2, None, 0)]).max_lineage_depth(), 0);
+}
+
+#[test]
+fn deep_lineages_are_measured_in_one_pass() {
+    // A single line of 20000 generations plus a side branch off its middle
+    const GENERATIONS: u32 = 20_000;
+    let mut lineage = vec![record(1, None, 0)];
+    lineage.extend((2..=GENERATIONS).map(|id| record(id, Some(id - 1), id as u64)));
+    lineage.push(record(GENERATIONS + 1, Some(GENERATIONS / 2), 0));
+    lineage.reverse();
+    let results = results(lineage);
+
+    assert_eq!(results.max_lineage_depth(), GENERATIONS as usize - 1);
+    assert_eq!(results.ancestors(GENERATIONS + 1).len(), GENERATIONS as usize / 2);
+}
+
+#[test]
+fn cycles_in_edited_results_terminate() {
+    let results = results(vec![record(1, Some(2), 0), record(2, Some(1), 0), record(3, Some(1), 0)]);
+    assert_eq!(results.ancestors(1), vec![2, 1]);
+    assert!(results.max_lineage_depth() >= 2);
+}
@@ -0,0 +1,63 @@
+//! Checks the memory graph's event arena: snapshots, merging and compaction.
+//!
+//! ```bash
+//! cargo test --test memory_arena
+//! ```
+
+use synthetic_consciousness::entities::CLUSTER_TAU;
+use synthetic_consciousness::memory::MemoryGraph;
+use synthetic_consciousness::Real;
+
+fn sample_graph() -> MemoryGraph {
+    let mut graph = MemoryGraph::new();
+    for t in 0..20u64 {
+        let x = t as Real / 10.0;
+        graph.add_clustered_event(&[x, 1.0 - x, 0.5], t, CLUSTER_TAU);
+    }
+    graph.add_edge(0, 1);
+    graph
+}
+
+fn events(graph: &MemoryGraph) -> Vec<Vec<Real>> {
+    (0..graph.nodes.len()).map(|idx| graph.event(idx).to_vec()).collect()
+}
+
+#[test]
+fn snapshots_keep_events_inline_and_rebuild_the_arena() {
+    let graph = sample_graph();
+    let json = serde_json::to_value(&graph).unwrap();
+    assert_eq!(json["nodes"][3]["event"], serde_json::json!([0.3 as Real, 1.0 - 0.3 as Real, 0.5]));
+
+    let restored: MemoryGraph = serde_json::from_value(json).unwrap();
+    assert_eq!(events(&restored), events(&graph));
+    assert_eq!(restored.clusters.len(), graph.clusters.len());
+    assert_eq!(restored.edges, graph.edges);
+}
+
+#[test]
+fn merged_nodes_copy_their_events() {
+    let mut target = MemoryGraph::new();
+    target.add_clustered_event(&[-1.0, 0.0, 0.0], 100, CLUSTER_TAU);
+    let source = sample_graph();
+    target.merge_from(&source, 0.5, CLUSTER_TAU);
+
+    assert_eq!(target.nodes.len(), 1 + source.nodes.len());
+    let mut merged = events(&target)[1..].to_vec();
+    let mut expected = events(&source);
+    merged.sort_by(|a, b| a.partial_cmp(b).unwrap());
+    expected.sort_by(|a, b| a.partial_cmp(b).unwrap());
+    assert_eq!(merged, expected);
+}
+
+#[test]
+fn compaction_drops_pruned_events() {
+    let mut graph = sample_graph();
+    let kept = events(&graph)[..5].to_vec();
+    graph.nodes.truncate(5);
+    graph.compact_events();
+
+    assert_eq!(events(&graph), kept);
+    for (idx, node) in graph.nodes.iter().enumerate() {
+        assert_eq!(node.event.offset, 3 * idx);
+    }
+}
@@ -0,0 +1,82 @@
+//! Checks insertion-time deduplication of near-identical memories.
+//!
+//! ```bash
+//! cargo test --test memory_dedup
+//! ```
+
+use rand::{rngs::StdRng, Rng, SeedableRng};
+use synthetic_consciousness::entities::CLUSTER_TAU;
+use synthetic_consciousness::memory::MemoryGraph;
+use synthetic_consciousness::{Real, Simulation, SimulationConfig};
+
+const EVENTS: u64 = 2000;
+const THRESHOLD: Real = 0.99;
+
+fn dedup_graph() -> MemoryGraph {
+    let mut graph = MemoryGraph::new();
+    graph.dedup_threshold = Some(THRESHOLD);
+    graph
+}
+
+#[test]
+fn constant_stimulus_plateaus() {
+    let mut rng = StdRng::seed_from_u64(3);
+    let mut graph = dedup_graph();
+    for t in 0..EVENTS {
+        // Small perturbations of one stimulus
+        let event = [1.0 + rng.gen_range(-0.01..0.01), 0.5 + rng.gen_range(-0.01..0.01)];
+        graph.add_clustered_event(&event, t, CLUSTER_TAU);
+    }
+
+    assert!(graph.nodes.len() <= 5, "{} nodes", graph.nodes.len());
+    let reinforcements: u32 = graph.nodes.iter().map(|node| node.reinforcements).sum();
+    assert_eq!(reinforcements as usize + graph.nodes.len(), EVENTS as usize);
+    let weight: Real = graph.clusters.values().map(|cluster| cluster.weight).sum();
+    assert!(weight > EVENTS as Real / 2.0);
+}
+
+#[test]
+fn varied_stimulus_keeps_growing() {
+    let mut rng = StdRng::seed_from_u64(4);
+    let mut graph = dedup_graph();
+    for t in 0..EVENTS {
+        let event: Vec<Real> = (0..8).map(|_| rng.gen_range(-1.0..1.0)).collect();
+        graph.add_clustered_event(&event, t, CLUSTER_TAU);
+    }
+    assert_eq!(graph.nodes.len(), EVENTS as usize);
+}
+
+#[test]
+fn reinforced_node_is_reactivated() {
+    let mut graph = dedup_graph();
+    let first = graph.add_clustered_event(&[1.0, 0.0], 0, CLUSTER_TAU);
+    graph.decay(0.5);
+    let again = graph.add_clustered_event(&[1.0, 0.001], 1, CLUSTER_TAU);
+
+    assert_eq!(again, first);
+    assert_eq!(graph.nodes.len(), 1);
+    assert_eq!(graph.nodes[first].activation, 1.0);
+    assert_eq!(graph.nodes[first].reinforcements, 1);
+    assert_eq!(graph.activation_entropy(), graph.exact_activation_entropy());
+}
+
+#[test]
+fn disabled_by_default_and_set_from_config() {
+    let mut graph = MemoryGraph::new();
+    for t in 0..10 {
+        graph.add_clustered_event(&[1.0, 0.0], t, CLUSTER_TAU);
+    }
+    assert_eq!(graph.nodes.len(), 10);
+
+    let mut config = SimulationConfig::default_2d();
+    config.state.dedup_threshold = Some(THRESHOLD);
+    let sim = Simulation::new(config.clone()).unwrap();
+    assert!(sim
+        .entities
+        .all_entities()
+        .iter()
+        .all(|entity| entity.memory_graph.dedup_threshold == Some(THRESHOLD)));
+
+    config.state.dedup_threshold = Some(1.5);
+    assert!(config.validate().is_err());
+}
@@ -0,0 +1,52 @@
+//! Information content of memory nodes: a lone memory carries no surprise,
+//! equally active alternatives carry `ln n` nats each.
+//!
+//! ```bash
+//! cargo test --test memory_information
+//! ```
+
+use synthetic_consciousness::entities::CLUSTER_TAU;
+use synthetic_consciousness::memory::MemoryGraph;
+use synthetic_consciousness::Real;
+
+#[test]
+fn a_single_node_carries_no_information() {
+    let mut graph = MemoryGraph::new();
+    graph.add_event(&[0.3, 0.7], 0);
+    assert_eq!(graph.information_content(0), 0.0);
+    assert_eq!(graph.mean_information_content(), 0.0);
+    // Whatever its activation
+    graph.set_activation(0, 0.05);
+    assert_eq!(graph.information_content(0), 0.0);
+
+    // The same for a node in a cluster of its own
+    let mut clustered = MemoryGraph::new();
+    let idx = clustered.add_clustered_event(&[0.3, 0.7], 0, CLUSTER_TAU);
+    assert_eq!(clustered.clusters.len(), 1);
+    assert_eq!(clustered.information_content(idx), 0.0);
+    assert_eq!(clustered.mean_information_content(), 0.0);
+}
+
+#[test]
+fn empty_graphs_and_missing_nodes_carry_none() {
+    let graph = MemoryGraph::new();
+    assert_eq!(graph.information_content(0), 0.0);
+    assert_eq!(graph.mean_information_content(), 0.0);
+
+    let mut graph = MemoryGraph::new();
+    graph.add_event(&[1.0, 0.0], 0);
+    assert_eq!(graph.information_content(1), 0.0);
+}
+
+#[test]
+fn equally_active_nodes_carry_ln_n() {
+    let mut graph = MemoryGraph::new();
+    for t in 0..4 {
+        graph.add_event(&[t as Real, 1.0], t);
+    }
+    let expected = (4.0 as Real).ln();
+    for idx in 0..4 {
+        assert!((graph.information_content(idx) - expected).abs() < 1e-5);
+    }
+    assert!((graph.mean_information_content() - expected).abs() < 1e-5);
+}
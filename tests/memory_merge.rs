@@ -0,0 +1,166 @@
+//! Checks belief sharing between entities: merged memory graphs stay
+//! bounded however often the same source is merged, a memory reaching the
+//! graph through different clusters is kept once, and dissimilar beliefs
+//! arrive as new, down-weighted clusters.
+//!
+//! ```bash
+//! cargo test --test memory_merge
+//! ```
+
+use rand::{rngs::StdRng, Rng, SeedableRng};
+use std::collections::BTreeSet;
+use synthetic_consciousness::entities::CLUSTER_TAU;
+use synthetic_consciousness::memory::MemoryGraph;
+use synthetic_consciousness::{Entity, Real, SimulationConfig};
+
+/// Entity that has sensed `events` random stimuli around `center`.
+fn experienced(rng: &mut StdRng, center: [Real; 2], events: u64) -> Entity {
+    let config = SimulationConfig::default_2d();
+    let mut entity = Entity::builder(2).build(&config).unwrap();
+    for t in 0..events {
+        let event = [center[0] + rng.gen_range(-0.3..0.3), center[1] + rng.gen_range(-0.3..0.3)];
+        entity.sense(&event, t);
+    }
+    entity
+}
+
+#[test]
+fn repeated_merges_stay_within_both_graphs() {
+    let mut rng = StdRng::seed_from_u64(7);
+    let mut receiver = experienced(&mut rng, [1.0, 0.2], 40);
+    let donor = experienced(&mut rng, [-0.5, 1.0], 60);
+    let (nodes, clusters) = (receiver.memory_graph.nodes.len(), receiver.memory_graph.clusters.len());
+    let bound = nodes + donor.memory_graph.nodes.len();
+
+    receiver.merge_memory_from(&donor, 0.5);
+    let once = receiver.memory_graph.nodes.len();
+    assert!(once > nodes && once <= bound, "{} nodes after one merge", once);
+    let clusters_once = receiver.memory_graph.clusters.len();
+    assert!(clusters_once <= clusters + donor.memory_graph.clusters.len());
+
+    // Merging the same source again adds nothing
+    for _ in 0..20 {
+        receiver.merge_memory_from(&donor, 0.5);
+    }
+    assert_eq!(receiver.memory_graph.nodes.len(), once);
+    assert_eq!(receiver.memory_graph.clusters.len(), clusters_once);
+    for cluster in receiver.memory_graph.clusters.values() {
+        assert!(cluster.node_indices.iter().all(|&idx| idx < once));
+    }
+}
+
+#[test]
+fn merging_between_a_growing_population_stays_bounded() {
+    let mut rng = StdRng::seed_from_u64(8);
+    let mut entities: Vec<Entity> = (0..4).map(|i| experienced(&mut rng, [i as Real, 1.0 - i as Real], 25)).collect();
+    let total: usize = entities.iter().map(|e| e.memory_graph.nodes.len()).sum();
+
+    // Everyone shares with everyone, over and over
+    for _ in 0..5 {
+        for i in 0..entities.len() {
+            for j in 0..entities.len() {
+                if i != j {
+                    let donor = entities[j].clone();
+                    entities[i].merge_memory_from(&donor, 0.3);
+                }
+            }
+        }
+    }
+    for entity in &entities {
+        assert!(entity.memory_graph.nodes.len() <= total, "{} nodes", entity.memory_graph.nodes.len());
+    }
+}
+
+#[test]
+fn dissimilar_beliefs_arrive_as_weakened_clusters() {
+    let mut rng = StdRng::seed_from_u64(9);
+    let mut receiver = experienced(&mut rng, [1.0, 0.0], 10);
+    let donor = experienced(&mut rng, [-1.0, 0.0], 10);
+    let before: Vec<u32> = receiver.memory_graph.clusters.keys().copied().collect();
+
+    receiver.merge_memory_from(&donor, 0.25);
+    let donor_weight: Real = donor.memory_graph.clusters.values().map(|c| c.weight).sum();
+    let added: Real = receiver
+        .memory_graph
+        .clusters
+        .iter()
+        .filter(|(id, _)| !before.contains(id))
+        .map(|(_, c)| c.weight)
+        .sum();
+    assert!(added > 0.0);
+    assert!((added - 0.25 * donor_weight).abs() < 1e-4, "added weight {}", added);
+}
+
+/// Add `event` at `timestamp`, joining the best cluster when `tau` is below
+/// -1 and always starting a new one when it is above 1.
+fn remember(graph: &mut MemoryGraph, event: [Real; 2], timestamp: u64, tau: Real) -> usize {
+    let idx = graph.add_event(&event, timestamp);
+    graph.cluster_event(&event, idx, tau);
+    idx
+}
+
+/// Cluster of the only node of `graph` with this timestamp.
+fn cluster_at(graph: &MemoryGraph, timestamp: u64) -> u32 {
+    let nodes: Vec<_> = graph.nodes.iter().filter(|node| node.timestamp == timestamp).collect();
+    assert_eq!(nodes.len(), 1, "{} nodes at t = {}", nodes.len(), timestamp);
+    nodes[0].cluster_id.unwrap()
+}
+
+#[test]
+fn known_memories_are_not_imported_into_another_cluster() {
+    // Receiver beliefs along x (cluster A) and along y (cluster B)
+    let mut receiver = MemoryGraph::new();
+    for t in 0..3 {
+        remember(&mut receiver, [1.0, 0.05 * t as Real], t, CLUSTER_TAU);
+        remember(&mut receiver, [0.05 * t as Real, 1.0], 10 + t, CLUSTER_TAU);
+    }
+    assert_eq!(receiver.clusters.len(), 2);
+    let (a, b) = (cluster_at(&receiver, 0), cluster_at(&receiver, 10));
+
+    // A first donor passes on memory n, which joins A
+    let mut first = MemoryGraph::new();
+    remember(&mut first, [0.9, 0.2], 100, CLUSTER_TAU);
+    receiver.merge_from(&first, 0.5, CLUSTER_TAU);
+    assert_eq!(cluster_at(&receiver, 100), a);
+    let nodes = receiver.nodes.len();
+
+    // A second donor holds p in an x cluster, and n among y memories: its
+    // two clusters map to A and B, and n is already in A
+    let mut second = MemoryGraph::new();
+    for t in 0..3 {
+        remember(&mut second, [0.05, 1.0], 102 + t, CLUSTER_TAU);
+    }
+    remember(&mut second, [0.9, 0.2], 100, -2.0);
+    remember(&mut second, [1.0, 0.05], 101, 2.0);
+    assert_eq!(second.clusters.len(), 2);
+    receiver.merge_from(&second, 0.5, CLUSTER_TAU);
+
+    assert_eq!(receiver.nodes.len(), nodes + 4);
+    assert_eq!(cluster_at(&receiver, 100), a);
+    assert_eq!(cluster_at(&receiver, 101), a);
+    assert!((102..105).all(|t| cluster_at(&receiver, t) == b));
+    assert_eq!(receiver.clusters.len(), 2);
+
+    // Either donor again adds nothing
+    receiver.merge_from(&first, 0.5, CLUSTER_TAU);
+    receiver.merge_from(&second, 0.5, CLUSTER_TAU);
+    assert_eq!(receiver.nodes.len(), nodes + 4);
+    assert_eq!(receiver.clusters.len(), 2);
+}
+
+#[test]
+fn a_new_cluster_of_known_memories_is_not_created() {
+    let mut receiver = MemoryGraph::new();
+    remember(&mut receiver, [1.0, 0.0], 0, CLUSTER_TAU);
+    remember(&mut receiver, [0.0, 1.0], 1, 2.0);
+    // Together they match neither receiver cluster, but both are known
+    let mut donor = MemoryGraph::new();
+    remember(&mut donor, [1.0, 0.0], 0, CLUSTER_TAU);
+    remember(&mut donor, [0.0, 1.0], 1, -2.0);
+
+    let cluster_ids = |graph: &MemoryGraph| graph.clusters.keys().copied().collect::<BTreeSet<u32>>();
+    let before = cluster_ids(&receiver);
+    receiver.merge_from(&donor, 0.5, 0.9);
+    assert_eq!(receiver.nodes.len(), 2);
+    assert_eq!(cluster_ids(&receiver), before);
+}
@@ -0,0 +1,96 @@
+//! Classifies hand-made memory graphs into the 13 connected directed triad
+//! types and checks the exact motif counts of small composite graphs.
+//!
+//! ```bash
+//! cargo test --test motifs
+//! ```
+
+use std::collections::HashMap;
+use synthetic_consciousness::memory::MemoryGraph;
+use synthetic_consciousness::Real;
+
+/// Graph of `nodes` memory nodes linked by `edges`.
+fn graph(nodes: usize, edges: &[(usize, usize)]) -> MemoryGraph {
+    let mut graph = MemoryGraph::new();
+    for t in 0..nodes {
+        graph.add_event(&[t as Real, 0.0], t as u64);
+    }
+    for &(src, dst) in edges {
+        graph.add_edge(src, dst);
+    }
+    graph
+}
+
+fn counts(entries: &[(&str, usize)]) -> HashMap<String, usize> {
+    entries.iter().map(|&(label, count)| (label.to_string(), count)).collect()
+}
+
+/// One triad per MAN class, on nodes (0, 1, 2).
+const TRIADS: [(&str, &[(usize, usize)]); 13] = [
+    ("021D", &[(0, 1), (0, 2)]),
+    ("021U", &[(1, 0), (2, 0)]),
+    ("021C", &[(0, 1), (1, 2)]),
+    ("111D", &[(0, 1), (1, 0), (2, 1)]),
+    ("111U", &[(0, 1), (1, 0), (1, 2)]),
+    ("030T", &[(0, 1), (1, 2), (0, 2)]),
+    ("030C", &[(0, 1), (1, 2), (2, 0)]),
+    ("201", &[(0, 1), (1, 0), (0, 2), (2, 0)]),
+    ("120D", &[(0, 1), (0, 2), (1, 2), (2, 1)]),
+    ("120U", &[(1, 0), (2, 0), (1, 2), (2, 1)]),
+    ("120C", &[(0, 1), (1, 2), (0, 2), (2, 0)]),
+    ("210", &[(0, 1), (1, 0), (0, 2), (2, 0), (1, 2)]),
+    ("300", &[(0, 1), (1, 0), (0, 2), (2, 0), (1, 2), (2, 1)]),
+];
+
+#[test]
+fn each_triad_is_classified_under_every_labelling() {
+    const RELABELLINGS: [[usize; 3]; 6] = [[0, 1, 2], [0, 2, 1], [1, 0, 2], [1, 2, 0], [2, 0, 1], [2, 1, 0]];
+    for (label, edges) in TRIADS {
+        for relabel in RELABELLINGS {
+            // Spread over a larger graph, so node indices are not 0..3
+            let nodes = relabel.map(|k| 2 + 3 * k);
+            let edges: Vec<_> = edges.iter().map(|&(a, b)| (nodes[a], nodes[b])).collect();
+            assert_eq!(graph(9, &edges).motif_detection(3), counts(&[(label, 1)]), "{} as {:?}", label, nodes);
+        }
+    }
+}
+
+#[test]
+fn disconnected_triads_are_not_counted() {
+    // 012 (a single edge) and 102 (a single mutual pair) leave a node out
+    assert!(graph(3, &[(0, 1)]).motif_detection(3).is_empty());
+    assert!(graph(3, &[(0, 1), (1, 0)]).motif_detection(3).is_empty());
+    assert!(graph(3, &[]).motif_detection(3).is_empty());
+}
+
+#[test]
+fn composite_graphs_have_exact_counts() {
+    // Transitive tournament on four nodes: every triad is 030T
+    let transitive = graph(4, &[(0, 1), (0, 2), (0, 3), (1, 2), (1, 3), (2, 3)]);
+    assert_eq!(transitive.motif_detection(3), counts(&[("030T", 4)]));
+
+    // Directed 3-cycle plus a tail 2 -> 3: the cycle, the chain 1 -> 2 -> 3
+    // and the fan-out 0 <- 2 -> 3; {0, 1, 3} is disconnected
+    let cycle = graph(4, &[(0, 1), (1, 2), (2, 0), (2, 3)]);
+    assert_eq!(cycle.motif_detection(3), counts(&[("030C", 1), ("021C", 1), ("021D", 1)]));
+
+    // Fully mutual four-clique, with self-loops and duplicate edges ignored
+    let mut mutual: Vec<(usize, usize)> = (0..4).flat_map(|a| (0..4).map(move |b| (a, b))).collect();
+    mutual.extend([(0, 1), (2, 3)]);
+    assert_eq!(graph(4, &mutual).motif_detection(3), counts(&[("300", 4)]));
+
+    // Directed 4-cycle: four open chains, no triangles
+    let square = graph(4, &[(0, 1), (1, 2), (2, 3), (3, 0)]);
+    assert_eq!(square.motif_detection(3), counts(&[("021C", 4)]));
+
+    // Star of mutual links around node 0: three 201 triads
+    let star = graph(4, &[(0, 1), (1, 0), (0, 2), (2, 0), (0, 3), (3, 0)]);
+    assert_eq!(star.motif_detection(3), counts(&[("201", 3)]));
+}
+
+#[test]
+fn only_triads_are_supported() {
+    let cycle = graph(3, &[(0, 1), (1, 2), (2, 0)]);
+    assert!(cycle.motif_detection(2).is_empty());
+    assert!(cycle.motif_detection(4).is_empty());
+}
@@ -0,0 +1,88 @@
+//! Narrative summary: exact text for a hand-made analysis, and a run's
+//! summary agreeing with its own results.
+//!
+//! ```bash
+//! cargo test --test narrative
+//! ```
+
+use synthetic_consciousness::{Metrics, Real, Simulation, SimulationConfig, SimulationResults, SimulationStep};
+
+/// Results of a 4-entity, 200-step run whose analysis holds `values`
+/// against `thresholds`, with one recorded step holding `signals`.
+fn results(values: &[(&str, Real, Real)], signals: &[Real]) -> SimulationResults {
+    let mut results = SimulationResults::new("narrative".to_string(), 4, 200, String::new());
+    results.duration_seconds = 20.0;
+    let analysis = &mut results.consciousness_analysis;
+    for &(name, value, threshold) in values {
+        analysis.metric_values.insert(name.to_string(), value);
+        analysis.metric_thresholds.insert(name.to_string(), threshold);
+        if value >= threshold {
+            analysis.passed_metrics.push(name.to_string());
+        } else {
+            analysis.failed_metrics.push(name.to_string());
+        }
+    }
+    analysis.consciousness_achieved = analysis.failed_metrics.is_empty();
+
+    let sim = Simulation::new(SimulationConfig::default_2d()).unwrap();
+    let mut step = SimulationStep::new(200, Metrics::compute(&sim.entities, 200));
+    step.belief_clusters = vec![(1, signals.iter().enumerate().map(|(k, s)| (k as u32, *s, 3)).collect())];
+    results.steps.push(step);
+    results
+}
+
+#[test]
+fn a_failed_run_names_its_weakest_metric() {
+    // attention_entropy is at 50% of its threshold, memory_diversity at 80%
+    let results = results(
+        &[("attention_entropy", 0.5, 1.0), ("memory_diversity", 0.4, 0.5), ("identity_coherence", 0.9, 0.6)],
+        &[0.3, 0.2, -0.1],
+    );
+    assert_eq!(
+        results.narrative_summary(),
+        "The simulation ran 4 entities for 200 steps (20.0s). 1/3 consciousness criteria were met. \
+         The lowest performing metric was attention entropy (0.50). Entities organized into 3 distinct \
+         belief clusters with a positive affective character. The system did not achieve the \
+         consciousness threshold."
+    );
+}
+
+#[test]
+fn a_successful_run_names_its_strongest_metric() {
+    let results = results(&[("attention_entropy", 1.2, 1.0), ("identity_coherence", 0.9, 0.6)], &[-0.4, -0.2]);
+    let summary = results.narrative_summary();
+    assert!(summary.contains("2/2 consciousness criteria were met."), "{}", summary);
+    assert!(summary.contains("The highest performing metric was identity coherence (0.90)."), "{}", summary);
+    assert!(summary.contains("2 distinct belief clusters with a negative affective character."), "{}", summary);
+    assert!(summary.ends_with("The system did achieve the consciousness threshold."), "{}", summary);
+
+    // Without recorded steps the cluster sentence is left out
+    let mut bare = results;
+    bare.steps.clear();
+    assert!(!bare.narrative_summary().contains("belief clusters"));
+    assert_eq!(bare.narrative_summary().matches(". ").count() + 1, 4);
+}
+
+#[test]
+fn a_run_summary_agrees_with_its_results() {
+    let mut config = SimulationConfig::default_2d();
+    config.simulation.num_entities = 6;
+    config.simulation.num_steps = 40;
+    let mut sim = Simulation::new(config).unwrap();
+    sim.run();
+    sim.finalize_results();
+    let results = &sim.results;
+    let summary = results.narrative_summary();
+
+    assert!(summary.starts_with("The simulation ran 6 entities for 40 steps"), "{}", summary);
+    let analysis = &results.consciousness_analysis;
+    let criteria = format!("{}/{} consciousness criteria", analysis.passed_metrics.len(), analysis.metric_thresholds.len());
+    assert!(summary.contains(&criteria), "{}", summary);
+    let clusters: usize = results.steps.last().unwrap().belief_clusters.iter().map(|(_, c)| c.len()).sum();
+    assert!(summary.contains(&format!("into {} distinct belief clusters", clusters)), "{}", summary);
+    let verdict = if analysis.consciousness_achieved { "did achieve" } else { "did not achieve" };
+    assert!(summary.contains(verdict), "{}", summary);
+    // Three to five sentences
+    let sentences = summary.matches(". ").count() + 1;
+    assert!((3..=5).contains(&sentences), "{}", summary);
+}
@@ -0,0 +1,113 @@
+//! Checks the pool's neighbor queries, with and without the spatial grid,
+//! against a brute-force search on random populations.
+//!
+//! ```bash
+//! cargo test --test neighbors
+//! ```
+
+use rand::{rngs::StdRng, Rng, SeedableRng};
+use synthetic_consciousness::entities::{EntityId, EntityPool};
+use synthetic_consciousness::geometry::{periodic_distance, GeometryConfig};
+use synthetic_consciousness::{Entity, Real, SimulationConfig};
+
+/// Random population of `count` entities in `geometry`.
+fn random_pool(rng: &mut StdRng, count: usize, geometry: &GeometryConfig) -> EntityPool {
+    let mut config = SimulationConfig::default_2d();
+    config.geometry = geometry.clone();
+    let mut pool = EntityPool::new();
+    for _ in 0..count {
+        let position = geometry.bounds.iter().map(|b| rng.gen_range(0.0..*b)).collect();
+        pool.add_entity(Entity::builder(geometry.dimension).position(position).build(&config).unwrap());
+    }
+    pool
+}
+
+/// Every other entity with its distance, sorted by distance then id.
+fn brute_force(pool: &EntityPool, id: EntityId, geometry: &GeometryConfig) -> Vec<(EntityId, Real)> {
+    let origin = pool.get_entity(id).unwrap();
+    let mut all: Vec<_> = pool
+        .iter()
+        .filter(|other| other.id != id)
+        .map(|other| (other.id, periodic_distance(&origin.pose.position, &other.pose.position, geometry)))
+        .collect();
+    all.sort_by(|a, b| a.1.total_cmp(&b.1).then(a.0.cmp(&b.0)));
+    all
+}
+
+/// Open and periodic worlds in 2D and 3D, small enough for wrapped
+/// neighbors to be common.
+fn geometries() -> Vec<GeometryConfig> {
+    let world = |bounds: Vec<Real>, periodic: bool| GeometryConfig { dimension: bounds.len(), bounds, periodic };
+    vec![
+        world(vec![20.0, 10.0], false),
+        world(vec![20.0, 10.0], true),
+        world(vec![10.0, 10.0, 10.0], false),
+        world(vec![10.0, 10.0, 10.0], true),
+    ]
+}
+
+#[test]
+fn nearest_distance_matches_brute_force_with_and_without_the_grid() {
+    let mut rng = StdRng::seed_from_u64(5);
+    for geometry in geometries() {
+        for count in [1, 2, 30, 300] {
+            let mut pool = random_pool(&mut rng, count, &geometry);
+            let ids: Vec<EntityId> = pool.iter().map(|e| e.id).collect();
+            // The rebuilt grid reuses its buffers, so it is refilled after moves
+            for (round, cell_size) in [None, Some(0.3), Some(1.0), Some(1.0), Some(6.0)].into_iter().enumerate() {
+                if let Some(cell_size) = cell_size {
+                    pool.index_positions(cell_size);
+                }
+                for &id in &ids {
+                    let expected = brute_force(&pool, id, &geometry).first().map(|&(_, d)| d);
+                    assert_eq!(pool.nearest_distance(id, &geometry), expected, "{} entities in {:?}", count, geometry);
+                    assert_eq!(pool.nearest(id, 1, &geometry).first().map(|&(_, d)| d), expected);
+                }
+                let moved = ids[round % ids.len()];
+                let position = geometry.bounds.iter().map(|b| rng.gen_range(0.0..*b)).collect::<Vec<Real>>();
+                pool.get_entity_mut(moved).unwrap().pose.position = position.into();
+            }
+        }
+    }
+}
+
+#[test]
+fn queries_match_brute_force_with_and_without_the_grid() {
+    let mut rng = StdRng::seed_from_u64(11);
+    for geometry in geometries() {
+        let mut pool = random_pool(&mut rng, 150, &geometry);
+        let ids: Vec<EntityId> = pool.iter().map(|e| e.id).collect();
+        for cell_size in [None, Some(1.0), Some(2.5), Some(8.0)] {
+            if let Some(cell_size) = cell_size {
+                pool.index_positions(cell_size);
+            }
+            assert_eq!(pool.has_position_index(), cell_size.is_some());
+            for &id in ids.iter().step_by(7) {
+                let expected = brute_force(&pool, id, &geometry);
+                for radius in [0.5, 2.0, 4.5] {
+                    let within: Vec<_> = expected.iter().copied().filter(|(_, d)| *d <= radius).collect();
+                    assert_eq!(pool.neighbors_within(id, radius, &geometry), within, "radius {} in {:?}", radius, geometry);
+                }
+                for k in [0, 1, 5, 40, 200] {
+                    let nearest: Vec<_> = expected.iter().copied().take(k).collect();
+                    assert_eq!(pool.nearest(id, k, &geometry), nearest, "k {} in {:?}", k, geometry);
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn mutable_access_drops_the_grid() {
+    let geometry = GeometryConfig::default_2d();
+    let mut pool = random_pool(&mut StdRng::seed_from_u64(5), 20, &geometry);
+    pool.index_positions(5.0);
+    let id = pool.iter().next().unwrap().id;
+
+    // A moved entity must not be found where the grid last saw it
+    pool.get_entity_mut(id).unwrap().pose.position = vec![99.0, 99.0].into();
+    assert!(!pool.has_position_index());
+    let within: Vec<_> = brute_force(&pool, id, &geometry).into_iter().filter(|(_, d)| *d <= 5.0).collect();
+    assert_eq!(pool.neighbors_within(id, 5.0, &geometry), within);
+    assert!(pool.neighbors_within(EntityId(999), 5.0, &geometry).is_empty());
+}
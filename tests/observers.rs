@@ -0,0 +1,103 @@
+//! Attaches step observers to a simulation and checks when they are called
+//! and that returning `StepControl::Stop` ends the run.
+//!
+//! ```bash
+//! cargo test --test observers
+//! ```
+
+use std::sync::{Arc, Mutex};
+use synthetic_consciousness::plugin::{SimulationObserver, StepControl};
+use synthetic_consciousness::{Simulation, SimulationConfig, SimulationResults};
+
+fn config() -> SimulationConfig {
+    let mut config = SimulationConfig::default_2d();
+    config.simulation.num_entities = 4;
+    config.simulation.num_steps = 30;
+    config
+}
+
+/// (observer name, step, timestamp of the latest metrics) per call.
+type Calls = Arc<Mutex<Vec<(&'static str, u64, u64)>>>;
+
+/// Records its calls and optionally stops at a step.
+struct Recorder {
+    name: &'static str,
+    stop_at: Option<u64>,
+    calls: Calls,
+    finished: Arc<Mutex<bool>>,
+}
+
+impl SimulationObserver for Recorder {
+    fn on_step(&mut self, sim: &Simulation) -> StepControl {
+        let recorded = sim.metrics_history.last().map_or(0, |m| m.timestamp);
+        self.calls.lock().unwrap().push((self.name, sim.timestamp, recorded));
+        if self.stop_at == Some(sim.timestamp) {
+            StepControl::Stop
+        } else {
+            StepControl::Continue
+        }
+    }
+
+    fn on_finish(&mut self, _results: &SimulationResults) {
+        *self.finished.lock().unwrap() = true;
+    }
+}
+
+#[test]
+fn observers_see_every_step_in_order() {
+    let calls = Arc::new(Mutex::new(Vec::new()));
+    let finished = Arc::new(Mutex::new(false));
+    let mut sim = Simulation::new(config()).unwrap();
+    for name in ["first", "second"] {
+        sim.add_observer(Box::new(Recorder {
+            name,
+            stop_at: None,
+            calls: Arc::clone(&calls),
+            finished: Arc::clone(&finished),
+        }));
+    }
+    sim.run();
+    assert!(!sim.stop_requested());
+    sim.finalize_results();
+    assert!(*finished.lock().unwrap());
+
+    let calls = calls.lock().unwrap();
+    assert_eq!(calls.len(), 60);
+    assert_eq!(calls[0].0, "first");
+    assert_eq!(calls[1].0, "second");
+    // Called after the step's metrics were recorded
+    assert!(calls.iter().all(|(_, step, recorded)| *recorded + 1 == *step));
+    assert_eq!(calls.last().unwrap().1, 30);
+}
+
+#[test]
+fn stop_ends_the_run_after_every_observer_saw_the_step() {
+    let calls = Arc::new(Mutex::new(Vec::new()));
+    let finished = Arc::new(Mutex::new(false));
+    let mut sim = Simulation::new(config()).unwrap();
+    sim.add_observer(Box::new(Recorder {
+        name: "stopper",
+        stop_at: Some(12),
+        calls: Arc::clone(&calls),
+        finished: Arc::clone(&finished),
+    }));
+    sim.add_observer(Box::new(Recorder {
+        name: "watcher",
+        stop_at: None,
+        calls: Arc::clone(&calls),
+        finished: Arc::clone(&finished),
+    }));
+    sim.run();
+
+    assert_eq!(sim.timestamp, 12);
+    assert!(sim.stop_requested());
+    assert_eq!(calls.lock().unwrap().last(), Some(&("watcher", 12, 11)));
+    assert_eq!(sim.metrics_history.len(), 12);
+
+    // A stopped run still finalizes, and run() does not continue it
+    sim.run();
+    assert_eq!(sim.timestamp, 12);
+    sim.finalize_results();
+    assert!(*finished.lock().unwrap());
+    assert_eq!(sim.results.steps.len(), sim.metrics_history.len());
+}
@@ -0,0 +1,32 @@
+//! Checks that parallel and serial metric evaluation agree bit for bit.
+//!
+//! ```bash
+//! cargo test --test parallel_metrics
+//! ```
+
+use synthetic_consciousness::metrics::PARALLEL_MIN_ENTITIES;
+use synthetic_consciousness::{Metrics, Simulation, SimulationConfig};
+
+#[test]
+fn parallel_and_serial_metrics_are_identical() {
+    let mut config = SimulationConfig::default_2d();
+    config.simulation.num_entities = PARALLEL_MIN_ENTITIES as u32 + 88;
+    let mut sim = Simulation::new(config).unwrap();
+    // Build up memory graphs, clusters and motion
+    for _ in 0..5 {
+        sim.step();
+    }
+
+    let entities = sim.entities.all_entities();
+    let serial = Metrics::compute_with(&entities, sim.timestamp, false);
+    let parallel = Metrics::compute_with(&entities, sim.timestamp, true);
+
+    for ((name, s), p) in Metrics::FIELD_NAMES
+        .iter()
+        .zip(serial.field_values())
+        .zip(parallel.field_values())
+    {
+        assert_eq!(s.to_bits(), p.to_bits(), "{} differs: serial {} vs parallel {}", name, s, p);
+    }
+    assert!(serial.attention_entropy > 0.0);
+}
@@ -0,0 +1,73 @@
+//! Partitioning the population by role: every entity lands in exactly one
+//! group, ordered by id, with unassigned entities counted as members.
+//!
+//! ```bash
+//! cargo test --test partition
+//! ```
+
+use std::collections::{BTreeSet, HashMap};
+use synthetic_consciousness::entities::{EntityId, EntityPool};
+use synthetic_consciousness::roles::Role;
+use synthetic_consciousness::{Simulation, SimulationConfig};
+
+/// Every pool entity appears in exactly one group, each group ascending.
+fn assert_partition(pool: &EntityPool, roles: &HashMap<EntityId, Role>) -> HashMap<Role, Vec<u32>> {
+    let groups: HashMap<Role, Vec<u32>> = pool
+        .partition_by_role(roles)
+        .into_iter()
+        .map(|(role, entities)| (role, entities.iter().map(|e| e.id.0).collect()))
+        .collect();
+    let mut seen = BTreeSet::new();
+    for (role, ids) in &groups {
+        assert!(!ids.is_empty(), "empty {:?} group", role);
+        assert!(ids.windows(2).all(|w| w[0] < w[1]), "{:?} {:?}", role, ids);
+        for id in ids {
+            assert!(seen.insert(*id), "entity {} in two groups", id);
+        }
+    }
+    let all: BTreeSet<u32> = pool.all_entities().iter().map(|e| e.id.0).collect();
+    assert_eq!(seen, all);
+    groups
+}
+
+#[test]
+fn assigned_roles_cover_every_entity_once() {
+    let mut config = SimulationConfig::default_2d();
+    config.simulation.num_entities = 9;
+    let sim = Simulation::new(config).unwrap();
+
+    // 1 and 5 lead, 2, 3 and 7 follow; the rest are unassigned, and an id
+    // outside the pool is ignored
+    let mut roles: HashMap<EntityId, Role> =
+        [(1, Role::Leader), (5, Role::Leader), (2, Role::Follower), (3, Role::Follower), (7, Role::Follower)]
+            .into_iter()
+            .map(|(id, role)| (EntityId(id), role))
+            .collect();
+    roles.insert(EntityId(42), Role::Leader);
+    roles.insert(EntityId(9), Role::Member);
+
+    let groups = assert_partition(&sim.entities, &roles);
+    assert_eq!(groups[&Role::Leader], vec![1, 5]);
+    assert_eq!(groups[&Role::Follower], vec![2, 3, 7]);
+    assert_eq!(groups[&Role::Member], vec![4, 6, 8, 9]);
+
+    // Without any roles, everyone is a member
+    let groups = assert_partition(&sim.entities, &HashMap::new());
+    assert_eq!(groups.len(), 1);
+    assert_eq!(groups[&Role::Member], (1..=9).collect::<Vec<_>>());
+}
+
+#[test]
+fn roles_of_a_run_cover_every_entity_once() {
+    let mut config = SimulationConfig::default_2d();
+    config.simulation.num_entities = 20;
+    config.simulation.num_steps = 50;
+    config.roles.enabled = true;
+    let mut sim = Simulation::new(config).unwrap();
+    sim.run();
+    let roles = sim.entities.roles();
+    let groups = assert_partition(&sim.entities, &roles);
+    for (role, ids) in groups {
+        assert!(ids.iter().all(|id| roles[&EntityId(*id)] == role));
+    }
+}
@@ -0,0 +1,86 @@
+//! Removes and adds entities in the middle of a run and checks that the
+//! population, the recorded steps and the metrics stay consistent.
+//!
+//! ```bash
+//! cargo test --test perturbation
+//! ```
+
+use std::collections::BTreeSet;
+use synthetic_consciousness::entities::EntityId;
+use synthetic_consciousness::geometry::Pose;
+use synthetic_consciousness::{Simulation, SimulationConfig};
+
+const ENTITIES: u32 = 20;
+
+fn simulation() -> Simulation {
+    let mut config = SimulationConfig::default_2d();
+    config.simulation.num_entities = ENTITIES;
+    config.simulation.num_steps = 600;
+    config.roles.enabled = true;
+    Simulation::new(config).unwrap()
+}
+
+/// Every id recorded in the latest step belongs to a living entity.
+fn assert_latest_step_consistent(sim: &Simulation) {
+    let living: BTreeSet<u32> = sim.sorted_entity_ids().iter().map(|id| id.0).collect();
+    let step = sim.results.steps.last().unwrap();
+    let recorded: BTreeSet<u32> = step.entity_positions.iter().map(|(id, _)| *id).collect();
+    assert_eq!(recorded, living);
+    assert!(step.attractions.iter().all(|(a, b, _)| living.contains(a) && living.contains(b)));
+    assert!(step.entity_roles.iter().all(|(id, _)| living.contains(id)));
+    assert_eq!(step.attentions.len(), living.len());
+    assert!(step.attentions.iter().all(|(id, row)| living.contains(id) && row.len() == living.len()));
+    for id in &living {
+        let follows = sim.entities.get_entity(EntityId(*id)).unwrap().follows;
+        assert!(follows.is_none_or(|leader| living.contains(&leader.0)));
+    }
+}
+
+#[test]
+fn removing_half_the_entities_mid_run_keeps_metrics_computing() {
+    let mut sim = simulation();
+    for _ in 0..500 {
+        sim.step();
+    }
+
+    let ids = sim.sorted_entity_ids();
+    for id in ids.iter().step_by(2) {
+        assert!(sim.remove_entity(*id));
+    }
+    assert!(!sim.remove_entity(ids[0]), "already removed");
+    assert_eq!(sim.entities.count(), ENTITIES as usize / 2);
+    assert_eq!(sim.results.num_entities, ENTITIES / 2);
+
+    for _ in 0..100 {
+        sim.step();
+        assert_latest_step_consistent(&sim);
+    }
+    assert_eq!(sim.metrics_history.len(), 600);
+    let after = &sim.metrics_history[500..];
+    assert!(after.iter().all(|m| m.attention_entropy.is_finite() && m.cluster_stability.is_finite()));
+
+    sim.finalize_results();
+    assert_eq!(sim.results.num_entities, ENTITIES / 2);
+}
+
+#[test]
+fn a_naive_entity_joins_mid_run() {
+    let mut sim = simulation();
+    for _ in 0..50 {
+        sim.step();
+    }
+    let mut pose = Pose::new(2);
+    pose.position = vec![5.0, 5.0].into();
+    let id = sim.spawn_entity(Some(pose)).unwrap();
+    assert_eq!(sim.results.num_entities, ENTITIES + 1);
+    assert!(sim.entities.get_entity(id).unwrap().memory_graph.nodes.is_empty());
+
+    sim.step();
+    assert_latest_step_consistent(&sim);
+    assert!(sim.results.steps.last().unwrap().entity_positions.iter().any(|(e, _)| *e == id.0));
+
+    // Ids are never reused
+    assert!(sim.remove_entity(id));
+    let next = sim.spawn_entity(None).unwrap();
+    assert!(next.0 > id.0);
+}
@@ -0,0 +1,79 @@
+//! Profiles the phases of a step and checks the accumulated timings, that
+//! nothing is timed unless asked, and the timing table of the text report.
+//!
+//! ```bash
+//! cargo test --test phase_timings
+//! ```
+
+use std::time::Duration;
+use synthetic_consciousness::profiling::{Phase, PhaseStats};
+use synthetic_consciousness::{Simulation, SimulationConfig};
+
+fn simulation(profile: bool) -> Simulation {
+    let mut config = SimulationConfig::default_2d();
+    config.simulation.num_entities = 12;
+    config.simulation.num_steps = 25;
+    config.simulation.profile_phases = profile;
+    Simulation::new(config).unwrap()
+}
+
+#[test]
+fn stats_accumulate_min_mean_and_max() {
+    let mut stats = PhaseStats::default();
+    assert_eq!(stats.mean(), Duration::ZERO);
+    for micros in [30, 10, 20] {
+        stats.record(Duration::from_micros(micros));
+    }
+    assert_eq!(stats.count, 3);
+    assert_eq!(stats.min, Duration::from_micros(10));
+    assert_eq!(stats.max, Duration::from_micros(30));
+    assert_eq!(stats.mean(), Duration::from_micros(20));
+    assert_eq!(stats.total, Duration::from_micros(60));
+}
+
+#[test]
+fn every_phase_is_timed_once_per_step() {
+    let mut sim = simulation(true);
+    sim.run();
+    let timings = sim.phase_timings().unwrap();
+    assert_eq!(timings.iter().map(|(phase, _)| phase).collect::<Vec<_>>(), Phase::ALL);
+    for (phase, stats) in timings.iter() {
+        assert_eq!(stats.count, 25, "{} phase", phase);
+        assert!(stats.min <= stats.mean() && stats.mean() <= stats.max, "{} phase", phase);
+    }
+    assert!(timings.total() > Duration::ZERO);
+
+    sim.reset(None).unwrap();
+    assert!(sim.phase_timings().unwrap().is_empty());
+}
+
+#[test]
+fn profiling_is_off_by_default() {
+    let mut sim = simulation(false);
+    sim.run();
+    sim.finalize_results();
+    assert!(sim.phase_timings().is_none());
+    assert!(sim.results.phase_timings.is_none());
+}
+
+#[test]
+fn results_and_report_carry_the_timings() {
+    let mut sim = simulation(true);
+    sim.run();
+    sim.finalize_results();
+    let timings = sim.results.phase_timings.clone().unwrap();
+    assert_eq!(timings.get(Phase::Sense).count, 25);
+
+    let json = serde_json::to_string(&sim.results).unwrap();
+    let back: synthetic_consciousness::SimulationResults = serde_json::from_str(&json).unwrap();
+    assert_eq!(back.phase_timings, Some(timings));
+
+    let path = std::env::temp_dir().join(format!("sc_phase_timings_{}.txt", std::process::id()));
+    sim.results.generate_text_report(path.to_str().unwrap()).unwrap();
+    let report = std::fs::read_to_string(&path).unwrap();
+    std::fs::remove_file(path).unwrap();
+    assert!(report.contains("PHASE TIMINGS"));
+    for phase in Phase::ALL {
+        assert!(report.lines().any(|line| line.starts_with(phase.name())), "no {} row", phase);
+    }
+}
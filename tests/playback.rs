@@ -0,0 +1,97 @@
+//! Loads saved results and builds the replay visualization state without
+//! opening a window.
+//!
+//! ```bash
+//! cargo test --test playback
+//! ```
+
+#![cfg(feature = "gui")]
+
+use std::path::PathBuf;
+use synthetic_consciousness::results::SimulationResults;
+use synthetic_consciousness::visualization::Playback;
+use synthetic_consciousness::{Simulation, SimulationConfig};
+
+/// Results of a short finished run.
+fn finished_run() -> SimulationResults {
+    let mut config = SimulationConfig::default_2d();
+    config.simulation.num_entities = 4;
+    config.simulation.num_steps = 30;
+    let mut sim = Simulation::new(config).unwrap();
+    sim.run();
+    sim.finalize_results();
+    sim.results
+}
+
+fn temp_path(name: &str) -> PathBuf {
+    std::env::temp_dir().join(format!("sc_playback_{}_{}", std::process::id(), name))
+}
+
+#[test]
+fn compressed_results_round_trip() {
+    let results = finished_run();
+    let path = temp_path("results.json.gz");
+    results.save_json(path.to_str().unwrap()).unwrap();
+    let bytes = std::fs::read(&path).unwrap();
+    assert_eq!(&bytes[..2], &[0x1f, 0x8b], "not gzip-compressed");
+
+    let loaded = SimulationResults::load(path.to_str().unwrap()).unwrap();
+    let _ = std::fs::remove_file(&path);
+    assert_eq!(loaded.steps.len(), results.steps.len());
+    assert_eq!(loaded.world_bounds, results.world_bounds);
+    assert_eq!(loaded.config_fingerprint, results.config_fingerprint);
+}
+
+#[test]
+fn initial_state_shows_the_start_step_with_saved_geometry() {
+    let results = finished_run();
+    let path = temp_path("results.json");
+    results.save_json(path.to_str().unwrap()).unwrap();
+    let loaded = SimulationResults::load(path.to_str().unwrap()).unwrap();
+    let _ = std::fs::remove_file(&path);
+
+    let start = results.steps[10].step_number;
+    let playback = Playback::new(loaded, 2.0, start).unwrap();
+    assert_eq!(playback.frame_count(), results.steps.len() - 10);
+
+    let state = playback.initial_state();
+    assert!(state.replay);
+    assert_eq!(state.step, start);
+    assert_eq!(state.dimension, results.world_bounds.len());
+    assert_eq!(state.bounds.len(), results.world_bounds.len());
+    assert_eq!(state.entities.len(), results.steps[10].entity_positions.len());
+    assert_eq!(state.metrics.steps.len(), 11);
+    for entity in &state.entities {
+        let (_, position) = results.steps[10].entity_positions.iter().find(|(id, _)| *id == entity.id).unwrap();
+        assert_eq!(entity.position.len(), position.len());
+    }
+}
+
+#[test]
+fn unusable_playback_settings_are_rejected() {
+    let results = finished_run();
+    let last = results.steps.last().unwrap().step_number;
+    assert!(Playback::new(results.clone(), 0.0, 0).err().unwrap().contains("speed"));
+    let error = Playback::new(results, 1.0, last + 1).err().unwrap();
+    assert!(error.contains("after the last recorded step"), "{}", error);
+}
+
+#[test]
+fn missing_and_corrupt_files_give_readable_errors() {
+    let missing = temp_path("missing.json.gz");
+    let error = SimulationResults::load(missing.to_str().unwrap()).unwrap_err().to_string();
+    assert!(error.contains("cannot read results file"), "{}", error);
+    assert!(error.contains("missing.json.gz"), "{}", error);
+
+    let corrupt = temp_path("corrupt.json");
+    std::fs::write(&corrupt, "{\"steps\": [").unwrap();
+    let error = SimulationResults::load(corrupt.to_str().unwrap()).unwrap_err().to_string();
+    let _ = std::fs::remove_file(&corrupt);
+    assert!(error.contains("is not a simulation results file"), "{}", error);
+
+    let truncated = temp_path("truncated.json.gz");
+    std::fs::write(&truncated, [0x1f, 0x8b, 0x08, 0x00]).unwrap();
+    let error = SimulationResults::load(truncated.to_str().unwrap()).unwrap_err().to_string();
+    let _ = std::fs::remove_file(&truncated);
+    assert!(error.contains("is not a valid gzip file"), "{}", error);
+}
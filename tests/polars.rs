@@ -0,0 +1,94 @@
+//! Checks the polars frames against hand-computed values.
+//!
+//! `cargo test --features polars --test polars`
+
+#![cfg(feature = "polars")]
+
+use polars::prelude::*;
+use synthetic_consciousness::analysis::{
+    metrics_dataframe, per_entity_summary, rolling_metric_means, trajectories_dataframe,
+};
+use synthetic_consciousness::entities::EntityPool;
+use synthetic_consciousness::{Metrics, Real, SimulationResults, SimulationStep};
+
+/// Polars type of floating-point columns.
+const REAL_TYPE: DataType = if cfg!(feature = "f64") { DataType::Float64 } else { DataType::Float32 };
+
+fn metrics_at(step: u64, average_essence: Real) -> Metrics {
+    let mut metrics = Metrics::compute(&EntityPool::new(), step);
+    metrics.average_essence = average_essence;
+    metrics
+}
+
+/// Two entities over three steps in 2D.
+fn hand_built_results() -> SimulationResults {
+    let mut results = SimulationResults::new("hand built".to_string(), 2, 3, String::new());
+    for (step, essences) in [(1u64, [4.0 as Real, 6.0]), (2, [5.0, 7.0]), (3, [6.0, 2.0])] {
+        let mut record = SimulationStep::new(step, metrics_at(step, (essences[0] + essences[1]) / 2.0));
+        // Listed out of id order on purpose
+        record.entity_positions = vec![(2, vec![1.0, 1.0].into()), (1, vec![step as Real, 0.0].into())];
+        record.entity_velocities = vec![(1, vec![3.0, 4.0].into()), (2, vec![0.0, 1.0].into())];
+        record.entity_essence = vec![(1, essences[0]), (2, essences[1])];
+        record.entity_energy = vec![(1, 1.0), (2, 0.5)];
+        results.add_step(record);
+    }
+    results
+}
+
+#[test]
+fn metrics_frame_has_typed_columns() {
+    let metrics: Vec<Metrics> = hand_built_results().steps.iter().map(|s| s.metrics.clone()).collect();
+    let df = metrics_dataframe(&metrics).unwrap();
+
+    assert_eq!(df.height(), 3);
+    assert_eq!(df.get_column_names()[0].as_str(), "step");
+    assert_eq!(df.column("step").unwrap().dtype(), &DataType::UInt64);
+    for name in Metrics::FIELD_NAMES {
+        assert_eq!(df.column(name).unwrap().dtype(), &REAL_TYPE, "{}", name);
+    }
+
+    let smoothed = rolling_metric_means(&df, 2).unwrap();
+    let essence: Vec<Option<f64>> = smoothed
+        .column("average_essence")
+        .unwrap()
+        .cast(&DataType::Float64)
+        .unwrap()
+        .f64()
+        .unwrap()
+        .into_iter()
+        .collect();
+    // Raw averages are 5, 6, 4
+    assert_eq!(essence, [Some(5.0), Some(5.5), Some(5.0)]);
+}
+
+#[test]
+fn trajectories_frame_and_entity_summary() {
+    let df = trajectories_dataframe(&hand_built_results()).unwrap();
+
+    assert_eq!(df.height(), 6);
+    assert_eq!(df.column("entity_id").unwrap().dtype(), &DataType::UInt32);
+    assert_eq!(df.column("x").unwrap().dtype(), &REAL_TYPE);
+    assert_eq!(df.column("z").unwrap().null_count(), 6);
+    let ids: Vec<Option<u32>> = df.column("entity_id").unwrap().u32().unwrap().into_iter().collect();
+    assert_eq!(ids, [Some(1), Some(2), Some(1), Some(2), Some(1), Some(2)]);
+
+    let summary = per_entity_summary(&df).unwrap();
+    assert_eq!(summary.height(), 2);
+    let column = |name: &str| -> Vec<f64> {
+        summary
+            .column(name)
+            .unwrap()
+            .cast(&DataType::Float64)
+            .unwrap()
+            .f64()
+            .unwrap()
+            .into_no_null_iter()
+            .collect()
+    };
+    assert_eq!(column("samples"), [3.0, 3.0]);
+    assert_eq!(column("mean_essence"), [5.0, 5.0]);
+    assert_eq!(column("min_essence"), [4.0, 2.0]);
+    assert_eq!(column("max_essence"), [6.0, 7.0]);
+    assert_eq!(column("mean_energy"), [1.0, 0.5]);
+    assert_eq!(column("mean_speed"), [5.0, 1.0]);
+}
@@ -0,0 +1,56 @@
+//! Compares a long essence trajectory against its closed-form solution.
+//!
+//! With a constant affective signal `s`, the essence update
+//! `v' = v + (b - v)·d + s·k` relaxes to `v* = b + s·k/d` as
+//! `v_n = v* + (v_0 - v*)(1 - d)^n`. The reference is evaluated in f64
+//! from the same (possibly f32-rounded) parameters, so any gap is
+//! accumulation error of the model's `Real`.
+//!
+//! ```bash
+//! cargo test --test precision
+//! cargo test --features f64 --test precision
+//! ```
+
+use synthetic_consciousness::essence::{EssenceConfig, EssenceIndex};
+use synthetic_consciousness::real::{to_f64, Real};
+
+const STEPS: usize = 200_000;
+const SIGNAL: Real = 0.0005;
+
+/// Largest gap between the simulated trajectory and the closed form.
+fn max_trajectory_error() -> f64 {
+    let config = EssenceConfig {
+        baseline: 5.0,
+        decay: 0.001,
+        experience_scale: 1.0,
+    };
+    let (b, d) = (to_f64(config.baseline), to_f64(config.decay));
+    let drive = to_f64(SIGNAL) * to_f64(config.experience_scale);
+    let fixed_point = b + drive / d;
+    let mut essence = EssenceIndex::new(config);
+    let start = to_f64(essence.value);
+
+    let mut max_error: f64 = 0.0;
+    for n in 1..=STEPS {
+        essence.update(&[SIGNAL]);
+        let reference = fixed_point + (start - fixed_point) * (n as f64 * (1.0 - d).ln()).exp();
+        max_error = max_error.max((to_f64(essence.value) - reference).abs());
+    }
+    max_error
+}
+
+#[cfg(feature = "f64")]
+#[test]
+fn f64_essence_matches_closed_form() {
+    let error = max_trajectory_error();
+    assert!(error < 1e-9, "f64 essence drifted by {:e}", error);
+}
+
+/// Documents why the `f64` feature exists: single precision stalls once
+/// the per-step change falls below half an ulp of the essence value.
+#[cfg(not(feature = "f64"))]
+#[test]
+fn f32_essence_drifts_from_closed_form() {
+    let error = max_trajectory_error();
+    assert!(error > 1e-6, "f32 essence unexpectedly within {:e}", error);
+}
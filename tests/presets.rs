@@ -0,0 +1,57 @@
+//! Research presets: every preset is valid and runs, and the presets that
+//! name a mechanism switch it on.
+//!
+//! ```bash
+//! cargo test --test presets
+//! ```
+
+use synthetic_consciousness::{Simulation, SimulationConfig};
+
+/// Run `name` for `steps` steps, recording every step.
+fn run_preset(name: &str, steps: u32) -> Simulation {
+    let mut config = SimulationConfig::preset_library().remove(name).unwrap();
+    config.simulation.num_steps = steps;
+    let mut sim = Simulation::new(config).unwrap();
+    sim.run();
+    sim
+}
+
+#[test]
+fn every_preset_validates_and_runs() {
+    let presets = SimulationConfig::preset_library();
+    let mut names: Vec<&String> = presets.keys().collect();
+    names.sort();
+    assert_eq!(
+        names,
+        ["chaos_regime", "crystalline", "dense_3d", "hebbian_learning", "minimal_2d", "social_emergence"]
+    );
+    for (name, config) in &presets {
+        assert_eq!(&config.metadata.name, name);
+        assert!(!config.metadata.description.is_empty(), "{}", name);
+        config.validate().unwrap_or_else(|error| panic!("{}: {}", name, error));
+        assert!(config.problems().is_empty(), "{}: {:?}", name, config.problems());
+
+        let sim = run_preset(name, 5);
+        assert_eq!(sim.timestamp, 5, "{}", name);
+        assert_eq!(sim.entities.count(), config.simulation.num_entities as usize, "{}", name);
+        let metrics = sim.metrics_history.last().unwrap();
+        assert!(metrics.core_vector().iter().all(|m| m.is_finite()), "{}: {:?}", name, metrics);
+    }
+}
+
+#[test]
+fn hebbian_learning_reinforces_repeated_experiences() {
+    let sim = run_preset("hebbian_learning", 100);
+    let reinforced = sim.entities.iter().flat_map(|e| e.memory_graph.nodes.iter()).filter(|n| n.reinforcements > 0);
+    assert!(reinforced.count() > 0);
+    // Reinforced experiences are not stored again
+    assert!(sim.entities.iter().all(|e| e.memory_graph.nodes.len() < 100));
+}
+
+#[test]
+fn social_emergence_spreads_beliefs_through_signals() {
+    let sim = run_preset("social_emergence", 30);
+    let emitted: usize = sim.results.steps.iter().map(|step| step.signals_emitted).sum();
+    let received: usize = sim.results.steps.iter().map(|step| step.signals_received).sum();
+    assert!(emitted > 0 && received > 0, "{} emitted, {} received", emitted, received);
+}
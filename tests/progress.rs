@@ -0,0 +1,138 @@
+//! Times steps, reports progress with rates and ETA, and records the
+//! wall-clock time of a run.
+//!
+//! ```bash
+//! cargo test --test progress
+//! ```
+
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use synthetic_consciousness::progress::{ProgressReporter, ProgressSnapshot, ProgressTracker};
+use synthetic_consciousness::{Simulation, SimulationConfig, SimulationResults};
+
+fn small_config(steps: u32) -> SimulationConfig {
+    let mut config = SimulationConfig::default_2d();
+    config.simulation.num_entities = 4;
+    config.simulation.num_steps = steps;
+    config
+}
+
+/// Reporter keeping every snapshot it receives.
+struct Recorder(Arc<Mutex<Vec<ProgressSnapshot>>>);
+
+impl ProgressReporter for Recorder {
+    fn on_step(&mut self, _step: u64, _total_steps: u64) {
+        panic!("on_progress should be called instead");
+    }
+
+    fn on_progress(&mut self, progress: &ProgressSnapshot) {
+        self.0.lock().unwrap().push(progress.clone());
+    }
+}
+
+#[test]
+fn tracker_measures_rates_and_eta() {
+    let mut tracker = ProgressTracker::new();
+    assert_eq!(tracker.snapshot().steps_per_second, None);
+    assert_eq!(tracker.elapsed(), Duration::ZERO);
+
+    for step in 0..4 {
+        tracker.start_step(step);
+        std::thread::sleep(Duration::from_millis(5));
+        tracker.finish_step(step + 1, 10);
+    }
+    let snapshot = tracker.snapshot();
+    assert_eq!((snapshot.step, snapshot.total_steps), (4, 10));
+    assert!((snapshot.percent() - 40.0).abs() < 1e-9);
+    assert!(snapshot.elapsed >= Duration::from_millis(20));
+    let rate = snapshot.steps_per_second.unwrap();
+    assert!(rate > 0.0 && rate <= 200.0, "{}", rate);
+    assert!(snapshot.average_steps_per_second.unwrap() <= 200.0);
+    // Six steps left at no more than 200 steps/s
+    assert!(snapshot.eta.unwrap() >= Duration::from_millis(30));
+}
+
+#[test]
+fn snapshot_line_shows_known_values_only() {
+    let mut snapshot = ProgressSnapshot {
+        step: 250,
+        total_steps: 1000,
+        elapsed: Duration::from_secs(65),
+        ..ProgressSnapshot::default()
+    };
+    assert_eq!(snapshot.to_string(), " 25.0% 250/1000 | elapsed 1:05");
+
+    snapshot.steps_per_second = Some(512.4);
+    snapshot.average_steps_per_second = Some(498.0);
+    snapshot.eta = Some(Duration::from_secs(3725));
+    assert_eq!(
+        snapshot.to_string(),
+        " 25.0% 250/1000 | 512 steps/s (avg 498) | elapsed 1:05 | ETA 1:02:05"
+    );
+    assert!(!snapshot.is_complete());
+}
+
+#[test]
+fn simulation_reports_progress_after_every_step() {
+    let mut sim = Simulation::new(small_config(30)).unwrap();
+    let before = sim.progress();
+    assert_eq!((before.step, before.total_steps), (0, 30));
+    assert_eq!(before.eta, None);
+
+    let snapshots = Arc::new(Mutex::new(Vec::new()));
+    sim.set_progress_reporter(Box::new(Recorder(Arc::clone(&snapshots))));
+    sim.run();
+
+    let snapshots = snapshots.lock().unwrap();
+    assert_eq!(snapshots.len(), 30);
+    assert_eq!(snapshots.iter().map(|s| s.step).collect::<Vec<_>>(), (1..=30).collect::<Vec<_>>());
+    assert!(snapshots.last().unwrap().is_complete());
+    assert_eq!(snapshots.last().unwrap().eta, Some(Duration::ZERO));
+
+    let progress = sim.progress();
+    assert_eq!(progress.step, 30);
+    assert!(progress.average_steps_per_second.is_some());
+}
+
+#[test]
+fn run_with_progress_calls_back_after_every_step() {
+    let mut sim = Simulation::new(small_config(25)).unwrap();
+    for _ in 0..5 {
+        sim.step();
+    }
+
+    // A partly run simulation runs the rest, alongside its reporter
+    let reported = Arc::new(Mutex::new(Vec::new()));
+    sim.set_progress_reporter(Box::new(Recorder(Arc::clone(&reported))));
+    let mut seen = Vec::new();
+    sim.run_with_progress(|progress| seen.push(progress.clone()));
+
+    assert_eq!(seen.iter().map(|s| s.step).collect::<Vec<_>>(), (6..=25).collect::<Vec<_>>());
+    assert!(seen.iter().all(|s| s.total_steps == 25 && s.steps_per_second.is_some()));
+    assert!(seen.last().unwrap().is_complete());
+    assert_eq!(*reported.lock().unwrap(), seen);
+
+    // Nothing left to run
+    let mut calls = 0;
+    sim.run_with_progress(|_| calls += 1);
+    assert_eq!(calls, 0);
+}
+
+#[test]
+fn results_record_wall_clock_time_next_to_simulated_time() {
+    let mut sim = Simulation::new(small_config(20)).unwrap();
+    sim.run();
+    sim.finalize_results();
+
+    let results = &sim.results;
+    assert!(results.wall_clock_seconds > 0.0);
+    assert!((results.wall_clock_seconds - sim.progress().elapsed.as_secs_f64()).abs() < 1e-9);
+    let simulated = 20.0 * sim.config.dynamics.dt;
+    assert!((results.duration_seconds - simulated).abs() < 1e-4);
+
+    // Results saved before the field existed still load
+    let mut json: serde_json::Value = serde_json::to_value(results).unwrap();
+    json.as_object_mut().unwrap().remove("wall_clock_seconds");
+    let loaded: SimulationResults = serde_json::from_value(json).unwrap();
+    assert_eq!(loaded.wall_clock_seconds, 0.0);
+}
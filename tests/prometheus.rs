@@ -0,0 +1,83 @@
+//! Scrapes the Prometheus endpoint while a simulation is running.
+//!
+//! `cargo test --features prometheus --test prometheus`
+
+#![cfg(feature = "prometheus")]
+
+use std::collections::HashMap;
+use std::io::{Read, Write};
+use std::net::{SocketAddr, TcpStream};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::thread;
+use std::time::{Duration, Instant};
+use synthetic_consciousness::telemetry::{MetricsEndpoint, MetricsObserver};
+use synthetic_consciousness::{Simulation, SimulationConfig};
+
+/// GET `/metrics` over HTTP/1.0 and parse unlabeled samples into name → value.
+fn scrape(addr: SocketAddr) -> HashMap<String, f64> {
+    let mut stream = TcpStream::connect(addr).unwrap();
+    write!(stream, "GET /metrics HTTP/1.0\r\nHost: {}\r\n\r\n", addr).unwrap();
+    let mut response = String::new();
+    stream.read_to_string(&mut response).unwrap();
+    assert_eq!(&response[9..12], "200");
+    let (_, body) = response.split_once("\r\n\r\n").unwrap();
+    body.lines()
+        .filter(|line| !line.starts_with('#'))
+        .filter_map(|line| line.split_once(' '))
+        .map(|(name, value)| (name.to_string(), value.parse().unwrap()))
+        .collect()
+}
+
+#[test]
+fn scrape_mid_run() {
+    let observer = Arc::new(MetricsObserver::new().unwrap());
+    let endpoint = Arc::new(MetricsEndpoint::bind("127.0.0.1:0", Arc::clone(&observer)).unwrap());
+    let addr = endpoint.local_addr().unwrap();
+    let serving = Arc::clone(&endpoint);
+    let server = thread::spawn(move || serving.serve());
+
+    let mut config = SimulationConfig::default_2d();
+    config.simulation.num_entities = 6;
+    config.simulation.num_steps = 1_000_000;
+    let mut sim = Simulation::new(config).unwrap();
+    let stop = Arc::new(AtomicBool::new(false));
+    sim.set_stop_flag(Arc::clone(&stop));
+    let running = Arc::clone(&observer);
+    let run = thread::spawn(move || sim.run_observed(&running));
+
+    let deadline = Instant::now() + Duration::from_secs(60);
+    let samples = loop {
+        let samples = scrape(addr);
+        if samples.get("synthetic_consciousness_step").copied().unwrap_or(0.0) >= 10.0 {
+            break samples;
+        }
+        assert!(Instant::now() < deadline, "run made no progress");
+        thread::sleep(Duration::from_millis(20));
+    };
+    stop.store(true, Ordering::Relaxed);
+    run.join().unwrap();
+    endpoint.shutdown();
+    server.join().unwrap();
+
+    for family in [
+        "attention_entropy",
+        "average_essence",
+        "steps_per_second",
+        "entities",
+        "memory_nodes",
+        "belief_clusters",
+        "spawned_entities_total",
+        "config_patches_total",
+    ] {
+        let name = format!("synthetic_consciousness_{}", family);
+        assert!(samples.contains_key(&name), "missing {}", name);
+    }
+    assert!(samples["synthetic_consciousness_step"] < 1_000_000.0);
+    assert_eq!(samples["synthetic_consciousness_entities"], 6.0);
+    assert!(samples["synthetic_consciousness_memory_nodes"] >= 6.0);
+    assert!(samples["synthetic_consciousness_steps_per_second"] > 0.0);
+    assert_eq!(samples["synthetic_consciousness_config_patches_total"], 0.0);
+    let essence = samples["synthetic_consciousness_average_essence"];
+    assert!(essence.is_finite() && essence >= 0.0);
+}
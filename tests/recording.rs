@@ -0,0 +1,107 @@
+//! Thins the recorded steps with the `recording` config section and checks
+//! that the per-step metrics, the analysis and the final step are kept.
+//!
+//! ```bash
+//! cargo test --test recording
+//! ```
+
+use synthetic_consciousness::plugin::StimulusSource;
+use synthetic_consciousness::results::RecordingConfig;
+use synthetic_consciousness::{Entity, Real, Simulation, SimulationConfig};
+
+/// Strong stimuli cycling through positive, negative and neutral valence,
+/// which lets entities reach the consciousness criteria.
+struct Valenced;
+
+impl StimulusSource for Valenced {
+    fn stimulus(&mut self, entity: &Entity, step: u64) -> Option<Vec<Real>> {
+        let valence = [1.0, -1.0, 0.0][((step + u64::from(entity.id.0)) % 3) as usize];
+        Some(vec![valence, 0.3 * valence])
+    }
+}
+
+fn run(recording: RecordingConfig, steps: u32) -> Simulation {
+    let mut config = SimulationConfig::default_2d();
+    config.simulation.num_entities = 4;
+    config.simulation.num_steps = steps;
+    config.recording = recording;
+    let mut sim = Simulation::new(config).unwrap();
+    sim.set_stimulus_source(Box::new(Valenced));
+    sim.run();
+    sim.finalize_results();
+    sim
+}
+
+fn every(record_every: u32) -> RecordingConfig {
+    RecordingConfig { record_every, ..RecordingConfig::default() }
+}
+
+#[test]
+fn records_every_nth_step_and_the_last() {
+    let sim = run(every(10), 95);
+    let recorded: Vec<u64> = sim.results.steps.iter().map(|s| s.step_number).collect();
+    let mut expected: Vec<u64> = (0..95).step_by(10).collect();
+    expected.push(94);
+    assert_eq!(recorded, expected);
+    assert_eq!(sim.metrics_history.len(), 95);
+    assert_eq!(sim.results.steps_run(), 95);
+}
+
+#[test]
+fn sparse_recording_keeps_the_analysis() {
+    let dense = run(RecordingConfig::default(), 300);
+    let sparse = run(every(25), 300);
+    let (dense, sparse) = (&dense.results.consciousness_analysis, &sparse.results.consciousness_analysis);
+    assert!(dense.first_achieved_step.is_some());
+    assert_eq!(sparse.consciousness_achieved, dense.consciousness_achieved);
+    assert_eq!(sparse.first_achieved_step, dense.first_achieved_step);
+    assert_eq!(sparse.metric_values, dense.metric_values);
+}
+
+#[test]
+fn disabled_fields_stay_empty() {
+    let recording = RecordingConfig {
+        positions: false,
+        attractions: false,
+        clusters: false,
+        attentions: false,
+        ..RecordingConfig::default()
+    };
+    let sim = run(recording, 20);
+    assert_eq!(sim.results.steps.len(), 20);
+    for step in &sim.results.steps {
+        assert!(step.entity_positions.is_empty());
+        assert!(step.entity_velocities.is_empty());
+        assert!(step.attractions.is_empty());
+        assert!(step.belief_clusters.is_empty());
+        assert!(step.attentions.is_empty());
+    }
+}
+
+#[test]
+fn finalizing_an_early_stop_records_the_last_step() {
+    let mut config = SimulationConfig::default_2d();
+    config.simulation.num_entities = 4;
+    config.recording.record_every = 10;
+    let mut sim = Simulation::new(config).unwrap();
+    for _ in 0..37 {
+        sim.step();
+    }
+    assert_eq!(sim.results.steps.last().unwrap().step_number, 30);
+    sim.finalize_results();
+    assert_eq!(sim.results.steps.last().unwrap().step_number, 36);
+    assert_eq!(sim.results.steps_run(), 37);
+}
+
+#[test]
+fn the_cadence_is_configurable_from_the_command_line() {
+    let mut config = SimulationConfig::default_2d();
+    let args = ["recording.record_every=5".to_string(), "recording.attractions=false".to_string()];
+    config.apply_cli_args(&args).unwrap();
+    assert_eq!(config.recording.record_every, 5);
+    assert!(!config.recording.attractions);
+    assert!(config.summary().contains("every 5 steps, without attractions"));
+
+    assert!(config.apply_cli_args(&["recording.record_every=0".to_string()]).is_err());
+    assert!(config.problems().iter().any(|p| p.path == "recording.record_every"));
+}
@@ -0,0 +1,71 @@
+//! Recurrence quantification of metric series: exact measures for periodic
+//! and constant trajectories, and the empty, too-short and unknown-metric
+//! edge cases.
+//!
+//! ```bash
+//! cargo test --test recurrence
+//! ```
+
+use synthetic_consciousness::analysis::{recurrence_quantification, RQA};
+use synthetic_consciousness::{Metrics, Real, Simulation, SimulationConfig};
+
+/// Simulation whose metric history has `average_essence` following `series`.
+fn with_essence_series(series: &[Real]) -> Simulation {
+    let mut sim = Simulation::new(SimulationConfig::default_2d()).unwrap();
+    let template = Metrics::compute(&sim.entities, 0);
+    sim.metrics_history = series
+        .iter()
+        .enumerate()
+        .map(|(t, &value)| Metrics { timestamp: t as u64, average_essence: value, ..template.clone() })
+        .collect();
+    sim
+}
+
+#[test]
+fn periodic_trajectories_are_fully_deterministic() {
+    // Period 4 over 40 samples: each point recurs with the 9 others of its
+    // phase, all along full diagonals and never twice in a row
+    let series: Vec<Real> = (0..40).map(|t| (t % 4) as Real).collect();
+    let rqa = with_essence_series(&series).compute_recurrence_quantification("average_essence", 0.5);
+    assert_eq!(rqa, recurrence_quantification(&series, 0.5));
+    assert!((rqa.recurrence_rate - 9.0 / 39.0).abs() < 1e-6, "rate {}", rqa.recurrence_rate);
+    assert_eq!(rqa.determinism, 1.0);
+    assert_eq!(rqa.laminarity, 0.0);
+    assert_eq!(rqa.trapping_time, 0.0);
+    assert_eq!(rqa.max_diagonal_line, 36);
+
+    // A radius covering the whole range makes every pair recur; only the
+    // two corner points form lines shorter than two
+    let wide = recurrence_quantification(&series, 4.0);
+    assert_eq!(wide.recurrence_rate, 1.0);
+    assert!((wide.determinism - (1.0 - 2.0 / (40.0 * 39.0))).abs() < 1e-6);
+}
+
+#[test]
+fn constant_trajectories_recur_everywhere() {
+    let rqa = with_essence_series(&[0.7; 30]).compute_recurrence_quantification("average_essence", 1e-3);
+    assert_eq!(rqa.recurrence_rate, 1.0);
+    // Every point lies on a diagonal and a vertical line except the two
+    // corners and the two points cut off by the main diagonal
+    let all_but_two = 1.0 - 2.0 / (30.0 * 29.0);
+    assert!((rqa.determinism - all_but_two).abs() < 1e-6, "determinism {}", rqa.determinism);
+    assert!((rqa.laminarity - all_but_two).abs() < 1e-6, "laminarity {}", rqa.laminarity);
+    assert_eq!(rqa.max_diagonal_line, 29);
+    assert!(rqa.trapping_time > 1.0);
+}
+
+#[test]
+fn empty_short_and_unknown_series_give_no_measures() {
+    let sim = with_essence_series(&[]);
+    assert_eq!(sim.compute_recurrence_quantification("average_essence", 0.1), RQA::default());
+    let sim = with_essence_series(&[0.5]);
+    assert_eq!(sim.compute_recurrence_quantification("average_essence", 0.1), RQA::default());
+    let sim = with_essence_series(&[0.5; 10]);
+    assert_eq!(sim.compute_recurrence_quantification("no_such_metric", 0.1), RQA::default());
+
+    // Two equal samples recur, but too briefly to form a line
+    let pair = recurrence_quantification(&[0.5, 0.5], 0.1);
+    assert_eq!((pair.recurrence_rate, pair.determinism, pair.max_diagonal_line), (1.0, 0.0, 1));
+    // Nothing recurs within a zero radius
+    assert_eq!(recurrence_quantification(&[0.5; 10], 0.0), RQA::default());
+}
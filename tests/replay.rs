@@ -0,0 +1,70 @@
+//! Interactive replay export: the data embedded in the page parses as JSON
+//! with one frame per recorded step, and every frame holds the step's
+//! entities (nodes) and attractions (links).
+//!
+//! ```bash
+//! cargo test --test replay
+//! ```
+
+use synthetic_consciousness::{Simulation, SimulationConfig};
+
+const DATA_START: &str = r#"<script id="replay-data" type="application/json">"#;
+
+/// The replay data embedded in an exported page.
+fn replay_data(html: &str) -> serde_json::Value {
+    let start = html.find(DATA_START).unwrap() + DATA_START.len();
+    let end = start + html[start..].find("</script>").unwrap();
+    serde_json::from_str(&html[start..end]).unwrap()
+}
+
+#[test]
+fn every_recorded_step_becomes_a_frame_with_its_nodes_and_links() {
+    let mut config = SimulationConfig::default_2d();
+    config.simulation.num_entities = 12;
+    config.simulation.num_steps = 30;
+    config.recording.record_every = 5;
+    let mut sim = Simulation::new(config).unwrap();
+    // A title that would break the page if it were not escaped
+    sim.results.simulation_name = "replay </script> test".to_string();
+    sim.run();
+    assert!(sim.results.steps.iter().any(|step| !step.attractions.is_empty()));
+
+    let path = std::env::temp_dir().join(format!("sc_replay_{}.html", std::process::id()));
+    let path = path.to_str().unwrap();
+    for offline in [false, true] {
+        if offline {
+            sim.export_d3_visualization_offline(path).unwrap();
+        } else {
+            sim.export_d3_visualization(path).unwrap();
+        }
+        let html = std::fs::read_to_string(path).unwrap();
+        assert_eq!(html.contains("cdn.jsdelivr.net"), !offline);
+        assert!(html.contains("replay &lt;/script&gt; test") && !html.contains("replay </script>"));
+
+        let data = replay_data(&html);
+        assert_eq!(data["bounds"], serde_json::json!(sim.config.geometry.bounds));
+        assert_eq!(data["metrics"].as_array().unwrap().len(), 5);
+        let frames = data["frames"].as_array().unwrap();
+        assert_eq!(frames.len(), sim.results.steps.len());
+        for (frame, step) in frames.iter().zip(&sim.results.steps) {
+            assert_eq!(frame["step"], step.step_number);
+            let nodes = frame["entities"].as_array().unwrap();
+            assert_eq!(nodes.len(), 12);
+            let ids: Vec<u64> = nodes.iter().map(|node| node["id"].as_u64().unwrap()).collect();
+            assert_eq!(ids, step.entity_positions.iter().map(|(id, _)| *id as u64).collect::<Vec<_>>());
+            assert_eq!(frame["attractions"].as_array().unwrap().len(), step.attractions.len());
+        }
+    }
+    std::fs::remove_file(path).unwrap();
+}
+
+#[test]
+fn a_run_without_recorded_steps_has_no_frames() {
+    let sim = Simulation::new(SimulationConfig::default_2d()).unwrap();
+    let path = std::env::temp_dir().join(format!("sc_replay_empty_{}.html", std::process::id()));
+    let path = path.to_str().unwrap();
+    sim.export_d3_visualization_offline(path).unwrap();
+    let data = replay_data(&std::fs::read_to_string(path).unwrap());
+    assert!(data["frames"].as_array().unwrap().is_empty());
+    std::fs::remove_file(path).unwrap();
+}
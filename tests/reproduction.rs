@@ -0,0 +1,66 @@
+//! Runs open-ended simulations with reproduction enabled and checks that
+//! the population grows under generous conditions, stays put under harsh
+//! ones, respects its cap, and that offspring keep a balanced energy ledger.
+//!
+//! ```bash
+//! cargo test --test reproduction
+//! ```
+
+use synthetic_consciousness::{Simulation, SimulationConfig};
+
+fn config(generous: bool) -> SimulationConfig {
+    let mut config = SimulationConfig::default_2d();
+    config.simulation.num_entities = 4;
+    config.simulation.num_steps = 200;
+    config.reproduction.enabled = true;
+    config.reproduction.max_population = 12;
+    if generous {
+        config.reproduction.essence_threshold = 0.0;
+        config.reproduction.sustain_steps = 20;
+        config.reproduction.energy_threshold = 0.0;
+    } else {
+        config.reproduction.essence_threshold = 10.0;
+        config.reproduction.energy_threshold = config.energy.capacity;
+    }
+    config
+}
+
+#[test]
+fn generous_conditions_grow_the_population_up_to_the_cap() {
+    let mut sim = Simulation::new(config(true)).unwrap();
+    sim.run();
+
+    assert_eq!(sim.entities.count(), 12);
+    let lineage = &sim.results.lineage;
+    assert_eq!(lineage.len(), 12);
+    assert_eq!(lineage.iter().filter(|r| r.parent_id.is_none()).count(), 4);
+    for record in lineage.iter().filter(|r| r.parent_id.is_some()) {
+        assert!(record.birth_step > 0);
+        assert!(lineage.iter().any(|r| Some(r.entity_id) == record.parent_id));
+    }
+}
+
+#[test]
+fn harsh_conditions_keep_the_population_static() {
+    let mut sim = Simulation::new(config(false)).unwrap();
+    sim.run();
+
+    assert_eq!(sim.entities.count(), 4);
+    assert!(sim.results.lineage.iter().all(|r| r.parent_id.is_none()));
+}
+
+#[test]
+fn offspring_energy_ledgers_balance() {
+    let mut config = config(true);
+    config.energy.initial = 60.0;
+    config.reproduction.energy_transfer = 0.3;
+    let initial = config.energy.initial;
+    let mut sim = Simulation::new(config).unwrap();
+    sim.run();
+
+    assert!(sim.entities.count() > 4);
+    for entity in sim.entities.iter() {
+        let balance = initial + entity.energy_gained - entity.energy_spent;
+        assert!((entity.energy - balance).abs() < 1e-3, "entity {} is off its ledger", entity.id.0);
+    }
+}
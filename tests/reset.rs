@@ -0,0 +1,79 @@
+//! Resets simulations between repetitions and checks that the runs match
+//! freshly created simulations, that the seed can change, and that the
+//! history buffers are reused.
+//!
+//! ```bash
+//! cargo test --test reset
+//! ```
+
+use synthetic_consciousness::{Simulation, SimulationConfig};
+
+fn config() -> SimulationConfig {
+    let mut config = SimulationConfig::default_2d();
+    config.simulation.num_entities = 8;
+    config.simulation.num_steps = 40;
+    config.simulation.seed = 3;
+    config.signaling.enabled = true;
+    config.roles.enabled = true;
+    config
+}
+
+/// Finalized results as JSON without the fields that differ between runs.
+fn finished(sim: &mut Simulation) -> serde_json::Value {
+    sim.run();
+    sim.finalize_results();
+    let mut results = serde_json::to_value(&sim.results).unwrap();
+    let object = results.as_object_mut().unwrap();
+    for volatile in ["start_time", "end_time", "wall_clock_seconds"] {
+        object.remove(volatile);
+    }
+    results
+}
+
+#[test]
+fn a_reset_run_matches_a_fresh_one() {
+    let mut sim = Simulation::new(config()).unwrap();
+    let first = finished(&mut sim);
+
+    sim.reset(None).unwrap();
+    assert_eq!(sim.timestamp, 0);
+    assert!(sim.metrics_history.is_empty() && sim.results.steps.is_empty());
+    assert_eq!(sim.entities.count(), 8);
+    assert_eq!(finished(&mut sim), first);
+
+    let mut fresh = Simulation::new(config()).unwrap();
+    assert_eq!(finished(&mut fresh), first);
+}
+
+#[test]
+fn a_new_seed_matches_a_simulation_created_with_it() {
+    let mut sim = Simulation::new(config()).unwrap();
+    let first = finished(&mut sim);
+
+    sim.reset(Some(11)).unwrap();
+    assert_eq!((sim.seed(), sim.config.simulation.seed, sim.results.seed), (11, 11, 11));
+    let reseeded = finished(&mut sim);
+    assert_ne!(reseeded, first);
+
+    let mut with_seed = config();
+    with_seed.simulation.seed = 11;
+    assert_eq!(finished(&mut Simulation::new(with_seed).unwrap()), reseeded);
+}
+
+#[test]
+fn history_buffers_are_reused() {
+    let mut sim = Simulation::new(config()).unwrap();
+    sim.results.config_source = "experiment.toml".to_string();
+    sim.run();
+    let (metrics, steps) = (sim.metrics_history.capacity(), sim.results.steps.capacity());
+
+    sim.reset(None).unwrap();
+    assert!(sim.metrics_history.capacity() >= metrics.max(40));
+    assert!(sim.results.steps.capacity() >= steps);
+    assert_eq!(sim.results.config_source, "experiment.toml");
+
+    // Entities are numbered from 1 again
+    let mut ids: Vec<u32> = sim.entities.iter().map(|e| e.id.0).collect();
+    ids.sort();
+    assert_eq!(ids, (1..=8).collect::<Vec<_>>());
+}
@@ -0,0 +1,54 @@
+//! Resonance experiment: one score per sampled forcing frequency, in
+//! ascending frequency order, each that of the run forced at it alone.
+//!
+//! ```bash
+//! cargo test --test resonance
+//! ```
+
+use synthetic_consciousness::analysis::RESONANCE_SAMPLES;
+use synthetic_consciousness::{Simulation, SimulationConfig};
+
+fn simulation() -> Simulation {
+    let mut config = SimulationConfig::default_2d();
+    config.simulation.num_entities = 6;
+    Simulation::new(config).unwrap()
+}
+
+#[test]
+fn one_score_per_frequency_in_order() {
+    let sim = simulation();
+    let spectrum = sim.run_resonance_experiment((0.5, 5.0), 30);
+    assert_eq!(spectrum.frequencies.len(), RESONANCE_SAMPLES);
+    assert_eq!(spectrum.consciousness_scores.len(), RESONANCE_SAMPLES);
+    assert_eq!(spectrum.frequencies[0], 0.5);
+    assert!((spectrum.frequencies[RESONANCE_SAMPLES - 1] - 5.0).abs() < 1e-5);
+    assert!(spectrum.frequencies.windows(2).all(|w| (w[1] - w[0] - 0.5).abs() < 1e-5), "{:?}", spectrum.frequencies);
+
+    // Each score belongs to its frequency: sampling that frequency alone
+    // gives the same score
+    for (frequency, score) in spectrum.frequencies.iter().zip(&spectrum.consciousness_scores) {
+        let single = sim.run_resonance_experiment((*frequency, *frequency), 30);
+        assert_eq!(single.frequencies, vec![*frequency]);
+        assert_eq!(single.consciousness_scores, vec![*score], "frequency {}", frequency);
+    }
+
+    // The peak is the first of the highest scores
+    let peak = spectrum.frequencies.iter().position(|f| *f == spectrum.peak_frequency).unwrap();
+    let scores = &spectrum.consciousness_scores;
+    assert!(scores[..peak].iter().all(|score| *score < scores[peak]));
+    assert!(scores[peak..].iter().all(|score| *score <= scores[peak]));
+    assert_eq!(sim.timestamp, 0);
+}
+
+#[test]
+fn ranges_are_ordered_and_clamped() {
+    let sim = simulation();
+    let reversed = sim.run_resonance_experiment((5.0, 0.5), 10);
+    assert_eq!(reversed.frequencies, sim.run_resonance_experiment((0.5, 5.0), 10).frequencies);
+
+    let clamped = sim.run_resonance_experiment((-2.0, 1.0), 10);
+    assert_eq!(clamped.frequencies[0], 0.0);
+    assert_eq!(clamped.frequencies.len(), RESONANCE_SAMPLES);
+    let negative = sim.run_resonance_experiment((-2.0, -1.0), 10);
+    assert_eq!((negative.frequencies, negative.peak_frequency), (vec![0.0], 0.0));
+}
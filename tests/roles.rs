@@ -0,0 +1,59 @@
+//! Places one artificially central entity in a ring of others and checks
+//! that it is marked leader at every step, that the ring follows it, and
+//! that leadership never changes hands.
+//!
+//! ```bash
+//! cargo test --test roles
+//! ```
+
+use synthetic_consciousness::roles::Role;
+use synthetic_consciousness::{Entity, Real, Simulation};
+
+const RING: usize = 8;
+const STEPS: u32 = 60;
+
+/// The hub (id 1) at the center of a ring of [`RING`] entities, all at rest
+/// and without drives, with role dynamics enabled.
+fn hub_and_ring() -> Simulation {
+    let builder = Simulation::builder().entities(0).steps(STEPS).configure(|config| {
+        config.roles.enabled = true;
+        config.dynamics.min_speed = 0.0;
+        config.drives.responsiveness = 0.0;
+    });
+    let at = |position: Vec<Real>, attraction_weight: Real| {
+        let mut entity = Entity::builder(2)
+            .position(position)
+            .velocity(vec![0.0, 0.0])
+            .drives(0.0, 0.0)
+            .build(builder.config())
+            .unwrap();
+        entity.attraction_weight = attraction_weight;
+        entity
+    };
+    let hub = at(vec![5.0, 5.0], 2.0);
+    let ring: Vec<Entity> = (0..RING)
+        .map(|k| {
+            let angle = k as Real * std::f64::consts::TAU as Real / RING as Real;
+            at(vec![5.0 + 2.0 * angle.cos(), 5.0 + 2.0 * angle.sin()], 1.0)
+        })
+        .collect();
+    ring.into_iter().fold(builder.with_entity(hub), |b, e| b.with_entity(e)).build().unwrap()
+}
+
+#[test]
+fn the_central_entity_leads_at_every_step() {
+    let mut sim = hub_and_ring();
+    sim.run();
+
+    assert_eq!(sim.results.steps.len(), STEPS as usize);
+    for step in &sim.results.steps {
+        let leaders: Vec<u32> =
+            step.entity_roles.iter().filter(|(_, role)| *role == Role::Leader).map(|(id, _)| *id).collect();
+        assert_eq!(leaders, vec![1], "step {} led by {:?}", step.step_number, leaders);
+        assert!(step.entity_roles.iter().all(|&(id, role)| id == 1 || role == Role::Follower));
+    }
+
+    // The ring follows the hub, and leadership never changed hands
+    assert!(sim.entities.iter().skip(1).all(|e| e.follows.map(|id| id.0) == Some(1)));
+    assert!(sim.metrics_history.iter().skip(1).all(|m| m.role_stability == 1.0));
+}
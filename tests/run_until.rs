@@ -0,0 +1,78 @@
+//! Runs simulations until a user predicate holds and checks the step
+//! count, the step cap, cancellation and the finalized results.
+//!
+//! ```bash
+//! cargo test --test run_until
+//! ```
+
+use std::sync::atomic::AtomicBool;
+use std::sync::Arc;
+use synthetic_consciousness::{Simulation, SimulationConfig};
+
+fn simulation() -> Simulation {
+    let mut config = SimulationConfig::default_2d();
+    config.simulation.num_entities = 5;
+    config.simulation.num_steps = 300;
+    Simulation::new(config).unwrap()
+}
+
+#[test]
+fn stops_when_the_predicate_holds() {
+    let mut sim = simulation();
+    let run = sim.run_until(|sim| sim.timestamp >= 7, 100);
+    assert_eq!((run.steps_run, run.predicate_met), (7, true));
+    assert_eq!(sim.timestamp, 7);
+
+    // A satisfied predicate runs nothing more
+    let again = sim.run_until(|sim| sim.timestamp >= 7, 100);
+    assert_eq!((again.steps_run, again.predicate_met), (0, true));
+
+    // The predicate sees the metrics of the step just run
+    let mut seen = Vec::new();
+    sim.run_until(
+        |sim| {
+            seen.push(sim.metrics_history.len());
+            sim.metrics_history.last().is_some_and(|m| m.timestamp >= 9)
+        },
+        100,
+    );
+    assert_eq!(seen, [7, 8, 9, 10]);
+}
+
+#[test]
+fn the_cap_ends_runs_whose_predicate_never_holds() {
+    let mut sim = simulation();
+    let run = sim.run_until(|sim| sim.metrics_history.last().is_some_and(|m| m.average_essence > 100.0), 12);
+    assert_eq!((run.steps_run, run.predicate_met), (12, false));
+    assert_eq!(sim.metrics_history.len(), 12);
+
+    // Runs may go past the configured step count
+    let long = sim.run_until(|_| false, 400);
+    assert_eq!(long.steps_run, 400);
+    assert_eq!(sim.timestamp, 412);
+}
+
+#[test]
+fn a_stop_request_ends_the_run() {
+    let mut sim = simulation();
+    let flag = Arc::new(AtomicBool::new(true));
+    sim.set_stop_flag(flag);
+    let run = sim.run_until(|_| false, 50);
+    assert_eq!((run.steps_run, run.predicate_met), (0, false));
+}
+
+#[test]
+fn finalized_results_report_the_steps_run() {
+    let mut sim = simulation();
+    let run = sim.run_until(|sim| sim.timestamp == 25, 1000);
+    assert!(run.predicate_met);
+    sim.finalize_results();
+
+    assert_eq!(sim.results.num_steps, 25);
+    assert_eq!(sim.results.steps.last().unwrap().step_number, 24);
+    let path = std::env::temp_dir().join(format!("sc_run_until_{}.md", std::process::id()));
+    sim.results.generate_markdown_report(path.to_str().unwrap()).unwrap();
+    let report = std::fs::read_to_string(&path).unwrap();
+    std::fs::remove_file(path).unwrap();
+    assert!(report.contains("- **Steps:** 25\n"));
+}
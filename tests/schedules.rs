@@ -0,0 +1,120 @@
+//! Anneals parameters over a run with the `schedules` config section and
+//! checks the schedule shapes, the values applied and recorded each step,
+//! validation and the report.
+//!
+//! ```bash
+//! cargo test --test schedules
+//! ```
+
+use synthetic_consciousness::schedule::{ParameterSchedule, ScheduleKind};
+use synthetic_consciousness::{Real, Simulation, SimulationConfig};
+
+fn schedule(kind: ScheduleKind, start: Real, end: Real, from_step: u64, to_step: u64) -> ParameterSchedule {
+    ParameterSchedule { kind, start, end, from_step, to_step }
+}
+
+fn config() -> SimulationConfig {
+    let mut config = SimulationConfig::default_2d();
+    config.simulation.num_entities = 5;
+    config.simulation.num_steps = 30;
+    config
+}
+
+#[test]
+fn schedules_move_from_start_to_end() {
+    let linear = schedule(ScheduleKind::Linear, 1.0, 3.0, 10, 20);
+    assert_eq!(linear.value_at(0), 1.0);
+    assert_eq!(linear.value_at(10), 1.0);
+    assert_eq!(linear.value_at(15), 2.0);
+    assert_eq!(linear.value_at(20), 3.0);
+    assert_eq!(linear.value_at(100), 3.0);
+
+    let exponential = schedule(ScheduleKind::Exponential, 1.0, 100.0, 0, 10);
+    assert!((exponential.value_at(5) - 10.0).abs() < 1e-3);
+    assert_eq!(exponential.value_at(10), 100.0);
+
+    let step = schedule(ScheduleKind::Step, 1.0, 2.0, 0, 10);
+    assert_eq!(step.value_at(9), 1.0);
+    assert_eq!(step.value_at(10), 2.0);
+}
+
+#[test]
+fn scheduled_values_are_applied_and_recorded_each_step() {
+    let mut config = config();
+    config.schedules.insert("dynamics.damping".to_string(), schedule(ScheduleKind::Linear, 0.9, 0.99, 0, 10));
+    config.schedules.insert("essence.experience_scale".to_string(), schedule(ScheduleKind::Step, 1.0, 2.0, 0, 5));
+    config.schedules.insert("stimulus_amplitude".to_string(), schedule(ScheduleKind::Linear, 0.5, 0.0, 0, 20));
+    let mut sim = Simulation::new(config).unwrap();
+    sim.run();
+
+    for step in &sim.results.steps {
+        let n = step.step_number;
+        let damping = 0.9 + 0.09 * (n.min(10) as Real / 10.0);
+        let scale = if n < 5 { 1.0 } else { 2.0 };
+        let amplitude = 0.5 - 0.5 * (n.min(20) as Real / 20.0);
+        let values: Vec<&str> = step.scheduled_values.iter().map(|(path, _)| path.as_str()).collect();
+        assert_eq!(values, ["dynamics.damping", "essence.experience_scale", "stimulus_amplitude"]);
+        assert!((step.scheduled_values[0].1 - damping).abs() < 1e-6);
+        assert_eq!(step.scheduled_values[1].1, scale);
+        assert!((step.scheduled_values[2].1 - amplitude).abs() < 1e-6);
+    }
+
+    assert_eq!(sim.config.dynamics.damping, 0.99);
+    assert_eq!(sim.config.essence.experience_scale, 2.0);
+    for entity in sim.entities.iter() {
+        assert_eq!(entity.essence.config.experience_scale, 2.0);
+        assert_eq!(entity.stimulus_amplitude, 0.0);
+    }
+}
+
+#[test]
+fn runs_without_schedules_record_no_values() {
+    let mut sim = Simulation::new(config()).unwrap();
+    sim.run();
+    assert!(sim.results.steps.iter().all(|step| step.scheduled_values.is_empty()));
+}
+
+#[test]
+fn validation_rejects_bad_schedules() {
+    let with = |path: &str, schedule: ParameterSchedule| {
+        let mut config = config();
+        config.schedules.insert(path.to_string(), schedule);
+        config
+    };
+
+    let unknown = with("dynamics.friction", schedule(ScheduleKind::Linear, 0.1, 0.2, 0, 10));
+    assert_eq!(unknown.validate().unwrap_err(), "Schedule for unknown parameter 'dynamics.friction'");
+    assert_eq!(unknown.problems()[0].path, "schedules.\"dynamics.friction\"");
+
+    let backwards = with("dynamics.damping", schedule(ScheduleKind::Linear, 0.9, 0.99, 20, 10));
+    assert!(backwards.validate().is_err());
+    let exponential = with("attraction.sigma", schedule(ScheduleKind::Exponential, 0.0, 1.0, 0, 10));
+    assert!(exponential.validate().is_err());
+    let negative = with("stimulus_amplitude", schedule(ScheduleKind::Linear, 0.1, -0.1, 0, 10));
+    assert!(negative.validate().is_err());
+    assert_eq!(negative.problems()[0].path, "schedules.\"stimulus_amplitude\".end");
+
+    let fine = with("dynamics.damping", schedule(ScheduleKind::Linear, 0.9, 0.99, 0, 10));
+    assert!(fine.validate().is_ok());
+    assert!(fine.problems().is_empty());
+}
+
+#[test]
+fn schedules_parse_from_toml_and_appear_in_the_report() {
+    let text = format!(
+        "{}\n[schedules.\"dynamics.damping\"]\nkind = \"exponential\"\nstart = 0.5\nend = 0.9\nfrom_step = 5\nto_step = 25\n",
+        config().canonical_toml().unwrap()
+    );
+    let config = SimulationConfig::parse(&text, None).unwrap();
+    assert_eq!(config.schedules["dynamics.damping"], schedule(ScheduleKind::Exponential, 0.5, 0.9, 5, 25));
+
+    let mut sim = Simulation::new(config).unwrap();
+    sim.run();
+    sim.finalize_results();
+    let path = std::env::temp_dir().join(format!("sc_schedules_{}.md", std::process::id()));
+    sim.results.generate_markdown_report(path.to_str().unwrap()).unwrap();
+    let report = std::fs::read_to_string(&path).unwrap();
+    std::fs::remove_file(path).unwrap();
+    assert!(report.contains("## Parameter Schedules"));
+    assert!(report.contains("| dynamics.damping | exponential 0.5 → 0.9 over steps 5-25 | 0.5000 | 0.9000 |"));
+}
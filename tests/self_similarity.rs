@@ -0,0 +1,43 @@
+//! Temporal self-similarity of the recorded metrics: one row and column per
+//! recorded step, symmetric, with every step fully similar to itself.
+//!
+//! ```bash
+//! cargo test --test self_similarity
+//! ```
+
+use synthetic_consciousness::{Simulation, SimulationConfig};
+
+fn run(steps: u32, record_every: u32) -> Simulation {
+    let mut config = SimulationConfig::default_2d();
+    config.simulation.num_entities = 8;
+    config.simulation.num_steps = steps;
+    config.recording.record_every = record_every;
+    let mut sim = Simulation::new(config).unwrap();
+    sim.run();
+    sim
+}
+
+#[test]
+fn matrix_is_symmetric_with_a_unit_diagonal() {
+    for record_every in [1, 3] {
+        let sim = run(40, record_every);
+        let matrix = sim.compute_temporal_self_similarity_matrix();
+        let n = sim.results.steps.len();
+        assert!(n > 1);
+        assert_eq!(matrix.len(), n);
+        for (i, row) in matrix.iter().enumerate() {
+            assert_eq!(row.len(), n);
+            assert!((row[i] - 1.0).abs() < 1e-5, "S[{}][{}] = {}", i, i, row[i]);
+            for (j, value) in row.iter().enumerate() {
+                assert_eq!(value.to_bits(), matrix[j][i].to_bits(), "S[{}][{}]", i, j);
+                assert!((-1.0 - 1e-5..=1.0 + 1e-5).contains(value), "S[{}][{}] = {}", i, j, value);
+            }
+        }
+    }
+}
+
+#[test]
+fn nothing_recorded_gives_an_empty_matrix() {
+    let sim = Simulation::new(SimulationConfig::default_2d()).unwrap();
+    assert!(sim.compute_temporal_self_similarity_matrix().is_empty());
+}
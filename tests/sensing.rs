@@ -0,0 +1,127 @@
+//! Replaces the built-in sensory noise with other sense models and checks
+//! what they are given, what entities perceive and that the default model
+//! leaves runs unchanged.
+//!
+//! ```bash
+//! cargo test --test sensing
+//! ```
+
+use std::sync::{Arc, Mutex};
+use synthetic_consciousness::sensing::{NeighborSense, NoiseSense, SenseModel};
+use synthetic_consciousness::simulation::SimulationRng;
+use synthetic_consciousness::{Entity, Real, Simulation, SimulationConfig};
+
+fn config() -> SimulationConfig {
+    let mut config = SimulationConfig::default_2d();
+    config.simulation.num_entities = 5;
+    config.simulation.num_steps = 30;
+    config
+}
+
+/// Sensing entity ids with the neighbor ids they were given.
+type Seen = Arc<Mutex<Vec<(u32, Vec<u32>)>>>;
+
+/// Perceives a constant event and remembers the neighbors it was given.
+struct Probe {
+    radius: Option<Real>,
+    seen: Seen,
+}
+
+impl SenseModel for Probe {
+    fn sense(&mut self, entity: &Entity, neighbors: &[&Entity], _rng: &mut SimulationRng) -> Vec<Real> {
+        let ids = neighbors.iter().map(|n| n.id.0).collect();
+        self.seen.lock().unwrap().push((entity.id.0, ids));
+        vec![0.7, 0.0]
+    }
+
+    fn neighbor_radius(&self) -> Option<Real> {
+        self.radius
+    }
+}
+
+fn probe(radius: Option<Real>) -> (Simulation, Seen) {
+    let seen = Arc::new(Mutex::new(Vec::new()));
+    let model = Probe { radius, seen: Arc::clone(&seen) };
+    (Simulation::with_sense_model(config(), Box::new(model)).unwrap(), seen)
+}
+
+#[test]
+fn the_default_model_is_the_built_in_noise() {
+    let mut default = Simulation::new(config()).unwrap();
+    let mut explicit = Simulation::with_sense_model(config(), Box::new(NoiseSense)).unwrap();
+    default.run();
+    explicit.run();
+    let values = |sim: &Simulation| sim.metrics_history.iter().map(|m| m.field_values()).collect::<Vec<_>>();
+    assert_eq!(values(&default), values(&explicit));
+    assert_eq!(default.rng_state(), explicit.rng_state());
+}
+
+#[test]
+fn entities_sense_what_the_model_produces() {
+    let (mut sim, seen) = probe(None);
+    sim.step();
+
+    let seen = seen.lock().unwrap();
+    let ids: Vec<u32> = sim.sorted_entity_ids().iter().map(|id| id.0).collect();
+    assert_eq!(seen.iter().map(|(id, _)| *id).collect::<Vec<_>>(), ids);
+    assert!(seen.iter().all(|(_, neighbors)| neighbors.is_empty()));
+    for entity in sim.entities.iter() {
+        let graph = &entity.memory_graph;
+        assert!(graph.nodes.iter().any(|node| graph.node_event(node) == [0.7, 0.0]));
+    }
+}
+
+#[test]
+fn neighbors_are_the_other_entities_within_the_radius() {
+    let (mut sim, seen) = probe(Some(3.0));
+    let positions: Vec<(u32, Vec<Real>)> = sim
+        .sorted_entity_ids()
+        .iter()
+        .map(|id| (id.0, sim.entities.get_entity(*id).unwrap().pose.position.to_vec()))
+        .collect();
+    sim.step();
+
+    let geometry = &sim.config.geometry;
+    for (id, neighbors) in seen.lock().unwrap().iter() {
+        let own = &positions.iter().find(|(other, _)| other == id).unwrap().1;
+        let expected: Vec<u32> = positions
+            .iter()
+            .filter(|(other, position)| {
+                other != id && synthetic_consciousness::geometry::periodic_distance(own, position, geometry) <= 3.0
+            })
+            .map(|(other, _)| *other)
+            .collect();
+        assert_eq!(neighbors, &expected);
+    }
+}
+
+#[test]
+fn neighbor_sensing_encodes_the_essence_and_distance_of_neighbors() {
+    let config = config();
+    let entity = |id: u32, x: Real, essence: Real| {
+        let mut entity = Entity::builder(2).position(vec![x, 5.0]).essence(essence).build(&config).unwrap();
+        entity.id.0 = id;
+        entity.stimulus_amplitude = 0.0;
+        entity
+    };
+    let me = entity(0, 5.0, 5.0);
+    let joyful = entity(1, 5.5, 10.0);
+    let far = entity(2, 9.0, 0.0);
+    let mut model = NeighborSense::new(2.0, &config.geometry);
+    let mut rng = <SimulationRng as rand::SeedableRng>::seed_from_u64(1);
+
+    let near = model.sense(&me, &[&joyful], &mut rng);
+    assert!(near[0] > 0.5, "a joyful neighbor is a positive event");
+    assert!((near[1] - 0.75).abs() < 1e-6);
+
+    // Out of range neighbors add nothing
+    let alone = model.sense(&me, &[&far], &mut rng);
+    assert_eq!(alone, [0.0, 0.0]);
+    let crowd = model.sense(&me, &[&joyful, &joyful], &mut rng);
+    assert!(crowd[0] > 0.5 && crowd[1] == near[1]);
+
+    // In a run, every entity perceives through the model
+    let mut sim = Simulation::with_sense_model(config.clone(), Box::new(model)).unwrap();
+    sim.run();
+    assert_eq!(sim.metrics_history.len(), 30);
+}
@@ -0,0 +1,44 @@
+//! Sensitivity to initial conditions: unperturbed copies never separate,
+//! and equal seeds give identical reports.
+//!
+//! ```bash
+//! cargo test --test sensitivity
+//! ```
+
+use synthetic_consciousness::{Simulation, SimulationConfig};
+
+fn simulation(seed: u64) -> Simulation {
+    let mut config = SimulationConfig::default_2d();
+    config.simulation.num_entities = 8;
+    config.simulation.num_steps = 60;
+    config.simulation.seed = seed;
+    Simulation::new(config).unwrap()
+}
+
+#[test]
+fn zero_perturbation_gives_zero_divergence() {
+    let mut sim = simulation(7);
+    let report = sim.run_sensitivity_to_initial_conditions(3, 0.0);
+    assert_eq!(report.lyapunov_exponents, vec![0.0; 8]);
+    assert!(!report.chaos_indicator);
+    // No pair ever diverges, so every one counts the whole horizon
+    let horizon = 60.0 * sim.config.dynamics.dt;
+    assert!((report.mean_divergence_time - horizon).abs() < 1e-4, "{}", report.mean_divergence_time);
+    assert_eq!(sim.results.sensitivity, Some(report));
+    assert_eq!(sim.timestamp, 0);
+}
+
+#[test]
+fn equal_seeds_give_identical_reports() {
+    let report = simulation(11).run_sensitivity_to_initial_conditions(4, 1e-3);
+    assert_eq!(simulation(11).run_sensitivity_to_initial_conditions(4, 1e-3), report);
+    assert_eq!(report.lyapunov_exponents.len(), 8);
+    assert!(report.lyapunov_exponents.iter().all(|l| l.is_finite()));
+
+    // The analysis does not advance the simulation, so repeating it agrees too
+    let mut sim = simulation(11);
+    let first = sim.run_sensitivity_to_initial_conditions(4, 1e-3);
+    assert_eq!(sim.run_sensitivity_to_initial_conditions(4, 1e-3), first);
+
+    assert_ne!(simulation(12).run_sensitivity_to_initial_conditions(4, 1e-3), report);
+}
@@ -0,0 +1,104 @@
+//! Round-trips entities, entity pools and memory graphs through serde and
+//! checks that nothing is lost and that ids are never reused afterwards.
+//!
+//! ```bash
+//! cargo test --test serialization
+//! ```
+
+use synthetic_consciousness::entities::{EntityId, EntityPool};
+use synthetic_consciousness::memory::MemoryGraph;
+use synthetic_consciousness::{Entity, Simulation, SimulationConfig};
+
+/// A pool whose entities have lived a while, so their memory graphs hold
+/// nodes and belief clusters.
+fn lived_pool() -> EntityPool {
+    let mut config = SimulationConfig::default_2d();
+    config.simulation.num_entities = 5;
+    config.simulation.num_steps = 30;
+    let mut sim = Simulation::new(config).unwrap();
+    sim.run();
+    sim.entities
+}
+
+fn ids_of(pool: &EntityPool) -> Vec<EntityId> {
+    pool.iter().map(|e| e.id).collect()
+}
+
+fn round_trip<T: serde::Serialize + serde::de::DeserializeOwned>(value: &T) -> T {
+    serde_json::from_str(&serde_json::to_string(value).unwrap()).unwrap()
+}
+
+#[test]
+fn entities_round_trip_with_their_memory_graph() {
+    let pool = lived_pool();
+    let mut entity = pool.iter().max_by_key(|e| e.memory_graph.clusters.len()).unwrap().clone();
+    entity.memory_graph.add_edge(0, 1);
+    let graph = &entity.memory_graph;
+    assert!(graph.nodes.len() > 1 && graph.clusters.len() > 1);
+
+    let copy: Entity = round_trip(&entity);
+    assert_eq!(serde_json::to_value(&copy).unwrap(), serde_json::to_value(&entity).unwrap());
+    assert_eq!(copy.id, entity.id);
+    assert_eq!(copy.pose.position, entity.pose.position);
+    assert_eq!(copy.state.memory, entity.state.memory);
+    assert_eq!(copy.essence.value, entity.essence.value);
+
+    // The event arena is rebuilt node by node
+    let restored = &copy.memory_graph;
+    assert_eq!(restored.nodes.len(), graph.nodes.len());
+    for (a, b) in restored.nodes.iter().zip(&graph.nodes) {
+        assert_eq!(restored.node_event(a), graph.node_event(b));
+        assert_eq!((a.activation, a.timestamp, a.cluster_id), (b.activation, b.timestamp, b.cluster_id));
+    }
+    assert_eq!(restored.edges, graph.edges);
+    assert_eq!(restored.clusters.keys().collect::<Vec<_>>(), graph.clusters.keys().collect::<Vec<_>>());
+    assert_eq!(restored.next_cluster_id(), graph.next_cluster_id());
+}
+
+#[test]
+fn pools_round_trip_preserving_ids() {
+    let pool = lived_pool();
+    let copy: EntityPool = round_trip(&pool);
+    assert_eq!(serde_json::to_value(&copy).unwrap(), serde_json::to_value(&pool).unwrap());
+    assert_eq!(ids_of(&copy), ids_of(&pool));
+}
+
+#[test]
+fn ids_are_not_reused_after_deserializing() {
+    let mut pool = lived_pool();
+    let highest = pool.iter().map(|e| e.id).max().unwrap();
+    let removed = pool.remove_entity(highest).unwrap();
+
+    // The counter travels with the pool, so the removed id stays retired
+    let mut copy: EntityPool = round_trip(&pool);
+    let added = copy.add_entity(removed.clone());
+    assert!(added > highest, "new entity got id {:?}", added);
+
+    // Without the counter it is recomputed past every id present
+    let mut json = serde_json::to_value(&pool).unwrap();
+    json.as_object_mut().unwrap().remove("next_id");
+    let mut stripped: EntityPool = serde_json::from_value(json).unwrap();
+    let existing = ids_of(&stripped);
+    let added = stripped.add_entity(removed);
+    assert!(!existing.contains(&added), "id {:?} was reused", added);
+}
+
+#[test]
+fn cluster_ids_are_not_reused_after_deserializing() {
+    let pool = lived_pool();
+    let graph = &pool.iter().max_by_key(|e| e.memory_graph.clusters.len()).unwrap().memory_graph;
+    let highest = *graph.clusters.keys().max().unwrap();
+
+    // A stale counter is raised past the clusters present
+    let mut json = serde_json::to_value(graph).unwrap();
+    json["next_cluster_id"] = serde_json::json!(0);
+    let mut restored: MemoryGraph = serde_json::from_value(json).unwrap();
+    assert!(restored.next_cluster_id() > highest);
+
+    // A dissimilar event opens a fresh cluster under a new id
+    let event: Vec<_> = graph.node_event(&graph.nodes[0]).iter().map(|v| -v - 1.0).collect();
+    let before: Vec<u32> = restored.clusters.keys().copied().collect();
+    let node = restored.add_clustered_event(&event, 1_000, 0.999);
+    let cluster = restored.nodes[node].cluster_id.unwrap();
+    assert!(!before.contains(&cluster), "cluster id {} was reused", cluster);
+}
@@ -0,0 +1,39 @@
+//! Streams a short run over WebSocket and parses a received frame.
+//!
+//! `cargo test --features server --test server`
+
+#![cfg(feature = "server")]
+
+use std::thread;
+use std::time::{Duration, Instant};
+use synthetic_consciousness::server::{StepFrame, UpdateServer};
+use synthetic_consciousness::{Simulation, SimulationConfig};
+
+#[test]
+fn client_receives_step_frames() {
+    let mut server = UpdateServer::bind("127.0.0.1:0", 4).unwrap();
+    let url = format!("ws://{}/", server.local_addr());
+    let client = thread::spawn(move || {
+        let (mut socket, _) = tungstenite::connect(url.as_str()).unwrap();
+        let message = socket.read().unwrap();
+        serde_json::from_str::<StepFrame>(message.to_text().unwrap()).unwrap()
+    });
+
+    let mut config = SimulationConfig::default_2d();
+    config.simulation.num_entities = 5;
+    let mut sim = Simulation::new(config).unwrap();
+    let deadline = Instant::now() + Duration::from_secs(20);
+    while !client.is_finished() {
+        assert!(Instant::now() < deadline, "no frame received");
+        sim.step();
+        server.publish(&StepFrame::capture(&sim, &[]));
+        thread::sleep(Duration::from_millis(10));
+    }
+
+    let frame = client.join().unwrap();
+    assert_eq!(frame.kind, "step");
+    assert!(frame.step >= 1);
+    assert_eq!(frame.entities.len(), 5);
+    assert!(frame.entities.iter().all(|e| e.position.len() == 2));
+    assert!(frame.metrics.is_some());
+}
@@ -0,0 +1,75 @@
+//! Checks the distance attenuation of direct signals and that only entities
+//! within hearing range receive them.
+//!
+//! ```bash
+//! cargo test --test signaling
+//! ```
+
+use synthetic_consciousness::signaling::{attenuation, receive, Signal};
+use synthetic_consciousness::{Entity, Real, Simulation};
+
+fn signal(payload: Vec<Real>) -> Signal {
+    Signal { sender: 0, origin: vec![0.0, 0.0], payload, emitted_at: 0 }
+}
+
+#[test]
+fn attenuation_falls_linearly_to_zero_at_the_range() {
+    let range = 3.0;
+    for i in 0..=30 {
+        let distance = i as Real * 0.1;
+        let expected = 1.0 - distance / range;
+        assert!((attenuation(distance, range) - expected).abs() < 1e-5, "gain at {}", distance);
+    }
+    // Monotone, and nothing at or past the range or with no range at all
+    assert!(attenuation(0.5, range) > attenuation(1.5, range));
+    assert_eq!(attenuation(range, range), 0.0);
+    assert_eq!(attenuation(range + 1e-3, range), 0.0);
+    assert_eq!(attenuation(100.0, range), 0.0);
+    assert_eq!(attenuation(0.0, 0.0), 0.0);
+}
+
+#[test]
+fn received_payloads_are_scaled_by_the_gain() {
+    let message = signal(vec![2.0, -4.0, 1.0]);
+    let heard = receive(&message, 1.0, 4.0).unwrap();
+    assert_eq!(heard, vec![1.5, -3.0, 0.75]);
+    assert_eq!(receive(&message, 0.0, 4.0).unwrap(), message.payload);
+    assert!(receive(&message, 4.0, 4.0).is_none());
+    assert!(receive(&message, 9.0, 4.0).is_none());
+}
+
+#[test]
+fn entities_out_of_range_receive_nothing() {
+    // Every entity with a belief cluster emits; none of them moves
+    let builder = Simulation::builder().entities(0).steps(20).configure(|config| {
+        config.signaling.enabled = true;
+        config.signaling.emit_probability = 1.0;
+        config.signaling.essence_extremity = 0.0;
+        config.signaling.hearing_range = 3.0;
+        config.dynamics.min_speed = 0.0;
+        config.drives.responsiveness = 0.0;
+    });
+    let at = |position: [Real; 2]| {
+        Entity::builder(2)
+            .position(position.to_vec())
+            .velocity(vec![0.0, 0.0])
+            .drives(0.0, 0.0)
+            .build(builder.config())
+            .unwrap()
+    };
+    // A close pair and a loner far beyond hearing range of both
+    let (a, b, loner) = (at([2.0, 2.0]), at([3.0, 2.0]), at([8.0, 8.0]));
+    let mut sim = builder.with_entity(a).with_entity(b).with_entity(loner).build().unwrap();
+    sim.run();
+
+    // Deliveries trail emissions by a step; only the pair hears each other
+    let steps = &sim.results.steps;
+    assert!(steps.iter().any(|s| s.signals_emitted == 3), "not every entity emitted");
+    for (previous, step) in steps.iter().zip(&steps[1..]) {
+        let pair = previous.signals_emitted.min(2);
+        assert!(step.signals_received <= pair, "step {} delivered out of range", step.step_number);
+        if previous.signals_emitted == 3 {
+            assert_eq!(step.signals_received, 2, "step {}", step.step_number);
+        }
+    }
+}
@@ -0,0 +1,104 @@
+//! Checks the spatial grid against a brute-force pair search, and that
+//! recording attractions through it keeps every pair within the cutoff.
+//!
+//! ```bash
+//! cargo test --test spatial_grid
+//! ```
+
+use rand::{rngs::StdRng, Rng, SeedableRng};
+use synthetic_consciousness::attraction::DEFAULT_CUTOFF_SIGMAS;
+use synthetic_consciousness::geometry::SpatialGrid;
+use synthetic_consciousness::{Real, Simulation, SimulationConfig};
+
+fn brute_force_pairs(positions: &[&[Real]], radius: Real) -> Vec<(usize, usize)> {
+    let mut pairs = Vec::new();
+    for i in 0..positions.len() {
+        for j in i + 1..positions.len() {
+            let distance_sq: Real = positions[i].iter().zip(positions[j]).map(|(a, b)| (a - b).powi(2)).sum();
+            if distance_sq <= radius * radius {
+                pairs.push((i, j));
+            }
+        }
+    }
+    pairs
+}
+
+#[test]
+fn grid_pairs_match_brute_force() {
+    let mut rng = StdRng::seed_from_u64(3);
+    for dimension in [2, 3] {
+        // Includes negative coordinates, which fall into negative cells
+        let points: Vec<Vec<Real>> = (0..300)
+            .map(|_| (0..dimension).map(|_| rng.gen_range(-20.0..20.0)).collect())
+            .collect();
+        let positions: Vec<&[Real]> = points.iter().map(Vec::as_slice).collect();
+        for (cell_size, radius) in [(2.0, 2.0), (5.0, 3.0), (1.0, 2.5)] {
+            let mut grid = SpatialGrid::new(cell_size);
+            grid.rebuild(positions.iter().copied());
+            assert_eq!(grid.len(), positions.len());
+            assert_eq!(
+                grid.pairs_within(&positions, radius),
+                brute_force_pairs(&positions, radius),
+                "{}D, cell {}, radius {}",
+                dimension,
+                cell_size,
+                radius
+            );
+        }
+    }
+
+    // Rebuilding replaces the previous points
+    let mut grid = SpatialGrid::new(1.0);
+    grid.rebuild([&[0.0, 0.0][..], &[0.5, 0.5]]);
+    grid.rebuild([&[0.0, 0.0][..]]);
+    assert_eq!(grid.len(), 1);
+    assert!(grid.pairs_within(&[&[0.0, 0.0]], 1.0).is_empty());
+}
+
+fn config(cutoff: Option<Real>) -> SimulationConfig {
+    let mut config = SimulationConfig::default_2d();
+    config.simulation.num_entities = 40;
+    config.geometry.bounds = vec![40.0, 40.0];
+    config.attraction.cutoff = cutoff;
+    config
+}
+
+#[test]
+fn recorded_attractions_keep_every_pair_within_the_cutoff() {
+    let cutoff = DEFAULT_CUTOFF_SIGMAS * config(None).attraction.sigma;
+    let mut everything = Simulation::new(config(Some(1000.0))).unwrap();
+    let mut within = Simulation::new(config(None)).unwrap();
+    everything.step();
+    within.step();
+
+    let all = &everything.results.steps[0];
+    let near = &within.results.steps[0];
+    assert_eq!(all.entity_positions, near.entity_positions);
+    let position = |id: u32| &all.entity_positions.iter().find(|(e, _)| *e == id).unwrap().1;
+    let distance = |a: u32, b: u32| -> Real {
+        position(a).iter().zip(position(b)).map(|(x, y)| (x - y).powi(2)).sum::<Real>().sqrt()
+    };
+
+    let expected: Vec<_> = all.attractions.iter().filter(|(a, b, _)| distance(*a, *b) <= cutoff).cloned().collect();
+    assert!(!expected.is_empty());
+    assert!(expected.len() < all.attractions.len(), "the cutoff should drop distant pairs");
+    assert_eq!(near.attractions, expected);
+}
+
+#[test]
+fn cutoff_is_configurable_and_validated() {
+    let mut config = config(None);
+    assert_eq!(config.attraction.effective_cutoff(), DEFAULT_CUTOFF_SIGMAS * config.attraction.sigma);
+    config.apply_cli_args(&["attraction.cutoff=2.5".to_string()]).unwrap();
+    assert_eq!(config.attraction.cutoff, Some(2.5));
+    assert_eq!(config.attraction.effective_cutoff(), 2.5);
+
+    let toml = toml::to_string(&config).unwrap();
+    let parsed: SimulationConfig = toml::from_str(&toml).unwrap();
+    assert_eq!(parsed.attraction.cutoff, Some(2.5));
+
+    config.attraction.cutoff = Some(0.0);
+    assert!(config.validate().unwrap_err().contains("cutoff"));
+    let problems = config.problems();
+    assert!(problems.iter().any(|problem| problem.path == "attraction.cutoff"));
+}
@@ -0,0 +1,161 @@
+//! Mixes sub-populations with their own essence and state parameters in one
+//! space and checks the overrides, the per-species metrics and report
+//! breakdown, validation, and that light curious scouts explore more of the
+//! world than heavy anchors.
+//!
+//! ```bash
+//! cargo test --test species
+//! ```
+
+use synthetic_consciousness::schedule::{ParameterSchedule, ScheduleKind};
+use synthetic_consciousness::species::SpeciesId;
+use std::collections::{HashMap, HashSet};
+use synthetic_consciousness::{Real, Simulation, SimulationConfig, SimulationConfigPatch};
+
+const POPULATIONS: &str = r#"
+[species.optimist]
+count = 10
+essence = { baseline = 7.5 }
+
+[species.pessimist]
+count = 10
+essence = { baseline = 2.5, experience_scale = 1.5 }
+state = { decay_alpha = 0.8 }
+"#;
+
+const ROLES: &str = r#"
+[species.anchor]
+count = 10
+mass = 4.0
+curiosity = 0.1
+attraction_weight = 2.0
+
+[species.scout]
+count = 10
+mass = 0.5
+curiosity = 0.9
+attraction_weight = 0.5
+"#;
+
+/// Default 2D configuration with the species table `species`.
+fn with_species(species: &str) -> SimulationConfig {
+    let base = SimulationConfig::default_2d().canonical_toml().unwrap();
+    SimulationConfig::parse(&format!("{}\n{}", base.replace("[species]\n", ""), species), None).unwrap()
+}
+
+fn config() -> SimulationConfig {
+    let mut config = with_species(POPULATIONS);
+    config.simulation.num_entities = 20;
+    config.simulation.num_steps = 40;
+    config.simulation.per_species_metrics = true;
+    config
+}
+
+#[test]
+fn each_population_starts_from_its_own_parameters() {
+    let sim = Simulation::new(config()).unwrap();
+    let defaults = SimulationConfig::default_2d();
+    let (optimists, pessimists): (Vec<_>, Vec<_>) = sim.entities.iter().partition(|e| e.species == SpeciesId(1));
+    assert_eq!((optimists.len(), pessimists.len()), (10, 10));
+
+    for entity in &optimists {
+        assert_eq!((entity.essence.value, entity.essence.config.baseline), (7.5, 7.5));
+        assert_eq!(entity.essence.config.experience_scale, defaults.essence.experience_scale);
+        assert_eq!(entity.state.config.decay_alpha, defaults.state.decay_alpha);
+    }
+    for entity in &pessimists {
+        assert_eq!(entity.species, SpeciesId(2));
+        assert_eq!((entity.essence.value, entity.essence.config.baseline), (2.5, 2.5));
+        assert_eq!(entity.essence.config.experience_scale, 1.5);
+        assert_eq!(entity.state.config.decay_alpha, 0.8);
+    }
+}
+
+#[test]
+fn metrics_and_reports_break_down_by_population() {
+    let mut sim = Simulation::new(config()).unwrap();
+    sim.run();
+    sim.finalize_results();
+
+    let last = sim.results.steps.last().unwrap();
+    let names: Vec<&str> = last.species_metrics.iter().map(|(name, _)| name.as_str()).collect();
+    assert_eq!(names, ["optimist", "pessimist"]);
+    let essence = |i: usize| last.species_metrics[i].1.average_essence;
+    assert!(essence(0) > essence(1));
+
+    let path = std::env::temp_dir().join(format!("sc_species_{}.md", std::process::id()));
+    sim.results.generate_markdown_report(path.to_str().unwrap()).unwrap();
+    let report = std::fs::read_to_string(&path).unwrap();
+    std::fs::remove_file(path).unwrap();
+    assert!(report.contains("## Per-Species Metrics"));
+    assert!(report.contains("| optimist | "));
+    assert!(report.contains("| pessimist | "));
+}
+
+#[test]
+fn schedules_keep_the_other_overrides() {
+    let mut config = config();
+    let schedule = ParameterSchedule { kind: ScheduleKind::Step, start: 1.0, end: 2.0, from_step: 0, to_step: 5 };
+    config.schedules.insert("essence.experience_scale".to_string(), schedule);
+    let mut sim = Simulation::new(config).unwrap();
+    sim.run();
+    for entity in sim.entities.iter() {
+        assert_eq!(entity.essence.config.experience_scale, 2.0);
+        let baseline = if entity.species == SpeciesId(1) { 7.5 } else { 2.5 };
+        assert_eq!(entity.essence.config.baseline, baseline);
+    }
+}
+
+#[test]
+fn patches_leave_species_overrides_in_place() {
+    let mut sim = Simulation::new(config()).unwrap();
+    sim.step();
+    let patch = SimulationConfigPatch { experience_scale: Some(3.0), ..Default::default() };
+    sim.apply_config_patch(patch).unwrap();
+    sim.step();
+
+    assert_eq!(sim.config.essence.experience_scale, 3.0);
+    for entity in sim.entities.iter() {
+        // Only the pessimists override the scale
+        let scale = if entity.species == SpeciesId(1) { 3.0 } else { 1.5 };
+        assert_eq!(entity.essence.config.experience_scale, scale);
+    }
+}
+
+#[test]
+fn scouts_cover_more_exploration_cells_than_anchors() {
+    const CELL: Real = 0.5;
+    let mut config = with_species(ROLES);
+    config.simulation.num_entities = 20;
+    config.simulation.num_steps = 1000;
+    config.dynamics.dt = 0.05;
+    let mut sim = Simulation::new(config).unwrap();
+    sim.run();
+
+    // Distinct grid cells each entity passed through
+    let mut visited: HashMap<u32, HashSet<(i64, i64)>> = HashMap::new();
+    for step in &sim.results.steps {
+        for (id, position) in &step.entity_positions {
+            let cell = ((position[0] / CELL).floor() as i64, (position[1] / CELL).floor() as i64);
+            visited.entry(*id).or_default().insert(cell);
+        }
+    }
+    let mean_cells = |species: SpeciesId| {
+        let members: Vec<_> = sim.entities.iter().filter(|e| e.species == species).collect();
+        members.iter().map(|e| visited[&e.id.0].len()).sum::<usize>() as Real / members.len() as Real
+    };
+    let (anchors, scouts) = (mean_cells(SpeciesId(1)), mean_cells(SpeciesId(2)));
+    assert!(scouts > anchors, "scouts visited {} cells on average, anchors {}", scouts, anchors);
+}
+
+#[test]
+fn overrides_out_of_range_are_rejected() {
+    let mut bright = config();
+    bright.species.get_mut("optimist").unwrap().essence.baseline = Some(12.0);
+    assert_eq!(bright.validate().unwrap_err(), "Species 'optimist' has invalid overrides");
+    assert_eq!(bright.problems()[0].path, "species.optimist.essence.baseline");
+
+    let mut sticky = config();
+    sticky.species.get_mut("pessimist").unwrap().state.decay_alpha = Some(1.5);
+    assert_eq!(sticky.problems()[0].path, "species.pessimist.state.decay_alpha");
+}
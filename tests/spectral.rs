@@ -0,0 +1,81 @@
+//! Algebraic connectivity (Fiedler value) of weighted graphs with known
+//! spectra, and of the attention graph of small populations.
+//!
+//! ```bash
+//! cargo test --test spectral
+//! ```
+
+use synthetic_consciousness::analysis::{algebraic_connectivity, SPECTRAL_ITERATIONS};
+use synthetic_consciousness::{Entity, Real, Simulation};
+
+/// Symmetric weight matrix of `n` nodes with unit-weight `edges`.
+fn graph(n: usize, edges: &[(usize, usize)]) -> Vec<Vec<Real>> {
+    let mut weights = vec![vec![0.0; n]; n];
+    for &(a, b) in edges {
+        weights[a][b] = 1.0;
+        weights[b][a] = 1.0;
+    }
+    weights
+}
+
+fn fiedler(weights: &[Vec<Real>]) -> Real {
+    algebraic_connectivity(weights, SPECTRAL_ITERATIONS)
+}
+
+#[test]
+fn known_spectra() {
+    // Path on three nodes: Laplacian eigenvalues 0, 1, 3
+    assert!((fiedler(&graph(3, &[(0, 1), (1, 2)])) - 1.0).abs() < 1e-3);
+    // Cycle on four nodes: 0, 2, 2, 4
+    assert!((fiedler(&graph(4, &[(0, 1), (1, 2), (2, 3), (3, 0)])) - 2.0).abs() < 1e-3);
+    // Complete graph on n nodes: 0 and n (n − 1 times)
+    for n in [2, 4, 6] {
+        let edges: Vec<_> = (0..n).flat_map(|a| (a + 1..n).map(move |b| (a, b))).collect();
+        assert!((fiedler(&graph(n, &edges)) - n as Real).abs() < 1e-3, "K{}", n);
+    }
+    // Edge weights scale the spectrum
+    let mut heavy = graph(3, &[(0, 1), (1, 2)]);
+    heavy.iter_mut().flatten().for_each(|w| *w *= 2.5);
+    assert!((fiedler(&heavy) - 2.5).abs() < 1e-3);
+}
+
+#[test]
+fn disconnected_graphs_have_no_connectivity() {
+    assert!(fiedler(&graph(4, &[(0, 1), (2, 3)])) < 1e-4);
+    assert!(fiedler(&graph(3, &[(0, 1)])) < 1e-4);
+    assert_eq!(fiedler(&graph(3, &[])), 0.0);
+    assert_eq!(fiedler(&graph(1, &[])), 0.0);
+    assert_eq!(fiedler(&[]), 0.0);
+}
+
+/// Entities at rest at `positions`, each emitting `weight`.
+fn population(positions: &[[Real; 2]], weight: Real) -> Simulation {
+    let builder = Simulation::builder().entities(0);
+    let entities: Vec<Entity> = positions
+        .iter()
+        .map(|p| {
+            let mut entity = Entity::builder(2).position(p.to_vec()).build(builder.config()).unwrap();
+            entity.attraction_weight = weight;
+            entity
+        })
+        .collect();
+    entities.into_iter().fold(builder, |b, e| b.with_entity(e)).build().unwrap()
+}
+
+#[test]
+fn attention_graphs_of_populations() {
+    // Two entities 3 apart: one link of strength 1 / (1 + 3), λ₂ = 2 · 0.25
+    let pair = population(&[[2.0, 2.0], [5.0, 2.0]], 1.0);
+    assert!((pair.compute_integration_information_spectral(SPECTRAL_ITERATIONS) - 0.5).abs() < 1e-3);
+
+    // Every pair attends to every other; without emitted attraction the
+    // graph falls apart
+    let square = [[2.0, 2.0], [4.0, 2.0], [2.0, 4.0], [4.0, 4.0]];
+    let connected = population(&square, 1.0);
+    let spectral = connected.compute_integration_information_spectral(SPECTRAL_ITERATIONS);
+    assert_eq!(spectral, fiedler(&connected.attention_matrix()));
+    assert!(spectral > 0.1, "λ₂ {}", spectral);
+    let silent = population(&square, 0.0);
+    assert_eq!(silent.compute_integration_information_spectral(SPECTRAL_ITERATIONS), 0.0);
+    assert_eq!(population(&[[2.0, 2.0]], 1.0).compute_integration_information_spectral(SPECTRAL_ITERATIONS), 0.0);
+}
@@ -0,0 +1,85 @@
+//! Checks the state update s = α·s + β·g(F) + γ·m: the attention gradient
+//! and the belief clusters' memory input reach the state vector, and the
+//! β and γ weights of `StateConfig` change where the state ends up.
+//!
+//! ```bash
+//! cargo test --test state_update
+//! ```
+
+use synthetic_consciousness::memory::MemoryGraph;
+use synthetic_consciousness::{Entity, Real, Simulation, SimulationConfig};
+
+/// State vector of every entity after a run with the given weights.
+///
+/// Stimuli are strong enough to carry valence, so belief clusters have
+/// affective signals and the memory input is not zero.
+fn final_states(beta: Real, gamma: Real) -> Vec<Vec<Real>> {
+    let mut config = SimulationConfig::default_2d();
+    config.simulation.num_entities = 12;
+    config.simulation.num_steps = 30;
+    config.state.beta_attention = beta;
+    config.state.gamma_memory = gamma;
+    let mut sim = Simulation::new(config).unwrap();
+    for entity in sim.entities.iter_mut() {
+        entity.stimulus_amplitude = 2.0;
+    }
+    sim.run();
+    sim.entities.iter().map(|e| e.state.memory.clone()).collect()
+}
+
+#[test]
+fn without_weights_the_state_stays_at_rest() {
+    for state in final_states(0.0, 0.0) {
+        assert!(state.iter().all(|&s| s == 0.0));
+    }
+}
+
+#[test]
+fn attention_moves_the_spatial_components() {
+    let states = final_states(0.5, 0.0);
+    assert!(states.iter().any(|state| state[..2].iter().any(|&s| s != 0.0)));
+    for state in &states {
+        assert!(state[2..].iter().all(|&s| s == 0.0));
+    }
+}
+
+#[test]
+fn memory_input_reaches_every_cluster_slot() {
+    let config = SimulationConfig::default_2d();
+    let bound = 0.3 * 5.0 / (1.0 - config.state.decay_alpha);
+    let states = final_states(0.0, 0.3);
+    assert!(states.iter().any(|state| state[2..].iter().any(|&s| s != 0.0)));
+    for state in &states {
+        assert!(state.iter().all(|s| s.abs() <= bound), "state outside ±{}", bound);
+    }
+}
+
+#[test]
+fn sweeping_the_weights_changes_the_state() {
+    let base = final_states(0.5, 0.3);
+    assert_ne!(final_states(1.0, 0.3), base);
+    assert_ne!(final_states(0.5, 0.6), base);
+}
+
+#[test]
+fn memory_input_weights_signals_by_activation() {
+    let config = SimulationConfig::default_2d();
+    let pleasant = vec![0.9, 0.2];
+    let unpleasant = vec![-0.9, 3.0];
+    let entity = Entity::builder(2)
+        .with_memory_events(vec![pleasant.clone(), unpleasant, pleasant])
+        .build(&config)
+        .unwrap();
+    let mut graph = entity.memory_graph;
+    graph.update_affective_signals();
+    assert_eq!(graph.clusters.len(), 2);
+
+    let mut input = vec![1.0; 4];
+    graph.memory_input_into(&mut input);
+    assert!(input[0] > 0.0 && input[1] < 0.0);
+    assert!(input[0] > -input[1], "the larger cluster weighs more");
+    assert_eq!(&input[2..], [0.0, 0.0]);
+
+    MemoryGraph::new().memory_input_into(&mut input);
+    assert_eq!(input, vec![0.0; 4]);
+}
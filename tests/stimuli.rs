@@ -0,0 +1,109 @@
+//! Delivers external stimuli to single entities, immediately and at
+//! scheduled steps, and checks that they are sensed, recorded with their
+//! step and reported.
+//!
+//! ```bash
+//! cargo test --test stimuli
+//! ```
+
+use synthetic_consciousness::entities::EntityId;
+use synthetic_consciousness::{Real, Simulation, SimulationConfig};
+
+/// A strongly positive event.
+const POSITIVE: [Real; 2] = [0.9, 0.4];
+
+fn simulation(record_every: u32) -> Simulation {
+    let mut config = SimulationConfig::default_2d();
+    config.simulation.num_entities = 6;
+    config.simulation.num_steps = 60;
+    config.recording.record_every = record_every;
+    Simulation::new(config).unwrap()
+}
+
+fn run_to(sim: &mut Simulation, step: u64) {
+    while sim.timestamp < step {
+        sim.step();
+    }
+}
+
+#[test]
+fn scheduled_stimuli_are_delivered_at_their_step() {
+    let mut control = simulation(1);
+    let mut sim = simulation(1);
+    let target = sim.sorted_entity_ids()[0];
+    sim.schedule_stimulus(5, target, POSITIVE.to_vec()).unwrap();
+
+    run_to(&mut control, 6);
+    run_to(&mut sim, 6);
+    // The replaced noise is still drawn
+    assert_eq!(sim.rng_state(), control.rng_state());
+
+    for step in &sim.results.steps {
+        let expected = if step.step_number == 5 { vec![(target.0, POSITIVE.to_vec())] } else { Vec::new() };
+        assert_eq!(step.injected_stimuli, expected);
+    }
+    let entity = sim.entities.get_entity(target).unwrap();
+    let unperturbed = control.entities.get_entity(target).unwrap();
+    assert_ne!(entity.essence.value, unperturbed.essence.value);
+}
+
+#[test]
+fn injected_steps_are_recorded_between_recording_intervals() {
+    let mut sim = simulation(10);
+    let target = sim.sorted_entity_ids()[1];
+    sim.schedule_stimulus(13, target, POSITIVE.to_vec()).unwrap();
+    run_to(&mut sim, 17);
+    sim.inject_stimulus(target, POSITIVE.to_vec()).unwrap();
+    run_to(&mut sim, 21);
+
+    let recorded: Vec<u64> = sim.results.steps.iter().map(|s| s.step_number).collect();
+    assert_eq!(recorded, [0, 10, 13, 17, 20]);
+    assert_eq!(sim.results.steps[3].injected_stimuli, [(target.0, POSITIVE.to_vec())]);
+}
+
+#[test]
+fn invalid_stimuli_are_rejected() {
+    let mut sim = simulation(1);
+    run_to(&mut sim, 3);
+    let id = sim.sorted_entity_ids()[0];
+    assert!(sim.schedule_stimulus(2, id, POSITIVE.to_vec()).is_err());
+    assert!(sim.schedule_stimulus(3, id, vec![1.0]).is_err());
+    assert!(sim.inject_stimulus(EntityId(999), POSITIVE.to_vec()).is_err());
+
+    // Entities removed before delivery receive nothing
+    sim.schedule_stimulus(4, id, POSITIVE.to_vec()).unwrap();
+    assert!(sim.remove_entity(id));
+    sim.step();
+    assert!(sim.results.steps.last().unwrap().injected_stimuli.is_empty());
+}
+
+#[test]
+fn reports_list_the_injected_stimuli() {
+    let mut sim = simulation(1);
+    let target = sim.sorted_entity_ids()[2];
+    sim.schedule_stimulus(4, target, POSITIVE.to_vec()).unwrap();
+    sim.run();
+    sim.finalize_results();
+
+    let path = std::env::temp_dir().join(format!("sc_stimuli_{}.md", std::process::id()));
+    sim.results.generate_markdown_report(path.to_str().unwrap()).unwrap();
+    let report = std::fs::read_to_string(&path).unwrap();
+    std::fs::remove_file(path).unwrap();
+    assert!(report.contains("## Injected Stimuli"));
+    assert!(report.contains(&format!("| 4 | {} | [0.900, 0.400] |", target.0)));
+}
+
+#[test]
+fn checkpoints_keep_the_schedule() {
+    let mut sim = simulation(1);
+    let target = sim.sorted_entity_ids()[0];
+    sim.schedule_stimulus(8, target, POSITIVE.to_vec()).unwrap();
+    run_to(&mut sim, 4);
+    let path = std::env::temp_dir().join(format!("sc_stimuli_{}.ckpt.json", std::process::id()));
+    sim.save_checkpoint(path.to_str().unwrap()).unwrap();
+    let mut resumed = Simulation::load_checkpoint(path.to_str().unwrap()).unwrap();
+    std::fs::remove_file(path).unwrap();
+
+    run_to(&mut resumed, 9);
+    assert_eq!(resumed.results.steps[8].injected_stimuli, [(target.0, POSITIVE.to_vec())]);
+}
@@ -0,0 +1,174 @@
+//! Parses sweep parameters, sets configuration fields by path and runs a
+//! small grid.
+//!
+//! ```bash
+//! cargo test --test sweep
+//! ```
+
+use std::path::PathBuf;
+use std::sync::atomic::Ordering;
+use synthetic_consciousness::attraction::KernelType;
+use synthetic_consciousness::sweep::{expand_grid, set_config_value, CellStatus, SweepParam, SweepRunner, SUMMARY_CSV};
+use synthetic_consciousness::metrics::Metrics;
+use synthetic_consciousness::{Real, SimulationConfig};
+
+fn small_config() -> SimulationConfig {
+    let mut config = SimulationConfig::default_2d();
+    config.simulation.num_entities = 4;
+    config.simulation.num_steps = 20;
+    config
+}
+
+fn temp_dir(name: &str) -> PathBuf {
+    std::env::temp_dir().join(format!("sc_sweep_{}_{}", std::process::id(), name))
+}
+
+#[test]
+fn param_specs_split_path_and_values() {
+    let param: SweepParam = "attraction.sigma=0.5, 1.0,2.0".parse().unwrap();
+    assert_eq!(param.path, "attraction.sigma");
+    assert_eq!(param.values, vec!["0.5", "1.0", "2.0"]);
+
+    assert!("attraction.sigma".parse::<SweepParam>().unwrap_err().contains("path=value1"));
+    assert!("attraction..sigma=1".parse::<SweepParam>().unwrap_err().contains("not a valid parameter path"));
+    assert!("attraction.sigma=1,,2".parse::<SweepParam>().unwrap_err().contains("empty value"));
+}
+
+#[test]
+fn nested_fields_and_array_elements_are_set_by_path() {
+    let mut config = small_config();
+    set_config_value(&mut config, "dynamics.min_speed", "0.05").unwrap();
+    set_config_value(&mut config, "geometry.bounds.1", "25").unwrap();
+    set_config_value(&mut config, "metadata.name", "cell").unwrap();
+    assert!((config.dynamics.min_speed - 0.05).abs() < 1e-6);
+    assert_eq!(config.geometry.bounds, vec![10.0, 25.0]);
+    assert_eq!(config.metadata.name, "cell");
+
+    // Unset optional fields are accepted too
+    set_config_value(&mut config, "state.dedup_threshold", "0.9").unwrap();
+    assert!((config.state.dedup_threshold.unwrap() - 0.9).abs() < 1e-6);
+}
+
+#[test]
+fn values_are_coerced_to_the_field_type() {
+    let mut config = small_config();
+    set_config_value(&mut config, "attraction.sigma", "2").unwrap();
+    assert_eq!(config.attraction.sigma, 2.0);
+    set_config_value(&mut config, "simulation.num_entities", "12").unwrap();
+    assert_eq!(config.simulation.num_entities, 12);
+    set_config_value(&mut config, "geometry.periodic", "false").unwrap();
+    assert!(!config.geometry.periodic);
+    set_config_value(&mut config, "attraction.kernel", "InverseDistance").unwrap();
+    assert!(matches!(config.attraction.kernel, KernelType::InverseDistance));
+
+    let error = set_config_value(&mut config, "simulation.num_entities", "1.5").unwrap_err();
+    assert!(error.contains("expects an integer"), "{}", error);
+    let error = set_config_value(&mut config, "geometry.periodic", "yes").unwrap_err();
+    assert!(error.contains("true or false"), "{}", error);
+    let error = set_config_value(&mut config, "attraction.kernel", "Cubic").unwrap_err();
+    assert!(error.contains("attraction.kernel=Cubic"), "{}", error);
+    // Failed updates leave the configuration alone
+    assert_eq!(config.simulation.num_entities, 12);
+}
+
+#[test]
+fn unknown_paths_are_rejected() {
+    let mut config = small_config();
+    for path in ["attraction.sigmaa", "nosuch.field", "geometry.bounds.5", "attraction.sigma.x"] {
+        assert!(set_config_value(&mut config, path, "1").is_err(), "{}", path);
+    }
+    let error = set_config_value(&mut config, "geometry.bounds", "1").unwrap_err();
+    assert!(error.contains("not a single value"), "{}", error);
+}
+
+#[test]
+fn grid_is_the_cartesian_product_with_the_last_parameter_fastest() {
+    let params = vec![
+        "attraction.sigma=0.5,1.0,2.0".parse().unwrap(),
+        "dynamics.min_speed=0.01,0.05".parse().unwrap(),
+    ];
+    let cells = expand_grid(&small_config(), &params).unwrap();
+    assert_eq!(cells.len(), 6);
+    assert_eq!(cells[1].label(), "attraction.sigma=0.5, dynamics.min_speed=0.05");
+    assert_eq!(cells[5].index, 5);
+    assert_eq!(cells[5].config.attraction.sigma, 2.0);
+
+    let invalid = vec!["attraction.theta=0.5,-1".parse().unwrap()];
+    let error = expand_grid(&small_config(), &invalid).unwrap_err();
+    assert!(error.starts_with("cell 1 (attraction.theta=-1)"), "{}", error);
+}
+
+#[test]
+fn runner_writes_cell_summaries_and_the_csv() {
+    let out = temp_dir("run");
+    let params = vec!["attraction.sigma=0.5,2.0".parse().unwrap()];
+    let cells = expand_grid(&small_config(), &params).unwrap();
+    let report = SweepRunner::new(&out, 2).run(cells, |_, _, _| {}).unwrap();
+
+    assert!(!report.interrupted);
+    assert_eq!(report.count("done"), 2);
+    for outcome in &report.outcomes {
+        let CellStatus::Completed { summary_path, steps, .. } = &outcome.status else {
+            panic!("cell {} did not complete", outcome.index);
+        };
+        assert_eq!(*steps, 20);
+        let summary: serde_json::Value = serde_json::from_str(&std::fs::read_to_string(summary_path).unwrap()).unwrap();
+        assert_eq!(summary["cell"], outcome.index);
+        assert_eq!(summary["parameters"]["attraction.sigma"], outcome.assignments[0].1);
+    }
+
+    let csv = std::fs::read_to_string(out.join(SUMMARY_CSV)).unwrap();
+    let lines: Vec<&str> = csv.lines().collect();
+    assert!(lines[0].starts_with("cell,attraction.sigma,status,steps,consciousness_score"));
+    assert!(lines[2].starts_with("1,2.0,done,20,"), "{}", lines[2]);
+    assert_eq!(report.best_table(1).lines().count(), 2);
+    std::fs::remove_dir_all(out).unwrap();
+}
+
+#[test]
+fn csv_rows_carry_the_final_metrics_of_each_combination() {
+    let out = temp_dir("product");
+    let params = vec![
+        "attraction.sigma=0.5,2.0".parse().unwrap(),
+        "state.decay_alpha=0.9,0.99".parse().unwrap(),
+    ];
+    let cells = expand_grid(&small_config(), &params).unwrap();
+    assert_eq!(cells.len(), 4);
+    let report = SweepRunner::new(&out, 2).run(cells, |_, _, _| {}).unwrap();
+
+    let csv = std::fs::read_to_string(&report.csv_path).unwrap();
+    let rows: Vec<Vec<&str>> = csv.lines().map(|line| line.split(',').collect()).collect();
+    let header = &rows[0];
+    assert_eq!(&header[1..3], ["attraction.sigma", "state.decay_alpha"]);
+    let column = |name: &str| header.iter().position(|field| *field == name).unwrap();
+    assert_eq!(column("final_attention_entropy"), column("consciousness_achieved") + 1);
+    assert_eq!(rows.len(), 5);
+    for (row, outcome) in rows[1..].iter().zip(&report.outcomes) {
+        assert_eq!(row.len(), header.len());
+        let CellStatus::Completed { final_metrics, .. } = &outcome.status else {
+            panic!("cell {} did not complete", outcome.index);
+        };
+        for (i, name) in Metrics::FIELD_NAMES.iter().enumerate() {
+            let value: Real = row[column(&format!("final_{}", name))].parse().unwrap();
+            assert!((value - final_metrics[i]).abs() < 1e-4);
+        }
+    }
+    std::fs::remove_dir_all(out).unwrap();
+}
+
+#[test]
+fn stopped_sweep_still_writes_the_partial_summary() {
+    let out = temp_dir("stopped");
+    let params = vec!["attraction.sigma=0.5,1.0,2.0".parse().unwrap()];
+    let cells = expand_grid(&small_config(), &params).unwrap();
+    let runner = SweepRunner::new(&out, 1);
+    runner.stop_flag().store(true, Ordering::SeqCst);
+    let report = runner.run(cells, |_, _, _| {}).unwrap();
+
+    assert!(report.interrupted);
+    assert_eq!(report.count("skipped"), 3);
+    assert!(report.best(5).is_empty());
+    let csv = std::fs::read_to_string(&report.csv_path).unwrap();
+    assert_eq!(csv.lines().filter(|line| line.contains(",skipped,")).count(), 3);
+    std::fs::remove_dir_all(out).unwrap();
+}
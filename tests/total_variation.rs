@@ -0,0 +1,86 @@
+//! Total variation distance between recorded state distributions: zero for
+//! identical steps, one for disjoint ones, symmetric, and zero for empty
+//! or unrecorded steps.
+//!
+//! ```bash
+//! cargo test --test total_variation
+//! ```
+
+use rand::{rngs::StdRng, Rng, SeedableRng};
+use synthetic_consciousness::analysis::total_variation_distance;
+use synthetic_consciousness::{Real, Simulation, SimulationConfig, SimulationStep};
+
+/// A simulation that recorded a few steps, for step templates and bounds.
+fn recorded() -> Simulation {
+    let mut config = SimulationConfig::default_2d();
+    config.simulation.num_entities = 4;
+    config.simulation.num_steps = 3;
+    let mut sim = Simulation::new(config).unwrap();
+    sim.run();
+    sim
+}
+
+/// Step `number` with entities at rest at `positions`.
+fn step(template: &SimulationStep, number: u64, positions: &[[Real; 2]]) -> SimulationStep {
+    let mut step = template.clone();
+    step.step_number = number;
+    step.entity_positions = positions.iter().enumerate().map(|(i, p)| (i as u32 + 1, p.to_vec().into())).collect();
+    step.entity_velocities = positions.iter().enumerate().map(|(i, _)| (i as u32 + 1, vec![0.0, 0.0].into())).collect();
+    step
+}
+
+#[test]
+fn identical_and_disjoint_distributions() {
+    let mut sim = recorded();
+    let bounds = sim.config.geometry.bounds.clone();
+    let template = sim.results.steps[0].clone();
+    let (low, high) = (0.05 * bounds[0], 0.95 * bounds[0]);
+
+    let corner = step(&template, 0, &[[low, low], [low, low], [low, low]]);
+    let opposite = step(&template, 1, &[[high, high], [high, high], [high, high]]);
+    let split = step(&template, 2, &[[low, low], [high, high], [low, low], [high, high]]);
+    assert_eq!(total_variation_distance(&corner, &corner, &bounds), 0.0);
+    assert_eq!(total_variation_distance(&corner, &opposite, &bounds), 1.0);
+    assert!((total_variation_distance(&corner, &split, &bounds) - 0.5).abs() < 1e-6);
+
+    // The same through the recorded steps
+    sim.results.steps = vec![corner, opposite, split];
+    assert_eq!(sim.compute_total_variation_distance(0, 0), 0.0);
+    assert_eq!(sim.compute_total_variation_distance(0, 1), 1.0);
+    assert!((sim.compute_total_variation_distance(2, 0) - 0.5).abs() < 1e-6);
+}
+
+#[test]
+fn distance_is_symmetric_and_bounded() {
+    let sim = recorded();
+    let bounds = sim.config.geometry.bounds.clone();
+    let template = &sim.results.steps[0];
+    let mut rng = StdRng::seed_from_u64(3);
+    let mut random_step = |count: usize| {
+        let positions: Vec<[Real; 2]> =
+            (0..count).map(|_| [rng.gen_range(0.0..bounds[0]), rng.gen_range(0.0..bounds[1])]).collect();
+        step(template, 0, &positions)
+    };
+    for _ in 0..50 {
+        let (a, b) = (random_step(12), random_step(7));
+        // Equal up to the order the cell masses are summed in
+        let distance = total_variation_distance(&a, &b, &bounds);
+        assert!((distance - total_variation_distance(&b, &a, &bounds)).abs() < 1e-6);
+        assert!((0.0..=1.0 + 1e-6).contains(&distance), "distance {}", distance);
+    }
+
+    // Recorded steps of a real run, with velocities
+    let (first, last) = (&sim.results.steps[0], sim.results.steps.last().unwrap());
+    let distance = total_variation_distance(first, last, &bounds);
+    assert!((distance - total_variation_distance(last, first, &bounds)).abs() < 1e-6);
+}
+
+#[test]
+fn empty_and_unrecorded_steps_have_no_distance() {
+    let sim = recorded();
+    let bounds = &sim.config.geometry.bounds;
+    let empty = step(&sim.results.steps[0], 0, &[]);
+    assert_eq!(total_variation_distance(&empty, &empty, bounds), 0.0);
+    assert_eq!(sim.compute_total_variation_distance(0, 999), 0.0);
+    assert_eq!(sim.compute_total_variation_distance(999, 0), 0.0);
+}
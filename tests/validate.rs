@@ -0,0 +1,68 @@
+//! Checks configuration files the way the `validate` subcommand does: a
+//! valid file, a parse failure and a file with semantic problems.
+//!
+//! ```bash
+//! cargo test --test validate
+//! ```
+
+use synthetic_consciousness::config::ConfigFormat;
+use synthetic_consciousness::SimulationConfig;
+
+fn fixture(name: &str) -> String {
+    format!("{}/{}", env!("CARGO_MANIFEST_DIR"), name)
+}
+
+#[test]
+fn valid_file_has_no_problems() {
+    let (config, problems) = SimulationConfig::check_file(&fixture("test_2d.toml"), None).unwrap();
+    assert!(problems.is_empty(), "{:?}", problems);
+    let summary = config.summary();
+    assert!(summary.contains("Test 2D Visualization"), "{}", summary);
+    assert!(summary.contains("40 entities, 1000 steps"), "{}", summary);
+    assert!(summary.contains(&config.fingerprint()), "{}", summary);
+}
+
+#[test]
+fn parse_failure_is_an_error_not_a_problem_list() {
+    let error = SimulationConfig::check_file(&fixture("tests/fixtures/malformed_config.toml"), None).unwrap_err();
+    assert!(error.contains("sigma"), "{}", error);
+    let error = SimulationConfig::check_file(&fixture("tests/fixtures/no_such_config.toml"), None).unwrap_err();
+    assert!(error.contains("cannot read"), "{}", error);
+}
+
+#[test]
+fn semantic_failures_are_all_reported_with_their_paths() {
+    let (config, problems) = SimulationConfig::check_file(&fixture("tests/fixtures/invalid_config.toml"), None).unwrap();
+    let paths: Vec<&str> = problems.iter().map(|problem| problem.path.as_str()).collect();
+    assert_eq!(paths, vec!["attraction.sigma", "dynamics.damping", "entities[0].id"]);
+    assert_eq!(problems[2].to_string(), "entities[0].id: refers to unknown entity id 9 (ids are 1 to 5)");
+    assert!(config.validate().is_err());
+}
+
+#[test]
+fn problems_cover_every_validate_rule() {
+    let mut config = SimulationConfig::default_2d();
+    assert!(config.problems().is_empty());
+    config.geometry.bounds.push(10.0);
+    config.state.memory_dim = 0;
+    assert!(config.validate().is_err());
+    let paths: Vec<String> = config.problems().into_iter().map(|problem| problem.path).collect();
+    assert_eq!(paths, vec!["geometry.bounds", "state.memory_dim"]);
+}
+
+#[test]
+fn json_and_toml_are_told_apart_by_their_text() {
+    let config = SimulationConfig::default_2d();
+    let json = serde_json::to_string_pretty(&config).unwrap();
+    let toml = toml::to_string(&config).unwrap();
+    assert_eq!(ConfigFormat::sniff(&json), ConfigFormat::Json);
+    assert_eq!(ConfigFormat::sniff(&toml), ConfigFormat::Toml);
+
+    let from_json = SimulationConfig::parse(&json, None).unwrap();
+    let from_toml = SimulationConfig::parse(&toml, Some(ConfigFormat::Toml)).unwrap();
+    assert_eq!(from_json.fingerprint(), config.fingerprint());
+    assert_eq!(from_toml.fingerprint(), config.fingerprint());
+
+    let error = SimulationConfig::parse(&json, Some(ConfigFormat::Toml)).unwrap_err();
+    assert!(error.contains("TOML parse error"), "{}", error);
+}
@@ -0,0 +1,78 @@
+//! Carries the entities of a finished run into a new simulation with a
+//! different configuration and checks what they keep, what starts fresh,
+//! and that mismatched populations are rejected.
+//!
+//! ```bash
+//! cargo test --test warm_start
+//! ```
+
+use synthetic_consciousness::{Real, Simulation, SimulationConfig};
+
+fn trained() -> Simulation {
+    let mut config = SimulationConfig::default_2d();
+    config.simulation.num_entities = 8;
+    config.simulation.num_steps = 30;
+    let mut sim = Simulation::new(config).unwrap();
+    sim.run();
+    sim
+}
+
+/// Per entity: id, memory nodes, belief clusters, state vector and essence.
+type Snapshot = Vec<(u32, usize, usize, Vec<Real>, Real)>;
+
+fn snapshot(sim: &Simulation) -> Snapshot {
+    let mut entities: Snapshot = sim
+        .entities
+        .iter()
+        .map(|e| {
+            (e.id.0, e.memory_graph.nodes.len(), e.memory_graph.clusters.len(), e.state.memory.clone(), e.essence.value)
+        })
+        .collect();
+    entities.sort_by_key(|e| e.0);
+    entities
+}
+
+#[test]
+fn entities_continue_under_a_new_configuration() {
+    let training = trained();
+    let before = snapshot(&training);
+    let entities = training.take_entities();
+    assert!(entities.windows(2).all(|pair| pair[0].id < pair[1].id));
+
+    let mut config = SimulationConfig::default_2d();
+    config.simulation.num_steps = 20;
+    config.attraction.sigma = 2.0;
+    let mut sim = Simulation::from_entities(config, entities).unwrap();
+    assert_eq!(snapshot(&sim), before);
+
+    // Results, history and the step count start fresh
+    assert_eq!(sim.timestamp, 0);
+    assert!(sim.metrics_history.is_empty() && sim.results.steps.is_empty());
+    assert_eq!((sim.config.simulation.num_entities, sim.results.num_entities), (8, 8));
+    assert_eq!(sim.results.lineage.len(), 8);
+    assert!(sim.entities.iter().all(|e| e.birth_step == 0));
+
+    sim.run();
+    assert_eq!(sim.metrics_history.len(), 20);
+    let after = snapshot(&sim);
+    for (old, new) in before.iter().zip(&after) {
+        assert_eq!(old.0, new.0);
+        assert!(new.1 > old.1, "entity {} stopped remembering", new.0);
+    }
+
+    // New entities are numbered after the carried ones
+    assert_eq!(sim.spawn_entity(None).unwrap().0, 9);
+}
+
+#[test]
+fn mismatched_populations_are_rejected() {
+    let entities = trained().take_entities();
+
+    let error = Simulation::from_entities(SimulationConfig::default_3d(), entities.clone()).err().unwrap();
+    assert_eq!(error, "Entity 1 has 2 position and 2 velocity components, expected 3");
+
+    let mut twice = entities.clone();
+    twice.push(entities[0].clone());
+    let error = Simulation::from_entities(SimulationConfig::default_2d(), twice).err().unwrap();
+    assert_eq!(error, "Entity id 1 appears more than once");
+}
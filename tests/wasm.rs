@@ -0,0 +1,24 @@
+//! Runs a small simulation through the wasm-bindgen API in Node.
+//!
+//! `wasm-pack test --node -- --no-default-features --features wasm`
+
+#![cfg(all(feature = "wasm", target_arch = "wasm32"))]
+
+use synthetic_consciousness::bindings::wasm::WasmSimulation;
+use synthetic_consciousness::SimulationConfig;
+use wasm_bindgen_test::wasm_bindgen_test;
+
+#[wasm_bindgen_test]
+fn runs_one_hundred_steps() {
+    let mut config = SimulationConfig::default_2d();
+    config.simulation.num_entities = 8;
+    let mut sim = WasmSimulation::new(&serde_json::to_string(&config).unwrap()).unwrap();
+
+    sim.step_n(100);
+
+    assert_eq!(sim.positions().length() as usize, sim.entity_count() * sim.dimension());
+    let metrics: serde_json::Value = serde_json::from_str(&sim.metrics_json().unwrap()).unwrap();
+    assert_eq!(metrics["timestamp"], 100);
+    let summary: serde_json::Value = serde_json::from_str(&sim.summary_json().unwrap()).unwrap();
+    assert!(summary["consciousness_score"].as_f64().unwrap() >= 0.0);
+}
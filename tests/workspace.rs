@@ -0,0 +1,54 @@
+//! Global workspace integration: a broadcast from the most active entity
+//! travels hop by hop along a chain of strong attention links, but not
+//! across a gap to another component.
+//!
+//! ```bash
+//! cargo test --test workspace
+//! ```
+
+use synthetic_consciousness::{Entity, Real, Simulation};
+
+/// Entities at rest at `xs` along y = 5, all emitting attraction 1, so
+/// neighbors one apart attend with strength 1 / (1 + 1) = 0.5.
+fn line(xs: &[Real]) -> Simulation {
+    let builder = Simulation::builder().entities(0);
+    let entities: Vec<Entity> = xs
+        .iter()
+        .map(|&x| {
+            let mut entity = Entity::builder(2).position(vec![x, 5.0]).build(builder.config()).unwrap();
+            entity.attraction_weight = 1.0;
+            entity
+        })
+        .collect();
+    entities.into_iter().fold(builder, |b, e| b.with_entity(e)).build().unwrap()
+}
+
+/// Above the 1/3 of entities two apart, below the 0.5 of neighbors.
+const THRESHOLD: Real = 0.4;
+
+#[test]
+fn a_chain_is_reached_hop_by_hop() {
+    // Only neighbors are linked, so the far end is five hops from the source
+    let chain = line(&[1.0, 2.0, 3.0, 4.0, 5.0, 6.0]);
+    assert_eq!(chain.compute_workspace_integration(THRESHOLD), 1.0);
+    // No link is strong enough: the source alone
+    assert!((chain.compute_workspace_integration(0.6) - 1.0 / 6.0).abs() < 1e-6);
+}
+
+#[test]
+fn disconnected_components_are_not_reached() {
+    // Source (lowest id, all equally inactive) in a pair, then a chain of
+    // three four away from it
+    let split = line(&[1.0, 2.0, 6.0, 7.0, 8.0]);
+    assert!((split.compute_workspace_integration(THRESHOLD) - 2.0 / 5.0).abs() < 1e-6);
+    let split = line(&[6.0, 7.0, 8.0, 1.0, 2.0]);
+    assert!((split.compute_workspace_integration(THRESHOLD) - 3.0 / 5.0).abs() < 1e-6);
+    // A weak enough threshold bridges the gap
+    assert_eq!(split.compute_workspace_integration(0.15), 1.0);
+}
+
+#[test]
+fn an_empty_population_integrates_nothing() {
+    assert_eq!(line(&[]).compute_workspace_integration(THRESHOLD), 0.0);
+    assert_eq!(line(&[3.0]).compute_workspace_integration(THRESHOLD), 1.0);
+}
@@ -0,0 +1,69 @@
+//! Round-trips recorded trajectories through the extended-XYZ exporter.
+
+use synthetic_consciousness::{Real, Simulation, SimulationConfig, SimulationResults};
+
+/// Parsed frame: comment line and (type, x, y, z, essence) per entity.
+type Frame = (String, Vec<(String, [Real; 3], Real)>);
+
+fn parse_xyz(text: &str) -> Vec<Frame> {
+    let mut lines = text.lines();
+    let mut frames = Vec::new();
+    while let Some(count) = lines.next() {
+        let count: usize = count.trim().parse().unwrap();
+        let comment = lines.next().unwrap().to_string();
+        let atoms = (0..count)
+            .map(|_| {
+                let fields: Vec<&str> = lines.next().unwrap().split_whitespace().collect();
+                assert_eq!(fields.len(), 5);
+                let value = |i: usize| fields[i].parse::<Real>().unwrap();
+                (fields[0].to_string(), [value(1), value(2), value(3)], value(4))
+            })
+            .collect();
+        frames.push((comment, atoms));
+    }
+    frames
+}
+
+fn run_and_export(mut config: SimulationConfig, name: &str) -> (SimulationResults, Vec<Frame>) {
+    config.simulation.num_entities = 5;
+    config.simulation.num_steps = 6;
+    let mut sim = Simulation::new(config).unwrap();
+    sim.run();
+    let path = std::env::temp_dir().join(format!("synthetic_consciousness_{}_{}.xyz", name, std::process::id()));
+    sim.results.export_xyz(path.to_str().unwrap()).unwrap();
+    let frames = parse_xyz(&std::fs::read_to_string(&path).unwrap());
+    std::fs::remove_file(&path).unwrap();
+    (sim.results, frames)
+}
+
+#[test]
+fn frames_match_recorded_steps() {
+    let (results, frames) = run_and_export(SimulationConfig::default_3d(), "3d");
+    assert_eq!(frames.len(), results.steps.len());
+    for (step, (comment, atoms)) in results.steps.iter().zip(&frames) {
+        assert!(comment.contains(&format!("step={}", step.step_number)));
+        assert!(comment.starts_with("Lattice=\""));
+        let mut positions = step.entity_positions.clone();
+        positions.sort_by_key(|(id, _)| *id);
+        assert_eq!(atoms.len(), positions.len());
+        for ((kind, xyz, essence), (_, position)) in atoms.iter().zip(&positions) {
+            for axis in 0..3 {
+                assert!((xyz[axis] - position[axis]).abs() < 1e-4);
+            }
+            assert_eq!(*kind, format!("E{}", (essence.floor() as u32).min(9)));
+        }
+    }
+}
+
+#[test]
+fn two_dimensional_runs_write_zero_z() {
+    let mut config = SimulationConfig::default_2d();
+    config.geometry.periodic = true;
+    let bounds = config.geometry.bounds.clone();
+    let (results, frames) = run_and_export(config, "2d");
+    assert_eq!(frames.len(), results.steps.len());
+    let (comment, atoms) = &frames[0];
+    assert!(comment.contains(&format!("Lattice=\"{} 0 0 0 {} 0 0 0 1\"", bounds[0], bounds[1])));
+    assert!(comment.contains("pbc=\"T T F\""));
+    assert!(atoms.iter().all(|(_, xyz, _)| xyz[2] == 0.0));
+}